@@ -0,0 +1,68 @@
+//! Build script: embeds provenance metadata for `--version-json`.
+
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Honour SOURCE_DATE_EPOCH for reproducible builds. Otherwise this is the
+    // time the build script last ran, which is only on the rerun triggers
+    // below (not on every source edit).
+    let build_timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(str::to_string))
+        .map(|name| name.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=SAORSA_GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=SAORSA_BUILD_TIMESTAMP={}", build_timestamp);
+    println!(
+        "cargo:rustc-env=SAORSA_ENABLED_FEATURES={}",
+        features.join(",")
+    );
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Refresh the commit hash and timestamp when the checkout moves or the
+    // branch advances (including after `git gc` packs the refs)
+    for path in ["HEAD", "refs/heads", "packed-refs"]
+        .into_iter()
+        .filter_map(git_path)
+    {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+}
+
+/// Resolve a path inside the git directory, if we are in a checkout.
+fn git_path(name: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", name])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    std::path::Path::new(&path)
+        .canonicalize()
+        .ok()
+        .map(|p| p.display().to_string())
+}
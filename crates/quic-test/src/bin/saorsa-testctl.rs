@@ -5,15 +5,15 @@ use saorsa_quic_test::{
         AgentCapabilities, AgentClient, AgentInfo, AttemptResult, CollectionResult,
         FALLBACK_SOCKET_ADDR, GetResultsRequest, GetResultsResponse, HandshakeRequest,
         HandshakeResponse, HealthCheckResponse, LocalAgent, MixedOrchestrator,
-        MonitorHealthResponse, ProbeResponse, ProofsResponse, RemoteAgent, ResultFormat,
-        RunStatusResponse, RunSummary, ScenarioSpec, StartRunRequest, StartRunResponse,
-        StartRunResult, StatusPollResult, StopRunResponse,
+        MonitorHealthResponse, NatBehaviorProfile, ProbeResponse, ProofsResponse, RemoteAgent,
+        ResultFormat, RunStatusResponse, RunSummary, ScenarioSpec, StartRunRequest,
+        StartRunResponse, StartRunResult, StatusPollResult, StopRunResponse, results_to_csv,
     },
     orchestrator::NatTestMatrix,
 };
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -36,20 +36,29 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     Run {
-        #[arg(long)]
-        scenario: String,
+        #[arg(long, required_unless_present = "matrix")]
+        scenario: Option<String>,
+
+        /// Scenario matrix YAML file to run instead of a built-in scenario
+        #[arg(long, conflicts_with = "scenario")]
+        matrix: Option<PathBuf>,
 
         #[arg(long)]
         agents: Vec<String>,
 
-        #[arg(long, default_value = "100")]
-        attempts: u32,
+        /// Attempts per cell [default: 100, or the matrix file's value]
+        #[arg(long)]
+        attempts: Option<u32>,
 
         #[arg(long)]
         output: Option<PathBuf>,
 
         #[arg(long)]
         seed: Option<u64>,
+
+        /// NAT profile to apply: a preset name (full-cone, symmetric, cgnat) or a TOML file
+        #[arg(long)]
+        nat_profile: Option<String>,
     },
 
     Status {
@@ -83,6 +92,10 @@ enum Commands {
 
         #[arg(long)]
         output: Option<PathBuf>,
+
+        /// Also write one CSV row per pair-method to this file
+        #[arg(long)]
+        results_csv: Option<PathBuf>,
     },
 
     Discover {
@@ -129,6 +142,10 @@ enum Commands {
         #[arg(long, default_value = "ci_fast")]
         scenario: String,
 
+        /// Scenario matrix YAML file to run instead of --scenario
+        #[arg(long)]
+        matrix: Option<PathBuf>,
+
         /// Number of local agents to spawn
         #[arg(long, default_value = "2")]
         local_agents: u32,
@@ -137,9 +154,9 @@ enum Commands {
         #[arg(long)]
         remote_agents: Vec<String>,
 
-        /// Number of attempts per test cell
-        #[arg(long, default_value = "10")]
-        attempts: u32,
+        /// Number of attempts per test cell [default: 10, or the matrix file's value]
+        #[arg(long)]
+        attempts: Option<u32>,
 
         /// Output file for results
         #[arg(long)]
@@ -148,6 +165,10 @@ enum Commands {
         /// Timeout for the entire run in seconds
         #[arg(long, default_value = "300")]
         timeout_secs: u64,
+
+        /// NAT profile to apply: a preset name (full-cone, symmetric, cgnat) or a TOML file
+        #[arg(long)]
+        nat_profile: Option<String>,
     },
 
     /// Run long-duration network monitoring (24-hour by default)
@@ -560,6 +581,43 @@ fn load_scenario(name: &str) -> Result<ScenarioSpec> {
     }
 }
 
+/// Load the `--matrix` file if given, else the named built-in scenario with
+/// `default_attempts`. An explicit `--attempts` overrides either.
+fn resolve_scenario(
+    name: &str,
+    matrix: Option<&Path>,
+    attempts: Option<u32>,
+    default_attempts: u32,
+) -> Result<ScenarioSpec> {
+    let mut spec = match matrix {
+        Some(path) => ScenarioSpec::from_yaml_file(path)?,
+        None => {
+            let mut spec = load_scenario(name)?;
+            spec.test_matrix.attempts_per_cell = default_attempts;
+            spec
+        }
+    };
+    if let Some(attempts) = attempts {
+        spec.test_matrix.attempts_per_cell = attempts;
+    }
+    Ok(spec)
+}
+
+/// Replace the scenario's NAT profiles with the one named by `--nat-profile`.
+fn apply_nat_profile(spec: &mut ScenarioSpec, name_or_path: &str) -> Result<()> {
+    let profile = NatBehaviorProfile::resolve(name_or_path)?;
+    for warning in profile.coherence_warnings() {
+        warn!("NAT profile '{}': {}", profile.name, warning);
+    }
+    info!(
+        "Using NAT profile '{}' ({})",
+        profile.name,
+        profile.rfc4787_classification()
+    );
+    spec.nat_profiles = vec![profile.to_profile_spec()];
+    Ok(())
+}
+
 fn generate_matrix_report(scope: &str) -> String {
     let matrix = match scope {
         "minimal" => NatTestMatrix::minimal(),
@@ -668,10 +726,12 @@ async fn main() -> Result<()> {
     match cli.command {
         Commands::Run {
             scenario,
+            matrix,
             agents,
             attempts,
             output,
             seed,
+            nat_profile,
         } => {
             if agents.is_empty() {
                 anyhow::bail!("At least one agent URL required (--agents)");
@@ -684,11 +744,22 @@ async fn main() -> Result<()> {
 
             orchestrator.handshake_agents().await?;
 
-            let mut scenario_spec = load_scenario(&scenario)?;
+            let mut scenario_spec = resolve_scenario(
+                scenario.as_deref().unwrap_or_default(),
+                matrix.as_deref(),
+                attempts,
+                100,
+            )?;
+            if matrix.is_some() {
+                scenario_spec.check_agent_count(discovered.len())?;
+            }
+            let scenario = scenario_spec.id.clone();
             if let Some(s) = seed {
                 scenario_spec.seed = Some(s);
             }
-            scenario_spec.test_matrix.attempts_per_cell = attempts;
+            if let Some(ref profile) = nat_profile {
+                apply_nat_profile(&mut scenario_spec, profile)?;
+            }
 
             info!("Starting run with scenario: {}", scenario);
             let start_result = orchestrator.start_run(scenario_spec).await?;
@@ -797,6 +868,7 @@ async fn main() -> Result<()> {
             agents,
             format,
             output,
+            results_csv,
         } => {
             if agents.is_empty() {
                 anyhow::bail!("At least one agent URL required");
@@ -805,6 +877,11 @@ async fn main() -> Result<()> {
             orchestrator.discover_agents(&agents).await?;
             let collection = orchestrator.collect_results(run_id).await?;
 
+            if let Some(csv_path) = results_csv {
+                std::fs::write(&csv_path, results_to_csv(&collection.items))?;
+                info!("CSV results written to {:?}", csv_path);
+            }
+
             if !collection.is_complete() {
                 warn!(
                     "WARNING: Results incomplete - {} sources failed: {:?}",
@@ -931,11 +1008,13 @@ async fn main() -> Result<()> {
 
         Commands::LocalRun {
             scenario,
+            matrix,
             local_agents,
             remote_agents,
             attempts,
             output,
             timeout_secs,
+            nat_profile,
         } => {
             info!("Starting local test run with {} local agents", local_agents);
 
@@ -998,12 +1077,18 @@ async fn main() -> Result<()> {
             );
 
             // Load and configure scenario
-            let mut scenario_spec = load_scenario(&scenario)?;
-            scenario_spec.test_matrix.attempts_per_cell = attempts;
+            let mut scenario_spec = resolve_scenario(&scenario, matrix.as_deref(), attempts, 10)?;
+            if matrix.is_some() {
+                scenario_spec.check_agent_count(mixed_orch.agent_count())?;
+            }
+            let scenario = scenario_spec.id.clone();
+            if let Some(ref profile) = nat_profile {
+                apply_nat_profile(&mut scenario_spec, profile)?;
+            }
 
             info!(
                 "Running scenario '{}' with {} attempts per cell",
-                scenario, attempts
+                scenario, scenario_spec.test_matrix.attempts_per_cell
             );
 
             // Run tests
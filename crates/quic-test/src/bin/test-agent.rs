@@ -18,24 +18,29 @@
 
 use axum::{
     Json, Router,
+    body::Bytes,
     extract::{Path, State},
     http::StatusCode,
-    routing::{get, post},
+    routing::{get, post, put},
 };
 use clap::Parser;
 use saorsa_quic_test::harness::{
     AgentCapabilities, AgentInfo, AgentStatus, ApplyProfileRequest, ApplyProfileResponse,
-    AttemptResult, BarrierRequest, BarrierResponse, ClearProfileRequest, ClearProfileResponse,
+    ArtifactChunkQuery, ArtifactChunkQueryResponse, AttemptResult, BarrierRequest, BarrierResponse,
+    ClearProfileRequest, ClearProfileResponse, CommitArtifactRequest, CommitArtifactResponse,
     ConnectivityProofData, ConnectivitySummary, CrdtProofData, FailureCategory, GetResultsResponse,
     GossipProofData, HandshakeRequest, HandshakeResponse, HealthCheckResponse, IpMode, LogEntry,
     LogsQuery, MatrixEntry, MonitorHealthResponse, MonitorLogsResponse, MonitorMatrixResponse,
     MonitorPeerInfo, MonitorPeersResponse, PeerAgentInfo, PeerConnectionStatus, ProbeResponse,
     ProofSummary, ProofsResponse, RunProgress, RunStatus, RunStatusResponse, RunSummary,
     ScenarioSpec, StartRunRequest, StartRunResponse, StopRunRequest, StopRunResponse,
+    is_bundle_relative,
 };
 use saorsa_quic_test::registry::{ConnectionMethod, FailureReasonCode, NatType, SuccessLevel};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::net::UdpSocket;
@@ -81,6 +86,10 @@ struct Cli {
     /// Maximum concurrent test runs
     #[arg(long, default_value = "4")]
     max_concurrent: u32,
+
+    /// Directory uploaded artifacts are stored in (default: system temp dir)
+    #[arg(long)]
+    artifact_dir: Option<PathBuf>,
 }
 
 /// State for an active test run.
@@ -107,15 +116,22 @@ struct AgentState {
     last_error: RwLock<Option<String>>,
     // Barrier coordination
     barriers: RwLock<HashMap<String, Vec<String>>>,
+    artifact_dir: PathBuf,
 }
 
 impl AgentState {
-    fn new(agent_id: String, p2p_listen_addr: SocketAddr, capabilities: AgentCapabilities) -> Self {
+    fn new(
+        agent_id: String,
+        p2p_listen_addr: SocketAddr,
+        capabilities: AgentCapabilities,
+        artifact_dir: PathBuf,
+    ) -> Self {
         Self {
             agent_id,
             start_time: Instant::now(),
             p2p_listen_addr,
             capabilities,
+            artifact_dir,
             active_runs: RwLock::new(HashMap::new()),
             current_status: RwLock::new(AgentStatus::Idle),
             last_error: RwLock::new(None),
@@ -126,6 +142,10 @@ impl AgentState {
     fn uptime_secs(&self) -> u64 {
         self.start_time.elapsed().as_secs()
     }
+
+    fn chunk_dir(&self, run_id: Uuid) -> PathBuf {
+        self.artifact_dir.join(run_id.to_string()).join("chunks")
+    }
 }
 
 type SharedState = Arc<AgentState>;
@@ -178,10 +198,16 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Create shared state
+    let artifact_dir = cli
+        .artifact_dir
+        .unwrap_or_else(|| std::env::temp_dir().join("saorsa-test-agent"));
+    info!("Storing uploaded artifacts in {}", artifact_dir.display());
+
     let state = Arc::new(AgentState::new(
         agent_id.clone(),
         p2p_listen_addr,
         capabilities,
+        artifact_dir,
     ));
 
     // Build router
@@ -196,6 +222,12 @@ async fn main() -> anyhow::Result<()> {
         .route("/node/profile", post(apply_profile_handler))
         .route("/node/profile/clear", post(clear_profile_handler))
         .route("/barrier", post(barrier_handler))
+        .route("/run/{id}/artifacts", post(commit_artifact_handler))
+        .route("/run/{id}/artifacts/chunks", post(query_chunks_handler))
+        .route(
+            "/run/{id}/artifacts/chunks/{hash}",
+            put(upload_chunk_handler),
+        )
         // Monitoring API endpoints
         .route("/api/probe", get(probe_handler))
         .route("/api/health", get(monitor_health_handler))
@@ -493,6 +525,7 @@ async fn simulate_connectivity_test(
 
     if let Some(time_ms) = connect_time_ms {
         result.record_success(ConnectionMethod::Direct, time_ms, SuccessLevel::Usable);
+        result.record_rtt(time_ms);
     } else {
         result.record_failure(
             "Connection timeout",
@@ -595,6 +628,102 @@ async fn results_handler(
     }
 }
 
+/// Report which of the requested chunk hashes are already stored.
+async fn query_chunks_handler(
+    State(state): State<SharedState>,
+    Path(run_id): Path<Uuid>,
+    Json(query): Json<ArtifactChunkQuery>,
+) -> Json<ArtifactChunkQueryResponse> {
+    let chunk_dir = state.chunk_dir(run_id);
+    let present = query
+        .hashes
+        .into_iter()
+        .filter(|hash| is_chunk_hash(hash) && chunk_dir.join(hash).is_file())
+        .collect();
+    Json(ArtifactChunkQueryResponse { present })
+}
+
+/// Store one chunk under its hash, rejecting bodies that don't match it.
+async fn upload_chunk_handler(
+    State(state): State<SharedState>,
+    Path((run_id, hash)): Path<(Uuid, String)>,
+    body: Bytes,
+) -> StatusCode {
+    if !is_chunk_hash(&hash) || hex::encode(Sha256::digest(&body)) != hash {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    let chunk_dir = state.chunk_dir(run_id);
+    let partial = chunk_dir.join(format!("{}.partial", hash));
+    let result = async {
+        tokio::fs::create_dir_all(&chunk_dir).await?;
+        tokio::fs::write(&partial, &body).await?;
+        tokio::fs::rename(&partial, chunk_dir.join(&hash)).await
+    }
+    .await;
+
+    match result {
+        Ok(()) => StatusCode::CREATED,
+        Err(e) => {
+            error!("Failed to store chunk {} for run {}: {}", hash, run_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+/// Assemble an artifact from previously uploaded chunks.
+async fn commit_artifact_handler(
+    State(state): State<SharedState>,
+    Path(run_id): Path<Uuid>,
+    Json(request): Json<CommitArtifactRequest>,
+) -> Result<Json<CommitArtifactResponse>, StatusCode> {
+    if !is_bundle_relative(&request.entry.path) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let chunk_dir = state.chunk_dir(run_id);
+    let dest = state
+        .artifact_dir
+        .join(run_id.to_string())
+        .join(&request.entry.path);
+    let chunks = request.chunks;
+    let result = tokio::task::spawn_blocking(move || chunks.assemble(&chunk_dir, &dest))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let response = match result {
+        Ok(missing) => {
+            if missing.is_empty() {
+                info!(
+                    "Stored {} artifact {} for run {}",
+                    request.entry.artifact_type, request.entry.path, run_id
+                );
+            }
+            CommitArtifactResponse {
+                complete: missing.is_empty(),
+                missing,
+                error: None,
+            }
+        }
+        Err(e) => {
+            warn!(
+                "Failed to assemble {} for run {}: {}",
+                request.entry.path, run_id, e
+            );
+            CommitArtifactResponse {
+                complete: false,
+                missing: Vec::new(),
+                error: Some(e.to_string()),
+            }
+        }
+    };
+    Ok(Json(response))
+}
+
+fn is_chunk_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
 async fn apply_profile_handler(
     State(state): State<SharedState>,
     Json(request): Json<ApplyProfileRequest>,
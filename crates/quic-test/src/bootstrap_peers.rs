@@ -34,9 +34,23 @@
 //! - **Address Restricted**: Medium difficulty (saorsa-4, saorsa-5)
 //! - **Port Restricted**: Hard (saorsa-6, saorsa-7)
 //! - **Symmetric**: Most restrictive (saorsa-8, saorsa-9)
+//!
+//! # Overriding the Bootstrap Set
+//!
+//! An isolated testnet can replace the hardcoded list at runtime. Precedence:
+//!
+//! 1. `--bootstrap <host:port>` CLI flags (repeatable)
+//! 2. The `SAORSA_BOOTSTRAP` environment variable (comma-separated `host:port`)
+//! 3. The hardcoded [`BOOTSTRAP_PEERS`]
+//!
+//! `--local-only` suppresses the hardcoded list entirely; an explicit
+//! override is still used.
 
 use crate::NatType;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+/// Environment variable overriding the bootstrap peers.
+pub const BOOTSTRAP_ENV_VAR: &str = "SAORSA_BOOTSTRAP";
 
 /// A bootstrap peer with its network addresses and capabilities.
 #[derive(Debug, Clone)]
@@ -240,6 +254,44 @@ pub fn bootstrap_addrs_all() -> Vec<SocketAddr> {
         .collect()
 }
 
+/// Resolve a `host:port` bootstrap entry to its socket addresses.
+pub fn parse_bootstrap_entry(entry: &str) -> Result<Vec<SocketAddr>, String> {
+    let entry = entry.trim();
+    let addrs: Vec<SocketAddr> = entry
+        .to_socket_addrs()
+        .map_err(|e| format!("Invalid bootstrap peer '{}': {}", entry, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!(
+            "Bootstrap peer '{}' resolved to no addresses",
+            entry
+        ));
+    }
+    Ok(addrs)
+}
+
+/// Bootstrap addresses overriding the hardcoded list, if any.
+///
+/// CLI entries win over the environment variable value. An empty result
+/// means no override: use the hardcoded [`BOOTSTRAP_PEERS`].
+pub fn bootstrap_override(cli: &[String], env: Option<&str>) -> Result<Vec<SocketAddr>, String> {
+    let entries: Vec<&str> = if !cli.is_empty() {
+        cli.iter().map(String::as_str).collect()
+    } else {
+        env.map(|v| v.split(',').filter(|e| !e.trim().is_empty()).collect())
+            .unwrap_or_default()
+    };
+    let mut addrs = Vec::new();
+    for entry in entries {
+        for addr in parse_bootstrap_entry(entry)? {
+            if !addrs.contains(&addr) {
+                addrs.push(addr);
+            }
+        }
+    }
+    Ok(addrs)
+}
+
 /// Get the primary relay node (saorsa-1).
 #[must_use]
 pub fn relay_node() -> &'static BootstrapPeer {
@@ -370,4 +422,27 @@ mod tests {
         assert!(peer.is_some());
         assert_eq!(peer.unwrap().ipv4, Ipv4Addr::new(144, 126, 230, 161));
     }
+
+    #[test]
+    fn test_bootstrap_override_precedence() {
+        let cli = vec!["10.0.0.1:9000".to_string()];
+        let env = Some("10.0.0.2:9000, 10.0.0.3:9000");
+
+        let addrs = bootstrap_override(&cli, env).unwrap();
+        assert_eq!(addrs, vec!["10.0.0.1:9000".parse::<SocketAddr>().unwrap()]);
+
+        let addrs = bootstrap_override(&[], env).unwrap();
+        assert_eq!(
+            addrs,
+            vec![
+                "10.0.0.2:9000".parse::<SocketAddr>().unwrap(),
+                "10.0.0.3:9000".parse().unwrap(),
+            ]
+        );
+
+        // Neither set: fall back to the hardcoded list
+        assert!(bootstrap_override(&[], None).unwrap().is_empty());
+        assert!(bootstrap_override(&[], Some("")).unwrap().is_empty());
+        assert!(bootstrap_override(&["no-port".to_string()], None).is_err());
+    }
 }
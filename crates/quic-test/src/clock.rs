@@ -0,0 +1,89 @@
+//! Time source abstraction.
+//!
+//! Code with timeouts, TTLs or backoffs asks a [`Clock`] for the current
+//! time instead of calling `Instant::now()` directly. Production code uses
+//! [`SystemClock`]; tests use [`MockClock`] and move time forward
+//! explicitly, so expiry fires deterministically without real sleeps.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of monotonic time.
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// The current instant.
+    fn now(&self) -> Instant;
+}
+
+/// Shared handle to a clock.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and pass
+/// another to the code under test.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl MockClock {
+    /// A mock clock starting at the current real time.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Move time forward by `by`.
+    pub fn advance(&self, by: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += by;
+        }
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now
+            .lock()
+            .map(|now| *now)
+            .unwrap_or_else(|e| *e.into_inner())
+    }
+}
+
+/// The system clock as a [`SharedClock`].
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let shared: SharedClock = Arc::new(clock.clone());
+        let start = shared.now();
+        assert_eq!(shared.now(), start);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(shared.now() - start, Duration::from_secs(90));
+    }
+}
@@ -15,8 +15,13 @@
 //! 4. Capture final state hashes from all nodes
 //! 5. Verify all states match
 //! 6. Verify conflict resolution was semantically correct
+//!
+//! For chaos testing, [`CrdtVerifier::inject_conflict`] writes two different
+//! records for one key into the peer caches of two gossip replicas before
+//! they sync, then runs the state merge in both directions and checks the
+//! replicas agree.
 
-use crate::epidemic_gossip::CrdtStats;
+use crate::epidemic_gossip::{CrdtStats, EpidemicGossip, PeerCacheEntry};
 use crate::registry::{
     CrdtConvergenceProof, CrdtOperation, CrdtType, ProofType, SignedAttestation,
 };
@@ -260,6 +265,8 @@ pub struct CrdtVerifier {
     convergence: ConvergenceState,
     crdt_type: CrdtType,
     test_id: String,
+    /// Outcomes of deliberately injected conflicts.
+    injected_conflicts: Vec<ConflictResolutionResult>,
 }
 
 impl CrdtVerifier {
@@ -276,6 +283,7 @@ impl CrdtVerifier {
             convergence: ConvergenceState::new(),
             crdt_type,
             test_id: uuid::Uuid::new_v4().to_string(),
+            injected_conflicts: Vec::new(),
         }
     }
 
@@ -338,6 +346,7 @@ impl CrdtVerifier {
                 had_conflicts: false,
                 correctly_resolved: true,
                 details: "No concurrent operations detected".to_string(),
+                ..Default::default()
             };
         }
 
@@ -391,9 +400,104 @@ impl CrdtVerifier {
             had_conflicts: !concurrent_pairs.is_empty(),
             correctly_resolved: self.convergence.is_converged(),
             details,
+            ..Default::default()
         }
     }
 
+    /// Write `value_a` at `node_a` and `value_b` at `node_b` for `key`
+    /// without letting them sync, then exchange state as gossip would and
+    /// check both replicas reach the same state hash.
+    ///
+    /// The writes are peer cache records (the value is the record's
+    /// location) in each replica's real CRDT state, merged with
+    /// [`EpidemicGossip::merge_peer_cache_state`]. The peer cache is an
+    /// OR-Set, so concurrent writes must both survive on every replica.
+    pub async fn inject_conflict(
+        &mut self,
+        key: &str,
+        value_a: &str,
+        value_b: &str,
+        node_a: &EpidemicGossip,
+        node_b: &EpidemicGossip,
+    ) -> ConflictResolutionResult {
+        let started = Instant::now();
+        let peer_id = compute_state_hash(key.as_bytes());
+        let name_a = hex::encode(node_a.peer_id().as_bytes());
+        let name_b = hex::encode(node_b.peer_id().as_bytes());
+        let last_seen = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        for (node, name, value) in [(node_a, &name_a, value_a), (node_b, &name_b, value_b)] {
+            node.add_to_peer_cache(PeerCacheEntry {
+                peer_id,
+                addresses: Vec::new(),
+                last_seen,
+                location: Some(value.to_string()),
+            })
+            .await;
+            let mut operation = CrdtOperation::new(name.clone(), "add".to_string());
+            operation.payload_hash = compute_state_hash(format!("{}={}", key, value).as_bytes());
+            self.operations.record(operation);
+        }
+
+        // One gossip round: each side merges the other's pre-merge state
+        let snapshot_a = node_a.peer_cache_state().await;
+        let snapshot_b = node_b.peer_cache_state().await;
+        node_a.merge_peer_cache_state(&snapshot_b).await;
+        node_b.merge_peer_cache_state(&snapshot_a).await;
+
+        let entries_a = node_a.get_peer_cache_entries().await;
+        let entries_b = node_b.get_peer_cache_entries().await;
+        let converged = peer_cache_state_hash(&entries_a) == peer_cache_state_hash(&entries_b);
+        let mut settled: Vec<String> = entries_a
+            .iter()
+            .filter(|entry| entry.peer_id == peer_id)
+            .filter_map(|entry| entry.location.clone())
+            .collect();
+        settled.sort();
+        let kept_both = [value_a, value_b]
+            .iter()
+            .all(|value| settled.iter().any(|kept| kept == value));
+        let short = |name: &str| name[..8.min(name.len())].to_string();
+
+        let result = ConflictResolutionResult {
+            had_conflicts: true,
+            // Concurrent adds must both survive; losing either is a bug
+            correctly_resolved: converged && kept_both,
+            details: if converged {
+                format!(
+                    "Injected conflict on '{}': {} vs {} -> kept [{}]",
+                    key,
+                    short(&name_a),
+                    short(&name_b),
+                    settled.join(", ")
+                )
+            } else {
+                format!(
+                    "Injected conflict on '{}': replicas diverged ({} entries at {}, {} at {})",
+                    key,
+                    entries_a.len(),
+                    short(&name_a),
+                    entries_b.len(),
+                    short(&name_b)
+                )
+            },
+            winning_value: converged.then(|| settled.join(", ")),
+            // Neither write wins outright in an OR-Set
+            winning_node: None,
+            convergence_time_ms: converged.then(|| started.elapsed().as_millis() as u64),
+        };
+        self.injected_conflicts.push(result.clone());
+        result
+    }
+
+    /// Outcomes of conflicts injected with [`inject_conflict`](Self::inject_conflict).
+    pub fn injected_conflicts(&self) -> &[ConflictResolutionResult] {
+        &self.injected_conflicts
+    }
+
     /// Generate a convergence proof.
     pub fn generate_proof(&self, observer_id: String) -> CrdtConvergenceProof {
         let conflict_result = self.verify_conflict_resolution();
@@ -404,7 +508,11 @@ impl CrdtVerifier {
         proof.node_final_states = self.convergence.current.clone();
         proof.convergence_achieved = self.convergence.is_converged();
         proof.convergence_time_ms = self.convergence.convergence_time_ms().unwrap_or(0);
-        proof.conflict_resolution_correct = conflict_result.correctly_resolved;
+        proof.conflict_resolution_correct = conflict_result.correctly_resolved
+            && self
+                .injected_conflicts
+                .iter()
+                .all(|result| result.correctly_resolved);
 
         // Create attestation
         let attestation = SignedAttestation::new(
@@ -444,11 +552,12 @@ impl CrdtVerifier {
         self.operations.clear();
         self.convergence = ConvergenceState::new();
         self.test_id = uuid::Uuid::new_v4().to_string();
+        self.injected_conflicts.clear();
     }
 }
 
 /// Result of conflict resolution verification.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ConflictResolutionResult {
     /// Whether any concurrent operations were detected.
     pub had_conflicts: bool,
@@ -456,6 +565,12 @@ pub struct ConflictResolutionResult {
     pub correctly_resolved: bool,
     /// Human-readable details about the resolution.
     pub details: String,
+    /// Value every replica settled on (injected conflicts only).
+    pub winning_value: Option<String>,
+    /// Node whose write won (injected conflicts only).
+    pub winning_node: Option<String>,
+    /// Time from the conflicting writes to convergence (injected conflicts only).
+    pub convergence_time_ms: Option<u64>,
 }
 
 /// Summary of CRDT verification results.
@@ -516,6 +631,16 @@ pub fn compute_state_hash(data: &[u8]) -> [u8; 32] {
     hash
 }
 
+/// State hash of a peer cache, independent of the order entries are listed in.
+pub fn peer_cache_state_hash(entries: &[PeerCacheEntry]) -> [u8; 32] {
+    let mut encoded: Vec<Vec<u8>> = entries
+        .iter()
+        .map(|entry| serde_json::to_vec(entry).unwrap_or_default())
+        .collect();
+    encoded.sort();
+    compute_state_hash(&encoded.concat())
+}
+
 /// Live CRDT state captured from a gossip node.
 ///
 /// This represents the actual state captured from a running gossip instance,
@@ -740,6 +865,45 @@ impl Default for LiveConvergenceVerifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use saorsa_gossip_types::PeerId as GossipPeerId;
+
+    #[tokio::test]
+    async fn test_injected_conflict_keeps_both_concurrent_writes() {
+        let replica = |seed: u8| {
+            let (tx, _rx) = tokio::sync::mpsc::channel(1);
+            EpidemicGossip::new(
+                GossipPeerId::new([seed; 32]),
+                crate::epidemic_gossip::EpidemicConfig::default(),
+                tx,
+            )
+        };
+        let (node_a, node_b) = (replica(1), replica(2));
+        let mut verifier = CrdtVerifier::new(CrdtType::PeerCache);
+
+        let result = verifier
+            .inject_conflict("motd", "hello", "goodbye", &node_a, &node_b)
+            .await;
+        assert!(result.had_conflicts);
+        assert!(result.correctly_resolved, "{}", result.details);
+        assert!(result.convergence_time_ms.is_some());
+        assert_eq!(result.winning_value.as_deref(), Some("goodbye, hello"));
+        assert_eq!(node_a.peer_cache_len().await, 2);
+        assert_eq!(
+            peer_cache_state_hash(&node_a.get_peer_cache_entries().await),
+            peer_cache_state_hash(&node_b.get_peer_cache_entries().await)
+        );
+
+        assert_eq!(verifier.injected_conflicts().len(), 1);
+        assert!(verifier.verify_conflict_resolution().had_conflicts);
+        assert!(
+            verifier
+                .generate_proof("observer".to_string())
+                .conflict_resolution_correct
+        );
+
+        verifier.reset();
+        assert!(verifier.injected_conflicts().is_empty());
+    }
 
     #[test]
     fn test_operation_tracking() {
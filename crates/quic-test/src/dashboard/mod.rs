@@ -9,6 +9,7 @@
 //! - `/gossip` - Gossip health (HyParView, SWIM, Plumtree)
 //! - `/matrix` - Connectivity matrix (N×N)
 //! - `/log` - Protocol log (real-time frames)
+//! - `/peer/{peer_id}` - Event timeline of one peer
 //!
 //! # API Endpoints
 //!
@@ -16,8 +17,21 @@
 //! - `GET /api/peers` - All registered peers
 //! - `GET /api/overview` - Aggregated overview data
 //! - `GET /api/connections` - Connection history with directional stats
+//! - `GET /api/connections.csv` - Connectivity matrix edges as CSV
+//! - `GET /api/topology.geojson` - Globe peers and connections as GeoJSON
+//! - `GET /api/latency-matrix` - Median RTT between each pair of countries
 //! - `GET /api/frames` - Recent protocol frames
 //! - `GET /api/gossip` - Gossip protocol health
+//! - `GET /api/peer/{peer_id}/timeline` - One peer's events, oldest first
+//!
+//! With `serve_ui` disabled (`--no-dashboard`) only the API endpoints and
+//! `/ws/live` are served; page and static routes return 404.
+//!
+//! Clients that connect to `/ws/live?delta=true` receive `full_state`
+//! resyncs as merge patches against the previous snapshot. warp's WebSocket
+//! layer does not support permessage-deflate, so clients that can inflate
+//! raw DEFLATE ask for `?compress=deflate` and get binary frames of
+//! compressed JSON instead. Other clients get full text messages.
 
 pub mod types;
 
@@ -27,7 +41,7 @@ use rust_embed::Embed;
 use std::sync::Arc;
 use warp::Filter;
 
-use crate::registry::PeerStore;
+use crate::registry::{ApiError, PeerStore};
 
 /// Embedded static files from the static/ directory.
 #[derive(Embed)]
@@ -35,8 +49,12 @@ use crate::registry::PeerStore;
 struct StaticFiles;
 
 /// Create dashboard routes.
+///
+/// `serve_ui` controls whether the HTML pages and embedded static assets are
+/// served; the API and WebSocket routes are always available.
 pub fn dashboard_routes(
     store: Arc<PeerStore>,
+    serve_ui: bool,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     // Page routes
     let index = warp::path::end().and_then(serve_index);
@@ -50,6 +68,7 @@ pub fn dashboard_routes(
         .and(warp::path::end())
         .and_then(serve_matrix);
     let log = warp::path("log").and(warp::path::end()).and_then(serve_log);
+    let peer = warp::path!("peer" / String).and_then(serve_peer);
 
     // Static files
     let static_files = warp::path("static")
@@ -78,6 +97,26 @@ pub fn dashboard_routes(
         .and(with_store(store.clone()))
         .and_then(get_connections);
 
+    let api_connections_csv = warp::path!("api" / "connections.csv")
+        .and(warp::get())
+        .and(with_store(store.clone()))
+        .and_then(get_connections_csv);
+
+    let api_topology_geojson = warp::path!("api" / "topology.geojson")
+        .and(warp::get())
+        .and(with_store(store.clone()))
+        .and_then(get_topology_geojson);
+
+    let api_latency_matrix = warp::path!("api" / "latency-matrix")
+        .and(warp::get())
+        .and(with_store(store.clone()))
+        .and_then(get_latency_matrix);
+
+    let api_rtt_histogram = warp::path!("api" / "rtt-histogram")
+        .and(warp::get())
+        .and(with_store(store.clone()))
+        .and_then(get_rtt_histogram);
+
     let api_frames = warp::path!("api" / "frames")
         .and(warp::get())
         .and(warp::query::<FramesQuery>())
@@ -89,27 +128,61 @@ pub fn dashboard_routes(
         .and(with_store(store.clone()))
         .and_then(get_gossip);
 
+    let api_peer_timeline = warp::path!("api" / "peer" / String / "timeline")
+        .and(warp::get())
+        .and(with_store(store.clone()))
+        .and_then(get_peer_timeline);
+
     // WebSocket
     let ws_live = warp::path!("ws" / "live")
         .and(warp::ws())
+        .and(warp::query::<LiveOptions>())
         .and(with_store(store))
-        .map(|ws: warp::ws::Ws, store: Arc<PeerStore>| {
-            ws.on_upgrade(move |socket| handle_websocket(socket, store))
-        });
+        .map(
+            |ws: warp::ws::Ws, options: LiveOptions, store: Arc<PeerStore>| {
+                ws.on_upgrade(move |socket| handle_websocket(socket, store, options))
+            },
+        );
 
     // Combine routes in groups to avoid type recursion issues
     // Box intermediate groups to break the deeply nested Or<Or<Or<...>>> type chain
-    let pages = index.or(overview).or(gossip).or(matrix).or(log).boxed();
+    let pages = index
+        .or(overview)
+        .or(gossip)
+        .or(matrix)
+        .or(log)
+        .or(peer)
+        .boxed();
 
     let api = api_stats
         .or(api_peers)
         .or(api_overview)
         .or(api_connections)
+        .or(api_connections_csv)
+        .or(api_topology_geojson)
+        .or(api_latency_matrix)
+        .or(api_rtt_histogram)
         .or(api_frames)
         .or(api_gossip)
+        .or(api_peer_timeline)
         .boxed();
 
-    pages.or(static_files).or(api).or(ws_live)
+    let ui = ui_enabled(serve_ui).and(pages.or(static_files));
+
+    ui.or(api).or(ws_live)
+}
+
+/// Passes when the web UI is enabled, otherwise rejects as not found.
+fn ui_enabled(enabled: bool) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::any()
+        .and_then(move || async move {
+            if enabled {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
 }
 
 /// Query parameters for frames endpoint.
@@ -155,6 +228,10 @@ async fn serve_log() -> Result<impl warp::Reply, warp::Rejection> {
     serve_html_page("log.html").await
 }
 
+async fn serve_peer(_peer_id: String) -> Result<impl warp::Reply, warp::Rejection> {
+    serve_html_page("peer.html").await
+}
+
 async fn serve_html_page(filename: &str) -> Result<impl warp::Reply, warp::Rejection> {
     match StaticFiles::get(filename) {
         Some(content) => Ok(warp::reply::html(
@@ -191,16 +268,54 @@ async fn get_peers(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Reje
 
 /// Get aggregated overview data for the overview page.
 async fn get_overview(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
-    let response = store.get_overview_data();
+    let response = store.get_overview_data().await;
     Ok(warp::reply::json(&response))
 }
 
 /// Get connection history for the connectivity matrix.
 async fn get_connections(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
-    let response = store.get_connections_data();
+    let response = store.get_connections_data().await;
+    Ok(warp::reply::json(&response))
+}
+
+/// Export the connectivity matrix edges as CSV.
+async fn get_connections_csv(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut csv = Vec::new();
+    store
+        .get_connections_data()
+        .await
+        .to_csv(&mut csv)
+        .map_err(|e| warp::reject::custom(ApiError::internal(e.to_string())))?;
+    Ok(warp::reply::with_header(
+        csv,
+        "Content-Type",
+        "text/csv; charset=utf-8",
+    ))
+}
+
+/// Export the globe topology as a GeoJSON FeatureCollection.
+async fn get_topology_geojson(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
+    let peers = store.get_all_peers();
+    let connections = store.get_connections_data().await;
+    let geojson = topology_geojson(&peers, &connections.edges);
+    Ok(warp::reply::with_header(
+        geojson.to_string(),
+        "Content-Type",
+        "application/geo+json",
+    ))
+}
+
+/// Get the country-to-country latency matrix for the heatmap.
+async fn get_latency_matrix(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
+    let response = store.get_latency_matrix().await;
     Ok(warp::reply::json(&response))
 }
 
+/// Get bucketed RTT counts per connection method.
+async fn get_rtt_histogram(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rejection> {
+    Ok(warp::reply::json(&store.get_rtt_histogram()))
+}
+
 /// Get recent protocol frames for the log display.
 async fn get_frames(
     query: FramesQuery,
@@ -216,96 +331,300 @@ async fn get_gossip(store: Arc<PeerStore>) -> Result<impl warp::Reply, warp::Rej
     Ok(warp::reply::json(&response))
 }
 
-async fn handle_websocket(ws: warp::ws::WebSocket, store: Arc<PeerStore>) {
-    use futures_util::{SinkExt, StreamExt};
-    use tokio::time::{Duration, interval};
+/// Get one peer's event timeline; 404 for peers the registry never saw.
+async fn get_peer_timeline(
+    peer_id: String,
+    store: Arc<PeerStore>,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    use warp::Reply;
 
-    let (mut tx, mut rx) = ws.split();
+    match store.get_peer_timeline(&peer_id).await {
+        Some(timeline) => Ok(warp::reply::json(&timeline).into_response()),
+        None => Ok(ApiError::peer_not_found(format!("Unknown peer: {}", peer_id)).into_reply()),
+    }
+}
 
-    // Send initial full state
-    let initial_state = serde_json::json!({
+/// JSON message for a network event, tagged with its sequence number.
+fn event_message(seq: u64, event: &crate::registry::NetworkEvent) -> serde_json::Value {
+    let mut msg = match event {
+        crate::registry::NetworkEvent::NodeRegistered {
+            peer_id,
+            country_code,
+            latitude,
+            longitude,
+            location_estimated,
+        } => serde_json::json!({
+            "type": "node_registered",
+            "peer_id": peer_id,
+            "country_code": country_code,
+            "latitude": latitude,
+            "longitude": longitude,
+            "location_estimated": location_estimated,
+        }),
+        crate::registry::NetworkEvent::NodeOffline { peer_id } => serde_json::json!({
+            "type": "node_offline",
+            "peer_id": peer_id,
+        }),
+        crate::registry::NetworkEvent::ConnectionEstablished {
+            from_peer,
+            to_peer,
+            method,
+            rtt_ms,
+        } => serde_json::json!({
+            "type": "connection_established",
+            "from_peer": from_peer,
+            "to_peer": to_peer,
+            "method": format!("{:?}", method).to_lowercase(),
+            "rtt_ms": rtt_ms,
+        }),
+        crate::registry::NetworkEvent::StatsUpdate(stats) => serde_json::json!({
+            "type": "stats_update",
+            "stats": stats,
+        }),
+        crate::registry::NetworkEvent::ConnectivityTestRequest {
+            peer_id,
+            addresses,
+            relay_addr,
+            timestamp_ms,
+        } => serde_json::json!({
+            "type": "connectivity_test_request",
+            "peer_id": peer_id,
+            "addresses": addresses.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            "relay_addr": relay_addr.map(|a| a.to_string()),
+            "timestamp_ms": timestamp_ms,
+        }),
+        crate::registry::NetworkEvent::SuccessRateAlert { rate, threshold } => {
+            serde_json::json!({
+                "type": "success_rate_alert",
+                "rate": rate,
+                "threshold": threshold,
+            })
+        }
+        crate::registry::NetworkEvent::SuccessRateRecovered { rate, threshold } => {
+            serde_json::json!({
+                "type": "success_rate_recovered",
+                "rate": rate,
+                "threshold": threshold,
+            })
+        }
+    };
+    msg["seq"] = seq.into();
+    msg
+}
+
+/// JSON snapshot of every peer and the network stats as of event `seq`.
+fn full_state_message(store: &PeerStore, seq: u64) -> serde_json::Value {
+    serde_json::json!({
         "type": "full_state",
+        "seq": seq,
         "nodes": store.get_all_peers(),
         "stats": store.get_stats(),
-    });
+    })
+}
 
-    if tx
-        .send(warp::ws::Message::text(initial_state.to_string()))
-        .await
-        .is_err()
-    {
-        return;
+/// Messages from the dashboard to the server over `/ws/live`.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Replay every event after sequence number `since`
+    Resync { since: u64 },
+}
+
+/// Query options for `/ws/live`.
+#[derive(Debug, Default, serde::Deserialize)]
+struct LiveOptions {
+    /// Send `full_state` resyncs as deltas (`?delta=true`)
+    #[serde(default)]
+    delta: bool,
+    /// Compress frames (`?compress=deflate`)
+    #[serde(default)]
+    compress: Option<FrameCompression>,
+}
+
+/// Frame compression a client can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FrameCompression {
+    /// Raw DEFLATE (RFC 1951), which browsers inflate with
+    /// `DecompressionStream("deflate-raw")`
+    Deflate,
+}
+
+/// JSON merge patch (RFC 7396) that turns `old` into `new`.
+///
+/// Unchanged fields are left out, nested objects are diffed recursively and
+/// removed keys are set to `null`.
+fn merge_patch(old: &serde_json::Value, new: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut patch = serde_json::Map::new();
+            for (key, value) in new {
+                match old.get(key) {
+                    Some(previous) if previous == value => {}
+                    Some(previous) => {
+                        patch.insert(key.clone(), merge_patch(previous, value));
+                    }
+                    None => {
+                        patch.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                patch.insert(key.clone(), Value::Null);
+            }
+            Value::Object(patch)
+        }
+        _ => new.clone(),
     }
+}
 
-    let mut event_rx = store.subscribe();
-
-    // Spawn task to forward events to WebSocket
-    let forward_task = tokio::spawn(async move {
-        while let Ok(event) = event_rx.recv().await {
-            let msg = match event {
-                crate::registry::NetworkEvent::NodeRegistered {
-                    peer_id,
-                    country_code,
-                    latitude,
-                    longitude,
-                } => serde_json::json!({
-                    "type": "node_registered",
-                    "peer_id": peer_id,
-                    "country_code": country_code,
-                    "latitude": latitude,
-                    "longitude": longitude,
-                }),
-                crate::registry::NetworkEvent::NodeOffline { peer_id } => serde_json::json!({
-                    "type": "node_offline",
-                    "peer_id": peer_id,
-                }),
-                crate::registry::NetworkEvent::ConnectionEstablished {
-                    from_peer,
-                    to_peer,
-                    method,
-                    rtt_ms,
-                } => serde_json::json!({
-                    "type": "connection_established",
-                    "from_peer": from_peer,
-                    "to_peer": to_peer,
-                    "method": format!("{:?}", method).to_lowercase(),
-                    "rtt_ms": rtt_ms,
-                }),
-                crate::registry::NetworkEvent::StatsUpdate(stats) => serde_json::json!({
-                    "type": "stats_update",
-                    "stats": stats,
-                }),
-                crate::registry::NetworkEvent::ConnectivityTestRequest {
-                    peer_id,
-                    addresses,
-                    relay_addr,
-                    timestamp_ms,
-                } => serde_json::json!({
-                    "type": "connectivity_test_request",
-                    "peer_id": peer_id,
-                    "addresses": addresses.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
-                    "relay_addr": relay_addr.map(|a| a.to_string()),
-                    "timestamp_ms": timestamp_ms,
-                }),
-            };
-
-            if tx
-                .send(warp::ws::Message::text(msg.to_string()))
-                .await
-                .is_err()
-            {
-                break;
+/// The parts of a `full_state` message a delta patches, with the nodes
+/// keyed by peer ID so one node's change does not resend the whole list.
+fn snapshot_state(msg: &serde_json::Value) -> serde_json::Value {
+    let nodes: serde_json::Map<String, serde_json::Value> = msg["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|node| Some((node["peer_id"].as_str()?.to_string(), node.clone())))
+        .collect();
+    serde_json::json!({ "nodes": nodes, "stats": msg["stats"] })
+}
+
+/// Encodes the messages for one `/ws/live` client into WebSocket frames.
+///
+/// With `delta` set, every `full_state` after the first is sent as
+/// `{"type":"full_state","seq":N,"delta":true,"patch":...}`, a merge patch
+/// against the previous snapshot (see [`snapshot_state`]). Clients keep the
+/// last snapshot apart from the state they update with events, so the patch
+/// always applies to what the server diffed against.
+#[derive(Debug, Default)]
+struct FrameEncoder {
+    delta: bool,
+    compress: Option<FrameCompression>,
+    /// Last snapshot sent, when delta-encoding
+    last_snapshot: Option<serde_json::Value>,
+    /// Bytes the messages take as plain JSON
+    plain_bytes: usize,
+    /// Bytes actually sent for them
+    sent_bytes: usize,
+}
+
+impl FrameEncoder {
+    fn new(options: &LiveOptions) -> Self {
+        Self {
+            delta: options.delta,
+            compress: options.compress,
+            ..Default::default()
+        }
+    }
+
+    /// Encode `msg` into the frame to send.
+    fn encode(&mut self, mut msg: serde_json::Value) -> warp::ws::Message {
+        self.plain_bytes += msg.to_string().len();
+        if self.delta && msg["type"] == "full_state" {
+            let snapshot = snapshot_state(&msg);
+            if let Some(previous) = self.last_snapshot.replace(snapshot.clone()) {
+                msg = serde_json::json!({
+                    "type": "full_state",
+                    "seq": msg["seq"],
+                    "delta": true,
+                    "patch": merge_patch(&previous, &snapshot),
+                });
+            }
+        }
+
+        let text = msg.to_string();
+        let frame = match self.compress {
+            Some(FrameCompression::Deflate) => {
+                warp::ws::Message::binary(miniz_oxide::deflate::compress_to_vec(text.as_bytes(), 6))
             }
+            None => warp::ws::Message::text(text),
+        };
+        self.sent_bytes += frame.as_bytes().len();
+        frame
+    }
+
+    /// Fraction of bytes saved so far, if this client is encoded at all.
+    fn saved_ratio(&self) -> Option<f64> {
+        ((self.delta || self.compress.is_some()) && self.plain_bytes > 0)
+            .then(|| 1.0 - self.sent_bytes as f64 / self.plain_bytes as f64)
+    }
+}
+
+/// Messages that bring a client from event `since` up to date: the missed
+/// events if the server still has them, otherwise a fresh full state.
+///
+/// Returns the messages and the last sequence number they cover.
+fn resync_messages(store: &PeerStore, since: u64) -> (Vec<serde_json::Value>, u64) {
+    match store.events_since(since) {
+        Some(events) => {
+            let last = events.last().map_or(since, |(seq, _)| *seq);
+            let messages = events
+                .iter()
+                .map(|(seq, event)| event_message(*seq, event))
+                .collect();
+            (messages, last)
+        }
+        None => {
+            let seq = store.event_seq();
+            (vec![full_state_message(store, seq)], seq)
         }
-    });
+    }
+}
+
+/// Stream events to a dashboard client.
+///
+/// Every event message carries a `seq`; a client that notices a gap sends
+/// `{"type":"resync","since":N}` and gets the missed events replayed, or a
+/// new `full_state` when they have already left the server's event log.
+///
+/// Frames are delta-encoded and compressed as `options` ask (see
+/// [`FrameEncoder`]); the bytes saved are logged when the client disconnects.
+async fn handle_websocket(ws: warp::ws::WebSocket, store: Arc<PeerStore>, options: LiveOptions) {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::sync::broadcast::error::RecvError;
+    use tokio::time::{Duration, interval};
+
+    let (mut tx, mut rx) = ws.split();
+
+    // Subscribe before taking the snapshot so no event falls in between
+    let mut event_rx = store.subscribe_sequenced();
+    let mut last_sent = store.event_seq();
+
+    // Send initial full state
+    let initial_state = full_state_message(&store, last_sent);
+    let mut encoder = FrameEncoder::new(&options);
+
+    if tx.send(encoder.encode(initial_state)).await.is_err() {
+        return;
+    }
 
     // Keep connection alive with pings and handle incoming messages
     let mut ping_interval = interval(Duration::from_secs(30));
 
-    loop {
-        tokio::select! {
+    'stream: loop {
+        let messages = tokio::select! {
             _ = ping_interval.tick() => {
                 // Ping is handled by warp internally
+                continue;
+            }
+            event = event_rx.recv() => {
+                match event {
+                    // Already covered by the snapshot or a replay
+                    Ok((seq, _)) if seq <= last_sent => continue,
+                    Ok((seq, event)) => {
+                        last_sent = seq;
+                        vec![event_message(seq, &event)]
+                    }
+                    Err(RecvError::Lagged(_)) => {
+                        let (messages, last) = resync_messages(&store, last_sent);
+                        last_sent = last;
+                        messages
+                    }
+                    Err(RecvError::Closed) => break,
+                }
             }
             msg = rx.next() => {
                 match msg {
@@ -313,12 +632,454 @@ async fn handle_websocket(ws: warp::ws::WebSocket, store: Arc<PeerStore>) {
                         if msg.is_close() {
                             break;
                         }
+                        let request = msg
+                            .to_str()
+                            .ok()
+                            .and_then(|text| serde_json::from_str::<ClientMessage>(text).ok());
+                        match request {
+                            Some(ClientMessage::Resync { since }) => {
+                                let (messages, last) = resync_messages(&store, since);
+                                last_sent = last_sent.max(last);
+                                messages
+                            }
+                            None => continue,
+                        }
                     }
                     Some(Err(_)) | None => break,
                 }
             }
+        };
+
+        for msg in messages {
+            if tx.send(encoder.encode(msg)).await.is_err() {
+                break 'stream;
+            }
+        }
+    }
+
+    if let Some(ratio) = encoder.saved_ratio() {
+        tracing::info!(
+            "Dashboard client disconnected: encoding saved {:.0}% ({} of {} bytes sent)",
+            ratio * 100.0,
+            encoder.sent_bytes,
+            encoder.plain_bytes
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_no_dashboard_serves_api_only() {
+        let routes = dashboard_routes(PeerStore::new(), false);
+        for page in ["/", "/overview", "/peer/aaaa", "/static/js/ws-manager.js"] {
+            let resp = warp::test::request().path(page).reply(&routes).await;
+            assert_eq!(resp.status(), 404, "{} should not be served", page);
+        }
+        for api in ["/api/stats", "/api/peers", "/api/overview"] {
+            let resp = warp::test::request().path(api).reply(&routes).await;
+            assert_eq!(resp.status(), 200, "{} should still be served", api);
+        }
+
+        let routes = dashboard_routes(PeerStore::new(), true);
+        let resp = warp::test::request().path("/").reply(&routes).await;
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_connections_csv_export() {
+        use crate::registry::{ConnectionMethod, ConnectivityMatrix};
+
+        let store = PeerStore::new();
+        let routes = dashboard_routes(Arc::clone(&store), false);
+        let resp = warp::test::request()
+            .path("/api/connections.csv")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers()["content-type"], "text/csv; charset=utf-8");
+        assert_eq!(
+            resp.body().as_ref(),
+            b"from_peer,to_peer,method,direction,rtt_ms,success\r\n"
+        );
+
+        let connectivity = ConnectivityMatrix {
+            ipv4_direct_tested: true,
+            ipv4_direct_rtt_ms: None,
+            nat_traversal_tested: true,
+            nat_traversal_success: true,
+            nat_traversal_rtt_ms: Some(42),
+            ..Default::default()
+        };
+        store
+            .record_connection(
+                "aaaa".to_string(),
+                "bb,\"b".to_string(),
+                ConnectionMethod::HolePunched,
+                false,
+                Some(42),
+                connectivity,
+            )
+            .await;
+        store
+            .record_connection(
+                "cccc".to_string(),
+                "aaaa".to_string(),
+                ConnectionMethod::Direct,
+                true,
+                Some(7),
+                ConnectivityMatrix::default(),
+            )
+            .await;
+
+        let resp = warp::test::request()
+            .path("/api/connections.csv")
+            .reply(&routes)
+            .await;
+        let body = String::from_utf8(resp.body().to_vec()).unwrap();
+        let rows: Vec<&str> = body.split_terminator("\r\n").collect();
+        assert_eq!(
+            rows,
+            vec![
+                ConnectionsResponse::CSV_HEADER,
+                "aaaa,\"bb,\"\"b\",direct_ipv4,outbound,,false",
+                "aaaa,\"bb,\"\"b\",nat_traversal,outbound,42,true",
+                "cccc,aaaa,direct,outbound,7,true",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_topology_geojson_export() {
+        use crate::registry::{
+            ConnectionMethod, ConnectivityMatrix, NatType, NodeCapabilities, NodeRegistration,
+        };
+
+        let store = PeerStore::new();
+        let registration = |peer_id: &str, external: &str| NodeRegistration {
+            peer_id: peer_id.to_string(),
+            public_key: "test_key".to_string(),
+            listen_addresses: vec![],
+            external_addresses: external.parse().into_iter().collect(),
+            nat_type: NatType::FullCone,
+            version: "0.14.1".to_string(),
+            capabilities: NodeCapabilities::default(),
+            location_label: None,
+        };
+        store
+            .register(registration("aaaa", "203.0.113.1:9000"))
+            .unwrap();
+        store
+            .register(registration("cccc", "203.0.113.2:9000"))
+            .unwrap();
+        // No address to geolocate: only an estimated position
+        store.register(registration("bbbb", "")).unwrap();
+
+        for (to, method, success) in [
+            ("cccc", ConnectionMethod::Direct, true),
+            ("bbbb", ConnectionMethod::Direct, true),
+        ] {
+            store
+                .record_connection(
+                    "aaaa".to_string(),
+                    to.to_string(),
+                    method,
+                    success,
+                    Some(10),
+                    ConnectivityMatrix::default(),
+                )
+                .await;
+        }
+
+        let routes = dashboard_routes(Arc::clone(&store), false);
+        let resp = warp::test::request()
+            .path("/api/topology.geojson")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.headers()["content-type"], "application/geo+json");
+
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["type"], "FeatureCollection");
+        let features = body["features"].as_array().unwrap();
+
+        let points: Vec<&str> = features
+            .iter()
+            .filter(|f| f["geometry"]["type"] == "Point")
+            .map(|f| f["properties"]["peer_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(points.len(), 2);
+        assert!(!points.contains(&"bbbb"));
+        assert!(features[0]["properties"].get("country_code").is_some());
+
+        let lines: Vec<_> = features
+            .iter()
+            .filter(|f| f["geometry"]["type"] == "LineString")
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["properties"]["to_peer"], "cccc");
+        assert_eq!(lines[0]["properties"]["color"], method_color("direct"));
+        assert_eq!(
+            lines[0]["geometry"]["coordinates"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_peer_timeline() {
+        use crate::registry::{
+            ConnectionMethod, ConnectivityMatrix, NatType, NodeCapabilities, NodeRegistration,
+        };
+
+        let store = PeerStore::new();
+        let routes = dashboard_routes(Arc::clone(&store), false);
+        store
+            .register(NodeRegistration {
+                peer_id: "AAAA".to_string(),
+                public_key: "test_key".to_string(),
+                listen_addresses: vec![],
+                external_addresses: vec![],
+                nat_type: NatType::FullCone,
+                version: "0.14.1".to_string(),
+                capabilities: NodeCapabilities::default(),
+                location_label: None,
+            })
+            .unwrap();
+        for (from, to, method) in [
+            ("aaaa", "bbbb", ConnectionMethod::Direct),
+            ("bbbb", "aaaa", ConnectionMethod::Relayed),
+            ("cccc", "dddd", ConnectionMethod::Direct),
+        ] {
+            store
+                .record_connection(
+                    from.to_string(),
+                    to.to_string(),
+                    method,
+                    false,
+                    Some(12),
+                    ConnectivityMatrix::default(),
+                )
+                .await;
         }
+        store.deregister("aaaa").unwrap();
+
+        let resp = warp::test::request()
+            .path("/api/peer/aaaa/timeline")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        let events = body["events"].as_array().unwrap();
+        let types: Vec<&str> = events.iter().map(|e| e["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            types,
+            [
+                "registered",
+                "connection_attempt",
+                "method_changed",
+                "connection_attempt",
+                "offline"
+            ]
+        );
+        assert_eq!(events[1]["direction"], "outbound");
+        assert_eq!(events[2]["remote_peer"], "bbbb");
+        assert_eq!(events[2]["from"], "direct");
+        assert_eq!(events[2]["to"], "relayed");
+        assert_eq!(events[3]["direction"], "inbound");
+        assert!(events[0]["utc"].as_str().unwrap().ends_with("+00:00"));
+        assert!(events[0]["seconds_ago"].as_u64().unwrap() <= 1);
+
+        let resp = warp::test::request()
+            .path("/api/peer/ffff/timeline")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
+
+    async fn recv_json(client: &mut warp::test::WsClient) -> serde_json::Value {
+        let msg = client.recv().await.expect("message");
+        serde_json::from_str(msg.to_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_websocket_sequences_and_resync() {
+        use crate::registry::{NatType, NodeCapabilities, NodeRegistration};
+
+        let store = PeerStore::new();
+        let routes = dashboard_routes(Arc::clone(&store), false);
+        let mut client = warp::test::ws()
+            .path("/ws/live")
+            .handshake(routes)
+            .await
+            .expect("handshake");
+
+        let initial = recv_json(&mut client).await;
+        assert_eq!(initial["type"], "full_state");
+        assert_eq!(initial["seq"], 0);
+
+        store
+            .register(NodeRegistration {
+                peer_id: "aaaa".to_string(),
+                public_key: "test_key".to_string(),
+                listen_addresses: vec![],
+                external_addresses: vec![],
+                nat_type: NatType::FullCone,
+                version: "0.14.1".to_string(),
+                capabilities: NodeCapabilities::default(),
+                location_label: None,
+            })
+            .unwrap();
+        let registered = recv_json(&mut client).await;
+        assert_eq!(registered["type"], "node_registered");
+        assert_eq!(registered["seq"], 1);
+        assert_eq!(recv_json(&mut client).await["seq"], 2);
+
+        // Missed events are replayed from the server's log
+        client.send_text(r#"{"type":"resync","since":0}"#).await;
+        let replayed = recv_json(&mut client).await;
+        assert_eq!(replayed["type"], "node_registered");
+        assert_eq!(replayed["seq"], 1);
+        assert_eq!(recv_json(&mut client).await["seq"], 2);
+
+        // A sequence the server never issued gets a fresh snapshot
+        client.send_text(r#"{"type":"resync","since":99}"#).await;
+        let snapshot = recv_json(&mut client).await;
+        assert_eq!(snapshot["type"], "full_state");
+        assert_eq!(snapshot["seq"], 2);
+        assert_eq!(snapshot["nodes"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_merge_patch() {
+        let old = serde_json::json!({
+            "total_nodes": 2,
+            "active_nodes": 2,
+            "breakdown": {"direct": 1, "relayed": 1},
+            "countries": {"DE": 1, "US": 1},
+        });
+        let new = serde_json::json!({
+            "total_nodes": 3,
+            "active_nodes": 2,
+            "breakdown": {"direct": 2, "relayed": 1},
+            "countries": {"DE": 1, "GB": 1},
+        });
+        assert_eq!(
+            merge_patch(&old, &new),
+            serde_json::json!({
+                "total_nodes": 3,
+                "breakdown": {"direct": 2},
+                "countries": {"GB": 1, "US": null},
+            })
+        );
+        assert_eq!(merge_patch(&new, &new), serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_frame_encoder_deltas_snapshots() {
+        let full_state = |seq: u64, nodes: serde_json::Value, total: u64| {
+            serde_json::json!({
+                "type": "full_state",
+                "seq": seq,
+                "nodes": nodes,
+                "stats": {"total_nodes": total, "uptime_secs": 10},
+            })
+        };
+        let mut encoder = FrameEncoder::new(&LiveOptions {
+            delta: true,
+            compress: None,
+        });
+        let decode = |frame: warp::ws::Message| -> serde_json::Value {
+            serde_json::from_str(frame.to_str().unwrap()).unwrap()
+        };
+
+        // The first snapshot goes out in full
+        let first = decode(encoder.encode(full_state(
+            1,
+            serde_json::json!([{"peer_id": "aaaa", "country_code": "DE"}]),
+            1,
+        )));
+        assert_eq!(first["nodes"].as_array().unwrap().len(), 1);
+        assert!(first.get("delta").is_none());
+
+        // Later ones only carry what changed
+        let second = decode(encoder.encode(full_state(
+            5,
+            serde_json::json!([
+                {"peer_id": "aaaa", "country_code": "DE"},
+                {"peer_id": "bbbb", "country_code": "US"},
+            ]),
+            2,
+        )));
+        assert_eq!(second["delta"], true);
+        assert_eq!(second["seq"], 5);
+        assert_eq!(
+            second["patch"],
+            serde_json::json!({
+                "nodes": {"bbbb": {"peer_id": "bbbb", "country_code": "US"}},
+                "stats": {"total_nodes": 2},
+            })
+        );
+
+        // Events are never rewritten
+        let event = serde_json::json!({"type": "node_offline", "seq": 6, "peer_id": "aaaa"});
+        assert_eq!(decode(encoder.encode(event.clone())), event);
+        assert!(encoder.saved_ratio().unwrap() > 0.0);
+
+        // Clients that ask for neither get plain messages and no ratio
+        let mut plain = FrameEncoder::new(&LiveOptions::default());
+        plain.encode(event);
+        assert!(plain.saved_ratio().is_none());
     }
 
-    forward_task.abort();
+    #[tokio::test]
+    async fn test_websocket_deflate_frames() {
+        use crate::registry::{NatType, NodeCapabilities, NodeRegistration};
+
+        async fn recv_inflated(client: &mut warp::test::WsClient) -> serde_json::Value {
+            let msg = client.recv().await.expect("message");
+            assert!(msg.is_binary());
+            let json = miniz_oxide::inflate::decompress_to_vec(msg.as_bytes()).expect("inflate");
+            serde_json::from_slice(&json).unwrap()
+        }
+
+        let store = PeerStore::new();
+        let routes = dashboard_routes(Arc::clone(&store), false);
+        let mut client = warp::test::ws()
+            .path("/ws/live?delta=true&compress=deflate")
+            .handshake(routes)
+            .await
+            .expect("handshake");
+
+        let initial = recv_inflated(&mut client).await;
+        assert_eq!(initial["type"], "full_state");
+        assert_eq!(initial["nodes"].as_array().unwrap().len(), 0);
+
+        store
+            .register(NodeRegistration {
+                peer_id: "aaaa".to_string(),
+                public_key: "test_key".to_string(),
+                listen_addresses: vec![],
+                external_addresses: vec![],
+                nat_type: NatType::FullCone,
+                version: "0.14.1".to_string(),
+                capabilities: NodeCapabilities::default(),
+                location_label: None,
+            })
+            .unwrap();
+        assert_eq!(recv_inflated(&mut client).await["seq"], 1);
+        assert_eq!(recv_inflated(&mut client).await["seq"], 2);
+
+        // A resync snapshot only carries the node added since the first one
+        client.send_text(r#"{"type":"resync","since":99}"#).await;
+        let snapshot = recv_inflated(&mut client).await;
+        assert_eq!(snapshot["type"], "full_state");
+        assert_eq!(snapshot["delta"], true);
+        assert_eq!(snapshot["seq"], 2);
+        assert_eq!(snapshot["patch"]["nodes"]["aaaa"]["peer_id"], "aaaa");
+    }
 }
@@ -3,7 +3,9 @@
 //! These types are designed for JSON serialization to the frontend,
 //! providing clean API contracts separate from internal TUI types.
 
+use crate::registry::{FailureReasonCode, MatrixCoverage, PeerInfo};
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 
 /// Overview page response containing proof status, network stats, and connected peers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,23 @@ pub struct OverviewResponse {
     pub local_node: LocalNodeApi,
     /// Uptime in seconds
     pub uptime_secs: u64,
+    /// Peers with no successful connections (need attention)
+    #[serde(default)]
+    pub isolated_peers: Vec<String>,
+    /// Network-wide failed connections by reason, most frequent first
+    #[serde(default)]
+    pub failure_reasons: Vec<FailureReasonApi>,
+}
+
+/// Count of failed connections with one classified reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureReasonApi {
+    /// Reason code (snake_case, e.g. "timeout")
+    pub reason: FailureReasonCode,
+    /// Human-readable reason
+    pub label: String,
+    /// Failures reported with this reason
+    pub count: u64,
 }
 
 /// Proof status for API consumption.
@@ -157,6 +176,208 @@ pub struct ConnectionsResponse {
     pub total_peers: usize,
     /// Currently connected count
     pub connected_count: usize,
+    /// Per-path outcomes between peer pairs, one per tested path
+    #[serde(default)]
+    pub edges: Vec<ConnectionEdgeApi>,
+    /// How many of the N×(N-1) ordered peer pairs have been tested
+    #[serde(default)]
+    pub coverage: MatrixCoverage,
+}
+
+impl ConnectionsResponse {
+    /// Header row written by [`ConnectionsResponse::to_csv`].
+    pub const CSV_HEADER: &'static str = "from_peer,to_peer,method,direction,rtt_ms,success";
+
+    /// Write the edges as RFC 4180 CSV: a header row, then one row per edge.
+    ///
+    /// An empty matrix produces the header only.
+    pub fn to_csv(&self, w: &mut impl Write) -> std::io::Result<()> {
+        write!(w, "{}\r\n", Self::CSV_HEADER)?;
+        for edge in &self.edges {
+            write!(
+                w,
+                "{},{},{},{},{},{}\r\n",
+                csv_field(&edge.from_peer),
+                csv_field(&edge.to_peer),
+                csv_field(&edge.method),
+                csv_field(&edge.direction),
+                edge.rtt_ms.map(|rtt| rtt.to_string()).unwrap_or_default(),
+                edge.success,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Globe line color for a connection path, matching the dashboard palette.
+pub fn method_color(method: &str) -> &'static str {
+    match method {
+        "direct_ipv4" | "direct_ipv6" | "direct" => "#00ff88",
+        "nat_traversal" | "hole_punched" => "#ffd700",
+        "relay" | "relayed" | "masque" => "#a855f7",
+        _ => "#00d9ff",
+    }
+}
+
+/// Build a GeoJSON FeatureCollection of the globe: one Point per geolocated
+/// peer and one LineString per successful edge between two such peers.
+///
+/// Peers whose position is only an estimate are left out, along with any
+/// edge touching them, so nothing is plotted at a made-up location.
+pub fn topology_geojson(peers: &[PeerInfo], edges: &[ConnectionEdgeApi]) -> serde_json::Value {
+    let located: std::collections::HashMap<&str, [f64; 2]> = peers
+        .iter()
+        .filter(|peer| !peer.location_estimated)
+        .map(|peer| (peer.peer_id.as_str(), [peer.longitude, peer.latitude]))
+        .collect();
+
+    let points = peers
+        .iter()
+        .filter(|peer| !peer.location_estimated)
+        .map(|peer| {
+            serde_json::json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [peer.longitude, peer.latitude],
+                },
+                "properties": {
+                    "peer_id": peer.peer_id,
+                    "country_code": peer.country_code,
+                },
+            })
+        });
+
+    let lines = edges.iter().filter(|edge| edge.success).filter_map(|edge| {
+        let from = located.get(edge.from_peer.as_str())?;
+        let to = located.get(edge.to_peer.as_str())?;
+        Some(serde_json::json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": [from, to],
+            },
+            "properties": {
+                "from_peer": edge.from_peer,
+                "to_peer": edge.to_peer,
+                "method": edge.method,
+                "rtt_ms": edge.rtt_ms,
+                "color": method_color(&edge.method),
+            },
+        }))
+    });
+
+    serde_json::json!({
+        "type": "FeatureCollection",
+        "features": points.chain(lines).collect::<Vec<_>>(),
+    })
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Outcome of one connection path between two peers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionEdgeApi {
+    /// Reporting peer ID
+    pub from_peer: String,
+    /// Remote peer ID
+    pub to_peer: String,
+    /// Path: "direct_ipv4", "direct_ipv6", "nat_traversal", "relay", "masque",
+    /// or the reported method when no per-path results were sent
+    pub method: String,
+    /// Direction: "outbound", "inbound", "simultaneous"
+    pub direction: String,
+    /// Path RTT in milliseconds
+    pub rtt_ms: Option<u64>,
+    /// Whether the path connected
+    pub success: bool,
+}
+
+/// Everything the registry knows happened to one peer, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerTimelineResponse {
+    /// Peer ID
+    pub peer_id: String,
+    /// Unix timestamp the timeline was built at (for relative times)
+    pub generated_at: u64,
+    /// Events in chronological order
+    pub events: Vec<PeerTimelineEventApi>,
+}
+
+/// One event on a peer's timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerTimelineEventApi {
+    /// Unix timestamp of the event
+    pub timestamp: u64,
+    /// The same time as RFC 3339 UTC
+    pub utc: String,
+    /// Seconds between the event and `generated_at`
+    pub seconds_ago: u64,
+    /// What happened
+    #[serde(flatten)]
+    pub kind: PeerTimelineKind,
+}
+
+/// Kind of peer timeline event, tagged by `type`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PeerTimelineKind {
+    /// Peer registered (or came back after going offline)
+    Registered,
+    /// One connection path tested between the peer and `remote_peer`
+    ConnectionAttempt {
+        remote_peer: String,
+        /// Path, as in [`ConnectionEdgeApi::method`]
+        method: String,
+        /// "outbound" when this peer reported it, "inbound" otherwise
+        direction: String,
+        success: bool,
+        rtt_ms: Option<u64>,
+    },
+    /// The method used to reach `remote_peer` differs from the previous connection
+    MethodChanged {
+        remote_peer: String,
+        from: String,
+        to: String,
+    },
+    /// Peer stopped heartbeating or deregistered
+    Offline,
+}
+
+/// Country pairs with fewer RTT samples than this report no median.
+pub const MIN_LATENCY_SAMPLES: usize = 3;
+
+/// Median RTT between countries, for the latency heatmap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyMatrixResponse {
+    /// Every country appearing in a cell, sorted
+    pub countries: Vec<String>,
+    /// One cell per (source, destination) pair with at least one sample
+    pub cells: Vec<LatencyCellApi>,
+}
+
+/// Aggregated RTT for connections from one country to another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LatencyCellApi {
+    /// Country code of the connecting peer
+    pub source_country: String,
+    /// Flag emoji for the source country
+    pub source_flag: String,
+    /// Country code of the remote peer (same as source on the diagonal)
+    pub dest_country: String,
+    /// Flag emoji for the destination country
+    pub dest_flag: String,
+    /// Median RTT in milliseconds, `None` below [`MIN_LATENCY_SAMPLES`]
+    pub median_rtt_ms: Option<u64>,
+    /// Number of connection reports with an RTT
+    pub samples: usize,
 }
 
 /// Single connection entry for the matrix view.
@@ -16,9 +16,18 @@
 //! - Connection timeouts to specific IPs
 //! - CRDT state divergence
 //! - Gossip message drops
+//!
+//! It can also raise an early warning when the network-wide connection
+//! success rate falls below [`DebuggerConfig::success_rate_threshold`].
+//!
+//! Reports render to Markdown with [`DebugReport::to_markdown`] and convert
+//! to a GitHub issue with [`DebugReport::to_issue_report`].
 
+use crate::lib_verification::issue_reporter::{EnvironmentInfo, IssueReport};
 use crate::registry::TestAnomaly;
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Configuration for automated debugging.
@@ -32,6 +41,10 @@ pub struct DebuggerConfig {
     pub error_patterns: Vec<ErrorPattern>,
     /// Minimum severity to report.
     pub min_severity: Severity,
+    /// Connection success rate (0.0-1.0) below which an anomaly is raised.
+    pub success_rate_threshold: f64,
+    /// Window over which the connection success rate is measured (ms).
+    pub success_rate_window_ms: u64,
 }
 
 impl Default for DebuggerConfig {
@@ -41,6 +54,8 @@ impl Default for DebuggerConfig {
             correlation_window_ms: 5000,
             error_patterns: default_error_patterns(),
             min_severity: Severity::Warning,
+            success_rate_threshold: 0.5,
+            success_rate_window_ms: 60_000,
         }
     }
 }
@@ -69,6 +84,36 @@ impl std::fmt::Display for Severity {
     }
 }
 
+/// Subsystem an anomaly points at, so findings can be triaged by area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnomalyCategory {
+    /// QUIC connection setup, timeouts and TLS failures.
+    Connectivity,
+    /// Gossip overlay membership and message delivery.
+    GossipPartition,
+    /// NAT traversal and hole punching.
+    NatFailure,
+    /// Registry heartbeats and peer list drift.
+    RegistryDesync,
+    /// CRDT replicas failing to converge.
+    CrdtDivergence,
+    /// Resource exhaustion, crashes and degraded throughput.
+    Performance,
+}
+
+impl std::fmt::Display for AnomalyCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connectivity => write!(f, "Connectivity"),
+            Self::GossipPartition => write!(f, "Gossip partition"),
+            Self::NatFailure => write!(f, "NAT failure"),
+            Self::RegistryDesync => write!(f, "Registry desync"),
+            Self::CrdtDivergence => write!(f, "CRDT divergence"),
+            Self::Performance => write!(f, "Performance"),
+        }
+    }
+}
+
 /// A pattern to detect in logs.
 #[derive(Debug, Clone)]
 pub struct ErrorPattern {
@@ -78,6 +123,8 @@ pub struct ErrorPattern {
     pub pattern: String,
     /// Severity when matched.
     pub severity: Severity,
+    /// Subsystem the pattern belongs to.
+    pub category: AnomalyCategory,
     /// Suggested cause.
     pub suggested_cause: String,
     /// Suggested fix.
@@ -90,6 +137,7 @@ impl ErrorPattern {
         name: impl Into<String>,
         pattern: impl Into<String>,
         severity: Severity,
+        category: AnomalyCategory,
         cause: impl Into<String>,
         fix: impl Into<String>,
     ) -> Self {
@@ -97,6 +145,7 @@ impl ErrorPattern {
             name: name.into(),
             pattern: pattern.into(),
             severity,
+            category,
             suggested_cause: cause.into(),
             suggested_fix: fix.into(),
         }
@@ -110,6 +159,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "zero_active_peers",
             "active peers|0 active|active_view_size: 0|active view: 0",
             Severity::Error,
+            AnomalyCategory::GossipPartition,
             "HyParView not bootstrapping - no initial peers or join failed",
             "Check registry connectivity, verify peer list is non-empty",
         ),
@@ -117,6 +167,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "address_accumulation",
             "too many addresses|address overflow|addresses accumulated",
             Severity::Warning,
+            AnomalyCategory::Performance,
             "Address accumulation - peers not pruning stale addresses",
             "Check address TTL settings, verify cleanup task is running",
         ),
@@ -124,6 +175,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "connection_timeout",
             "connection timeout|timed out|ConnectTimeout|connect failed",
             Severity::Warning,
+            AnomalyCategory::Connectivity,
             "Connection timeouts - network issues or firewall blocking",
             "Check firewall rules, verify QUIC ports are open (UDP)",
         ),
@@ -131,6 +183,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "state_divergence",
             "divergent state|state mismatch|convergence failed|not converged",
             Severity::Critical,
+            AnomalyCategory::CrdtDivergence,
             "CRDT state divergence - nodes not converging",
             "Check vector clock sync, verify gossip message delivery",
         ),
@@ -138,6 +191,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "gossip_drop",
             "message dropped|gossip failed|broadcast error|delivery failed",
             Severity::Warning,
+            AnomalyCategory::GossipPartition,
             "Gossip messages being dropped",
             "Check message queue sizes, verify network bandwidth",
         ),
@@ -145,6 +199,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "memory_pressure",
             "out of memory|OOM|memory exhausted|allocation failed",
             Severity::Critical,
+            AnomalyCategory::Performance,
             "Memory exhaustion - likely a leak or unbounded growth",
             "Check for unbounded collections, profile memory usage",
         ),
@@ -152,6 +207,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "certificate_error",
             "certificate invalid|cert error|TLS failed|handshake failed",
             Severity::Error,
+            AnomalyCategory::Connectivity,
             "Certificate/TLS issues - likely expired or misconfigured",
             "Check certificate dates, verify crypto configuration",
         ),
@@ -159,6 +215,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "panic",
             "panic|PANIC|panicked|unwrap failed",
             Severity::Critical,
+            AnomalyCategory::Performance,
             "Code panic - unexpected error condition",
             "Check stack trace for source location",
         ),
@@ -166,6 +223,7 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "swim_false_positive",
             "false positive|incorrectly marked dead|alive but dead",
             Severity::Warning,
+            AnomalyCategory::GossipPartition,
             "SWIM false positive - live node marked as dead",
             "Check SWIM timeout settings, reduce suspicion threshold",
         ),
@@ -173,9 +231,18 @@ fn default_error_patterns() -> Vec<ErrorPattern> {
             "nat_traversal_failed",
             "NAT traversal failed|hole punch failed|relay required",
             Severity::Warning,
+            AnomalyCategory::NatFailure,
             "NAT traversal failing - fallback to relay needed",
             "Check relay availability, verify STUN server connectivity",
         ),
+        ErrorPattern::new(
+            "registry_desync",
+            "heartbeat failed|registry unreachable|registry sync failed|unknown to registry",
+            Severity::Warning,
+            AnomalyCategory::RegistryDesync,
+            "Registry view out of sync - heartbeats not reaching the registry",
+            "Check registry URL and reachability, verify heartbeat interval",
+        ),
     ]
 }
 
@@ -335,6 +402,8 @@ pub enum EventType {
 pub struct Anomaly {
     /// Severity of the anomaly.
     pub severity: Severity,
+    /// Subsystem the anomaly points at.
+    pub category: AnomalyCategory,
     /// Pattern that matched.
     pub pattern_name: String,
     /// Node where detected.
@@ -371,6 +440,51 @@ impl Anomaly {
     }
 }
 
+/// Outcome of one connection attempt, sampled for success-rate alerting.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionOutcome {
+    /// When the attempt finished.
+    pub timestamp: SystemTime,
+    /// Whether the connection was established.
+    pub success: bool,
+}
+
+/// How the success rate moved since the previous window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuccessRateTrend {
+    /// Higher than the previous window.
+    Improving,
+    /// Lower than the previous window.
+    Degrading,
+    /// Same as the previous window.
+    Steady,
+}
+
+impl std::fmt::Display for SuccessRateTrend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Improving => write!(f, "improving"),
+            Self::Degrading => write!(f, "degrading"),
+            Self::Steady => write!(f, "steady"),
+        }
+    }
+}
+
+/// Raised when the connection success rate falls below the threshold.
+#[derive(Debug, Clone)]
+pub struct SuccessRateAlert {
+    /// Success rate (0.0-1.0) in the latest window.
+    pub rate: f64,
+    /// Success rate in the window before it, if it had any attempts.
+    pub previous_rate: Option<f64>,
+    /// Direction of travel, if there is a previous window to compare.
+    pub trend: Option<SuccessRateTrend>,
+    /// The anomaly to report.
+    pub anomaly: Anomaly,
+    /// What to do about it.
+    pub fix: SuggestedFix,
+}
+
 /// Root cause analysis result.
 #[derive(Debug, Clone)]
 pub struct RootCause {
@@ -408,6 +522,18 @@ pub struct CodeLocation {
     pub function: Option<String>,
 }
 
+/// Timeline rows rendered in Markdown; the rest are summarised.
+const MARKDOWN_TIMELINE_ROWS: usize = 50;
+
+/// Most recent raw log lines included in the collapsible Markdown section.
+const MARKDOWN_RAW_LOG_LINES: usize = 200;
+
+/// Environment context key for the node version.
+pub const NODE_VERSION_CONTEXT: &str = "Node version";
+
+/// Environment context key for the NAT type.
+pub const NAT_TYPE_CONTEXT: &str = "NAT type";
+
 /// Complete debug report.
 #[derive(Debug, Clone)]
 pub struct DebugReport {
@@ -425,6 +551,279 @@ pub struct DebugReport {
     pub suggested_fixes: Vec<SuggestedFix>,
     /// Summary statistics.
     pub stats: DebugStats,
+    /// Where the investigation ran, including the NAT types recorded with
+    /// [`AutomatedDebugger::record_nat_type`].
+    pub environment: EnvironmentInfo,
+}
+
+impl DebugReport {
+    /// Anomalies grouped by subsystem, in category order.
+    pub fn by_category(&self) -> BTreeMap<AnomalyCategory, Vec<&Anomaly>> {
+        let mut grouped: BTreeMap<AnomalyCategory, Vec<&Anomaly>> = BTreeMap::new();
+        for anomaly in &self.anomalies {
+            grouped.entry(anomaly.category).or_default().push(anomaly);
+        }
+        grouped
+    }
+
+    /// Anomalies grouped by severity, most severe first.
+    pub fn by_severity(&self) -> Vec<(Severity, Vec<&Anomaly>)> {
+        let mut grouped: BTreeMap<Severity, Vec<&Anomaly>> = BTreeMap::new();
+        for anomaly in &self.anomalies {
+            grouped.entry(anomaly.severity).or_default().push(anomaly);
+        }
+        grouped.into_iter().rev().collect()
+    }
+
+    /// Library the most severe anomaly points at, for routing the issue.
+    pub fn suspected_library(&self) -> &'static str {
+        let category = self
+            .anomalies
+            .iter()
+            .max_by_key(|a| a.severity)
+            .map(|a| a.category);
+        match category {
+            Some(AnomalyCategory::GossipPartition | AnomalyCategory::CrdtDivergence) => {
+                "saorsa-gossip"
+            }
+            Some(AnomalyCategory::Connectivity | AnomalyCategory::NatFailure) => "ant-quic",
+            _ => "saorsa-quic-test",
+        }
+    }
+
+    /// Render as a GitHub issue body: environment, root cause, fixes,
+    /// anomalies by severity, a timeline table and collapsed raw logs.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        // Writing to a String cannot fail
+        let _ = self.write_markdown(&mut md);
+        md
+    }
+
+    fn write_markdown(&self, md: &mut String) -> std::fmt::Result {
+        let env = &self.environment;
+        let context = |key: &str| {
+            env.additional
+                .get(key)
+                .map(String::as_str)
+                .unwrap_or("unknown")
+        };
+
+        writeln!(md, "## Automated debug report")?;
+        writeln!(md)?;
+        writeln!(
+            md,
+            "Investigated {} log lines from {} nodes spanning {} ms ({}).",
+            self.stats.log_lines_analyzed,
+            self.stats.nodes_examined,
+            self.stats.time_span_ms,
+            utc(self.completed_at).format("%Y-%m-%d %H:%M:%S UTC")
+        )?;
+        writeln!(md)?;
+
+        writeln!(md, "### Environment")?;
+        writeln!(md)?;
+        writeln!(
+            md,
+            "- **{}**: {}",
+            NODE_VERSION_CONTEXT,
+            context(NODE_VERSION_CONTEXT)
+        )?;
+        writeln!(
+            md,
+            "- **{}**: {}",
+            NAT_TYPE_CONTEXT,
+            context(NAT_TYPE_CONTEXT)
+        )?;
+        writeln!(md, "- **OS**: {} {}", env.os, env.os_version)?;
+        if !env.rust_version.is_empty() {
+            writeln!(md, "- **Rust**: {}", env.rust_version)?;
+        }
+        if let Some(commit) = &env.commit_hash {
+            writeln!(md, "- **Commit**: {}", commit)?;
+        }
+        let extra: BTreeMap<_, _> = env
+            .additional
+            .iter()
+            .filter(|(k, _)| *k != NODE_VERSION_CONTEXT && *k != NAT_TYPE_CONTEXT)
+            .collect();
+        for (key, value) in extra {
+            writeln!(md, "- **{}**: {}", key, value)?;
+        }
+        writeln!(md)?;
+
+        writeln!(md, "### Root cause")?;
+        writeln!(md)?;
+        match &self.root_cause {
+            Some(root_cause) => {
+                writeln!(
+                    md,
+                    "**{}** (confidence {:.0}%)",
+                    root_cause.primary_cause,
+                    root_cause.confidence * 100.0
+                )?;
+                writeln!(md)?;
+                for evidence in &root_cause.evidence {
+                    writeln!(md, "- {}", evidence)?;
+                }
+                if !root_cause.alternatives.is_empty() {
+                    writeln!(md)?;
+                    writeln!(md, "Alternatives:")?;
+                    for (cause, confidence) in &root_cause.alternatives {
+                        writeln!(md, "- {} ({:.0}%)", cause, confidence * 100.0)?;
+                    }
+                }
+            }
+            None => writeln!(md, "No root cause identified.")?,
+        }
+        writeln!(md)?;
+
+        if !self.suggested_fixes.is_empty() {
+            writeln!(md, "### Suggested fixes")?;
+            writeln!(md)?;
+            writeln!(md, "| Priority | Component | Fix | Location |")?;
+            writeln!(md, "|---|---|---|---|")?;
+            for fix in &self.suggested_fixes {
+                let location = fix
+                    .code_location
+                    .as_ref()
+                    .map(|loc| match loc.line {
+                        Some(line) => format!("`{}:{}`", loc.file, line),
+                        None => format!("`{}`", loc.file),
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                writeln!(
+                    md,
+                    "| {} | {} | {} | {} |",
+                    fix.priority,
+                    cell(&fix.component),
+                    cell(&fix.description),
+                    location
+                )?;
+            }
+            writeln!(md)?;
+        }
+
+        writeln!(md, "### Anomalies")?;
+        writeln!(md)?;
+        let by_severity = self.by_severity();
+        if by_severity.is_empty() {
+            writeln!(md, "None detected.")?;
+            writeln!(md)?;
+        }
+        for (severity, anomalies) in by_severity {
+            writeln!(md, "#### {} ({})", severity, anomalies.len())?;
+            writeln!(md)?;
+            writeln!(md, "| Node | Category | Pattern | Message | Likely cause |")?;
+            writeln!(md, "|---|---|---|---|---|")?;
+            for anomaly in anomalies {
+                writeln!(
+                    md,
+                    "| {} | {} | {} | {} | {} |",
+                    cell(&anomaly.node_id),
+                    anomaly.category,
+                    cell(&anomaly.pattern_name),
+                    cell(&anomaly.message),
+                    cell(&anomaly.suggested_cause)
+                )?;
+            }
+            writeln!(md)?;
+        }
+
+        let events = self.timeline.events();
+        writeln!(md, "### Timeline")?;
+        writeln!(md)?;
+        if events.is_empty() {
+            writeln!(md, "No events recorded.")?;
+        } else {
+            writeln!(md, "| Time (UTC) | Node | Event | Description |")?;
+            writeln!(md, "|---|---|---|---|")?;
+            for event in events.iter().take(MARKDOWN_TIMELINE_ROWS) {
+                writeln!(
+                    md,
+                    "| {} | {} | {:?} | {} |",
+                    utc(event.timestamp).format("%H:%M:%S%.3f"),
+                    cell(&event.node_id),
+                    event.event_type,
+                    cell(&event.description)
+                )?;
+            }
+            if events.len() > MARKDOWN_TIMELINE_ROWS {
+                writeln!(md)?;
+                writeln!(
+                    md,
+                    "_{} more events omitted._",
+                    events.len() - MARKDOWN_TIMELINE_ROWS
+                )?;
+            }
+        }
+        writeln!(md)?;
+
+        let logs: Vec<&LogEntry> = events.iter().flat_map(|e| &e.log_entries).collect();
+        let shown = &logs[logs.len().saturating_sub(MARKDOWN_RAW_LOG_LINES)..];
+        writeln!(md, "<details>")?;
+        if shown.len() < logs.len() {
+            writeln!(
+                md,
+                "<summary>Raw logs (last {} of {} lines)</summary>",
+                shown.len(),
+                logs.len()
+            )?;
+        } else {
+            writeln!(md, "<summary>Raw logs ({} lines)</summary>", logs.len())?;
+        }
+        writeln!(md)?;
+        writeln!(md, "```text")?;
+        for log in shown {
+            // A stray fence in a message would close the code block early
+            writeln!(
+                md,
+                "{} {} {} {}",
+                utc(log.timestamp).format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+                log.node_id,
+                log.level,
+                log.message.replace("```", "` ` `")
+            )?;
+        }
+        writeln!(md, "```")?;
+        writeln!(md)?;
+        writeln!(md, "</details>")?;
+        Ok(())
+    }
+
+    /// Turn this report into an issue against [`Self::suspected_library`],
+    /// ready to print or file with [`IssueReport::create_if_new`].
+    pub fn to_issue_report(&self) -> IssueReport {
+        let title = match &self.root_cause {
+            Some(root_cause) => format!("Automated debug: {}", root_cause.primary_cause),
+            None => format!(
+                "Automated debug: {} anomalies across {} nodes",
+                self.anomalies.len(),
+                self.stats.nodes_examined
+            ),
+        };
+        let mut issue = IssueReport::builder(self.suspected_library())
+            .title(&title)
+            .test_name("automated_debugger")
+            .body(&self.to_markdown())
+            .labels(&["bug", "automated-debug"])
+            .build();
+        issue
+            .environment
+            .additional
+            .extend(self.environment.additional.clone());
+        issue.environment.commit_hash = self.environment.commit_hash.clone();
+        issue
+    }
+}
+
+fn utc(time: SystemTime) -> DateTime<Utc> {
+    DateTime::<Utc>::from(time)
+}
+
+/// Make text safe for a single Markdown table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
 }
 
 /// Statistics from the debug investigation.
@@ -444,6 +843,8 @@ pub struct DebugStats {
 pub struct AutomatedDebugger {
     config: DebuggerConfig,
     logs: Vec<LogEntry>,
+    outcomes: Vec<ConnectionOutcome>,
+    nat_types: BTreeMap<String, String>,
 }
 
 impl AutomatedDebugger {
@@ -457,6 +858,8 @@ impl AutomatedDebugger {
         Self {
             config,
             logs: Vec::new(),
+            outcomes: Vec::new(),
+            nat_types: BTreeMap::new(),
         }
     }
 
@@ -490,6 +893,49 @@ impl AutomatedDebugger {
         }
     }
 
+    /// Add connection attempt outcomes for success-rate alerting.
+    pub fn add_outcomes(&mut self, outcomes: impl IntoIterator<Item = ConnectionOutcome>) {
+        self.outcomes.extend(outcomes);
+    }
+
+    /// Record a node's NAT classification for the report environment.
+    pub fn record_nat_type(&mut self, node_id: impl Into<String>, nat_type: impl Into<String>) {
+        self.nat_types.insert(node_id.into(), nat_type.into());
+    }
+
+    /// NAT types of the nodes with anomalies, or of every recorded node
+    /// when none of those are known. One type is shown bare, a mix as
+    /// per-type node counts.
+    fn nat_type_summary(&self, anomalies: &[Anomaly]) -> Option<String> {
+        let anomalous: Vec<&String> = anomalies
+            .iter()
+            .filter_map(|a| self.nat_types.get(&a.node_id))
+            .collect();
+        let mut counts: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        if anomalous.is_empty() {
+            for (node, nat_type) in &self.nat_types {
+                counts.entry(nat_type).or_default().insert(node);
+            }
+        } else {
+            for anomaly in anomalies {
+                if let Some(nat_type) = self.nat_types.get(&anomaly.node_id) {
+                    counts.entry(nat_type).or_default().insert(&anomaly.node_id);
+                }
+            }
+        }
+        match counts.len() {
+            0 => None,
+            1 => counts.keys().next().map(|t| t.to_string()),
+            _ => Some(
+                counts
+                    .iter()
+                    .map(|(nat_type, nodes)| format!("{} ({})", nat_type, nodes.len()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        }
+    }
+
     /// Parse a raw log line.
     pub fn parse_log_line(node_id: &str, line: &str) -> Option<LogEntry> {
         // Common log format: [timestamp] [level] message
@@ -573,6 +1019,7 @@ impl AutomatedDebugger {
                 {
                     anomalies.push(Anomaly {
                         severity: error_pattern.severity,
+                        category: error_pattern.category,
                         pattern_name: error_pattern.name.clone(),
                         node_id: log.node_id.clone(),
                         timestamp: log.timestamp,
@@ -704,12 +1151,104 @@ impl AutomatedDebugger {
         suggestions
     }
 
+    /// Check the connection success rate over the latest window of
+    /// `outcomes`, ending at the newest outcome.
+    ///
+    /// Returns an alert when the rate is below
+    /// [`DebuggerConfig::success_rate_threshold`], with the trend against
+    /// the window before it. Returns `None` when there are no outcomes.
+    pub fn check_success_rate(&self, outcomes: &[ConnectionOutcome]) -> Option<SuccessRateAlert> {
+        let window = self.config.success_rate_window_ms;
+        let timestamp_ms = |outcome: &ConnectionOutcome| {
+            outcome
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0)
+        };
+        let newest = outcomes.iter().max_by_key(|o| timestamp_ms(o))?;
+        let end_ms = timestamp_ms(newest);
+
+        // Windows are (end - window, end] and the one before it
+        let rate_in = |windows_back: u64| {
+            let upper = end_ms.saturating_sub(windows_back * window);
+            let lower = end_ms.saturating_sub((windows_back + 1) * window);
+            let (total, successes) = outcomes
+                .iter()
+                .filter(|o| {
+                    let ts = timestamp_ms(o);
+                    ts > lower && ts <= upper
+                })
+                .fold((0usize, 0usize), |(total, successes), o| {
+                    (total + 1, successes + usize::from(o.success))
+                });
+            (total > 0).then(|| (successes as f64 / total as f64, successes, total))
+        };
+
+        let (rate, successes, total) = rate_in(0)?;
+        let threshold = self.config.success_rate_threshold;
+        if rate >= threshold {
+            return None;
+        }
+        let previous_rate = rate_in(1).map(|(rate, _, _)| rate);
+        let trend = previous_rate.map(|previous| {
+            if rate > previous {
+                SuccessRateTrend::Improving
+            } else if rate < previous {
+                SuccessRateTrend::Degrading
+            } else {
+                SuccessRateTrend::Steady
+            }
+        });
+
+        let mut message = format!(
+            "Connection success rate {:.1}% ({}/{}) below {:.1}% threshold",
+            rate * 100.0,
+            successes,
+            total,
+            threshold * 100.0
+        );
+        if let (Some(trend), Some(previous)) = (trend, previous_rate) {
+            message.push_str(&format!(", {} from {:.1}%", trend, previous * 100.0));
+        }
+        let suggested_fix =
+            "Check NAT traversal and relay availability on the failing nodes".to_string();
+        let anomaly = Anomaly {
+            severity: Severity::Error,
+            category: AnomalyCategory::Connectivity,
+            pattern_name: "low_success_rate".to_string(),
+            node_id: "network".to_string(),
+            timestamp: newest.timestamp,
+            message,
+            suggested_cause: "Network-wide connection failures are increasing".to_string(),
+            suggested_fix: suggested_fix.clone(),
+            related: Vec::new(),
+        };
+        let fix = SuggestedFix {
+            description: suggested_fix,
+            priority: 75,
+            component: anomaly.pattern_name.clone(),
+            code_location: None,
+        };
+
+        Some(SuccessRateAlert {
+            rate,
+            previous_rate,
+            trend,
+            anomaly,
+            fix,
+        })
+    }
+
     /// Run complete investigation and generate report.
     pub fn investigate(&self) -> DebugReport {
         let started_at = SystemTime::now();
 
         let timeline = self.build_timeline();
-        let anomalies = self.detect_anomalies();
+        let mut anomalies = self.detect_anomalies();
+        if let Some(alert) = self.check_success_rate(&self.outcomes) {
+            anomalies.push(alert.anomaly);
+        }
         let root_cause = self.identify_root_cause(&anomalies);
         let suggested_fixes = self.generate_suggestions(&anomalies);
 
@@ -751,6 +1290,15 @@ impl AutomatedDebugger {
             stats.time_span_ms = max_ts.saturating_sub(min_ts);
         }
 
+        let mut environment = EnvironmentInfo {
+            os: std::env::consts::OS.to_string(),
+            ..Default::default()
+        };
+        environment.add_context(NODE_VERSION_CONTEXT, env!("CARGO_PKG_VERSION"));
+        if let Some(nat_type) = self.nat_type_summary(&anomalies) {
+            environment.add_context(NAT_TYPE_CONTEXT, &nat_type);
+        }
+
         DebugReport {
             started_at,
             completed_at: SystemTime::now(),
@@ -759,12 +1307,15 @@ impl AutomatedDebugger {
             root_cause,
             suggested_fixes,
             stats,
+            environment,
         }
     }
 
-    /// Clear all collected logs.
+    /// Clear all collected logs, outcomes and NAT types.
     pub fn clear(&mut self) {
         self.logs.clear();
+        self.outcomes.clear();
+        self.nat_types.clear();
     }
 }
 
@@ -804,6 +1355,15 @@ impl std::fmt::Display for DebugReport {
         }
         writeln!(f)?;
 
+        let by_category = self.by_category();
+        if !by_category.is_empty() {
+            writeln!(f, "Anomalies by Category:")?;
+            for (category, anomalies) in &by_category {
+                writeln!(f, "  {}: {}", category, anomalies.len())?;
+            }
+            writeln!(f)?;
+        }
+
         if let Some(ref root_cause) = self.root_cause {
             writeln!(
                 f,
@@ -888,6 +1448,155 @@ mod tests {
         println!("{}", report);
     }
 
+    #[test]
+    fn test_anomalies_grouped_by_category() {
+        let mut debugger = AutomatedDebugger::new();
+        debugger.add_logs(vec![
+            LogEntry::new("node1", SystemTime::now(), "WARN", "connection timeout"),
+            LogEntry::new("node2", SystemTime::now(), "WARN", "hole punch failed"),
+            LogEntry::new("node3", SystemTime::now(), "WARN", "connect failed"),
+            LogEntry::new("node3", SystemTime::now(), "WARN", "heartbeat failed"),
+        ]);
+
+        let report = debugger.investigate();
+        let grouped = report.by_category();
+        assert_eq!(grouped[&AnomalyCategory::Connectivity].len(), 2);
+        assert_eq!(grouped[&AnomalyCategory::NatFailure].len(), 1);
+        assert_eq!(grouped[&AnomalyCategory::RegistryDesync].len(), 1);
+        assert!(!grouped.contains_key(&AnomalyCategory::CrdtDivergence));
+
+        let printed = report.to_string();
+        assert!(printed.contains("Anomalies by Category:"));
+        assert!(printed.contains("  Connectivity: 2"));
+        assert!(printed.contains("  NAT failure: 1"));
+    }
+
+    #[test]
+    fn test_markdown_issue_export() {
+        let mut debugger = AutomatedDebugger::new();
+        debugger.add_logs(vec![
+            LogEntry::new("node1", SystemTime::now(), "INFO", "node starting"),
+            LogEntry::new("node1", SystemTime::now(), "WARN", "connection timeout"),
+            LogEntry::new(
+                "node2",
+                SystemTime::now(),
+                "ERROR",
+                "divergent state | retry",
+            ),
+        ]);
+        debugger.record_nat_type("node1", "Symmetric");
+        debugger.record_nat_type("node2", "Symmetric");
+        // Nodes without anomalies don't count towards the NAT type
+        debugger.record_nat_type("node3", "Full Cone");
+
+        let report = debugger.investigate();
+        let md = report.to_markdown();
+
+        assert!(md.contains(&format!(
+            "- **Node version**: {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(md.contains("- **NAT type**: Symmetric"));
+        // Most severe group first, pipes escaped inside table cells
+        let critical = md.find("#### CRITICAL (1)").unwrap();
+        let warn = md.find("#### WARN (1)").unwrap();
+        assert!(critical < warn);
+        assert!(md.contains(
+            "| node2 | CRDT divergence | state_divergence | divergent state \\| retry |"
+        ));
+        assert!(md.contains("| Time (UTC) | Node | Event | Description |"));
+        assert!(md.contains("<summary>Raw logs (3 lines)</summary>"));
+        assert!(md.contains("### Suggested fixes"));
+
+        // The critical CRDT finding routes the issue to saorsa-gossip
+        let issue = report.to_issue_report();
+        assert_eq!(issue.library, "saorsa-gossip");
+        assert_eq!(issue.github_repo(), Some("dirvine/saorsa-gossip"));
+        assert!(issue.title.starts_with("Automated debug: "));
+        assert_eq!(issue.body, md);
+        assert_eq!(issue.environment.additional[NAT_TYPE_CONTEXT], "Symmetric");
+        assert!(issue.to_github_markdown().contains("### Timeline"));
+
+        debugger.record_nat_type("node2", "Full Cone");
+        let mixed = debugger.investigate();
+        assert_eq!(
+            mixed.environment.additional[NAT_TYPE_CONTEXT],
+            "Full Cone (1), Symmetric (1)"
+        );
+
+        let empty = AutomatedDebugger::new().investigate();
+        assert_eq!(empty.suspected_library(), "saorsa-quic-test");
+        assert!(empty.to_markdown().contains("No root cause identified."));
+        assert!(empty.to_markdown().contains("- **NAT type**: unknown"));
+    }
+
+    #[test]
+    fn test_success_rate_alert() {
+        let debugger = AutomatedDebugger::with_config(DebuggerConfig {
+            success_rate_threshold: 0.6,
+            success_rate_window_ms: 1000,
+            ..Default::default()
+        });
+        let start = UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        let sample = |previous: &[bool], latest: &[bool]| {
+            let at = |ms| start + std::time::Duration::from_millis(ms);
+            previous
+                .iter()
+                .map(|&success| ConnectionOutcome {
+                    timestamp: at(500),
+                    success,
+                })
+                .chain(latest.iter().map(|&success| ConnectionOutcome {
+                    timestamp: at(1500),
+                    success,
+                }))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(debugger.check_success_rate(&[]).is_none());
+        assert!(
+            debugger
+                .check_success_rate(&sample(&[], &[true, true, false]))
+                .is_none()
+        );
+
+        let alert = debugger
+            .check_success_rate(&sample(&[true, true, false], &[true, false, false]))
+            .unwrap();
+        assert!((alert.rate - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(alert.trend, Some(SuccessRateTrend::Degrading));
+        assert_eq!(alert.anomaly.severity, Severity::Error);
+        assert_eq!(alert.anomaly.category, AnomalyCategory::Connectivity);
+        assert!(alert.anomaly.message.contains("degrading from 66.7%"));
+        assert_eq!(alert.fix.component, "low_success_rate");
+
+        let alert = debugger
+            .check_success_rate(&sample(&[false, false], &[true, false]))
+            .unwrap();
+        assert_eq!(alert.trend, Some(SuccessRateTrend::Improving));
+
+        let alert = debugger.check_success_rate(&sample(&[], &[false])).unwrap();
+        assert_eq!(alert.previous_rate, None);
+        assert_eq!(alert.trend, None);
+
+        // Recorded outcomes surface in the investigation report
+        let mut debugger = debugger;
+        debugger.add_outcomes(sample(&[true, true, false], &[true, false, false]));
+        let report = debugger.investigate();
+        assert!(
+            report
+                .anomalies
+                .iter()
+                .any(|a| a.pattern_name == "low_success_rate")
+        );
+        assert!(
+            report
+                .suggested_fixes
+                .iter()
+                .any(|f| f.component == "low_success_rate")
+        );
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Critical > Severity::Error);
@@ -0,0 +1,153 @@
+//! Node diagnostics.
+//!
+//! Checks that help explain unexpected network behaviour without attaching
+//! a debugger:
+//! - Gossip-vs-registry peer-set consistency: in gossip-first mode the peers
+//!   learned via gossip should roughly match the registry's view. Peers that
+//!   only one side knows about point at propagation or registration problems.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::gossip::load_cached_peer_ids;
+use crate::node::get_data_dir;
+use crate::registry::RegistryClient;
+
+/// Difference between the gossip-discovered and registry peer sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerSetDiff {
+    /// Peers known via gossip but not registered.
+    pub only_in_gossip: Vec<String>,
+    /// Peers registered but not (yet) seen via gossip.
+    pub only_in_registry: Vec<String>,
+    /// Number of peers present in both sets.
+    pub in_both: usize,
+}
+
+impl PeerSetDiff {
+    /// Compare two peer sets, ignoring `exclude` (normally our own peer ID).
+    ///
+    /// Output lists are sorted so the report is stable between runs.
+    pub fn compute<G, R>(gossip: G, registry: R, exclude: Option<&str>) -> Self
+    where
+        G: IntoIterator,
+        G::Item: AsRef<str>,
+        R: IntoIterator,
+        R::Item: AsRef<str>,
+    {
+        let keep = |id: &String| Some(id.as_str()) != exclude;
+        let gossip: BTreeSet<String> = gossip
+            .into_iter()
+            .map(|id| id.as_ref().to_string())
+            .filter(keep)
+            .collect();
+        let registry: BTreeSet<String> = registry
+            .into_iter()
+            .map(|id| id.as_ref().to_string())
+            .filter(keep)
+            .collect();
+
+        Self {
+            only_in_gossip: gossip.difference(&registry).cloned().collect(),
+            only_in_registry: registry.difference(&gossip).cloned().collect(),
+            in_both: gossip.intersection(&registry).count(),
+        }
+    }
+
+    /// True when both sides agree on the peer set.
+    pub fn is_consistent(&self) -> bool {
+        self.only_in_gossip.is_empty() && self.only_in_registry.is_empty()
+    }
+
+    /// One-line summary suitable for a `TuiEvent::Info` message.
+    pub fn summary(&self) -> String {
+        if self.is_consistent() {
+            format!(
+                "Peer sets consistent: {} peers in gossip and registry",
+                self.in_both
+            )
+        } else {
+            format!(
+                "Peer set mismatch: {} shared, {} gossip-only [{}], {} registry-only [{}]",
+                self.in_both,
+                self.only_in_gossip.len(),
+                short_ids(&self.only_in_gossip),
+                self.only_in_registry.len(),
+                short_ids(&self.only_in_registry),
+            )
+        }
+    }
+}
+
+/// Abbreviate a list of peer IDs for display (first 8 chars, at most 5 IDs).
+fn short_ids(ids: &[String]) -> String {
+    const MAX_SHOWN: usize = 5;
+    let mut shown: Vec<&str> = ids
+        .iter()
+        .take(MAX_SHOWN)
+        .map(|id| &id[..8.min(id.len())])
+        .collect();
+    let more = ids.len().saturating_sub(MAX_SHOWN);
+    let more_label = format!("+{} more", more);
+    if more > 0 {
+        shown.push(&more_label);
+    }
+    shown.join(", ")
+}
+
+/// Compare the persisted gossip peer cache against the registry.
+///
+/// Used by the `diagnose` CLI subcommand, which runs without a live node and
+/// therefore relies on the cache a node saves on shutdown. `data_dir` defaults
+/// to the same platform directory the node uses.
+pub async fn diagnose_peer_sets(
+    registry_url: &str,
+    data_dir: Option<&PathBuf>,
+) -> anyhow::Result<PeerSetDiff> {
+    let cache_path = get_data_dir(data_dir).join("peer_cache.cbor");
+    let gossip_ids = load_cached_peer_ids(&cache_path)?;
+    let registry_peers = RegistryClient::new(registry_url).get_peers().await?;
+    Ok(PeerSetDiff::compute(
+        gossip_ids,
+        registry_peers.iter().map(|p| p.peer_id.as_str()),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symmetric_difference() {
+        let gossip = ["aaaa", "bbbb", "cccc", "self"];
+        let registry = ["bbbb", "cccc", "dddd", "eeee", "self"];
+
+        let diff = PeerSetDiff::compute(gossip, registry, Some("self"));
+
+        assert_eq!(diff.only_in_gossip, vec!["aaaa".to_string()]);
+        assert_eq!(
+            diff.only_in_registry,
+            vec!["dddd".to_string(), "eeee".to_string()]
+        );
+        assert_eq!(diff.in_both, 2);
+        assert!(!diff.is_consistent());
+        assert!(diff.summary().contains("1 gossip-only"));
+        assert!(diff.summary().contains("2 registry-only"));
+    }
+
+    #[test]
+    fn test_identical_sets_are_consistent() {
+        let diff = PeerSetDiff::compute(["a", "b"], ["b", "a", "a"], None);
+        assert!(diff.is_consistent());
+        assert_eq!(diff.in_both, 2);
+    }
+
+    #[test]
+    fn test_short_ids_truncates_list() {
+        let ids: Vec<String> = (0..7).map(|i| format!("{:016x}", i)).collect();
+        let shown = short_ids(&ids);
+        assert!(shown.ends_with("+2 more"));
+        assert_eq!(shown.matches(", ").count(), 5);
+    }
+}
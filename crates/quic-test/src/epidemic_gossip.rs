@@ -45,13 +45,14 @@ use saorsa_gossip_rendezvous::{Capability, ProviderSummary};
 use saorsa_gossip_transport::{
     AntQuicTransport, AntQuicTransportConfig, GossipStreamType, GossipTransport,
 };
-use saorsa_gossip_types::{PeerId, TopicId};
+use saorsa_gossip_types::{MessageKind, PeerId, TopicId};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, broadcast, mpsc};
 use tracing::{debug, info, warn};
 
 /// The network topic for ant-quic-test-network gossip.
@@ -147,12 +148,31 @@ pub struct PlumtreeStats {
     pub messages_sent: u64,
     /// Messages received.
     pub messages_received: u64,
-    /// Duplicate messages (already seen).
+    /// Deliveries repeating a recently delivered payload.
     pub duplicates: u64,
     /// Grafts performed (promoted lazy to eager).
     pub grafts: u64,
     /// Prunes performed (demoted eager to lazy).
     pub prunes: u64,
+    /// Grafts inferred from delivery order; `PlumtreePubSub` does not
+    /// expose the ones it performs.
+    pub estimated_grafts: u64,
+    /// Prunes inferred from delivery order; `PlumtreePubSub` does not
+    /// expose the ones it performs.
+    pub estimated_prunes: u64,
+}
+
+/// Plumtree anti-entropy activity around an eager push we withheld.
+///
+/// Times are milliseconds since the gossip layer was created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AntiEntropyEvent {
+    /// An eager push was dropped before reaching Plumtree.
+    EagerWithheld { message_id: String, at_ms: u64 },
+    /// An IHAVE advertised the withheld message, so Plumtree sent IWANT/GRAFT.
+    GraftRequested { message_id: String },
+    /// The withheld message arrived again and was delivered.
+    Recovered { message_id: String, at_ms: u64 },
 }
 
 /// Statistics for CRDT synchronization (saorsa-gossip-crdt-sync).
@@ -639,8 +659,15 @@ pub struct EpidemicGossip {
     groups_state: Arc<RwLock<GroupState>>,
     /// Rendezvous state for peer discovery (saorsa-gossip-rendezvous).
     rendezvous_state: Arc<RwLock<RendezvousState>>,
+    /// Data peers sent us with [`EpidemicGossip::send_to_peer`].
+    direct_tx: broadcast::Sender<(PeerId, Bytes)>,
+    /// Withheld eager pushes and the IHAVE/IWANT traffic recovering them.
+    anti_entropy: Arc<std::sync::Mutex<AntiEntropyMonitor>>,
 }
 
+/// Direct messages buffered per subscriber before the oldest are dropped.
+const DIRECT_CHANNEL_CAPACITY: usize = 1024;
+
 impl EpidemicGossip {
     /// Create a new epidemic gossip layer.
     ///
@@ -666,9 +693,36 @@ impl EpidemicGossip {
             coordinator_state: Arc::new(RwLock::new(CoordinatorState::new(is_coordinator))),
             groups_state: Arc::new(RwLock::new(GroupState::new())),
             rendezvous_state: Arc::new(RwLock::new(RendezvousState::new())),
+            direct_tx: broadcast::channel(DIRECT_CHANNEL_CAPACITY).0,
+            anti_entropy: Arc::new(std::sync::Mutex::new(AntiEntropyMonitor::new())),
         }
     }
 
+    /// Drop the next eager push we receive so Plumtree has to recover it
+    /// through IHAVE/IWANT. Used by the proof to exercise anti-entropy.
+    pub fn withhold_next_eager(&self) {
+        self.anti_entropy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .withhold_next = true;
+    }
+
+    /// Drain anti-entropy events observed since the last call.
+    pub fn take_anti_entropy_events(&self) -> Vec<AntiEntropyEvent> {
+        std::mem::take(
+            &mut self
+                .anti_entropy
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .events,
+        )
+    }
+
+    /// Subscribe to data peers send us directly with `send_to_peer`.
+    pub fn subscribe_direct(&self) -> broadcast::Receiver<(PeerId, Bytes)> {
+        self.direct_tx.subscribe()
+    }
+
     /// Start the gossip layer.
     ///
     /// This initializes:
@@ -752,9 +806,9 @@ impl EpidemicGossip {
         {
             let mut stack_guard = self.stack.write().await;
             *stack_guard = Some(GossipStack {
-                transport,
+                transport: transport.clone(),
                 membership,
-                pubsub,
+                pubsub: pubsub.clone(),
             });
         }
 
@@ -818,6 +872,7 @@ impl EpidemicGossip {
         self.spawn_stats_updater();
         self.spawn_event_monitor();
         self.spawn_message_receiver(message_receiver);
+        self.spawn_transport_receiver(transport, pubsub);
 
         info!("Epidemic gossip started successfully");
         Ok(())
@@ -1028,6 +1083,18 @@ impl EpidemicGossip {
         }
     }
 
+    /// Peers SWIM failure detection has declared dead.
+    pub async fn dead_peers(&self) -> Vec<PeerId> {
+        let membership = {
+            let stack_guard = self.stack.read().await;
+            stack_guard.as_ref().map(|s| Arc::clone(&s.membership))
+        };
+        match membership {
+            Some(membership) => membership.swim().get_peers_in_state(PeerState::Dead).await,
+            None => Vec::new(),
+        }
+    }
+
     /// Bootstrap from known peers.
     ///
     /// NOTE: We use `transport.dial_bootstrap()` directly instead of `membership.join()`
@@ -1341,6 +1408,11 @@ impl EpidemicGossip {
         new_entries
     }
 
+    /// Snapshot of the full CRDT peer cache, as sent for a state sync.
+    pub async fn peer_cache_state(&self) -> OrSet<PeerCacheEntry> {
+        self.crdt_state.read().await.peer_cache.clone()
+    }
+
     /// Merge another node's full CRDT state into ours.
     pub async fn merge_peer_cache_state(&self, other: &OrSet<PeerCacheEntry>) {
         let mut crdt_guard = self.crdt_state.write().await;
@@ -1974,6 +2046,7 @@ impl EpidemicGossip {
         let stats = self.stats.clone();
 
         let topic = TopicId::from_entity(NETWORK_TOPIC);
+        let mut tracker = PlumtreeTracker::default();
 
         tokio::spawn(async move {
             info!("Gossip message receiver started");
@@ -1986,9 +2059,14 @@ impl EpidemicGossip {
                             data.len()
                         );
 
-                        {
+                        let first = {
                             let mut stats_guard = stats.write().await;
-                            stats_guard.plumtree.messages_received += 1;
+                            tracker.observe(topic, from_peer, &data, &mut stats_guard.plumtree)
+                        };
+                        if !first {
+                            // Plumtree already dropped repeats of the same message ID,
+                            // so this is a separate publish of the same payload
+                            debug!("Repeated gossip payload from {:?}", from_peer);
                         }
 
                         if let Err(e) = event_tx
@@ -2010,6 +2088,195 @@ impl EpidemicGossip {
             info!("Message receiver task stopped");
         });
     }
+
+    /// Drain the transport: Plumtree traffic goes to pub/sub, direct sends
+    /// go to [`EpidemicGossip::subscribe_direct`] subscribers.
+    fn spawn_transport_receiver(
+        &self,
+        transport: Arc<AntQuicTransport>,
+        pubsub: Arc<PlumtreePubSub<AntQuicTransport>>,
+    ) {
+        let running = self.running.clone();
+        let direct_tx = self.direct_tx.clone();
+        let anti_entropy = self.anti_entropy.clone();
+
+        tokio::spawn(async move {
+            while running.load(std::sync::atomic::Ordering::SeqCst) {
+                let received =
+                    tokio::time::timeout(Duration::from_millis(100), transport.receive_message())
+                        .await;
+                let (from, stream_type, data) = match received {
+                    Ok(Ok(message)) => message,
+                    Ok(Err(e)) => {
+                        warn!("Gossip transport receive failed: {}", e);
+                        break;
+                    }
+                    Err(_) => continue,
+                };
+                match stream_type {
+                    GossipStreamType::PubSub => {
+                        let deliver = anti_entropy
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .observe(&data);
+                        if !deliver {
+                            debug!("Withholding eager push from {:?}", from);
+                            continue;
+                        }
+                        if let Err(e) = pubsub.handle_message(from, data).await {
+                            debug!("Dropping pub/sub message from {:?}: {}", from, e);
+                        }
+                    }
+                    GossipStreamType::Bulk => {
+                        // No subscribers is fine: nobody is testing right now
+                        let _ = direct_tx.send((from, data));
+                    }
+                    other => {
+                        debug!("Ignoring {:?} message from {:?}", other, from);
+                    }
+                }
+            }
+            info!("Transport receiver task stopped");
+        });
+    }
+}
+
+/// Tracks eager pushes dropped by [`EpidemicGossip::withhold_next_eager`]
+/// until Plumtree recovers them.
+#[derive(Debug)]
+struct AntiEntropyMonitor {
+    started: Instant,
+    withhold_next: bool,
+    /// Withheld message IDs, and whether a GRAFT was already seen for each.
+    withheld: HashMap<[u8; 32], bool>,
+    events: Vec<AntiEntropyEvent>,
+}
+
+impl AntiEntropyMonitor {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            withhold_next: false,
+            withheld: HashMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Inspect a pub/sub frame, returning `false` if it must be dropped.
+    fn observe(&mut self, data: &[u8]) -> bool {
+        let Ok(message) = bincode::deserialize::<saorsa_gossip_pubsub::GossipMessage>(data) else {
+            return true;
+        };
+        let id = message.header.msg_id;
+        let at_ms = self.started.elapsed().as_millis() as u64;
+        match message.header.kind {
+            MessageKind::Eager => {
+                if self.withheld.remove(&id).is_some() {
+                    self.events.push(AntiEntropyEvent::Recovered {
+                        message_id: hex::encode(id),
+                        at_ms,
+                    });
+                } else if std::mem::take(&mut self.withhold_next) {
+                    self.withheld.insert(id, false);
+                    self.events.push(AntiEntropyEvent::EagerWithheld {
+                        message_id: hex::encode(id),
+                        at_ms,
+                    });
+                    return false;
+                }
+            }
+            MessageKind::IHave => {
+                let ids: Vec<[u8; 32]> = message
+                    .payload
+                    .and_then(|payload| bincode::deserialize(&payload).ok())
+                    .unwrap_or_default();
+                for id in ids {
+                    if let Some(grafted) = self.withheld.get_mut(&id)
+                        && !std::mem::replace(grafted, true)
+                    {
+                        self.events.push(AntiEntropyEvent::GraftRequested {
+                            message_id: hex::encode(id),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Message IDs remembered per topic for duplicate detection.
+const SEEN_MESSAGES_PER_TOPIC: usize = 4096;
+
+/// Bounded set of recently seen message IDs; the oldest ID is forgotten
+/// once the set is full.
+#[derive(Debug, Default)]
+struct SeenMessages {
+    ids: HashSet<[u8; 32]>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl SeenMessages {
+    /// Remember `id`, returning `false` if it was already seen.
+    fn insert(&mut self, id: [u8; 32]) -> bool {
+        if !self.ids.insert(id) {
+            return false;
+        }
+        self.order.push_back(id);
+        while self.order.len() > SEEN_MESSAGES_PER_TOPIC {
+            match self.order.pop_front() {
+                Some(oldest) => self.ids.remove(&oldest),
+                None => break,
+            };
+        }
+        true
+    }
+}
+
+/// Duplicate counting and estimated eager/lazy bookkeeping for delivered
+/// messages.
+///
+/// `PlumtreePubSub` keeps its peer sets and counters private, so this
+/// estimates its grafts and prunes by applying its rules to what we
+/// receive: a duplicate from an eager peer prunes it to lazy, and a first
+/// delivery from a lazy peer grafts it back to eager. Duplicates are
+/// counted, never dropped.
+#[derive(Debug, Default)]
+struct PlumtreeTracker {
+    seen: HashMap<TopicId, SeenMessages>,
+    eager: HashSet<PeerId>,
+    lazy: HashSet<PeerId>,
+}
+
+impl PlumtreeTracker {
+    /// Record a message from `from`, updating `stats`. Returns `false` if
+    /// the payload repeats one delivered recently.
+    fn observe(
+        &mut self,
+        topic: TopicId,
+        from: PeerId,
+        payload: &[u8],
+        stats: &mut PlumtreeStats,
+    ) -> bool {
+        stats.messages_received += 1;
+        let id: [u8; 32] = Sha256::digest(payload).into();
+        let first = self.seen.entry(topic).or_default().insert(id);
+
+        if first {
+            if self.lazy.remove(&from) {
+                stats.estimated_grafts += 1;
+            }
+            self.eager.insert(from);
+        } else {
+            stats.duplicates += 1;
+            if self.eager.remove(&from) {
+                stats.estimated_prunes += 1;
+            }
+            self.lazy.insert(from);
+        }
+        first
+    }
 }
 
 /// Errors from the epidemic gossip layer.
@@ -2204,6 +2471,52 @@ mod tests {
         gossip.stop().await;
     }
 
+    #[tokio::test]
+    #[ignore = "requires network access - run with --ignored"]
+    async fn test_direct_send_reaches_subscriber() {
+        let port = std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let receiver_addr: SocketAddr = format!("127.0.0.1:{port}").parse().unwrap();
+        let (tx, _rx) = mpsc::channel(100);
+        let receiver = EpidemicGossip::new(
+            PeerId::new([1u8; 32]),
+            EpidemicConfig {
+                listen_addr: receiver_addr,
+                ..Default::default()
+            },
+            tx,
+        );
+        receiver.start().await.unwrap();
+        let mut direct_rx = receiver.subscribe_direct();
+
+        let (tx, _rx) = mpsc::channel(100);
+        let sender = EpidemicGossip::new(
+            PeerId::new([2u8; 32]),
+            EpidemicConfig {
+                listen_addr: "127.0.0.1:0".parse().unwrap(),
+                bootstrap_peers: vec![receiver_addr],
+                ..Default::default()
+            },
+            tx,
+        );
+        sender.start().await.unwrap();
+        assert_eq!(sender.bootstrap().await, Ok(1));
+        let target = sender.active_view().await[0];
+
+        sender.send_to_peer(target, b"ping".to_vec()).await.unwrap();
+        let (_, data) = tokio::time::timeout(Duration::from_secs(5), direct_rx.recv())
+            .await
+            .expect("direct message not delivered")
+            .unwrap();
+        assert_eq!(&data[..], b"ping");
+
+        sender.stop().await;
+        receiver.stop().await;
+    }
+
     // ============================================================
     // PROOF POINT 5: Connection Type Tracking Tests
     // ============================================================
@@ -3167,4 +3480,120 @@ mod tests {
         assert_eq!(gossip.get_providers(&target2).await.len(), 1);
         assert_eq!(gossip.active_providers_count().await, 2);
     }
+
+    #[test]
+    fn test_plumtree_tracker_counts_duplicates_grafts_and_prunes() {
+        let topic = TopicId::from_entity(NETWORK_TOPIC);
+        let a = PeerId::new([1u8; 32]);
+        let b = PeerId::new([2u8; 32]);
+        let mut tracker = PlumtreeTracker::default();
+        let mut stats = PlumtreeStats::default();
+
+        // First deliveries make both senders eager.
+        assert!(tracker.observe(topic, a, b"m1", &mut stats));
+        assert!(tracker.observe(topic, b, b"m2", &mut stats));
+        assert_eq!((tracker.eager.len(), tracker.lazy.len()), (2, 0));
+
+        // A duplicate from an eager peer is estimated to prune it.
+        assert!(!tracker.observe(topic, b, b"m1", &mut stats));
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.estimated_prunes, 1);
+        assert_eq!((tracker.eager.len(), tracker.lazy.len()), (1, 1));
+
+        // A new message from the lazy peer is estimated to graft it back.
+        assert!(tracker.observe(topic, b, b"m3", &mut stats));
+        assert_eq!(stats.estimated_grafts, 1);
+        assert_eq!((tracker.eager.len(), tracker.lazy.len()), (2, 0));
+        assert_eq!(stats.messages_received, 4);
+
+        // Estimates never stand in for the real Plumtree state.
+        assert_eq!((stats.grafts, stats.prunes), (0, 0));
+        assert_eq!((stats.eager_peers, stats.lazy_peers), (0, 0));
+
+        // The same payload on another topic is not a duplicate.
+        let other = TopicId::from_entity("other-topic");
+        assert!(tracker.observe(other, a, b"m1", &mut stats));
+        assert_eq!(stats.duplicates, 1);
+    }
+
+    #[test]
+    fn test_seen_messages_is_bounded() {
+        let mut seen = SeenMessages::default();
+        for i in 0..=SEEN_MESSAGES_PER_TOPIC {
+            let mut id = [0u8; 32];
+            id[..8].copy_from_slice(&(i as u64).to_le_bytes());
+            assert!(seen.insert(id));
+        }
+        assert_eq!(seen.ids.len(), SEEN_MESSAGES_PER_TOPIC);
+        // The oldest ID was evicted and is treated as new again.
+        assert!(seen.insert([0u8; 32]));
+    }
+
+    fn pubsub_frame(kind: MessageKind, msg_id: [u8; 32], payload: Option<Bytes>) -> Vec<u8> {
+        let message = saorsa_gossip_pubsub::GossipMessage {
+            header: saorsa_gossip_types::MessageHeader {
+                version: 1,
+                topic: TopicId::from_entity("anti-entropy"),
+                msg_id,
+                kind,
+                hop: 0,
+                ttl: 10,
+            },
+            payload,
+            signature: Vec::new(),
+            public_key: Vec::new(),
+        };
+        bincode::serialize(&message).expect("serialize gossip message")
+    }
+
+    #[test]
+    fn test_withheld_eager_push_is_recovered_through_ihave() {
+        use crate::gossip_verification::GossipVerifier;
+
+        let mut monitor = AntiEntropyMonitor::new();
+        let (m1, m2) = ([1u8; 32], [2u8; 32]);
+        let eager = |id| pubsub_frame(MessageKind::Eager, id, Some(Bytes::from_static(b"data")));
+        let ihave = |ids: &[[u8; 32]]| {
+            pubsub_frame(
+                MessageKind::IHave,
+                [9u8; 32],
+                Some(bincode::serialize(ids).expect("serialize ids").into()),
+            )
+        };
+
+        // Nothing is withheld until the hook is armed.
+        assert!(monitor.observe(&eager(m2)));
+        monitor.withhold_next = true;
+        assert!(!monitor.observe(&eager(m1)));
+        assert!(!monitor.withhold_next);
+
+        // A lazy peer's IHAVE makes Plumtree GRAFT once, then the IWANT
+        // reply is delivered.
+        assert!(monitor.observe(&ihave(&[m1, m2])));
+        assert!(monitor.observe(&ihave(&[m1])));
+        assert!(monitor.observe(&eager(m1)));
+        assert!(monitor.observe(b"not a gossip message"));
+
+        let events = std::mem::take(&mut monitor.events);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                AntiEntropyEvent::EagerWithheld { .. },
+                AntiEntropyEvent::GraftRequested { .. },
+                AntiEntropyEvent::Recovered { .. },
+            ]
+        ));
+
+        let mut verifier = GossipVerifier::new();
+        verifier.record_anti_entropy("node1", &events);
+        let recovery = verifier.anti_entropy_recovery();
+        assert_eq!((recovery.dropped, recovery.unrecovered), (1, 0));
+        assert_eq!(recovery.graft_requests, 1);
+
+        // A push that is withheld and never recovered fails the check.
+        monitor.withhold_next = true;
+        assert!(!monitor.observe(&eager(m2)));
+        verifier.record_anti_entropy("node2", &monitor.events);
+        assert_eq!(verifier.anti_entropy_recovery().unrecovered, 1);
+    }
 }
@@ -9,10 +9,11 @@
 //!
 //! Uses saorsa-gossip's Plumtree epidemic broadcast for O(log n) message propagation.
 
+use crate::epidemic_gossip::EpidemicConfig;
 use saorsa_gossip_coordinator::{NatClass, PeerCache, PeerCacheEntry, PeerRoles};
 use saorsa_gossip_types::PeerId;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -28,6 +29,22 @@ pub const TOPIC_COORDINATORS: &str = "ant-quic/coordinators/v1";
 /// New topics for peer connection queries (NAT coordination)
 pub const TOPIC_PEER_QUERY: &str = "ant-quic/peer-query/v1";
 pub const TOPIC_PEER_RESPONSE: &str = "ant-quic/peer-response/v1";
+/// Tagged probes used to measure Plumtree broadcast propagation latency
+pub const TOPIC_LATENCY_PROBE: &str = "ant-quic/latency-probe/v1";
+
+/// Default HyParView active view size.
+pub const DEFAULT_ACTIVE_VIEW_SIZE: usize = 8;
+
+/// Default HyParView passive view size.
+pub const DEFAULT_PASSIVE_VIEW_SIZE: usize = 64;
+
+/// Topics whose subscriber counts are reported in [`GossipMetrics`].
+const SUBSCRIBABLE_TOPICS: [&str; 4] = [
+    TOPIC_PEERS,
+    TOPIC_RELAYS,
+    TOPIC_COORDINATORS,
+    TOPIC_LATENCY_PROBE,
+];
 
 /// A peer announcement broadcast via gossip.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +71,9 @@ pub struct PeerAnnouncement {
     /// Monotonic epoch for delta-based gossip (0 = legacy, >0 = versioned).
     #[serde(default)]
     pub epoch: u64,
+    /// Discovery topics the peer is subscribed to (empty from older nodes).
+    #[serde(default)]
+    pub topics: Vec<String>,
 }
 
 /// Capabilities advertised by a peer.
@@ -162,6 +182,43 @@ impl RelayAnnouncement {
     }
 }
 
+/// How to choose among several announced relays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RelaySelectionPolicy {
+    /// Take the first relay with spare capacity, in announcement order.
+    FirstAvailable,
+    /// Prefer the relay with the fewest active connections, ties broken by RTT.
+    #[default]
+    LeastLoaded,
+    /// Prefer the relay with the lowest measured RTT, ties broken by load.
+    LowestRtt,
+}
+
+impl RelaySelectionPolicy {
+    /// Pick a relay from `relays` according to this policy.
+    ///
+    /// Relays at capacity are skipped. `rtt_of` returns the measured RTT for a
+    /// relay's peer ID; relays without a measurement rank after measured ones.
+    pub fn select<'a, F>(
+        self,
+        relays: impl IntoIterator<Item = &'a RelayAnnouncement>,
+        rtt_of: F,
+    ) -> Option<&'a RelayAnnouncement>
+    where
+        F: Fn(&str) -> Option<Duration>,
+    {
+        let mut available = relays
+            .into_iter()
+            .filter(|r| r.active_connections < r.max_connections);
+        let rtt = |r: &RelayAnnouncement| rtt_of(&r.peer_id).unwrap_or(Duration::MAX);
+        match self {
+            Self::FirstAvailable => available.next(),
+            Self::LeastLoaded => available.min_by_key(|r| (r.active_connections, rtt(r))),
+            Self::LowestRtt => available.min_by_key(|r| (rtt(r), r.active_connections)),
+        }
+    }
+}
+
 /// A coordinator announcement for NAT traversal.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoordinatorAnnouncement {
@@ -205,6 +262,17 @@ pub struct PeerConnectionResponse {
     pub connection_quality: f64,
 }
 
+/// Tagged probe broadcast to measure how long a message takes to reach every node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyProbe {
+    /// Unique probe ID, reported back with the receipt timestamp.
+    pub probe_id: String,
+    /// Hex-encoded peer ID (or observer name) that injected the probe.
+    pub origin_id: String,
+    /// Timestamp when the probe was published.
+    pub sent_at_ms: u64,
+}
+
 /// Events from the gossip layer.
 #[derive(Debug, Clone)]
 pub enum GossipEvent {
@@ -222,6 +290,22 @@ pub enum GossipEvent {
     PeerResponseReceived(PeerConnectionResponse),
 }
 
+/// Leading byte of binary-encoded announcements. JSON payloads always start
+/// with `{`, so decoders can tell the two encodings apart.
+const WIRE_BINARY_V1: u8 = 0x01;
+
+/// Encoding used for peer and relay announcements.
+///
+/// Decoding accepts both, so nodes with different settings interoperate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    /// Plain JSON, easy to inspect in captures.
+    #[default]
+    Json,
+    /// bincode behind a version byte, for smaller gossip payloads.
+    Binary,
+}
+
 /// Configuration for the gossip layer.
 #[derive(Debug, Clone)]
 pub struct GossipConfig {
@@ -237,6 +321,15 @@ pub struct GossipConfig {
     pub max_coordinators: usize,
     /// Path to persistent peer cache (optional).
     pub cache_path: Option<PathBuf>,
+    /// Target HyParView active view size (overlay fanout).
+    pub active_view_size: usize,
+    /// Target HyParView passive view size.
+    pub passive_view_size: usize,
+    /// Encoding for outgoing peer and relay announcements.
+    pub wire_format: WireFormat,
+    /// Peers each announcement is forwarded to (at least 1); `None`
+    /// forwards to every connected peer.
+    pub gossip_fanout: Option<usize>,
 }
 
 impl Default for GossipConfig {
@@ -248,6 +341,35 @@ impl Default for GossipConfig {
             max_relays: 50,
             max_coordinators: 50,
             cache_path: None,
+            active_view_size: DEFAULT_ACTIVE_VIEW_SIZE,
+            passive_view_size: DEFAULT_PASSIVE_VIEW_SIZE,
+            wire_format: WireFormat::Json,
+            gossip_fanout: None,
+        }
+    }
+}
+
+impl GossipConfig {
+    /// Reject a zero fanout; warn when it exceeds the active view size,
+    /// since pushes beyond the overlay's active peers rarely find a target.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self.gossip_fanout {
+            Some(0) => anyhow::bail!("gossip fanout must be at least 1"),
+            Some(fanout) if fanout > self.active_view_size => warn!(
+                "Gossip fanout {} exceeds the active view size {}",
+                fanout, self.active_view_size
+            ),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Apply this config's HyParView view sizes to an epidemic layer config.
+    pub fn apply_view_sizes(&self, epidemic: EpidemicConfig) -> EpidemicConfig {
+        EpidemicConfig {
+            max_active: self.active_view_size,
+            max_passive: self.passive_view_size,
+            ..epidemic
         }
     }
 }
@@ -273,6 +395,22 @@ pub struct GossipMetrics {
     pub cache_hits: AtomicU64,
     /// Bootstrap cache misses.
     pub cache_misses: AtomicU64,
+    /// Configured HyParView active view size target.
+    pub active_view_target: AtomicU64,
+    /// Configured HyParView passive view size target.
+    pub passive_view_target: AtomicU64,
+    /// Subscribers of each discovery topic (topic name -> count).
+    ///
+    /// Counts this node if it subscribed, plus every known peer whose
+    /// announcement lists the topic. Peers that merely publish on a topic
+    /// are not counted, so a zero means nobody would receive it.
+    pub topic_subscribers: std::sync::Mutex<HashMap<String, usize>>,
+    /// First receipt time of each latency probe (probe ID -> unix ms).
+    pub probe_receipts: std::sync::Mutex<HashMap<String, u64>>,
+    /// Bytes of announcements encoded in the configured wire format.
+    pub announcement_bytes_encoded: AtomicU64,
+    /// Bytes the same announcements would have taken as JSON.
+    pub announcement_bytes_json: AtomicU64,
 }
 
 impl GossipMetrics {
@@ -283,7 +421,7 @@ impl GossipMetrics {
 
     /// Get metrics as Prometheus-format text.
     pub fn to_prometheus(&self) -> String {
-        format!(
+        let mut text = format!(
             r#"# HELP ant_quic_gossip_announcements_sent Total peer announcements sent
 # TYPE ant_quic_gossip_announcements_sent counter
 ant_quic_gossip_announcements_sent {}
@@ -319,6 +457,22 @@ ant_quic_gossip_cache_hits {}
 # HELP ant_quic_gossip_cache_misses Bootstrap cache misses
 # TYPE ant_quic_gossip_cache_misses counter
 ant_quic_gossip_cache_misses {}
+
+# HELP ant_quic_gossip_active_view_target Configured HyParView active view size
+# TYPE ant_quic_gossip_active_view_target gauge
+ant_quic_gossip_active_view_target {}
+
+# HELP ant_quic_gossip_passive_view_target Configured HyParView passive view size
+# TYPE ant_quic_gossip_passive_view_target gauge
+ant_quic_gossip_passive_view_target {}
+
+# HELP ant_quic_gossip_announcement_bytes_encoded Announcement bytes in the configured wire format
+# TYPE ant_quic_gossip_announcement_bytes_encoded counter
+ant_quic_gossip_announcement_bytes_encoded {}
+
+# HELP ant_quic_gossip_announcement_bytes_json Announcement bytes had they been sent as JSON
+# TYPE ant_quic_gossip_announcement_bytes_json counter
+ant_quic_gossip_announcement_bytes_json {}
 "#,
             self.announcements_sent.load(Ordering::Relaxed),
             self.announcements_received.load(Ordering::Relaxed),
@@ -329,7 +483,76 @@ ant_quic_gossip_cache_misses {}
             self.cache_updates.load(Ordering::Relaxed),
             self.cache_hits.load(Ordering::Relaxed),
             self.cache_misses.load(Ordering::Relaxed),
-        )
+            self.active_view_target.load(Ordering::Relaxed),
+            self.passive_view_target.load(Ordering::Relaxed),
+            self.announcement_bytes_encoded.load(Ordering::Relaxed),
+            self.announcement_bytes_json.load(Ordering::Relaxed),
+        );
+        let mut topics: Vec<_> = self.topic_subscribers().into_iter().collect();
+        topics.sort();
+        if !topics.is_empty() {
+            text.push_str(
+                "\n# HELP ant_quic_gossip_topic_subscribers Subscribers of each discovery topic\n\
+                 # TYPE ant_quic_gossip_topic_subscribers gauge\n",
+            );
+            for (topic, count) in topics {
+                text.push_str(&format!(
+                    "ant_quic_gossip_topic_subscribers{{topic=\"{}\"}} {}\n",
+                    topic, count
+                ));
+            }
+        }
+        text
+    }
+
+    /// Set the number of subscribers of `topic`.
+    pub fn set_topic_subscribers(&self, topic: &str, count: usize) {
+        if let Ok(mut topics) = self.topic_subscribers.lock() {
+            topics.insert(topic.to_string(), count);
+        }
+    }
+
+    /// Snapshot of per-topic subscriber counts.
+    pub fn topic_subscribers(&self) -> HashMap<String, usize> {
+        self.topic_subscribers
+            .lock()
+            .map(|topics| topics.clone())
+            .unwrap_or_default()
+    }
+
+    /// Record when a latency probe was received; later copies are ignored.
+    pub fn record_probe_receipt(&self, probe_id: &str, received_at_ms: u64) {
+        if let Ok(mut receipts) = self.probe_receipts.lock() {
+            receipts
+                .entry(probe_id.to_string())
+                .or_insert(received_at_ms);
+        }
+    }
+
+    /// Record the size of one encoded announcement and its JSON equivalent.
+    pub fn record_announcement_size(&self, encoded: usize, json: usize) {
+        self.announcement_bytes_encoded
+            .fetch_add(encoded as u64, Ordering::Relaxed);
+        self.announcement_bytes_json
+            .fetch_add(json as u64, Ordering::Relaxed);
+    }
+
+    /// Percentage of announcement bytes saved versus JSON (0 when sending JSON).
+    pub fn wire_size_reduction_percent(&self) -> f64 {
+        let json = self.announcement_bytes_json.load(Ordering::Relaxed);
+        if json == 0 {
+            return 0.0;
+        }
+        let encoded = self.announcement_bytes_encoded.load(Ordering::Relaxed);
+        (1.0 - encoded as f64 / json as f64) * 100.0
+    }
+
+    /// Snapshot of latency probe receipt times.
+    pub fn probe_receipts(&self) -> HashMap<String, u64> {
+        self.probe_receipts
+            .lock()
+            .map(|receipts| receipts.clone())
+            .unwrap_or_default()
     }
 }
 
@@ -362,6 +585,8 @@ pub struct GossipDiscovery {
     shutdown: Arc<std::sync::atomic::AtomicBool>,
     /// Gossip metrics.
     metrics: Arc<GossipMetrics>,
+    /// Discovery topics this node is subscribed to.
+    subscriptions: std::sync::Mutex<BTreeSet<String>>,
 }
 
 impl GossipDiscovery {
@@ -384,6 +609,17 @@ impl GossipDiscovery {
         config: GossipConfig,
         event_tx: mpsc::Sender<GossipEvent>,
     ) -> Self {
+        let metrics = GossipMetrics::new();
+        metrics
+            .active_view_target
+            .store(config.active_view_size as u64, Ordering::Relaxed);
+        metrics
+            .passive_view_target
+            .store(config.passive_view_size as u64, Ordering::Relaxed);
+        for topic in SUBSCRIBABLE_TOPICS {
+            metrics.set_topic_subscribers(topic, 0);
+        }
+
         Self {
             peer_id,
             addresses,
@@ -396,7 +632,8 @@ impl GossipDiscovery {
             known_coordinators: Arc::new(RwLock::new(HashMap::new())),
             event_tx,
             shutdown: Arc::new(std::sync::atomic::AtomicBool::new(false)),
-            metrics: Arc::new(GossipMetrics::new()),
+            metrics: Arc::new(metrics),
+            subscriptions: std::sync::Mutex::new(BTreeSet::new()),
         }
     }
 
@@ -413,6 +650,69 @@ impl GossipDiscovery {
         &self.metrics
     }
 
+    /// Peers each announcement is forwarded to; `None` means all of them.
+    pub fn fanout(&self) -> Option<usize> {
+        self.config.gossip_fanout
+    }
+
+    /// Pick at most [`Self::fanout`] of `peers` to forward an announcement
+    /// to, or all of them without a fanout.
+    pub fn fanout_targets<T>(&self, peers: Vec<T>) -> Vec<T> {
+        match self.config.gossip_fanout {
+            Some(fanout) => select_fanout(peers, fanout, &mut rand::thread_rng()),
+            None => peers,
+        }
+    }
+
+    /// Encode an outgoing announcement in the configured wire format,
+    /// recording its size against the JSON equivalent.
+    pub fn encode_announcement<T: Serialize>(&self, announcement: &T) -> Vec<u8> {
+        let encoded = encode_wire(announcement, self.config.wire_format);
+        let json_len = match self.config.wire_format {
+            WireFormat::Json => encoded.len(),
+            WireFormat::Binary => encode_wire(announcement, WireFormat::Json).len(),
+        };
+        self.metrics
+            .record_announcement_size(encoded.len(), json_len);
+        encoded
+    }
+
+    /// Subscribe this node to `topic`; it is advertised in our announcements.
+    pub async fn subscribe(&self, topic: &str) {
+        if let Ok(mut subscriptions) = self.subscriptions.lock() {
+            subscriptions.insert(topic.to_string());
+        }
+        self.refresh_topic_subscribers().await;
+    }
+
+    /// Discovery topics this node is subscribed to.
+    pub fn subscribed_topics(&self) -> Vec<String> {
+        self.subscriptions
+            .lock()
+            .map(|subscriptions| subscriptions.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Recount the subscribers of each discovery topic into the metrics.
+    async fn refresh_topic_subscribers(&self) {
+        let mut counts: HashMap<&str, usize> = SUBSCRIBABLE_TOPICS
+            .into_iter()
+            .map(|topic| (topic, 0))
+            .collect();
+        let local = self.subscribed_topics();
+        let peers = self.known_peers.read().await;
+        let remote = peers.values().flat_map(|(a, _)| a.topics.iter());
+        for topic in local.iter().chain(remote) {
+            if let Some(count) = counts.get_mut(topic.as_str()) {
+                *count += 1;
+            }
+        }
+        drop(peers);
+        for (topic, count) in counts {
+            self.metrics.set_topic_subscribers(topic, count);
+        }
+    }
+
     pub fn create_announcement(&self, capabilities: PeerCapabilities) -> PeerAnnouncement {
         PeerAnnouncement {
             peer_id: self.peer_id.clone(),
@@ -424,6 +724,7 @@ impl GossipDiscovery {
             country_code: None,
             capabilities,
             epoch: Self::timestamp_ms(),
+            topics: self.subscribed_topics(),
         }
     }
 
@@ -464,6 +765,7 @@ impl GossipDiscovery {
         }
 
         drop(peers);
+        self.refresh_topic_subscribers().await;
 
         if is_new {
             debug!(
@@ -584,6 +886,22 @@ impl GossipDiscovery {
             .await;
     }
 
+    /// Handle an incoming latency probe by stamping its receipt time.
+    ///
+    /// The receipt is reported to the registry in the next heartbeat so the
+    /// proof orchestrator can compute broadcast propagation latency.
+    pub fn handle_latency_probe(&self, probe: LatencyProbe) {
+        let received_at_ms = Self::timestamp_ms();
+        debug!(
+            "Gossip: received latency probe {} from {} after {}ms",
+            probe.probe_id,
+            &probe.origin_id[..8.min(probe.origin_id.len())],
+            received_at_ms.saturating_sub(probe.sent_at_ms)
+        );
+        self.metrics
+            .record_probe_receipt(&probe.probe_id, received_at_ms);
+    }
+
     /// Get all known peers.
     pub async fn get_peers(&self) -> Vec<PeerAnnouncement> {
         let peers = self.known_peers.read().await;
@@ -657,6 +975,31 @@ impl GossipDiscovery {
         candidates.first().map(|(r, _)| (*r).clone())
     }
 
+    /// Select a relay able to reach any of `targets` using `policy`.
+    ///
+    /// Relays are considered in peer ID order so `FirstAvailable` is stable
+    /// across calls. `exclude` drops a peer (typically the target itself).
+    pub async fn select_relay<F>(
+        &self,
+        targets: &[SocketAddr],
+        exclude: &str,
+        policy: RelaySelectionPolicy,
+        rtt_of: F,
+    ) -> Option<RelayAnnouncement>
+    where
+        F: Fn(&str) -> Option<Duration>,
+    {
+        let relays = self.known_relays.read().await;
+        let mut candidates: Vec<_> = relays
+            .values()
+            .map(|(r, _)| r)
+            .filter(|r| r.peer_id != exclude)
+            .filter(|r| targets.is_empty() || targets.iter().any(|t| r.can_reach(t)))
+            .collect();
+        candidates.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        policy.select(candidates, rtt_of).cloned()
+    }
+
     /// Get all dual-stack relays (can bridge IPv4 ↔ IPv6).
     pub async fn get_dual_stack_relays(&self) -> Vec<RelayAnnouncement> {
         let relays = self.known_relays.read().await;
@@ -732,6 +1075,8 @@ impl GossipDiscovery {
             let mut coordinators = self.known_coordinators.write().await;
             coordinators.retain(|_, (_, t)| now.duration_since(*t) <= ttl);
         }
+
+        self.refresh_topic_subscribers().await;
     }
 
     /// Shutdown the gossip layer.
@@ -1050,24 +1395,89 @@ fn parse_peer_id(hex_str: &str) -> Option<PeerId> {
     }
 }
 
+/// Choose `fanout` of `peers` uniformly at random, or all of them if there
+/// are no more than that.
+pub fn select_fanout<T, R: rand::Rng + ?Sized>(
+    mut peers: Vec<T>,
+    fanout: usize,
+    rng: &mut R,
+) -> Vec<T> {
+    if peers.len() > fanout {
+        use rand::seq::SliceRandom;
+        peers.partial_shuffle(rng, fanout);
+        peers.truncate(fanout);
+    }
+    peers
+}
+
+/// Load the hex-encoded peer IDs stored in a persisted peer cache file.
+pub fn load_cached_peer_ids(path: &std::path::Path) -> anyhow::Result<Vec<String>> {
+    let cache = PeerCache::load(path)?;
+    Ok(cache
+        .get_by_role(|_| true)
+        .iter()
+        .map(|entry| hex::encode(entry.peer_id.as_bytes()))
+        .collect())
+}
+
+/// Encode `value` as JSON or as version-tagged bincode.
+fn encode_wire<T: Serialize>(value: &T, format: WireFormat) -> Vec<u8> {
+    match format {
+        WireFormat::Json => serde_json::to_vec(value).unwrap_or_default(),
+        WireFormat::Binary => match bincode::serialize(value) {
+            Ok(body) => {
+                let mut bytes = Vec::with_capacity(body.len() + 1);
+                bytes.push(WIRE_BINARY_V1);
+                bytes.extend_from_slice(&body);
+                bytes
+            }
+            Err(_) => Vec::new(),
+        },
+    }
+}
+
+/// Decode a payload produced by [`encode_wire`] in either format.
+pub(crate) fn decode_wire<T: serde::de::DeserializeOwned>(data: &[u8]) -> Option<T> {
+    match data.split_first() {
+        Some((&WIRE_BINARY_V1, body)) => bincode::deserialize(body).ok(),
+        _ => serde_json::from_slice(data).ok(),
+    }
+}
+
 /// Serialize a peer announcement to bytes for gossip.
 pub fn serialize_peer_announcement(announcement: &PeerAnnouncement) -> Vec<u8> {
-    serde_json::to_vec(announcement).unwrap_or_default()
+    encode_wire(announcement, WireFormat::Json)
+}
+
+/// Serialize a peer announcement in the given wire format.
+pub fn serialize_peer_announcement_as(
+    announcement: &PeerAnnouncement,
+    format: WireFormat,
+) -> Vec<u8> {
+    encode_wire(announcement, format)
 }
 
-/// Deserialize a peer announcement from bytes.
+/// Deserialize a peer announcement from JSON or binary bytes.
 pub fn deserialize_peer_announcement(data: &[u8]) -> Option<PeerAnnouncement> {
-    serde_json::from_slice(data).ok()
+    decode_wire(data)
 }
 
 /// Serialize a relay announcement to bytes for gossip.
 pub fn serialize_relay_announcement(announcement: &RelayAnnouncement) -> Vec<u8> {
-    serde_json::to_vec(announcement).unwrap_or_default()
+    encode_wire(announcement, WireFormat::Json)
+}
+
+/// Serialize a relay announcement in the given wire format.
+pub fn serialize_relay_announcement_as(
+    announcement: &RelayAnnouncement,
+    format: WireFormat,
+) -> Vec<u8> {
+    encode_wire(announcement, format)
 }
 
-/// Deserialize a relay announcement from bytes.
+/// Deserialize a relay announcement from JSON or binary bytes.
 pub fn deserialize_relay_announcement(data: &[u8]) -> Option<RelayAnnouncement> {
-    serde_json::from_slice(data).ok()
+    decode_wire(data)
 }
 
 /// Serialize a coordinator announcement to bytes for gossip.
@@ -1100,6 +1510,16 @@ pub fn deserialize_peer_response(data: &[u8]) -> Option<PeerConnectionResponse>
     serde_json::from_slice(data).ok()
 }
 
+/// Serialize a latency probe to bytes for gossip.
+pub fn serialize_latency_probe(probe: &LatencyProbe) -> Vec<u8> {
+    serde_json::to_vec(probe).unwrap_or_default()
+}
+
+/// Deserialize a latency probe from bytes.
+pub fn deserialize_latency_probe(data: &[u8]) -> Option<LatencyProbe> {
+    serde_json::from_slice(data).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1124,6 +1544,7 @@ mod tests {
                 supports_dual_stack: false,
             },
             epoch: 1234567890,
+            topics: vec![TOPIC_PEERS.to_string()],
         };
 
         let bytes = serialize_peer_announcement(&announcement);
@@ -1148,6 +1569,104 @@ mod tests {
         );
     }
 
+    fn full_peer_announcement(country_code: Option<String>) -> PeerAnnouncement {
+        PeerAnnouncement {
+            peer_id: "ab".repeat(32),
+            addresses: vec![
+                "203.0.113.7:9000".parse().unwrap(),
+                "[2001:db8::7]:9000".parse().unwrap(),
+            ],
+            is_public: true,
+            is_public_ipv4: true,
+            is_public_ipv6: true,
+            timestamp_ms: 1_700_000_000_123,
+            country_code,
+            capabilities: PeerCapabilities {
+                direct: true,
+                direct_ipv4: true,
+                direct_ipv6: true,
+                hole_punch: true,
+                relay: true,
+                coordinator: true,
+                supports_dual_stack: true,
+            },
+            epoch: 42,
+            topics: vec![TOPIC_PEERS.to_string(), TOPIC_RELAYS.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_announcements_round_trip_in_both_wire_formats() {
+        let relays = [Some("eu-west".to_string()), None].map(|region| RelayAnnouncement {
+            peer_id: "cd".repeat(32),
+            addresses: vec!["198.51.100.1:9000".parse().unwrap()],
+            active_connections: 3,
+            max_connections: 64,
+            timestamp_ms: 1_700_000_000_456,
+            region,
+            supports_dual_stack: true,
+        });
+
+        for format in [WireFormat::Json, WireFormat::Binary] {
+            for country_code in [Some("GB".to_string()), None] {
+                let announcement = full_peer_announcement(country_code);
+                let bytes = serialize_peer_announcement_as(&announcement, format);
+                let decoded = deserialize_peer_announcement(&bytes).expect("decode failed");
+                assert_eq!(
+                    serde_json::to_value(&decoded).unwrap(),
+                    serde_json::to_value(&announcement).unwrap(),
+                    "{:?}",
+                    format
+                );
+            }
+            for relay in &relays {
+                let bytes = serialize_relay_announcement_as(relay, format);
+                let decoded = deserialize_relay_announcement(&bytes).expect("decode failed");
+                assert_eq!(
+                    serde_json::to_value(&decoded).unwrap(),
+                    serde_json::to_value(relay).unwrap(),
+                    "{:?}",
+                    format
+                );
+            }
+        }
+
+        let binary =
+            serialize_peer_announcement_as(&full_peer_announcement(None), WireFormat::Binary);
+        assert_eq!(binary[0], WIRE_BINARY_V1);
+        assert!(deserialize_peer_announcement(&binary[..binary.len() / 2]).is_none());
+    }
+
+    #[test]
+    fn test_binary_wire_format_records_size_reduction() {
+        let (tx, _rx) = mpsc::channel(10);
+        let config = GossipConfig {
+            wire_format: WireFormat::Binary,
+            ..GossipConfig::default()
+        };
+        let discovery =
+            GossipDiscovery::new("self".to_string(), vec![], true, true, false, config, tx);
+        let announcement = full_peer_announcement(Some("GB".to_string()));
+
+        let bytes = discovery.encode_announcement(&announcement);
+        let json_len = serialize_peer_announcement(&announcement).len();
+        let metrics = discovery.metrics();
+        assert_eq!(
+            metrics.announcement_bytes_encoded.load(Ordering::Relaxed),
+            bytes.len() as u64
+        );
+        assert_eq!(
+            metrics.announcement_bytes_json.load(Ordering::Relaxed),
+            json_len as u64
+        );
+        assert!(bytes.len() < json_len);
+        assert!(metrics.wire_size_reduction_percent() > 0.0);
+        assert!(metrics.to_prometheus().contains(&format!(
+            "ant_quic_gossip_announcement_bytes_json {}",
+            json_len
+        )));
+    }
+
     #[test]
     fn test_peer_query_serialization() {
         let query = PeerConnectionQuery {
@@ -1194,6 +1713,146 @@ mod tests {
         assert!(prometheus.contains("ant_quic_gossip_cache_hits 5"));
     }
 
+    #[test]
+    fn test_fanout_is_validated_and_honored() {
+        let zero = GossipConfig {
+            gossip_fanout: Some(0),
+            ..Default::default()
+        };
+        assert!(zero.validate().is_err());
+        // Above the active view size is allowed, just warned about
+        let wide = GossipConfig {
+            gossip_fanout: Some(20),
+            ..Default::default()
+        };
+        assert!(wide.active_view_size < 20);
+        assert!(wide.validate().is_ok());
+
+        // By default every connected peer gets a copy
+        let (tx, _rx) = mpsc::channel(10);
+        let discovery = GossipDiscovery::new(
+            "self".into(),
+            vec![],
+            false,
+            false,
+            false,
+            GossipConfig::default(),
+            tx,
+        );
+        assert_eq!(discovery.fanout(), None);
+        let peers: Vec<u32> = (0..10).collect();
+        assert_eq!(discovery.fanout_targets(peers.clone()), peers);
+
+        let (tx, _rx) = mpsc::channel(10);
+        let config = GossipConfig {
+            gossip_fanout: Some(3),
+            ..Default::default()
+        };
+        let discovery =
+            GossipDiscovery::new("self".into(), vec![], false, false, false, config, tx);
+        assert_eq!(discovery.fanout(), Some(3));
+        let mut targets = discovery.fanout_targets(peers.clone());
+        assert_eq!(targets.len(), 3);
+        targets.sort_unstable();
+        targets.dedup();
+        assert_eq!(targets.len(), 3);
+        assert!(targets.iter().all(|t| peers.contains(t)));
+        assert_eq!(discovery.fanout_targets(vec![1, 2]), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_view_sizes_pass_through_to_epidemic_and_metrics() {
+        let (tx, _rx) = mpsc::channel(10);
+        let config = GossipConfig {
+            active_view_size: 4,
+            passive_view_size: 32,
+            ..Default::default()
+        };
+
+        let epidemic = config.apply_view_sizes(EpidemicConfig::default());
+        assert_eq!(epidemic.max_active, 4);
+        assert_eq!(epidemic.max_passive, 32);
+
+        let integration = GossipIntegration::new(
+            "test_peer".to_string(),
+            vec![],
+            true,
+            true,
+            false,
+            config,
+            tx,
+        );
+        let metrics = integration.metrics();
+        assert_eq!(metrics.active_view_target.load(Ordering::Relaxed), 4);
+        assert_eq!(metrics.passive_view_target.load(Ordering::Relaxed), 32);
+        assert!(
+            metrics
+                .to_prometheus()
+                .contains("ant_quic_gossip_active_view_target 4")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_topic_subscribers_count_subscriptions_not_announcers() {
+        let (tx, _rx) = mpsc::channel(10);
+        let discovery = GossipDiscovery::new(
+            "self".to_string(),
+            vec![],
+            true,
+            true,
+            false,
+            GossipConfig::default(),
+            tx,
+        );
+        let counts = discovery.metrics().topic_subscribers();
+        assert_eq!(counts.get(TOPIC_RELAYS), Some(&0));
+
+        discovery.subscribe(TOPIC_PEERS).await;
+        assert_eq!(discovery.subscribed_topics(), vec![TOPIC_PEERS.to_string()]);
+        assert_eq!(
+            discovery
+                .create_announcement(PeerCapabilities::default())
+                .topics,
+            vec![TOPIC_PEERS.to_string()]
+        );
+
+        // Publishing relays are not subscribers
+        for peer_id in ["relay1", "relay2"] {
+            discovery
+                .handle_relay_announcement(RelayAnnouncement {
+                    peer_id: peer_id.to_string(),
+                    addresses: vec!["192.168.1.1:9000".parse().unwrap()],
+                    active_connections: 0,
+                    max_connections: 100,
+                    timestamp_ms: 0,
+                    region: None,
+                    supports_dual_stack: false,
+                })
+                .await;
+        }
+        let counts = discovery.metrics().topic_subscribers();
+        assert_eq!(counts.get(TOPIC_RELAYS), Some(&0));
+        assert_eq!(counts.get(TOPIC_PEERS), Some(&1));
+
+        // Peers count on the topics their announcements list
+        let mut subscriber = full_peer_announcement(None);
+        subscriber.peer_id = "peer1".to_string();
+        discovery.handle_peer_announcement(subscriber).await;
+        let mut legacy = full_peer_announcement(None);
+        legacy.peer_id = "peer2".to_string();
+        legacy.topics.clear();
+        discovery.handle_peer_announcement(legacy).await;
+
+        let counts = discovery.metrics().topic_subscribers();
+        assert_eq!(counts.get(TOPIC_PEERS), Some(&2));
+        assert_eq!(counts.get(TOPIC_RELAYS), Some(&1));
+        assert_eq!(counts.get(TOPIC_COORDINATORS), Some(&0));
+        assert!(discovery.metrics().to_prometheus().contains(&format!(
+            "ant_quic_gossip_topic_subscribers{{topic=\"{}\"}} 2",
+            TOPIC_PEERS
+        )));
+    }
+
     #[test]
     fn test_parse_peer_id() {
         // Full 32-byte hex (64 chars)
@@ -1232,6 +1891,33 @@ mod tests {
         assert!(status.cache_path.is_none());
     }
 
+    #[test]
+    fn test_load_cached_peer_ids() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("peer_cache.cbor");
+        let (tx, _rx) = mpsc::channel(10);
+        let config = GossipConfig {
+            cache_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let integration = GossipIntegration::new(
+            "test_peer".to_string(),
+            vec![],
+            true,
+            true,
+            false,
+            config,
+            tx,
+        );
+
+        let peer_hex = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        integration.add_peer(peer_hex, &["1.2.3.4:9000".parse().unwrap()], true);
+        integration.save_cache().expect("save cache");
+
+        let ids = load_cached_peer_ids(&path).expect("load cache");
+        assert_eq!(ids, vec![peer_hex.to_string()]);
+    }
+
     // ========== Dual-Stack Capability Tests ==========
 
     #[test]
@@ -1345,6 +2031,43 @@ mod tests {
         assert!(dual_relay.can_reach(&"[2001:db8::2]:8080".parse().unwrap())); // IPv6 target
     }
 
+    #[test]
+    fn test_relay_selection_policies() {
+        let relay = |id: &str, active: u32| RelayAnnouncement {
+            peer_id: id.to_string(),
+            addresses: vec!["192.168.1.1:9000".parse().unwrap()],
+            active_connections: active,
+            max_connections: 10,
+            timestamp_ms: 0,
+            region: None,
+            supports_dual_stack: false,
+        };
+        let relays = vec![relay("a", 10), relay("b", 4), relay("c", 2), relay("d", 2)];
+        let rtt_of = |id: &str| match id {
+            "b" => Some(Duration::from_millis(5)),
+            "c" => Some(Duration::from_millis(80)),
+            "d" => Some(Duration::from_millis(30)),
+            _ => None,
+        };
+        let pick = |policy: RelaySelectionPolicy| {
+            policy
+                .select(&relays, rtt_of)
+                .map(|r| r.peer_id.clone())
+                .unwrap()
+        };
+
+        // "a" is full, so it is never chosen
+        assert_eq!(pick(RelaySelectionPolicy::FirstAvailable), "b");
+        // "c" and "d" tie on load; "d" wins on RTT
+        assert_eq!(pick(RelaySelectionPolicy::LeastLoaded), "d");
+        assert_eq!(pick(RelaySelectionPolicy::LowestRtt), "b");
+        assert!(
+            RelaySelectionPolicy::LeastLoaded
+                .select(&relays[..1], rtt_of)
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_relay_announcement_ip_version_helpers() {
         let ipv4_addr: SocketAddr = "192.168.1.1:9000".parse().unwrap();
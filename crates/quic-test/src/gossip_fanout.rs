@@ -0,0 +1,264 @@
+//! Gossip fanout sweep experiment.
+//!
+//! Simulates epidemic broadcast over a random HyParView-like overlay to
+//! show how [`GossipConfig::gossip_fanout`](crate::gossip::GossipConfig)
+//! trades convergence speed against bandwidth. Every node keeps an active
+//! view of random neighbours; on first receipt of a message it forwards it
+//! to `fanout` of them, chosen with the same [`select_fanout`] the live
+//! node uses. One round is one hop, so convergence time is rounds times the
+//! configured hop latency.
+//!
+//! The simulation is seeded, so a sweep is reproducible run to run.
+
+use crate::gossip::select_fanout;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+
+/// Fanout values swept when none are given.
+pub const DEFAULT_SWEEP_FANOUTS: &[usize] = &[1, 2, 3, 4, 6, 8];
+
+/// Parameters of a fanout sweep.
+#[derive(Debug, Clone)]
+pub struct FanoutSweepConfig {
+    /// Simulated network size.
+    pub nodes: usize,
+    /// Neighbours each node picks for its active view.
+    pub active_view_size: usize,
+    /// Fanout values to compare.
+    pub fanouts: Vec<usize>,
+    /// Broadcasts simulated per fanout value.
+    pub trials: usize,
+    /// Latency of one gossip hop (ms).
+    pub hop_latency_ms: u64,
+    /// RNG seed for the overlay and forwarding choices.
+    pub seed: u64,
+}
+
+impl Default for FanoutSweepConfig {
+    fn default() -> Self {
+        Self {
+            nodes: 100,
+            active_view_size: 8,
+            fanouts: DEFAULT_SWEEP_FANOUTS.to_vec(),
+            trials: 20,
+            hop_latency_ms: 50,
+            seed: 0,
+        }
+    }
+}
+
+/// Outcome of the broadcasts run with one fanout value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanoutResult {
+    /// Fanout under test.
+    pub fanout: usize,
+    /// Mean fraction of nodes reached (0.0-1.0).
+    pub coverage: f64,
+    /// Broadcasts that reached every node.
+    pub converged_trials: usize,
+    /// Trials run.
+    pub trials: usize,
+    /// Mean hops until the last node was reached, over converged trials.
+    pub rounds: Option<f64>,
+    /// Mean convergence time (ms), over converged trials.
+    pub convergence_ms: Option<u64>,
+    /// Mean messages sent per broadcast.
+    pub messages: f64,
+}
+
+/// Results of a sweep, one row per fanout value.
+#[derive(Debug, Clone)]
+pub struct FanoutSweep {
+    /// Simulated network size.
+    pub nodes: usize,
+    /// Active view size used for the overlay.
+    pub active_view_size: usize,
+    /// One result per fanout, in sweep order.
+    pub results: Vec<FanoutResult>,
+}
+
+/// Random symmetric overlay: each node links to `view` others.
+fn build_overlay(nodes: usize, view: usize, rng: &mut StdRng) -> Vec<Vec<usize>> {
+    let mut neighbours = vec![Vec::new(); nodes];
+    let view = view.min(nodes.saturating_sub(1));
+    for node in 0..nodes {
+        for pick in sample(rng, nodes - 1, view) {
+            // Skip over ourselves
+            let other = if pick >= node { pick + 1 } else { pick };
+            if !neighbours[node].contains(&other) {
+                neighbours[node].push(other);
+                neighbours[other].push(node);
+            }
+        }
+    }
+    neighbours
+}
+
+/// Broadcast from `origin`; returns (nodes reached, rounds, messages sent).
+fn broadcast(
+    overlay: &[Vec<usize>],
+    origin: usize,
+    fanout: usize,
+    rng: &mut StdRng,
+) -> (usize, usize, u64) {
+    let mut received = vec![false; overlay.len()];
+    received[origin] = true;
+    let mut reached = 1;
+    let mut rounds = 0;
+    let mut messages = 0u64;
+    let mut frontier = vec![origin];
+    while !frontier.is_empty() {
+        let mut next = Vec::new();
+        for node in frontier {
+            for target in select_fanout(overlay[node].clone(), fanout, rng) {
+                messages += 1;
+                if !received[target] {
+                    received[target] = true;
+                    reached += 1;
+                    next.push(target);
+                }
+            }
+        }
+        if !next.is_empty() {
+            rounds += 1;
+        }
+        frontier = next;
+    }
+    (reached, rounds, messages)
+}
+
+/// Run the sweep described by `config`.
+pub fn run_fanout_sweep(config: &FanoutSweepConfig) -> anyhow::Result<FanoutSweep> {
+    if config.nodes < 2 {
+        anyhow::bail!("fanout sweep needs at least 2 nodes");
+    }
+    if config.fanouts.is_empty() || config.fanouts.contains(&0) {
+        anyhow::bail!("fanout values must be at least 1");
+    }
+    let trials = config.trials.max(1);
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let overlay = build_overlay(config.nodes, config.active_view_size, &mut rng);
+
+    let results = config
+        .fanouts
+        .iter()
+        .map(|&fanout| {
+            let mut reached_total = 0usize;
+            let mut messages_total = 0u64;
+            let mut converged_rounds = Vec::new();
+            for trial in 0..trials {
+                let origin = trial % config.nodes;
+                let (reached, rounds, messages) = broadcast(&overlay, origin, fanout, &mut rng);
+                reached_total += reached;
+                messages_total += messages;
+                if reached == config.nodes {
+                    converged_rounds.push(rounds);
+                }
+            }
+            let rounds = (!converged_rounds.is_empty()).then(|| {
+                converged_rounds.iter().sum::<usize>() as f64 / converged_rounds.len() as f64
+            });
+            FanoutResult {
+                fanout,
+                coverage: reached_total as f64 / (trials * config.nodes) as f64,
+                converged_trials: converged_rounds.len(),
+                trials,
+                rounds,
+                convergence_ms: rounds.map(|r| (r * config.hop_latency_ms as f64).round() as u64),
+                messages: messages_total as f64 / trials as f64,
+            }
+        })
+        .collect();
+
+    Ok(FanoutSweep {
+        nodes: config.nodes,
+        active_view_size: config.active_view_size,
+        results,
+    })
+}
+
+impl std::fmt::Display for FanoutSweep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Gossip fanout sweep ({} nodes, active view {})",
+            self.nodes, self.active_view_size
+        )?;
+        writeln!(
+            f,
+            "{:>6}  {:>8}  {:>9}  {:>6}  {:>9}  {:>8}",
+            "Fanout", "Coverage", "Converged", "Rounds", "Time (ms)", "Messages"
+        )?;
+        for r in &self.results {
+            writeln!(
+                f,
+                "{:>6}  {:>7.1}%  {:>9}  {:>6}  {:>9}  {:>8.0}",
+                r.fanout,
+                r.coverage * 100.0,
+                format!("{}/{}", r.converged_trials, r.trials),
+                r.rounds
+                    .map(|r| format!("{:.1}", r))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.convergence_ms
+                    .map(|ms| ms.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                r.messages
+            )?;
+        }
+        if let Some(capped) = self
+            .results
+            .iter()
+            .map(|r| r.fanout)
+            .find(|&fanout| fanout > self.active_view_size)
+        {
+            writeln!(
+                f,
+                "Note: fanout {} exceeds the active view; each node has about {} neighbours to push to",
+                capped, self.active_view_size
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wider_fanout_converges_faster_for_more_messages() {
+        let config = FanoutSweepConfig {
+            fanouts: vec![1, 4, 8],
+            ..Default::default()
+        };
+        let sweep = run_fanout_sweep(&config).unwrap();
+        let [narrow, mid, wide] = sweep.results.as_slice() else {
+            panic!("one row per fanout");
+        };
+
+        // Fanout 1 is a random walk that dies out long before covering 100 nodes
+        assert_eq!(narrow.converged_trials, 0);
+        assert!(narrow.coverage < mid.coverage);
+        assert_eq!(wide.converged_trials, wide.trials);
+        assert!(wide.rounds.unwrap() <= mid.rounds.unwrap_or(f64::MAX));
+        assert!(narrow.messages < mid.messages && mid.messages < wide.messages);
+        assert_eq!(
+            wide.convergence_ms,
+            wide.rounds.map(|r| (r * 50.0).round() as u64)
+        );
+
+        // Seeded, so the table is reproducible
+        let again = run_fanout_sweep(&config).unwrap();
+        assert_eq!(again.results, sweep.results);
+        let table = sweep.to_string();
+        assert!(table.contains("Fanout  Coverage  Converged"));
+        assert!(table.contains(&format!("{}/{}", wide.trials, wide.trials)));
+
+        let zero = FanoutSweepConfig {
+            fanouts: vec![2, 0],
+            ..Default::default()
+        };
+        assert!(run_fanout_sweep(&zero).is_err());
+    }
+}
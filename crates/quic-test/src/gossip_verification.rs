@@ -8,13 +8,29 @@
 //! It validates that these protocols are functioning correctly by checking
 //! their statistics against expected behavior and generating cryptographic
 //! proofs of correct operation.
-
-use crate::epidemic_gossip::GossipStats;
+//!
+//! Broadcast propagation latency is measured by injecting a tagged
+//! [`LatencyProbe`] on [`TOPIC_LATENCY_PROBE`]; each node reports its receipt
+//! time back through the registry heartbeat.
+//!
+//! Network partitions are found by treating every reported HyParView active
+//! view as a set of undirected edges and counting connected components; a
+//! healthy overlay is a single component even when per-node view sizes look
+//! fine.
+//!
+//! Plumtree's lazy-push recovery is checked by withholding one eager push
+//! from a chosen node and timing how long the node takes to obtain the
+//! message through IHAVE/GRAFT anti-entropy. A node that has not recovered
+//! within [`GossipVerifierConfig::max_recovery_rounds`] flush rounds fails
+//! Plumtree verification.
+
+use crate::epidemic_gossip::{AntiEntropyEvent, GossipStats};
+use crate::gossip::LatencyProbe;
 use crate::registry::{
     GossipProtocolProof, HyParViewProof, PlumtreeProof, ProofType, SignedAttestation, SwimProof,
 };
-use std::collections::HashMap;
-use std::time::SystemTime;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Configuration for gossip protocol verification.
 #[derive(Debug, Clone)]
@@ -29,10 +45,20 @@ pub struct GossipVerifierConfig {
     pub min_ping_rate: f64,
     /// Maximum acceptable false positive rate (0.0-1.0).
     pub max_false_positive_rate: f64,
+    /// Maximum (reporter, victim) pairs where SWIM declared a still
+    /// heartbeating node dead before verification fails.
+    pub max_swim_false_positives: usize,
     /// Minimum acceptable message delivery rate (0.0-1.0).
     pub min_delivery_rate: f64,
     /// Maximum acceptable failure detection latency (ms).
     pub max_failure_detection_ms: u64,
+    /// How long a node has to receive a latency probe before it counts as a
+    /// delivery failure (ms).
+    pub probe_timeout_ms: u64,
+    /// Length of one anti-entropy round, i.e. the IHAVE flush interval (ms).
+    pub anti_entropy_round_ms: u64,
+    /// Rounds a node has to recover a dropped eager push via IHAVE/GRAFT.
+    pub max_recovery_rounds: u64,
 }
 
 impl Default for GossipVerifierConfig {
@@ -43,8 +69,12 @@ impl Default for GossipVerifierConfig {
             min_shuffle_rate: 0.8,
             min_ping_rate: 0.9,
             max_false_positive_rate: 0.05,
+            max_swim_false_positives: 0,
             min_delivery_rate: 0.95,
             max_failure_detection_ms: 5000,
+            probe_timeout_ms: 10_000,
+            anti_entropy_round_ms: 100,
+            max_recovery_rounds: 5,
         }
     }
 }
@@ -65,6 +95,91 @@ pub struct GossipVerifier {
     plumtree_measurements: HashMap<String, PlumtreeMeasurements>,
     /// Actual HyParView measurements (not approximations).
     hyparview_measurements: HashMap<String, HyParViewMeasurements>,
+    /// Latency probes in flight (probe_id -> probe).
+    probes: HashMap<String, PendingProbe>,
+    /// Peers each node's SWIM instance has declared dead (reporter -> victims).
+    swim_dead: HashMap<String, HashSet<String>>,
+    /// Nodes the registry has seen heartbeat within its TTL.
+    known_alive: HashSet<String>,
+    /// HyParView active view reported by each node (node -> peers).
+    active_views: HashMap<String, Vec<String>>,
+    /// Eager pushes deliberately withheld ((message_id, node_id) -> drop).
+    dropped_eager: HashMap<(String, String), DroppedEager>,
+}
+
+/// An eager push withheld from one node, awaiting anti-entropy recovery.
+#[derive(Debug, Clone, Default)]
+struct DroppedEager {
+    dropped_at_ms: u64,
+    recovered_at_ms: Option<u64>,
+    graft_requests: u64,
+}
+
+/// How dropped eager pushes were recovered through IHAVE/GRAFT.
+///
+/// Recoveries slower than the round bound count as `unrecovered` and are
+/// left out of the latency, so a late recovery cannot pass as a fast one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AntiEntropyRecovery {
+    /// Eager pushes withheld.
+    pub dropped: usize,
+    /// Withheld pushes not recovered within the round bound.
+    pub unrecovered: usize,
+    /// Slowest in-bound recovery (ms).
+    pub max_latency_ms: Option<u64>,
+    /// Anti-entropy rounds the slowest in-bound recovery took.
+    pub max_rounds: Option<u64>,
+    /// GRAFT requests sent by the nodes recovering the messages.
+    pub graft_requests: u64,
+}
+
+/// A latency probe awaiting receipts from the expected nodes.
+#[derive(Debug, Clone)]
+struct PendingProbe {
+    sent_at_ms: u64,
+    expected_nodes: Vec<String>,
+    /// First receipt time reported by each node (node_id -> unix ms).
+    receipts: HashMap<String, u64>,
+}
+
+/// Broadcast propagation latency across all latency probes.
+///
+/// Nodes that did not receive a probe within the timeout are counted in
+/// `delivery_failures` and enter the percentiles at the timeout value, so a
+/// lossy broadcast cannot look fast by dropping its slowest nodes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BroadcastLatency {
+    /// Expected (probe, node) deliveries.
+    pub expected_deliveries: usize,
+    /// Deliveries missing or later than the probe timeout.
+    pub delivery_failures: usize,
+    /// Median propagation latency (ms).
+    pub p50_ms: Option<u64>,
+    /// 95th percentile propagation latency (ms).
+    pub p95_ms: Option<u64>,
+    /// 99th percentile propagation latency (ms).
+    pub p99_ms: Option<u64>,
+}
+
+impl BroadcastLatency {
+    /// Fraction of expected deliveries that arrived in time (1.0 if none expected).
+    pub fn delivery_rate(&self) -> f64 {
+        if self.expected_deliveries == 0 {
+            1.0
+        } else {
+            (self.expected_deliveries - self.delivery_failures) as f64
+                / self.expected_deliveries as f64
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[u64], pct: u64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct as usize * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
 }
 
 /// Timing measurements for protocol verification.
@@ -126,6 +241,11 @@ impl GossipVerifier {
             swim_measurements: HashMap::new(),
             plumtree_measurements: HashMap::new(),
             hyparview_measurements: HashMap::new(),
+            probes: HashMap::new(),
+            swim_dead: HashMap::new(),
+            known_alive: HashSet::new(),
+            active_views: HashMap::new(),
+            dropped_eager: HashMap::new(),
         }
     }
 
@@ -153,6 +273,84 @@ impl GossipVerifier {
             .push(delivery_time_ms);
     }
 
+    /// Create a tagged latency probe to publish on
+    /// [`TOPIC_LATENCY_PROBE`](crate::gossip::TOPIC_LATENCY_PROBE).
+    ///
+    /// The probe is tracked until [`clear`](Self::clear); every node in
+    /// `expected_nodes` is expected to report a receipt for it.
+    pub fn create_latency_probe(
+        &mut self,
+        origin_id: &str,
+        expected_nodes: Vec<String>,
+    ) -> LatencyProbe {
+        let probe = LatencyProbe {
+            probe_id: uuid::Uuid::new_v4().to_string(),
+            origin_id: origin_id.to_string(),
+            sent_at_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        };
+        self.track_latency_probe(&probe, expected_nodes);
+        probe
+    }
+
+    /// Track a latency probe that was published elsewhere.
+    pub fn track_latency_probe(&mut self, probe: &LatencyProbe, expected_nodes: Vec<String>) {
+        self.probes.insert(
+            probe.probe_id.clone(),
+            PendingProbe {
+                sent_at_ms: probe.sent_at_ms,
+                expected_nodes,
+                receipts: HashMap::new(),
+            },
+        );
+    }
+
+    /// Record when a node received a latency probe.
+    ///
+    /// Receipts for unknown probes are ignored; the earliest receipt per node wins.
+    pub fn record_probe_receipt(&mut self, probe_id: &str, node_id: &str, received_at_ms: u64) {
+        if let Some(probe) = self.probes.get_mut(probe_id) {
+            let receipt = probe
+                .receipts
+                .entry(node_id.to_string())
+                .or_insert(received_at_ms);
+            *receipt = (*receipt).min(received_at_ms);
+        }
+    }
+
+    /// Compute broadcast propagation latency percentiles across all probes.
+    pub fn broadcast_latency(&self) -> BroadcastLatency {
+        let timeout = self.config.probe_timeout_ms;
+        let mut result = BroadcastLatency::default();
+        let mut latencies = Vec::new();
+
+        for probe in self.probes.values() {
+            for node_id in &probe.expected_nodes {
+                result.expected_deliveries += 1;
+                let latency = probe
+                    .receipts
+                    .get(node_id)
+                    .map(|received| received.saturating_sub(probe.sent_at_ms))
+                    .filter(|latency| *latency <= timeout);
+                match latency {
+                    Some(latency) => latencies.push(latency),
+                    None => {
+                        result.delivery_failures += 1;
+                        latencies.push(timeout);
+                    }
+                }
+            }
+        }
+
+        latencies.sort_unstable();
+        result.p50_ms = percentile(&latencies, 50);
+        result.p95_ms = percentile(&latencies, 95);
+        result.p99_ms = percentile(&latencies, 99);
+        result
+    }
+
     /// Record actual SWIM measurements from a node.
     ///
     /// These are actual measurements, not approximations:
@@ -212,6 +410,170 @@ impl GossipVerifier {
         measurement.view_updates_from_shuffles += view_updates;
     }
 
+    /// Record the peers a node's SWIM failure detector currently considers dead.
+    ///
+    /// Replaces any list previously recorded for `node_id`.
+    pub fn record_swim_dead(&mut self, node_id: String, dead: Vec<String>) {
+        self.swim_dead.insert(node_id, dead.into_iter().collect());
+    }
+
+    /// Record the nodes known to be alive, e.g. the registry's peers that have
+    /// heartbeated within its TTL.
+    pub fn record_known_alive(&mut self, node_ids: impl IntoIterator<Item = String>) {
+        self.known_alive.extend(node_ids);
+    }
+
+    /// Record the peers in a node's HyParView active view.
+    ///
+    /// Replaces any view previously recorded for `node_id`.
+    pub fn record_active_view(&mut self, node_id: String, peers: Vec<String>) {
+        self.active_views.insert(node_id, peers);
+    }
+
+    /// Record the anti-entropy events `node_id`'s gossip layer observed.
+    pub fn record_anti_entropy(&mut self, node_id: &str, events: &[AntiEntropyEvent]) {
+        for event in events {
+            match event {
+                AntiEntropyEvent::EagerWithheld { message_id, at_ms } => {
+                    self.record_dropped_eager(message_id, node_id, *at_ms)
+                }
+                AntiEntropyEvent::GraftRequested { message_id } => {
+                    self.record_graft_request(message_id, node_id)
+                }
+                AntiEntropyEvent::Recovered { message_id, at_ms } => {
+                    self.record_recovery(message_id, node_id, *at_ms)
+                }
+            }
+        }
+    }
+
+    /// Record that the eager push of `message_id` was withheld from `node_id`.
+    pub fn record_dropped_eager(&mut self, message_id: &str, node_id: &str, dropped_at_ms: u64) {
+        self.dropped_eager.insert(
+            (message_id.to_string(), node_id.to_string()),
+            DroppedEager {
+                dropped_at_ms,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Record a GRAFT `node_id` sent to pull a withheld message.
+    ///
+    /// Ignored unless the push was recorded as dropped.
+    pub fn record_graft_request(&mut self, message_id: &str, node_id: &str) {
+        if let Some(drop) = self
+            .dropped_eager
+            .get_mut(&(message_id.to_string(), node_id.to_string()))
+        {
+            drop.graft_requests += 1;
+        }
+    }
+
+    /// Record when `node_id` obtained a withheld message.
+    ///
+    /// Ignored unless the push was recorded as dropped; the earliest time wins.
+    pub fn record_recovery(&mut self, message_id: &str, node_id: &str, recovered_at_ms: u64) {
+        if let Some(drop) = self
+            .dropped_eager
+            .get_mut(&(message_id.to_string(), node_id.to_string()))
+        {
+            let recovered = drop.recovered_at_ms.get_or_insert(recovered_at_ms);
+            *recovered = (*recovered).min(recovered_at_ms);
+        }
+    }
+
+    /// Summarise anti-entropy recovery of all dropped eager pushes.
+    pub fn anti_entropy_recovery(&self) -> AntiEntropyRecovery {
+        let round_ms = self.config.anti_entropy_round_ms.max(1);
+        let bound_ms = round_ms * self.config.max_recovery_rounds;
+        let mut result = AntiEntropyRecovery {
+            dropped: self.dropped_eager.len(),
+            ..Default::default()
+        };
+
+        for drop in self.dropped_eager.values() {
+            result.graft_requests += drop.graft_requests;
+            let latency = drop
+                .recovered_at_ms
+                .map(|recovered| recovered.saturating_sub(drop.dropped_at_ms))
+                .filter(|latency| *latency <= bound_ms);
+            match latency {
+                Some(latency) => {
+                    result.max_latency_ms = result.max_latency_ms.max(Some(latency));
+                }
+                None => result.unrecovered += 1,
+            }
+        }
+        result.max_rounds = result
+            .max_latency_ms
+            .map(|latency| latency.div_ceil(round_ms));
+        result
+    }
+
+    /// Connected components of the gossip overlay built from the recorded
+    /// active views, largest first, each with its members sorted.
+    ///
+    /// Active-view links are treated as undirected, and peers that appear in
+    /// someone's view without reporting their own still count as members.
+    /// Empty if no views were recorded.
+    pub fn detect_partitions(&self) -> Vec<Vec<String>> {
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (node, peers) in &self.active_views {
+            adjacency.entry(node).or_default();
+            for peer in peers.iter().filter(|peer| *peer != node) {
+                adjacency.entry(node).or_default().push(peer);
+                adjacency.entry(peer).or_default().push(node);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut partitions = Vec::new();
+        for start in adjacency.keys() {
+            if !seen.insert(*start) {
+                continue;
+            }
+            let mut members = BTreeSet::new();
+            let mut stack = vec![*start];
+            while let Some(node) = stack.pop() {
+                members.insert(node.to_string());
+                for next in &adjacency[node] {
+                    if seen.insert(*next) {
+                        stack.push(*next);
+                    }
+                }
+            }
+            partitions.push(members.into_iter().collect::<Vec<_>>());
+        }
+        partitions.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        partitions
+    }
+
+    /// Whether a HyParView proof passes and the overlay is not split.
+    fn hyparview_valid(&self, hyparview: &HyParViewProof) -> bool {
+        hyparview.is_valid() && self.detect_partitions().len() <= 1
+    }
+
+    /// (reporter, victim) pairs where SWIM declared a node dead while it was
+    /// still heartbeating to the registry, sorted.
+    pub fn swim_false_positives(&self) -> Vec<(String, String)> {
+        let pairs: BTreeSet<(String, String)> = self
+            .swim_dead
+            .iter()
+            .flat_map(|(reporter, dead)| {
+                dead.iter()
+                    .filter(move |victim| *victim != reporter && self.known_alive.contains(*victim))
+                    .map(move |victim| (reporter.clone(), victim.clone()))
+            })
+            .collect();
+        pairs.into_iter().collect()
+    }
+
+    /// Whether a SWIM proof passes, including the false-positive threshold.
+    fn swim_valid(&self, swim: &SwimProof) -> bool {
+        swim.is_valid() && swim.false_positive_pairs.len() <= self.config.max_swim_false_positives
+    }
+
     /// Get aggregated SWIM measurements across all nodes.
     fn get_aggregated_swim_measurements(&self) -> SwimMeasurements {
         let mut aggregate = SwimMeasurements::default();
@@ -357,6 +719,9 @@ impl GossipVerifier {
             proof.false_positive_rate = total_suspect as f64 / total_nodes.max(1) as f64;
         }
 
+        // Dead declarations contradicted by the registry's heartbeats
+        proof.false_positive_pairs = self.swim_false_positives();
+
         // Get failure detection latency from timing data
         if !self.timing_data.failure_detection.is_empty() {
             let avg_detection: u64 = self.timing_data.failure_detection.values().sum::<u64>()
@@ -523,7 +888,11 @@ impl GossipVerifier {
         let swim = self.verify_swim();
         let plumtree = self.verify_plumtree();
 
-        hyparview.is_valid() && swim.is_valid() && plumtree.is_valid()
+        self.hyparview_valid(&hyparview)
+            && self.swim_valid(&swim)
+            && plumtree.is_valid()
+            && self.broadcast_latency().delivery_rate() >= self.config.min_delivery_rate
+            && self.anti_entropy_recovery().unrecovered == 0
     }
 
     /// Get a summary of verification results.
@@ -531,21 +900,31 @@ impl GossipVerifier {
         let hyparview = self.verify_hyparview();
         let swim = self.verify_swim();
         let plumtree = self.verify_plumtree();
+        let broadcast = self.broadcast_latency();
+        let partitions = self.detect_partitions();
+        let recovery = self.anti_entropy_recovery();
 
         VerificationSummary {
-            hyparview_valid: hyparview.is_valid(),
+            hyparview_valid: self.hyparview_valid(&hyparview),
             hyparview_details: format!(
-                "active={}/{}, shuffle_rate={:.2}",
+                "active={}/{}, shuffle_rate={:.2}, components={}",
                 hyparview.active_view_size,
                 hyparview.expected_active_size,
-                hyparview.shuffle_success_rate
+                hyparview.shuffle_success_rate,
+                partitions.len()
             ),
-            swim_valid: swim.is_valid(),
+            swim_valid: self.swim_valid(&swim),
             swim_details: format!(
-                "ping_rate={:.2}, false_positive={:.2}, period_consistent={}",
-                swim.ping_success_rate, swim.false_positive_rate, swim.protocol_period_consistent
+                "ping_rate={:.2}, false_positive={:.2}, false_positive_pairs={}, period_consistent={}",
+                swim.ping_success_rate,
+                swim.false_positive_rate,
+                swim.false_positive_pairs.len(),
+                swim.protocol_period_consistent
             ),
-            plumtree_valid: plumtree.is_valid(),
+            swim_false_positives: swim.false_positive_pairs.clone(),
+            plumtree_valid: plumtree.is_valid()
+                && broadcast.delivery_rate() >= self.config.min_delivery_rate
+                && recovery.unrecovered == 0,
             plumtree_details: format!(
                 "delivery_rate={:.2}, tree_valid={}",
                 if plumtree.messages_broadcast > 0 {
@@ -555,7 +934,21 @@ impl GossipVerifier {
                 },
                 plumtree.tree_structure_valid
             ),
+            broadcast_latency_p50_ms: broadcast.p50_ms,
+            broadcast_latency_p95_ms: broadcast.p95_ms,
+            broadcast_latency_p99_ms: broadcast.p99_ms,
+            broadcast_delivery_failures: broadcast.delivery_failures,
+            anti_entropy_dropped: recovery.dropped,
+            anti_entropy_unrecovered: recovery.unrecovered,
+            anti_entropy_recovery_ms: recovery.max_latency_ms,
+            anti_entropy_rounds: recovery.max_rounds,
+            anti_entropy_graft_requests: recovery.graft_requests,
             nodes_analyzed: self.node_stats.len(),
+            partitions: if partitions.len() > 1 {
+                partitions
+            } else {
+                Vec::new()
+            },
         }
     }
 
@@ -566,6 +959,11 @@ impl GossipVerifier {
         self.swim_measurements.clear();
         self.plumtree_measurements.clear();
         self.hyparview_measurements.clear();
+        self.probes.clear();
+        self.swim_dead.clear();
+        self.known_alive.clear();
+        self.active_views.clear();
+        self.dropped_eager.clear();
     }
 }
 
@@ -586,12 +984,35 @@ pub struct VerificationSummary {
     pub swim_valid: bool,
     /// SWIM verification details.
     pub swim_details: String,
+    /// (reporter, victim) pairs where a heartbeating node was declared dead.
+    pub swim_false_positives: Vec<(String, String)>,
     /// Whether Plumtree passed verification.
     pub plumtree_valid: bool,
     /// Plumtree verification details.
     pub plumtree_details: String,
+    /// Median broadcast propagation latency (ms), if a probe was sent.
+    pub broadcast_latency_p50_ms: Option<u64>,
+    /// 95th percentile broadcast propagation latency (ms).
+    pub broadcast_latency_p95_ms: Option<u64>,
+    /// 99th percentile broadcast propagation latency (ms).
+    pub broadcast_latency_p99_ms: Option<u64>,
+    /// Probe deliveries that never arrived within the timeout.
+    pub broadcast_delivery_failures: usize,
+    /// Eager pushes deliberately withheld to exercise anti-entropy.
+    pub anti_entropy_dropped: usize,
+    /// Withheld pushes not recovered within the round bound.
+    pub anti_entropy_unrecovered: usize,
+    /// Slowest anti-entropy recovery (ms).
+    pub anti_entropy_recovery_ms: Option<u64>,
+    /// Anti-entropy rounds the slowest recovery took.
+    pub anti_entropy_rounds: Option<u64>,
+    /// GRAFT requests sent while recovering withheld pushes.
+    pub anti_entropy_graft_requests: u64,
     /// Number of nodes analyzed.
     pub nodes_analyzed: usize,
+    /// Members of each gossip partition, largest first. Empty unless the
+    /// overlay has split into more than one component.
+    pub partitions: Vec<Vec<String>>,
 }
 
 impl std::fmt::Display for VerificationSummary {
@@ -607,18 +1028,60 @@ impl std::fmt::Display for VerificationSummary {
             if self.hyparview_valid { "PASS" } else { "FAIL" },
             self.hyparview_details
         )?;
+        for (i, members) in self.partitions.iter().enumerate() {
+            writeln!(
+                f,
+                "    partition {} ({} nodes): {}",
+                i + 1,
+                members.len(),
+                members.join(", ")
+            )?;
+        }
         writeln!(
             f,
             "  SWIM:      {} - {}",
             if self.swim_valid { "PASS" } else { "FAIL" },
             self.swim_details
         )?;
+        for (reporter, victim) in &self.swim_false_positives {
+            writeln!(
+                f,
+                "    false positive: {} declared {} dead",
+                reporter, victim
+            )?;
+        }
         writeln!(
             f,
             "  Plumtree:  {} - {}",
             if self.plumtree_valid { "PASS" } else { "FAIL" },
             self.plumtree_details
-        )
+        )?;
+        if let (Some(p50), Some(p95), Some(p99)) = (
+            self.broadcast_latency_p50_ms,
+            self.broadcast_latency_p95_ms,
+            self.broadcast_latency_p99_ms,
+        ) {
+            writeln!(
+                f,
+                "  Broadcast: p50={}ms p95={}ms p99={}ms, {} delivery failure(s)",
+                p50, p95, p99, self.broadcast_delivery_failures
+            )?;
+        }
+        if self.anti_entropy_dropped > 0 {
+            write!(
+                f,
+                "  Anti-entropy: {}/{} dropped push(es) recovered",
+                self.anti_entropy_dropped - self.anti_entropy_unrecovered,
+                self.anti_entropy_dropped
+            )?;
+            if let (Some(ms), Some(rounds)) =
+                (self.anti_entropy_recovery_ms, self.anti_entropy_rounds)
+            {
+                write!(f, " in <={}ms ({} round(s))", ms, rounds)?;
+            }
+            writeln!(f, ", {} GRAFT(s)", self.anti_entropy_graft_requests)?;
+        }
+        Ok(())
     }
 }
 
@@ -650,6 +1113,7 @@ mod tests {
                 duplicates: 5,
                 grafts: 2,
                 prunes: 1,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -699,6 +1163,210 @@ mod tests {
         assert_eq!(proof.attestation.proof_type, ProofType::GossipProtocol);
     }
 
+    #[test]
+    fn test_broadcast_latency_counts_missing_receipts_as_failures() {
+        let mut verifier = GossipVerifier::with_config(GossipVerifierConfig {
+            probe_timeout_ms: 1000,
+            ..Default::default()
+        });
+        let probe = LatencyProbe {
+            probe_id: "probe-1".to_string(),
+            origin_id: "observer".to_string(),
+            sent_at_ms: 10_000,
+        };
+        let nodes: Vec<String> = (0..4).map(|i| format!("node{}", i)).collect();
+        verifier.track_latency_probe(&probe, nodes);
+        verifier.record_probe_receipt("probe-1", "node0", 10_050);
+        verifier.record_probe_receipt("probe-1", "node1", 10_200);
+        verifier.record_probe_receipt("probe-1", "node1", 10_100);
+        // node2 receives too late, node3 never reports.
+        verifier.record_probe_receipt("probe-1", "node2", 12_000);
+        verifier.record_probe_receipt("unknown", "node3", 10_010);
+
+        let latency = verifier.broadcast_latency();
+        assert_eq!(latency.expected_deliveries, 4);
+        assert_eq!(latency.delivery_failures, 2);
+        assert_eq!(latency.p50_ms, Some(100));
+        assert_eq!(latency.p95_ms, Some(1000));
+        assert_eq!(latency.p99_ms, Some(1000));
+
+        let summary = verifier.get_summary();
+        assert_eq!(summary.broadcast_latency_p50_ms, Some(100));
+        assert_eq!(summary.broadcast_delivery_failures, 2);
+        assert!(!summary.plumtree_valid);
+        assert!(summary.to_string().contains("2 delivery failure(s)"));
+
+        verifier.clear();
+        assert_eq!(verifier.broadcast_latency(), BroadcastLatency::default());
+    }
+
+    #[test]
+    fn test_swim_false_positives_cross_checked_with_registry() {
+        let mut verifier = GossipVerifier::new();
+        verifier.record_node_stats("node1".to_string(), make_test_stats());
+        verifier.record_node_stats("node2".to_string(), make_test_stats());
+        verifier.record_known_alive(["node1", "node2", "node3"].map(String::from));
+        // node4 really is gone; node3 is still heartbeating
+        verifier.record_swim_dead("node1".to_string(), vec!["node4".to_string()]);
+        assert!(verifier.swim_false_positives().is_empty());
+        assert!(verifier.get_summary().swim_valid);
+
+        verifier.record_swim_dead(
+            "node2".to_string(),
+            vec!["node3".to_string(), "node4".to_string()],
+        );
+        assert_eq!(
+            verifier.swim_false_positives(),
+            vec![("node2".to_string(), "node3".to_string())]
+        );
+        assert_eq!(verifier.verify_swim().false_positive_pairs.len(), 1);
+
+        let summary = verifier.get_summary();
+        assert!(!summary.swim_valid);
+        assert!(!verifier.all_protocols_valid());
+        assert!(
+            summary
+                .to_string()
+                .contains("false positive: node2 declared node3 dead")
+        );
+
+        // Tolerated up to the configured threshold
+        let mut lenient = GossipVerifier::with_config(GossipVerifierConfig {
+            max_swim_false_positives: 1,
+            ..Default::default()
+        });
+        lenient.record_node_stats("node1".to_string(), make_test_stats());
+        lenient.record_known_alive(["node3".to_string()]);
+        lenient.record_swim_dead(
+            "node1".to_string(),
+            vec![
+                "node3".to_string(),
+                "node4".to_string(),
+                "node5".to_string(),
+            ],
+        );
+        assert!(lenient.get_summary().swim_valid);
+    }
+
+    #[test]
+    fn test_partitions_detected_from_active_views() {
+        let mut verifier = GossipVerifier::new();
+        verifier.record_node_stats("a".to_string(), make_test_stats());
+        verifier.record_node_stats("d".to_string(), make_test_stats());
+        assert!(verifier.detect_partitions().is_empty());
+
+        // a-b-c form a chain; e only appears in d's view
+        verifier.record_active_view("a".to_string(), vec!["b".to_string()]);
+        verifier.record_active_view("b".to_string(), vec!["c".to_string()]);
+        verifier.record_active_view("d".to_string(), vec!["e".to_string()]);
+        assert_eq!(
+            verifier.detect_partitions(),
+            vec![
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                vec!["d".to_string(), "e".to_string()],
+            ]
+        );
+
+        let summary = verifier.get_summary();
+        assert!(!summary.hyparview_valid);
+        assert!(!verifier.all_protocols_valid());
+        assert_eq!(summary.partitions.len(), 2);
+        assert!(summary.to_string().contains("partition 2 (2 nodes): d, e"));
+
+        // One link heals the split
+        verifier.record_active_view("e".to_string(), vec!["c".to_string()]);
+        assert_eq!(verifier.detect_partitions().len(), 1);
+        let summary = verifier.get_summary();
+        assert!(summary.hyparview_valid);
+        assert!(summary.partitions.is_empty());
+    }
+
+    /// Plumtree rounds over `eager` and `lazy` links, with the eager push to
+    /// `withheld` dropped. Each round, holders announce the message by IHAVE
+    /// to lazy peers; a node missing it GRAFTs and has it by the round's end.
+    fn simulate_anti_entropy(
+        verifier: &mut GossipVerifier,
+        eager: &[(&str, &str)],
+        lazy: &[(&str, &str)],
+        withheld: &str,
+        rounds: u64,
+    ) {
+        let round_ms = verifier.config.anti_entropy_round_ms;
+        let sent_at = 1_000;
+        let mut holders: HashSet<&str> = HashSet::from(["origin"]);
+        let mut frontier = vec!["origin"];
+        while let Some(node) = frontier.pop() {
+            for &(_, to) in eager.iter().filter(|(from, _)| *from == node) {
+                if to == withheld {
+                    verifier.record_dropped_eager("msg-1", to, sent_at);
+                } else if holders.insert(to) {
+                    frontier.push(to);
+                }
+            }
+        }
+
+        for round in 1..=rounds {
+            let mut grafted = HashSet::new();
+            for &(from, to) in lazy {
+                if holders.contains(from) && !holders.contains(to) && grafted.insert(to) {
+                    verifier.record_graft_request("msg-1", to);
+                }
+            }
+            for node in grafted {
+                holders.insert(node);
+                verifier.record_recovery("msg-1", node, sent_at + round * round_ms);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dropped_eager_push_recovered_via_anti_entropy() {
+        let eager = [("origin", "a"), ("origin", "b"), ("a", "c")];
+
+        // c misses the push from a, but b announces it lazily
+        let mut verifier = GossipVerifier::new();
+        verifier.record_node_stats("a".to_string(), make_test_stats());
+        simulate_anti_entropy(&mut verifier, &eager, &[("b", "c")], "c", 10);
+        let recovery = verifier.anti_entropy_recovery();
+        assert_eq!(recovery.dropped, 1);
+        assert_eq!(recovery.unrecovered, 0);
+        assert_eq!(recovery.max_latency_ms, Some(100));
+        assert_eq!(recovery.max_rounds, Some(1));
+        assert_eq!(recovery.graft_requests, 1);
+
+        let summary = verifier.get_summary();
+        assert!(summary.plumtree_valid);
+        assert_eq!(summary.anti_entropy_graft_requests, 1);
+        assert!(
+            summary
+                .to_string()
+                .contains("1/1 dropped push(es) recovered in <=100ms (1 round(s)), 1 GRAFT(s)")
+        );
+
+        // Recovery must come within the round bound, not eventually: via an
+        // extra lazy hop it takes two rounds
+        let mut slow = GossipVerifier::with_config(GossipVerifierConfig {
+            max_recovery_rounds: 1,
+            ..Default::default()
+        });
+        slow.record_node_stats("a".to_string(), make_test_stats());
+        simulate_anti_entropy(&mut slow, &eager, &[("b", "d"), ("d", "c")], "c", 10);
+        let recovery = slow.anti_entropy_recovery();
+        assert_eq!((recovery.unrecovered, recovery.max_latency_ms), (1, None));
+        assert!(!slow.get_summary().plumtree_valid);
+
+        // Without a lazy link the message is simply lost
+        let mut lost = GossipVerifier::new();
+        lost.record_node_stats("a".to_string(), make_test_stats());
+        simulate_anti_entropy(&mut lost, &eager, &[], "c", 10);
+        let recovery = lost.anti_entropy_recovery();
+        assert_eq!((recovery.unrecovered, recovery.graft_requests), (1, 0));
+        assert!(!lost.all_protocols_valid());
+
+        lost.clear();
+        assert_eq!(lost.anti_entropy_recovery(), AntiEntropyRecovery::default());
+    }
+
     #[test]
     fn test_verification_summary() {
         let mut verifier = GossipVerifier::new();
@@ -1,8 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
 use uuid::Uuid;
 
-use super::{ArtifactManifest, AttemptResult, NatProfileSpec, ScenarioSpec};
+use super::{
+    ARTIFACT_CHUNK_SIZE, ArtifactChunkList, ArtifactEntry, ArtifactManifest, AttemptResult,
+    NatProfileSpec, ScenarioSpec,
+};
 use crate::registry::NatType;
 
 pub const FALLBACK_SOCKET_ADDR: SocketAddr =
@@ -214,6 +219,42 @@ pub struct GetResultsResponse {
     pub summary: Option<super::RunSummary>,
 }
 
+/// Ask which of an artifact's chunks the agent already holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactChunkQuery {
+    pub hashes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactChunkQueryResponse {
+    pub present: Vec<String>,
+}
+
+/// Assemble an uploaded artifact from its chunks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitArtifactRequest {
+    pub entry: ArtifactEntry,
+    pub chunks: ArtifactChunkList,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitArtifactResponse {
+    pub complete: bool,
+    /// Chunk hashes still missing; empty when `complete`.
+    pub missing: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Outcome of [`AgentClient::upload_artifact_resumable`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArtifactUploadReport {
+    pub chunks_total: usize,
+    pub chunks_uploaded: usize,
+    pub bytes_uploaded: u64,
+    /// Bytes the agent already had from an earlier attempt.
+    pub bytes_skipped: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApplyProfileRequest {
     pub profile: NatProfileSpec,
@@ -439,6 +480,9 @@ pub mod endpoints {
     pub const PROFILE_APPLY: &str = "/node/profile";
     pub const PROFILE_CLEAR: &str = "/node/profile/clear";
     pub const BARRIER: &str = "/barrier";
+    pub const ARTIFACT_CHUNKS: &str = "/run/{id}/artifacts/chunks";
+    pub const ARTIFACT_CHUNK: &str = "/run/{id}/artifacts/chunks/{hash}";
+    pub const ARTIFACT_COMMIT: &str = "/run/{id}/artifacts";
 
     // Monitoring API endpoints
     pub const API_PROBE: &str = "/api/probe";
@@ -669,6 +713,99 @@ impl AgentClient {
     pub fn apply_profile_url(&self) -> String {
         format!("{}{}", self.base_url, endpoints::PROFILE_APPLY)
     }
+
+    pub fn artifact_chunks_url(&self, run_id: Uuid) -> String {
+        format!("{}/run/{}/artifacts/chunks", self.base_url, run_id)
+    }
+
+    pub fn artifact_chunk_url(&self, run_id: Uuid, hash: &str) -> String {
+        format!("{}/run/{}/artifacts/chunks/{}", self.base_url, run_id, hash)
+    }
+
+    pub fn artifact_commit_url(&self, run_id: Uuid) -> String {
+        format!("{}/run/{}/artifacts", self.base_url, run_id)
+    }
+
+    /// Upload the artifact at `path` in content-addressed chunks.
+    ///
+    /// The agent is asked which chunks it already has and only the missing
+    /// ones are sent, so calling this again after an interrupted upload
+    /// resumes it instead of starting over.
+    pub async fn upload_artifact_resumable(
+        &self,
+        http: &reqwest::Client,
+        run_id: Uuid,
+        entry: &ArtifactEntry,
+        path: &Path,
+    ) -> anyhow::Result<ArtifactUploadReport> {
+        let file = path.to_path_buf();
+        let chunks = tokio::task::spawn_blocking(move || {
+            ArtifactChunkList::from_file(&file, ARTIFACT_CHUNK_SIZE)
+        })
+        .await??;
+
+        let query = ArtifactChunkQuery {
+            hashes: chunks.chunks.iter().map(|c| c.sha256.clone()).collect(),
+        };
+        let present: ArtifactChunkQueryResponse = http
+            .post(self.artifact_chunks_url(run_id))
+            .json(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let present: HashSet<String> = present.present.into_iter().collect();
+
+        let missing = chunks.missing(&present);
+        let mut report = ArtifactUploadReport {
+            chunks_total: chunks.chunks.len(),
+            ..Default::default()
+        };
+        let mut file = tokio::fs::File::open(path).await?;
+        for chunk in &missing {
+            use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+            let mut data = vec![0u8; chunk.size_bytes as usize];
+            file.seek(std::io::SeekFrom::Start(chunk.offset)).await?;
+            file.read_exact(&mut data).await?;
+            http.put(self.artifact_chunk_url(run_id, &chunk.sha256))
+                .body(data)
+                .send()
+                .await?
+                .error_for_status()?;
+            report.chunks_uploaded += 1;
+            report.bytes_uploaded += chunk.size_bytes;
+        }
+        report.bytes_skipped = chunks
+            .chunks
+            .iter()
+            .filter(|c| present.contains(&c.sha256))
+            .map(|c| c.size_bytes)
+            .sum();
+
+        let mut entry = entry.clone().with_size(chunks.size_bytes);
+        entry.sha256 = Some(chunks.sha256.clone());
+        let commit: CommitArtifactResponse = http
+            .post(self.artifact_commit_url(run_id))
+            .json(&CommitArtifactRequest { entry, chunks })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if !commit.complete {
+            anyhow::bail!(
+                "agent {} could not assemble {}: {}",
+                self.agent_id,
+                path.display(),
+                commit
+                    .error
+                    .unwrap_or_else(|| format!("{} chunks missing", commit.missing.len()))
+            );
+        }
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
@@ -690,6 +827,10 @@ mod tests {
 
         let run_id = Uuid::new_v4();
         assert!(client.status_url(run_id).contains(&run_id.to_string()));
+        assert_eq!(
+            client.artifact_chunk_url(run_id, "ab12"),
+            format!("http://localhost:8080/run/{}/artifacts/chunks/ab12", run_id)
+        );
     }
 
     #[test]
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -251,6 +253,130 @@ impl ArtifactBundle {
     }
 }
 
+/// Size of the content-addressed chunks artifacts are uploaded in.
+///
+/// Kept under the agent API's default 2 MiB request body limit.
+pub const ARTIFACT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// One content-addressed slice of an artifact file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactChunk {
+    /// Hex SHA-256 of the chunk contents.
+    pub sha256: String,
+    pub offset: u64,
+    pub size_bytes: u64,
+}
+
+/// An artifact file split into chunks, with the checksum of the whole file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactChunkList {
+    pub chunks: Vec<ArtifactChunk>,
+    pub sha256: String,
+    pub size_bytes: u64,
+}
+
+impl ArtifactChunkList {
+    /// Split the file at `path` into chunks of at most `chunk_size` bytes,
+    /// reading it one chunk at a time.
+    pub fn from_file(path: &Path, chunk_size: usize) -> io::Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+        let mut whole = Sha256::new();
+        let mut chunks = Vec::new();
+        let mut offset = 0u64;
+        let mut buf = vec![0u8; chunk_size.max(1)];
+
+        loop {
+            let len = read_full(&mut file, &mut buf)?;
+            if len == 0 {
+                break;
+            }
+            whole.update(&buf[..len]);
+            chunks.push(ArtifactChunk {
+                sha256: hex::encode(Sha256::digest(&buf[..len])),
+                offset,
+                size_bytes: len as u64,
+            });
+            offset += len as u64;
+        }
+
+        Ok(Self {
+            chunks,
+            sha256: hex::encode(whole.finalize()),
+            size_bytes: offset,
+        })
+    }
+
+    /// Chunks whose hashes are not in `present`, without repeats.
+    pub fn missing<'a>(&'a self, present: &HashSet<String>) -> Vec<&'a ArtifactChunk> {
+        let mut seen = HashSet::new();
+        self.chunks
+            .iter()
+            .filter(|c| !present.contains(&c.sha256) && seen.insert(c.sha256.as_str()))
+            .collect()
+    }
+
+    /// Concatenate the chunks stored as `chunk_dir/<sha256>` into `dest`,
+    /// verifying the whole-file checksum. Returns the hashes of chunks that
+    /// are not in `chunk_dir` yet; `dest` is only written when none are.
+    pub fn assemble(&self, chunk_dir: &Path, dest: &Path) -> io::Result<Vec<String>> {
+        let missing: Vec<String> = self
+            .chunks
+            .iter()
+            .filter(|c| !chunk_dir.join(&c.sha256).is_file())
+            .map(|c| c.sha256.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Ok(missing);
+        }
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial = dest.with_extension("partial");
+        let mut out = std::fs::File::create(&partial)?;
+        let mut whole = Sha256::new();
+        for chunk in &self.chunks {
+            let data = std::fs::read(chunk_dir.join(&chunk.sha256))?;
+            whole.update(&data);
+            out.write_all(&data)?;
+        }
+        out.sync_all()?;
+
+        let actual = hex::encode(whole.finalize());
+        if actual != self.sha256 {
+            let _ = std::fs::remove_file(&partial);
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("assembled artifact checksum {} != {}", actual, self.sha256),
+            ));
+        }
+        std::fs::rename(&partial, dest)?;
+        Ok(Vec::new())
+    }
+}
+
+/// Whether `path` is a relative path that stays inside the bundle root.
+pub fn is_bundle_relative(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Read until `buf` is full or EOF, returning the number of bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +415,46 @@ mod tests {
         assert_eq!(ArtifactType::Pcap.file_extension(), "pcap.zst");
         assert_eq!(ArtifactType::NatState.file_extension(), "json");
     }
+
+    #[test]
+    fn test_chunk_list_resumes_and_assembles() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("capture.pcap");
+        // Two identical leading chunks plus a short tail.
+        let mut data = vec![7u8; 8];
+        data.extend_from_slice(&[9u8; 3]);
+        std::fs::write(&src, &data).unwrap();
+
+        let list = ArtifactChunkList::from_file(&src, 4).unwrap();
+        assert_eq!(list.size_bytes, 11);
+        assert_eq!(list.chunks.len(), 3);
+        assert_eq!(list.chunks[0].sha256, list.chunks[1].sha256);
+        assert_eq!(list.chunks[2].offset, 8);
+        assert_eq!(list.missing(&HashSet::new()).len(), 2);
+
+        // Only the first chunk made it before the interruption.
+        let chunk_dir = dir.path().join("chunks");
+        std::fs::create_dir_all(&chunk_dir).unwrap();
+        std::fs::write(chunk_dir.join(&list.chunks[0].sha256), &data[..4]).unwrap();
+        let dest = dir.path().join("out/capture.pcap");
+        let missing = list.assemble(&chunk_dir, &dest).unwrap();
+        assert_eq!(missing, vec![list.chunks[2].sha256.clone()]);
+        assert!(!dest.exists());
+
+        let present: HashSet<String> = [list.chunks[0].sha256.clone()].into();
+        let remaining = list.missing(&present);
+        assert_eq!(remaining.len(), 1);
+        std::fs::write(chunk_dir.join(&remaining[0].sha256), &data[8..]).unwrap();
+
+        assert!(list.assemble(&chunk_dir, &dest).unwrap().is_empty());
+        assert_eq!(std::fs::read(&dest).unwrap(), data);
+    }
+
+    #[test]
+    fn test_is_bundle_relative() {
+        assert!(is_bundle_relative("pcap/attempt-0001.pcap.zst"));
+        assert!(!is_bundle_relative("../etc/passwd"));
+        assert!(!is_bundle_relative("/etc/passwd"));
+        assert!(!is_bundle_relative(""));
+    }
 }
@@ -26,6 +26,8 @@ pub struct AttemptResult {
     pub success: bool,
     pub path_used: Option<ConnectionMethod>,
     pub connect_time_ms: Option<u64>,
+    /// Round-trip time measured on the established connection.
+    pub rtt_ms: Option<u64>,
     pub error: Option<String>,
     pub failure_code: Option<FailureReasonCode>,
     pub failure_category: Option<FailureCategory>,
@@ -71,6 +73,7 @@ impl AttemptResult {
             success: false,
             path_used: None,
             connect_time_ms: None,
+            rtt_ms: None,
             error: None,
             failure_code: None,
             failure_category: None,
@@ -143,6 +146,10 @@ impl AttemptResult {
         self.failure_category = None;
     }
 
+    pub fn record_rtt(&mut self, rtt_ms: u64) {
+        self.rtt_ms = Some(rtt_ms);
+    }
+
     pub fn record_failure(
         &mut self,
         error: &str,
@@ -226,6 +233,101 @@ impl AttemptResult {
     pub fn to_jsonl(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
     }
+
+    /// Flatten into CSV rows matching [`RESULTS_CSV_HEADER`].
+    ///
+    /// One row per technique tried. `ip_version` is `v4` or `v6`, taken from
+    /// the technique when it names a family and otherwise from a single-stack
+    /// `ip_mode`; it is left empty when neither says. `rtt_ms` is filled on
+    /// successful rows from the recorded RTT. Attempts without a recorded
+    /// technique sequence produce a single row from `path_used`.
+    pub fn to_csv_rows(&self) -> Vec<String> {
+        let from = if self.peer_a_id.is_empty() {
+            &self.agent_a_id
+        } else {
+            &self.peer_a_id
+        };
+        let to = if self.peer_b_id.is_empty() {
+            &self.agent_b_id
+        } else {
+            &self.peer_b_id
+        };
+        let nat_pair = format!(
+            "{}/{}",
+            to_snake_case(&format!("{:?}", self.nat_a)),
+            to_snake_case(&format!("{:?}", self.nat_b)),
+        );
+        let row = |method: String, success: bool, technique: Option<ConnectionTechnique>| {
+            let ip_version = match technique {
+                Some(ConnectionTechnique::DirectIpv4 | ConnectionTechnique::MasqueRelayIpv4) => {
+                    "v4"
+                }
+                Some(ConnectionTechnique::DirectIpv6 | ConnectionTechnique::MasqueRelayIpv6) => {
+                    "v6"
+                }
+                _ => match self.ip_mode {
+                    IpMode::Ipv4Only => "v4",
+                    IpMode::Ipv6Only => "v6",
+                    IpMode::DualStack => "",
+                },
+            };
+            let rtt_ms = self.rtt_ms.filter(|_| success);
+            [
+                csv_field(from),
+                csv_field(to),
+                csv_field(&method),
+                ip_version.to_string(),
+                if success { "success" } else { "failure" }.to_string(),
+                rtt_ms.map(|ms| ms.to_string()).unwrap_or_default(),
+                csv_field(&nat_pair),
+            ]
+            .join(",")
+        };
+
+        if self.technique_sequence.is_empty() {
+            let method = self
+                .path_used
+                .map(|m| to_snake_case(&format!("{:?}", m)))
+                .unwrap_or_default();
+            return vec![row(method, self.success, None)];
+        }
+
+        self.technique_sequence
+            .iter()
+            .map(|t| {
+                row(
+                    to_snake_case(&format!("{:?}", t.technique)),
+                    t.success,
+                    Some(t.technique),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Column header for [`results_to_csv`].
+pub const RESULTS_CSV_HEADER: &str = "from,to,method,ip_version,outcome,rtt_ms,nat_pair";
+
+/// Export results as CSV with one row per pair-method, for notebook analysis.
+pub fn results_to_csv(results: &[AttemptResult]) -> String {
+    let mut csv = String::from(RESULTS_CSV_HEADER);
+    csv.push('\n');
+    for result in results {
+        for row in result.to_csv_rows() {
+            csv.push_str(&row);
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 /// IP addressing mode for connectivity testing.
@@ -842,6 +944,58 @@ mod tests {
         assert!(result.is_passing());
     }
 
+    #[test]
+    fn test_results_to_csv_one_row_per_pair_method() {
+        let mut punched = AttemptResult::new(Uuid::new_v4(), "test", 1)
+            .with_dimensions(NatType::FullCone, NatType::Symmetric, IpMode::DualStack)
+            .with_peers("peer-a", "peer-b");
+        punched.add_technique_result(TechniqueResult::failure(
+            ConnectionTechnique::DirectIpv4,
+            3000,
+            "timeout",
+        ));
+        punched.add_technique_result(TechniqueResult::success(
+            ConnectionTechnique::HolePunch,
+            120,
+        ));
+        punched.record_rtt(48);
+
+        let mut direct =
+            AttemptResult::new(Uuid::new_v4(), "test", 2).with_agents("agent-a", "agent-c");
+        direct.record_success(ConnectionMethod::Direct, 15, SuccessLevel::Usable);
+        direct.record_rtt(12);
+
+        let csv = results_to_csv(&[punched, direct]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0].split(',').collect::<Vec<_>>(),
+            vec![
+                "from",
+                "to",
+                "method",
+                "ip_version",
+                "outcome",
+                "rtt_ms",
+                "nat_pair"
+            ]
+        );
+        assert_eq!(
+            lines[1],
+            "peer-a,peer-b,direct_ipv4,v4,failure,,full_cone/symmetric"
+        );
+        assert_eq!(
+            lines[2],
+            "peer-a,peer-b,hole_punch,,success,48,full_cone/symmetric"
+        );
+        assert_eq!(
+            lines[3],
+            "agent-a,agent-c,direct,v4,success,12,unknown/unknown"
+        );
+        assert!(lines.iter().all(|l| l.split(',').count() == 7));
+    }
+
     #[test]
     fn test_attempt_result_failure() {
         let mut result = AttemptResult::new(Uuid::new_v4(), "test", 1);
@@ -328,6 +328,7 @@ impl LocalAgent {
                     u64::from(rtt),
                     SuccessLevel::Usable,
                 );
+                result.record_rtt(u64::from(rtt));
             } else {
                 result.record_failure(
                     "Simulated timeout",
@@ -0,0 +1,249 @@
+//! Scenario matrices loaded from YAML.
+//!
+//! A matrix file is a declarative [`ScenarioSpec`]: topology, NAT profiles by
+//! name, test matrix, timing and thresholds.
+//!
+//! ```yaml
+//! id: symmetric_vs_symmetric
+//! name: Symmetric vs Symmetric
+//! topology:
+//!   node_count: 2
+//!   topology_type: pair
+//!   agent_assignments:
+//!     - { agent_id: agent-a, role: initiator }
+//!     - { agent_id: agent-b, role: responder }
+//! nat_profiles: [symmetric]
+//! test_matrix:
+//!   nat_scenarios: [symmetric_both]
+//!   ip_modes: [ipv4_only]
+//!   test_patterns: [outbound]
+//!   attempts_per_cell: 10
+//! thresholds:
+//!   min_success_rate: 0.5
+//!   max_harness_failure_rate: 0.01
+//!   max_p95_latency_ms: 5000
+//!   min_data_proof_rate: 0.95
+//!   min_method_proof_rate: 0.9
+//! ```
+//!
+//! `description`, `suite`, `timing`, `artifacts`, `seed` and `metadata` are
+//! optional. NAT profile names are the built-in ones (`none`, `full_cone`,
+//! `address_restricted`, `port_restricted`, `symmetric`, `cgnat`, `hairpin`,
+//! `mobile_carrier`, `double_nat`). Errors name the offending field and line.
+
+use super::{
+    ArtifactSpec, NatBehaviorProfile, NatProfileSpec, ScenarioSpec, TestMatrixSpec, ThresholdSpec,
+    TimingSpec, TopologySpec, TopologyType,
+};
+use crate::registry::TestSuite;
+use anyhow::{Context, bail};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// On-disk shape of a matrix file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MatrixFile {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    suite: TestSuite,
+    topology: TopologySpec,
+    nat_profiles: Vec<String>,
+    test_matrix: TestMatrixSpec,
+    thresholds: ThresholdSpec,
+    #[serde(default)]
+    timing: TimingSpec,
+    #[serde(default)]
+    artifacts: ArtifactSpec,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl ScenarioSpec {
+    /// Load a scenario matrix from a YAML file.
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read matrix file {}", path.display()))?;
+        Self::from_yaml_str(&content)
+            .with_context(|| format!("Invalid matrix file {}", path.display()))
+    }
+
+    /// Parse a scenario matrix from YAML source.
+    pub fn from_yaml_str(source: &str) -> anyhow::Result<Self> {
+        // serde_yaml errors already carry the field path and line
+        let file: MatrixFile = serde_yaml::from_str(source)?;
+        let mut errors = Vec::new();
+
+        let mut nat_profiles = Vec::new();
+        for (i, name) in file.nat_profiles.iter().enumerate() {
+            match profile_by_name(name) {
+                Some(profile) => nat_profiles.push(profile),
+                None => errors.push(format!(
+                    "nat_profiles[{}]{}: unknown NAT profile '{}'",
+                    i,
+                    at_line(source, "nat_profiles", name),
+                    name
+                )),
+            }
+        }
+
+        let topology = &file.topology;
+        if topology.node_count != topology.agent_assignments.len() {
+            errors.push(format!(
+                "topology.node_count{}: {} nodes but {} agent assignments",
+                at_line(source, "topology", "node_count"),
+                topology.node_count,
+                topology.agent_assignments.len()
+            ));
+        }
+        if topology.topology_type == TopologyType::Pair && topology.node_count != 2 {
+            errors.push(format!(
+                "topology.node_count{}: pair topology needs exactly 2 nodes, got {}",
+                at_line(source, "topology", "node_count"),
+                topology.node_count
+            ));
+        }
+
+        let spec = Self {
+            id: file.id,
+            name: file.name,
+            description: file.description,
+            suite: file.suite,
+            topology: file.topology,
+            nat_profiles,
+            test_matrix: file.test_matrix,
+            thresholds: file.thresholds,
+            timing: file.timing,
+            artifacts: file.artifacts,
+            seed: file.seed,
+            metadata: file.metadata,
+        };
+        // Unknown profile names already leave nat_profiles short; don't report twice
+        match spec.validate() {
+            Err(invalid) if errors.is_empty() => errors.extend(invalid),
+            _ => {}
+        }
+
+        if !errors.is_empty() {
+            bail!("{}", errors.join("; "));
+        }
+        Ok(spec)
+    }
+
+    /// Check that enough agents are available to fill the topology.
+    pub fn check_agent_count(&self, agents: usize) -> anyhow::Result<()> {
+        if agents < self.topology.node_count {
+            bail!(
+                "Scenario '{}' needs {} agents for its topology, got {}",
+                self.id,
+                self.topology.node_count,
+                agents
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Resolve a built-in NAT profile by name (`full-cone` and `full_cone` both work).
+fn profile_by_name(name: &str) -> Option<NatProfileSpec> {
+    let name = name.replace('-', "_");
+    let mut standard = NatProfileSpec::all_standard();
+    standard.push(NatProfileSpec::cgnat());
+    standard
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .or_else(|| {
+            NatBehaviorProfile::all_profiles()
+                .into_iter()
+                .find(|profile| profile.name == name)
+                .map(|profile| profile.to_profile_spec())
+        })
+}
+
+/// " (line N)" for the first line mentioning `needle` inside the `section`
+/// block, or "" if it can't be found.
+fn at_line(source: &str, section: &str, needle: &str) -> String {
+    let header = format!("{}:", section);
+    source
+        .lines()
+        .enumerate()
+        .skip_while(|(_, line)| !line.starts_with(&header))
+        .find(|(_, line)| {
+            let value = line.strip_prefix(&header).unwrap_or(line);
+            value.contains(needle)
+        })
+        .map(|(i, _)| format!(" (line {})", i + 1))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{NatScenario, NatType};
+
+    const EXAMPLE: &str = include_str!("matrices/symmetric-vs-symmetric.yaml");
+
+    #[test]
+    fn test_example_matrix_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("matrix.yaml");
+        std::fs::write(&path, EXAMPLE).unwrap();
+
+        let spec = ScenarioSpec::from_yaml_file(&path).unwrap();
+        assert_eq!(spec.id, "symmetric_vs_symmetric");
+        assert_eq!(spec.topology.node_count, 2);
+        assert_eq!(spec.nat_profiles.len(), 1);
+        assert_eq!(spec.nat_profiles[0].nat_type, NatType::Symmetric);
+        assert_eq!(
+            spec.test_matrix.nat_scenarios,
+            vec![NatScenario::SymmetricBoth]
+        );
+        assert_eq!(spec.timing.attempt_timeout.as_secs(), 20);
+        assert!(spec.check_agent_count(2).is_ok());
+        assert!(spec.check_agent_count(1).is_err());
+    }
+
+    #[test]
+    fn test_invalid_matrices_point_at_field_and_line() {
+        let cases = [
+            (
+                EXAMPLE.replace("nat_profiles: [symmetric]", "nat_profiles: [symetric]"),
+                "nat_profiles[0] (line 17): unknown NAT profile 'symetric'",
+            ),
+            (
+                EXAMPLE.replace("node_count: 2", "node_count: 3"),
+                "topology.node_count (line 8): 3 nodes but 2 agent assignments",
+            ),
+            (
+                EXAMPLE.replace("attempts_per_cell: 10", "attempts_per_cell: ten"),
+                "test_matrix.attempts_per_cell: invalid type",
+            ),
+            (
+                EXAMPLE.replace("min_success_rate: 0.5", "min_success_rate: 1.5"),
+                "min_success_rate must be between 0.0 and 1.0",
+            ),
+        ];
+        for (source, expected) in cases {
+            let err = ScenarioSpec::from_yaml_str(&source).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(expected),
+                "{:#} should mention {}",
+                err,
+                expected
+            );
+        }
+
+        let err = ScenarioSpec::from_yaml_str(
+            &EXAMPLE.replace("attempts_per_cell: 10", "attempts_per_cell: ten"),
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("line 23"), "{:#}", err);
+    }
+}
@@ -36,7 +36,9 @@ pub mod failure_taxonomy;
 pub mod golden_fixtures;
 pub mod harness_metrics;
 pub mod local_agent;
+mod matrix_file;
 pub mod matrix_runner;
+mod nat_profile_file;
 pub mod replay_mode;
 pub mod run_recovery;
 pub mod scenario_spec;
@@ -0,0 +1,342 @@
+//! User-defined NAT behavior profiles loaded from TOML.
+//!
+//! A profile file describes one NAT in flat `key = value` form:
+//!
+//! ```toml
+//! name = "home_router"
+//! mapping = "endpoint_independent"       # or EIM / ADM / APDM
+//! filtering = "address_port_dependent"   # or EIF / ADF / APDF
+//! port_preservation = "preferred"        # preferred / not_preserved / overloaded
+//! port_range = [32768, 33023]            # optional
+//! ```
+//!
+//! Optional keys: `hairpin`, `mapping_timeout_secs`, `estimated_success_rate`,
+//! `docker_build_context`, `docker_image`, `iptables_rules`, `tc_rules`,
+//! `ci_compatible` and `test_priority`. Behavior fields that are not given
+//! default to the closest built-in NAT type.
+//!
+//! The `full-cone`, `symmetric` and `cgnat` presets are embedded in the binary
+//! so [`NatBehaviorProfile::resolve`] accepts them by name without a file.
+
+use super::{NatBehaviorProfile, NatProfileSpec};
+use crate::registry::{FilteringBehavior, MappingBehavior, NatBehavior, NatType, PortPreservation};
+use anyhow::{Context, bail};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Embedded preset profiles selectable by name (name, TOML source).
+const PRESETS: &[(&str, &str)] = &[
+    ("full-cone", include_str!("nat_profiles/full-cone.toml")),
+    ("symmetric", include_str!("nat_profiles/symmetric.toml")),
+    ("cgnat", include_str!("nat_profiles/cgnat.toml")),
+];
+
+/// On-disk layout of a profile file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    name: String,
+    mapping: String,
+    filtering: String,
+    port_preservation: String,
+    port_range: Option<(u16, u16)>,
+    hairpin: Option<bool>,
+    mapping_timeout_secs: Option<u32>,
+    estimated_success_rate: Option<f64>,
+    docker_build_context: Option<String>,
+    docker_image: Option<String>,
+    #[serde(default)]
+    iptables_rules: Vec<String>,
+    #[serde(default)]
+    tc_rules: Vec<String>,
+    ci_compatible: Option<bool>,
+    test_priority: Option<u8>,
+}
+
+impl NatBehaviorProfile {
+    /// Names of the embedded preset profiles.
+    #[must_use]
+    pub fn preset_names() -> Vec<&'static str> {
+        PRESETS.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// Look up an embedded preset by name (`full-cone` and `full_cone` both work).
+    #[must_use]
+    pub fn preset(name: &str) -> Option<Self> {
+        let name = name.replace('_', "-");
+        PRESETS
+            .iter()
+            .find(|(preset, _)| *preset == name)
+            .and_then(|(_, source)| Self::from_toml_str(source).ok())
+    }
+
+    /// Resolve a `--nat-profile` argument: a preset name, else a TOML file path.
+    pub fn resolve(name_or_path: &str) -> anyhow::Result<Self> {
+        if let Some(profile) = Self::preset(name_or_path) {
+            return Ok(profile);
+        }
+        let path = Path::new(name_or_path);
+        if !path.exists() {
+            bail!(
+                "Unknown NAT profile '{}': not a preset ({}) or an existing file",
+                name_or_path,
+                Self::preset_names().join(", ")
+            );
+        }
+        Self::from_toml_file(path)
+    }
+
+    /// Load a profile from a TOML file.
+    pub fn from_toml_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read NAT profile {}", path.display()))?;
+        Self::from_toml_str(&content)
+            .with_context(|| format!("Invalid NAT profile {}", path.display()))
+    }
+
+    /// Parse a profile from TOML source.
+    pub fn from_toml_str(source: &str) -> anyhow::Result<Self> {
+        let file: ProfileFile = toml::from_str(source)?;
+
+        let mapping = parse_mapping(&file.mapping)?;
+        let filtering = parse_filtering(&file.filtering)?;
+        let port_preservation = parse_port_preservation(&file.port_preservation)?;
+        if matches!(file.port_range, Some((low, high)) if low > high) {
+            bail!("'port_range' must be [low, high] with low <= high");
+        }
+
+        let mut behavior = NatBehavior::from_nat_type(closest_nat_type(
+            mapping,
+            filtering,
+            file.port_range.is_some(),
+        ));
+        behavior.mapping = mapping;
+        behavior.filtering = filtering;
+        behavior.port_preservation = port_preservation;
+        behavior.port_range = file.port_range;
+        if let Some(hairpin) = file.hairpin {
+            behavior.hairpin = hairpin;
+        }
+        if let Some(secs) = file.mapping_timeout_secs {
+            behavior.mapping_timeout_secs = secs;
+        }
+        if let Some(rate) = file.estimated_success_rate {
+            if !(0.0..=1.0).contains(&rate) {
+                bail!("'estimated_success_rate' must be between 0.0 and 1.0");
+            }
+            behavior.estimated_success_rate = rate;
+        }
+
+        Ok(Self {
+            name: file.name,
+            behavior,
+            docker_build_context: file.docker_build_context,
+            docker_image: file.docker_image,
+            iptables_rules: file.iptables_rules,
+            tc_rules: file.tc_rules,
+            network_impairment: None,
+            ci_compatible: file.ci_compatible.unwrap_or(true),
+            test_priority: file.test_priority.unwrap_or(10),
+        })
+    }
+
+    /// Describe behavior combinations that are valid but unusual in real NATs.
+    ///
+    /// RFC 4787 NATs filter at least as strictly as they map, so e.g. a
+    /// symmetric mapping with endpoint-independent filtering likely means the
+    /// profile was written by mistake.
+    #[must_use]
+    pub fn coherence_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mapping_rank = match self.behavior.mapping {
+            MappingBehavior::EndpointIndependent => 0,
+            MappingBehavior::AddressDependent => 1,
+            MappingBehavior::AddressPortDependent => 2,
+        };
+        let filtering_rank = match self.behavior.filtering {
+            FilteringBehavior::EndpointIndependent => 0,
+            FilteringBehavior::AddressDependent => 1,
+            FilteringBehavior::AddressPortDependent => 2,
+        };
+        if filtering_rank < mapping_rank {
+            warnings.push(format!(
+                "{} mapping with {} filtering is unusual: NATs normally filter at least as strictly as they map",
+                self.mapping_shorthand(),
+                self.filtering_shorthand()
+            ));
+        }
+        if self.behavior.port_preservation == PortPreservation::Overloaded {
+            warnings
+                .push("overloaded port preservation breaks most peer-to-peer traffic".to_string());
+        }
+        warnings
+    }
+
+    /// Convert to the scenario-level profile sent to agents.
+    #[must_use]
+    pub fn to_profile_spec(&self) -> NatProfileSpec {
+        NatProfileSpec {
+            name: self.name.clone(),
+            nat_type: closest_nat_type(
+                self.behavior.mapping,
+                self.behavior.filtering,
+                self.behavior.port_range.is_some(),
+            ),
+            docker_image: self.docker_image.clone(),
+            iptables_rules: (!self.iptables_rules.is_empty()).then(|| self.iptables_rules.clone()),
+            tc_rules: (!self.tc_rules.is_empty()).then(|| self.tc_rules.clone()),
+            network_impairment: self.network_impairment.clone(),
+        }
+    }
+}
+
+/// The built-in NAT type whose behavior best matches the given fields.
+fn closest_nat_type(
+    mapping: MappingBehavior,
+    filtering: FilteringBehavior,
+    limited_ports: bool,
+) -> NatType {
+    match (mapping, filtering) {
+        (MappingBehavior::EndpointIndependent, FilteringBehavior::EndpointIndependent) => {
+            NatType::FullCone
+        }
+        (MappingBehavior::EndpointIndependent, FilteringBehavior::AddressDependent) => {
+            NatType::AddressRestricted
+        }
+        (MappingBehavior::EndpointIndependent, FilteringBehavior::AddressPortDependent) => {
+            NatType::PortRestricted
+        }
+        _ if limited_ports => NatType::Cgnat,
+        _ => NatType::Symmetric,
+    }
+}
+
+fn parse_mapping(value: &str) -> anyhow::Result<MappingBehavior> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "endpoint_independent" | "eim" => MappingBehavior::EndpointIndependent,
+        "address_dependent" | "adm" => MappingBehavior::AddressDependent,
+        "address_port_dependent" | "apdm" => MappingBehavior::AddressPortDependent,
+        other => bail!("unknown mapping behavior '{}'", other),
+    })
+}
+
+fn parse_filtering(value: &str) -> anyhow::Result<FilteringBehavior> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "endpoint_independent" | "eif" => FilteringBehavior::EndpointIndependent,
+        "address_dependent" | "adf" => FilteringBehavior::AddressDependent,
+        "address_port_dependent" | "apdf" => FilteringBehavior::AddressPortDependent,
+        other => bail!("unknown filtering behavior '{}'", other),
+    })
+}
+
+fn parse_port_preservation(value: &str) -> anyhow::Result<PortPreservation> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "preferred" => PortPreservation::Preferred,
+        "not_preserved" => PortPreservation::NotPreserved,
+        "overloaded" => PortPreservation::Overloaded,
+        other => bail!("unknown port preservation '{}'", other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_match_builtin_profiles() {
+        for (name, builtin) in [
+            ("full-cone", NatBehaviorProfile::full_cone()),
+            ("symmetric", NatBehaviorProfile::symmetric()),
+            ("cgnat", NatBehaviorProfile::cgnat()),
+        ] {
+            let preset = NatBehaviorProfile::resolve(name).unwrap();
+            assert_eq!(preset.name, builtin.name);
+            assert_eq!(preset.behavior.mapping, builtin.behavior.mapping);
+            assert_eq!(preset.behavior.filtering, builtin.behavior.filtering);
+            assert_eq!(
+                preset.behavior.port_preservation,
+                builtin.behavior.port_preservation
+            );
+            assert_eq!(preset.behavior.port_range, builtin.behavior.port_range);
+            assert_eq!(preset.iptables_rules, builtin.iptables_rules);
+            assert!(preset.coherence_warnings().is_empty(), "{}", name);
+        }
+        assert_eq!(
+            NatBehaviorProfile::preset("full_cone")
+                .unwrap()
+                .to_profile_spec()
+                .nat_type,
+            NatType::FullCone
+        );
+    }
+
+    #[test]
+    fn test_custom_profile_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("odd.toml");
+        std::fs::write(
+            &path,
+            r#"
+# Symmetric mapping but anyone may send to a mapped port.
+name = "odd_nat"
+mapping = "APDM"
+filtering = "endpoint_independent"  # unusual
+port_preservation = "not_preserved"
+hairpin = true
+tc_rules = [
+    "qdisc add dev eth0 root netem delay 20ms",
+]
+"#,
+        )
+        .unwrap();
+
+        let profile = NatBehaviorProfile::resolve(path.to_str().unwrap()).unwrap();
+        assert_eq!(profile.name, "odd_nat");
+        assert_eq!(profile.rfc4787_classification(), "APDM/EIF");
+        assert!(profile.behavior.hairpin);
+        assert_eq!(profile.tc_rules.len(), 1);
+        assert_eq!(profile.test_priority, 10);
+
+        let warnings = profile.coherence_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("APDM mapping with EIF filtering"));
+    }
+
+    #[test]
+    fn test_invalid_profiles_rejected() {
+        let base = "name = \"x\"\nmapping = \"eim\"\nfiltering = \"eif\"\n";
+        let cases = [
+            (base.to_string(), "port_preservation"),
+            (
+                format!("{}port_preservation = \"preferred\"\nmaping = 1\n", base),
+                "unknown field `maping`",
+            ),
+            (
+                format!(
+                    "{}port_preservation = \"preferred\"\nport_range = [2, 1]\n",
+                    base
+                ),
+                "port_range",
+            ),
+            (
+                format!("{}port_preservation = true\n", base),
+                "expected a string",
+            ),
+            (
+                format!("[nat]\n{}port_preservation = \"preferred\"\n", base),
+                "unknown field `nat`",
+            ),
+        ];
+        for (source, expected) in cases {
+            let err = NatBehaviorProfile::from_toml_str(&source).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(expected),
+                "{:#} should mention {}",
+                err,
+                expected
+            );
+        }
+        assert!(NatBehaviorProfile::resolve("no-such-profile").is_err());
+    }
+}
@@ -1439,14 +1439,27 @@ pub struct TestMatrixSpec {
     pub nat_scenarios: Vec<NatScenario>,
     pub ip_modes: Vec<IpMode>,
     pub test_patterns: Vec<TestPattern>,
+    #[serde(default = "default_temporal_scenarios")]
     pub temporal_scenarios: Vec<TemporalScenario>,
+    #[serde(default = "default_network_profiles")]
     pub network_profiles: Vec<NetworkProfile>,
     pub attempts_per_cell: u32,
+    #[serde(default)]
     pub enable_migration_tests: bool,
+    #[serde(default)]
     pub enable_relay_tests: bool,
+    #[serde(default)]
     pub enable_gossip_tests: bool,
 }
 
+fn default_temporal_scenarios() -> Vec<TemporalScenario> {
+    vec![TemporalScenario::ColdStart]
+}
+
+fn default_network_profiles() -> Vec<NetworkProfile> {
+    vec![NetworkProfile::ideal()]
+}
+
 impl TestMatrixSpec {
     pub fn full_matrix() -> Self {
         Self {
@@ -64,11 +64,14 @@
 //! ```
 
 pub mod bootstrap_peers;
+pub mod clock;
 pub mod crdt_verification;
 pub mod dashboard;
 pub mod debug_automation;
+pub mod diagnostics;
 pub mod epidemic_gossip;
 pub mod gossip;
+pub mod gossip_fanout;
 pub mod gossip_tests;
 pub mod gossip_verification;
 pub mod harness;
@@ -78,21 +81,30 @@ pub mod lib_verification;
 // pub mod mcp;
 pub mod node;
 pub mod orchestrator;
+#[cfg(feature = "otlp")]
+pub mod otel;
 pub mod peer_discovery;
+#[cfg(all(feature = "flamegraph", unix))]
+pub mod profiling;
 pub mod proof_orchestrator;
 pub mod registry;
 pub mod tui;
 
 // Re-export key types for convenience
 pub use registry::{
+    ConnectionFunnel,
     ConnectionMethod,
     // Proof-based testing types
     CrdtConvergenceProof,
     CrdtOperation,
     CrdtType,
     CrossValidation,
+    EigenTrustConvergenceProof,
     GossipProtocolProof,
     HyParViewProof,
+    JoinTime,
+    JoinTimeSummary,
+    MatrixCoverage,
     NatType,
     NetworkConnectivityProof,
     NetworkEvent,
@@ -120,13 +132,21 @@ pub use tui::{
     TuiConfig, TuiEvent, run_tui, send_tui_event,
 };
 
-pub use node::{GlobalStats, TestNode, TestNodeConfig, TestPacket, TestResult};
+pub use node::{
+    DEFAULT_TEST_PACKET_SIZE, GlobalStats, MAX_TEST_PACKET_SIZE, TestNode, TestNodeConfig,
+    TestPacket, TestResult,
+};
 
 pub use gossip::{
-    CacheStatus, CoordinatorAnnouncement, GossipConfig, GossipDiscovery, GossipEvent,
-    GossipIntegration, GossipMetrics, PeerAnnouncement, PeerCapabilities, PeerConnectionQuery,
-    PeerConnectionResponse, RelayAnnouncement, TOPIC_COORDINATORS, TOPIC_PEER_QUERY,
-    TOPIC_PEER_RESPONSE, TOPIC_PEERS, TOPIC_RELAYS,
+    CacheStatus, CoordinatorAnnouncement, DEFAULT_ACTIVE_VIEW_SIZE, DEFAULT_PASSIVE_VIEW_SIZE,
+    GossipConfig, GossipDiscovery, GossipEvent, GossipIntegration, GossipMetrics, LatencyProbe,
+    PeerAnnouncement, PeerCapabilities, PeerConnectionQuery, PeerConnectionResponse,
+    RelayAnnouncement, RelaySelectionPolicy, TOPIC_COORDINATORS, TOPIC_LATENCY_PROBE,
+    TOPIC_PEER_QUERY, TOPIC_PEER_RESPONSE, TOPIC_PEERS, TOPIC_RELAYS, WireFormat, select_fanout,
+};
+
+pub use gossip_fanout::{
+    DEFAULT_SWEEP_FANOUTS, FanoutResult, FanoutSweep, FanoutSweepConfig, run_fanout_sweep,
 };
 
 pub use dashboard::{
@@ -162,20 +182,28 @@ pub use harness::{
     ThresholdSpec, TimingSpec, TopologySpec, TopologyType,
 };
 
-pub use gossip_verification::{GossipVerifier, GossipVerifierConfig, VerificationSummary};
+pub use gossip_verification::{
+    AntiEntropyRecovery, BroadcastLatency, GossipVerifier, GossipVerifierConfig,
+    VerificationSummary,
+};
 
 pub use crdt_verification::{
     ConflictResolutionResult, ConvergenceState, CrdtVerifier, CrdtVerifierConfig, OperationTracker,
     compute_state_hash,
 };
 
+pub use diagnostics::PeerSetDiff;
+
 pub use debug_automation::{
-    Anomaly, AutomatedDebugger, DebugReport, DebuggerConfig, ErrorPattern, LogEntry, RootCause,
-    Severity, SuggestedFix, Timeline,
+    Anomaly, AnomalyCategory, AutomatedDebugger, ConnectionOutcome, DebugReport, DebuggerConfig,
+    ErrorPattern, LogEntry, NAT_TYPE_CONTEXT, NODE_VERSION_CONTEXT, RootCause, Severity,
+    SuccessRateAlert, SuccessRateTrend, SuggestedFix, Timeline,
 };
 
 pub use proof_orchestrator::{
-    OrchestratorReport, ProofOrchestrator, ProofOrchestratorConfig, StepResult,
+    MAX_RUN_HISTORY, OrchestratorReport, OrchestratorReportJson, ProofOrchestrator,
+    ProofOrchestratorConfig, ProofOutcomeJson, ProofRunHistory, REPORT_SCHEMA_VERSION, ReportDiff,
+    StepResult,
 };
 
 pub use lib_verification::{
@@ -6,16 +6,47 @@
 //! for the large-scale ant-quic network testing infrastructure.
 
 use saorsa_quic_test::{
-    TestNode,
-    node::TestNodeConfig,
-    proof_orchestrator::{ProofOrchestrator, ProofOrchestratorConfig},
-    registry::{RegistryConfig, start_registry_server},
-    tui::{App, McpRequest, TuiEvent, run_tui},
+    DEFAULT_ACTIVE_VIEW_SIZE, DEFAULT_PASSIVE_VIEW_SIZE, FanoutSweepConfig, LatencyProbe, TestNode,
+    WireFormat,
+    bootstrap_peers::{BOOTSTRAP_ENV_VAR, bootstrap_addrs_all, bootstrap_override},
+    epidemic_gossip::{EpidemicConfig, EpidemicGossip},
+    gossip::serialize_latency_probe,
+    harness::IpMode,
+    node::{
+        DEFAULT_MAX_CONCURRENT_DIALS, DEFAULT_PEER_CACHE_TTL, DEFAULT_TEST_BURST_SIZE,
+        DEFAULT_TEST_PACKET_SIZE, DEREGISTER_TIMEOUT, MAX_TEST_PACKET_SIZE, NAT_DETECT_TIMEOUT,
+        TEST_PACKET_HEADER_SIZE, TestNodeConfig, detect_nat, load_peer_list, show_first_run_notice,
+    },
+    proof_orchestrator::{
+        IpVersion, OrchestratorReport, OrchestratorReportJson, ProofOrchestrator,
+        ProofOrchestratorConfig, ProofRunHistory,
+    },
+    registry::{
+        ADMIN_SECRET_ENV_VAR, ADMIN_TOKEN_ENV_VAR, AdminTlsConfig, RegistryClient, RegistryConfig,
+        load_blocklist, start_registry_server,
+    },
+    tui::{
+        App, McpRequest, ReplayControl, TuiEvent, history_to_events, load_history_file,
+        load_recorded_frames, replay_events, replay_frames, run_tui,
+    },
 };
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// How long `--check-registry` waits for the registry before giving up.
+const REGISTRY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long the proof run's gossip node joins the overlay before publishing
+/// its latency probe.
+const PROBE_JOIN_DELAY: Duration = Duration::from_secs(3);
+
+/// How long the proof run waits for nodes to report the probe in their
+/// heartbeats (two heartbeat intervals).
+const PROBE_REPORT_DELAY: Duration = Duration::from_secs(10);
+
 /// Command-line arguments for the test network binary.
 #[derive(Debug)]
 struct Args {
@@ -29,10 +60,17 @@ struct Args {
     quic_port: u16,
     /// QUIC bind port (for client mode)
     bind_port: u16,
-    /// Registry URL to connect to (for client mode)
+    /// Registry URL to connect to (for client mode), or a comma-separated
+    /// failover list
     registry_url: String,
     /// Maximum peer connections
     max_peers: usize,
+    /// Total size of each test packet in bytes
+    packet_size: usize,
+    /// Test packets per exchange, for jitter and loss measurement
+    burst_size: usize,
+    /// Outbound dials allowed in flight at once
+    max_concurrent_dials: usize,
     /// Disable TUI (log mode only)
     quiet: bool,
     /// Local-only mode: Disable external VPS connections (for Docker/local testing)
@@ -43,6 +81,88 @@ struct Args {
     gossip_first: bool,
     /// Custom data directory for identity and cache storage (enables unique identities per node)
     data_dir: Option<PathBuf>,
+    /// Run the gossip-vs-registry peer-set diagnostic and exit
+    diagnose: bool,
+    /// Check that `--registry-url` points at a working registry and exit
+    check_registry: bool,
+    /// File remembering dialed peers across restarts (default: in the data dir)
+    peer_cache: Option<PathBuf>,
+    /// Cached peers unseen for this long are pruned
+    peer_cache_ttl: Duration,
+    /// Classify the local NAT via bootstrap OBSERVED_ADDRESS reports and exit
+    detect_nat: bool,
+    /// Preset applied before explicit flags (if any)
+    profile: Option<Profile>,
+    /// How long in-flight work may finish after Ctrl+C before being aborted
+    shutdown_grace: Duration,
+    /// Stop the node cleanly after it has run this long
+    max_runtime: Option<Duration>,
+    /// Interval between registry heartbeats
+    heartbeat_interval: Duration,
+    /// Upper bound on a single outbound connection attempt
+    connect_timeout: Duration,
+    /// Unix socket to serve the JSON-RPC control API on
+    control_socket: Option<PathBuf>,
+    /// File listing peer IDs / CIDRs that must never be dialed
+    blacklist_file: Option<PathBuf>,
+    /// File listing the only peer IDs / CIDRs that may be dialed
+    allowlist_file: Option<PathBuf>,
+    /// NDJSON protocol frame recording to replay into the TUI (no network)
+    replay_frames: Option<PathBuf>,
+    /// Connectivity history file to replay into the TUI (no network)
+    replay: Option<PathBuf>,
+    /// Playback speed multiplier for `--replay`
+    replay_speed: f64,
+    /// Advertise this node as relay-capable
+    relay: bool,
+    /// Encode gossip announcements as binary instead of JSON
+    gossip_binary: bool,
+    /// Peers each gossip announcement is forwarded to (all connected if unset)
+    gossip_fanout: Option<usize>,
+    /// Target HyParView active view size
+    active_view_size: usize,
+    /// Target HyParView passive view size
+    passive_view_size: usize,
+    /// Run the simulated gossip fanout sweep over these values and exit
+    fanout_sweep: Option<Vec<usize>>,
+    /// Registry mode: serve only the API, not the web dashboard
+    no_dashboard: bool,
+    /// Registry mode: drop persisted connection history older than this
+    history_retention: Duration,
+    /// Registry mode: file listing peer IDs refused registration
+    peer_blocklist: Option<PathBuf>,
+    /// Registry mode: bearer token for `/api/admin/*`
+    admin_token: Option<String>,
+    /// Registry mode: proxies whose forwarding headers identify the client
+    trusted_proxies: Vec<std::net::IpAddr>,
+    /// Registry mode: serve `/api/admin/*` over mTLS with this certificate chain
+    admin_tls_cert: Option<PathBuf>,
+    /// Registry mode: private key for `admin_tls_cert`
+    admin_tls_key: Option<PathBuf>,
+    /// Registry mode: CA that admin client certificates must chain to
+    admin_client_ca: Option<PathBuf>,
+    /// Registry mode: port of the admin mTLS listener
+    admin_tls_port: u16,
+    /// Address families the proof test accepts
+    ip_mode: IpMode,
+    /// Write the proof test report as JSON to this file
+    report_json: Option<PathBuf>,
+    /// Write a failed proof test's debug report to this file as a GitHub issue
+    debug_issue: Option<PathBuf>,
+    /// Earlier JSON proof report to diff the current run against
+    baseline: Option<PathBuf>,
+    /// Re-run the proof test at this interval until interrupted
+    watch: Option<Duration>,
+    /// In watch mode, stop at the first failed run
+    fail_fast: bool,
+    /// Bootstrap peers (`host:port`) replacing the hardcoded VPS list
+    bootstrap: Vec<String>,
+    /// Endpoint for opt-in anonymized telemetry uploads
+    telemetry: Option<String>,
+    /// OTLP/HTTP collector to export tracing spans to
+    otlp_endpoint: Option<String>,
+    /// Write a CPU flamegraph of the run to this file on exit
+    profile_flamegraph: Option<PathBuf>,
 }
 
 impl Default for Args {
@@ -55,22 +175,194 @@ impl Default for Args {
             bind_port: 0,    // 0 = random available port
             registry_url: "https://saorsa-1.saorsalabs.com".to_string(),
             max_peers: 10,
+            packet_size: DEFAULT_TEST_PACKET_SIZE,
+            burst_size: DEFAULT_TEST_BURST_SIZE,
+            max_concurrent_dials: DEFAULT_MAX_CONCURRENT_DIALS,
             quiet: false,
             local_only: false, // Disabled by default - connect to external VPS nodes
             min_proof_nodes: 2,
             gossip_first: true, // Enabled by default - use epidemic gossip for peer discovery
             data_dir: None,     // Use default platform data directory
+            diagnose: false,
+            check_registry: false,
+            detect_nat: false,
+            peer_cache: None,
+            peer_cache_ttl: DEFAULT_PEER_CACHE_TTL,
+            profile: None,
+            shutdown_grace: Duration::from_secs(5),
+            max_runtime: None,
+            heartbeat_interval: Duration::from_secs(5),
+            connect_timeout: Duration::from_secs(30),
+            control_socket: None,
+            blacklist_file: None,
+            allowlist_file: None,
+            replay_frames: None,
+            replay: None,
+            replay_speed: 1.0,
+            relay: false,
+            gossip_binary: false,
+            gossip_fanout: None,
+            active_view_size: DEFAULT_ACTIVE_VIEW_SIZE,
+            passive_view_size: DEFAULT_PASSIVE_VIEW_SIZE,
+            fanout_sweep: None,
+            no_dashboard: false,
+            history_retention: Duration::from_secs(
+                saorsa_quic_test::registry::persistence::DEFAULT_HISTORY_RETENTION_SECS,
+            ),
+            peer_blocklist: None,
+            admin_token: None,
+            trusted_proxies: Vec::new(),
+            admin_tls_cert: None,
+            admin_tls_key: None,
+            admin_client_ca: None,
+            admin_tls_port: 8443,
+            ip_mode: IpMode::DualStack,
+            report_json: None,
+            debug_issue: None,
+            baseline: None,
+            watch: None,
+            fail_fast: false,
+            bootstrap: Vec::new(),
+            telemetry: None,
+            otlp_endpoint: None,
+            profile_flamegraph: None,
         }
     }
 }
 
+/// Wall-clock bound of the `ci` preset, so a stuck run can't hang the job.
+const CI_MAX_RUNTIME: Duration = Duration::from_secs(15 * 60);
+
+/// Named presets for common run configurations.
+///
+/// A preset only changes defaults: explicit flags on the command line always
+/// take precedence, regardless of where `--profile` appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Profile {
+    /// Local development against a registry on localhost
+    LocalDev,
+    /// Non-interactive, time-bounded CI run with no external VPS connections
+    Ci,
+    /// Long-running soak test with a larger peer budget
+    Soak,
+    /// Chaos testing with many peers and a larger proof quorum
+    Chaos,
+}
+
+impl Profile {
+    /// All presets, in the order they are documented.
+    const ALL: [Profile; 4] = [
+        Profile::LocalDev,
+        Profile::Ci,
+        Profile::Soak,
+        Profile::Chaos,
+    ];
+
+    /// Name used on the command line.
+    fn name(self) -> &'static str {
+        match self {
+            Profile::LocalDev => "local-dev",
+            Profile::Ci => "ci",
+            Profile::Soak => "soak",
+            Profile::Chaos => "chaos",
+        }
+    }
+
+    /// Apply the preset's defaults to `args`.
+    fn apply(self, args: &mut Args) {
+        match self {
+            Profile::LocalDev => {
+                args.local_only = true;
+                args.registry_url = "http://localhost:8080".to_string();
+                args.max_peers = 5;
+            }
+            Profile::Ci => {
+                args.quiet = true;
+                args.local_only = true;
+                args.max_peers = 5;
+                args.max_runtime = Some(CI_MAX_RUNTIME);
+            }
+            Profile::Soak => {
+                args.quiet = true;
+                args.max_peers = 50;
+            }
+            Profile::Chaos => {
+                args.quiet = true;
+                args.max_peers = 25;
+                args.min_proof_nodes = 5;
+            }
+        }
+        args.profile = Some(self);
+    }
+}
+
+impl std::str::FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Profile::ALL
+            .into_iter()
+            .find(|p| p.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Profile::ALL.iter().map(|p| p.name()).collect();
+                format!(
+                    "Unknown profile '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Parse a `--ip-mode` value.
+fn parse_ip_mode(s: &str) -> Result<IpMode, String> {
+    match s {
+        "dual" => Ok(IpMode::DualStack),
+        "v4-only" => Ok(IpMode::Ipv4Only),
+        "v6-only" => Ok(IpMode::Ipv6Only),
+        _ => Err(format!(
+            "Unknown IP mode '{}' (expected one of: dual, v4-only, v6-only)",
+            s
+        )),
+    }
+}
+
 fn parse_args() -> Args {
+    parse_args_from(std::env::args().skip(1).collect())
+}
+
+fn parse_args_from(argv: Vec<String>) -> Args {
     let mut args = Args::default();
-    let mut argv = std::env::args().skip(1);
 
+    // Apply the preset first so explicit flags override it regardless of order
+    if let Some(pos) = argv.iter().position(|a| a == "--profile") {
+        match argv.get(pos + 1).map(|name| name.parse::<Profile>()) {
+            Some(Ok(profile)) => profile.apply(&mut args),
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            None => {
+                eprintln!("--profile requires a name");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Repeated --registry-url flags add failover registries
+    let mut registry_urls: Vec<String> = Vec::new();
+    let mut argv = argv.into_iter();
     while let Some(arg) = argv.next() {
         match arg.as_str() {
+            "diagnose" => args.diagnose = true,
             "--registry" => args.registry = true,
+            "--check-registry" => args.check_registry = true,
+            "--detect-nat" => args.detect_nat = true,
+            "--no-dashboard" => args.no_dashboard = true,
+            "--profile" => {
+                // Already applied above
+                argv.next();
+            }
             "--port" => {
                 if let Some(port) = argv.next() {
                     if let Ok(p) = port.parse() {
@@ -80,7 +372,7 @@ fn parse_args() -> Args {
             }
             "--registry-url" => {
                 if let Some(url) = argv.next() {
-                    args.registry_url = url;
+                    registry_urls.push(url);
                 }
             }
             "--max-peers" => {
@@ -90,6 +382,101 @@ fn parse_args() -> Args {
                     }
                 }
             }
+            "--burst-size" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(size)) if size > 0 => args.burst_size = size,
+                Some(_) => {
+                    eprintln!("Invalid --burst-size: must be at least 1");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--burst-size requires a packet count");
+                    std::process::exit(1);
+                }
+            },
+            "--max-concurrent-dials" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(max)) if max > 0 => args.max_concurrent_dials = max,
+                Some(_) => {
+                    eprintln!("Invalid --max-concurrent-dials: must be at least 1");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--max-concurrent-dials requires a dial count");
+                    std::process::exit(1);
+                }
+            },
+            "--gossip-fanout" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(fanout)) if fanout > 0 => args.gossip_fanout = Some(fanout),
+                Some(_) => {
+                    eprintln!("Invalid --gossip-fanout: must be at least 1");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--gossip-fanout requires a peer count");
+                    std::process::exit(1);
+                }
+            },
+            "--active-view" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(size)) if size > 0 => args.active_view_size = size,
+                Some(_) => {
+                    eprintln!("Invalid --active-view: must be at least 1");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--active-view requires a peer count");
+                    std::process::exit(1);
+                }
+            },
+            "--passive-view" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(size)) if size > 0 => args.passive_view_size = size,
+                Some(_) => {
+                    eprintln!("Invalid --passive-view: must be at least 1");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--passive-view requires a peer count");
+                    std::process::exit(1);
+                }
+            },
+            "--fanout-sweep" => {
+                let fanouts = argv.next().map(|list| {
+                    list.split(',')
+                        .map(|v| v.trim().parse::<usize>())
+                        .collect::<Result<Vec<_>, _>>()
+                });
+                match fanouts {
+                    Some(Ok(fanouts)) if !fanouts.is_empty() && !fanouts.contains(&0) => {
+                        args.fanout_sweep = Some(fanouts)
+                    }
+                    Some(_) => {
+                        eprintln!(
+                            "Invalid --fanout-sweep: expected values of at least 1, e.g. 1,2,4,8"
+                        );
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--fanout-sweep requires a comma-separated list of fanouts");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--packet-size" => match argv.next().map(|s| s.parse::<usize>()) {
+                Some(Ok(size))
+                    if (TEST_PACKET_HEADER_SIZE..=MAX_TEST_PACKET_SIZE).contains(&size) =>
+                {
+                    args.packet_size = size
+                }
+                Some(_) => {
+                    eprintln!(
+                        "Invalid --packet-size: must be {}-{} bytes",
+                        TEST_PACKET_HEADER_SIZE, MAX_TEST_PACKET_SIZE
+                    );
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--packet-size requires a byte count");
+                    std::process::exit(1);
+                }
+            },
             "--bind-port" => {
                 if let Some(port) = argv.next() {
                     if let Ok(p) = port.parse() {
@@ -121,6 +508,259 @@ fn parse_args() -> Args {
                     args.data_dir = Some(PathBuf::from(dir));
                 }
             }
+            "--blacklist" => {
+                if let Some(file) = argv.next() {
+                    args.blacklist_file = Some(PathBuf::from(file));
+                }
+            }
+            "--peer-blocklist" => {
+                if let Some(file) = argv.next() {
+                    args.peer_blocklist = Some(PathBuf::from(file));
+                }
+            }
+            "--admin-token" | "--admin-secret" => {
+                if let Some(token) = argv.next() {
+                    args.admin_token = Some(token);
+                }
+            }
+            "--trusted-proxy" => {
+                if let Some(ip) = argv.next().and_then(|v| v.parse().ok()) {
+                    args.trusted_proxies.push(ip);
+                }
+            }
+            "--admin-tls-cert" => {
+                if let Some(file) = argv.next() {
+                    args.admin_tls_cert = Some(PathBuf::from(file));
+                }
+            }
+            "--admin-tls-key" => {
+                if let Some(file) = argv.next() {
+                    args.admin_tls_key = Some(PathBuf::from(file));
+                }
+            }
+            "--admin-client-ca" => {
+                if let Some(file) = argv.next() {
+                    args.admin_client_ca = Some(PathBuf::from(file));
+                }
+            }
+            "--admin-tls-port" => {
+                if let Some(port) = argv.next() {
+                    if let Ok(p) = port.parse() {
+                        args.admin_tls_port = p;
+                    }
+                }
+            }
+            "--allowlist" => {
+                if let Some(file) = argv.next() {
+                    args.allowlist_file = Some(PathBuf::from(file));
+                }
+            }
+            "--relay" => args.relay = true,
+            "--gossip-binary" => args.gossip_binary = true,
+            "--bootstrap" => {
+                if let Some(addr) = argv.next() {
+                    args.bootstrap.push(addr);
+                }
+            }
+            "--report-json" => {
+                if let Some(file) = argv.next() {
+                    args.report_json = Some(PathBuf::from(file));
+                }
+            }
+            "--debug-issue" => {
+                if let Some(file) = argv.next() {
+                    args.debug_issue = Some(PathBuf::from(file));
+                }
+            }
+            "--baseline" => {
+                if let Some(file) = argv.next() {
+                    args.baseline = Some(PathBuf::from(file));
+                }
+            }
+            "--watch" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(interval)) => args.watch = Some(interval),
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --watch: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--watch requires an interval");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--fail-fast" => args.fail_fast = true,
+            "--replay-frames" => {
+                if let Some(file) = argv.next() {
+                    args.replay_frames = Some(PathBuf::from(file));
+                }
+            }
+            "--replay" => {
+                if let Some(file) = argv.next() {
+                    args.replay = Some(PathBuf::from(file));
+                }
+            }
+            "--replay-speed" => match argv.next().map(|s| s.parse::<f64>()) {
+                Some(Ok(speed)) if speed > 0.0 && speed.is_finite() => args.replay_speed = speed,
+                Some(_) => {
+                    eprintln!("Invalid --replay-speed: must be a positive number");
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--replay-speed requires a multiplier");
+                    std::process::exit(1);
+                }
+            },
+            "--shutdown-grace" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(grace)) => args.shutdown_grace = grace,
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --shutdown-grace: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--shutdown-grace requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--max-runtime" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(runtime)) => args.max_runtime = Some(runtime),
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --max-runtime: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--max-runtime requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--control-socket" => {
+                if let Some(path) = argv.next() {
+                    args.control_socket = Some(PathBuf::from(path));
+                }
+            }
+            "--telemetry" => match argv.next() {
+                Some(url) => args.telemetry = Some(url),
+                None => {
+                    eprintln!("--telemetry requires an upload URL");
+                    std::process::exit(1);
+                }
+            },
+            "--otlp-endpoint" => {
+                if let Some(url) = argv.next() {
+                    args.otlp_endpoint = Some(url);
+                }
+            }
+            "--profile-flamegraph" => {
+                if let Some(path) = argv.next() {
+                    args.profile_flamegraph = Some(PathBuf::from(path));
+                }
+            }
+            "--heartbeat-interval" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(interval)) if !interval.is_zero() => args.heartbeat_interval = interval,
+                    Some(Ok(_)) => {
+                        eprintln!("--heartbeat-interval must be greater than zero");
+                        std::process::exit(1);
+                    }
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --heartbeat-interval: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--heartbeat-interval requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--connect-timeout" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(timeout)) if !timeout.is_zero() => args.connect_timeout = timeout,
+                    Some(Ok(_)) => {
+                        eprintln!("--connect-timeout must be greater than zero");
+                        std::process::exit(1);
+                    }
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --connect-timeout: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--connect-timeout requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--peer-cache" => {
+                if let Some(path) = argv.next() {
+                    args.peer_cache = Some(PathBuf::from(path));
+                }
+            }
+            "--peer-cache-ttl" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(ttl)) => args.peer_cache_ttl = ttl,
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --peer-cache-ttl: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--peer-cache-ttl requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--history-retention" => {
+                let parsed = argv
+                    .next()
+                    .map(|d| humantime_serde::re::humantime::parse_duration(&d));
+                match parsed {
+                    Some(Ok(retention)) => args.history_retention = retention,
+                    Some(Err(e)) => {
+                        eprintln!("Invalid --history-retention: {}", e);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("--history-retention requires a duration");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--ip-mode" => match argv.next().map(|m| parse_ip_mode(&m)) {
+                Some(Ok(mode)) => args.ip_mode = mode,
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+                None => {
+                    eprintln!("--ip-mode requires a mode");
+                    std::process::exit(1);
+                }
+            },
+            "--version-json" => {
+                println!("{}", version_json());
+                std::process::exit(0);
+            }
             "-h" | "--help" => {
                 print_help();
                 std::process::exit(0);
@@ -132,10 +772,33 @@ fn parse_args() -> Args {
             }
         }
     }
+    if !registry_urls.is_empty() {
+        args.registry_url = registry_urls.join(",");
+    }
 
     args
 }
 
+/// Build provenance (version, commit, timestamp, features) as JSON.
+///
+/// The commit, timestamp and feature list are embedded by `build.rs`. The
+/// timestamp is `SOURCE_DATE_EPOCH` when set, otherwise the time the build
+/// script last ran (build script changes or a new git HEAD/ref), so source
+/// edits between commits keep the older timestamp.
+fn version_json() -> serde_json::Value {
+    let features: Vec<&str> = env!("SAORSA_ENABLED_FEATURES")
+        .split(',')
+        .filter(|f| !f.is_empty())
+        .collect();
+    serde_json::json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "git_commit": env!("SAORSA_GIT_COMMIT"),
+        "build_timestamp": env!("SAORSA_BUILD_TIMESTAMP").parse::<u64>().unwrap_or(0),
+        "features": features,
+    })
+}
+
 fn print_help() {
     println!(
         r#"
@@ -145,23 +808,96 @@ Large-scale network testing for quantum-secure P2P connectivity.
 
 USAGE:
     ant-quic-test [OPTIONS]
+    ant-quic-test diagnose [--registry-url <URL>] [--data-dir <DIR>]
+
+COMMANDS:
+    diagnose                Compare the cached gossip peer set with the registry's peers
 
 OPTIONS:
     --registry              Run as central registry server
     --proof-test            Run proof-based network verification test
+    --check-registry        Check --registry-url is reachable (5s timeout), print its peer
+                            count and version, and exit 0/1 without starting a node
+    --detect-nat            Dial the bootstrap nodes from --bind-port, classify the NAT from
+                            their observed addresses, print the reasoning and exit (~10s)
     --port <PORT>           HTTP server port (registry mode) [default: 8080]
+    --no-dashboard          Registry mode: serve only /api/* and /ws/live, no web UI
+    --history-retention <DUR>  Registry mode: drop stored connection history older than this [default: 7d]
+    --peer-blocklist <FILE> Registry mode: refuse peer IDs listed in FILE (one per line)
+    --admin-token <TOKEN>   Registry mode: enable /api/admin/* for `Authorization: Bearer <TOKEN>`
+                            (or set SAORSA_ADMIN_TOKEN; --admin-secret is an alias)
+    --trusted-proxy <IP>    Registry mode: believe X-Forwarded-For/X-Real-IP from this proxy
+                            (repeatable; otherwise clients are keyed on their socket address)
+    --admin-tls-cert <FILE> Registry mode: serve /api/admin/* only over mTLS, with this PEM cert chain
+    --admin-tls-key <FILE>  Registry mode: PEM private key for --admin-tls-cert
+    --admin-client-ca <FILE>  Registry mode: PEM CA that admin client certificates must chain to
+    --admin-tls-port <PORT> Registry mode: port of the admin mTLS listener [default: 8443]
     --quic-port <PORT>      QUIC port for address discovery (registry mode, 0 to disable) [default: 9001]
     --bind-port <PORT>      QUIC UDP bind port (client mode) [default: 0 = random]
-    --registry-url <URL>    Registry URL to connect to [default: https://saorsa-1.saorsalabs.com]
+    --registry-url <URL>    Registry URL to connect to; repeat or comma-separate for failover
+                            [default: https://saorsa-1.saorsalabs.com]
     --max-peers <N>         Maximum peer connections [default: 10]
+    --packet-size <BYTES>   Test packet size, up to 65536 (multi-datagram) [default: 5120]
+    --burst-size <N>        Test packets per exchange, for jitter and loss [default: 10]
+    --max-concurrent-dials <N>  Outbound connection attempts in flight at once; the rest
+                            queue [default: 16]
     --min-proof-nodes <N>   Minimum nodes for proof test [default: 2]
+    --ip-mode <MODE>        Proof test address families: dual, v4-only, v6-only [default: dual]
+    --report-json <FILE>    Write the proof test report to FILE as versioned JSON (for CI)
+    --debug-issue <FILE>    On proof test failure, write the debug report to FILE as a GitHub issue
+    --baseline <FILE>       Print what changed versus an earlier --report-json report
+    --watch <INTERVAL>      Re-run the proof test every INTERVAL (e.g. 5m) until Ctrl+C
+    --fail-fast             With --watch, stop at the first failed run
     --local-only            Disable external VPS connections (for Docker/local testing)
     --gossip-first          Use epidemic gossip for peer discovery (default: enabled)
     --no-gossip-first       Use registry-based peer discovery instead of gossip
     --data-dir <DIR>        Custom data directory for identity storage (enables unique node IDs)
+    --profile <NAME>        Apply a preset; explicit flags override it
+    --relay                 Advertise this node as able to relay traffic for others
+    --gossip-binary         Send gossip announcements as compact binary instead of JSON
+    --gossip-fanout <N>     Peers each gossip announcement is forwarded to [default: all connected]
+    --active-view <N>       HyParView active view size [default: 8]
+    --passive-view <N>      HyParView passive view size [default: 64]
+    --fanout-sweep <LIST>   Simulate gossip with each fanout in LIST (e.g. 1,2,4,8) and print
+                            convergence time and message counts, then exit
+    --bootstrap <HOST:PORT> Bootstrap peer replacing the hardcoded VPS list (repeatable)
+    --blacklist <FILE>      Never dial peer IDs or CIDRs listed in FILE (one per line)
+    --allowlist <FILE>      Only dial peer IDs or CIDRs listed in FILE; ignore other discovery
+    --replay-frames <FILE>  Replay an NDJSON protocol frame recording into the TUI (no network)
+    --replay <FILE>         Replay a connectivity history file into the TUI (Space pauses)
+    --replay-speed <N>      Playback speed multiplier for --replay (default: 1)
+    --shutdown-grace <DUR>  Time in-flight work gets to finish on Ctrl+C, e.g. 10s [default: 5s]
+    --max-runtime <DUR>     Deregister and exit cleanly after running this long, e.g. 30m
+    --control-socket <PATH> Serve a JSON-RPC control API on a Unix socket (connect_peer,
+                            get_stats, list_connections, run_nat_test)
+    --heartbeat-interval <DUR>
+                            Registry heartbeat cadence; keep under half the registry TTL [default: 5s]
+    --connect-timeout <DUR> Give up on a single outbound connection attempt after this long [default: 30s]
+    --telemetry <URL>       Hourly upload of anonymized NAT type, country and connection
+                            counts (no peer ID, no IP) to URL [default: off]
+    --otlp-endpoint <URL>   Export registration, discovery, dial and packet-exchange spans
+                            to an OTLP/HTTP collector, e.g. http://localhost:4318
+                            (requires the `otlp` build feature)
+    --profile-flamegraph <FILE>
+                            Sample CPU for the whole run and write an SVG flamegraph to
+                            FILE on exit (requires the `flamegraph` build feature, Unix)
+    --peer-cache <FILE>     Remember dialed peers across restarts in FILE
+                            [default: <data-dir>/known_peers.json]
+    --peer-cache-ttl <DUR>  Prune cached peers not seen for this long [default: 7d]
     -q, --quiet             Disable TUI, log mode only
+    --version-json          Print build provenance as JSON and exit
     -h, --help              Print this help message
 
+PROFILES:
+    local-dev               --local-only, registry http://localhost:8080, --max-peers 5
+    ci                      --quiet, --local-only, --max-peers 5, --max-runtime 15m
+    soak                    --quiet, --max-peers 50
+    chaos                   --quiet, --max-peers 25, --min-proof-nodes 5
+
+ENVIRONMENT:
+    SAORSA_BOOTSTRAP        Comma-separated HOST:PORT bootstrap peers
+                            (precedence: --bootstrap > SAORSA_BOOTSTRAP > built-in VPS list)
+
 EXAMPLES:
     # Run as registry server
     ant-quic-test --registry --port 8080
@@ -172,6 +908,9 @@ EXAMPLES:
     # Run proof-based verification test
     ant-quic-test --proof-test --registry-url https://saorsa-1.saorsalabs.com
 
+    # Soak: re-verify every 5 minutes and print the pass rate on Ctrl+C
+    ant-quic-test --proof-test --watch 5m
+
     # Run multiple local instances with unique identities
     ant-quic-test --data-dir /tmp/node-1 &
     ant-quic-test --data-dir /tmp/node-2 &
@@ -181,6 +920,15 @@ EXAMPLES:
 
     # Connect to custom registry
     ant-quic-test --registry-url https://my-registry.example.com
+
+    # Check a registry URL before joining
+    ant-quic-test --check-registry --registry-url https://my-registry.example.com
+
+    # Isolated testnet against our own bootstrap nodes
+    ant-quic-test --local-only --bootstrap 10.0.0.1:9000 --bootstrap 10.0.0.2:9000
+
+    # CI preset, but allow more peers
+    ant-quic-test --profile ci --max-peers 8
 "#
     );
 }
@@ -195,13 +943,68 @@ async fn main() -> anyhow::Result<()> {
     let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
 
     let args = parse_args();
+    if let Some(profile) = args.profile {
+        eprintln!("Using profile: {}", profile.name());
+    }
 
-    // Only initialize logging for non-TUI modes (registry or quiet)
+    // Only log to stderr in non-TUI modes (registry, quiet, or no TTY)
     // TUI mode handles its own display - tracing to stderr ruins the interface
-    if args.registry || args.quiet {
+    #[cfg(feature = "otlp")]
+    let _otlp = match args.otlp_endpoint.as_deref() {
+        Some(endpoint) => {
+            let log_to_stderr = args.registry
+                || args.quiet
+                || !std::io::IsTerminal::is_terminal(&std::io::stdout());
+            Some(saorsa_quic_test::otel::init_otlp_tracing(
+                endpoint,
+                log_to_stderr,
+            )?)
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "otlp"))]
+    if args.otlp_endpoint.is_some() {
+        anyhow::bail!("--otlp-endpoint requires a build with the `otlp` feature");
+    }
+    if (args.registry || args.quiet) && args.otlp_endpoint.is_none() {
         tracing_subscriber::fmt::init();
     }
 
+    #[cfg(all(feature = "flamegraph", unix))]
+    let _flamegraph = args
+        .profile_flamegraph
+        .as_deref()
+        .map(saorsa_quic_test::profiling::FlamegraphProfiler::start)
+        .transpose()?;
+    #[cfg(not(all(feature = "flamegraph", unix)))]
+    if args.profile_flamegraph.is_some() {
+        anyhow::bail!("--profile-flamegraph requires a Unix build with the `flamegraph` feature");
+    }
+
+    if args.check_registry {
+        return run_check_registry(&args).await;
+    }
+
+    if let Some(fanouts) = &args.fanout_sweep {
+        return run_fanout_sweep(fanouts);
+    }
+
+    if args.detect_nat {
+        return run_detect_nat(&args).await;
+    }
+
+    if args.diagnose {
+        return run_diagnose(&args).await;
+    }
+
+    if let Some(path) = &args.replay_frames {
+        return run_frame_replay(path).await;
+    }
+
+    if let Some(path) = &args.replay {
+        return run_history_replay(path, args.replay_speed).await;
+    }
+
     if args.registry {
         // Run as registry server
         println!("Starting registry server on port {}...", args.port);
@@ -217,6 +1020,31 @@ async fn main() -> anyhow::Result<()> {
             None
         };
 
+        let blocklist = match &args.peer_blocklist {
+            Some(path) => load_blocklist(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read blocklist {:?}: {}", path, e))?,
+            None => Default::default(),
+        };
+
+        let admin_tls = match (
+            &args.admin_tls_cert,
+            &args.admin_tls_key,
+            &args.admin_client_ca,
+        ) {
+            (None, None, None) => None,
+            (Some(cert), Some(key), Some(ca)) => Some(AdminTlsConfig {
+                bind_addr: format!("[::]:{}", args.admin_tls_port)
+                    .parse()
+                    .expect("valid admin TLS address"),
+                cert_path: cert.clone(),
+                key_path: key.clone(),
+                client_ca_path: ca.clone(),
+            }),
+            _ => anyhow::bail!(
+                "--admin-tls-cert, --admin-tls-key and --admin-client-ca must be given together"
+            ),
+        };
+
         let config = RegistryConfig {
             bind_addr: format!("[::]:{}", args.port)
                 .parse()
@@ -227,6 +1055,18 @@ async fn main() -> anyhow::Result<()> {
             cleanup_interval_secs: 30,
             data_dir: std::path::PathBuf::from("./data"),
             persistence_enabled: true,
+            serve_dashboard: !args.no_dashboard,
+            history_retention_secs: args.history_retention.as_secs(),
+            blocklist,
+            admin_token: args
+                .admin_token
+                .clone()
+                .or_else(|| std::env::var(ADMIN_TOKEN_ENV_VAR).ok())
+                .or_else(|| std::env::var(ADMIN_SECRET_ENV_VAR).ok())
+                .filter(|token| !token.is_empty()),
+            admin_tls,
+            trusted_proxies: args.trusted_proxies.clone(),
+            ..Default::default()
         };
 
         start_registry_server(config).await?;
@@ -256,29 +1096,84 @@ async fn main() -> anyhow::Result<()> {
         let (event_tx, event_rx) = mpsc::channel::<TuiEvent>(1000);
 
         // Create TUI application
-        let app = App::new();
+        let mut app = App::new();
+        app.test_packet_size = args.packet_size;
 
         // TODO: Re-enable MCP client when communitas-core crate linking is fixed
         // Create MCP request channel for TUI -> future MCP integration
-        let (mcp_request_tx, _mcp_request_rx) = mpsc::channel::<McpRequest>(100);
+        let (mcp_request_tx, mcp_request_rx) = mpsc::channel::<McpRequest>(100);
         // MCP client disabled - communitas-core dependency not linking correctly
         // let mcp_client: Option<std::sync::Arc<McpClient>> = None;
 
         // Use dual-stack (IPv6 + IPv4) by binding to [::] instead of 0.0.0.0
         // On most systems, [::]:port accepts both IPv4 and IPv6 connections
         let bind_addr: SocketAddr = format!("[::]:{}", args.bind_port).parse()?;
+        let blacklist = match &args.blacklist_file {
+            Some(path) => load_peer_list(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read blacklist {:?}: {}", path, e))?,
+            None => Vec::new(),
+        };
+        let allowlist = match &args.allowlist_file {
+            Some(path) => Some(
+                load_peer_list(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read allowlist {:?}: {}", path, e))?,
+            ),
+            None => None,
+        };
+        let bootstrap_peers = bootstrap_override(
+            &args.bootstrap,
+            std::env::var(BOOTSTRAP_ENV_VAR).ok().as_deref(),
+        )
+        .map_err(|e| anyhow::anyhow!(e))?;
         let node_config = TestNodeConfig {
             registry_url: args.registry_url.clone(),
             max_peers: args.max_peers,
+            test_packet_size: args.packet_size,
+            test_burst_size: args.burst_size,
+            max_concurrent_dials: args.max_concurrent_dials,
             bind_addr,
             local_only: args.local_only,
             gossip_first: args.gossip_first,
             data_dir: args.data_dir.clone(),
+            shutdown_grace: args.shutdown_grace,
+            max_runtime: args.max_runtime,
+            heartbeat_interval: args.heartbeat_interval,
+            connect_timeout: args.connect_timeout,
+            blacklist,
+            allowlist,
+            relay: args.relay,
+            gossip_wire_format: if args.gossip_binary {
+                WireFormat::Binary
+            } else {
+                WireFormat::Json
+            },
+            gossip_fanout: args.gossip_fanout,
+            active_view_size: args.active_view_size,
+            passive_view_size: args.passive_view_size,
+            bootstrap_peers,
+            telemetry_url: args.telemetry.clone(),
+            peer_cache_path: args.peer_cache.clone(),
+            peer_cache_ttl: args.peer_cache_ttl,
             ..Default::default()
         };
+        if let Some(url) = &args.telemetry {
+            show_first_run_notice(args.data_dir.as_ref(), url);
+        }
 
         let tui_event_tx = event_tx.clone();
-        let test_node = TestNode::new(node_config, event_tx).await?;
+        let test_node = Arc::new(TestNode::new(node_config, event_tx).await?);
+
+        // Held for the node's lifetime; dropping it removes the socket file
+        #[cfg(unix)]
+        let _control_socket = args
+            .control_socket
+            .as_deref()
+            .map(|path| test_node.serve_control_socket(path))
+            .transpose()?;
+        #[cfg(not(unix))]
+        if args.control_socket.is_some() {
+            eprintln!("WARNING: --control-socket is only supported on Unix; ignoring");
+        }
 
         let use_quiet_mode = args.quiet || !std::io::IsTerminal::is_terminal(&std::io::stdout());
 
@@ -287,7 +1182,10 @@ async fn main() -> anyhow::Result<()> {
             // CRITICAL: Initialize tracing for auto-detected quiet mode
             // The initial check at line 159 only inits when --quiet is explicit,
             // but when running as a systemd service without TTY, we also need logging!
-            tracing_subscriber::fmt::init();
+            // With --otlp-endpoint the stderr layer was installed up front.
+            if args.otlp_endpoint.is_none() {
+                tracing_subscriber::fmt::init();
+            }
         }
 
         if use_quiet_mode {
@@ -305,28 +1203,94 @@ async fn main() -> anyhow::Result<()> {
                 }
             });
 
+            // Ctrl+C or SIGTERM (systemd stop) stops new work; run() then
+            // drains in-flight tasks and deregisters from the registry
+            let signal_node = Arc::clone(&test_node);
+            let grace = args.shutdown_grace;
+            tokio::spawn(async move {
+                shutdown_signal().await;
+                println!(
+                    "Shutting down (waiting up to {:?} for in-flight work)...",
+                    grace
+                );
+                signal_node.shutdown();
+            });
+
             // Run test node directly
             test_node.run().await?;
         } else {
             // Spawn the test node in the background
-            let node_handle = tokio::spawn(async move {
-                if let Err(e) = test_node.run().await {
+            let run_node = Arc::clone(&test_node);
+            let mut node_handle = tokio::spawn(async move {
+                if let Err(e) = run_node.run().await {
                     tracing::error!("Test node error: {}", e);
                 }
             });
 
+            // Node-level MCP requests (connectivity sweeps) are served here;
+            // the rest wait for the MCP client
+            tokio::spawn(serve_node_mcp_requests(
+                Arc::clone(&test_node),
+                mcp_request_rx,
+            ));
+
             // Run TUI in foreground (MCP disabled - pass request channel for future use)
             let mcp_tx = Some(mcp_request_tx);
             run_tui(app, event_rx, tui_event_tx, mcp_tx).await?;
 
-            // When TUI exits, abort the node
-            node_handle.abort();
+            // When TUI exits, give the node its grace period before aborting.
+            // The extra time covers deregistering and saving the peer cache
+            // after draining.
+            test_node.shutdown();
+            let deadline = args.shutdown_grace + DEREGISTER_TIMEOUT + Duration::from_secs(1);
+            if tokio::time::timeout(deadline, &mut node_handle)
+                .await
+                .is_err()
+            {
+                node_handle.abort();
+            }
         }
     }
 
     Ok(())
 }
 
+/// Handle the MCP requests the test node can answer itself.
+///
+/// Sweeps run in their own task so a long sweep doesn't hold up the
+/// channel; the node rejects overlapping or too-frequent sweeps.
+async fn serve_node_mcp_requests(node: Arc<TestNode>, mut rx: mpsc::Receiver<McpRequest>) {
+    while let Some(request) = rx.recv().await {
+        match request {
+            McpRequest::RunConnectivitySweep => {
+                let node = Arc::clone(&node);
+                tokio::spawn(async move {
+                    if let Err(reason) = node.run_connectivity_sweep().await {
+                        tracing::info!("Connectivity sweep not started: {}", reason);
+                    }
+                });
+            }
+            other => tracing::debug!("No MCP client for request: {:?}", other),
+        }
+    }
+}
+
+/// Wait for Ctrl+C or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
 /// Convert NodeGossipStats to epidemic_gossip::GossipStats for the proof orchestrator.
 fn convert_gossip_stats(
     node_stats: &saorsa_quic_test::registry::NodeGossipStats,
@@ -355,9 +1319,11 @@ fn convert_gossip_stats(
             lazy_peers: node_stats.plumtree_lazy,
             messages_sent: node_stats.plumtree_sent,
             messages_received: node_stats.plumtree_received,
-            duplicates: 0,
-            grafts: 0,
-            prunes: 0,
+            duplicates: node_stats.plumtree_duplicates,
+            grafts: node_stats.plumtree_grafts_sent,
+            prunes: node_stats.plumtree_prunes_sent,
+            estimated_grafts: node_stats.plumtree_grafts_estimated,
+            estimated_prunes: node_stats.plumtree_prunes_estimated,
         },
         connection_types: ConnectionBreakdown {
             direct_ipv4: node_stats.conn_direct_ipv4,
@@ -408,14 +1374,140 @@ fn _format_last_seen(timestamp: i64) -> String {
     }
 }
 
-/// Run proof-based network verification test.
-async fn run_proof_test(args: &Args) -> anyhow::Result<()> {
-    use saorsa_quic_test::registry::RegistryClient;
+/// Report peers known to only one of gossip (local cache) and the registry.
+async fn run_diagnose(args: &Args) -> anyhow::Result<()> {
+    println!(
+        "Comparing gossip peer cache with registry {}...",
+        args.registry_url
+    );
+    let diff = saorsa_quic_test::diagnostics::diagnose_peer_sets(
+        &args.registry_url,
+        args.data_dir.as_ref(),
+    )
+    .await?;
+
+    println!("{}", diff.summary());
+    for peer_id in &diff.only_in_gossip {
+        println!("  gossip only:   {}", peer_id);
+    }
+    for peer_id in &diff.only_in_registry {
+        println!("  registry only: {}", peer_id);
+    }
+    Ok(())
+}
 
-    println!("Fetching peer list from registry...");
+/// Pre-flight check of `--registry-url`; errors make the process exit 1.
+async fn run_check_registry(args: &Args) -> anyhow::Result<()> {
+    println!("Checking registry {}...", args.registry_url);
+    let check = RegistryClient::new(&args.registry_url)
+        .check(REGISTRY_CHECK_TIMEOUT)
+        .await
+        .map_err(|e| anyhow::anyhow!("Registry {} is not usable: {}", args.registry_url, e))?;
+    println!(
+        "Registry OK: version {}, {} active peers ({} registered)",
+        check.version.as_deref().unwrap_or("unknown"),
+        check.active_nodes,
+        check.total_nodes
+    );
+    Ok(())
+}
 
-    // Create registry client
-    let client = RegistryClient::new(&args.registry_url);
+/// Classify the NAT in front of `--bind-port` and print how we got there.
+///
+/// Observers are the `--bootstrap` peers (or `SAORSA_BOOTSTRAP`), falling
+/// back to the built-in bootstrap nodes.
+async fn run_detect_nat(args: &Args) -> anyhow::Result<()> {
+    let mut observers = bootstrap_override(
+        &args.bootstrap,
+        std::env::var(BOOTSTRAP_ENV_VAR).ok().as_deref(),
+    )
+    .map_err(|e| anyhow::anyhow!(e))?;
+    if observers.is_empty() {
+        observers = bootstrap_addrs_all();
+    }
+    let bind_addr: SocketAddr = format!("[::]:{}", args.bind_port).parse()?;
+    println!(
+        "Detecting NAT type via {} observers (up to {}s)...",
+        observers.len(),
+        NAT_DETECT_TIMEOUT.as_secs()
+    );
+    let detection = detect_nat(bind_addr, &observers, NAT_DETECT_TIMEOUT).await?;
+    print!("{}", detection);
+    Ok(())
+}
+
+/// Print how each fanout trades convergence time against message count.
+fn run_fanout_sweep(fanouts: &[usize]) -> anyhow::Result<()> {
+    let config = FanoutSweepConfig {
+        fanouts: fanouts.to_vec(),
+        ..Default::default()
+    };
+    print!("{}", saorsa_quic_test::run_fanout_sweep(&config)?);
+    Ok(())
+}
+
+/// Replay a recorded protocol frame log into the TUI without starting a node.
+async fn run_frame_replay(path: &std::path::Path) -> anyhow::Result<()> {
+    let frames = load_recorded_frames(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read frame recording {:?}: {}", path, e))?;
+
+    let (event_tx, event_rx) = mpsc::channel::<TuiEvent>(1000);
+    let _ = event_tx
+        .send(TuiEvent::Info(format!(
+            "Replaying {} recorded frames from {}",
+            frames.len(),
+            path.display()
+        )))
+        .await;
+    let replay = tokio::spawn(replay_frames(frames, event_tx.clone()));
+
+    let mut app = App::new();
+    app.auto_connecting = false;
+    run_tui(app, event_rx, event_tx, None).await?;
+    replay.abort();
+    Ok(())
+}
+
+/// Replay a connectivity history file into the TUI without starting a node.
+///
+/// Entries are delivered at their recorded spacing divided by `speed`;
+/// the spacebar pauses and resumes playback.
+async fn run_history_replay(path: &std::path::Path, speed: f64) -> anyhow::Result<()> {
+    let history = load_history_file(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read history file {:?}: {}", path, e))?;
+    let events = history_to_events(&history);
+
+    let (event_tx, event_rx) = mpsc::channel::<TuiEvent>(1000);
+    let _ = event_tx
+        .send(TuiEvent::Info(format!(
+            "Replaying {} history entries from node {} at {}x",
+            history.entries.len(),
+            history.node_id,
+            speed
+        )))
+        .await;
+    let (control, paused) = ReplayControl::new(speed);
+    let replay = tokio::spawn(replay_events(events, speed, paused, event_tx.clone()));
+
+    let mut app = App::new();
+    app.auto_connecting = false;
+    app.replay = Some(control);
+    run_tui(app, event_rx, event_tx, None).await?;
+    replay.abort();
+    Ok(())
+}
+
+/// Collect node state from the registry and run one proof-based verification.
+///
+/// With `verbose` unset (watch mode) only waiting and errors are printed.
+async fn run_proof_once(
+    args: &Args,
+    client: &RegistryClient,
+    verbose: bool,
+) -> anyhow::Result<OrchestratorReport> {
+    if verbose {
+        println!("Fetching peer list from registry...");
+    }
 
     // Get current peers from registry
     let peers = client.get_peers().await?;
@@ -456,20 +1548,27 @@ async fn run_proof_test(args: &Args) -> anyhow::Result<()> {
 
     // Refresh peer list
     let peers = client.get_peers().await?;
-    println!("Found {} nodes for testing", peers.len());
-    println!();
+    if verbose {
+        println!("Found {} nodes for testing", peers.len());
+        println!();
+    }
 
     // Create proof orchestrator
     let mut orchestrator = ProofOrchestrator::with_config(ProofOrchestratorConfig {
         observer_id: "proof-test-cli".to_string(),
         min_nodes: args.min_proof_nodes,
         debug_on_failure: true,
+        ip_mode: args.ip_mode,
         ..Default::default()
     });
 
     // Register all known peers and record their gossip stats
-    println!("Registering nodes and collecting gossip stats...");
+    if verbose {
+        println!("Registering nodes and collecting gossip stats...");
+    }
     let mut nodes_with_gossip = 0;
+    // The registry only lists nodes that heartbeat within its TTL
+    orchestrator.record_registry_alive(peers.iter().map(|peer| peer.peer_id.clone()));
     for peer in &peers {
         orchestrator.register_node(peer.peer_id.clone());
         // Build connection list from peer's known peers
@@ -480,39 +1579,152 @@ async fn run_proof_test(args: &Args) -> anyhow::Result<()> {
             .map(|p| p.peer_id.clone())
             .collect();
         orchestrator.record_connections(&peer.peer_id, other_peers);
+        orchestrator.record_nat_type(&peer.peer_id, peer.nat_type);
+        if let Some(join_time) = peer.time_to_first_connection {
+            orchestrator.record_join_time(&peer.peer_id, join_time);
+        }
 
         // Record gossip stats if available
         if let Some(ref node_stats) = peer.gossip_stats {
             let gossip_stats = convert_gossip_stats(node_stats);
-            println!(
-                "  Node {}...: HyParView active={}, SWIM alive={}, Plumtree eager={}",
-                &peer.peer_id[..8.min(peer.peer_id.len())],
-                gossip_stats.hyparview.active_view_size,
-                gossip_stats.swim.alive_count,
-                gossip_stats.plumtree.eager_peers,
-            );
+            if verbose {
+                println!(
+                    "  Node {}...: HyParView active={}, SWIM alive={}, Plumtree eager={}",
+                    &peer.peer_id[..8.min(peer.peer_id.len())],
+                    gossip_stats.hyparview.active_view_size,
+                    gossip_stats.swim.alive_count,
+                    gossip_stats.plumtree.eager_peers,
+                );
+            }
             orchestrator.record_gossip_stats(&peer.peer_id, gossip_stats);
+            orchestrator
+                .record_active_view(&peer.peer_id, node_stats.hyparview_active_peers.clone());
+            orchestrator.record_swim_dead(&peer.peer_id, node_stats.swim_dead_peers.clone());
+            if !node_stats.trust_vector.is_empty() {
+                orchestrator.record_trust_vector(
+                    &peer.peer_id,
+                    node_stats.trust_vector.clone(),
+                    node_stats.trust_iterations,
+                );
+            }
             nodes_with_gossip += 1;
-        } else {
+        } else if verbose {
             println!(
                 "  Node {}...: No gossip stats available",
                 &peer.peer_id[..8.min(peer.peer_id.len())],
             );
         }
     }
-    println!(
-        "Collected gossip stats from {}/{} nodes",
-        nodes_with_gossip,
-        peers.len()
+    if verbose {
+        println!(
+            "Collected gossip stats from {}/{} nodes",
+            nodes_with_gossip,
+            peers.len()
+        );
+    }
+
+    // Broadcast a latency probe and collect the receipts nodes report back
+    let probe = orchestrator.start_latency_probe();
+    match publish_latency_probe(&peers, &probe).await {
+        Ok(()) => {
+            if verbose {
+                println!(
+                    "Published latency probe {}; waiting {}s for receipts...",
+                    probe.probe_id,
+                    PROBE_REPORT_DELAY.as_secs()
+                );
+            }
+            tokio::time::sleep(PROBE_REPORT_DELAY).await;
+            for peer in client.get_peers().await? {
+                if let Some(ref node_stats) = peer.gossip_stats {
+                    orchestrator.record_probe_receipts(&peer.peer_id, &node_stats.probe_receipts);
+                }
+            }
+        }
+        Err(e) => eprintln!("WARNING: latency probe not published: {:#}", e),
+    }
+
+    let results = client.get_results().await?;
+    // Feed every technique attempt into the success-rate check
+    for conn in &results.connections {
+        orchestrator.record_technique_attempts(
+            &conn.from_peer,
+            conn.connectivity.technique_attempts.iter().cloned(),
+        );
+    }
+
+    // Record which address family each successful connection used
+    if args.ip_mode != IpMode::DualStack {
+        for conn in &results.connections {
+            let version = if conn.is_ipv6 {
+                IpVersion::V6
+            } else {
+                IpVersion::V4
+            };
+            orchestrator.record_established_path(&conn.from_peer, &conn.to_peer, version);
+            orchestrator.record_established_path(&conn.to_peer, &conn.from_peer, version);
+        }
+        if verbose {
+            println!(
+                "Checking {} connections against {} mode",
+                results.connections.len(),
+                args.ip_mode
+            );
+        }
+    }
+
+    if verbose {
+        println!("Running proof-based verification...");
+        println!();
+    }
+    Ok(orchestrator.run_comprehensive_test())
+}
+
+/// Publish `probe` on the latency probe topic from a short-lived gossip
+/// node bootstrapped from the registry's peers.
+async fn publish_latency_probe(
+    peers: &[saorsa_quic_test::registry::PeerInfo],
+    probe: &LatencyProbe,
+) -> anyhow::Result<()> {
+    let (event_tx, _event_rx) = mpsc::channel(100);
+    let gossip = EpidemicGossip::new(
+        saorsa_gossip_types::PeerId::new(rand::random()),
+        EpidemicConfig {
+            bootstrap_peers: peers.iter().flat_map(|p| p.addresses.clone()).collect(),
+            ..EpidemicConfig::default()
+        },
+        event_tx,
     );
+    gossip
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("gossip start failed: {}", e))?;
+    tokio::time::sleep(PROBE_JOIN_DELAY).await;
+    let published = gossip.publish(serialize_latency_probe(probe)).await;
+    // Give the eager pushes time to leave before the transport closes
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    gossip.stop().await;
+    published.map_err(|e| anyhow::anyhow!("probe publish failed: {}", e))
+}
 
-    // Run comprehensive test
-    println!("Running proof-based verification...");
-    println!();
-    let report = orchestrator.run_comprehensive_test();
+/// Run proof-based network verification test.
+async fn run_proof_test(args: &Args) -> anyhow::Result<()> {
+    let client = RegistryClient::new(&args.registry_url);
+    // Load the baseline up front so a bad path fails before the test runs
+    let baseline = args.baseline.as_deref().map(load_baseline).transpose()?;
+    if let Some(interval) = args.watch {
+        return watch_proof_test(args, &client, interval, baseline.as_ref()).await;
+    }
+
+    let report = run_proof_once(args, &client, true).await?;
 
     // Print report
     println!("{}", report);
+    if let Some(baseline) = &baseline {
+        println!("{}", report.to_json().diff(baseline));
+    }
+    write_report_json(args, &report)?;
+    write_debug_issue(args, &report)?;
 
     // Return status code based on result
     if report.passed {
@@ -524,3 +1736,480 @@ async fn run_proof_test(args: &Args) -> anyhow::Result<()> {
         anyhow::bail!("Proof-based test failed")
     }
 }
+
+/// Write the report as JSON if `--report-json` was given.
+fn write_report_json(args: &Args, report: &OrchestratorReport) -> anyhow::Result<()> {
+    if let Some(path) = &args.report_json {
+        std::fs::write(path, serde_json::to_string_pretty(&report.to_json())?)
+            .map_err(|e| anyhow::anyhow!("Failed to write report {:?}: {}", path, e))?;
+        println!("Wrote JSON report to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Write the debug report as a ready-to-paste GitHub issue if
+/// `--debug-issue` was given and the run produced one.
+fn write_debug_issue(args: &Args, report: &OrchestratorReport) -> anyhow::Result<()> {
+    let (Some(path), Some(debug_report)) = (&args.debug_issue, &report.debug_report) else {
+        return Ok(());
+    };
+    let issue = debug_report.to_issue_report();
+    let body = format!("# {}\n\n{}", issue.title, issue.to_github_markdown());
+    std::fs::write(path, body)
+        .map_err(|e| anyhow::anyhow!("Failed to write debug issue {:?}: {}", path, e))?;
+    println!(
+        "Wrote debug issue for {} to {}",
+        issue.library,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Read an earlier `--report-json` report to diff against.
+fn load_baseline(path: &std::path::Path) -> anyhow::Result<OrchestratorReportJson> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read baseline {:?}: {}", path, e))?;
+    serde_json::from_str(&data)
+        .map_err(|e| anyhow::anyhow!("Failed to parse baseline {:?}: {}", path, e))
+}
+
+/// Re-run the proof test every `interval` until interrupted.
+///
+/// Prints one line per run and the aggregate on Ctrl+C / SIGTERM. Failed
+/// runs are recorded and the loop continues unless `--fail-fast` is set.
+async fn watch_proof_test(
+    args: &Args,
+    client: &RegistryClient,
+    interval: Duration,
+    baseline: Option<&OrchestratorReportJson>,
+) -> anyhow::Result<()> {
+    println!(
+        "Watch mode: re-running every {} (Ctrl+C to stop)",
+        humantime_serde::re::humantime::format_duration(interval)
+    );
+    let mut history = ProofRunHistory::new();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let stopped_by_failure = loop {
+        let run = tokio::select! {
+            _ = &mut shutdown => break false,
+            run = run_proof_once(args, client, false) => run,
+        };
+        let run_number = history.total_runs() + 1;
+        let failed = match run {
+            Ok(report) => {
+                println!("[run {}] {}", run_number, report.summary_line());
+                if let Some(baseline) = baseline {
+                    print!("{}", report.to_json().diff(baseline));
+                }
+                write_report_json(args, &report)?;
+                write_debug_issue(args, &report)?;
+                let failed = !report.passed;
+                history.record(report);
+                failed
+            }
+            Err(e) => {
+                println!("[run {}] ERROR {}", run_number, e);
+                history.record_error();
+                true
+            }
+        };
+        if failed && args.fail_fast {
+            break true;
+        }
+        tokio::select! {
+            _ = &mut shutdown => break false,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    };
+
+    println!();
+    println!("Watch summary: {}", history);
+    if stopped_by_failure {
+        anyhow::bail!("Proof-based test failed (--fail-fast)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_profiles_set_documented_values() {
+        let dev = parse_args_from(argv(&["--profile", "local-dev"]));
+        assert_eq!(dev.profile, Some(Profile::LocalDev));
+        assert!(dev.local_only);
+        assert!(!dev.quiet);
+        assert_eq!(dev.registry_url, "http://localhost:8080");
+        assert_eq!(dev.max_peers, 5);
+
+        let ci = parse_args_from(argv(&["--profile", "ci"]));
+        assert_eq!(ci.profile, Some(Profile::Ci));
+        assert!(ci.quiet);
+        assert!(ci.local_only);
+        assert_eq!(ci.max_peers, 5);
+        assert_eq!(ci.max_runtime, Some(Duration::from_secs(15 * 60)));
+
+        let soak = parse_args_from(argv(&["--profile", "soak"]));
+        assert!(soak.quiet);
+        assert!(!soak.local_only);
+        assert_eq!(soak.max_peers, 50);
+
+        let chaos = parse_args_from(argv(&["--profile", "chaos"]));
+        assert!(chaos.quiet);
+        assert_eq!(chaos.max_peers, 25);
+        assert_eq!(chaos.min_proof_nodes, 5);
+    }
+
+    #[test]
+    fn test_explicit_flag_overrides_profile() {
+        // Flag after the profile
+        let args = parse_args_from(argv(&["--profile", "ci", "--max-peers", "8"]));
+        assert_eq!(args.max_peers, 8);
+        assert!(args.quiet);
+
+        // Flag before the profile still wins
+        let args = parse_args_from(argv(&["--max-peers", "8", "--profile", "ci"]));
+        assert_eq!(args.max_peers, 8);
+        let args = parse_args_from(argv(&["--max-runtime", "1h", "--profile", "ci"]));
+        assert_eq!(args.max_runtime, Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_version_json_contains_crate_version() {
+        let output = version_json().to_string();
+        let parsed: serde_json::Value = serde_json::from_str(&output).expect("valid JSON");
+        assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+        assert!(parsed["git_commit"].is_string());
+        assert!(parsed["build_timestamp"].is_u64());
+        assert!(parsed["features"].is_array());
+    }
+
+    #[test]
+    fn test_shutdown_grace_flag() {
+        assert_eq!(
+            parse_args_from(vec![]).shutdown_grace,
+            Duration::from_secs(5)
+        );
+        let args = parse_args_from(argv(&["--shutdown-grace", "1m 30s"]));
+        assert_eq!(args.shutdown_grace, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_no_dashboard_flag() {
+        assert!(!parse_args_from(vec![]).no_dashboard);
+        assert!(parse_args_from(argv(&["--registry", "--no-dashboard"])).no_dashboard);
+    }
+
+    #[test]
+    fn test_registry_blocklist_flags() {
+        let args = parse_args_from(vec![]);
+        assert!(args.peer_blocklist.is_none() && args.admin_token.is_none());
+        assert!(args.admin_tls_cert.is_none() && args.admin_client_ca.is_none());
+        assert_eq!(args.admin_tls_port, 8443);
+        let args = parse_args_from(argv(&[
+            "--registry",
+            "--peer-blocklist",
+            "blocked.txt",
+            "--admin-secret",
+            "s3cret",
+        ]));
+        assert_eq!(args.peer_blocklist, Some(PathBuf::from("blocked.txt")));
+        assert_eq!(args.admin_token.as_deref(), Some("s3cret"));
+        assert!(args.trusted_proxies.is_empty());
+        let args = parse_args_from(argv(&[
+            "--registry",
+            "--trusted-proxy",
+            "127.0.0.1",
+            "--trusted-proxy",
+            "::1",
+        ]));
+        assert_eq!(
+            args.trusted_proxies,
+            vec![
+                "127.0.0.1".parse::<std::net::IpAddr>().unwrap(),
+                "::1".parse().unwrap()
+            ]
+        );
+        let args = parse_args_from(argv(&[
+            "--registry",
+            "--admin-token",
+            "t0ken",
+            "--admin-tls-cert",
+            "server.pem",
+            "--admin-tls-key",
+            "server.key",
+            "--admin-client-ca",
+            "ca.pem",
+            "--admin-tls-port",
+            "9443",
+        ]));
+        assert_eq!(args.admin_token.as_deref(), Some("t0ken"));
+        assert_eq!(args.admin_tls_cert, Some(PathBuf::from("server.pem")));
+        assert_eq!(args.admin_tls_key, Some(PathBuf::from("server.key")));
+        assert_eq!(args.admin_client_ca, Some(PathBuf::from("ca.pem")));
+        assert_eq!(args.admin_tls_port, 9443);
+    }
+
+    #[test]
+    fn test_max_runtime_flag() {
+        assert_eq!(parse_args_from(vec![]).max_runtime, None);
+        let args = parse_args_from(argv(&["--quiet", "--max-runtime", "90s"]));
+        assert_eq!(args.max_runtime, Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_otlp_endpoint_flag() {
+        assert_eq!(parse_args_from(vec![]).otlp_endpoint, None);
+        let args = parse_args_from(argv(&["--otlp-endpoint", "http://collector:4318"]));
+        assert_eq!(args.otlp_endpoint.as_deref(), Some("http://collector:4318"));
+    }
+
+    #[test]
+    fn test_profile_flamegraph_flag() {
+        assert_eq!(parse_args_from(vec![]).profile_flamegraph, None);
+        let args = parse_args_from(argv(&["--profile-flamegraph", "/tmp/cpu.svg"]));
+        assert_eq!(args.profile_flamegraph, Some(PathBuf::from("/tmp/cpu.svg")));
+    }
+
+    #[test]
+    fn test_control_socket_flag() {
+        assert_eq!(parse_args_from(vec![]).control_socket, None);
+        let args = parse_args_from(argv(&["--quiet", "--control-socket", "/tmp/node.sock"]));
+        assert_eq!(args.control_socket, Some(PathBuf::from("/tmp/node.sock")));
+    }
+
+    #[test]
+    fn test_telemetry_is_opt_in() {
+        assert_eq!(parse_args_from(vec![]).telemetry, None);
+        let args = parse_args_from(argv(&["--telemetry", "https://telemetry.example/v1"]));
+        assert_eq!(
+            args.telemetry.as_deref(),
+            Some("https://telemetry.example/v1")
+        );
+    }
+
+    #[test]
+    fn test_heartbeat_interval_flag() {
+        assert_eq!(
+            parse_args_from(vec![]).heartbeat_interval,
+            Duration::from_secs(5)
+        );
+        let args = parse_args_from(argv(&["--heartbeat-interval", "30s"]));
+        assert_eq!(args.heartbeat_interval, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_connect_timeout_flag() {
+        assert_eq!(
+            parse_args_from(vec![]).connect_timeout,
+            Duration::from_secs(30)
+        );
+        let args = parse_args_from(argv(&["--connect-timeout", "5s"]));
+        assert_eq!(args.connect_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_history_retention_flag() {
+        assert_eq!(
+            parse_args_from(vec![]).history_retention,
+            Duration::from_secs(7 * 24 * 3600)
+        );
+        let args = parse_args_from(argv(&["--registry", "--history-retention", "36h"]));
+        assert_eq!(args.history_retention, Duration::from_secs(36 * 3600));
+    }
+
+    #[test]
+    fn test_bootstrap_flag_is_repeatable() {
+        assert!(parse_args_from(vec![]).bootstrap.is_empty());
+        let args = parse_args_from(argv(&[
+            "--bootstrap",
+            "10.0.0.1:9000",
+            "--bootstrap",
+            "seed.example:9000",
+        ]));
+        assert_eq!(args.bootstrap, vec!["10.0.0.1:9000", "seed.example:9000"]);
+    }
+
+    #[test]
+    fn test_watch_and_fail_fast_flags() {
+        let args = parse_args_from(argv(&["--proof-test"]));
+        assert_eq!(args.watch, None);
+        assert!(!args.fail_fast);
+
+        let args = parse_args_from(argv(&["--proof-test", "--watch", "90s", "--fail-fast"]));
+        assert_eq!(args.watch, Some(Duration::from_secs(90)));
+        assert!(args.fail_fast);
+    }
+
+    #[test]
+    fn test_check_registry_flag() {
+        assert!(!parse_args_from(vec![]).check_registry);
+        let args = parse_args_from(argv(&[
+            "--check-registry",
+            "--registry-url",
+            "http://localhost:8080",
+        ]));
+        assert!(args.check_registry);
+        assert_eq!(args.registry_url, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_peer_cache_flags() {
+        let args = parse_args_from(vec![]);
+        assert!(args.peer_cache.is_none());
+        assert_eq!(args.peer_cache_ttl, DEFAULT_PEER_CACHE_TTL);
+
+        let args = parse_args_from(argv(&[
+            "--peer-cache",
+            "/tmp/peers.json",
+            "--peer-cache-ttl",
+            "2d",
+        ]));
+        assert_eq!(args.peer_cache, Some(PathBuf::from("/tmp/peers.json")));
+        assert_eq!(args.peer_cache_ttl, Duration::from_secs(2 * 24 * 60 * 60));
+    }
+
+    #[test]
+    fn test_detect_nat_flag() {
+        assert!(!parse_args_from(vec![]).detect_nat);
+        let args = parse_args_from(argv(&["--detect-nat", "--bind-port", "9000"]));
+        assert!(args.detect_nat);
+        assert_eq!(args.bind_port, 9000);
+    }
+
+    #[test]
+    fn test_repeated_registry_url_flags() {
+        let args = parse_args_from(argv(&[
+            "--registry-url",
+            "https://a.example",
+            "--registry-url",
+            "https://b.example,https://c.example",
+        ]));
+        assert_eq!(
+            args.registry_url,
+            "https://a.example,https://b.example,https://c.example"
+        );
+    }
+
+    #[test]
+    fn test_baseline_flag() {
+        assert_eq!(parse_args_from(vec![]).baseline, None);
+        let args = parse_args_from(argv(&["--proof-test", "--baseline", "last-good.json"]));
+        assert_eq!(args.baseline, Some(PathBuf::from("last-good.json")));
+    }
+
+    #[test]
+    fn test_report_json_flag() {
+        assert_eq!(parse_args_from(vec![]).report_json, None);
+        let args = parse_args_from(argv(&["--proof-test", "--report-json", "out/report.json"]));
+        assert_eq!(args.report_json, Some(PathBuf::from("out/report.json")));
+    }
+
+    #[test]
+    fn test_debug_issue_flag() {
+        assert_eq!(parse_args_from(vec![]).debug_issue, None);
+        let args = parse_args_from(argv(&["--proof-test", "--debug-issue", "issue.md"]));
+        assert_eq!(args.debug_issue, Some(PathBuf::from("issue.md")));
+    }
+
+    #[test]
+    fn test_ip_mode_flag() {
+        assert_eq!(parse_args_from(vec![]).ip_mode, IpMode::DualStack);
+        let args = parse_args_from(argv(&["--proof-test", "--ip-mode", "v6-only"]));
+        assert_eq!(args.ip_mode, IpMode::Ipv6Only);
+        assert_eq!(parse_ip_mode("v4-only"), Ok(IpMode::Ipv4Only));
+        assert!(parse_ip_mode("ipv6").is_err());
+    }
+
+    #[test]
+    fn test_relay_flag() {
+        assert!(!parse_args_from(vec![]).relay);
+        assert!(parse_args_from(argv(&["--relay"])).relay);
+    }
+
+    #[test]
+    fn test_gossip_binary_flag() {
+        assert!(!parse_args_from(vec![]).gossip_binary);
+        assert!(parse_args_from(argv(&["--gossip-binary"])).gossip_binary);
+    }
+
+    #[test]
+    fn test_replay_frames_flag() {
+        assert_eq!(parse_args_from(vec![]).replay_frames, None);
+        let args = parse_args_from(argv(&["--replay-frames", "demo.ndjson"]));
+        assert_eq!(args.replay_frames, Some(PathBuf::from("demo.ndjson")));
+    }
+
+    #[test]
+    fn test_packet_size_flag() {
+        assert_eq!(parse_args_from(vec![]).packet_size, 5120);
+        let args = parse_args_from(argv(&["--packet-size", "65536"]));
+        assert_eq!(args.packet_size, 65536);
+    }
+
+    #[test]
+    fn test_burst_size_flag() {
+        assert_eq!(parse_args_from(vec![]).burst_size, 10);
+        assert_eq!(parse_args_from(argv(&["--burst-size", "3"])).burst_size, 3);
+    }
+
+    #[test]
+    fn test_max_concurrent_dials_flag() {
+        assert_eq!(parse_args_from(vec![]).max_concurrent_dials, 16);
+        let args = parse_args_from(argv(&["--max-concurrent-dials", "4"]));
+        assert_eq!(args.max_concurrent_dials, 4);
+    }
+
+    #[test]
+    fn test_gossip_fanout_flags() {
+        let args = parse_args_from(vec![]);
+        assert_eq!(args.gossip_fanout, None);
+        assert_eq!(args.fanout_sweep, None);
+        let args = parse_args_from(argv(&["--gossip-fanout", "3", "--fanout-sweep", "1, 2,4"]));
+        assert_eq!(args.gossip_fanout, Some(3));
+        assert_eq!(args.fanout_sweep, Some(vec![1, 2, 4]));
+    }
+
+    #[test]
+    fn test_view_size_flags() {
+        let args = parse_args_from(vec![]);
+        assert_eq!(args.active_view_size, DEFAULT_ACTIVE_VIEW_SIZE);
+        assert_eq!(args.passive_view_size, DEFAULT_PASSIVE_VIEW_SIZE);
+        let args = parse_args_from(argv(&["--active-view", "5", "--passive-view", "30"]));
+        assert_eq!(args.active_view_size, 5);
+        assert_eq!(args.passive_view_size, 30);
+    }
+
+    #[test]
+    fn test_replay_flags() {
+        let args = parse_args_from(vec![]);
+        assert_eq!(args.replay, None);
+        assert_eq!(args.replay_speed, 1.0);
+        let args = parse_args_from(argv(&[
+            "--replay",
+            "history/2026-10-14-03.json",
+            "--replay-speed",
+            "60",
+        ]));
+        assert_eq!(
+            args.replay,
+            Some(PathBuf::from("history/2026-10-14-03.json"))
+        );
+        assert_eq!(args.replay_speed, 60.0);
+    }
+
+    #[test]
+    fn test_profile_names_round_trip() {
+        for profile in Profile::ALL {
+            assert_eq!(profile.name().parse::<Profile>(), Ok(profile));
+        }
+        assert!("unknown".parse::<Profile>().is_err());
+    }
+}
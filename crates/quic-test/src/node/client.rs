@@ -6,34 +6,39 @@
 //! automatic connections using REAL P2pEndpoint QUIC connections,
 //! and test traffic generation over actual QUIC streams.
 
+use crate::clock::{SharedClock, system_clock};
+use crate::diagnostics::PeerSetDiff;
 use crate::epidemic_gossip::{
     ConnectionType as GossipConnectionType, EpidemicConfig, EpidemicEvent, EpidemicGossip,
     GossipStats,
 };
 use crate::gossip::{
-    GossipConfig, GossipEvent, GossipIntegration, PeerCapabilities as GossipCapabilities,
-    PeerConnectionResponse, serialize_peer_response,
+    DEFAULT_ACTIVE_VIEW_SIZE, DEFAULT_PASSIVE_VIEW_SIZE, GossipConfig, GossipDiscovery,
+    GossipEvent, GossipIntegration, PeerCapabilities as GossipCapabilities, PeerConnectionResponse,
+    RelayAnnouncement, RelaySelectionPolicy, TOPIC_LATENCY_PROBE, TOPIC_PEERS, WireFormat,
+    deserialize_latency_probe, serialize_peer_response,
 };
 use crate::registry::{
     BgpGeoProvider, ConnectionDirection, ConnectionMethod, ConnectionReport, ConnectivityMatrix,
-    DataProof, FullMeshProbeResult, NatStats, NatType, NetworkEvent, NodeCapabilities,
-    NodeGossipStats, NodeHeartbeat, NodeRegistration, PeerInfo, PeerStatus, RegistryClient,
-    SuccessLevel,
+    DataProof, FailureReasonCode, FullMeshProbeResult, GeoCache, JoinTime, NatStats, NatType,
+    NetworkEvent, NodeCapabilities, NodeGossipStats, NodeHeartbeat, NodeRegistration, PeerInfo,
+    PeerStatus, RegistryClient, SuccessLevel, TelemetryRecord, classify_connection_error,
+    geo_address,
 };
 use crate::tui::{
-    CacheHealth, ConnectedPeer, FrameDirection, GeographicDistribution, LocalNodeInfo,
-    NatTraversalPhase, NatTypeAnalytics, ProtocolFrame, TestConnectivityMethod, TrafficType,
-    TuiEvent, country_flag, send_tui_event,
+    AddressFamily, ConnectedPeer, ConnectivitySweepSummary, FrameDirection, GeographicDistribution,
+    LocalNodeInfo, NatTraversalPhase, NatTypeAnalytics, NegotiatedCrypto, ProtocolFrame,
+    TestConnectivityMethod, TrafficType, TuiEvent, country_flag, send_tui_event,
 };
 use saorsa_gossip_types::PeerId as GossipPeerId;
 use std::collections::{HashMap, HashSet};
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, mpsc};
-use tracing::{debug, error, info, warn};
+use tracing::{Instrument, debug, error, info, warn};
 
 use ant_quic::{Node, P2pEndpoint, P2pEvent, PeerId as QuicPeerId};
 // TODO: Re-enable when communitas-core crate linking is fixed
@@ -44,17 +49,27 @@ use ant_quic::crypto::raw_public_keys::key_utils::{
     MlDsaPublicKey, MlDsaSecretKey, generate_ml_dsa_keypair,
 };
 
+use super::dial_limiter::{DEFAULT_MAX_CONCURRENT_DIALS, DialLimiter};
+use super::handshake_crypto::peer_crypto;
+use super::join_time::JoinTimer;
+use super::nat_detect::{classify_nat, has_public_address};
+use super::nat_trace::{NatFrameCounts, NatPhaseTracer};
+use super::peer_cache::{DEFAULT_PEER_CACHE_TTL, PEER_CACHE_FILE, PersistentPeerCache};
+use super::peer_filter::PeerFilter;
+use super::resumption::{DialHandshake, ResumptionTracker};
+use super::telemetry::TELEMETRY_INTERVAL;
 use super::test_protocol::{
-    CanYouReachRequest, GossipMessage, GossipPeerAnnouncement, GossipPeerInfo, PeerListMessage,
-    RELAY_MAGIC, ReachResponse, RelayAckResponse, RelayMessage, RelayPunchMeNowRequest, RelayState,
-    RelayedDataResponse, TestPacket, is_gossip_stream_type, relay_stream_type,
+    CanYouReachRequest, DEFAULT_TEST_BURST_SIZE, DEFAULT_TEST_PACKET_SIZE, GossipMessage,
+    GossipPeerAnnouncement, GossipPeerInfo, PacketType, PeerListMessage, RELAY_MAGIC,
+    ReachResponse, RelayAckResponse, RelayMessage, RelayPunchMeNowRequest, RelayState,
+    RelayedDataResponse, TestPacket, TestResult, is_gossip_stream_type, relay_stream_type,
     test_packet_stream_type,
 };
 
 /// Configuration for the test node.
 #[derive(Debug, Clone)]
 pub struct TestNodeConfig {
-    /// Registry URL to connect to.
+    /// Registry URL to connect to, or a comma-separated failover list.
     pub registry_url: String,
     /// Maximum number of peer connections.
     pub max_peers: usize,
@@ -64,6 +79,10 @@ pub struct TestNodeConfig {
     pub connect_interval: Duration,
     /// Interval between test packet exchanges.
     pub test_interval: Duration,
+    /// Total size of each test packet in bytes (capped at `MAX_TEST_PACKET_SIZE`).
+    pub test_packet_size: usize,
+    /// Test packets sent to each peer per exchange, for jitter and loss.
+    pub test_burst_size: usize,
     /// Interval between heartbeats.
     pub heartbeat_interval: Duration,
     /// Local-only mode: Disable external VPS bootstrap connections.
@@ -83,6 +102,55 @@ pub struct TestNodeConfig {
     /// If None, uses the default platform-specific data directory.
     /// Each node instance should use a unique data directory to have a unique peer ID.
     pub data_dir: Option<PathBuf>,
+    /// How long direct and hole-punch attempts get before relay is tried.
+    /// Relay is used earlier only if every direct/NAT method has already failed.
+    pub relay_fallback_after: Duration,
+    /// Head start IPv6 gets over IPv4 when dialing a dual-stack peer. The
+    /// families are raced and the slower attempt is cancelled.
+    pub happy_eyeballs_delay: Duration,
+    /// How to choose between relays announced over gossip when a peer can
+    /// only be reached through one.
+    pub relay_selection: RelaySelectionPolicy,
+    /// Upper bound on each outbound connection attempt: one hole-punch, or
+    /// one address dialed directly (direct dials are never given more than
+    /// `DIRECT_DIAL_TIMEOUT`). A timed-out attempt moves on to the next
+    /// method or candidate.
+    pub connect_timeout: Duration,
+    /// How long in-flight work (test exchanges, a final heartbeat) may run
+    /// after shutdown is requested before it is aborted.
+    pub shutdown_grace: Duration,
+    /// Shut down cleanly once `run()` has been running this long.
+    pub max_runtime: Option<Duration>,
+    /// Peer IDs or address CIDRs that must never be dialed.
+    pub blacklist: Vec<String>,
+    /// If set, only these peer IDs or address CIDRs are dialed; all other
+    /// discovered peers are ignored.
+    pub allowlist: Option<Vec<String>>,
+    /// Advertise this node as able to relay traffic for other nodes.
+    pub relay: bool,
+    /// Encoding for outgoing gossip peer and relay announcements.
+    pub gossip_wire_format: WireFormat,
+    /// Peers each gossip announcement is forwarded to; `None` for all
+    /// connected peers.
+    pub gossip_fanout: Option<usize>,
+    /// Target HyParView active view size.
+    pub active_view_size: usize,
+    /// Target HyParView passive view size.
+    pub passive_view_size: usize,
+    /// Time source for timeouts (the system clock outside tests).
+    pub clock: SharedClock,
+    /// Bootstrap peer addresses. When non-empty they replace the hardcoded
+    /// VPS bootstrap list, even in local-only mode.
+    pub bootstrap_peers: Vec<SocketAddr>,
+    /// Endpoint for anonymized telemetry uploads. Nothing is sent when unset.
+    pub telemetry_url: Option<String>,
+    /// File remembering dialed peers across restarts. Defaults to
+    /// `known_peers.json` in the data directory.
+    pub peer_cache_path: Option<PathBuf>,
+    /// Cached peers not seen for this long are pruned.
+    pub peer_cache_ttl: Duration,
+    /// Outbound dials allowed in flight at once; the rest wait their turn.
+    pub max_concurrent_dials: usize,
 }
 
 impl Default for TestNodeConfig {
@@ -94,6 +162,8 @@ impl Default for TestNodeConfig {
             bind_addr: "[::]:0".parse().expect("valid default address"),
             connect_interval: Duration::from_secs(5),
             test_interval: Duration::from_secs(5),
+            test_packet_size: DEFAULT_TEST_PACKET_SIZE,
+            test_burst_size: DEFAULT_TEST_BURST_SIZE,
             // 5-second heartbeat keeps NAT holes open for hole-punched connections
             // (NAT devices typically close UDP mappings after 30-60 seconds of inactivity)
             heartbeat_interval: Duration::from_secs(5),
@@ -103,10 +173,78 @@ impl Default for TestNodeConfig {
             gossip_first: true,
             // Use default data directory (platform-specific)
             data_dir: None,
+            // Give direct + hole-punch a fair chance before relaying
+            relay_fallback_after: Duration::from_secs(20),
+            // RFC 8305's recommended connection attempt delay
+            happy_eyeballs_delay: Duration::from_millis(250),
+            // Spread relayed connections instead of piling onto one relay
+            relay_selection: RelaySelectionPolicy::LeastLoaded,
+            // Long enough for hole-punch coordination through a relay
+            connect_timeout: Duration::from_secs(30),
+            // Matches the heartbeat/test intervals so one more cycle can finish
+            shutdown_grace: Duration::from_secs(5),
+            max_runtime: None,
+            blacklist: Vec::new(),
+            allowlist: None,
+            relay: false,
+            // JSON stays readable in packet captures
+            gossip_wire_format: WireFormat::Json,
+            gossip_fanout: None,
+            active_view_size: DEFAULT_ACTIVE_VIEW_SIZE,
+            passive_view_size: DEFAULT_PASSIVE_VIEW_SIZE,
+            clock: system_clock(),
+            bootstrap_peers: Vec::new(),
+            // Telemetry is strictly opt-in
+            telemetry_url: None,
+            peer_cache_path: None,
+            peer_cache_ttl: DEFAULT_PEER_CACHE_TTL,
+            // Enough to reach a fresh network quickly without an FD spike
+            max_concurrent_dials: DEFAULT_MAX_CONCURRENT_DIALS,
         }
     }
 }
 
+impl TestNodeConfig {
+    /// Capabilities this node advertises in its registration.
+    ///
+    /// Address families follow the bind address (`[::]` is dual-stack) and
+    /// IPv6 is only claimed when global IPv6 connectivity was detected. Relay
+    /// is advertised only when explicitly enabled. PQC is always on.
+    pub fn advertised_capabilities(&self, ipv6_available: bool) -> NodeCapabilities {
+        let ip = self.bind_addr.ip();
+        NodeCapabilities {
+            pqc: true,
+            ipv4: ip.is_ipv4() || ip.is_unspecified(),
+            ipv6: ip.is_ipv6() && ipv6_available,
+            nat_traversal: true,
+            relay: self.relay,
+        }
+    }
+
+    /// Warning if `heartbeat_interval` is unsafe for a registry TTL of
+    /// `ttl_secs`. At half the TTL or more, a single late or lost heartbeat
+    /// drops the node from the peer list until the next one lands.
+    pub fn heartbeat_ttl_warning(&self, ttl_secs: u64) -> Option<String> {
+        let ttl = Duration::from_secs(ttl_secs);
+        (self.heartbeat_interval * 2 >= ttl).then(|| {
+            format!(
+                "Heartbeat interval {:?} is at least half the registry TTL ({}s); \
+                 this node may flap in and out of the peer list",
+                self.heartbeat_interval, ttl_secs
+            )
+        })
+    }
+}
+
+/// Longest a single direct dial to one address may take.
+const DIRECT_DIAL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a test ping waits for its pong before it counts as lost.
+const TEST_PONG_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Test pings awaiting a pong, keyed by peer ID hex and sequence number.
+type PendingPongs = Arc<std::sync::Mutex<HashMap<(String, u64), tokio::sync::oneshot::Sender<()>>>>;
+
 /// Maximum consecutive failures before disconnecting a direct peer.
 const MAX_CONSECUTIVE_FAILURES: u32 = 5;
 
@@ -149,6 +287,124 @@ fn both_are_vps(peer: &PeerInfo, external_addresses: &[SocketAddr]) -> bool {
     peer_is_vps(peer) && we_are_vps(external_addresses)
 }
 
+/// Await direct/NAT connection attempts for at most `fallback_after`.
+///
+/// Returns the method that succeeded inside the window. `None` means relay
+/// fallback should now be attempted: either every earlier method failed, or
+/// they were still pending when the window closed.
+async fn attempt_within_relay_window<F>(
+    earlier: F,
+    fallback_after: Duration,
+) -> Option<ConnectionMethod>
+where
+    F: std::future::Future<Output = Option<ConnectionMethod>>,
+{
+    tokio::time::timeout(fallback_after, earlier)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Race an IPv6 dial against an IPv4 dial (happy eyeballs, RFC 8305).
+///
+/// IPv4 starts once IPv6 has had `head_start`, or as soon as IPv6 fails.
+/// The first success wins and the other attempt is dropped, cancelling it.
+/// Failures are returned with their family so callers know which paths
+/// actually completed.
+async fn happy_eyeballs<T, E, F6, F4>(
+    ipv6: F6,
+    ipv4: F4,
+    head_start: Duration,
+) -> (Option<(AddressFamily, T)>, Vec<(AddressFamily, E)>)
+where
+    F6: std::future::Future<Output = Result<T, E>>,
+    F4: std::future::Future<Output = Result<T, E>>,
+{
+    tokio::pin!(ipv6, ipv4);
+    let mut failures = Vec::new();
+
+    let first = tokio::select! {
+        result = &mut ipv6 => (AddressFamily::Ipv6, result),
+        _ = tokio::time::sleep(head_start) => tokio::select! {
+            biased;
+            result = &mut ipv6 => (AddressFamily::Ipv6, result),
+            result = &mut ipv4 => (AddressFamily::Ipv4, result),
+        },
+    };
+    let remaining = match first {
+        (family, Ok(value)) => return (Some((family, value)), failures),
+        (AddressFamily::Ipv6, Err(e)) => {
+            failures.push((AddressFamily::Ipv6, e));
+            (AddressFamily::Ipv4, ipv4.await)
+        }
+        (AddressFamily::Ipv4, Err(e)) => {
+            failures.push((AddressFamily::Ipv4, e));
+            (AddressFamily::Ipv6, ipv6.await)
+        }
+    };
+    match remaining {
+        (family, Ok(value)) => (Some((family, value)), failures),
+        (family, Err(e)) => {
+            failures.push((family, e));
+            (None, failures)
+        }
+    }
+}
+
+/// Resolve once `shutdown` is set, checked as often as `run()` checks it.
+async fn shutdown_requested(shutdown: &AtomicBool) {
+    while !shutdown.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Detached work (such as registry reports) that shutdown waits for.
+#[derive(Clone, Default)]
+struct InFlightTasks(Arc<std::sync::Mutex<tokio::task::JoinSet<()>>>);
+
+impl InFlightTasks {
+    /// Run `task` in the background, tracked until it finishes.
+    fn spawn<F>(&self, task: F)
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let mut tasks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        // Reap finished tasks so the set only holds in-flight work
+        while tasks.try_join_next().is_some() {}
+        tasks.spawn(task);
+    }
+
+    /// Wait up to `grace` for tracked tasks to finish, then abort the rest.
+    ///
+    /// Returns how many tasks had to be aborted.
+    async fn drain(&self, grace: Duration) -> usize {
+        let mut tasks = std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()));
+        let deadline = tokio::time::Instant::now() + grace;
+        while let Ok(Some(_)) = tokio::time::timeout_at(deadline, tasks.join_next()).await {}
+        let aborted = tasks.len();
+        tasks.abort_all();
+        aborted
+    }
+}
+
+/// Wait up to `grace` for `handles` to finish, then abort the rest.
+///
+/// Returns how many tasks were still running and had to be aborted.
+async fn drain_tasks(handles: Vec<tokio::task::JoinHandle<()>>, grace: Duration) -> usize {
+    let deadline = tokio::time::Instant::now() + grace;
+    let mut aborted = 0;
+    for mut handle in handles {
+        if tokio::time::timeout_at(deadline, &mut handle)
+            .await
+            .is_err()
+        {
+            handle.abort();
+            aborted += 1;
+        }
+    }
+    aborted
+}
+
 fn vps_gossip_bootstrap_addrs() -> Vec<SocketAddr> {
     VPS_NODE_IPS
         .iter()
@@ -161,6 +417,79 @@ fn vps_gossip_bootstrap_addrs() -> Vec<SocketAddr> {
         .collect()
 }
 
+/// Candidate entry for a hardcoded VPS bootstrap peer.
+fn vps_bootstrap_candidate(vps_peer: &crate::bootstrap_peers::BootstrapPeer) -> PeerInfo {
+    // Create deterministic peer ID for VPS node
+    // IMPORTANT: Peer IDs must be 32 bytes (64 hex chars) to match
+    // the format used by actual connected peers
+    use sha2::{Digest, Sha256};
+    let peer_id = {
+        let mut hasher = Sha256::new();
+        hasher.update(vps_peer.name.as_bytes());
+        hasher.update(vps_peer.ipv4.octets());
+        hex::encode(hasher.finalize()) // Full 32 bytes as 64 hex chars
+    };
+
+    PeerInfo {
+        capabilities: NodeCapabilities {
+            pqc: true,
+            ipv4: true,
+            ipv6: vps_peer.ipv6.is_some(),
+            nat_traversal: vps_peer.is_coordinator,
+            relay: vps_peer.is_relay,
+        },
+        version: String::from("vps-bootstrap"),
+        ..bootstrap_candidate(peer_id, vps_peer.all_addrs())
+    }
+}
+
+/// Candidate entry for a bootstrap peer configured at runtime.
+fn configured_bootstrap_candidate(addr: SocketAddr) -> PeerInfo {
+    use sha2::{Digest, Sha256};
+    let peer_id = hex::encode(Sha256::digest(format!("bootstrap:{}", addr).as_bytes()));
+
+    PeerInfo {
+        capabilities: NodeCapabilities {
+            pqc: true,
+            ipv4: addr.is_ipv4(),
+            ipv6: addr.is_ipv6(),
+            nat_traversal: true,
+            relay: false,
+        },
+        version: String::from("configured-bootstrap"),
+        ..bootstrap_candidate(peer_id, vec![addr])
+    }
+}
+
+/// Bootstrap peers are public and always considered active.
+fn bootstrap_candidate(peer_id: String, addresses: Vec<SocketAddr>) -> PeerInfo {
+    PeerInfo {
+        peer_id,
+        addresses,
+        nat_type: NatType::None,
+        country_code: None,
+        latitude: 0.0,
+        longitude: 0.0,
+        location_estimated: false,
+        last_seen: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        connection_success_rate: 1.0,
+        capabilities: NodeCapabilities::default(),
+        version: String::new(),
+        is_active: true,
+        status: Default::default(),
+        bytes_sent: 0,
+        bytes_received: 0,
+        connected_peers: 0,
+        gossip_stats: None,
+        full_mesh_probes: None,
+        last_error: None,
+        time_to_first_connection: None,
+    }
+}
+
 /// Detect if the system has global IPv6 connectivity using UDP socket connect.
 ///
 /// This is cross-platform and doesn't require running external commands.
@@ -203,7 +532,7 @@ fn can_reach_peer(peer: &PeerInfo, our_has_ipv6: bool) -> bool {
 /// This approach is cross-platform and doesn't require running external commands.
 /// It works by creating a UDP socket and "connecting" it to a public address -
 /// this doesn't send any data but reveals the local IP that would be used.
-fn detect_local_addresses(bind_port: u16) -> (Option<SocketAddr>, Option<SocketAddr>) {
+pub(super) fn detect_local_addresses(bind_port: u16) -> (Option<SocketAddr>, Option<SocketAddr>) {
     use std::net::UdpSocket;
 
     debug!("Detecting local addresses with bind_port: {}", bind_port);
@@ -258,20 +587,15 @@ fn detect_nat_type(
     local_ipv6: &Option<SocketAddr>,
     local_node: &LocalNodeInfo,
 ) -> NatType {
-    let external_ipv4 = local_node.external_ipv4;
-    let external_ipv6 = local_node.external_ipv6;
-
-    let ipv4_match = match (local_ipv4, external_ipv4) {
-        (Some(local), Some(external)) => local.ip() == external.ip(),
-        _ => false,
-    };
-
-    let ipv6_match = match (local_ipv6, external_ipv6) {
-        (Some(local), Some(external)) => local.ip() == external.ip(),
-        _ => false,
-    };
+    let local: Vec<SocketAddr> = local_ipv4.iter().chain(local_ipv6).copied().collect();
+    let external: Vec<SocketAddr> = local_node
+        .external_ipv4
+        .iter()
+        .chain(&local_node.external_ipv6)
+        .copied()
+        .collect();
 
-    if ipv4_match || ipv6_match {
+    if has_public_address(&local, &external) {
         NatType::None
     } else {
         NatType::Unknown
@@ -287,6 +611,111 @@ const HEALTH_CHECK_INTERVAL_SECS: u64 = 15;
 /// Chance to rotate a peer each health check cycle (1 in N).
 const PEER_ROTATION_CHANCE: u32 = 10;
 
+/// How long shutdown waits for the registry to acknowledge deregistration.
+pub const DEREGISTER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long to wait for a peer to connect back after a ConnectBackRequest:
+/// 30s for the peer's NAT mapping to expire plus 30s for it to dial us.
+const CONNECT_BACK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Deadline for a pending connect-back, measured on a [`Clock`](crate::clock::Clock).
+#[derive(Debug, Clone)]
+struct ConnectBackWait {
+    clock: SharedClock,
+    deadline: Instant,
+}
+
+impl ConnectBackWait {
+    /// Start waiting now for up to `timeout`.
+    fn start(clock: SharedClock, timeout: Duration) -> Self {
+        let deadline = clock.now() + timeout;
+        Self { clock, deadline }
+    }
+
+    /// Whole seconds left before the wait times out.
+    fn seconds_remaining(&self) -> u32 {
+        self.deadline
+            .saturating_duration_since(self.clock.now())
+            .as_secs()
+            .try_into()
+            .unwrap_or(u32::MAX)
+    }
+
+    /// True once the deadline has passed.
+    fn is_expired(&self) -> bool {
+        self.clock.now() >= self.deadline
+    }
+}
+
+/// Minimum time between on-demand connectivity sweeps, so a user holding
+/// the sweep key can't turn it into a connection storm.
+pub const CONNECTIVITY_SWEEP_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Most peers the connect loop dials in one pass.
+const CONNECT_PASS_MAX_DIALS: usize = 20;
+
+/// Peers dialled concurrently during a connectivity sweep.
+const CONNECTIVITY_SWEEP_CONCURRENCY: usize = 8;
+
+/// Most peers a single connectivity sweep will dial.
+const CONNECTIVITY_SWEEP_MAX_PEERS: usize = 100;
+
+/// Admission control for connectivity sweeps: one at a time, and no more
+/// often than the cooldown allows.
+#[derive(Debug, Default)]
+struct SweepGate {
+    running: bool,
+    last_started: Option<Instant>,
+}
+
+impl SweepGate {
+    /// Claim the gate at `now`, or explain why a sweep can't start yet.
+    fn try_begin(&mut self, now: Instant, cooldown: Duration) -> Result<(), String> {
+        if self.running {
+            return Err("a sweep is already running".to_string());
+        }
+        if let Some(last) = self.last_started {
+            let elapsed = now.saturating_duration_since(last);
+            if elapsed < cooldown {
+                return Err(format!(
+                    "rate limited, try again in {}s",
+                    (cooldown - elapsed).as_secs().max(1)
+                ));
+            }
+        }
+        self.running = true;
+        self.last_started = Some(now);
+        Ok(())
+    }
+
+    /// Release the gate once the sweep has finished.
+    fn finish(&mut self) {
+        self.running = false;
+    }
+
+    /// Claim `gate` and return a guard that releases it when dropped.
+    fn begin(
+        gate: &Arc<std::sync::Mutex<SweepGate>>,
+        now: Instant,
+        cooldown: Duration,
+    ) -> Result<SweepGuard, String> {
+        gate.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .try_begin(now, cooldown)?;
+        Ok(SweepGuard(Arc::clone(gate)))
+    }
+}
+
+/// Releases a claimed [`SweepGate`] on drop, so a sweep that panics or is
+/// cancelled mid-flight can't leave the gate held forever.
+struct SweepGuard(Arc<std::sync::Mutex<SweepGate>>);
+
+impl Drop for SweepGuard {
+    fn drop(&mut self) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).finish();
+    }
+}
+
 /// Statistics for a connected peer.
 #[derive(Debug, Clone, Default)]
 pub struct PeerStats {
@@ -300,8 +729,12 @@ pub struct PeerStats {
     pub packets_sent: u64,
     /// Packets received from this peer.
     pub packets_received: u64,
-    /// Last RTT measurement.
+    /// Last RTT measurement (mean over the last burst).
     pub last_rtt: Option<Duration>,
+    /// RTT jitter (standard deviation) over the last burst.
+    pub last_jitter: Option<Duration>,
+    /// Packet loss over the last burst, in percent.
+    pub last_loss_pct: f64,
 }
 
 /// Internal peer tracking.
@@ -334,6 +767,12 @@ struct TrackedPeer {
     quic_test_success: bool,
     /// Whether gossip transport test succeeded (for dual transport testing).
     gossip_test_success: bool,
+    /// Relay carrying this connection, if it is relayed.
+    relay_peer_id: Option<String>,
+    /// Whether the connection resumed the session with 0-RTT.
+    zero_rtt: bool,
+    /// Algorithms read from the connection, if they could be determined.
+    crypto: Option<NegotiatedCrypto>,
 }
 
 impl TrackedPeer {
@@ -341,6 +780,7 @@ impl TrackedPeer {
     fn to_connected_peer(&self) -> ConnectedPeer {
         let mut peer =
             ConnectedPeer::with_direction(&self.info.peer_id, self.method, self.direction);
+        peer.crypto = self.crypto.clone();
 
         // Set location with flag
         if let Some(ref cc) = self.info.country_code {
@@ -349,7 +789,7 @@ impl TrackedPeer {
 
         // Set RTT and quality
         if let Some(rtt) = self.stats.last_rtt {
-            peer.update_rtt(rtt);
+            peer.update_rtt_with_jitter(rtt, self.stats.last_jitter);
         }
 
         peer.packets_sent = self.stats.packets_sent;
@@ -357,6 +797,15 @@ impl TrackedPeer {
         peer.connected_at = self.connected_at;
         peer.addresses = self.info.addresses.clone();
         peer.connectivity = self.connectivity.clone();
+        peer.relay_peer_id = self.relay_peer_id.clone();
+        peer.zero_rtt = self.zero_rtt;
+        peer.address_family = match self.connectivity.active_method {
+            Some(ConnectionMethod::Direct) if self.connectivity.active_is_ipv6 => {
+                Some(AddressFamily::Ipv6)
+            }
+            Some(ConnectionMethod::Direct) => Some(AddressFamily::Ipv4),
+            _ => None,
+        };
 
         // NAT traversal verification state
         peer.outbound_verified = self.outbound_verified;
@@ -397,6 +846,7 @@ pub struct TestNode {
     hole_punched_peers: Arc<RwLock<HashMap<String, bool>>>,
     disconnection_times: Arc<RwLock<HashMap<String, Instant>>>,
     pending_outbound: Arc<RwLock<HashSet<String>>>,
+    pending_pongs: PendingPongs,
     inbound_connections: Arc<AtomicU64>,
     outbound_connections: Arc<AtomicU64>,
     relay_state: Arc<RwLock<RelayState>>,
@@ -407,11 +857,21 @@ pub struct TestNode {
     full_mesh_probes: Arc<RwLock<HashMap<String, FullMeshProbeResult>>>,
     geo_provider: Arc<BgpGeoProvider>,
     fully_tested_peers: Arc<RwLock<HashSet<String>>>,
+    peer_filter: Arc<PeerFilter>,
+    nat_tracer: Arc<NatPhaseTracer>,
+    sweep_gate: Arc<std::sync::Mutex<SweepGate>>,
+    resumption: Arc<ResumptionTracker>,
+    peer_cache: Arc<PersistentPeerCache>,
+    dial_limiter: DialLimiter,
+    join_timer: Arc<JoinTimer>,
+    in_flight: InFlightTasks,
+    /// Peer ID of the startup self-test probe, which is never a real peer.
+    self_test_probe: Option<QuicPeerId>,
 }
 
 /// Get the data directory for persistent storage.
 /// If `custom_dir` is provided, uses that instead of the default platform directory.
-fn get_data_dir(custom_dir: Option<&PathBuf>) -> PathBuf {
+pub(crate) fn get_data_dir(custom_dir: Option<&PathBuf>) -> PathBuf {
     if let Some(dir) = custom_dir {
         dir.clone()
     } else {
@@ -530,6 +990,21 @@ impl TestNode {
     ) -> Result<Self, anyhow::Error> {
         let registry = RegistryClient::new(&config.registry_url);
         let data_dir = get_data_dir(config.data_dir.as_ref());
+        let peer_filter = Arc::new(
+            PeerFilter::new(&config.blacklist, config.allowlist.as_deref())
+                .map_err(anyhow::Error::msg)?,
+        );
+        if !config.blacklist.is_empty() {
+            info!("Blacklist active: {} entries", config.blacklist.len());
+        }
+        if let Some(allowlist) = &config.allowlist {
+            let msg = format!(
+                "Allowlist mode: only dialing {} listed peers/ranges, other discovery ignored",
+                allowlist.len()
+            );
+            info!("{}", msg);
+            let _ = event_tx.try_send(TuiEvent::Info(msg));
+        }
 
         info!("Creating unified QUIC endpoint via gossip transport...");
 
@@ -541,19 +1016,51 @@ impl TestNode {
 
         let (epidemic_event_tx, epidemic_event_rx) = mpsc::channel(100);
         // In local_only mode, skip external VPS bootstrap to avoid NAT traversal issues in Docker
-        let vps_bootstrap = if config.local_only {
+        let vps_bootstrap = if !config.bootstrap_peers.is_empty() {
+            info!(
+                "Using {} configured bootstrap peers instead of the VPS list",
+                config.bootstrap_peers.len()
+            );
+            config.bootstrap_peers.clone()
+        } else if config.local_only {
             info!("Local-only mode: skipping external VPS bootstrap peers");
             Vec::new()
         } else {
             vps_gossip_bootstrap_addrs()
         };
-        let epidemic_config = EpidemicConfig {
+        let peer_cache_path = config
+            .peer_cache_path
+            .clone()
+            .unwrap_or_else(|| data_dir.join(PEER_CACHE_FILE));
+        let peer_cache = Arc::new(PersistentPeerCache::load(
+            peer_cache_path,
+            config.peer_cache_ttl,
+        ));
+        if !peer_cache.is_empty() {
+            let msg = format!(
+                "Loaded {} peers from cache {:?}",
+                peer_cache.len(),
+                peer_cache.path()
+            );
+            info!("{}", msg);
+            let _ = event_tx.try_send(TuiEvent::Info(msg));
+        }
+        let gossip_config = GossipConfig {
+            cache_path: Some(data_dir.join("peer_cache.cbor")),
+            wire_format: config.gossip_wire_format,
+            gossip_fanout: config.gossip_fanout,
+            active_view_size: config.active_view_size,
+            passive_view_size: config.passive_view_size,
+            ..GossipConfig::default()
+        };
+        gossip_config.validate()?;
+        let epidemic_config = gossip_config.apply_view_sizes(EpidemicConfig {
             listen_addr: config.bind_addr,
             bootstrap_peers: vps_bootstrap.clone(),
             registry_url: Some(config.registry_url.clone()),
             keypair: Some(keypair_bytes.clone()),
             ..EpidemicConfig::default()
-        };
+        });
 
         let temp_peer_id = {
             let peer_id_bytes = hex::decode("0".repeat(64)).unwrap_or_else(|_| vec![0u8; 32]);
@@ -590,6 +1097,27 @@ impl TestNode {
             node.public_key_bytes().len()
         );
 
+        // Fail fast if our own QUIC stack cannot complete a handshake
+        let self_test_probe = match node.local_addr() {
+            Some(bound) => {
+                let target = super::self_test::loopback_target(bound);
+                let pass = super::self_test::loopback_handshake_test(
+                    target,
+                    super::self_test::SELF_TEST_TIMEOUT,
+                )
+                .await
+                .map_err(|e| anyhow::anyhow!("Local QUIC self-test failed: {}", e))?;
+                info!("Loopback self-test passed in {:?}", pass.elapsed);
+                // The probe is not a peer; drop its connection right away
+                let _ = node.disconnect(&pass.probe_peer_id).await;
+                Some(pass.probe_peer_id)
+            }
+            None => {
+                warn!("No bound address, skipping loopback self-test");
+                None
+            }
+        };
+
         let actual_port = node
             .local_addr()
             .map(|a| a.port())
@@ -621,6 +1149,7 @@ impl TestNode {
         local_node.local_ipv6 = local_ipv6;
         local_node.nat_type = NatType::Unknown;
         local_node.registered = false;
+        local_node.connect_timeout = Some(config.connect_timeout);
 
         // Send initial node info to TUI (non-blocking to avoid deadlock)
         let _ = event_tx.try_send(TuiEvent::UpdateLocalNode(local_node.clone()));
@@ -677,10 +1206,6 @@ impl TestNode {
 
         // Initialize gossip integration layer with bootstrap cache (created early for event handler)
         let (gossip_event_tx, gossip_event_rx) = mpsc::channel(100);
-        let gossip_config = GossipConfig {
-            cache_path: Some(data_dir.join("peer_cache.cbor")),
-            ..GossipConfig::default()
-        };
         let gossip_integration = Arc::new(GossipIntegration::new(
             peer_id.clone(),
             listen_addresses.clone(),
@@ -690,14 +1215,27 @@ impl TestNode {
             gossip_config,
             gossip_event_tx,
         ));
+        // The discovery topics this node consumes
+        gossip_integration.discovery().subscribe(TOPIC_PEERS).await;
+        gossip_integration
+            .discovery()
+            .subscribe(TOPIC_LATENCY_PROBE)
+            .await;
         info!(
             "Initialized gossip integration with bootstrap cache ({} cached peers)",
             gossip_integration.cache_size()
         );
 
+        let nat_tracer = Arc::new(NatPhaseTracer::new());
+        let join_timer = Arc::new(JoinTimer::new(Arc::clone(&config.clock)));
+        let in_flight = InFlightTasks::default();
+        let dial_limiter = DialLimiter::new(config.max_concurrent_dials);
+
         let node_for_events = Arc::clone(&node);
         let event_tx_for_events = event_tx.clone();
         let hole_punched_for_events = Arc::clone(&hole_punched_peers);
+        let nat_tracer_for_events = Arc::clone(&nat_tracer);
+        let nat_coordinator_addrs_for_events = vps_bootstrap.clone();
         let external_addresses_for_events = Arc::clone(&external_addresses);
         let disconnection_times_for_events = Arc::clone(&disconnection_times);
         let pending_outbound_for_events = Arc::clone(&pending_outbound);
@@ -708,8 +1246,14 @@ impl TestNode {
         let listen_addresses_for_events = listen_addresses.clone();
         let connected_peers_for_events = Arc::clone(&connected_peers);
         let peer_id_for_events = peer_id.clone();
-        let registry_url_for_events = config.registry_url.clone();
+        let connect_timeout_for_events = config.connect_timeout;
+        let registry_for_events = registry.clone();
+        let in_flight_for_events = in_flight.clone();
+        let clock_for_events = Arc::clone(&config.clock);
         let nat_stats_for_events = Arc::clone(&nat_stats);
+        let join_timer_for_events = Arc::clone(&join_timer);
+        let peer_filter_for_events = Arc::clone(&peer_filter);
+        let dial_limiter_for_events = dial_limiter.clone();
         let local_ipv4_for_events = local_ipv4;
         let local_ipv6_for_events = local_ipv6;
         // For comprehensive peer cache sharing - include ALL known peers, not just connected
@@ -743,6 +1287,7 @@ impl TestNode {
 
                         let mut local_node = LocalNodeInfo::default();
                         local_node.set_peer_id(&peer_id_for_events);
+                        local_node.connect_timeout = Some(connect_timeout_for_events);
                         local_node.local_ipv4 = local_ipv4_for_events;
                         local_node.local_ipv6 = local_ipv6_for_events;
                         for a in addrs.iter() {
@@ -792,10 +1337,25 @@ impl TestNode {
                             TraversalPhase::Failed => NatTraversalPhase::Relayed,
                         };
 
+                        let coordinator =
+                            nat_coordinator(&node_for_events, &nat_coordinator_addrs_for_events)
+                                .await;
+                        // Settle the closing phase's frames, then baseline the new one
+                        if let Some((_, frames)) = &coordinator {
+                            nat_tracer_for_events.coordinator_frames(&peer_hex, *frames);
+                        }
+                        nat_tracer_for_events.transition(
+                            &peer_hex,
+                            tui_phase,
+                            coordinator.as_ref().map(|(id, _)| id.as_str()),
+                        );
+                        if let Some((_, frames)) = &coordinator {
+                            nat_tracer_for_events.coordinator_frames(&peer_hex, *frames);
+                        }
                         let _ = event_tx_for_events.try_send(TuiEvent::NatPhaseUpdate {
                             peer_id: peer_hex.clone(),
                             phase: tui_phase,
-                            coordinator_id: None,
+                            coordinator_id: coordinator.map(|(id, _)| id),
                         });
 
                         let frame_type = match &phase {
@@ -824,6 +1384,9 @@ impl TestNode {
                             hole_punched_for_events.write().await.insert(peer_hex, true);
                         }
                     }
+                    P2pEvent::PeerConnected { peer_id, .. } if Some(peer_id) == self_test_probe => {
+                        debug!("Ignoring self-test probe connection");
+                    }
                     P2pEvent::PeerConnected {
                         peer_id,
                         addr,
@@ -842,6 +1405,24 @@ impl TestNode {
                         // Side::Client means WE connected to THEM (outbound)
                         let is_inbound = side.is_server();
 
+                        // The handshake has completed: flag a classical
+                        // fallback here so headless runs see it too
+                        let crypto = peer_crypto(&node_for_events, &peer_id);
+                        if let Some(fallback) = crypto.as_ref().filter(|c| !c.is_pqc()) {
+                            warn!(
+                                "Connection to {} may have fallen back to classical crypto (offered {})",
+                                &peer_hex[..8.min(peer_hex.len())],
+                                fallback.label()
+                            );
+                            send_tui_event(
+                                &event_tx_for_events,
+                                TuiEvent::ClassicalCryptoFallback {
+                                    peer_id: peer_hex.clone(),
+                                    crypto: fallback.clone(),
+                                },
+                            );
+                        }
+
                         if addr.is_ipv4() {
                             let _ = event_tx_for_events.try_send(TuiEvent::Ipv4Connection);
                         } else {
@@ -875,6 +1456,7 @@ impl TestNode {
                                 ConnectionDirection::Inbound,
                             );
                             inbound_peer.addresses = vec![addr];
+                            inbound_peer.crypto = crypto.clone();
 
                             // Set connectivity matrix based on address type + NAT success
                             let is_ipv6 = addr.is_ipv6();
@@ -914,6 +1496,7 @@ impl TestNode {
                                 ConnectionDirection::Outbound,
                             );
                             outbound_peer.addresses = vec![addr];
+                            outbound_peer.crypto = crypto.clone();
 
                             // Set connectivity matrix based on address type
                             let is_ipv6 = addr.is_ipv6();
@@ -964,6 +1547,7 @@ impl TestNode {
                                     country_code: None,
                                     latitude: 0.0,
                                     longitude: 0.0,
+                                    location_estimated: false,
                                     last_seen: std::time::SystemTime::now()
                                         .duration_since(std::time::UNIX_EPOCH)
                                         .map(|d| d.as_secs())
@@ -978,6 +1562,8 @@ impl TestNode {
                                     connected_peers: 0,
                                     gossip_stats: None,
                                     full_mesh_probes: None,
+                                    last_error: None,
+                                    time_to_first_connection: None,
                                 };
 
                                 // Set connectivity based on actual address
@@ -1016,9 +1602,15 @@ impl TestNode {
                                     last_nat_test_time: None,
                                     quic_test_success: false,
                                     gossip_test_success: false,
+                                    relay_peer_id: None,
+                                    zero_rtt: false,
+                                    crypto,
                                 };
 
                                 peers.insert(peer_hex.clone(), tracked);
+                                if outbound_verified && inbound_verified {
+                                    join_timer_for_events.mark_verified();
+                                }
 
                                 // Increment outbound counter for lock-free heartbeat access
                                 if !is_inbound {
@@ -1076,12 +1668,11 @@ impl TestNode {
                                     to_peer: peer_hex.clone(),
                                     method,
                                     is_ipv6,
-                                    rtt_ms: None,
+                                    rtt_ms: connection_rtt_ms(&node_for_events, &peer_id),
                                     connectivity: connectivity_for_report,
                                 };
-                                let registry_url = registry_url_for_events.clone();
-                                tokio::spawn(async move {
-                                    let registry = RegistryClient::new(&registry_url);
+                                let registry = registry_for_events.clone();
+                                in_flight_for_events.spawn(async move {
                                     if let Err(e) = registry.report_connection(&report).await {
                                         debug!("Failed to report connection to registry: {}", e);
                                     }
@@ -1165,6 +1756,9 @@ impl TestNode {
                             let event_tx_for_callback = event_tx_for_events.clone();
                             let connected_peers_for_callback =
                                 Arc::clone(&connected_peers_for_events);
+                            let clock_for_callback = Arc::clone(&clock_for_events);
+                            let peer_filter_for_callback = Arc::clone(&peer_filter_for_events);
+                            let dial_limiter_for_callback = dial_limiter_for_events.clone();
 
                             tokio::spawn(async move {
                                 use super::test_protocol::ConnectBackRequest;
@@ -1205,15 +1799,19 @@ impl TestNode {
                                                 peer_short
                                             );
 
+                                            let wait = ConnectBackWait::start(
+                                                clock_for_callback,
+                                                CONNECT_BACK_TIMEOUT,
+                                            );
+                                            let mut last_reported = wait.seconds_remaining();
                                             let _ = event_tx_for_callback.try_send(
                                                 TuiEvent::NatTestWaitingForConnectBack {
                                                     peer_id: target_peer_hex.clone(),
-                                                    seconds_remaining: 60,
+                                                    seconds_remaining: last_reported,
                                                 },
                                             );
 
-                                            // 60s = 30s for peer's NAT to expire + 30s for them to connect
-                                            for countdown in (0..60).rev() {
+                                            while !wait.is_expired() {
                                                 tokio::time::sleep(Duration::from_secs(1)).await;
 
                                                 let peers =
@@ -1230,14 +1828,18 @@ impl TestNode {
                                                 }
                                                 drop(peers);
 
-                                                if countdown % 10 == 0 && countdown > 0 {
+                                                let remaining = wait.seconds_remaining();
+                                                if remaining > 0
+                                                    && remaining / 10 < last_reported / 10
+                                                {
                                                     let _ = event_tx_for_callback.try_send(
                                                         TuiEvent::NatTestWaitingForConnectBack {
                                                             peer_id: target_peer_hex.clone(),
-                                                            seconds_remaining: countdown,
+                                                            seconds_remaining: remaining,
                                                         },
                                                     );
                                                 }
+                                                last_reported = remaining;
                                             }
 
                                             info!(
@@ -1253,10 +1855,20 @@ impl TestNode {
                                             // Skip reconnection attempt for VPS nodes - they have public IPs
                                             // and don't need NAT traversal. The connect() call triggers
                                             // ant-quic's NAT coordination which can freeze the node.
-                                            if we_are_vps(&our_addresses) {
+                                            // Filtered peers are never redialed either.
+                                            let skip_reason = if we_are_vps(&our_addresses) {
+                                                Some("VPS node")
+                                            } else if !peer_filter_for_callback
+                                                .may_dial_peer(&target_peer_hex)
+                                            {
+                                                Some("rejected by peer filter")
+                                            } else {
+                                                None
+                                            };
+                                            if let Some(reason) = skip_reason {
                                                 info!(
-                                                    "ConnectBackRequest: {} timeout, skipping reconnect (VPS node)",
-                                                    peer_short
+                                                    "ConnectBackRequest: {} timeout, skipping reconnect ({})",
+                                                    peer_short, reason
                                                 );
                                                 let _ = event_tx_for_callback.try_send(
                                                     TuiEvent::NatTestPeerUnreachable {
@@ -1272,6 +1884,8 @@ impl TestNode {
                                                 },
                                             );
 
+                                            let _dial_slot =
+                                                dial_limiter_for_callback.acquire().await;
                                             let retry_result = tokio::time::timeout(
                                                 Duration::from_secs(10),
                                                 node_for_callback.connect(target_peer),
@@ -1392,6 +2006,7 @@ impl TestNode {
             hole_punched_peers,
             disconnection_times,
             pending_outbound,
+            pending_pongs: PendingPongs::default(),
             inbound_connections,
             outbound_connections,
             relay_state,
@@ -1402,6 +2017,15 @@ impl TestNode {
             full_mesh_probes: Arc::new(RwLock::new(HashMap::new())),
             geo_provider,
             fully_tested_peers: Arc::new(RwLock::new(HashSet::new())),
+            peer_filter,
+            nat_tracer,
+            sweep_gate: Arc::new(std::sync::Mutex::new(SweepGate::default())),
+            resumption: Arc::new(ResumptionTracker::new()),
+            peer_cache,
+            dial_limiter,
+            join_timer,
+            in_flight,
+            self_test_probe,
         })
     }
 
@@ -1475,6 +2099,33 @@ impl TestNode {
             );
         }
 
+        // Prefer a connected relay that announced itself over gossip, chosen
+        // by the configured policy rather than always the first one.
+        if let Some(announced) = select_announced_relay(
+            self.gossip_integration.discovery(),
+            &self.connected_peers,
+            &[],
+            &target_hex,
+            self.config.relay_selection,
+        )
+        .await
+        {
+            let relay_id = peer_id_to_bytes(&announced.peer_id);
+            if candidates
+                .iter()
+                .any(|c| c.is_connected && c.peer_id == relay_id)
+            {
+                info!(
+                    "Selected announced relay {} ({}/{} connections, policy={:?})",
+                    &announced.peer_id[..8.min(announced.peer_id.len())],
+                    announced.active_connections,
+                    announced.max_connections,
+                    self.config.relay_selection
+                );
+                return Some(relay_id);
+            }
+        }
+
         // TODO: Implement CAN_YOU_REACH message exchange
         // For now, return the highest priority connected peer as potential relay
         // This will be enhanced with actual reachability checking
@@ -1511,37 +2162,28 @@ impl TestNode {
         rs.get_relay_for(target_peer_id)
     }
 
-    /// Connect to a peer with relay fallback.
-    ///
-    /// This implements the connection strategy:
-    /// 1. Try direct IPv4 connection
-    /// 2. Try direct IPv6 connection (if available)
-    /// 3. Try NAT traversal (hole-punching via ant-quic)
-    /// 4. If all fail, find a relay and use it for PUNCH_ME_NOW exchange
-    /// 5. If relay-assisted holepunch fails, keep the relay for traffic
+    /// Try direct IPv4, direct IPv6 and then NAT traversal to `peer`.
     ///
-    /// Returns the connection method used and whether we're using a relay.
-    pub async fn connect_with_relay_fallback(
+    /// Returns the method that succeeded, or `None` once all have failed.
+    async fn connect_direct_or_nat(
         &self,
         peer: &crate::PeerInfo,
-    ) -> Result<(ConnectionMethod, Option<[u8; 32]>), String> {
+        target_peer_id: &[u8; 32],
+    ) -> Option<ConnectionMethod> {
         let peer_id_short = &peer.peer_id[..8.min(peer.peer_id.len())];
-        let target_peer_id = peer_id_to_bytes(&peer.peer_id);
-
-        info!(
-            "Connecting to {} with relay fallback enabled",
-            peer_id_short
-        );
+        let direct_timeout = DIRECT_DIAL_TIMEOUT.min(self.config.connect_timeout);
+        let addrs = self
+            .peer_filter
+            .dial_addrs(Some(&peer.peer_id), &peer.addresses);
 
         // 1. Try direct IPv4 connections
-        for addr in peer.addresses.iter().filter(|a| a.is_ipv4()) {
-            match tokio::time::timeout(Duration::from_secs(10), self.node.connect_addr(*addr)).await
-            {
+        for addr in addrs.iter().filter(|a| a.is_ipv4()) {
+            match tokio::time::timeout(direct_timeout, self.node.connect_addr(*addr)).await {
                 Ok(Ok(_conn)) => {
                     info!("Direct IPv4 connection to {} succeeded", peer_id_short);
                     // Clear any existing relay since direct works
-                    self.remove_relay(&target_peer_id).await;
-                    return Ok((ConnectionMethod::Direct, None));
+                    self.remove_relay(target_peer_id).await;
+                    return Some(ConnectionMethod::Direct);
                 }
                 Ok(Err(e)) => {
                     debug!("Direct IPv4 to {} at {} failed: {}", peer_id_short, addr, e);
@@ -1553,13 +2195,12 @@ impl TestNode {
         }
 
         // 2. Try direct IPv6 connections
-        for addr in peer.addresses.iter().filter(|a| a.is_ipv6()) {
-            match tokio::time::timeout(Duration::from_secs(10), self.node.connect_addr(*addr)).await
-            {
+        for addr in addrs.iter().filter(|a| a.is_ipv6()) {
+            match tokio::time::timeout(direct_timeout, self.node.connect_addr(*addr)).await {
                 Ok(Ok(_conn)) => {
                     info!("Direct IPv6 connection to {} succeeded", peer_id_short);
-                    self.remove_relay(&target_peer_id).await;
-                    return Ok((ConnectionMethod::Direct, None));
+                    self.remove_relay(target_peer_id).await;
+                    return Some(ConnectionMethod::Direct);
                 }
                 Ok(Err(e)) => {
                     debug!("Direct IPv6 to {} at {} failed: {}", peer_id_short, addr, e);
@@ -1582,7 +2223,7 @@ impl TestNode {
             );
         }
 
-        if !skip_nat_traversal {
+        if !skip_nat_traversal && self.peer_filter.may_dial_peer(&peer.peer_id) {
             if let Ok(peer_id_bytes) = hex::decode(&peer.peer_id) {
                 if peer_id_bytes.len() >= 32 {
                     let mut peer_id_array = [0u8; 32];
@@ -1590,15 +2231,15 @@ impl TestNode {
                     let quic_peer_id = QuicPeerId(peer_id_array);
 
                     match tokio::time::timeout(
-                        Duration::from_secs(30),
+                        self.config.connect_timeout,
                         self.node.connect(quic_peer_id),
                     )
                     .await
                     {
                         Ok(Ok(_conn)) => {
                             info!("NAT traversal to {} succeeded", peer_id_short);
-                            self.remove_relay(&target_peer_id).await;
-                            return Ok((ConnectionMethod::HolePunched, None));
+                            self.remove_relay(target_peer_id).await;
+                            return Some(ConnectionMethod::HolePunched);
                         }
                         Ok(Err(e)) => {
                             debug!("NAT traversal to {} failed: {}", peer_id_short, e);
@@ -1611,9 +2252,55 @@ impl TestNode {
             }
         }
 
-        // 4. All direct methods failed - try to find a relay
+        None
+    }
+
+    /// Connect to a peer with relay fallback.
+    ///
+    /// This implements the connection strategy:
+    /// 1. Try direct IPv4 connection
+    /// 2. Try direct IPv6 connection (if available)
+    /// 3. Try NAT traversal (hole-punching via ant-quic)
+    /// 4. If all fail, or none succeed within `relay_fallback_after`, find a
+    ///    relay and use it for PUNCH_ME_NOW exchange
+    /// 5. If relay-assisted holepunch fails, keep the relay for traffic
+    ///
+    /// Returns the connection method used and whether we're using a relay.
+    pub async fn connect_with_relay_fallback(
+        &self,
+        peer: &crate::PeerInfo,
+    ) -> Result<(ConnectionMethod, Option<[u8; 32]>), String> {
+        let peer_id_short = &peer.peer_id[..8.min(peer.peer_id.len())];
+        let target_peer_id = peer_id_to_bytes(&peer.peer_id);
+
+        if !self.peer_filter.may_dial_peer(&peer.peer_id)
+            && self
+                .peer_filter
+                .dial_addrs(Some(&peer.peer_id), &peer.addresses)
+                .is_empty()
+        {
+            return Err(format!("{} is excluded by the peer filter", peer_id_short));
+        }
+
+        // Held for the whole attempt, relay lookup included
+        let _dial_slot = self.dial_limiter.acquire().await;
+        info!(
+            "Connecting to {} with relay fallback enabled",
+            peer_id_short
+        );
+
+        if let Some(method) = attempt_within_relay_window(
+            self.connect_direct_or_nat(peer, &target_peer_id),
+            self.config.relay_fallback_after,
+        )
+        .await
+        {
+            return Ok((method, None));
+        }
+
+        // 4. Direct methods failed or ran out of time - try to find a relay
         info!(
-            "All direct connections failed for {}, looking for relay...",
+            "Direct connections to {} failed or exceeded fallback window, looking for relay...",
             peer_id_short
         );
 
@@ -1737,6 +2424,7 @@ impl TestNode {
         let event_tx = self.event_tx.clone();
         let inbound_connections = Arc::clone(&self.inbound_connections);
         let max_peers = self.config.max_peers;
+        let self_test_probe = self.self_test_probe;
 
         tokio::spawn(async move {
             info!("Accept loop started - listening for incoming connections");
@@ -1744,6 +2432,10 @@ impl TestNode {
             while !shutdown.load(Ordering::SeqCst) {
                 // Accept incoming connection
                 if let Some(peer_conn) = endpoint.accept().await {
+                    if Some(peer_conn.peer_id) == self_test_probe {
+                        debug!("Ignoring self-test probe connection");
+                        continue;
+                    }
                     let new_peer_hex = hex::encode(peer_conn.peer_id.0);
                     let addr = peer_conn.remote_addr;
                     info!(
@@ -1768,6 +2460,7 @@ impl TestNode {
                                 country_code: None,
                                 latitude: 0.0,
                                 longitude: 0.0,
+                                location_estimated: false,
                                 last_seen: std::time::SystemTime::now()
                                     .duration_since(std::time::UNIX_EPOCH)
                                     .map(|d| d.as_secs())
@@ -1782,6 +2475,8 @@ impl TestNode {
                                 connected_peers: 0,
                                 gossip_stats: None,
                                 full_mesh_probes: None,
+                                last_error: None,
+                                time_to_first_connection: None,
                             };
 
                             // Set connectivity based on actual address
@@ -1810,6 +2505,9 @@ impl TestNode {
                                 last_nat_test_time: None,
                                 quic_test_success: false,
                                 gossip_test_success: false,
+                                relay_peer_id: None,
+                                zero_rtt: false,
+                                crypto: peer_crypto(&endpoint, &peer_conn.peer_id),
                             };
 
                             peers.insert(new_peer_hex.clone(), tracked);
@@ -1924,6 +2622,7 @@ impl TestNode {
                         ConnectionDirection::Inbound,
                     );
                     tui_peer.addresses = vec![addr];
+                    tui_peer.crypto = peer_crypto(&endpoint, &peer_conn.peer_id);
                     // Set connectivity matrix based on address type (inbound = NAT traversal success)
                     let is_ipv6 = addr.is_ipv6();
                     tui_peer.connectivity.ipv4_direct_tested = !is_ipv6;
@@ -1965,10 +2664,14 @@ impl TestNode {
         let endpoint = Arc::clone(&self.node);
         let connected_peers = Arc::clone(&self.connected_peers);
         let fully_tested_peers = Arc::clone(&self.fully_tested_peers);
+        let join_timer = Arc::clone(&self.join_timer);
         let max_peers = self.config.max_peers;
         let epidemic_gossip = Arc::clone(&self.epidemic_gossip);
         let relay_state = Arc::clone(&self.relay_state);
         let external_addresses = Arc::clone(&self.external_addresses);
+        let peer_filter = Arc::clone(&self.peer_filter);
+        let dial_limiter = self.dial_limiter.clone();
+        let nat_tracer = Arc::clone(&self.nat_tracer);
 
         tokio::spawn(async move {
             // Periodic cleanup and announcement ticker
@@ -2032,9 +2735,12 @@ impl TestNode {
                                         debug!("Gossip: already connected to {}, skipping", peer_id_short);
                                     } else if at_capacity {
                                         debug!("Gossip: at max peers ({}), skipping {}", max_peers, peer_id_short);
-                                    } else if !announcement.addresses.is_empty() {
-                                        // Try to connect to the first available address
-                                        let addr = announcement.addresses[0];
+                                    } else if let Some(addr) = first_dialable_addr(
+                                        &peer_filter,
+                                        &announcement.peer_id,
+                                        &announcement.addresses,
+                                    ) {
+                                        // Try to connect to the first dialable address
                                         info!(
                                             "Gossip: attempting connection to {} at {}",
                                             peer_id_short, addr
@@ -2042,9 +2748,11 @@ impl TestNode {
 
                                         // Spawn connection attempt (don't block the gossip loop)
                                         let endpoint_clone = Arc::clone(&endpoint);
+                                        let dial_limiter = dial_limiter.clone();
                                         let gossip_clone = Arc::clone(&gossip_integration);
                                         let peer_id_for_task = announcement.peer_id.clone();
                                         tokio::spawn(async move {
+                                            let _dial_slot = dial_limiter.acquire().await;
                                             match tokio::time::timeout(
                                                 Duration::from_secs(10),
                                                 endpoint_clone.connect_addr(addr)
@@ -2260,22 +2968,29 @@ impl TestNode {
                                         let inner_ep_for_broadcast = Arc::clone(endpoint.inner_endpoint());
                                         let connected_for_broadcast = Arc::clone(&connected_peers);
                                         let sender_id = sender_hex.clone();
+                                        let gossip_for_broadcast = Arc::clone(&gossip_integration);
                                         tokio::spawn(async move {
                                             let _ = broadcast_peer_announcement(
                                                 &inner_ep_for_broadcast,
                                                 &connected_for_broadcast,
                                                 &announcement,
                                                 Some(&sender_id),
+                                                gossip_for_broadcast.discovery(),
                                             ).await;
                                         });
 
                                         // Attempt connection to new peer
-                                        if !peer_info.addresses.is_empty() {
-                                            let addr = peer_info.addresses[0];
+                                        if let Some(addr) = first_dialable_addr(
+                                            &peer_filter,
+                                            &peer_info.peer_id,
+                                            &peer_info.addresses,
+                                        ) {
                                             let endpoint_clone = Arc::clone(&endpoint);
+                                            let dial_limiter = dial_limiter.clone();
                                             let gossip_clone = Arc::clone(&gossip_integration);
                                             let peer_id_for_connect = peer_info.peer_id.clone();
                                             tokio::spawn(async move {
+                                                let _dial_slot = dial_limiter.acquire().await;
                                                 if let Ok(Ok(_)) = tokio::time::timeout(
                                                     Duration::from_secs(10),
                                                     endpoint_clone.connect_addr(addr),
@@ -2330,12 +3045,22 @@ impl TestNode {
                                         let at_capacity = connected.len() >= max_peers;
                                         drop(connected);
 
-                                        if !already_connected && !at_capacity {
-                                            let addr = peer_info.addresses[0];
+                                        let dial_addr = (!already_connected && !at_capacity)
+                                            .then(|| {
+                                                first_dialable_addr(
+                                                    &peer_filter,
+                                                    &peer_info.peer_id,
+                                                    &peer_info.addresses,
+                                                )
+                                            })
+                                            .flatten();
+                                        if let Some(addr) = dial_addr {
                                             let endpoint_clone = Arc::clone(&endpoint);
+                                            let dial_limiter = dial_limiter.clone();
                                             let gossip_clone = Arc::clone(&gossip_integration);
                                             let peer_id_for_connect = peer_info.peer_id.clone();
                                             tokio::spawn(async move {
+                                                let _dial_slot = dial_limiter.acquire().await;
                                                 if let Ok(Ok(_)) = tokio::time::timeout(
                                                     Duration::from_secs(10),
                                                     endpoint_clone.connect_addr(addr),
@@ -2361,8 +3086,12 @@ impl TestNode {
 
                                             if request.requester_peer_id != peer_id {
                                                 let endpoint_clone = Arc::clone(&endpoint);
+                                                let dial_limiter = dial_limiter.clone();
                                                 let requester_id = request.requester_peer_id.clone();
-                                                let addresses = request.requester_addresses.clone();
+                                                let addresses = peer_filter.dial_addrs(
+                                                    Some(&request.requester_peer_id),
+                                                    &request.requester_addresses,
+                                                );
                                                 let event_tx_clone = event_tx.clone();
 
                                                 tokio::spawn(async move {
@@ -2386,6 +3115,7 @@ impl TestNode {
                                                         addresses.len()
                                                     );
 
+                                                    let _dial_slot = dial_limiter.acquire().await;
                                                     for addr in &addresses {
                                                         match tokio::time::timeout(
                                                             Duration::from_secs(10),
@@ -2445,11 +3175,13 @@ impl TestNode {
                                                 let responder_id = response.responder_peer_id.clone();
                                                 let connected_peers_clone = Arc::clone(&connected_peers);
                                                 let fully_tested_clone = Arc::clone(&fully_tested_peers);
+                                                let join_timer_clone = Arc::clone(&join_timer);
                                                 tokio::spawn(async move {
                                                     let mut peers = connected_peers_clone.write().await;
                                                     if let Some(tracked) = peers.get_mut(&responder_id) {
                                                         tracked.inbound_verified = true;
                                                         if tracked.outbound_verified {
+                                                            join_timer_clone.mark_verified();
                                                             let mut tested = fully_tested_clone.write().await;
                                                             tested.insert(responder_id.clone());
                                                         }
@@ -2459,9 +3191,13 @@ impl TestNode {
                                         }
                                         GossipMessage::DisconnectAndConnectBack(request) => {
                                             let requester_id = request.requester_peer_id.clone();
-                                            let addresses = request.requester_addresses.clone();
+                                            let addresses = peer_filter.dial_addrs(
+                                                Some(&request.requester_peer_id),
+                                                &request.requester_addresses,
+                                            );
                                             let delay = request.delay_seconds;
                                             let endpoint_clone = Arc::clone(&endpoint);
+                                            let dial_limiter = dial_limiter.clone();
                                             let event_tx_clone = event_tx.clone();
                                             let peer_id_clone = peer_id.clone();
 
@@ -2479,6 +3215,7 @@ impl TestNode {
 
                                                 tokio::time::sleep(Duration::from_secs(delay as u64)).await;
 
+                                                let _dial_slot = dial_limiter.acquire().await;
                                                 for addr in &addresses {
                                                     match tokio::time::timeout(
                                                         Duration::from_secs(15),
@@ -2525,6 +3262,22 @@ impl TestNode {
                                         _ => {}
                                     }
                                 }
+                            } else if let Ok(packet) = TestPacket::from_bytes(&data) {
+                                if let Err(e) = packet.validate() {
+                                    warn!(
+                                        "Dropping invalid QUIC test packet from {} (seq={}): {}",
+                                        &sender_hex[..8.min(sender_hex.len())],
+                                        packet.sequence,
+                                        e
+                                    );
+                                } else {
+                                    debug!(
+                                        "QUIC test packet from {} (seq={}, {} bytes)",
+                                        &sender_hex[..8.min(sender_hex.len())],
+                                        packet.sequence,
+                                        data.len()
+                                    );
+                                }
                             } else if RelayMessage::is_relay_message(&data) {
                                 // Handle relay messages
                                 debug!(
@@ -2533,6 +3286,22 @@ impl TestNode {
                                     &sender_hex[..8.min(sender_hex.len())]
                                 );
 
+                                // A forwarded PUNCH_ME_NOW addressed to us: the sender
+                                // relayed it, so it coordinates our punch to the requester
+                                if let Ok(RelayMessage::RelayPunchMeNow(req)) =
+                                    RelayMessage::from_bytes(&data)
+                                {
+                                    if req.target_peer_id == peer_id_to_bytes(&peer_id) {
+                                        let requester_hex = hex::encode(req.requester_peer_id);
+                                        nat_tracer.transition(
+                                            &requester_hex,
+                                            NatTraversalPhase::Punching,
+                                            Some(&sender_hex),
+                                        );
+                                        nat_tracer.frame(&requester_hex, FrameDirection::Received);
+                                    }
+                                }
+
                                 // Process the relay message
                                 let response = handle_relay_message_standalone(
                                     &data,
@@ -2540,6 +3309,8 @@ impl TestNode {
                                     &relay_state,
                                     &peer_id,
                                     &external_addresses,
+                                    &peer_filter,
+                                    &dial_limiter,
                                 ).await;
 
                                 // Send response back to the sender if any
@@ -2620,15 +3391,10 @@ impl TestNode {
                 .unwrap_or(0),
         };
 
-        let announcement = GossipPeerAnnouncement::new(peer_info, self.peer_id.clone(), 8);
+        let announcement = GossipPeerAnnouncement::new(peer_info, self.peer_id.clone(), 8)
+            .with_topics(self.gossip_integration.discovery().subscribed_topics());
 
-        let payload = match announcement.to_bytes() {
-            Ok(p) => p,
-            Err(e) => {
-                warn!("Failed to serialize announcement: {}", e);
-                return;
-            }
-        };
+        let payload = announcement.to_wire(self.gossip_integration.discovery());
 
         let epidemic_peers = self.epidemic_gossip.connected_peers_with_addresses().await;
         let use_epidemic = self.epidemic_gossip.is_running() && !epidemic_peers.is_empty();
@@ -2656,16 +3422,17 @@ impl TestNode {
         }
     }
 
-    /// Broadcast data to all connected peers via P2pEndpoint (fallback for NATted clients).
+    /// Broadcast data to the connected peers, or a random gossip fanout of
+    /// them, via P2pEndpoint (fallback for NATted clients).
     async fn broadcast_via_p2p_endpoint(&self, data: &[u8]) {
-        let peers = self.connected_peers.read().await;
+        let peers: Vec<String> = self.connected_peers.read().await.keys().cloned().collect();
         if peers.is_empty() {
             debug!("No P2pEndpoint peers to broadcast to");
             return;
         }
 
         let mut sent = 0;
-        for peer_hex in peers.keys() {
+        for peer_hex in self.gossip_integration.discovery().fanout_targets(peers) {
             if let Ok(peer_bytes) = hex::decode(peer_hex) {
                 if peer_bytes.len() == 32 {
                     let mut arr = [0u8; 32];
@@ -2693,6 +3460,8 @@ impl TestNode {
         let epidemic_gossip = Arc::clone(&self.epidemic_gossip);
         let our_peer_id_hex = self.peer_id.clone();
         let bytes_received = Arc::clone(&self.total_bytes_received);
+        let peer_filter = Arc::clone(&self.peer_filter);
+        let dial_limiter = self.dial_limiter.clone();
 
         tokio::spawn(async move {
             let mut last_periodic = Instant::now();
@@ -2769,14 +3538,20 @@ impl TestNode {
                                     // Try to connect via QUIC if not already connected
                                     {
                                         let peers = connected_peers.read().await;
-                                        if !peers.contains_key(&peer_id_hex) && !addresses.is_empty() {
+                                        let dial_addr =
+                                            first_dialable_addr(&peer_filter, &peer_id_hex, &addresses);
+                                        if let (false, Some(addr)) =
+                                            (peers.contains_key(&peer_id_hex), dial_addr)
+                                        {
                                             drop(peers);
                                             // Spawn connection attempt
                                             let endpoint_clone = Arc::clone(&endpoint);
+                                            let dial_limiter = dial_limiter.clone();
                                             let gossip_clone = Arc::clone(&gossip_integration);
                                             let event_tx_clone = event_tx.clone();
                                             tokio::spawn(async move {
-                                                if let Err(e) = endpoint_clone.connect_addr(addresses[0]).await {
+                                                let _dial_slot = dial_limiter.acquire().await;
+                                                if let Err(e) = endpoint_clone.connect_addr(addr).await {
                                                     debug!("Failed to connect to epidemic peer {}: {}", &peer_id_hex[..8], e);
                                                 } else {
                                                     gossip_clone.record_success(&peer_id_hex);
@@ -2874,6 +3649,7 @@ impl TestNode {
                                                         supports_dual_stack: false,
                                                     },
                                                     epoch: announcement.timestamp_ms,
+                                                    topics: announcement.topics.clone(),
                                                 };
                                                 gossip_integration.discovery().handle_peer_announcement(discovery_announcement).await;
                                             }
@@ -2899,6 +3675,8 @@ impl TestNode {
                                                 }
                                             });
                                         }
+                                    } else if let Some(probe) = deserialize_latency_probe(&payload) {
+                                        gossip_integration.discovery().handle_latency_probe(probe);
                                     } else if let Ok(announcement) = GossipPeerAnnouncement::from_bytes(&payload) {
                                         // Process epidemic gossip message (peer announcement)
                                         let peer_id_hex = &announcement.peer.peer_id;
@@ -2935,6 +3713,7 @@ impl TestNode {
                                                 supports_dual_stack: false,
                                             },
                                             epoch: announcement.timestamp_ms,
+                                            topics: announcement.topics.clone(),
                                         };
                                         gossip_integration.discovery().handle_peer_announcement(discovery_announcement).await;
                                     }
@@ -3004,17 +3783,14 @@ impl TestNode {
                                     peer_info,
                                     our_peer_id_hex.clone(),
                                     8,
-                                );
+                                )
+                                .with_topics(gossip_integration.discovery().subscribed_topics());
 
-                            if let Ok(payload) = announcement.to_bytes() {
-                                if let Err(e) = epidemic_gossip.publish(payload).await {
-                                    debug!("Periodic epidemic announce failed: {}", e);
-                                } else {
-                                    debug!(
-                                        "Periodic epidemic announce: {} peers in view",
-                                        peer_count
-                                    );
-                                }
+                            let payload = announcement.to_wire(gossip_integration.discovery());
+                            if let Err(e) = epidemic_gossip.publish(payload).await {
+                                debug!("Periodic epidemic announce failed: {}", e);
+                            } else {
+                                debug!("Periodic epidemic announce: {} peers in view", peer_count);
                             }
                         }
                     }
@@ -3544,6 +4320,16 @@ impl TestNode {
         // Try to parse the relayed data as a test packet or other message
         // The data could be anything - test packets, gossip messages, etc.
         if let Ok(test_packet) = serde_json::from_slice::<TestPacket>(&req.data) {
+            if let Err(e) = test_packet.validate() {
+                warn!(
+                    "Dropping invalid relayed test packet from {} (seq={}): {}",
+                    &source_hex[..8.min(source_hex.len())],
+                    test_packet.sequence,
+                    e
+                );
+                return None;
+            }
+
             // It's a test packet - update stats
             self.total_bytes_received
                 .fetch_add(req.data.len() as u64, Ordering::Relaxed);
@@ -3816,15 +4602,20 @@ impl TestNode {
             return;
         }
 
-        let known_quic_peers: Vec<SocketAddr> = VPS_NODE_IPS
-            .iter()
-            .filter_map(|ip| {
-                ip.parse::<std::net::IpAddr>()
-                    .ok()
-                    .map(|addr| SocketAddr::new(addr, 9000))
-            })
-            .collect();
-
+        let known_quic_peers: Vec<SocketAddr> = if !self.config.bootstrap_peers.is_empty() {
+            self.config.bootstrap_peers.clone()
+        } else {
+            VPS_NODE_IPS
+                .iter()
+                .filter_map(|ip| {
+                    ip.parse::<std::net::IpAddr>()
+                        .ok()
+                        .map(|addr| SocketAddr::new(addr, 9000))
+                })
+                .collect()
+        };
+
+        let known_quic_peers = self.peer_filter.dial_addrs(None, &known_quic_peers);
         if known_quic_peers.is_empty() {
             warn!("No known QUIC peers configured for address discovery");
             return;
@@ -3843,11 +4634,21 @@ impl TestNode {
             let ep = Arc::clone(&endpoint);
             let ext_addrs = Arc::clone(&external_addresses);
             let tx = tx.clone();
+            let event_tx = self.event_tx.clone();
+            let dial_limiter = self.dial_limiter.clone();
 
             tokio::spawn(async move {
-                if let Ok(Ok(_conn)) =
-                    tokio::time::timeout(Duration::from_secs(3), ep.connect_addr(peer_addr)).await
-                {
+                let _dial_slot = dial_limiter.acquire().await;
+                let started = Instant::now();
+                let probe =
+                    tokio::time::timeout(Duration::from_secs(3), ep.connect_addr(peer_addr)).await;
+                let reachable = matches!(probe, Ok(Ok(_)));
+                let _ = event_tx.try_send(TuiEvent::BootstrapStatus {
+                    addr: peer_addr,
+                    reachable,
+                    rtt: reachable.then(|| started.elapsed()),
+                });
+                if reachable {
                     tokio::time::sleep(Duration::from_millis(500)).await;
                     let addrs = ext_addrs.read().await;
                     if !addrs.is_empty() {
@@ -3874,6 +4675,10 @@ impl TestNode {
     }
 
     pub async fn run(&self) -> anyhow::Result<()> {
+        // The runtime limit counts from here, not from process start
+        let started = self.config.clock.now();
+        self.join_timer.start();
+
         self.discover_external_address().await;
 
         if self.is_public_node().await {
@@ -3935,6 +4740,7 @@ impl TestNode {
         info!("DIAGNOSTIC: Heartbeat loop spawned, JoinHandle created");
         let connect_handle = self.spawn_connect_loop();
         let test_handle = self.spawn_test_loop();
+        let test_responder_handle = self.spawn_test_responder_loop();
         let health_handle = self.spawn_health_check_loop();
         let relay_stats_handle = self.spawn_relay_stats_loop();
         let gossip_handle = self.spawn_gossip_loop();
@@ -3944,6 +4750,7 @@ impl TestNode {
         let nat_callback_handle = self.spawn_nat_callback_loop();
         let websocket_handle = self.spawn_websocket_event_loop();
         let proof_handle = self.spawn_proof_orchestrator_loop();
+        let telemetry_handle = self.spawn_telemetry_loop();
 
         // Announce ourselves to gossip network
         self.announce_to_gossip().await;
@@ -3951,13 +4758,21 @@ impl TestNode {
         self.announce_via_epidemic().await;
 
         // Wait for shutdown
+        let mut runtime_expired = false;
         while !shutdown.load(Ordering::SeqCst) {
             tokio::time::sleep(Duration::from_millis(100)).await;
+            let elapsed = self.config.clock.now().duration_since(started);
+            if let Some(max_runtime) = self.config.max_runtime.filter(|max| elapsed >= *max) {
+                info!("Max runtime of {:?} reached, shutting down", max_runtime);
+                runtime_expired = true;
+                self.shutdown();
+            }
         }
 
-        heartbeat_handle.abort();
-        connect_handle.abort();
-        test_handle.abort();
+        // Stop taking on new work immediately. The connect, test and
+        // heartbeat loops notice the flag themselves and finish what they
+        // are doing (the heartbeat loop sends one last heartbeat).
+        test_responder_handle.abort();
         health_handle.abort();
         relay_stats_handle.abort();
         gossip_handle.abort();
@@ -3967,21 +4782,69 @@ impl TestNode {
         nat_callback_handle.abort();
         websocket_handle.abort();
         proof_handle.abort();
+        if let Some(handle) = telemetry_handle {
+            handle.abort();
+        }
+
+        // Let in-flight dials, test exchanges, registry reports and the final
+        // heartbeat finish
+        let grace = self.config.shutdown_grace;
+        let (aborted_loops, aborted_reports) = tokio::join!(
+            drain_tasks(vec![heartbeat_handle, test_handle, connect_handle], grace),
+            self.in_flight.drain(grace),
+        );
+        let aborted = aborted_loops + aborted_reports;
+        if aborted > 0 {
+            warn!(
+                "Aborted {} task(s) still running after {:?} shutdown grace",
+                aborted, self.config.shutdown_grace
+            );
+        }
+
+        // Leave the registry now rather than lingering until the TTL expires
+        self.deregister().await;
 
-        // Save peer cache and shutdown gossip integration
+        // Save peer caches and shutdown gossip integration
         if let Err(e) = self.gossip_integration.save_cache() {
             warn!("Failed to save peer cache on shutdown: {}", e);
         }
+        match self.peer_cache.save() {
+            Ok(()) => info!(
+                "Saved {} peers to {:?}",
+                self.peer_cache.len(),
+                self.peer_cache.path()
+            ),
+            Err(e) => warn!(
+                "Failed to save peer cache {:?}: {}",
+                self.peer_cache.path(),
+                e
+            ),
+        }
         self.gossip_integration.discovery().shutdown();
 
         // Stop saorsa-gossip epidemic layer
         self.epidemic_gossip.stop().await;
 
         info!("Test node shutting down");
+        if runtime_expired {
+            // Nobody is at the keyboard to quit the TUI: close it too
+            let _ = self.event_tx.try_send(TuiEvent::Info(format!(
+                "Max runtime of {} reached - node stopped",
+                humantime_serde::re::humantime::format_duration(
+                    self.config.max_runtime.unwrap_or_default()
+                )
+            )));
+            let _ = self.event_tx.try_send(TuiEvent::Quit);
+        }
         Ok(())
     }
 
     /// Register with the central registry.
+    #[tracing::instrument(
+        name = "registration",
+        skip_all,
+        fields(peer_id = tracing::field::Empty)
+    )]
     async fn register(&self) -> anyhow::Result<()> {
         let external_addrs = self.external_addresses.read().await.clone();
 
@@ -4009,6 +4872,7 @@ impl TestNode {
                 self.peer_id.clone()
             }
         };
+        tracing::Span::current().record("peer_id", registration_peer_id.as_str());
 
         // Detect actual network capabilities
         let ipv6_available = has_global_ipv6();
@@ -4017,20 +4881,15 @@ impl TestNode {
         let is_public = rs.are_we_public();
         drop(rs);
 
-        let capabilities = NodeCapabilities {
-            pqc: true,
-            ipv4: true,
-            ipv6: ipv6_available,
-            nat_traversal: true,
-            // Public nodes can relay traffic for others
-            relay: is_public,
-        };
+        let capabilities = self.config.advertised_capabilities(ipv6_available);
 
         if ipv6_available {
             info!("IPv6 connectivity detected");
         }
-        if is_public {
-            info!("Public node detected - will advertise relay capability");
+        if capabilities.relay {
+            info!("Relay enabled - advertising relay capability");
+        } else if is_public {
+            info!("Public node detected - pass --relay to advertise relay capability");
         }
 
         let registration = NodeRegistration {
@@ -4051,6 +4910,13 @@ impl TestNode {
                         "Registered with registry, got {} peers",
                         response.peers.len()
                     );
+                    if let Some(warning) = response
+                        .ttl_secs
+                        .and_then(|ttl| self.config.heartbeat_ttl_warning(ttl))
+                    {
+                        debug!("{}", warning);
+                        let _ = self.event_tx.try_send(TuiEvent::Info(warning));
+                    }
 
                     let local_port = self.node.local_addr().map(|a| a.port()).unwrap_or(9000);
                     let (local_ipv4, local_ipv6) = detect_local_addresses(local_port);
@@ -4061,6 +4927,7 @@ impl TestNode {
                     local_node.local_ipv6 = local_ipv6;
                     local_node.registered = true;
                     local_node.last_heartbeat = Some(std::time::Instant::now());
+                    local_node.connect_timeout = Some(self.config.connect_timeout);
 
                     // Set external addresses if we have any
                     if !external_addrs.is_empty() {
@@ -4085,6 +4952,10 @@ impl TestNode {
                         .event_tx
                         .try_send(TuiEvent::UpdateLocalNode(local_node));
                     let _ = self.event_tx.try_send(TuiEvent::RegistrationComplete);
+                    let _ = self.event_tx.try_send(TuiEvent::RegistryActive {
+                        url: self.registry.base_url().to_string(),
+                        failover: self.registry.is_failed_over(),
+                    });
                     let _ = self
                         .event_tx
                         .try_send(TuiEvent::ProtocolFrame(ProtocolFrame {
@@ -4206,8 +5077,53 @@ impl TestNode {
     }
 
     /// Spawn the heartbeat background task.
+    /// Upload an anonymized [`TelemetryRecord`] every [`TELEMETRY_INTERVAL`].
+    ///
+    /// Returns `None`, and never sends anything, unless a telemetry URL is
+    /// configured.
+    fn spawn_telemetry_loop(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let url = self.config.telemetry_url.clone()?;
+        let registry = self.registry.clone();
+        let shutdown = Arc::clone(&self.shutdown);
+        let external_addresses = Arc::clone(&self.external_addresses);
+        let listen_addresses = self.listen_addresses.clone();
+        let connected_peers = Arc::clone(&self.connected_peers);
+        let nat_stats = Arc::clone(&self.nat_stats);
+        let geo_cache = GeoCache::new(Arc::clone(&self.geo_provider));
+
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(TELEMETRY_INTERVAL);
+            // Skip the immediate first tick so NAT detection has settled
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                if shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let stats = nat_stats.read().await.clone();
+                let external = external_addresses.read().await.clone();
+                // Every connected peer is an observer that could have sent
+                // us an OBSERVED_ADDRESS, as in `--detect-nat`
+                let observers = connected_peers.read().await.len();
+                let nat_type = classify_nat(&listen_addresses, &external, observers).nat_type;
+                let country_code = external
+                    .first()
+                    .and_then(|addr| geo_cache.resolve(addr.ip()))
+                    .and_then(|geo| geo.country_code);
+
+                let record = TelemetryRecord::new(nat_type, country_code, &stats);
+                match registry.upload_telemetry(&url, &record).await {
+                    Ok(()) => debug!("Uploaded telemetry to {}", url),
+                    Err(e) => debug!("Telemetry upload to {} failed: {}", url, e),
+                }
+            }
+        }))
+    }
+
     fn spawn_heartbeat_loop(&self) -> tokio::task::JoinHandle<()> {
-        let registry = RegistryClient::new(&self.config.registry_url);
+        let registry = self.registry.clone();
         let quic_peer_id = self.peer_id.clone(); // Fallback if transport not ready
         let public_key = self.public_key.clone();
         let listen_addresses = self.listen_addresses.clone();
@@ -4227,9 +5143,12 @@ impl TestNode {
         let epidemic_gossip = Arc::clone(&self.epidemic_gossip);
         let full_mesh_probes = Arc::clone(&self.full_mesh_probes);
         let event_tx = self.event_tx.clone();
-        let geo_provider = Arc::clone(&self.geo_provider);
-        // Clone relay state to check public status for re-registration
-        let relay_state = Arc::clone(&self.relay_state);
+        let geo_cache = GeoCache::new(Arc::clone(&self.geo_provider));
+        // Node config for capabilities advertised on re-registration
+        let node_config = self.config.clone();
+        let peer_cache = Arc::clone(&self.peer_cache);
+        let dial_limiter = self.dial_limiter.clone();
+        let join_timer = Arc::clone(&self.join_timer);
 
         tokio::spawn(async move {
             info!("DIAGNOSTIC: Heartbeat task STARTED - entering main loop");
@@ -4239,9 +5158,14 @@ impl TestNode {
             // Cache the transport peer ID to avoid repeated async calls
             let mut cached_peer_id: Option<String> = None;
 
-            while !shutdown.load(Ordering::SeqCst) {
+            loop {
                 info!("DIAGNOSTIC: Heartbeat awaiting ticker.tick()...");
-                ticker.tick().await;
+                // Wake early on shutdown so the registry gets a final heartbeat
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown_requested(&shutdown) => {}
+                }
+                let final_heartbeat = shutdown.load(Ordering::SeqCst);
                 info!(
                     "DIAGNOSTIC: Heartbeat ticker fired, count={}",
                     heartbeat_count
@@ -4297,6 +5221,7 @@ impl TestNode {
                     );
                     NatStats::default()
                 };
+                stats.time_to_first_connection = join_timer.join_time();
 
                 // Detect NAT status by comparing local vs external addresses
                 // If external IPs differ from local IPs, we're behind NAT
@@ -4389,6 +5314,25 @@ impl TestNode {
                 // stats.inbound_connections = connections initiated by others
                 let total_connections = peer_count + stats.inbound_connections as usize;
 
+                let hyparview_active_peers = match tokio::time::timeout(
+                    Duration::from_millis(500),
+                    epidemic_gossip.active_view(),
+                )
+                .await
+                {
+                    Ok(view) => view.iter().map(|p| hex::encode(p.as_bytes())).collect(),
+                    Err(_) => Vec::new(),
+                };
+                let swim_dead_peers = match tokio::time::timeout(
+                    Duration::from_millis(500),
+                    epidemic_gossip.dead_peers(),
+                )
+                .await
+                {
+                    Ok(dead) => dead.iter().map(|p| hex::encode(p.as_bytes())).collect(),
+                    Err(_) => Vec::new(),
+                };
+
                 let gossip_stats = NodeGossipStats {
                     announcements_sent: gossip_metrics.announcements_sent.load(Ordering::Relaxed),
                     announcements_received: gossip_metrics
@@ -4406,9 +5350,16 @@ impl TestNode {
                     cache_hits: gossip_metrics.cache_hits.load(Ordering::Relaxed),
                     cache_misses: gossip_metrics.cache_misses.load(Ordering::Relaxed),
                     cache_size: gossip_integration.cache_size() as u64,
+                    topic_subscribers: gossip_metrics.topic_subscribers(),
+                    probe_receipts: gossip_metrics.probe_receipts(),
+                    // No EigenTrust layer runs on the node yet
+                    trust_vector: HashMap::new(),
+                    trust_iterations: 0,
                     // Real HyParView stats from saorsa-gossip
                     hyparview_active: epidemic_stats.hyparview.active_view_size,
                     hyparview_passive: epidemic_stats.hyparview.passive_view_size,
+                    hyparview_active_peers,
+                    swim_dead_peers,
                     // Real SWIM stats from saorsa-gossip
                     swim_alive: epidemic_stats.swim.alive_count,
                     swim_suspect: epidemic_stats.swim.suspect_count,
@@ -4440,6 +5391,9 @@ impl TestNode {
                     plumtree_ihaves_received: 0, // Not tracked in current implementation
                     plumtree_grafts_sent: epidemic_stats.plumtree.grafts,
                     plumtree_prunes_sent: epidemic_stats.plumtree.prunes,
+                    plumtree_grafts_estimated: epidemic_stats.plumtree.estimated_grafts,
+                    plumtree_prunes_estimated: epidemic_stats.plumtree.estimated_prunes,
+                    plumtree_duplicates: epidemic_stats.plumtree.duplicates,
                     plumtree_broadcasts: epidemic_stats.plumtree.messages_sent,
 
                     // CRDT stats from saorsa-gossip-crdt-sync
@@ -4516,6 +5470,7 @@ impl TestNode {
                     nat_stats: Some(stats.clone()),
                     gossip_stats: Some(gossip_stats),
                     full_mesh_probes: probes,
+                    seq: Some(heartbeat_count),
                 };
 
                 // Add timeout to registry heartbeat to prevent blocking
@@ -4538,22 +5493,7 @@ impl TestNode {
                         info!("Re-registering with registry after heartbeat failures...");
 
                         // Detect actual network capabilities
-                        let ipv6_available = has_global_ipv6();
-                        // Check if we're public (can act as relay/coordinator)
-                        let is_public = if let Ok(rs) = relay_state.try_read() {
-                            rs.are_we_public()
-                        } else {
-                            false
-                        };
-
-                        let capabilities = NodeCapabilities {
-                            pqc: true,
-                            ipv4: true,
-                            ipv6: ipv6_available,
-                            nat_traversal: true,
-                            // Public nodes can relay traffic for others
-                            relay: is_public,
-                        };
+                        let capabilities = node_config.advertised_capabilities(has_global_ipv6());
 
                         let registration = NodeRegistration {
                             peer_id: peer_id.clone(),
@@ -4603,6 +5543,10 @@ impl TestNode {
                     consecutive_failures = 0;
                     debug!("Heartbeat sent successfully");
                     let _ = event_tx.try_send(TuiEvent::HeartbeatSent);
+                    let _ = event_tx.try_send(TuiEvent::RegistryActive {
+                        url: registry.base_url().to_string(),
+                        failover: registry.is_failed_over(),
+                    });
                     let _ = event_tx.try_send(TuiEvent::ProtocolFrame(ProtocolFrame {
                         peer_id: "registry".to_string(),
                         frame_type: "HEARTBEAT".to_string(),
@@ -4614,22 +5558,15 @@ impl TestNode {
                     let _ = event_tx
                         .try_send(TuiEvent::UpdateGossipStats(gossip_stats_for_tui.clone()));
 
-                    let cache_health = CacheHealth {
-                        total_peers: gossip_integration.cache_size(),
-                        valid_peers: gossip_integration.cache_size(),
-                        public_peers: total_connections,
-                        average_quality: 0.8,
-                        cache_age: Duration::from_secs(0),
-                        last_updated: Some(Instant::now()),
-                        cache_hits: gossip_metrics.cache_hits.load(Ordering::Relaxed),
-                        cache_misses: gossip_metrics.cache_misses.load(Ordering::Relaxed),
-                        fresh_peers: epidemic_stats.swim.alive_count,
-                        stale_peers: epidemic_stats.swim.dead_count,
-                        private_peers: 0,
-                        public_quality: 0.9,
-                        private_quality: 0.5,
-                    };
+                    let cache_health = peer_cache.health();
                     let _ = event_tx.try_send(TuiEvent::CacheHealthUpdate(cache_health));
+                    let _ = event_tx.try_send(TuiEvent::DialQueue {
+                        in_flight: dial_limiter.in_flight(),
+                        queued: dial_limiter.queued(),
+                    });
+                    if let Some(join_time) = stats.time_to_first_connection {
+                        let _ = event_tx.try_send(TuiEvent::JoinTime(join_time));
+                    }
 
                     let mut nat_analytics = NatTypeAnalytics::default();
                     nat_analytics.full_cone.attempts =
@@ -4644,16 +5581,16 @@ impl TestNode {
 
                     let peers_for_geo = connected_peers.read().await;
                     let mut geo_dist = GeographicDistribution::new();
-                    for tracked in peers_for_geo.values() {
-                        for addr in &tracked.info.addresses {
-                            let (_lat, _lon, country) = geo_provider.lookup(addr.ip());
-                            if let Some(cc) = country {
-                                geo_dist.add_peer(cc);
-                                break;
-                            }
+                    let peer_ips: Vec<IpAddr> = peers_for_geo
+                        .values()
+                        .filter_map(|tracked| geo_address(&tracked.info.addresses))
+                        .collect();
+                    drop(peers_for_geo);
+                    for geo in geo_cache.resolve_batch(&peer_ips).into_iter().flatten() {
+                        if let Some(cc) = geo.country_code {
+                            geo_dist.add_peer(cc);
                         }
                     }
-                    drop(peers_for_geo);
                     if geo_dist.total_peers > 0 {
                         let _ = event_tx.try_send(TuiEvent::GeographicDistributionUpdate(geo_dist));
                     }
@@ -4668,6 +5605,11 @@ impl TestNode {
                     // For production nodes with full DHT/EigenTrust, use the data_bridge
                     // module to convert saorsa_core::dht::metrics types to TUI types.
                 }
+
+                if final_heartbeat {
+                    info!("Final heartbeat sent before shutdown");
+                    break;
+                }
             }
         })
     }
@@ -4685,7 +5627,7 @@ impl TestNode {
     /// In gossip-first mode, the registry is only used for reporting, not discovery.
     #[allow(clippy::excessive_nesting)]
     fn spawn_connect_loop(&self) -> tokio::task::JoinHandle<()> {
-        let registry = RegistryClient::new(&self.config.registry_url);
+        let registry = self.registry.clone();
         let shutdown = Arc::clone(&self.shutdown);
         let connected_peers = Arc::clone(&self.connected_peers);
         let fully_tested_peers = Arc::clone(&self.fully_tested_peers);
@@ -4706,20 +5648,36 @@ impl TestNode {
         let gossip_integration = Arc::clone(&self.gossip_integration);
         let relay_state = Arc::clone(&self.relay_state);
         let gossip_first = self.config.gossip_first;
+        let local_only = self.config.local_only;
+        let happy_eyeballs_delay = self.config.happy_eyeballs_delay;
+        let connect_timeout = self.config.connect_timeout;
+        let relay_selection = self.config.relay_selection;
+        let bootstrap_override = self.config.bootstrap_peers.clone();
         let epidemic_gossip = Arc::clone(&self.epidemic_gossip);
         let outbound_connections = Arc::clone(&self.outbound_connections);
+        let peer_filter = Arc::clone(&self.peer_filter);
+        let nat_tracer = Arc::clone(&self.nat_tracer);
+        let resumption = Arc::clone(&self.resumption);
+        let peer_cache = Arc::clone(&self.peer_cache);
+        let dial_limiter = self.dial_limiter.clone();
+        let join_timer = Arc::clone(&self.join_timer);
         #[allow(unused_variables)]
         let disconnection_times = Arc::clone(&self.disconnection_times);
 
         tokio::spawn(async move {
             let mut ticker = tokio::time::interval(interval);
             let mut first_run = true;
+            // Last gossip-vs-registry consistency report, to avoid repeating it every tick
+            let mut last_peer_set_summary: Option<String> = None;
 
             while !shutdown.load(Ordering::SeqCst) {
                 if first_run {
                     first_run = false;
                 } else {
-                    ticker.tick().await;
+                    tokio::select! {
+                        _ = ticker.tick() => {}
+                        _ = shutdown_requested(&shutdown) => break,
+                    }
                 }
 
                 // === GOSSIP-FIRST PEER DISCOVERY ===
@@ -4733,37 +5691,60 @@ impl TestNode {
                 // - Primary source: Registry
                 // - Secondary source: Gossip
 
-                // Get peers from gossip (decentralized discovery)
-                let gossip_announcements = gossip_integration.discovery().get_peers().await;
-
-                // Fetch peers from registry (optional in gossip-first mode)
-                let registry_peers = if gossip_first {
-                    // In gossip-first mode, registry fetch is optional
-                    // We still try to get peers for additional coverage, but don't depend on it
-                    match registry.get_peers().await {
-                        Ok(p) => {
-                            debug!(
-                                "Gossip-first: Got {} peers from registry (supplemental)",
-                                p.len()
-                            );
-                            p
-                        }
-                        Err(e) => {
-                            // This is expected in gossip-first mode - registry is optional
-                            debug!("Gossip-first: Registry fetch skipped ({})", e);
-                            Vec::new()
+                let discovery_span = tracing::info_span!("discovery", gossip_first);
+                let (gossip_announcements, registry_peers, registry_fetched) = async {
+                    // Get peers from gossip (decentralized discovery)
+                    let gossip_announcements = gossip_integration.discovery().get_peers().await;
+
+                    // Fetch peers from registry (optional in gossip-first mode)
+                    let mut registry_fetched = false;
+                    let registry_peers = if gossip_first {
+                        // In gossip-first mode, registry fetch is optional
+                        // We still try to get peers for additional coverage, but don't depend on it
+                        match registry.get_peers().await {
+                            Ok(p) => {
+                                debug!(
+                                    "Gossip-first: Got {} peers from registry (supplemental)",
+                                    p.len()
+                                );
+                                registry_fetched = true;
+                                p
+                            }
+                            Err(e) => {
+                                // This is expected in gossip-first mode - registry is optional
+                                debug!("Gossip-first: Registry fetch skipped ({})", e);
+                                Vec::new()
+                            }
                         }
-                    }
-                } else {
-                    // Legacy mode: registry is primary source
-                    match registry.get_peers().await {
-                        Ok(p) => p,
-                        Err(e) => {
-                            warn!("Failed to fetch peers from registry: {}", e);
-                            Vec::new()
+                    } else {
+                        // Legacy mode: registry is primary source
+                        match registry.get_peers().await {
+                            Ok(p) => p,
+                            Err(e) => {
+                                warn!("Failed to fetch peers from registry: {}", e);
+                                Vec::new()
+                            }
                         }
+                    };
+                    (gossip_announcements, registry_peers, registry_fetched)
+                }
+                .instrument(discovery_span)
+                .await;
+
+                // Check that gossip and the registry roughly agree on who is online
+                if gossip_first && registry_fetched {
+                    let diff = PeerSetDiff::compute(
+                        gossip_announcements.iter().map(|g| g.peer_id.as_str()),
+                        registry_peers.iter().map(|p| p.peer_id.as_str()),
+                        Some(&our_peer_id),
+                    );
+                    let summary = diff.summary();
+                    if last_peer_set_summary.as_ref() != Some(&summary) {
+                        debug!("{}", summary);
+                        let _ = event_tx.try_send(TuiEvent::Info(summary.clone()));
+                        last_peer_set_summary = Some(summary);
                     }
-                };
+                }
 
                 // Build a set of registry peer IDs for deduplication
                 let registry_peer_ids: std::collections::HashSet<_> =
@@ -4788,6 +5769,7 @@ impl TestNode {
                             country_code: g.country_code.clone(),
                             latitude: 0.0,
                             longitude: 0.0,
+                            location_estimated: false,
                             last_seen: g.timestamp_ms / 1000,
                             connection_success_rate: 0.5, // Unknown
                             capabilities: NodeCapabilities {
@@ -4807,6 +5789,8 @@ impl TestNode {
                             connected_peers: 0,
                             gossip_stats: None,
                             full_mesh_probes: None,
+                            last_error: None,
+                            time_to_first_connection: None,
                         }
                     })
                     .collect();
@@ -4821,71 +5805,50 @@ impl TestNode {
                     peers.extend(gossip_peers);
                 }
 
-                // In gossip-first mode, add hardcoded VPS bootstrap peers
-                // These are always available and ensure connectivity even when
-                // gossip/registry discovery returns no peers
+                // In gossip-first mode, add the bootstrap peers (configured, or
+                // the hardcoded VPS list unless local-only). These ensure
+                // connectivity even when gossip/registry discovery returns no peers
                 if gossip_first {
-                    use crate::bootstrap_peers::BOOTSTRAP_PEERS;
+                    let bootstrap: Vec<PeerInfo> = if !bootstrap_override.is_empty() {
+                        bootstrap_override
+                            .iter()
+                            .map(|addr| configured_bootstrap_candidate(*addr))
+                            .collect()
+                    } else if local_only {
+                        Vec::new()
+                    } else {
+                        crate::bootstrap_peers::BOOTSTRAP_PEERS
+                            .iter()
+                            .map(vps_bootstrap_candidate)
+                            .collect()
+                    };
 
                     let existing_peer_ids: std::collections::HashSet<_> =
                         peers.iter().map(|p| p.peer_id.clone()).collect();
-
-                    for vps_peer in BOOTSTRAP_PEERS {
-                        // Create deterministic peer ID for VPS node
-                        // IMPORTANT: Peer IDs must be 32 bytes (64 hex chars) to match
-                        // the format used by actual connected peers
-                        use sha2::{Digest, Sha256};
-                        let peer_id_bytes = {
-                            let mut hasher = Sha256::new();
-                            hasher.update(vps_peer.name.as_bytes());
-                            hasher.update(vps_peer.ipv4.octets());
-                            let hash = hasher.finalize();
-                            hex::encode(hash) // Full 32 bytes as 64 hex chars
-                        };
-
-                        // Skip if already in the peer list
-                        if existing_peer_ids.contains(&peer_id_bytes) {
-                            continue;
-                        }
-
-                        // Add VPS peer with direct IP address
-                        let vps_peer_info = PeerInfo {
-                            peer_id: peer_id_bytes,
-                            addresses: vps_peer.all_addrs(),
-                            nat_type: NatType::None, // VPS nodes are public
-                            country_code: None,
-                            latitude: 0.0,
-                            longitude: 0.0,
-                            last_seen: std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .map(|d| d.as_secs())
-                                .unwrap_or(0),
-                            connection_success_rate: 1.0, // VPS nodes are reliable
-                            capabilities: NodeCapabilities {
-                                pqc: true,
-                                ipv4: true,
-                                ipv6: vps_peer.ipv6.is_some(),
-                                nat_traversal: vps_peer.is_coordinator,
-                                relay: vps_peer.is_relay,
-                            },
-                            version: String::from("vps-bootstrap"),
-                            is_active: true, // VPS nodes are always active
-                            status: Default::default(),
-                            bytes_sent: 0,
-                            bytes_received: 0,
-                            connected_peers: 0,
-                            gossip_stats: None,
-                            full_mesh_probes: None,
-                        };
-                        peers.push(vps_peer_info);
-                    }
+                    let before = peers.len();
+                    peers.extend(
+                        bootstrap
+                            .into_iter()
+                            .filter(|p| !existing_peer_ids.contains(&p.peer_id)),
+                    );
 
                     debug!(
-                        "Gossip-first: Added {} VPS bootstrap peers to candidate list",
-                        BOOTSTRAP_PEERS.len()
+                        "Gossip-first: Added {} bootstrap peers to candidate list",
+                        peers.len() - before
                     );
                 }
 
+                // Peers remembered from earlier runs, so a restart need not
+                // wait for gossip or the registry
+                let existing_peer_ids: std::collections::HashSet<_> =
+                    peers.iter().map(|p| p.peer_id.clone()).collect();
+                peers.extend(
+                    peer_cache
+                        .seed_peers()
+                        .into_iter()
+                        .filter(|p| !existing_peer_ids.contains(&p.peer_id)),
+                );
+
                 // Update existing tracked peers with fresh registry data (especially NAT type)
                 {
                     let mut connected = connected_peers.write().await;
@@ -4912,14 +5875,14 @@ impl TestNode {
 
                 // Connect to peers we haven't fully tested (bidirectional) yet
                 let total_peers = peers.len();
-                let candidates: Vec<PeerInfo> = peers
-                    .iter()
+                let candidates: Vec<PeerInfo> = peer_filter
+                    .dial_targets(peers.iter().cloned())
+                    .into_iter()
                     .filter(|p| p.peer_id != our_peer_id)
                     .filter(|p| !tested.contains(&p.peer_id))
                     .filter(|p| !connected.contains_key(&p.peer_id))
-                    .filter(|p| p.is_active || peer_is_vps(p))
+                    .filter(|p| p.is_active || peer_is_vps(p) || peer_cache.contains(&p.peer_id))
                     .filter(|p| can_reach_peer(p, our_has_ipv6))
-                    .cloned()
                     .collect();
 
                 drop(connected);
@@ -4935,12 +5898,19 @@ impl TestNode {
                     continue;
                 }
 
-                info!("Connecting to {} untested peers", candidates.len());
+                info!(
+                    "Connecting to {} of {} untested peers",
+                    candidates.len().min(CONNECT_PASS_MAX_DIALS),
+                    candidates.len()
+                );
+                join_timer.mark_discovered();
 
-                // Connect to ALL eligible peers concurrently (max 20 at a time)
-                // Higher limit ensures DO nodes quickly reach all community test nodes
+                // Dial a bounded batch per pass so one pass (and the registry
+                // refresh that comes with the next) never waits on hundreds
+                // of slow attempts; the rest are picked up on later passes.
+                // Within the batch, dials share the node-wide dial limiter.
                 let mut connect_futures = Vec::new();
-                for candidate in candidates.into_iter().take(20) {
+                for candidate in candidates.into_iter().take(CONNECT_PASS_MAX_DIALS) {
                     let endpoint = Arc::clone(&endpoint);
                     let external_addresses = Arc::clone(&external_addresses);
                     let nat_stats = Arc::clone(&nat_stats);
@@ -4953,14 +5923,21 @@ impl TestNode {
                     let hole_punched_peers = Arc::clone(&hole_punched_peers);
                     let pending_outbound = Arc::clone(&pending_outbound);
                     let event_tx = event_tx.clone();
-                    let registry = RegistryClient::new(registry.base_url());
+                    let registry = registry.clone();
                     let our_peer_id = our_peer_id.clone();
                     let gossip_integration = Arc::clone(&gossip_integration);
                     let relay_state = Arc::clone(&relay_state);
                     let epidemic_gossip = Arc::clone(&epidemic_gossip);
                     let outbound_connections = Arc::clone(&outbound_connections);
+                    let nat_tracer = Arc::clone(&nat_tracer);
+                    let resumption = Arc::clone(&resumption);
+                    let peer_cache = Arc::clone(&peer_cache);
+                    let dial_limiter = dial_limiter.clone();
+                    let join_timer = Arc::clone(&join_timer);
 
                     let fut = async move {
+                        // Held until this attempt finishes, however it ends
+                        let _dial_slot = dial_limiter.acquire().await;
                         let peer_id_short = &candidate.peer_id[..8.min(candidate.peer_id.len())];
                         let addr_str = candidate
                             .addresses
@@ -4988,6 +5965,11 @@ impl TestNode {
                             stats.attempts += 1;
                         }
 
+                        nat_tracer.transition(
+                            &candidate.peer_id,
+                            NatTraversalPhase::Discovering,
+                            None,
+                        );
                         let _ = event_tx.try_send(TuiEvent::ProtocolFrame(ProtocolFrame {
                             peer_id: candidate.peer_id.clone(),
                             frame_type: "CONNECT".to_string(),
@@ -5000,12 +5982,23 @@ impl TestNode {
                         let skip_nat_for_vps_pair = both_are_vps(&candidate, &our_addrs);
                         drop(our_addrs);
 
+                        let attempt_started = Instant::now();
                         let result = real_connect_comprehensive(
                             &endpoint,
                             &candidate,
                             skip_nat_for_vps_pair,
+                            happy_eyeballs_delay,
+                            connect_timeout,
                         )
                         .await;
+                        nat_tracer.finish(
+                            &candidate.peer_id,
+                            if result.success {
+                                "connected"
+                            } else {
+                                "failed"
+                            },
+                        );
 
                         if result.success {
                             success.fetch_add(1, Ordering::Relaxed);
@@ -5070,6 +6063,9 @@ impl TestNode {
                                 }
                             }
                             let connectivity_for_report = result.matrix.clone();
+                            let zero_rtt = result.handshake.is_some_and(|handshake| {
+                                resumption.record_dial(&candidate.peer_id, &handshake)
+                            });
 
                             // Preserve inbound_verified if peer already had inbound connection
                             let mut peers = connected_peers.write().await;
@@ -5093,12 +6089,21 @@ impl TestNode {
                                 last_nat_test_time: None,
                                 quic_test_success: false,
                                 gossip_test_success: false,
+                                relay_peer_id: None,
+                                zero_rtt,
+                                crypto: peer_crypto(
+                                    &endpoint,
+                                    &QuicPeerId(peer_id_to_bytes(&candidate.peer_id)),
+                                ),
                             };
 
                             let peer_for_tui = tracked.to_connected_peer();
                             let was_new_peer = !peers.contains_key(&candidate.peer_id);
                             peers.insert(candidate.peer_id.clone(), tracked);
                             drop(peers);
+                            if existing_inbound_verified {
+                                join_timer.mark_verified();
+                            }
 
                             // Only increment if this is a NEW outbound peer (not already tracked)
                             if was_new_peer {
@@ -5121,6 +6126,18 @@ impl TestNode {
                             }));
 
                             send_tui_event(&event_tx, TuiEvent::PeerConnected(peer_for_tui));
+                            if let Some(family) = result
+                                .family
+                                .filter(|_| final_method == ConnectionMethod::Direct)
+                            {
+                                send_tui_event(
+                                    &event_tx,
+                                    TuiEvent::AddressFamilySelected {
+                                        peer_id: candidate.peer_id.clone(),
+                                        family,
+                                    },
+                                );
+                            }
 
                             // Report successful connection to registry
                             let report = ConnectionReport {
@@ -5128,7 +6145,14 @@ impl TestNode {
                                 to_peer: candidate.peer_id.clone(),
                                 method: final_method,
                                 is_ipv6,
-                                rtt_ms: None,
+                                rtt_ms: result.handshake.map(|h| h.rtt.as_millis() as u64).or_else(
+                                    || {
+                                        connection_rtt_ms(
+                                            &endpoint,
+                                            &QuicPeerId(peer_id_to_bytes(&candidate.peer_id)),
+                                        )
+                                    },
+                                ),
                                 connectivity: connectivity_for_report,
                             };
                             if let Err(e) = registry.report_connection(&report).await {
@@ -5136,6 +6160,7 @@ impl TestNode {
                             }
 
                             gossip_integration.record_success(&candidate.peer_id);
+                            peer_cache.record_success(&candidate);
 
                             // Send DisconnectAndConnectBack to trigger reverse test
                             let our_addrs = external_addresses.read().await.clone();
@@ -5177,6 +6202,7 @@ impl TestNode {
                                 &relay_state,
                                 &our_peer_id,
                                 &external_addresses,
+                                &nat_tracer,
                             )
                             .await;
 
@@ -5212,6 +6238,11 @@ impl TestNode {
                                 let inner_ep = endpoint.inner_endpoint();
                                 let quic_target = QuicPeerId(target_peer_id);
                                 if let Ok(Some(_)) = inner_ep.get_quic_connection(&quic_target) {
+                                    nat_tracer.transition(
+                                        &candidate.peer_id,
+                                        NatTraversalPhase::Connected,
+                                        None,
+                                    );
                                     success.fetch_add(1, Ordering::Relaxed);
                                     holepunch.fetch_add(1, Ordering::Relaxed);
                                     {
@@ -5259,12 +6290,21 @@ impl TestNode {
                                         last_nat_test_time: None,
                                         quic_test_success: false,
                                         gossip_test_success: false,
+                                        relay_peer_id: None,
+                                        zero_rtt: false,
+                                        crypto: peer_crypto(
+                                            &endpoint,
+                                            &QuicPeerId(peer_id_to_bytes(&candidate.peer_id)),
+                                        ),
                                     };
 
                                     let peer_for_tui = tracked.to_connected_peer();
                                     let was_new_peer = !peers.contains_key(&candidate.peer_id);
                                     peers.insert(candidate.peer_id.clone(), tracked);
                                     drop(peers);
+                                    if existing_inbound_verified {
+                                        join_timer.mark_verified();
+                                    }
 
                                     if was_new_peer {
                                         outbound_connections.fetch_add(1, Ordering::SeqCst);
@@ -5279,6 +6319,29 @@ impl TestNode {
                                         &event_tx,
                                         TuiEvent::PeerConnected(peer_for_tui),
                                     );
+
+                                    let punched = endpoint
+                                        .inner_endpoint()
+                                        .get_quic_connection(&QuicPeerId(peer_id_to_bytes(
+                                            &candidate.peer_id,
+                                        )))
+                                        .ok()
+                                        .flatten();
+                                    let report = ConnectionReport {
+                                        from_peer: our_peer_id.clone(),
+                                        to_peer: candidate.peer_id.clone(),
+                                        method: ConnectionMethod::HolePunched,
+                                        is_ipv6: punched
+                                            .as_ref()
+                                            .is_some_and(|conn| conn.remote_address().is_ipv6()),
+                                        rtt_ms: punched
+                                            .as_ref()
+                                            .map(|conn| conn.rtt().as_millis() as u64),
+                                        connectivity: matrix,
+                                    };
+                                    if let Err(e) = registry.report_connection(&report).await {
+                                        warn!("Failed to report hole-punched connection: {}", e);
+                                    }
                                     // Connection succeeded via gossip-based hole-punch
                                     // Skip the relay fallback by returning early from this future
                                     return true;
@@ -5286,6 +6349,7 @@ impl TestNode {
                             }
                             // If gossip traversal initiated but connection not established,
                             // fall through to existing relay fallback
+                            nat_tracer.finish(&candidate.peer_id, "failed");
 
                             if let Err(e) = &gossip_traversal_result {
                                 debug!(
@@ -5295,24 +6359,35 @@ impl TestNode {
                             }
 
                             // Check if we have a relay that can reach the target (existing fallback)
+                            let announced_relay = select_announced_relay(
+                                gossip_integration.discovery(),
+                                &connected_peers,
+                                &candidate.addresses,
+                                &candidate.peer_id,
+                                relay_selection,
+                            )
+                            .await;
                             let relay_found = {
-                                let rs = relay_state.read().await;
+                                let mut rs = relay_state.write().await;
                                 // First check if we already have an active relay for this target
-                                if rs.active_relays.contains_key(&target_peer_id) {
-                                    Some(true) // Already have a relay
+                                if let Some(active) = rs.get_relay_for(&target_peer_id) {
+                                    Some(hex::encode(active))
+                                } else if let Some(announced) = announced_relay {
+                                    // Pick among gossip-announced relays by policy
+                                    rs.set_relay_for(
+                                        target_peer_id,
+                                        peer_id_to_bytes(&announced.peer_id),
+                                    );
+                                    Some(announced.peer_id)
                                 } else {
-                                    // Check if any of our relay candidates can reach the target
-                                    let candidates = rs.get_relay_candidates();
-                                    if !candidates.is_empty() {
-                                        // We have potential relays to try
-                                        Some(false)
-                                    } else {
-                                        None
-                                    }
+                                    // Fall back to the best of our own relay candidates
+                                    rs.get_relay_candidates()
+                                        .first()
+                                        .map(|c| hex::encode(c.peer_id))
                                 }
                             };
 
-                            if let Some(_has_relay) = relay_found {
+                            if let Some(relay_peer_id) = relay_found {
                                 success.fetch_add(1, Ordering::Relaxed);
                                 relay.fetch_add(1, Ordering::Relaxed);
                                 {
@@ -5360,12 +6435,21 @@ impl TestNode {
                                     last_nat_test_time: None,
                                     quic_test_success: false,
                                     gossip_test_success: false,
+                                    relay_peer_id: Some(relay_peer_id.clone()),
+                                    zero_rtt: false,
+                                    crypto: peer_crypto(
+                                        &endpoint,
+                                        &QuicPeerId(peer_id_to_bytes(&candidate.peer_id)),
+                                    ),
                                 };
 
                                 let peer_for_tui = tracked.to_connected_peer();
                                 let was_new_peer = !peers.contains_key(&candidate.peer_id);
                                 peers.insert(candidate.peer_id.clone(), tracked);
                                 drop(peers);
+                                if existing_inbound_verified {
+                                    join_timer.mark_verified();
+                                }
 
                                 // Only increment if this is a NEW outbound peer (not already tracked)
                                 if was_new_peer {
@@ -5373,20 +6457,26 @@ impl TestNode {
                                 }
 
                                 info!(
-                                    "Direct connection failed but RELAY available for {} (matrix: {})",
+                                    "Direct connection failed but RELAY {} available for {} (matrix: {})",
+                                    &relay_peer_id[..8.min(relay_peer_id.len())],
                                     peer_id_short,
                                     peer_for_tui.connectivity_summary()
                                 );
 
                                 send_tui_event(&event_tx, TuiEvent::PeerConnected(peer_for_tui));
 
-                                // Report relayed connection to registry
+                                // Report relayed connection to registry. Only the
+                                // hop to the relay is measurable from here, so the
+                                // RTT is a lower bound for the relayed path.
                                 let report = ConnectionReport {
                                     from_peer: our_peer_id.clone(),
                                     to_peer: candidate.peer_id.clone(),
                                     method: ConnectionMethod::Relayed,
                                     is_ipv6: false,
-                                    rtt_ms: None,
+                                    rtt_ms: connection_rtt_ms(
+                                        &endpoint,
+                                        &QuicPeerId(peer_id_to_bytes(&relay_peer_id)),
+                                    ),
                                     connectivity: matrix,
                                 };
                                 if let Err(e) = registry.report_connection(&report).await {
@@ -5398,14 +6488,19 @@ impl TestNode {
                                     let mut pending = pending_outbound.write().await;
                                     pending.remove(&candidate.peer_id);
                                 }
+                                peer_cache.record_failure(&candidate.peer_id);
 
                                 // Only count as failure if peer is still LIVE
                                 // (they might have gone offline, not a hole-punch failure)
                                 if candidate.is_active {
                                     failed.fetch_add(1, Ordering::Relaxed);
+                                    let reason =
+                                        result.last_error.unwrap_or(FailureReasonCode::Unknown);
                                     {
                                         let mut stats = nat_stats.write().await;
                                         stats.failures += 1;
+                                        stats.last_failure = Some(reason);
+                                        *stats.failure_reasons.entry(reason).or_default() += 1;
                                     }
                                     warn!(
                                         "COMPREHENSIVE test FAILED to {} (peer is LIVE, no relay available, matrix: {})",
@@ -5420,7 +6515,11 @@ impl TestNode {
                                             timestamp: Instant::now(),
                                             context: candidate.country_code.clone(),
                                         }));
-                                    let _ = event_tx.try_send(TuiEvent::ConnectionFailed);
+                                    let _ = event_tx.try_send(TuiEvent::ConnectionFailed {
+                                        peer_id: candidate.peer_id.clone(),
+                                        reason,
+                                        elapsed: attempt_started.elapsed(),
+                                    });
                                 } else {
                                     debug!(
                                         "Connection to {} failed but peer went offline",
@@ -5448,6 +6547,81 @@ impl TestNode {
         })
     }
 
+    /// Spawn the task receiving test packets peers send over the gossip transport.
+    ///
+    /// Every packet is validated before it counts as received; truncated or
+    /// corrupted packets are dropped. Pings are answered with a pong and pongs
+    /// complete the matching [`gossip_test_exchange`].
+    fn spawn_test_responder_loop(&self) -> tokio::task::JoinHandle<()> {
+        let shutdown = Arc::clone(&self.shutdown);
+        let connected_peers = Arc::clone(&self.connected_peers);
+        let total_received = Arc::clone(&self.total_bytes_received);
+        let pending_pongs = Arc::clone(&self.pending_pongs);
+        let gossip = Arc::clone(&self.epidemic_gossip);
+        let our_peer_id_bytes = peer_id_to_bytes(&self.peer_id);
+        let mut direct_rx = self.epidemic_gossip.subscribe_direct();
+
+        tokio::spawn(async move {
+            while !shutdown.load(Ordering::SeqCst) {
+                let (from, data) = match direct_rx.recv().await {
+                    Ok(message) => message,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Test responder fell behind, skipped {} messages", skipped);
+                        continue;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                let Ok(packet) = TestPacket::from_bytes(&data) else {
+                    continue;
+                };
+                let from_hex = hex::encode(from.as_bytes());
+                if let Err(e) = packet.validate() {
+                    warn!(
+                        "Dropping invalid test packet from {} (seq={}): {}",
+                        &from_hex[..8.min(from_hex.len())],
+                        packet.sequence,
+                        e
+                    );
+                    continue;
+                }
+
+                total_received.fetch_add(data.len() as u64, Ordering::Relaxed);
+                if let Some(peer) = connected_peers.write().await.get_mut(&from_hex) {
+                    peer.last_activity = Instant::now();
+                }
+
+                match packet.packet_type {
+                    PacketType::Ping => {
+                        let pong = packet.create_pong(our_peer_id_bytes);
+                        let sent = match pong.to_bytes() {
+                            Ok(bytes) => gossip
+                                .send_to_peer(from, bytes)
+                                .await
+                                .map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        };
+                        if let Err(e) = sent {
+                            debug!(
+                                "Failed to answer test ping from {}: {}",
+                                &from_hex[..8.min(from_hex.len())],
+                                e
+                            );
+                        }
+                    }
+                    PacketType::Pong => {
+                        let waiter = pending_pongs
+                            .lock()
+                            .ok()
+                            .and_then(|mut pending| pending.remove(&(from_hex, packet.sequence)));
+                        if let Some(waiter) = waiter {
+                            let _ = waiter.send(());
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     /// Spawn the test traffic background task.
     ///
     /// Uses DUAL TRANSPORT testing: sends test packets via BOTH gossip and QUIC transports.
@@ -5455,12 +6629,15 @@ impl TestNode {
         let shutdown = Arc::clone(&self.shutdown);
         let connected_peers = Arc::clone(&self.connected_peers);
         let interval = self.config.test_interval;
+        let test_packet_size = self.config.test_packet_size;
+        let burst_size = self.config.test_burst_size.max(1);
         let event_tx = self.event_tx.clone();
         let our_peer_id_bytes = peer_id_to_bytes(&self.peer_id);
         let total_sent = Arc::clone(&self.total_bytes_sent);
         let total_received = Arc::clone(&self.total_bytes_received);
         // Use gossip transport for test packets (uses configured gossip_port)
         let gossip = Arc::clone(&self.epidemic_gossip);
+        let pending_pongs = Arc::clone(&self.pending_pongs);
         // Use QUIC transport for dual transport testing
         let endpoint = Arc::clone(&self.node);
 
@@ -5468,7 +6645,10 @@ impl TestNode {
             let mut ticker = tokio::time::interval(interval);
 
             while !shutdown.load(Ordering::SeqCst) {
-                ticker.tick().await;
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown_requested(&shutdown) => break,
+                }
 
                 // CRITICAL: Collect peer info WITHOUT holding lock during network operations
                 // This prevents lock starvation that was blocking heartbeats
@@ -5477,7 +6657,9 @@ impl TestNode {
                     peers
                         .iter()
                         .map(|(id, tracked)| {
-                            let seq = tracked.sequence.fetch_add(1, Ordering::Relaxed);
+                            let seq = tracked
+                                .sequence
+                                .fetch_add(burst_size as u64, Ordering::Relaxed);
                             (id.clone(), seq)
                         })
                         .collect()
@@ -5485,7 +6667,8 @@ impl TestNode {
                 // Lock released here before network operations
 
                 for (peer_id, seq) in peer_info {
-                    let packet = TestPacket::new_ping(our_peer_id_bytes, seq);
+                    let packet =
+                        TestPacket::new_ping_sized(our_peer_id_bytes, seq, test_packet_size);
                     let packet_size = packet.size() as u64;
 
                     // === DUAL TRANSPORT TESTING ===
@@ -5497,46 +6680,88 @@ impl TestNode {
                         is_gossip_stream_type(stream_type)
                     );
 
-                    // Test 1: Gossip transport (saorsa-gossip)
-                    let gossip_result = gossip_test_exchange(&gossip, &peer_id, &packet).await;
+                    // Test 1: Gossip transport (saorsa-gossip), as a burst so
+                    // jitter and loss can be measured. The pings are in flight
+                    // together so a dead peer costs one pong timeout, not one
+                    // per packet.
+                    let burst_packets: Vec<TestPacket> = (0..burst_size as u64)
+                        .map(|i| {
+                            if i == 0 {
+                                packet.clone()
+                            } else {
+                                TestPacket::new_ping_sized(
+                                    our_peer_id_bytes,
+                                    seq + i,
+                                    test_packet_size,
+                                )
+                            }
+                        })
+                        .collect();
+                    let outcomes =
+                        futures_util::future::join_all(burst_packets.iter().map(|burst_packet| {
+                            gossip_test_exchange(&gossip, &pending_pongs, &peer_id, burst_packet)
+                        }))
+                        .await;
+                    let mut rtts = Vec::with_capacity(burst_size);
+                    let mut last_error = None;
+                    for outcome in outcomes {
+                        match outcome {
+                            Ok(rtt) => rtts.push(rtt),
+                            Err(e) => last_error = Some(e),
+                        }
+                    }
+                    let burst = TestResult::from_burst(seq, burst_size, &rtts);
 
                     // Test 2: QUIC transport (P2pEndpoint)
                     // The response is handled asynchronously via P2pEvent::DataReceived
                     let quic_result = quic_test_exchange(&endpoint, &peer_id, &packet).await;
 
                     // Now briefly acquire lock to update stats
-                    let gossip_success = gossip_result.is_ok();
-                    let rtt_for_tui = gossip_result.as_ref().ok().copied();
+                    let gossip_success = burst.success;
+                    let jitter = burst
+                        .jitter_ms
+                        .map(|ms| Duration::from_secs_f64(ms / 1000.0));
 
                     {
                         let mut peers = connected_peers.write().await;
                         if let Some(tracked) = peers.get_mut(&peer_id) {
+                            let received = rtts.len() as u64;
+                            tracked.stats.packets_sent += burst_size as u64;
+                            tracked.stats.last_loss_pct = burst.loss_pct;
+
                             // Track gossip transport success
                             if gossip_success {
                                 tracked.gossip_test_success = true;
                                 tracked.stats.tests_success += 1;
-                                if let Ok(rtt) = &gossip_result {
+                                if let Some(rtt) = burst.rtt {
                                     tracked.stats.total_rtt_ms += rtt.as_millis() as u64;
-                                    tracked.stats.last_rtt = Some(*rtt);
+                                    tracked.stats.last_rtt = Some(rtt);
                                 }
-                                tracked.stats.packets_sent += 1;
-                                tracked.stats.packets_received += 1;
+                                tracked.stats.last_jitter = jitter;
+                                tracked.stats.packets_received += received;
                                 tracked.last_activity = Instant::now();
                                 tracked.consecutive_failures = 0; // Reset on success
 
-                                total_sent.fetch_add(packet_size, Ordering::Relaxed);
-                                total_received.fetch_add(packet_size, Ordering::Relaxed);
+                                total_sent.fetch_add(packet_size * received, Ordering::Relaxed);
+                                total_received.fetch_add(packet_size * received, Ordering::Relaxed);
 
                                 debug!(
-                                    "GOSSIP test packet sent to {} ({} bytes, RTT: {:?})",
+                                    "GOSSIP test burst sent to {} ({}x{} bytes, RTT: {:?}, jitter: {:?}, loss: {:.0}%)",
                                     &peer_id[..8],
+                                    burst_size,
                                     packet_size,
-                                    gossip_result.as_ref().ok()
+                                    burst.rtt,
+                                    jitter,
+                                    burst.loss_pct
                                 );
-                            } else if let Err(e) = &gossip_result {
+                            } else {
                                 tracked.stats.tests_failed += 1;
                                 tracked.consecutive_failures += 1;
-                                warn!("Gossip test packet to {} failed: {}", &peer_id[..8], e);
+                                warn!(
+                                    "Gossip test burst to {} failed: {}",
+                                    &peer_id[..8],
+                                    last_error.as_deref().unwrap_or("all packets lost")
+                                );
                             }
 
                             // Track QUIC transport success (send only - response via DataReceived)
@@ -5554,7 +6779,8 @@ impl TestNode {
                     let _ = event_tx.try_send(TuiEvent::TestPacketResult {
                         peer_id: peer_id.clone(),
                         success: gossip_success,
-                        rtt: rtt_for_tui,
+                        rtt: burst.rtt,
+                        jitter,
                     });
                     let _ = event_tx.try_send(TuiEvent::TrafficTypeUpdate {
                         peer_id: peer_id.clone(),
@@ -5800,18 +7026,21 @@ impl TestNode {
         let endpoint = Arc::clone(&self.node);
         let peer_id = self.peer_id.clone();
         let event_tx = self.event_tx.clone();
+        let peer_filter = Arc::clone(&self.peer_filter);
 
-        let registry_url = self.config.registry_url.clone();
-        let ws_url = registry_url
-            .replace("https://", "wss://")
-            .replace("http://", "ws://")
-            + "/ws/live";
+        let registry = self.registry.clone();
 
         tokio::spawn(async move {
             loop {
                 if shutdown.load(Ordering::SeqCst) {
                     break;
                 }
+                // Follow registry failover between reconnects
+                let ws_url = registry
+                    .base_url()
+                    .replace("https://", "wss://")
+                    .replace("http://", "ws://")
+                    + "/ws/live";
 
                 info!(
                     "Connecting to registry WebSocket at {} for connectivity test events...",
@@ -5849,6 +7078,16 @@ impl TestNode {
                                                 addresses.len()
                                             );
 
+                                            let addresses = peer_filter
+                                                .dial_addrs(Some(&target_peer_id), &addresses);
+                                            if addresses.is_empty() {
+                                                debug!(
+                                                    "Peer filter: skipping connectivity test to {}",
+                                                    &target_peer_id[..8.min(target_peer_id.len())]
+                                                );
+                                                continue;
+                                            }
+
                                             Self::handle_connectivity_test_request(
                                                 &endpoint,
                                                 &target_peer_id,
@@ -5907,7 +7146,9 @@ impl TestNode {
         let connected_peers = Arc::clone(&self.connected_peers);
         let peer_id = self.peer_id.clone();
         let epidemic_gossip = Arc::clone(&self.epidemic_gossip);
-        let registry_url = self.config.registry_url.clone();
+        let registry = self.registry.clone();
+        let join_timer = Arc::clone(&self.join_timer);
+        let resumption = Arc::clone(&self.resumption);
 
         tokio::spawn(async move {
             // Wait 30 seconds before first run to let network stabilize
@@ -5963,15 +7204,51 @@ impl TestNode {
 
                 // Record our own gossip stats
                 orchestrator.record_gossip_stats(&peer_id, gossip_stats.clone());
+                orchestrator
+                    .record_anti_entropy(&peer_id, &epidemic_gossip.take_anti_entropy_events());
+                orchestrator.record_reconnects(
+                    &peer_id,
+                    resumption.resumable_reconnects(),
+                    resumption.zero_rtt_reconnects(),
+                );
+                if let Some(join_time) = join_timer.join_time() {
+                    orchestrator.record_join_time(&peer_id, join_time);
+                }
+                let active_view = epidemic_gossip.active_view().await;
+                orchestrator.record_active_view(
+                    &peer_id,
+                    active_view
+                        .iter()
+                        .map(|p| hex::encode(p.as_bytes()))
+                        .collect(),
+                );
+                // Withhold one eager push before the next run so it can check
+                // that Plumtree recovers it; that needs a second path to us.
+                if active_view.len() >= 2 {
+                    epidemic_gossip.withhold_next_eager();
+                }
+                orchestrator.record_swim_dead(
+                    &peer_id,
+                    epidemic_gossip
+                        .dead_peers()
+                        .await
+                        .iter()
+                        .map(|p| hex::encode(p.as_bytes()))
+                        .collect(),
+                );
 
                 // Try to fetch additional peer data from registry if available
-                if let Ok(registry_peers) = crate::registry::RegistryClient::new(&registry_url)
-                    .get_peers()
-                    .await
-                {
+                if let Ok(registry_peers) = registry.get_peers().await {
+                    // The registry only lists nodes that heartbeat within its TTL
+                    orchestrator.record_registry_alive(
+                        registry_peers.iter().map(|peer| peer.peer_id.clone()),
+                    );
                     for peer in &registry_peers {
                         if peer.peer_id != peer_id {
                             orchestrator.register_node(peer.peer_id.clone());
+                            if let Some(join_time) = peer.time_to_first_connection {
+                                orchestrator.record_join_time(&peer.peer_id, join_time);
+                            }
 
                             // Record peer's gossip stats if available
                             if let Some(ref node_stats) = peer.gossip_stats {
@@ -5999,9 +7276,11 @@ impl TestNode {
                                         lazy_peers: node_stats.plumtree_lazy,
                                         messages_sent: node_stats.plumtree_sent,
                                         messages_received: node_stats.plumtree_received,
-                                        duplicates: 0,
-                                        grafts: 0,
-                                        prunes: 0,
+                                        duplicates: node_stats.plumtree_duplicates,
+                                        grafts: node_stats.plumtree_grafts_sent,
+                                        prunes: node_stats.plumtree_prunes_sent,
+                                        estimated_grafts: node_stats.plumtree_grafts_estimated,
+                                        estimated_prunes: node_stats.plumtree_prunes_estimated,
                                     },
                                     connection_types: ConnectionBreakdown {
                                         direct_ipv4: node_stats.conn_direct_ipv4,
@@ -6032,6 +7311,21 @@ impl TestNode {
                                     },
                                 };
                                 orchestrator.record_gossip_stats(&peer.peer_id, peer_gossip);
+                                orchestrator.record_active_view(
+                                    &peer.peer_id,
+                                    node_stats.hyparview_active_peers.clone(),
+                                );
+                                orchestrator.record_swim_dead(
+                                    &peer.peer_id,
+                                    node_stats.swim_dead_peers.clone(),
+                                );
+                                if !node_stats.trust_vector.is_empty() {
+                                    orchestrator.record_trust_vector(
+                                        &peer.peer_id,
+                                        node_stats.trust_vector.clone(),
+                                        node_stats.trust_iterations,
+                                    );
+                                }
                             }
                         }
                     }
@@ -6159,10 +7453,35 @@ impl TestNode {
         }
     }
 
+    /// Ask `run()` to stop.
+    ///
+    /// `run()` drains in-flight work, deregisters from the registry and
+    /// returns; callers wait on it rather than on this method.
     pub fn shutdown(&self) {
         self.shutdown.store(true, Ordering::SeqCst);
     }
 
+    /// Remove this node from the registry so it drops out of the views
+    /// immediately.
+    ///
+    /// Best-effort: failures are logged, and the call gives up after
+    /// [`DEREGISTER_TIMEOUT`] so shutdown never hangs on the registry.
+    pub async fn deregister(&self) {
+        // Heartbeats (and registration) use the gossip transport's peer ID
+        let peer_id = match self.epidemic_gossip.transport_peer_id().await {
+            Ok(pid) => hex::encode(pid.as_bytes()),
+            Err(_) => self.peer_id.clone(),
+        };
+        match tokio::time::timeout(DEREGISTER_TIMEOUT, self.registry.deregister(&peer_id)).await {
+            Ok(Ok(())) => info!("Deregistered from registry"),
+            Ok(Err(e)) => warn!("Failed to deregister from registry: {}", e),
+            Err(_) => warn!(
+                "Registry did not acknowledge deregistration within {:?}",
+                DEREGISTER_TIMEOUT
+            ),
+        }
+    }
+
     /// Get current connected peer count.
     pub async fn connected_peer_count(&self) -> usize {
         self.connected_peers.read().await.len()
@@ -6187,7 +7506,149 @@ impl TestNode {
             relay_connections: self.relay_connections.load(Ordering::Relaxed),
             bytes_sent: self.total_bytes_sent.load(Ordering::Relaxed),
             bytes_received: self.total_bytes_received.load(Ordering::Relaxed),
+            resumable_reconnects: self.resumption.resumable_reconnects(),
+            zero_rtt_reconnects: self.resumption.zero_rtt_reconnects(),
+            time_to_first_connection: self.join_timer.join_time(),
+        }
+    }
+
+    /// Look up a peer by ID among connected peers, then in the registry.
+    async fn find_peer(&self, peer_id: &str) -> anyhow::Result<PeerInfo> {
+        let peer_id = peer_id.trim();
+        let tracked = self
+            .connected_peers
+            .read()
+            .await
+            .values()
+            .find(|tracked| tracked.info.peer_id.eq_ignore_ascii_case(peer_id))
+            .map(|tracked| tracked.info.clone());
+        if let Some(info) = tracked {
+            return Ok(info);
         }
+        self.registry
+            .get_peers()
+            .await?
+            .into_iter()
+            .find(|peer| peer.peer_id.eq_ignore_ascii_case(peer_id))
+            .ok_or_else(|| anyhow::anyhow!("Unknown peer {}", peer_id))
+    }
+
+    /// Connect to `peer_id` now, falling back to a relay if needed.
+    ///
+    /// Returns the method used and the relay's peer ID if one was chosen.
+    pub async fn connect_peer(
+        &self,
+        peer_id: &str,
+    ) -> anyhow::Result<(ConnectionMethod, Option<[u8; 32]>)> {
+        let peer = self.find_peer(peer_id).await?;
+        self.connect_with_relay_fallback(&peer)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// Test every path to `peer_id` (direct, NAT traversal) and return the
+    /// resulting connectivity matrix.
+    pub async fn run_nat_test(&self, peer_id: &str) -> anyhow::Result<ConnectivityMatrix> {
+        let peer = self
+            .peer_filter
+            .dial_targets([self.find_peer(peer_id).await?])
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("{} is excluded by the peer filter", peer_id))?;
+        let _dial_slot = self.dial_limiter.acquire().await;
+        let result = real_connect_comprehensive(
+            &self.node,
+            &peer,
+            false,
+            self.config.happy_eyeballs_delay,
+            self.config.connect_timeout,
+        )
+        .await;
+        Ok(result.matrix)
+    }
+
+    /// Dial every currently-known peer once and report what was reachable.
+    ///
+    /// Known peers are the registry's view plus anything we're already
+    /// connected to, after the blacklist/allowlist. Sweeps are refused while
+    /// one is running or within [`CONNECTIVITY_SWEEP_COOLDOWN`] of the last,
+    /// and dial at most [`CONNECTIVITY_SWEEP_CONCURRENCY`] peers at a time.
+    /// Progress and the result are reported to the TUI as
+    /// `ConnectivitySweep*` events.
+    pub async fn run_connectivity_sweep(&self) -> Result<ConnectivitySweepSummary, String> {
+        use futures::StreamExt;
+
+        let _gate = match SweepGate::begin(
+            &self.sweep_gate,
+            Instant::now(),
+            CONNECTIVITY_SWEEP_COOLDOWN,
+        ) {
+            Ok(guard) => guard,
+            Err(reason) => {
+                send_tui_event(
+                    &self.event_tx,
+                    TuiEvent::ConnectivitySweepRejected {
+                        reason: reason.clone(),
+                    },
+                );
+                return Err(reason);
+            }
+        };
+
+        let mut known: HashMap<String, PeerInfo> = self
+            .connected_peers
+            .read()
+            .await
+            .values()
+            .map(|tracked| (tracked.info.peer_id.clone(), tracked.info.clone()))
+            .collect();
+        match self.registry.get_peers().await {
+            Ok(peers) => {
+                for peer in peers {
+                    known.entry(peer.peer_id.clone()).or_insert(peer);
+                }
+            }
+            Err(e) => warn!("Connectivity sweep: registry unavailable: {}", e),
+        }
+        let targets: Vec<PeerInfo> = self
+            .peer_filter
+            .dial_targets(known.into_values())
+            .into_iter()
+            .filter(|p| p.peer_id != self.peer_id)
+            .take(CONNECTIVITY_SWEEP_MAX_PEERS)
+            .collect();
+
+        info!("Connectivity sweep: dialling {} peers", targets.len());
+        send_tui_event(
+            &self.event_tx,
+            TuiEvent::ConnectivitySweepStarted {
+                peers: targets.len(),
+            },
+        );
+
+        let started = Instant::now();
+        let results: Vec<Option<ConnectionMethod>> = futures::stream::iter(targets)
+            .map(|peer| async move {
+                self.connect_with_relay_fallback(&peer)
+                    .await
+                    .map(|(method, _)| method)
+                    .ok()
+            })
+            .buffer_unordered(CONNECTIVITY_SWEEP_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut summary = ConnectivitySweepSummary::default();
+        for method in results {
+            summary.record(method);
+        }
+        summary.duration = started.elapsed();
+
+        info!("Connectivity sweep: {}", summary.describe());
+        send_tui_event(
+            &self.event_tx,
+            TuiEvent::ConnectivitySweepComplete(summary.clone()),
+        );
+        Ok(summary)
     }
 }
 
@@ -6208,6 +7669,12 @@ pub struct GlobalStats {
     pub bytes_sent: u64,
     /// Total bytes received.
     pub bytes_received: u64,
+    /// Reconnects made while holding a resumption token for the peer.
+    pub resumable_reconnects: u64,
+    /// Reconnects that used 0-RTT, inferred from dial timing.
+    pub zero_rtt_reconnects: u64,
+    /// Time from start to the first NAT-verified peer, once joined.
+    pub time_to_first_connection: Option<JoinTime>,
 }
 
 impl GlobalStats {
@@ -6224,6 +7691,28 @@ impl GlobalStats {
 
 // Helper functions
 
+/// Pick a gossip-announced relay able to reach `targets` under `policy`.
+///
+/// RTTs come from our own connected-peer stats, so relays we have exchanged
+/// test traffic with win ties over ones we have never measured.
+async fn select_announced_relay(
+    discovery: &GossipDiscovery,
+    connected_peers: &RwLock<HashMap<String, TrackedPeer>>,
+    targets: &[SocketAddr],
+    exclude: &str,
+    policy: RelaySelectionPolicy,
+) -> Option<RelayAnnouncement> {
+    let rtts: HashMap<String, Duration> = connected_peers
+        .read()
+        .await
+        .iter()
+        .filter_map(|(id, p)| p.stats.last_rtt.map(|rtt| (id.clone(), rtt)))
+        .collect();
+    discovery
+        .select_relay(targets, exclude, policy, |id| rtts.get(id).copied())
+        .await
+}
+
 /// Convert peer ID string to 32-byte array.
 fn peer_id_to_bytes(peer_id: &str) -> [u8; 32] {
     let mut bytes = [0u8; 32];
@@ -6234,9 +7723,34 @@ fn peer_id_to_bytes(peer_id: &str) -> [u8; 32] {
     bytes
 }
 
+/// The address to dial for a peer learned from gossip or from a peer's
+/// request: the first one the peer filter allows.
+///
+/// `None` means the filter rules out the peer or all of its addresses, and
+/// the peer must not be dialed.
+fn first_dialable_addr(
+    filter: &PeerFilter,
+    peer_id: &str,
+    addrs: &[SocketAddr],
+) -> Option<SocketAddr> {
+    let addr = filter.dial_addrs(Some(peer_id), addrs).into_iter().next();
+    if addr.is_none() && !addrs.is_empty() {
+        debug!(
+            "Peer filter: not dialing {}",
+            &peer_id[..8.min(peer_id.len())]
+        );
+    }
+    addr
+}
+
 const DATA_PROOF_PAYLOAD_SIZE: usize = 1024;
 const DATA_PROOF_TIMEOUT_SECS: u64 = 5;
 
+#[tracing::instrument(
+    name = "packet_exchange",
+    skip_all,
+    fields(peer_id = %hex::encode(peer_id.0), transport = "data_proof")
+)]
 async fn perform_bidirectional_data_exchange(
     endpoint: &Arc<P2pEndpoint>,
     peer_id: &QuicPeerId,
@@ -6314,28 +7828,67 @@ async fn perform_bidirectional_data_exchange(
     }
 }
 
+/// Dial `addrs` in order until one connects.
+///
+/// Returns the connected peer, the address and how long the family took, or
+/// the reason the last address failed (`None` if there were no addresses).
+async fn dial_family(
+    endpoint: &Arc<P2pEndpoint>,
+    addrs: &[&SocketAddr],
+    family: AddressFamily,
+    connect_timeout: Duration,
+) -> Result<(QuicPeerId, SocketAddr, Duration), Option<FailureReasonCode>> {
+    let start = Instant::now();
+    let timeout = DIRECT_DIAL_TIMEOUT.min(connect_timeout);
+    let mut last_error = None;
+    for addr in addrs {
+        match tokio::time::timeout(timeout, endpoint.connect(**addr)).await {
+            Ok(Ok(conn)) => return Ok((conn.peer_id, **addr, start.elapsed())),
+            Ok(Err(e)) => {
+                last_error = Some(classify_connection_error(&e));
+                debug!("{} direct to {} failed: {}", family, addr, e);
+            }
+            Err(_) => {
+                last_error = Some(FailureReasonCode::Timeout);
+                debug!("{} direct to {} timed out", family, addr);
+            }
+        }
+    }
+    Err(last_error)
+}
+
 struct ComprehensiveConnectResult {
     matrix: ConnectivityMatrix,
     best_method: ConnectionMethod,
     success: bool,
+    /// Reason the last failed path attempt failed (`None` on success)
+    last_error: Option<FailureReasonCode>,
+    /// Address family that won the direct-connection race
+    family: Option<AddressFamily>,
+    /// Handshake timing of the direct connection, for 0-RTT tracking
+    handshake: Option<DialHandshake>,
 }
 
 /// Comprehensive connection test that tries ALL paths for complete network analysis.
 ///
 /// Unlike real_connect which returns on first success, this function tests:
-/// 1. IPv4 direct connections (all IPv4 addresses)
-/// 2. IPv6 direct connections (all IPv6 addresses)
-/// 3. NAT traversal (hole-punching) - skipped if `skip_nat_test` is true
-/// 4. Relay (if available)
+/// 1. Direct connections, racing IPv6 against IPv4 with `happy_eyeballs_delay`
+///    head start for IPv6; the losing family is cancelled and left untested
+/// 2. NAT traversal (hole-punching) - skipped if `skip_nat_test` is true
+/// 3. Relay (if available)
 ///
 /// Set `skip_nat_test` to true when both peers are VPS nodes (direct always works).
+#[tracing::instrument(name = "dial", skip_all, fields(peer_id = %peer.peer_id, skip_nat_test))]
 async fn real_connect_comprehensive(
     node: &Arc<Node>,
     peer: &PeerInfo,
     skip_nat_test: bool,
+    happy_eyeballs_delay: Duration,
+    connect_timeout: Duration,
 ) -> ComprehensiveConnectResult {
     let peer_id_short = &peer.peer_id[..8.min(peer.peer_id.len())];
     let mut matrix = ConnectivityMatrix::default();
+    let mut last_error = None;
     let endpoint = node.inner_endpoint();
 
     let ipv4_addrs: Vec<_> = peer.addresses.iter().filter(|a| a.is_ipv4()).collect();
@@ -6348,82 +7901,63 @@ async fn real_connect_comprehensive(
         ipv6_addrs.len()
     );
 
-    if !ipv4_addrs.is_empty() {
-        matrix.ipv4_direct_tested = true;
-        let start = Instant::now();
-        for addr in &ipv4_addrs {
-            match tokio::time::timeout(Duration::from_secs(10), endpoint.connect(**addr)).await {
-                Ok(Ok(conn)) => {
-                    matrix.ipv4_direct_success = true;
-                    matrix.ipv4_direct_rtt_ms = Some(start.elapsed().as_millis() as u64);
-
-                    let data_proof =
-                        perform_bidirectional_data_exchange(endpoint, &conn.peer_id).await;
-                    if data_proof.is_some() {
-                        matrix.data_proof = data_proof;
-                        matrix.success_level = SuccessLevel::Usable;
-                        info!(
-                            "IPv4 direct to {} at {} succeeded with data proof",
-                            peer_id_short, addr
-                        );
-                    } else {
-                        matrix.success_level = SuccessLevel::Established;
-                        info!(
-                            "IPv4 direct to {} at {} connected (no data proof)",
-                            peer_id_short, addr
-                        );
-                    }
-                    break;
-                }
-                Ok(Err(e)) => {
-                    debug!("IPv4 direct to {} failed: {}", addr, e);
-                }
-                Err(_) => {
-                    debug!("IPv4 direct to {} timed out", addr);
-                }
-            }
+    let (winner, failures) = happy_eyeballs(
+        dial_family(endpoint, &ipv6_addrs, AddressFamily::Ipv6, connect_timeout),
+        dial_family(endpoint, &ipv4_addrs, AddressFamily::Ipv4, connect_timeout),
+        happy_eyeballs_delay,
+    )
+    .await;
+
+    for (family, error) in failures {
+        // A family with no addresses fails instantly and was never tried
+        match family {
+            AddressFamily::Ipv4 => matrix.ipv4_direct_tested = !ipv4_addrs.is_empty(),
+            AddressFamily::Ipv6 => matrix.ipv6_direct_tested = !ipv6_addrs.is_empty(),
         }
+        last_error = error.or(last_error);
     }
 
-    if !ipv6_addrs.is_empty() {
-        matrix.ipv6_direct_tested = true;
-        let start = Instant::now();
-        for addr in &ipv6_addrs {
-            match tokio::time::timeout(Duration::from_secs(10), endpoint.connect(**addr)).await {
-                Ok(Ok(conn)) => {
-                    matrix.ipv6_direct_success = true;
-                    matrix.ipv6_direct_rtt_ms = Some(start.elapsed().as_millis() as u64);
-
-                    if matrix.data_proof.is_none() {
-                        let data_proof =
-                            perform_bidirectional_data_exchange(endpoint, &conn.peer_id).await;
-                        if data_proof.is_some() {
-                            matrix.data_proof = data_proof;
-                            matrix.success_level = SuccessLevel::Usable;
-                            info!(
-                                "IPv6 direct to {} at {} succeeded with data proof",
-                                peer_id_short, addr
-                            );
-                        } else {
-                            if matrix.success_level < SuccessLevel::Established {
-                                matrix.success_level = SuccessLevel::Established;
-                            }
-                            info!(
-                                "IPv6 direct to {} at {} connected (no data proof)",
-                                peer_id_short, addr
-                            );
-                        }
-                    }
-                    break;
-                }
-                Ok(Err(e)) => {
-                    debug!("IPv6 direct to {} failed: {}", addr, e);
-                }
-                Err(_) => {
-                    debug!("IPv6 direct to {} timed out", addr);
-                }
+    let family = winner.as_ref().map(|(family, _)| *family);
+    let mut handshake = None;
+    if let Some((family, (peer_id, addr, rtt))) = winner {
+        let rtt_ms = Some(rtt.as_millis() as u64);
+        match family {
+            AddressFamily::Ipv4 => {
+                matrix.ipv4_direct_tested = true;
+                matrix.ipv4_direct_success = true;
+                matrix.ipv4_direct_rtt_ms = rtt_ms;
+            }
+            AddressFamily::Ipv6 => {
+                matrix.ipv6_direct_tested = true;
+                matrix.ipv6_direct_success = true;
+                matrix.ipv6_direct_rtt_ms = rtt_ms;
             }
         }
+
+        let data_proof = perform_bidirectional_data_exchange(endpoint, &peer_id).await;
+        if data_proof.is_some() {
+            matrix.data_proof = data_proof;
+            matrix.success_level = SuccessLevel::Usable;
+            info!(
+                "{} direct to {} at {} succeeded with data proof",
+                family, peer_id_short, addr
+            );
+        } else {
+            matrix.success_level = SuccessLevel::Established;
+            info!(
+                "{} direct to {} at {} connected (no data proof)",
+                family, peer_id_short, addr
+            );
+        }
+
+        // Read after the data exchange so the peer's NEW_TOKEN has arrived
+        if let Ok(Some(conn)) = endpoint.get_quic_connection(&peer_id) {
+            handshake = Some(DialHandshake {
+                duration: rtt,
+                rtt: conn.rtt(),
+                token_received: conn.stats().frame_rx.new_token > 0,
+            });
+        }
     }
 
     if skip_nat_test {
@@ -6441,7 +7975,7 @@ async fn real_connect_comprehensive(
             let start = Instant::now();
 
             match tokio::time::timeout(
-                Duration::from_secs(30),
+                connect_timeout,
                 endpoint.connect_to_peer(quic_peer_id, None),
             )
             .await
@@ -6472,9 +8006,11 @@ async fn real_connect_comprehensive(
                     }
                 }
                 Ok(Err(e)) => {
+                    last_error = Some(classify_connection_error(&e));
                     debug!("NAT traversal to {} failed: {}", peer_id_short, e);
                 }
                 Err(_) => {
+                    last_error = Some(FailureReasonCode::Timeout);
                     debug!("NAT traversal to {} timed out", peer_id_short);
                 }
             }
@@ -6487,7 +8023,7 @@ async fn real_connect_comprehensive(
 
     // Determine best method and whether we have any connection
     let (best_method, active_is_ipv6) = if matrix.ipv6_direct_success {
-        // Prefer IPv6 if available (typically better for P2P)
+        // Only the family that won the happy-eyeballs race connected directly
         (ConnectionMethod::Direct, true)
     } else if matrix.ipv4_direct_success {
         (ConnectionMethod::Direct, false)
@@ -6518,9 +8054,43 @@ async fn real_connect_comprehensive(
         matrix,
         best_method,
         success,
+        last_error: if success { None } else { last_error },
+        family,
+        handshake,
     }
 }
 
+/// RTT of our live QUIC connection to `peer_id`, in milliseconds.
+fn connection_rtt_ms(node: &Node, peer_id: &QuicPeerId) -> Option<u64> {
+    let conn = node.inner_endpoint().get_quic_connection(peer_id).ok()??;
+    Some(conn.rtt().as_millis() as u64)
+}
+
+/// The peer ant-quic coordinates hole punches through, with the NAT frame
+/// counts on our connection to it.
+///
+/// ant-quic's fallback strategy uses the first known peer as coordinator, and
+/// the node's known peers are the gossip bootstrap addresses.
+async fn nat_coordinator(
+    node: &Node,
+    bootstrap_addrs: &[SocketAddr],
+) -> Option<(String, NatFrameCounts)> {
+    let coordinator_addr = bootstrap_addrs.first()?;
+    let peer = node.connected_peers().await.into_iter().find(|p| {
+        p.remote_addr.ip().to_canonical() == coordinator_addr.ip().to_canonical()
+            && p.remote_addr.port() == coordinator_addr.port()
+    })?;
+    let conn = node
+        .inner_endpoint()
+        .get_quic_connection(&peer.peer_id)
+        .ok()
+        .flatten()?;
+    Some((
+        hex::encode(peer.peer_id.0),
+        NatFrameCounts::from_stats(&conn.stats()),
+    ))
+}
+
 /// Attempt gossip-based NAT traversal (standalone version for worker contexts).
 ///
 /// When direct connection and standard NAT traversal fail, this uses connected
@@ -6535,6 +8105,7 @@ async fn try_gossip_nat_traversal_standalone(
     relay_state: &Arc<RwLock<RelayState>>,
     our_peer_id: &str,
     external_addresses: &Arc<RwLock<Vec<SocketAddr>>>,
+    nat_tracer: &NatPhaseTracer,
 ) -> Result<bool, String> {
     let target_hex = hex::encode(&target_peer_id[..8]);
     info!(
@@ -6706,6 +8277,13 @@ async fn try_gossip_nat_traversal_standalone(
                                 &relay_peer_hex[..8.min(relay_peer_hex.len())],
                                 target_hex
                             );
+                            let target_peer_hex = hex::encode(target_peer_id);
+                            nat_tracer.transition(
+                                &target_peer_hex,
+                                NatTraversalPhase::Coordinating,
+                                Some(&relay_peer_hex),
+                            );
+                            nat_tracer.frame(&target_peer_hex, FrameDirection::Sent);
                             return Ok(true);
                         }
                     }
@@ -6729,6 +8307,8 @@ async fn handle_relay_message_standalone(
     _relay_state: &Arc<RwLock<RelayState>>,
     our_peer_id: &str,
     external_addresses: &Arc<RwLock<Vec<SocketAddr>>>,
+    peer_filter: &PeerFilter,
+    dial_limiter: &DialLimiter,
 ) -> Option<Vec<u8>> {
     use super::test_protocol::{ReachResponse, RelayAckResponse, RelayMessage};
 
@@ -6797,11 +8377,14 @@ async fn handle_relay_message_standalone(
 
                 // Try to connect to the requester at their addresses
                 let endpoint_clone = Arc::clone(endpoint);
-                let addresses = req.requester_addresses.clone();
+                let addresses =
+                    peer_filter.dial_addrs(Some(&requester_hex), &req.requester_addresses);
                 let round = req.round;
                 let requester_hex_for_spawn = requester_hex.clone();
+                let dial_limiter = dial_limiter.clone();
 
                 tokio::spawn(async move {
+                    let _dial_slot = dial_limiter.acquire().await;
                     for addr in &addresses {
                         info!(
                             "Punching to {} at {} (round={})",
@@ -6951,9 +8534,17 @@ async fn handle_relay_message_standalone(
 /// Perform test packet exchange via gossip transport.
 ///
 /// This uses the saorsa-gossip transport (configured via --bind-port flag),
-/// bypassing the P2pEndpoint that uses a different port.
+/// bypassing the P2pEndpoint that uses a different port. The timer runs from
+/// the send until the peer's pong for this sequence arrives; no pong within
+/// [`TEST_PONG_TIMEOUT`] is an error, which the burst counts as a lost packet.
+#[tracing::instrument(
+    name = "packet_exchange",
+    skip_all,
+    fields(peer_id = peer_id_hex, transport = "gossip")
+)]
 async fn gossip_test_exchange(
     gossip: &EpidemicGossip,
+    pending_pongs: &PendingPongs,
     peer_id_hex: &str,
     packet: &TestPacket,
 ) -> Result<Duration, String> {
@@ -6975,29 +8566,56 @@ async fn gossip_test_exchange(
     let packet_data =
         serde_json::to_vec(packet).map_err(|e| format!("Failed to serialize packet: {}", e))?;
 
+    let key = (peer_id_hex.to_string(), packet.sequence);
+    let (pong_tx, pong_rx) = tokio::sync::oneshot::channel();
+    pending_pongs
+        .lock()
+        .map_err(|_| "Pending pong map poisoned".to_string())?
+        .insert(key.clone(), pong_tx);
+    let forget = || {
+        if let Ok(mut pending) = pending_pongs.lock() {
+            pending.remove(&key);
+        }
+    };
+
     let start = Instant::now();
 
     // Send via gossip transport (uses configured gossip_port)
-    gossip
-        .send_to_peer(gossip_peer_id, packet_data)
-        .await
-        .map_err(|e| format!("Gossip send failed: {}", e))?;
-
-    let rtt = start.elapsed();
-
-    debug!(
-        "GOSSIP test packet sent to {} (RTT: {:?})",
-        &peer_id_hex[..8.min(peer_id_hex.len())],
-        rtt
-    );
+    if let Err(e) = gossip.send_to_peer(gossip_peer_id, packet_data).await {
+        forget();
+        return Err(format!("Gossip send failed: {}", e));
+    }
 
-    Ok(rtt)
+    match tokio::time::timeout(TEST_PONG_TIMEOUT, pong_rx).await {
+        Ok(Ok(())) => {
+            let rtt = start.elapsed();
+            debug!(
+                "GOSSIP pong from {} (seq={}, RTT: {:?})",
+                &peer_id_hex[..8.min(peer_id_hex.len())],
+                packet.sequence,
+                rtt
+            );
+            Ok(rtt)
+        }
+        _ => {
+            forget();
+            Err(format!(
+                "No pong for seq {} within {:?}",
+                packet.sequence, TEST_PONG_TIMEOUT
+            ))
+        }
+    }
 }
 
 /// Perform test packet exchange via QUIC transport.
 ///
 /// This uses the P2pEndpoint QUIC transport, sending directly to the peer.
 /// The response is handled asynchronously via P2pEvent::DataReceived.
+#[tracing::instrument(
+    name = "packet_exchange",
+    skip_all,
+    fields(peer_id = peer_id_hex, transport = "quic")
+)]
 async fn quic_test_exchange(
     node: &Arc<Node>,
     peer_id_hex: &str,
@@ -7087,7 +8705,8 @@ async fn send_gossip_peer_list(
     Ok(())
 }
 
-/// Broadcast a peer announcement to all connected peers.
+/// Broadcast a peer announcement to the connected peers, or a random gossip
+/// fanout of them, in `discovery`'s wire format.
 ///
 /// Called when a new peer is discovered to propagate the information.
 async fn broadcast_peer_announcement(
@@ -7095,6 +8714,7 @@ async fn broadcast_peer_announcement(
     connected_peers: &RwLock<HashMap<String, TrackedPeer>>,
     announcement: &GossipPeerAnnouncement,
     exclude_peer: Option<&str>,
+    discovery: &crate::gossip::GossipDiscovery,
 ) -> usize {
     let peer_ids: Vec<String> = {
         let peers = connected_peers.read().await;
@@ -7111,9 +8731,11 @@ async fn broadcast_peer_announcement(
             .collect()
     };
 
+    let targets = discovery.fanout_targets(peer_ids);
+    let message_data = announcement.to_wire(discovery);
     let mut success_count = 0;
 
-    for peer_id_hex in peer_ids {
+    for peer_id_hex in targets {
         let peer_id_bytes = match hex::decode(&peer_id_hex) {
             Ok(bytes) => bytes,
             Err(_) => continue,
@@ -7132,12 +8754,6 @@ async fn broadcast_peer_announcement(
             _ => continue,
         };
 
-        // Serialize the announcement
-        let message_data = match announcement.to_bytes() {
-            Ok(data) => data,
-            Err(_) => continue,
-        };
-
         // Open a unidirectional stream and send the announcement
         let result = async {
             let mut send_stream = connection.open_uni().await?;
@@ -7288,3 +8904,447 @@ async fn build_comprehensive_peer_list(
 
 // Note: Gossip message receiving is now done via the central endpoint.recv()
 // loop in start_gossip_listener, not via per-peer receivers.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_gate_rate_limits_and_serialises() {
+        let cooldown = Duration::from_secs(60);
+        let start = Instant::now();
+        let mut gate = SweepGate::default();
+
+        assert!(gate.try_begin(start, cooldown).is_ok());
+        let err = gate.try_begin(start, cooldown).unwrap_err();
+        assert!(err.contains("already running"), "{}", err);
+
+        gate.finish();
+        let err = gate
+            .try_begin(start + Duration::from_secs(45), cooldown)
+            .unwrap_err();
+        assert!(err.contains("15s"), "{}", err);
+
+        assert!(gate.try_begin(start + cooldown, cooldown).is_ok());
+    }
+
+    #[test]
+    fn test_sweep_guard_releases_gate_on_unwind() {
+        let cooldown = Duration::ZERO;
+        let gate = Arc::new(std::sync::Mutex::new(SweepGate::default()));
+
+        let shared = Arc::clone(&gate);
+        let panicked = std::panic::catch_unwind(move || {
+            let _guard = SweepGate::begin(&shared, Instant::now(), cooldown).unwrap();
+            panic!("sweep failed");
+        });
+        assert!(panicked.is_err());
+
+        let guard = SweepGate::begin(&gate, Instant::now(), cooldown).unwrap();
+        assert!(SweepGate::begin(&gate, Instant::now(), cooldown).is_err());
+        drop(guard);
+        assert!(SweepGate::begin(&gate, Instant::now(), cooldown).is_ok());
+    }
+
+    #[test]
+    fn test_advertised_capabilities_follow_config() {
+        let config = TestNodeConfig::default();
+        let caps = config.advertised_capabilities(true);
+        assert!(caps.pqc);
+        assert!(!caps.relay);
+        assert!(caps.ipv4 && caps.ipv6);
+        assert!(!config.advertised_capabilities(false).ipv6);
+
+        let relay = TestNodeConfig {
+            relay: true,
+            ..Default::default()
+        };
+        assert!(relay.advertised_capabilities(false).relay);
+
+        let ipv4_only = TestNodeConfig {
+            bind_addr: "0.0.0.0:0".parse().unwrap(),
+            ..Default::default()
+        };
+        let caps = ipv4_only.advertised_capabilities(true);
+        assert!(caps.ipv4 && !caps.ipv6);
+    }
+
+    #[test]
+    fn test_heartbeat_ttl_warning_at_half_ttl() {
+        let config = TestNodeConfig::default();
+        assert_eq!(config.heartbeat_ttl_warning(120), None);
+        assert_eq!(config.heartbeat_ttl_warning(11), None);
+        assert!(config.heartbeat_ttl_warning(10).is_some());
+
+        let slow = TestNodeConfig {
+            heartbeat_interval: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let warning = slow.heartbeat_ttl_warning(120).unwrap();
+        assert!(warning.contains("120s"), "{}", warning);
+    }
+
+    #[tokio::test]
+    async fn test_relay_not_attempted_while_earlier_methods_pending() {
+        let window = Duration::from_millis(100);
+        let started = Instant::now();
+
+        // Direct/NAT still pending: relay must wait for the full window
+        let early = tokio::time::timeout(
+            Duration::from_millis(20),
+            attempt_within_relay_window(std::future::pending(), window),
+        )
+        .await;
+        assert!(early.is_err(), "relay attempted before fallback delay");
+
+        let outcome = attempt_within_relay_window(std::future::pending(), window).await;
+        assert_eq!(outcome, None);
+        assert!(started.elapsed() >= window);
+    }
+
+    #[tokio::test]
+    async fn test_happy_eyeballs_ipv6_head_start() {
+        // IPv6 answering inside its head start means IPv4 is never dialed
+        let ipv4_started = AtomicBool::new(false);
+        let (winner, failures) = happy_eyeballs(
+            async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                Ok::<_, &str>("v6")
+            },
+            async {
+                ipv4_started.store(true, Ordering::SeqCst);
+                Ok("v4")
+            },
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(winner, Some((AddressFamily::Ipv6, "v6")));
+        assert!(failures.is_empty());
+        assert!(!ipv4_started.load(Ordering::SeqCst));
+
+        // A slow IPv6 path loses to IPv4 once the head start runs out
+        let started = Instant::now();
+        let (winner, failures) = happy_eyeballs(
+            async {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok::<_, &str>("v6")
+            },
+            async { Ok("v4") },
+            Duration::from_millis(20),
+        )
+        .await;
+        assert_eq!(winner, Some((AddressFamily::Ipv4, "v4")));
+        assert!(failures.is_empty());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_happy_eyeballs_ipv6_failure_starts_ipv4_immediately() {
+        let started = Instant::now();
+        let (winner, failures) = happy_eyeballs(
+            async { Err("refused") },
+            async { Ok("v4") },
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(winner, Some((AddressFamily::Ipv4, "v4")));
+        assert_eq!(failures, vec![(AddressFamily::Ipv6, "refused")]);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let (winner, failures) = happy_eyeballs(
+            async { Err::<(), _>("refused") },
+            async { Err("timeout") },
+            Duration::ZERO,
+        )
+        .await;
+        assert_eq!(winner, None);
+        assert_eq!(failures.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_drain_tasks_waits_for_in_flight_work() {
+        let done = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&done);
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let aborted = drain_tasks(vec![task], Duration::from_secs(5)).await;
+        assert_eq!(aborted, 0);
+        assert!(done.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_drain_tasks_aborts_after_grace() {
+        let done = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&done);
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let started = Instant::now();
+        let aborted = drain_tasks(vec![task], Duration::from_millis(50)).await;
+        assert_eq!(aborted, 1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!done.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_tasks_drain_waits_then_aborts() {
+        let in_flight = InFlightTasks::default();
+        let reported = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&reported);
+        in_flight.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            flag.store(true, Ordering::SeqCst);
+        });
+        in_flight.spawn(tokio::time::sleep(Duration::from_secs(30)));
+
+        let started = Instant::now();
+        let aborted = in_flight.drain(Duration::from_millis(200)).await;
+        assert_eq!(aborted, 1);
+        assert!(reported.load(Ordering::SeqCst));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(in_flight.drain(Duration::ZERO).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_relay_window_returns_earlier_success() {
+        let outcome = attempt_within_relay_window(
+            async { Some(ConnectionMethod::HolePunched) },
+            Duration::from_secs(5),
+        )
+        .await;
+        assert_eq!(outcome, Some(ConnectionMethod::HolePunched));
+    }
+
+    #[tokio::test]
+    async fn test_relay_allowed_immediately_when_earlier_methods_fail() {
+        let started = Instant::now();
+        let outcome = attempt_within_relay_window(async { None }, Duration::from_secs(5)).await;
+        assert_eq!(outcome, None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Span name and `peer_id` field, as recorded by [`SpanRecorder`].
+    type RecordedSpan = (String, Option<String>);
+
+    /// Records the name and `peer_id` field of every span created.
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<std::sync::Mutex<Vec<RecordedSpan>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            struct PeerIdField(Option<String>);
+            impl tracing::field::Visit for PeerIdField {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    if field.name() == "peer_id" {
+                        self.0 = Some(format!("{:?}", value));
+                    }
+                }
+            }
+
+            let mut peer_id = PeerIdField(None);
+            attrs.record(&mut peer_id);
+            if let Ok(mut spans) = self.0.lock() {
+                spans.push((attrs.metadata().name().to_string(), peer_id.0));
+            }
+        }
+    }
+
+    /// A registry peer with no addresses, so every dial to it fails fast.
+    fn unreachable_peer(peer_id: &str) -> PeerInfo {
+        PeerInfo {
+            peer_id: peer_id.to_string(),
+            addresses: Vec::new(),
+            nat_type: NatType::Unknown,
+            country_code: None,
+            latitude: 0.0,
+            longitude: 0.0,
+            location_estimated: false,
+            last_seen: 0,
+            connection_success_rate: 0.5,
+            capabilities: NodeCapabilities::default(),
+            version: String::new(),
+            is_active: true,
+            status: Default::default(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            connected_peers: 0,
+            gossip_stats: None,
+            full_mesh_probes: None,
+            last_error: None,
+            time_to_first_connection: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dial_emits_span_with_peer_id() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let recorder = SpanRecorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let node = Arc::new(Node::bind("127.0.0.1:0".parse().unwrap()).await.unwrap());
+        let peer_id = "ab".repeat(32);
+        let result = real_connect_comprehensive(
+            &node,
+            &unreachable_peer(&peer_id),
+            true,
+            Duration::from_millis(250),
+            DIRECT_DIAL_TIMEOUT,
+        )
+        .await;
+        assert!(!result.success);
+
+        let spans = recorder.0.lock().unwrap().clone();
+        assert!(
+            spans.contains(&("dial".to_string(), Some(peer_id))),
+            "no dial span with peer_id in {:?}",
+            spans
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gossip_exchange_without_pong_is_lost() {
+        let (tx, _rx) = mpsc::channel(1);
+        let gossip = EpidemicGossip::new(
+            saorsa_gossip_types::PeerId::new([3u8; 32]),
+            crate::epidemic_gossip::EpidemicConfig::default(),
+            tx,
+        );
+        let pending = PendingPongs::default();
+        let packet = TestPacket::new_ping([3u8; 32], 42);
+
+        let result = gossip_test_exchange(&gossip, &pending, &"ab".repeat(32), &packet).await;
+        assert!(result.is_err());
+        assert!(pending.lock().unwrap().is_empty());
+
+        let burst = TestResult::from_burst(42, 1, &[]);
+        assert!(!burst.success);
+        assert_eq!(burst.loss_pct, 100.0);
+    }
+
+    #[cfg(feature = "otlp")]
+    #[tokio::test]
+    async fn test_dial_span_exported_with_peer_id_attribute() {
+        use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry().with(crate::otel::otel_layer(&provider)),
+        );
+
+        let node = Arc::new(Node::bind("127.0.0.1:0".parse().unwrap()).await.unwrap());
+        let peer_id = "cd".repeat(32);
+        let result = real_connect_comprehensive(
+            &node,
+            &unreachable_peer(&peer_id),
+            true,
+            Duration::from_millis(250),
+            DIRECT_DIAL_TIMEOUT,
+        )
+        .await;
+        assert!(!result.success);
+        provider.force_flush().unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let dial = spans
+            .iter()
+            .find(|span| span.name == "dial")
+            .expect("dial span exported");
+        assert!(
+            dial.attributes
+                .iter()
+                .any(|kv| kv.key.as_str() == "peer_id" && kv.value.as_str() == peer_id.as_str()),
+            "no peer_id attribute in {:?}",
+            dial.attributes
+        );
+    }
+
+    #[test]
+    fn test_blacklisted_gossip_peer_is_never_dialed() {
+        let blocked = "ab".repeat(32);
+        let other = "cd".repeat(32);
+        let filter = PeerFilter::new(&[blocked.clone(), "10.0.0.0/8".to_string()], None).unwrap();
+        let addrs: Vec<SocketAddr> = vec![
+            "10.1.2.3:9000".parse().unwrap(),
+            "203.0.113.9:9000".parse().unwrap(),
+        ];
+
+        // Announced under a blacklisted ID: nothing to dial, by address or ID
+        assert_eq!(first_dialable_addr(&filter, &blocked, &addrs), None);
+        assert_eq!(
+            first_dialable_addr(&filter, &blocked.to_uppercase(), &addrs),
+            None
+        );
+        assert!(!filter.may_dial_peer(&blocked));
+
+        // Other peers are dialed outside the blacklisted range only
+        assert_eq!(first_dialable_addr(&filter, &other, &addrs), Some(addrs[1]));
+        assert_eq!(first_dialable_addr(&filter, &other, &addrs[..1]), None);
+        assert!(filter.may_dial_peer(&other));
+    }
+
+    #[test]
+    fn test_allowlist_applies_to_gossip_dials() {
+        let listed = "ab".repeat(32);
+        let unlisted = "cd".repeat(32);
+        let allow = vec![listed.clone(), "192.168.50.0/24".to_string()];
+        let filter = PeerFilter::new(&[], Some(&allow)).unwrap();
+        let public: SocketAddr = "203.0.113.9:9000".parse().unwrap();
+        let lab: SocketAddr = "192.168.50.7:9000".parse().unwrap();
+
+        // Listed by ID: any of its addresses, and NAT traversal by ID
+        assert_eq!(
+            first_dialable_addr(&filter, &listed, &[public]),
+            Some(public)
+        );
+        assert!(filter.may_dial_peer(&listed));
+
+        // Gossip about anyone else is ignored unless it points into the lab range
+        assert_eq!(first_dialable_addr(&filter, &unlisted, &[public]), None);
+        assert_eq!(
+            first_dialable_addr(&filter, &unlisted, &[public, lab]),
+            Some(lab)
+        );
+        assert!(!filter.may_dial_peer(&unlisted));
+
+        // Bootstrap addresses carry no ID and need an allowed range
+        assert!(filter.dial_addrs(None, &[public]).is_empty());
+        assert_eq!(filter.dial_addrs(None, &[public, lab]), vec![lab]);
+    }
+
+    #[test]
+    fn test_connect_back_wait_times_out_on_clock() {
+        let clock = crate::clock::MockClock::new();
+        let wait = ConnectBackWait::start(Arc::new(clock.clone()), CONNECT_BACK_TIMEOUT);
+        assert_eq!(wait.seconds_remaining(), 60);
+
+        clock.advance(Duration::from_secs(59));
+        assert!(!wait.is_expired());
+        assert_eq!(wait.seconds_remaining(), 1);
+
+        clock.advance(Duration::from_secs(1));
+        assert!(wait.is_expired());
+        assert_eq!(wait.seconds_remaining(), 0);
+    }
+}
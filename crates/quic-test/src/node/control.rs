@@ -0,0 +1,450 @@
+//! JSON-RPC control socket for driving a running test node.
+//!
+//! Enabled with `--control-socket <PATH>`, the node listens on a Unix domain
+//! socket for newline-delimited JSON-RPC 2.0 requests, one response line per
+//! request:
+//!
+//! ```text
+//! {"jsonrpc":"2.0","id":1,"method":"connect_peer","params":{"peer_id":"ab12..."}}
+//! {"jsonrpc":"2.0","id":1,"result":{"peer_id":"ab12...","method":"Direct","relay":null}}
+//! ```
+//!
+//! Methods: `connect_peer(peer_id)`, `get_stats()`, `list_connections()` and
+//! `run_nat_test(peer_id)`. Params may be given by name or by position.
+//! Malformed requests get standard JSON-RPC error objects. The socket file
+//! is removed when the [`ControlSocket`] is dropped.
+
+use super::client::TestNode;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{debug, info, warn};
+
+/// Invalid JSON was received.
+const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The node could not carry out a valid request (unknown peer, dial failed).
+const NODE_ERROR: i64 = -32000;
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn node(error: anyhow::Error) -> Self {
+        Self::new(NODE_ERROR, format!("{:#}", error))
+    }
+}
+
+/// A parsed control request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ControlMethod {
+    ConnectPeer { peer_id: String },
+    GetStats,
+    ListConnections,
+    RunNatTest { peer_id: String },
+}
+
+impl ControlMethod {
+    fn parse(method: &str, params: &Value) -> Result<Self, RpcError> {
+        match method {
+            "connect_peer" => Ok(Self::ConnectPeer {
+                peer_id: peer_id_param(params)?,
+            }),
+            "get_stats" => Ok(Self::GetStats),
+            "list_connections" => Ok(Self::ListConnections),
+            "run_nat_test" => Ok(Self::RunNatTest {
+                peer_id: peer_id_param(params)?,
+            }),
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", other),
+            )),
+        }
+    }
+}
+
+/// `peer_id` from `{"peer_id": ...}` or `[...]` params.
+fn peer_id_param(params: &Value) -> Result<String, RpcError> {
+    let value = match params {
+        Value::Object(map) => map.get("peer_id"),
+        Value::Array(items) => items.first(),
+        _ => None,
+    };
+    value
+        .and_then(Value::as_str)
+        .filter(|peer_id| !peer_id.trim().is_empty())
+        .map(|peer_id| peer_id.trim().to_string())
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing string param 'peer_id'"))
+}
+
+/// Carries out control requests against a node.
+#[async_trait::async_trait]
+pub(crate) trait ControlHandler: Send + Sync + 'static {
+    async fn call(&self, method: ControlMethod) -> Result<Value, RpcError>;
+}
+
+#[async_trait::async_trait]
+impl ControlHandler for TestNode {
+    async fn call(&self, method: ControlMethod) -> Result<Value, RpcError> {
+        match method {
+            ControlMethod::ConnectPeer { peer_id } => {
+                let (method, relay) = self.connect_peer(&peer_id).await.map_err(RpcError::node)?;
+                Ok(json!({
+                    "peer_id": peer_id,
+                    "method": method,
+                    "relay": relay.map(hex::encode),
+                }))
+            }
+            ControlMethod::GetStats => {
+                let stats = self.get_stats();
+                Ok(json!({
+                    "peer_id": self.peer_id(),
+                    "connected_peers": self.connected_peer_count().await,
+                    "total_connections_success": stats.total_connections_success,
+                    "total_connections_failed": stats.total_connections_failed,
+                    "direct_connections": stats.direct_connections,
+                    "holepunch_connections": stats.holepunch_connections,
+                    "relay_connections": stats.relay_connections,
+                    "bytes_sent": stats.bytes_sent,
+                    "bytes_received": stats.bytes_received,
+                    "success_rate": stats.success_rate(),
+                }))
+            }
+            ControlMethod::ListConnections => {
+                let peers = self.get_connected_peers().await;
+                let connections: Vec<Value> = peers
+                    .iter()
+                    .map(|peer| {
+                        json!({
+                            "peer_id": peer.full_id,
+                            "method": peer.method,
+                            "direction": peer.direction,
+                            "address_family": peer.address_family.map(|f| f.to_string()),
+                            "addresses": peer.addresses,
+                            "rtt_ms": peer.rtt.map(|rtt| rtt.as_millis() as u64),
+                            "packets_sent": peer.packets_sent,
+                            "packets_received": peer.packets_received,
+                            "outbound_verified": peer.outbound_verified,
+                            "inbound_verified": peer.inbound_verified,
+                            "connected_secs": peer.connected_at.elapsed().as_secs(),
+                            "connectivity": peer.connectivity,
+                        })
+                    })
+                    .collect();
+                Ok(Value::Array(connections))
+            }
+            ControlMethod::RunNatTest { peer_id } => {
+                let matrix = self.run_nat_test(&peer_id).await.map_err(RpcError::node)?;
+                serde_json::to_value(matrix).map_err(|e| RpcError::node(e.into()))
+            }
+        }
+    }
+}
+
+/// Answer one request line, or `None` for a notification (no `id`).
+pub(crate) async fn handle_line<H: ControlHandler + ?Sized>(
+    handler: &H,
+    line: &str,
+) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Parse error: {}", e)),
+            ));
+        }
+    };
+    let Some(object) = request.as_object() else {
+        return Some(error_response(
+            Value::Null,
+            RpcError::new(INVALID_REQUEST, "Request must be a single JSON object"),
+        ));
+    };
+    let id = object.get("id").cloned();
+    let method = match (object.get("jsonrpc"), object.get("method")) {
+        (Some(version), Some(Value::String(method))) if version == "2.0" => method,
+        _ => {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(
+                    INVALID_REQUEST,
+                    "Request needs \"jsonrpc\": \"2.0\" and a string \"method\"",
+                ),
+            ));
+        }
+    };
+    let params = object.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match ControlMethod::parse(method, &params) {
+        Ok(method) => handler.call(method).await,
+        Err(e) => Err(e),
+    };
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// A running control socket server. Dropping it stops the server and
+/// removes the socket file.
+pub struct ControlSocket {
+    path: PathBuf,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ControlSocket {
+    /// Listen on `path` and serve requests with `handler`. A stale socket
+    /// left by a previous run is replaced, but any other file at `path` is
+    /// an error rather than being deleted. The socket is made owner-only
+    /// (0600).
+    pub(crate) fn start<H: ControlHandler>(path: &Path, handler: Arc<H>) -> anyhow::Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_socket() => {
+                std::fs::remove_file(path).map_err(|e| {
+                    anyhow::anyhow!("Cannot replace control socket {}: {}", path.display(), e)
+                })?;
+            }
+            Ok(_) => anyhow::bail!(
+                "Control socket path {} exists and is not a socket",
+                path.display()
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => anyhow::bail!("Cannot inspect control socket {}: {}", path.display(), e),
+        }
+        let listener = UnixListener::bind(path)
+            .map_err(|e| anyhow::anyhow!("Cannot bind control socket {}: {}", path.display(), e))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).map_err(|e| {
+            anyhow::anyhow!(
+                "Cannot restrict control socket {} permissions: {}",
+                path.display(),
+                e
+            )
+        })?;
+        info!("Control socket listening on {}", path.display());
+
+        let task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve_connection(stream, Arc::clone(&handler)));
+                    }
+                    Err(e) => {
+                        warn!("Control socket accept failed: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            path: path.to_path_buf(),
+            task,
+        })
+    }
+
+    /// Path of the socket file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        self.task.abort();
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            debug!("Control socket {} not removed: {}", self.path.display(), e);
+        }
+    }
+}
+
+async fn serve_connection<H: ControlHandler>(stream: UnixStream, handler: Arc<H>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => return,
+            Err(e) => {
+                debug!("Control connection read failed: {}", e);
+                return;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = handle_line(handler.as_ref(), &line).await else {
+            continue;
+        };
+        let mut out = response.to_string();
+        out.push('\n');
+        if writer.write_all(out.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+impl TestNode {
+    /// Serve the JSON-RPC control API on a Unix socket at `path` until the
+    /// returned handle is dropped.
+    pub fn serve_control_socket(self: &Arc<Self>, path: &Path) -> anyhow::Result<ControlSocket> {
+        ControlSocket::start(path, Arc::clone(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Echoes the parsed method back and counts calls.
+    #[derive(Default)]
+    struct EchoHandler {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ControlHandler for EchoHandler {
+        async fn call(&self, method: ControlMethod) -> Result<Value, RpcError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match method {
+                ControlMethod::ConnectPeer { peer_id } if peer_id == "missing" => {
+                    Err(RpcError::node(anyhow::anyhow!("Unknown peer missing")))
+                }
+                ControlMethod::ConnectPeer { peer_id } => Ok(json!({ "peer_id": peer_id })),
+                other => Ok(json!(format!("{:?}", other))),
+            }
+        }
+    }
+
+    fn error_code(response: &Value) -> i64 {
+        response["error"]["code"].as_i64().unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_requests_and_error_objects() {
+        let handler = EchoHandler::default();
+        let call = |line: &'static str| handle_line(&handler, line);
+
+        let ok =
+            call(r#"{"jsonrpc":"2.0","id":1,"method":"connect_peer","params":{"peer_id":"ab12"}}"#)
+                .await
+                .unwrap();
+        assert_eq!(ok["id"], 1);
+        assert_eq!(ok["result"]["peer_id"], "ab12");
+
+        let positional =
+            call(r#"{"jsonrpc":"2.0","id":"x","method":"run_nat_test","params":["cd34"]}"#)
+                .await
+                .unwrap();
+        assert_eq!(positional["result"], "RunNatTest { peer_id: \"cd34\" }");
+
+        let cases = [
+            (r#"{"jsonrpc":"2.0","id":2,"method""#, PARSE_ERROR),
+            (
+                r#"[{"jsonrpc":"2.0","id":3,"method":"get_stats"}]"#,
+                INVALID_REQUEST,
+            ),
+            (r#"{"id":4,"method":"get_stats"}"#, INVALID_REQUEST),
+            (
+                r#"{"jsonrpc":"2.0","id":5,"method":"reboot"}"#,
+                METHOD_NOT_FOUND,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","id":6,"method":"connect_peer"}"#,
+                INVALID_PARAMS,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","id":7,"method":"connect_peer","params":{"peer_id":"missing"}}"#,
+                NODE_ERROR,
+            ),
+        ];
+        for (line, code) in cases {
+            let response = call(line).await.unwrap();
+            assert_eq!(error_code(&response), code, "{}", line);
+            assert_eq!(response["jsonrpc"], "2.0");
+        }
+
+        // Notifications run but get no reply
+        assert!(
+            call(r#"{"jsonrpc":"2.0","method":"get_stats"}"#)
+                .await
+                .is_none()
+        );
+        // ok, positional, node error, notification; the rest never reach the handler
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_socket_round_trip_and_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("node.sock");
+        // A stale socket from a crashed run is replaced
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let socket = ControlSocket::start(&path, Arc::new(EchoHandler::default())).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let stream = UnixStream::connect(socket.path()).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        writer
+            .write_all(b"not json\n{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"get_stats\"}\n")
+            .await
+            .unwrap();
+        let first: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(error_code(&first), PARSE_ERROR);
+        assert_eq!(first["id"], Value::Null);
+        let second: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(second["result"], "GetStats");
+
+        drop(socket);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_start_refuses_to_replace_regular_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, b"user data").unwrap();
+
+        let err = ControlSocket::start(&path, Arc::new(EchoHandler::default()))
+            .err()
+            .expect("regular file must not be replaced");
+        assert!(err.to_string().contains("not a socket"), "{}", err);
+        assert_eq!(std::fs::read(&path).unwrap(), b"user data");
+    }
+}
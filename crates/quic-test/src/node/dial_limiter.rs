@@ -0,0 +1,126 @@
+//! Bound on concurrent outbound dials.
+//!
+//! Learning about hundreds of peers at once must not start hundreds of
+//! handshakes. Every dial holds a [`DialSlot`] while it runs; the slot
+//! releases its permit when dropped, so success, failure, timeout and task
+//! cancellation all give it back.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Default number of outbound dials allowed in flight at once.
+pub const DEFAULT_MAX_CONCURRENT_DIALS: usize = 16;
+
+/// Counter incremented for as long as the guard lives.
+#[derive(Debug)]
+struct CountGuard(Arc<AtomicUsize>);
+
+impl CountGuard {
+    fn new(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(Arc::clone(counter))
+    }
+}
+
+impl Drop for CountGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Permission to run one dial; dropping it lets the next queued dial start.
+#[derive(Debug)]
+pub struct DialSlot {
+    _permit: OwnedSemaphorePermit,
+    _in_flight: CountGuard,
+}
+
+/// Shared limiter handing out [`DialSlot`]s.
+#[derive(Debug, Clone)]
+pub struct DialLimiter {
+    permits: Arc<Semaphore>,
+    in_flight: Arc<AtomicUsize>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl DialLimiter {
+    /// Allow at most `max` dials at once (at least one).
+    pub fn new(max: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max.max(1))),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            queued: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Wait for a free slot. Counted as queued until one is granted.
+    pub async fn acquire(&self) -> DialSlot {
+        let queued = CountGuard::new(&self.queued);
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("dial semaphore is never closed");
+        drop(queued);
+        DialSlot {
+            _permit: permit,
+            _in_flight: CountGuard::new(&self.in_flight),
+        }
+    }
+
+    /// Dials currently running.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Dials waiting for a slot.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_slots_are_released_on_every_path() {
+        let limiter = DialLimiter::new(2);
+        let first = limiter.acquire().await;
+        let second = limiter.acquire().await;
+        assert_eq!(limiter.in_flight(), 2);
+
+        // A third dial queues until a slot frees up
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(limiter.queued(), 1);
+        drop(first);
+        let third = waiter.await.expect("waiter");
+        assert_eq!((limiter.in_flight(), limiter.queued()), (2, 0));
+
+        // A dial cancelled by a timeout while queued leaves no trace
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(timed_out.is_err());
+        assert_eq!(limiter.queued(), 0);
+
+        // A dial cancelled while running returns its permit
+        let running = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move {
+                let _slot = limiter.acquire().await;
+                std::future::pending::<()>().await;
+            })
+        };
+        drop((second, third));
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(limiter.in_flight(), 1);
+        running.abort();
+        let _ = running.await;
+        assert_eq!(limiter.in_flight(), 0);
+        assert_eq!(limiter.permits.available_permits(), 2);
+    }
+}
@@ -0,0 +1,135 @@
+//! Algorithms of a live ant-quic connection.
+//!
+//! The signature scheme is read from the peer's raw public key, which the
+//! handshake exposes as an SPKI. ant-quic does not surface the key-exchange
+//! group it negotiated, so that half is the groups its crypto provider
+//! offers, reported as offered: the handshake cannot have used anything
+//! else. When the provider offers several groups the label lists them all
+//! rather than guessing which one won.
+//!
+//! If ant-quic cannot build its PQC provider it falls back to the aws-lc-rs
+//! defaults, which include classical groups, and connections then report
+//! those groups. The fallback therefore shows up as classical crypto rather
+//! than as unknown.
+
+use crate::tui::NegotiatedCrypto;
+use ant_quic::crypto::pqc::{PqcConfig, create_crypto_provider};
+use ant_quic::{HighLevelConnection, Node, PeerId};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::CertificateDer;
+use std::sync::{Arc, OnceLock};
+
+/// DER-encoded OID of ML-DSA-65 (2.16.840.1.101.3.4.3.18).
+const ML_DSA_65_OID: &[u8] = &[
+    0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x12,
+];
+/// DER-encoded OID of Ed25519 (1.3.101.112).
+const ED25519_OID: &[u8] = &[0x06, 0x03, 0x2B, 0x65, 0x70];
+
+/// Signature scheme named by a DER SubjectPublicKeyInfo.
+pub fn signature_scheme(spki: &[u8]) -> Option<&'static str> {
+    let contains = |oid: &[u8]| spki.windows(oid.len()).any(|w| w == oid);
+    if contains(ML_DSA_65_OID) {
+        Some("ML-DSA-65")
+    } else if contains(ED25519_OID) {
+        Some("Ed25519")
+    } else {
+        None
+    }
+}
+
+/// Key-exchange groups ant-quic's crypto provider offers, joined with `|`.
+///
+/// Mirrors ant-quic's provider selection, including its fallback to the
+/// classical aws-lc-rs defaults when the PQC provider cannot be built.
+pub fn offered_key_exchange() -> &'static str {
+    static OFFERED: OnceLock<String> = OnceLock::new();
+    OFFERED.get_or_init(|| {
+        let provider = create_crypto_provider(&PqcConfig::default())
+            .unwrap_or_else(|_| Arc::new(rustls::crypto::aws_lc_rs::default_provider()));
+        kx_group_label(&provider)
+    })
+}
+
+/// `|`-joined names of the key-exchange groups `provider` offers.
+fn kx_group_label(provider: &CryptoProvider) -> String {
+    provider
+        .kx_groups
+        .iter()
+        .map(|g| format!("{:?}", g.name()))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Algorithms of an established connection, or `None` if the peer's key
+/// cannot be read.
+pub fn connection_crypto(conn: &HighLevelConnection) -> Option<NegotiatedCrypto> {
+    let identity = conn.peer_identity()?;
+    let certs = identity.downcast_ref::<Vec<CertificateDer<'static>>>()?;
+    let signature = signature_scheme(certs.first()?.as_ref())?;
+    Some(NegotiatedCrypto::new(offered_key_exchange(), signature))
+}
+
+/// Algorithms of `node`'s current connection to `peer_id`, if any.
+pub fn peer_crypto(node: &Node, peer_id: &PeerId) -> Option<NegotiatedCrypto> {
+    let conn = node.inner_endpoint().get_quic_connection(peer_id).ok()??;
+    connection_crypto(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_scheme_from_spki_oid() {
+        let mut ml_dsa = vec![0x30, 0x82, 0x07, 0xb2, 0x30, 0x0b];
+        ml_dsa.extend_from_slice(ML_DSA_65_OID);
+        ml_dsa.extend_from_slice(&[0x03, 0x82, 0x07, 0xa1, 0x00]);
+        assert_eq!(signature_scheme(&ml_dsa), Some("ML-DSA-65"));
+
+        let mut ed25519 = vec![0x30, 0x2a, 0x30, 0x05];
+        ed25519.extend_from_slice(ED25519_OID);
+        ed25519.extend_from_slice(&[0x03, 0x21, 0x00]);
+        assert_eq!(signature_scheme(&ed25519), Some("Ed25519"));
+
+        assert_eq!(signature_scheme(&[0x30, 0x00]), None);
+    }
+
+    #[test]
+    fn test_offered_key_exchange_is_pqc() {
+        let kex = offered_key_exchange();
+        for group in kex.split('|') {
+            assert!(
+                NegotiatedCrypto::new(group, "ML-DSA-65").is_pqc(),
+                "{} is not an ML-KEM group",
+                group
+            );
+        }
+    }
+
+    #[test]
+    fn test_classical_fallback_reported_as_classical() {
+        let fallback = kx_group_label(&rustls::crypto::aws_lc_rs::default_provider());
+        assert!(fallback.split('|').any(|g| g == "X25519"));
+        assert!(!NegotiatedCrypto::new(&fallback, "Ed25519").is_pqc());
+    }
+
+    #[tokio::test]
+    async fn test_peer_crypto_read_from_live_connection() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let server = Arc::new(Node::bind("127.0.0.1:0".parse().unwrap()).await.unwrap());
+        let accept_node = Arc::clone(&server);
+        tokio::spawn(async move { while accept_node.accept().await.is_some() {} });
+
+        let client = Node::bind("127.0.0.1:0".parse().unwrap()).await.unwrap();
+        let conn = client
+            .connect_addr(server.local_addr().unwrap())
+            .await
+            .unwrap();
+
+        let crypto = peer_crypto(&client, &conn.peer_id).expect("connection crypto");
+        assert_eq!(crypto.signature, "ML-DSA-65");
+        assert!(crypto.is_pqc());
+        assert_eq!(peer_crypto(&client, &PeerId([7u8; 32])), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! Time from node start to its first NAT-verified connection.
+//!
+//! The join is split at the first discovered peer: discovery covers
+//! bootstrap and registry lookups, connection covers dialing and
+//! verifying that peer in both directions.
+
+use crate::clock::SharedClock;
+use crate::registry::JoinTime;
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Default)]
+struct Marks {
+    started: Option<Instant>,
+    discovered: Option<Instant>,
+    verified: Option<Instant>,
+}
+
+/// Records the join milestones once each; later calls are ignored.
+#[derive(Debug)]
+pub struct JoinTimer {
+    clock: SharedClock,
+    marks: Mutex<Marks>,
+}
+
+impl JoinTimer {
+    /// A timer that has not started yet.
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            marks: Mutex::new(Marks::default()),
+        }
+    }
+
+    fn mark(&self, f: impl FnOnce(&mut Marks, Instant)) {
+        let now = self.clock.now();
+        if let Ok(mut marks) = self.marks.lock() {
+            f(&mut marks, now);
+        }
+    }
+
+    /// The node started.
+    pub fn start(&self) {
+        self.mark(|m, now| {
+            m.started.get_or_insert(now);
+        });
+    }
+
+    /// The first dialable peer is known.
+    pub fn mark_discovered(&self) {
+        self.mark(|m, now| {
+            if m.started.is_some() {
+                m.discovered.get_or_insert(now);
+            }
+        });
+    }
+
+    /// A peer is NAT-verified. Also counts as discovery if that was missed,
+    /// e.g. when the first verified peer dialed us.
+    pub fn mark_verified(&self) {
+        self.mark(|m, now| {
+            if m.started.is_some() {
+                m.discovered.get_or_insert(now);
+                m.verified.get_or_insert(now);
+            }
+        });
+    }
+
+    /// The join time, once a peer has been verified.
+    pub fn join_time(&self) -> Option<JoinTime> {
+        let marks = self.marks.lock().ok()?;
+        let (started, discovered, verified) = (marks.started?, marks.discovered?, marks.verified?);
+        Some(JoinTime {
+            discovery_ms: discovered.duration_since(started).as_millis() as u64,
+            connection_ms: verified.duration_since(discovered).as_millis() as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_join_time_splits_discovery_and_connection() {
+        let clock = MockClock::new();
+        let timer = JoinTimer::new(Arc::new(clock.clone()));
+
+        // Nothing counts before start
+        timer.mark_verified();
+        timer.start();
+        assert_eq!(timer.join_time(), None);
+
+        clock.advance(Duration::from_millis(300));
+        timer.mark_discovered();
+        clock.advance(Duration::from_millis(3900));
+        timer.mark_discovered();
+        assert_eq!(timer.join_time(), None);
+        timer.mark_verified();
+        clock.advance(Duration::from_secs(10));
+        timer.mark_verified();
+
+        let join = timer.join_time().unwrap();
+        assert_eq!((join.discovery_ms, join.connection_ms), (300, 3900));
+        assert_eq!(join.total_ms(), 4200);
+
+        // An inbound peer verified before any discovery is all discovery time
+        let timer = JoinTimer::new(Arc::new(clock.clone()));
+        timer.start();
+        clock.advance(Duration::from_millis(500));
+        timer.mark_verified();
+        let join = timer.join_time().unwrap();
+        assert_eq!((join.discovery_ms, join.connection_ms), (500, 0));
+    }
+}
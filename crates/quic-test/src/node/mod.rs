@@ -4,12 +4,41 @@
 //! functionality for the network testing infrastructure.
 
 mod client;
+#[cfg(unix)]
+mod control;
+mod dial_limiter;
+mod handshake_crypto;
+mod join_time;
+mod nat_detect;
+mod nat_trace;
+mod peer_cache;
+mod peer_filter;
+mod resumption;
+mod self_test;
+mod telemetry;
 mod test_protocol;
 
-pub use client::{GlobalStats, TestNode, TestNodeConfig};
+pub(crate) use client::get_data_dir;
+pub use client::{DEREGISTER_TIMEOUT, GlobalStats, TestNode, TestNodeConfig};
+#[cfg(unix)]
+pub use control::ControlSocket;
+pub use dial_limiter::{DEFAULT_MAX_CONCURRENT_DIALS, DialLimiter, DialSlot};
+pub use handshake_crypto::{
+    connection_crypto, offered_key_exchange, peer_crypto, signature_scheme,
+};
+pub use join_time::JoinTimer;
+pub use nat_detect::{NAT_DETECT_TIMEOUT, NatDetection, classify_nat, detect_nat};
+pub use peer_cache::{
+    CachedPeerRecord, DEFAULT_PEER_CACHE_TTL, PEER_CACHE_FILE, PersistentPeerCache,
+};
+pub use peer_filter::{PeerFilter, PeerRule, load_peer_list};
+pub use self_test::{SELF_TEST_TIMEOUT, SelfTestPass, loopback_handshake_test, loopback_target};
+pub use telemetry::{TELEMETRY_INTERVAL, show_first_run_notice, telemetry_notice};
 pub use test_protocol::{
-    CanYouReachRequest, ConnectBackRequest, ConnectBackResponse, GossipMessage, PeerNetworkInfo,
-    RELAY_MAGIC, ReachResponse, RelayAckResponse, RelayCandidate, RelayDataRequest, RelayMessage,
-    RelayPunchMeNowRequest, RelayState, RelayedDataResponse, TestPacket, TestResult,
+    CanYouReachRequest, ConnectBackRequest, ConnectBackResponse, DEFAULT_TEST_BURST_SIZE,
+    DEFAULT_TEST_PACKET_SIZE, GossipMessage, MAX_TEST_PACKET_SIZE, PeerNetworkInfo, RELAY_MAGIC,
+    ReachResponse, RelayAckResponse, RelayCandidate, RelayDataRequest, RelayMessage,
+    RelayPunchMeNowRequest, RelayState, RelayedDataResponse, TEST_PACKET_HEADER_SIZE, TestPacket,
+    TestResult,
     connectivity_test::{ConnectivityMethod, ConnectivityTestPhase, PeerConnectivityResult},
 };
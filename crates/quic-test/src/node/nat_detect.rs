@@ -0,0 +1,254 @@
+//! One-shot NAT detection for `--detect-nat`.
+//!
+//! A fresh endpoint dials several bootstrap nodes from a single socket and
+//! collects the addresses they report via OBSERVED_ADDRESS frames. If an
+//! observed IP is one of our own interface addresses there is no NAT (the
+//! same check [`TestNode`](super::TestNode) uses). Otherwise the mapping
+//! behaviour decides: different external ports for the same socket mean an
+//! endpoint-dependent (symmetric) NAT, one port seen by several observers
+//! means an endpoint-independent (cone) NAT. Fewer than two agreeing
+//! observers is reported as undetermined rather than guessed.
+
+use crate::registry::NatType;
+use ant_quic::{Node, P2pEvent};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long `--detect-nat` waits for observers before classifying.
+pub const NAT_DETECT_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Extra time to wait for OBSERVED_ADDRESS frames after the last dial.
+const OBSERVATION_SETTLE: Duration = Duration::from_secs(1);
+
+/// Whether any external address is on one of our own interfaces.
+pub(crate) fn has_public_address(local: &[SocketAddr], external: &[SocketAddr]) -> bool {
+    external
+        .iter()
+        .any(|ext| local.iter().any(|l| l.ip() == ext.ip()))
+}
+
+/// Result of classifying the observed addresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NatDetection {
+    /// Best classification; `Unknown` when not `confident`.
+    pub nat_type: NatType,
+    /// Whether the observations were enough to classify.
+    pub confident: bool,
+    /// Local addresses of the probe endpoint.
+    pub local: Vec<SocketAddr>,
+    /// Distinct external addresses reported by observers.
+    pub observed: Vec<SocketAddr>,
+    /// Observers we completed a handshake with.
+    pub observers: usize,
+    /// Human-readable steps that led to the classification.
+    pub reasoning: Vec<String>,
+}
+
+/// Classify the NAT in front of a socket from what observers reported.
+///
+/// `observers` is the number of peers that completed a handshake; it
+/// matters because agreeing observers only report one address.
+pub fn classify_nat(
+    local: &[SocketAddr],
+    observed: &[SocketAddr],
+    observers: usize,
+) -> NatDetection {
+    let observed: Vec<SocketAddr> = observed
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut reasoning = Vec::new();
+    let result = |nat_type, confident, reasoning| NatDetection {
+        nat_type,
+        confident,
+        local: local.to_vec(),
+        observed: observed.clone(),
+        observers,
+        reasoning,
+    };
+
+    if observed.is_empty() {
+        reasoning.push(format!(
+            "{} observer(s) reached, none reported an OBSERVED_ADDRESS",
+            observers
+        ));
+        return result(NatType::Unknown, false, reasoning);
+    }
+
+    if has_public_address(local, &observed) {
+        reasoning.push("External IP matches a local interface address".to_string());
+        return result(NatType::None, true, reasoning);
+    }
+
+    for addr in &observed {
+        let local_port = local
+            .iter()
+            .find(|l| l.is_ipv4() == addr.is_ipv4())
+            .map(|l| l.port());
+        reasoning.push(match local_port {
+            Some(port) if port == addr.port() => {
+                format!("External {} keeps local port {}", addr, port)
+            }
+            Some(port) => format!("External {} maps local port {}", addr, port),
+            None => format!("External {} observed", addr),
+        });
+    }
+
+    // Observations from one socket with differing ports per family
+    for v4 in [true, false] {
+        let ports: BTreeSet<u16> = observed
+            .iter()
+            .filter(|a| a.is_ipv4() == v4)
+            .map(SocketAddr::port)
+            .collect();
+        if ports.len() > 1 {
+            reasoning.push(format!(
+                "Observers saw {} different external ports for one socket: \
+                 mapping is endpoint-dependent",
+                ports.len()
+            ));
+            return result(NatType::Symmetric, true, reasoning);
+        }
+    }
+
+    if observers < 2 {
+        reasoning
+            .push("Only one observer reached; mapping behaviour cannot be compared".to_string());
+        return result(NatType::Unknown, false, reasoning);
+    }
+
+    reasoning.push(format!(
+        "{} observers agree on the external port: mapping is endpoint-independent",
+        observers
+    ));
+    reasoning
+        .push("Filtering was not probed; reporting the most restrictive cone type".to_string());
+    result(NatType::PortRestricted, true, reasoning)
+}
+
+impl fmt::Display for NatDetection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.confident {
+            writeln!(f, "NAT type: {:?}", self.nat_type)?;
+        } else {
+            writeln!(f, "NAT type: could not determine")?;
+        }
+        for addr in &self.local {
+            writeln!(f, "  local:    {}", addr)?;
+        }
+        for addr in &self.observed {
+            writeln!(f, "  external: {}", addr)?;
+        }
+        for line in &self.reasoning {
+            writeln!(f, "  - {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bind `bind_addr`, dial `observers` and classify what they report.
+///
+/// Returns after every dial finished (plus a short settle) or `timeout`.
+pub async fn detect_nat(
+    bind_addr: SocketAddr,
+    observers: &[SocketAddr],
+    timeout: Duration,
+) -> anyhow::Result<NatDetection> {
+    let deadline = Instant::now() + timeout;
+    let node = Arc::new(
+        Node::bind(bind_addr)
+            .await
+            .map_err(|e| anyhow::anyhow!("could not bind {}: {}", bind_addr, e))?,
+    );
+    let mut events = node.subscribe_raw();
+
+    let local_port = node.local_addr().map(|a| a.port()).unwrap_or(0);
+    let (local_ipv4, local_ipv6) = super::client::detect_local_addresses(local_port);
+    let local: Vec<SocketAddr> = local_ipv4.into_iter().chain(local_ipv6).collect();
+
+    let mut dials = tokio::task::JoinSet::new();
+    for &addr in observers {
+        let node = Arc::clone(&node);
+        dials.spawn(async move { node.connect_addr(addr).await.is_ok() });
+    }
+
+    let mut observed = Vec::new();
+    let mut reached = 0;
+    let mut settle_until: Option<Instant> = None;
+    loop {
+        let until = settle_until.map_or(deadline, |s| s.min(deadline));
+        let sleep = tokio::time::sleep_until(until.into());
+        tokio::select! {
+            _ = sleep => break,
+            event = events.recv() => match event {
+                Ok(P2pEvent::ExternalAddressDiscovered { addr }) => observed.push(addr),
+                Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                Err(_) => break,
+            },
+            Some(dial) = dials.join_next(), if !dials.is_empty() => {
+                if matches!(dial, Ok(true)) {
+                    reached += 1;
+                }
+                if dials.is_empty() {
+                    settle_until = Some(Instant::now() + OBSERVATION_SETTLE);
+                }
+            }
+        }
+    }
+    dials.abort_all();
+    if let Some(addr) = node.external_addr() {
+        observed.push(addr);
+    }
+
+    Ok(classify_nat(&local, &observed, reached))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_classify_nat() {
+        let local = [addr("192.168.1.10:5000")];
+
+        let none = classify_nat(&local, &[], 3);
+        assert!(!none.confident);
+        assert_eq!(none.nat_type, NatType::Unknown);
+
+        let public = classify_nat(&[addr("203.0.113.5:5000")], &[addr("203.0.113.5:5000")], 1);
+        assert!(public.confident);
+        assert_eq!(public.nat_type, NatType::None);
+
+        let symmetric = classify_nat(
+            &local,
+            &[addr("198.51.100.1:40001"), addr("198.51.100.1:40002")],
+            2,
+        );
+        assert!(symmetric.confident);
+        assert_eq!(symmetric.nat_type, NatType::Symmetric);
+
+        // One observer can't distinguish cone from symmetric
+        let single = classify_nat(&local, &[addr("198.51.100.1:5000")], 1);
+        assert!(!single.confident);
+        assert!(single.to_string().contains("could not determine"));
+
+        let cone = classify_nat(
+            &local,
+            &[addr("198.51.100.1:5000"), addr("198.51.100.1:5000")],
+            3,
+        );
+        assert!(cone.confident);
+        assert_eq!(cone.nat_type, NatType::PortRestricted);
+        assert_eq!(cone.observed.len(), 1);
+        assert!(cone.reasoning[0].contains("keeps local port 5000"));
+    }
+}
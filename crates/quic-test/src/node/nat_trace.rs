@@ -0,0 +1,258 @@
+//! Tracing spans around NAT traversal phases.
+//!
+//! Each peer gets one `nat_phase` span per [`NatTraversalPhase`] it passes
+//! through, carrying the peer ID, coordinator and frame counts as fields.
+//! Frame counts are PUNCH_ME_NOW / ADD_ADDRESS frames on the coordinator's
+//! QUIC connection plus the relay PUNCH_ME_NOW messages this node exchanges.
+//! When a phase ends a `nat phase complete` event is logged inside the span
+//! with its duration, so grepping a headless node's logs for a peer ID gives
+//! the full traversal timeline.
+
+use crate::tui::{FrameDirection, NatTraversalPhase};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{Span, field};
+
+/// PUNCH_ME_NOW and ADD_ADDRESS frames sent and received on a connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct NatFrameCounts {
+    pub(crate) sent: u64,
+    pub(crate) received: u64,
+}
+
+impl NatFrameCounts {
+    /// Read the counts from a QUIC connection's frame statistics.
+    pub(crate) fn from_stats(stats: &ant_quic::ConnectionStats) -> Self {
+        Self {
+            sent: stats.frame_tx.punch_me_now + stats.frame_tx.add_address,
+            received: stats.frame_rx.punch_me_now + stats.frame_rx.add_address,
+        }
+    }
+}
+
+/// The phase a peer is currently in.
+struct OpenPhase {
+    phase: NatTraversalPhase,
+    span: Span,
+    started: Instant,
+    /// Relay PUNCH_ME_NOW messages counted with [`NatPhaseTracer::frame`].
+    relay_frames: NatFrameCounts,
+    /// Coordinator connection counts when the phase opened.
+    coordinator_baseline: Option<NatFrameCounts>,
+    /// Coordinator connection frames since the phase opened.
+    coordinator_frames: NatFrameCounts,
+}
+
+impl OpenPhase {
+    fn start(peer_id: &str, phase: NatTraversalPhase, coordinator_id: Option<&str>) -> Self {
+        let span = tracing::info_span!(
+            "nat_phase",
+            peer_id = %peer_id,
+            phase = ?phase,
+            coordinator_id = field::Empty,
+            frames_sent = 0u64,
+            frames_received = 0u64,
+            duration_ms = field::Empty,
+        );
+        if let Some(coordinator) = coordinator_id {
+            span.record("coordinator_id", coordinator);
+        }
+        Self {
+            phase,
+            span,
+            started: Instant::now(),
+            relay_frames: NatFrameCounts::default(),
+            coordinator_baseline: None,
+            coordinator_frames: NatFrameCounts::default(),
+        }
+    }
+
+    fn frames_sent(&self) -> u64 {
+        self.relay_frames.sent + self.coordinator_frames.sent
+    }
+
+    fn frames_received(&self) -> u64 {
+        self.relay_frames.received + self.coordinator_frames.received
+    }
+
+    fn record_frames(&self) {
+        self.span.record("frames_sent", self.frames_sent());
+        self.span.record("frames_received", self.frames_received());
+    }
+
+    /// Record the final counts and log the phase duration; the span closes
+    /// when `self` is dropped.
+    fn close(self, outcome: &str) {
+        let duration_ms = self.started.elapsed().as_millis() as u64;
+        self.span.record("duration_ms", duration_ms);
+        self.span.in_scope(|| {
+            tracing::info!(
+                phase = ?self.phase,
+                outcome,
+                duration_ms,
+                frames_sent = self.frames_sent(),
+                frames_received = self.frames_received(),
+                "nat phase complete"
+            );
+        });
+    }
+}
+
+/// Open NAT traversal phase spans, keyed by peer ID.
+#[derive(Default)]
+pub(crate) struct NatPhaseTracer {
+    open: Mutex<HashMap<String, OpenPhase>>,
+}
+
+impl NatPhaseTracer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move `peer_id` into `phase`, closing its previous phase span.
+    ///
+    /// Repeating the current phase keeps the open span. `Connected` and
+    /// `Relayed` end the traversal, so they close the span without opening
+    /// another.
+    pub(crate) fn transition(
+        &self,
+        peer_id: &str,
+        phase: NatTraversalPhase,
+        coordinator_id: Option<&str>,
+    ) {
+        let Ok(mut open) = self.open.lock() else {
+            return;
+        };
+        if let Some(current) = open.get(peer_id).filter(|c| c.phase == phase) {
+            if let Some(coordinator) = coordinator_id {
+                current.span.record("coordinator_id", coordinator);
+            }
+            return;
+        }
+        if let Some(previous) = open.remove(peer_id) {
+            previous.close(&format!("{:?}", phase).to_lowercase());
+        }
+        if !matches!(
+            phase,
+            NatTraversalPhase::Connected | NatTraversalPhase::Relayed
+        ) {
+            open.insert(
+                peer_id.to_string(),
+                OpenPhase::start(peer_id, phase, coordinator_id),
+            );
+        }
+    }
+
+    /// Count a relay PUNCH_ME_NOW message exchanged for `peer_id` against
+    /// its open phase.
+    pub(crate) fn frame(&self, peer_id: &str, direction: FrameDirection) {
+        let Ok(mut open) = self.open.lock() else {
+            return;
+        };
+        if let Some(current) = open.get_mut(peer_id) {
+            match direction {
+                FrameDirection::Sent => current.relay_frames.sent += 1,
+                FrameDirection::Received => current.relay_frames.received += 1,
+            }
+            current.record_frames();
+        }
+    }
+
+    /// Update `peer_id`'s open phase with the coordinator connection's
+    /// current frame counts.
+    ///
+    /// The first sample in a phase is its baseline; later samples count the
+    /// frames since then. Sample before a transition to settle the closing
+    /// phase and after it to set the new phase's baseline.
+    pub(crate) fn coordinator_frames(&self, peer_id: &str, counts: NatFrameCounts) {
+        let Ok(mut open) = self.open.lock() else {
+            return;
+        };
+        if let Some(current) = open.get_mut(peer_id) {
+            let baseline = *current.coordinator_baseline.get_or_insert(counts);
+            current.coordinator_frames = NatFrameCounts {
+                sent: counts.sent.saturating_sub(baseline.sent),
+                received: counts.received.saturating_sub(baseline.received),
+            };
+            current.record_frames();
+        }
+    }
+
+    /// Close `peer_id`'s open phase, e.g. when the attempt gives up.
+    pub(crate) fn finish(&self, peer_id: &str, outcome: &str) {
+        let Ok(mut open) = self.open.lock() else {
+            return;
+        };
+        if let Some(previous) = open.remove(peer_id) {
+            previous.close(outcome);
+        }
+    }
+
+    /// The phase `peer_id` is currently in, if traversal is under way.
+    #[cfg(test)]
+    pub(crate) fn current_phase(&self, peer_id: &str) -> Option<NatTraversalPhase> {
+        self.open
+            .lock()
+            .ok()
+            .and_then(|open| open.get(peer_id).map(|p| p.phase))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_spans_follow_transitions() {
+        let tracer = NatPhaseTracer::new();
+        tracer.transition("peer1", NatTraversalPhase::Discovering, None);
+        tracer.frame("peer1", FrameDirection::Sent);
+        tracer.transition("peer1", NatTraversalPhase::Coordinating, Some("coord1"));
+        tracer.transition("peer1", NatTraversalPhase::Coordinating, None);
+        tracer.frame("peer1", FrameDirection::Received);
+        assert_eq!(
+            tracer.current_phase("peer1"),
+            Some(NatTraversalPhase::Coordinating)
+        );
+        {
+            let open = tracer.open.lock().unwrap();
+            let current = &open["peer1"];
+            assert_eq!((current.frames_sent(), current.frames_received()), (0, 1));
+        }
+
+        tracer.transition("peer1", NatTraversalPhase::Punching, None);
+        tracer.transition("peer1", NatTraversalPhase::Connected, None);
+        assert_eq!(tracer.current_phase("peer1"), None);
+
+        tracer.transition("peer2", NatTraversalPhase::Punching, None);
+        tracer.finish("peer2", "timeout");
+        assert_eq!(tracer.current_phase("peer2"), None);
+    }
+
+    #[test]
+    fn test_coordinator_frames_count_from_phase_baseline() {
+        let tracer = NatPhaseTracer::new();
+        let counts = |sent, received| NatFrameCounts { sent, received };
+
+        // Frames sent before the phase opened are not counted
+        tracer.coordinator_frames("peer1", counts(4, 4));
+        tracer.transition("peer1", NatTraversalPhase::Coordinating, Some("coord1"));
+        tracer.coordinator_frames("peer1", counts(10, 7));
+        tracer.coordinator_frames("peer1", counts(12, 8));
+        tracer.frame("peer1", FrameDirection::Sent);
+        {
+            let open = tracer.open.lock().unwrap();
+            let current = &open["peer1"];
+            assert_eq!((current.frames_sent(), current.frames_received()), (3, 1));
+        }
+
+        // The next phase starts from the counts at its transition
+        tracer.transition("peer1", NatTraversalPhase::Punching, Some("coord1"));
+        tracer.coordinator_frames("peer1", counts(12, 8));
+        tracer.coordinator_frames("peer1", counts(13, 8));
+        let open = tracer.open.lock().unwrap();
+        let current = &open["peer1"];
+        assert_eq!((current.frames_sent(), current.frames_received()), (1, 0));
+    }
+}
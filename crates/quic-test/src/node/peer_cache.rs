@@ -0,0 +1,381 @@
+//! Persistent peer cache (`--peer-cache`).
+//!
+//! Peers we have dialed are remembered across restarts with their last
+//! known addresses, NAT type and success history, so a restarted node can
+//! dial them straight away instead of waiting for gossip and the registry.
+//! Entries not seen within the TTL are pruned on load and on save. A
+//! missing or unreadable file is not an error: the node logs it and starts
+//! with an empty cache.
+
+use crate::registry::{NatType, NodeCapabilities, PeerInfo};
+use crate::tui::CacheHealth;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// File name of the cache inside the data directory.
+pub const PEER_CACHE_FILE: &str = "known_peers.json";
+
+/// Default age after which an unseen peer is dropped.
+pub const DEFAULT_PEER_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Entries seen within this long count as fresh in [`CacheHealth`].
+const FRESH_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// What we remember about one peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedPeerRecord {
+    /// Last addresses the peer was dialed on.
+    pub addresses: Vec<SocketAddr>,
+    /// Last known NAT type.
+    pub nat_type: NatType,
+    /// Successful outbound connections.
+    pub successes: u32,
+    /// Failed outbound connections.
+    pub failures: u32,
+    /// Unix seconds of the last successful connection. Failures do not
+    /// update it, so unreachable peers age out.
+    pub last_seen: u64,
+}
+
+impl CachedPeerRecord {
+    /// Fraction of attempts that succeeded.
+    pub fn success_rate(&self) -> f64 {
+        let attempts = self.successes + self.failures;
+        if attempts == 0 {
+            0.0
+        } else {
+            f64::from(self.successes) / f64::from(attempts)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    peers: HashMap<String, CachedPeerRecord>,
+    /// Dials to peers that were in the cache before the dial.
+    hits: u64,
+    misses: u64,
+    last_updated: Option<Instant>,
+}
+
+/// On-disk cache of previously dialed peers, keyed by peer ID.
+#[derive(Debug)]
+pub struct PersistentPeerCache {
+    path: PathBuf,
+    ttl: Duration,
+    state: Mutex<CacheState>,
+}
+
+impl PersistentPeerCache {
+    /// Load the cache at `path`, dropping entries older than `ttl`.
+    ///
+    /// A missing file gives an empty cache; a corrupt one is logged and
+    /// replaced on the next [`save`](Self::save).
+    pub fn load(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        let path = path.into();
+        let peers = match std::fs::read(&path) {
+            Ok(bytes) => {
+                match serde_json::from_slice::<HashMap<String, CachedPeerRecord>>(&bytes) {
+                    Ok(peers) => peers,
+                    Err(e) => {
+                        warn!("Peer cache {:?} is corrupt ({}), starting fresh", path, e);
+                        HashMap::new()
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => {
+                warn!(
+                    "Could not read peer cache {:?} ({}), starting fresh",
+                    path, e
+                );
+                HashMap::new()
+            }
+        };
+        let cache = Self {
+            path,
+            ttl,
+            state: Mutex::new(CacheState {
+                peers,
+                ..CacheState::default()
+            }),
+        };
+        let pruned = cache.prune();
+        debug!(
+            "Loaded {} cached peers from {:?} ({} expired)",
+            cache.len(),
+            cache.path,
+            pruned
+        );
+        cache
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Where the cache is stored.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of cached peers.
+    pub fn len(&self) -> usize {
+        self.state().peers.len()
+    }
+
+    /// Whether no peers are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `peer_id` is cached.
+    pub fn contains(&self, peer_id: &str) -> bool {
+        self.state().peers.contains_key(peer_id)
+    }
+
+    /// Drop entries not seen within the TTL. Returns how many were removed.
+    pub fn prune(&self) -> usize {
+        let cutoff = unix_now().saturating_sub(self.ttl.as_secs());
+        let mut state = self.state();
+        let before = state.peers.len();
+        state.peers.retain(|_, record| record.last_seen >= cutoff);
+        before - state.peers.len()
+    }
+
+    /// Record a successful outbound connection to `peer`.
+    pub fn record_success(&self, peer: &PeerInfo) {
+        let now = unix_now();
+        let mut state = self.state();
+        let hit = state.peers.contains_key(&peer.peer_id);
+        let record = state
+            .peers
+            .entry(peer.peer_id.clone())
+            .or_insert_with(|| CachedPeerRecord {
+                addresses: Vec::new(),
+                nat_type: NatType::Unknown,
+                successes: 0,
+                failures: 0,
+                last_seen: now,
+            });
+        if !peer.addresses.is_empty() {
+            record.addresses = peer.addresses.clone();
+        }
+        if peer.nat_type != NatType::Unknown {
+            record.nat_type = peer.nat_type;
+        }
+        record.successes = record.successes.saturating_add(1);
+        record.last_seen = now;
+        if hit {
+            state.hits += 1;
+        }
+        state.last_updated = Some(Instant::now());
+    }
+
+    /// Record a failed outbound connection to `peer_id`.
+    ///
+    /// Only peers already in the cache are updated: a peer we never
+    /// reached is not worth remembering.
+    pub fn record_failure(&self, peer_id: &str) {
+        let mut state = self.state();
+        let Some(record) = state.peers.get_mut(peer_id) else {
+            return;
+        };
+        record.failures = record.failures.saturating_add(1);
+        state.misses += 1;
+        state.last_updated = Some(Instant::now());
+    }
+
+    /// Cached peers as dial candidates, most reliable first.
+    pub fn seed_peers(&self) -> Vec<PeerInfo> {
+        let state = self.state();
+        let mut records: Vec<(&String, &CachedPeerRecord)> = state
+            .peers
+            .iter()
+            .filter(|(_, record)| !record.addresses.is_empty())
+            .collect();
+        records.sort_by(|a, b| b.1.success_rate().total_cmp(&a.1.success_rate()));
+        records
+            .into_iter()
+            .map(|(peer_id, record)| cached_candidate(peer_id, record))
+            .collect()
+    }
+
+    /// Write the cache to disk, pruning expired entries first.
+    pub fn save(&self) -> std::io::Result<()> {
+        self.prune();
+        let json = serde_json::to_vec_pretty(&self.state().peers)?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write then rename so a crash mid-save leaves the old cache intact
+        let partial = self.path.with_extension("json.partial");
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, &self.path)
+    }
+
+    /// Summary for the TUI cache panel.
+    pub fn health(&self) -> CacheHealth {
+        let now = unix_now();
+        let state = self.state();
+        let records = state.peers.values();
+        let total_peers = state.peers.len();
+        let valid_peers = records.clone().filter(|r| r.successes > 0).count();
+        let public_peers = records
+            .clone()
+            .filter(|r| r.nat_type == NatType::None)
+            .count();
+        let fresh_peers = records
+            .clone()
+            .filter(|r| now.saturating_sub(r.last_seen) <= FRESH_WINDOW.as_secs())
+            .count();
+        let oldest = records.clone().map(|r| r.last_seen).min().unwrap_or(now);
+        let mean_rate = |filter: &dyn Fn(&CachedPeerRecord) -> bool| -> f32 {
+            let rates: Vec<f64> = state
+                .peers
+                .values()
+                .filter(|r| filter(r))
+                .map(CachedPeerRecord::success_rate)
+                .collect();
+            if rates.is_empty() {
+                0.0
+            } else {
+                (rates.iter().sum::<f64>() / rates.len() as f64) as f32
+            }
+        };
+
+        CacheHealth {
+            total_peers,
+            valid_peers,
+            public_peers,
+            average_quality: mean_rate(&|_| true),
+            cache_age: Duration::from_secs(now.saturating_sub(oldest)),
+            last_updated: state.last_updated,
+            cache_hits: state.hits,
+            cache_misses: state.misses,
+            fresh_peers,
+            stale_peers: total_peers - fresh_peers,
+            private_peers: total_peers - public_peers,
+            public_quality: mean_rate(&|r| r.nat_type == NatType::None),
+            private_quality: mean_rate(&|r| r.nat_type != NatType::None),
+        }
+    }
+}
+
+/// Dial candidate for a cached peer.
+///
+/// Cached peers are not known to be online, so they are marked inactive
+/// and a failed dial is not counted as a connectivity failure.
+fn cached_candidate(peer_id: &str, record: &CachedPeerRecord) -> PeerInfo {
+    PeerInfo {
+        peer_id: peer_id.to_string(),
+        addresses: record.addresses.clone(),
+        nat_type: record.nat_type,
+        country_code: None,
+        latitude: 0.0,
+        longitude: 0.0,
+        location_estimated: false,
+        last_seen: record.last_seen,
+        connection_success_rate: record.success_rate(),
+        capabilities: NodeCapabilities {
+            pqc: true,
+            ipv4: record.addresses.iter().any(|a| a.is_ipv4()),
+            ipv6: record.addresses.iter().any(|a| a.is_ipv6()),
+            nat_traversal: true,
+            relay: false,
+        },
+        version: String::from("cached"),
+        is_active: false,
+        status: Default::default(),
+        bytes_sent: 0,
+        bytes_received: 0,
+        connected_peers: 0,
+        gossip_stats: None,
+        full_mesh_probes: None,
+        last_error: None,
+        time_to_first_connection: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(peer_id: &str, addr: &str, nat_type: NatType) -> PeerInfo {
+        PeerInfo {
+            nat_type,
+            ..cached_candidate(
+                peer_id,
+                &CachedPeerRecord {
+                    addresses: vec![addr.parse().unwrap()],
+                    nat_type,
+                    successes: 0,
+                    failures: 0,
+                    last_seen: 0,
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_cache_survives_restart_and_prunes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(PEER_CACHE_FILE);
+        let ttl = Duration::from_secs(3600);
+
+        let cache = PersistentPeerCache::load(&path, ttl);
+        assert!(cache.is_empty());
+        cache.record_success(&peer("a", "198.51.100.1:9000", NatType::None));
+        cache.record_success(&peer("b", "198.51.100.2:9000", NatType::Symmetric));
+        cache.record_failure("b");
+        // Never reached, so not remembered
+        cache.record_failure("c");
+        cache.save().expect("save");
+
+        let reloaded = PersistentPeerCache::load(&path, ttl);
+        assert_eq!(reloaded.len(), 2);
+        let seeds = reloaded.seed_peers();
+        assert_eq!(seeds[0].peer_id, "a");
+        assert_eq!(seeds[1].nat_type, NatType::Symmetric);
+        assert!(!seeds[0].is_active);
+
+        reloaded.record_success(&seeds[0]);
+        let health = reloaded.health();
+        assert_eq!(health.total_peers, 2);
+        assert_eq!(health.public_peers, 1);
+        assert_eq!(health.cache_hits, 1);
+
+        // Entries older than the TTL are dropped on load
+        reloaded.state().peers.get_mut("b").unwrap().last_seen = 0;
+        reloaded.save().expect("save");
+        assert_eq!(PersistentPeerCache::load(&path, ttl).len(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_cache_starts_fresh() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(PEER_CACHE_FILE);
+        std::fs::write(&path, b"{not json").unwrap();
+
+        let cache = PersistentPeerCache::load(&path, DEFAULT_PEER_CACHE_TTL);
+        assert!(cache.is_empty());
+        cache.record_success(&peer("a", "198.51.100.1:9000", NatType::None));
+        cache.save().expect("corrupt file is overwritten");
+        assert_eq!(
+            PersistentPeerCache::load(&path, DEFAULT_PEER_CACHE_TTL).len(),
+            1
+        );
+    }
+}
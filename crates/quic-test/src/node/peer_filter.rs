@@ -0,0 +1,327 @@
+//! Peer filtering for controlled testing.
+//!
+//! A blacklist keeps the node from dialing known-bad peers. An allowlist, when
+//! configured, restricts dialing to the listed peers only (controlled-lab
+//! testing). Entries are either peer IDs (hex) or address ranges in CIDR
+//! notation (`10.0.0.0/8`, `2001:db8::/32`); a bare IP address matches only
+//! itself.
+
+use crate::registry::PeerInfo;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A single blacklist or allowlist entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerRule {
+    /// Match a peer by its (lowercase hex) ID.
+    PeerId(String),
+    /// Match any address inside `network/prefix_len`.
+    Cidr { network: IpAddr, prefix_len: u8 },
+}
+
+impl PeerRule {
+    /// True if this rule matches the given peer ID.
+    pub fn matches_peer_id(&self, peer_id: &str) -> bool {
+        match self {
+            PeerRule::PeerId(id) => id.eq_ignore_ascii_case(peer_id.trim()),
+            PeerRule::Cidr { .. } => false,
+        }
+    }
+
+    /// True if this rule matches the given IP address.
+    pub fn matches_ip(&self, ip: IpAddr) -> bool {
+        let PeerRule::Cidr {
+            network,
+            prefix_len,
+        } = self
+        else {
+            return false;
+        };
+        match (network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                prefix_matches(&net.octets(), &addr.octets(), *prefix_len)
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                prefix_matches(&net.octets(), &addr.octets(), *prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for PeerRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some((network, prefix)) = s.split_once('/') {
+            let network: IpAddr = network
+                .parse()
+                .map_err(|_| format!("Invalid CIDR network address: {}", s))?;
+            let max_len = if network.is_ipv4() { 32 } else { 128 };
+            let prefix_len: u8 = prefix
+                .parse()
+                .ok()
+                .filter(|len| *len <= max_len)
+                .ok_or_else(|| format!("Invalid CIDR prefix length: {}", s))?;
+            return Ok(PeerRule::Cidr {
+                network,
+                prefix_len,
+            });
+        }
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            let prefix_len = if ip.is_ipv4() { 32 } else { 128 };
+            return Ok(PeerRule::Cidr {
+                network: ip,
+                prefix_len,
+            });
+        }
+        if s.is_empty() || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Not a peer ID or CIDR: {}", s));
+        }
+        Ok(PeerRule::PeerId(s.to_ascii_lowercase()))
+    }
+}
+
+/// Compare the first `prefix_len` bits of two addresses of the same family.
+fn prefix_matches(network: &[u8], addr: &[u8], prefix_len: u8) -> bool {
+    let full_bytes = usize::from(prefix_len / 8);
+    let rem_bits = prefix_len % 8;
+    if network[..full_bytes] != addr[..full_bytes] {
+        return false;
+    }
+    if rem_bits == 0 {
+        return true;
+    }
+    let mask = 0xffu8 << (8 - rem_bits);
+    (network[full_bytes] & mask) == (addr[full_bytes] & mask)
+}
+
+/// Decides which discovered peers may be dialed.
+#[derive(Debug, Clone, Default)]
+pub struct PeerFilter {
+    blacklist: Vec<PeerRule>,
+    /// When set, only peers matching one of these rules may be dialed
+    allowlist: Option<Vec<PeerRule>>,
+}
+
+impl PeerFilter {
+    /// Build a filter from blacklist and optional allowlist entries.
+    pub fn new(blacklist: &[String], allowlist: Option<&[String]>) -> Result<Self, String> {
+        let blacklist = parse_rules(blacklist)?;
+        let allowlist = allowlist.map(parse_rules).transpose()?;
+        Ok(Self {
+            blacklist,
+            allowlist,
+        })
+    }
+
+    /// True when no rules are configured.
+    pub fn is_empty(&self) -> bool {
+        self.blacklist.is_empty() && self.allowlist.is_none()
+    }
+
+    /// True in allowlist-only mode.
+    pub fn is_allowlist_only(&self) -> bool {
+        self.allowlist.is_some()
+    }
+
+    /// True if the peer ID is blacklisted.
+    pub fn is_peer_blocked(&self, peer_id: &str) -> bool {
+        self.blacklist.iter().any(|r| r.matches_peer_id(peer_id))
+    }
+
+    /// True if the address falls inside a blacklisted range.
+    pub fn is_addr_blocked(&self, addr: &SocketAddr) -> bool {
+        self.blacklist.iter().any(|r| r.matches_ip(addr.ip()))
+    }
+
+    /// True if `addr` may be dialed, for the peer `peer_id` when known.
+    ///
+    /// Blacklisted peers and ranges are never dialed. In allowlist mode an
+    /// address is dialable if its peer is listed by ID or the address itself
+    /// falls inside an allowed range.
+    pub fn allows(&self, peer_id: Option<&str>, addr: &SocketAddr) -> bool {
+        if peer_id.is_some_and(|id| self.is_peer_blocked(id)) || self.is_addr_blocked(addr) {
+            return false;
+        }
+        match &self.allowlist {
+            None => true,
+            Some(allow) => {
+                peer_id.is_some_and(|id| allow.iter().any(|r| r.matches_peer_id(id)))
+                    || allow.iter().any(|r| r.matches_ip(addr.ip()))
+            }
+        }
+    }
+
+    /// The addresses of `peer_id` that may be dialed, in their original order.
+    ///
+    /// Every outbound dial goes through this, or through
+    /// [`may_dial_peer`](Self::may_dial_peer) for dials by ID alone, whatever
+    /// discovered the peer. `peer_id` is `None` when only an address is known,
+    /// e.g. a bootstrap node.
+    pub fn dial_addrs(&self, peer_id: Option<&str>, addrs: &[SocketAddr]) -> Vec<SocketAddr> {
+        addrs
+            .iter()
+            .filter(|a| self.allows(peer_id, a))
+            .copied()
+            .collect()
+    }
+
+    /// True if `peer_id` may be dialed by ID alone (NAT traversal).
+    ///
+    /// In allowlist mode that needs the ID itself to be listed: an allowed
+    /// range says nothing about where NAT traversal will end up.
+    pub fn may_dial_peer(&self, peer_id: &str) -> bool {
+        !self.is_peer_blocked(peer_id)
+            && self
+                .allowlist
+                .as_ref()
+                .is_none_or(|allow| allow.iter().any(|r| r.matches_peer_id(peer_id)))
+    }
+
+    /// Filter discovered peers down to the set we may dial.
+    ///
+    /// Each peer keeps only the addresses [`allows`](Self::allows) accepts,
+    /// and peers left with no addresses are dropped.
+    pub fn dial_targets(&self, peers: impl IntoIterator<Item = PeerInfo>) -> Vec<PeerInfo> {
+        peers
+            .into_iter()
+            .filter_map(|mut p| {
+                p.addresses = self.dial_addrs(Some(&p.peer_id), &p.addresses);
+                (!p.addresses.is_empty()).then_some(p)
+            })
+            .collect()
+    }
+}
+
+fn parse_rules(entries: &[String]) -> Result<Vec<PeerRule>, String> {
+    entries.iter().map(|entry| entry.parse()).collect()
+}
+
+/// Load peer list entries from a file: one per line, `#` starts a comment.
+pub fn load_peer_list(path: &Path) -> anyhow::Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{NatType, NodeCapabilities};
+
+    fn peer(peer_id: &str, addrs: &[&str]) -> PeerInfo {
+        PeerInfo {
+            peer_id: peer_id.to_string(),
+            addresses: addrs.iter().map(|a| a.parse().unwrap()).collect(),
+            nat_type: NatType::Unknown,
+            country_code: None,
+            latitude: 0.0,
+            longitude: 0.0,
+            location_estimated: false,
+            last_seen: 0,
+            connection_success_rate: 0.5,
+            capabilities: NodeCapabilities::default(),
+            version: String::new(),
+            is_active: true,
+            status: Default::default(),
+            bytes_sent: 0,
+            bytes_received: 0,
+            connected_peers: 0,
+            gossip_stats: None,
+            full_mesh_probes: None,
+            last_error: None,
+            time_to_first_connection: None,
+        }
+    }
+
+    #[test]
+    fn test_blacklisted_peer_id_excluded() {
+        let filter = PeerFilter::new(&["AAAA1111".to_string()], None).unwrap();
+        let targets = filter.dial_targets(vec![
+            peer("aaaa1111", &["1.2.3.4:9000"]),
+            peer("bbbb2222", &["5.6.7.8:9000"]),
+        ]);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].peer_id, "bbbb2222");
+    }
+
+    #[test]
+    fn test_cidr_excludes_addresses_in_range() {
+        let filter = PeerFilter::new(
+            &["10.0.0.0/8".to_string(), "2001:db8::/32".to_string()],
+            None,
+        )
+        .unwrap();
+        assert!(filter.is_addr_blocked(&"10.20.30.40:9000".parse().unwrap()));
+        assert!(filter.is_addr_blocked(&"[2001:db8::1]:9000".parse().unwrap()));
+        assert!(!filter.is_addr_blocked(&"11.0.0.1:9000".parse().unwrap()));
+
+        let targets = filter.dial_targets(vec![
+            peer("aaaa", &["10.1.1.1:9000", "203.0.113.5:9000"]),
+            peer("bbbb", &["10.2.2.2:9000"]),
+        ]);
+        assert_eq!(targets.len(), 1);
+        assert_eq!(
+            targets[0].addresses,
+            vec!["203.0.113.5:9000".parse::<SocketAddr>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_allowlist_only_dials_listed_peers() {
+        let allow = vec!["aaaa".to_string(), "192.168.50.0/24".to_string()];
+        let filter = PeerFilter::new(&[], Some(&allow)).unwrap();
+        assert!(filter.is_allowlist_only());
+
+        let targets = filter.dial_targets(vec![
+            peer("aaaa", &["203.0.113.1:9000"]),
+            peer("bbbb", &["192.168.50.9:9000", "198.51.100.2:9000"]),
+            peer("cccc", &["198.51.100.3:9000"]),
+        ]);
+        let ids: Vec<&str> = targets.iter().map(|p| p.peer_id.as_str()).collect();
+        assert_eq!(ids, vec!["aaaa", "bbbb"]);
+        // Listed by range only: unlisted addresses are not dialed
+        assert_eq!(
+            targets[1].addresses,
+            vec!["192.168.50.9:9000".parse::<SocketAddr>().unwrap()]
+        );
+
+        // Blacklist still wins over the allowlist
+        let filter = PeerFilter::new(&["aaaa".to_string()], Some(&allow)).unwrap();
+        let targets = filter.dial_targets(vec![peer("aaaa", &["203.0.113.1:9000"])]);
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_rule_parsing() {
+        assert_eq!(
+            "192.168.1.7".parse::<PeerRule>(),
+            Ok(PeerRule::Cidr {
+                network: "192.168.1.7".parse().unwrap(),
+                prefix_len: 32
+            })
+        );
+        assert!("10.0.0.0/33".parse::<PeerRule>().is_err());
+        assert!("not-a-peer".parse::<PeerRule>().is_err());
+
+        let rule: PeerRule = "172.16.0.0/12".parse().unwrap();
+        assert!(rule.matches_ip("172.31.255.255".parse().unwrap()));
+        assert!(!rule.matches_ip("172.32.0.0".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_load_peer_list_skips_comments() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("blacklist.txt");
+        std::fs::write(&path, "# bad peers\naaaa1111\n\n10.0.0.0/8  # lab net\n").unwrap();
+        let entries = load_peer_list(&path).unwrap();
+        assert_eq!(entries, vec!["aaaa1111", "10.0.0.0/8"]);
+    }
+}
@@ -0,0 +1,125 @@
+//! 0-RTT resumption tracking for reconnecting peers.
+//!
+//! ant-quic hands clients a NEW_TOKEN after each handshake so their next
+//! connection to the same peer can skip address validation. Without one,
+//! the server's post-quantum handshake flight exceeds the 3x
+//! anti-amplification limit and costs an extra round trip; with one, the
+//! reconnect completes within a single RTT. A reconnect therefore counts as
+//! 0-RTT when a token was held for the peer and the dial finished within
+//! [`ZERO_RTT_DIAL_FACTOR`] RTTs. First connections can never be 0-RTT and
+//! are not counted.
+//!
+//! ant-quic dials internally and does not expose whether the server
+//! accepted 0-RTT, so the count is inferred from dial timing and the proof
+//! step built on it reports without gating the run.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Dials finishing within this many RTTs skipped the validation round trip.
+pub const ZERO_RTT_DIAL_FACTOR: f64 = 1.5;
+
+/// How a successful outbound dial went, read from its QUIC connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DialHandshake {
+    /// Time from starting the dial to the connection being established.
+    pub duration: Duration,
+    /// Smoothed RTT of the new connection.
+    pub rtt: Duration,
+    /// Whether the peer sent a NEW_TOKEN usable on the next reconnect.
+    pub token_received: bool,
+}
+
+impl DialHandshake {
+    /// Whether the handshake completed without an extra round trip.
+    fn within_one_rtt(&self) -> bool {
+        self.duration.as_secs_f64() <= self.rtt.as_secs_f64() * ZERO_RTT_DIAL_FACTOR
+    }
+}
+
+/// Tracks which peers we hold resumption tokens for, and how many
+/// reconnects to them used 0-RTT.
+#[derive(Debug, Default)]
+pub struct ResumptionTracker {
+    /// Peers that issued us a token on an earlier connection.
+    tickets: Mutex<HashSet<String>>,
+    resumable_reconnects: AtomicU64,
+    zero_rtt_reconnects: AtomicU64,
+}
+
+impl ResumptionTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successful outbound dial to `peer_id`.
+    ///
+    /// Returns whether it used 0-RTT. Only reconnects where a token existed
+    /// are counted; a token received on this connection is kept for the
+    /// next one.
+    pub fn record_dial(&self, peer_id: &str, handshake: &DialHandshake) -> bool {
+        let mut tickets = self.tickets.lock().unwrap_or_else(|e| e.into_inner());
+        let had_ticket = if handshake.token_received {
+            !tickets.insert(peer_id.to_string())
+        } else {
+            tickets.contains(peer_id)
+        };
+        drop(tickets);
+
+        if !had_ticket {
+            return false;
+        }
+        self.resumable_reconnects.fetch_add(1, Ordering::Relaxed);
+        let zero_rtt = handshake.within_one_rtt();
+        if zero_rtt {
+            self.zero_rtt_reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+        zero_rtt
+    }
+
+    /// Reconnects made while holding a token for the peer.
+    pub fn resumable_reconnects(&self) -> u64 {
+        self.resumable_reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Reconnects that used 0-RTT.
+    pub fn zero_rtt_reconnects(&self) -> u64 {
+        self.zero_rtt_reconnects.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dial(duration_ms: u64, token_received: bool) -> DialHandshake {
+        DialHandshake {
+            duration: Duration::from_millis(duration_ms),
+            rtt: Duration::from_millis(100),
+            token_received,
+        }
+    }
+
+    #[test]
+    fn test_only_reconnects_with_a_ticket_count() {
+        let tracker = ResumptionTracker::new();
+
+        // A fast first connection is still not 0-RTT
+        assert!(!tracker.record_dial("peer-a", &dial(90, false)));
+        assert!(!tracker.record_dial("peer-a", &dial(90, true)));
+        assert_eq!(tracker.resumable_reconnects(), 0);
+
+        // The token from the last connection makes this one resumable
+        assert!(tracker.record_dial("peer-a", &dial(120, true)));
+        assert!(!tracker.record_dial("peer-a", &dial(250, false)));
+        assert_eq!(tracker.resumable_reconnects(), 2);
+        assert_eq!(tracker.zero_rtt_reconnects(), 1);
+
+        // Tickets are per peer
+        assert!(!tracker.record_dial("peer-b", &dial(90, true)));
+        assert_eq!(tracker.resumable_reconnects(), 2);
+    }
+}
@@ -0,0 +1,143 @@
+//! Startup loopback self-test.
+//!
+//! Before the node starts trusting the network it checks its own QUIC stack:
+//! a throwaway probe endpoint dials the node's bound port over loopback,
+//! completes the PQC handshake and sends a test packet. If that fails the
+//! problem is local (bind, firewall, crypto provider), not the network.
+//!
+//! Nothing is echoed back: the node's receive path is owned by the gossip
+//! transport, so the handshake is the only reply the probe waits for.
+//!
+//! The node sees the probe as an ordinary inbound peer, so the caller gets
+//! the probe's peer ID back and must leave it out of its peer bookkeeping.
+
+use super::test_protocol::TestPacket;
+use ant_quic::{Node, PeerId};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+/// How long the loopback handshake and packet delivery may take.
+pub const SELF_TEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Address to dial for a node bound to `bound`.
+///
+/// Wildcard binds are reached over IPv4 loopback (`[::]` is dual-stack).
+pub fn loopback_target(bound: SocketAddr) -> SocketAddr {
+    if bound.ip().is_unspecified() {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), bound.port())
+    } else {
+        bound
+    }
+}
+
+/// A passed loopback self-test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestPass {
+    /// Time from binding the probe to sending the test packet.
+    pub elapsed: Duration,
+    /// Peer ID of the probe endpoint that dialed the node.
+    pub probe_peer_id: PeerId,
+}
+
+/// Handshake with `target` from a fresh probe endpoint and send it one test packet.
+pub async fn loopback_handshake_test(
+    target: SocketAddr,
+    timeout: Duration,
+) -> anyhow::Result<SelfTestPass> {
+    let start = Instant::now();
+    let probe_ip = if target.is_ipv4() {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    } else {
+        IpAddr::V6(Ipv6Addr::LOCALHOST)
+    };
+    // The probe is dropped rather than shut down: it never has more than
+    // this one connection, and a graceful shutdown can outlive the test.
+    let probe = Node::bind(SocketAddr::new(probe_ip, 0))
+        .await
+        .map_err(|e| anyhow::anyhow!("could not bind loopback probe: {}", e))?;
+
+    let exchange = async {
+        let conn = probe
+            .connect_addr(target)
+            .await
+            .map_err(|e| anyhow::anyhow!("handshake with {} failed: {}", target, e))?;
+        let packet = TestPacket::new_ping(probe.peer_id().0, 0);
+        probe
+            .send(&conn.peer_id, &packet.to_bytes()?)
+            .await
+            .map_err(|e| anyhow::anyhow!("sending test packet to {} failed: {}", target, e))?;
+        Ok::<(), anyhow::Error>(())
+    };
+
+    match tokio::time::timeout(timeout, exchange).await {
+        Ok(Ok(())) => Ok(SelfTestPass {
+            elapsed: start.elapsed(),
+            probe_peer_id: probe.peer_id(),
+        }),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(anyhow::anyhow!(
+            "handshake and test packet to {} did not complete within {:?}",
+            target,
+            timeout
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_loopback_target_for_wildcard_bind() {
+        assert_eq!(
+            loopback_target("[::]:9000".parse().unwrap()),
+            "127.0.0.1:9000".parse().unwrap()
+        );
+        assert_eq!(
+            loopback_target("10.0.0.5:9000".parse().unwrap()),
+            "10.0.0.5:9000".parse().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_self_test_passes_on_healthy_endpoint() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let node = Arc::new(Node::bind("127.0.0.1:0".parse().unwrap()).await.unwrap());
+        let (accepted_tx, mut accepted_rx) = tokio::sync::mpsc::unbounded_channel();
+        let accept_node = Arc::clone(&node);
+        tokio::spawn(async move {
+            while let Some(conn) = accept_node.accept().await {
+                let _ = accepted_tx.send(conn.peer_id);
+            }
+        });
+
+        let target = loopback_target(node.local_addr().unwrap());
+        let pass = loopback_handshake_test(target, SELF_TEST_TIMEOUT)
+            .await
+            .expect("self-test against a healthy endpoint");
+        assert!(pass.elapsed < SELF_TEST_TIMEOUT);
+        assert_ne!(pass.probe_peer_id, node.peer_id());
+
+        // The node sees the probe under the ID the caller is told to skip
+        let accepted = tokio::time::timeout(Duration::from_secs(2), accepted_rx.recv())
+            .await
+            .expect("endpoint saw the probe connection");
+        assert_eq!(accepted, Some(pass.probe_peer_id));
+    }
+
+    #[tokio::test]
+    async fn test_self_test_fails_when_endpoint_closed() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        // Reserve a port and release it so nothing is listening there
+        let closed = std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let err = loopback_handshake_test(closed, Duration::from_millis(500))
+            .await
+            .expect_err("nothing is listening");
+        assert!(err.to_string().contains(&closed.to_string()));
+    }
+}
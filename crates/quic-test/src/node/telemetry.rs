@@ -0,0 +1,107 @@
+//! Opt-in anonymized telemetry (`--telemetry <url>`).
+//!
+//! Nothing is uploaded unless a telemetry URL is configured. Each upload is a
+//! [`TelemetryRecord`](crate::registry::TelemetryRecord): NAT type, country
+//! and connection counts, never a peer ID or an address. The first time a
+//! URL is used the node prints a notice to stderr saying what is sent.
+
+use super::client::get_data_dir;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often a node with telemetry enabled uploads a record.
+pub const TELEMETRY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// File in the data directory recording the URL the notice was shown for.
+const NOTICE_MARKER: &str = "telemetry_notice_shown";
+
+/// Notice explaining what is uploaded to `url` and how to stop it.
+pub fn telemetry_notice(url: &str) -> String {
+    format!(
+        "NOTICE: anonymized telemetry is enabled (--telemetry).\n\
+         \x20 Every {} minutes this node sends to {}:\n\
+         \x20   - its detected NAT type\n\
+         \x20   - the country of its external address\n\
+         \x20   - connection attempt/success counts and the client version\n\
+         \x20 No peer ID and no IP address is sent. Run without --telemetry to send nothing.",
+        TELEMETRY_INTERVAL.as_secs() / 60,
+        url
+    )
+}
+
+/// Print [`telemetry_notice`] to stderr unless it was already shown for
+/// this `url` and data directory (`None` for the default one). Returns
+/// whether it was printed.
+pub fn show_first_run_notice(data_dir: Option<&PathBuf>, url: &str) -> bool {
+    let data_dir = get_data_dir(data_dir);
+    let marker = data_dir.join(NOTICE_MARKER);
+    if std::fs::read_to_string(&marker).is_ok_and(|shown| shown == url) {
+        return false;
+    }
+    eprintln!("{}", telemetry_notice(url));
+    if let Err(e) = std::fs::create_dir_all(&data_dir).and_then(|()| std::fs::write(&marker, url)) {
+        tracing::debug!("Could not record telemetry notice in {:?}: {}", marker, e);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{NatStats, NatType, TelemetryRecord};
+
+    #[test]
+    fn test_notice_shown_once_per_url() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let data_dir = dir.path().join("node");
+
+        assert!(show_first_run_notice(
+            Some(&data_dir),
+            "https://t.example/a"
+        ));
+        assert!(!show_first_run_notice(
+            Some(&data_dir),
+            "https://t.example/a"
+        ));
+        // A different endpoint is announced again
+        assert!(show_first_run_notice(
+            Some(&data_dir),
+            "https://t.example/b"
+        ));
+    }
+
+    #[test]
+    fn test_record_carries_no_identity() {
+        let stats = NatStats {
+            attempts: 10,
+            direct_success: 4,
+            hole_punch_success: 3,
+            relay_success: 1,
+            failures: 2,
+            ..Default::default()
+        };
+        let record = TelemetryRecord::new(NatType::None, Some("DE".to_string()), &stats);
+        let json = serde_json::to_value(&record).unwrap();
+        let mut fields: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        fields.sort_unstable();
+        assert_eq!(
+            fields,
+            [
+                "attempts",
+                "country_code",
+                "direct_success",
+                "failures",
+                "hole_punch_success",
+                "nat_type",
+                "relay_success",
+                "version",
+            ]
+        );
+        assert_eq!(json["attempts"], 10);
+    }
+}
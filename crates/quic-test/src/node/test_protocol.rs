@@ -1,7 +1,7 @@
 //! Test packet protocol for measuring connectivity.
 //!
-//! Implements a simple 5KB test packet exchange protocol to verify
-//! connectivity and measure round-trip times.
+//! Implements a simple test packet exchange protocol (5KB by default,
+//! configurable up to 64KB) to verify connectivity and measure round-trip times.
 //!
 //! Also includes relay discovery protocol for NAT traversal:
 //! - CAN_YOU_REACH: Ask a peer if they can connect to a target
@@ -18,6 +18,7 @@
 //! - WebRTC protocols: 0x20-0x2F
 //! - Test/NAT protocols: Uses gossip bulk stream type (0x02)
 
+use crate::gossip::{GossipDiscovery, decode_wire};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::net::SocketAddr;
@@ -26,9 +27,19 @@ use std::time::{Duration, Instant};
 // Unified transport types for protocol multiplexing
 use ant_quic::StreamType;
 
-/// Size of test packet payload (approximately 5KB total with headers).
+/// Payload size of connectivity test probes (approximately 5KB total with headers).
 pub const TEST_PAYLOAD_SIZE: usize = 5000;
 
+/// Fixed-size fields of a test packet: magic, type, sequence, timestamp,
+/// declared size, sender ID and checksum.
+pub const TEST_PACKET_HEADER_SIZE: usize = 4 + 1 + 8 + 8 + 4 + 32 + 32;
+
+/// Default total test packet size in bytes.
+pub const DEFAULT_TEST_PACKET_SIZE: usize = 5120;
+
+/// Largest allowed test packet; larger payloads span many QUIC datagrams.
+pub const MAX_TEST_PACKET_SIZE: usize = 64 * 1024;
+
 /// Magic bytes to identify test packets.
 pub const TEST_PACKET_MAGIC: [u8; 4] = *b"TEST";
 
@@ -42,7 +53,7 @@ pub enum PacketType {
     Pong = 1,
 }
 
-/// A 5KB test packet for connectivity verification.
+/// A test packet for connectivity verification (5KB unless configured).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestPacket {
     /// Magic bytes to identify test packets.
@@ -53,39 +64,48 @@ pub struct TestPacket {
     pub sequence: u64,
     /// Timestamp in nanoseconds since epoch.
     pub timestamp_ns: u64,
+    /// Total packet size the sender built, checked against the payload on receipt.
+    ///
+    /// Zero for packets from peers that predate the field; those are checked
+    /// by checksum only. Left out of the checksum so both versions agree on it.
+    #[serde(default)]
+    pub declared_size: u32,
     /// Sender's peer ID (32 bytes).
     pub sender_id: [u8; 32],
-    /// Random payload to reach ~5KB.
+    /// Random payload padding the packet to `declared_size`.
     pub payload: Vec<u8>,
     /// SHA-256 checksum of the packet contents.
     pub checksum: [u8; 32],
 }
 
 impl TestPacket {
-    /// Create a new ping packet.
+    /// Create a new ping packet of the default size.
     pub fn new_ping(sender_id: [u8; 32], sequence: u64) -> Self {
-        let mut packet = Self {
-            magic: TEST_PACKET_MAGIC,
-            packet_type: PacketType::Ping,
-            sequence,
-            timestamp_ns: current_timestamp_ns(),
-            sender_id,
-            payload: generate_random_payload(),
-            checksum: [0u8; 32],
-        };
-        packet.checksum = packet.calculate_checksum();
-        packet
+        Self::new_ping_sized(sender_id, sequence, DEFAULT_TEST_PACKET_SIZE)
     }
 
-    /// Create a pong response from a ping.
+    /// Create a new ping packet of `packet_size` total bytes.
+    ///
+    /// The size is clamped to `TEST_PACKET_HEADER_SIZE..=MAX_TEST_PACKET_SIZE`.
+    pub fn new_ping_sized(sender_id: [u8; 32], sequence: u64, packet_size: usize) -> Self {
+        Self::build(PacketType::Ping, sender_id, sequence, packet_size)
+    }
+
+    /// Create a pong response from a ping, echoing its size.
     pub fn create_pong(&self, sender_id: [u8; 32]) -> Self {
+        Self::build(PacketType::Pong, sender_id, self.sequence, self.size())
+    }
+
+    fn build(packet_type: PacketType, sender_id: [u8; 32], sequence: u64, size: usize) -> Self {
+        let size = size.clamp(TEST_PACKET_HEADER_SIZE, MAX_TEST_PACKET_SIZE);
         let mut packet = Self {
             magic: TEST_PACKET_MAGIC,
-            packet_type: PacketType::Pong,
-            sequence: self.sequence,
+            packet_type,
+            sequence,
             timestamp_ns: current_timestamp_ns(),
+            declared_size: size as u32,
             sender_id,
-            payload: generate_random_payload(),
+            payload: generate_random_payload(size - TEST_PACKET_HEADER_SIZE),
             checksum: [0u8; 32],
         };
         packet.checksum = packet.calculate_checksum();
@@ -111,8 +131,50 @@ impl TestPacket {
 
     /// Get the packet size in bytes.
     pub fn size(&self) -> usize {
-        // Approximate: 4 (magic) + 1 (type) + 8 (seq) + 8 (ts) + 32 (id) + payload + 32 (checksum)
-        4 + 1 + 8 + 8 + 32 + self.payload.len() + 32
+        TEST_PACKET_HEADER_SIZE + self.payload.len()
+    }
+
+    /// Validate a received packet end to end.
+    ///
+    /// Checks the magic, that the full payload arrived (the actual size must
+    /// match the declared size and the cap) and the checksum over every byte.
+    /// Legacy packets without a declared size skip the size comparison.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.magic != TEST_PACKET_MAGIC {
+            return Err("bad magic".to_string());
+        }
+        if self.size() > MAX_TEST_PACKET_SIZE {
+            return Err(format!(
+                "packet size {} exceeds maximum {}",
+                self.size(),
+                MAX_TEST_PACKET_SIZE
+            ));
+        }
+        let declared = self.declared_size as usize;
+        if declared == 0 {
+            return self.check_checksum();
+        }
+        if declared > MAX_TEST_PACKET_SIZE {
+            return Err(format!(
+                "declared size {} exceeds maximum {}",
+                declared, MAX_TEST_PACKET_SIZE
+            ));
+        }
+        if self.size() != declared {
+            return Err(format!(
+                "truncated packet: {} of {} bytes",
+                self.size(),
+                declared
+            ));
+        }
+        self.check_checksum()
+    }
+
+    fn check_checksum(&self) -> Result<(), String> {
+        if !self.verify_checksum() {
+            return Err("checksum mismatch".to_string());
+        }
+        Ok(())
     }
 
     /// Serialize to bytes for transmission.
@@ -126,14 +188,27 @@ impl TestPacket {
     }
 }
 
-/// Result of a test packet exchange.
+/// Default number of test packets sent per exchange.
+pub const DEFAULT_TEST_BURST_SIZE: usize = 10;
+
+/// Result of a test packet exchange (a burst of one or more packets).
+///
+/// RTT fields are `None` when no packet in the burst was answered.
 #[derive(Debug, Clone)]
 pub struct TestResult {
-    /// Sequence number of the test.
+    /// Sequence number of the first packet in the burst.
     pub sequence: u64,
-    /// Round-trip time.
-    pub rtt: Duration,
-    /// Whether the test was successful.
+    /// Mean round-trip time over the answered packets.
+    pub rtt: Option<Duration>,
+    /// Fastest round-trip time in the burst.
+    pub min_rtt: Option<Duration>,
+    /// Slowest round-trip time in the burst.
+    pub max_rtt: Option<Duration>,
+    /// RTT standard deviation in milliseconds.
+    pub jitter_ms: Option<f64>,
+    /// Percentage of packets in the burst that were lost (0-100).
+    pub loss_pct: f64,
+    /// Whether the test was successful (at least one packet answered).
     pub success: bool,
     /// Error message if failed.
     pub error: Option<String>,
@@ -142,34 +217,60 @@ pub struct TestResult {
 }
 
 impl TestResult {
-    /// Create a successful test result.
+    /// Create a successful single-packet test result.
     pub fn success(sequence: u64, rtt: Duration) -> Self {
-        Self {
-            sequence,
-            rtt,
-            success: true,
-            error: None,
-            timestamp: Instant::now(),
-        }
+        Self::from_burst(sequence, 1, &[rtt])
     }
 
     /// Create a failed test result.
     pub fn failure(sequence: u64, error: String) -> Self {
         Self {
-            sequence,
-            rtt: Duration::ZERO,
-            success: false,
             error: Some(error),
+            ..Self::from_burst(sequence, 1, &[])
+        }
+    }
+
+    /// Summarize a burst of `sent` packets of which `rtts` were answered.
+    ///
+    /// A burst with every packet lost reports 100% loss and no RTT figures.
+    pub fn from_burst(sequence: u64, sent: usize, rtts: &[Duration]) -> Self {
+        let sent = sent.max(rtts.len());
+        let loss_pct = if sent == 0 {
+            0.0
+        } else {
+            (sent - rtts.len()) as f64 * 100.0 / sent as f64
+        };
+        let (rtt, jitter_ms) = if rtts.is_empty() {
+            (None, None)
+        } else {
+            let mean = rtts.iter().sum::<Duration>() / rtts.len() as u32;
+            let mean_ms = mean.as_secs_f64() * 1000.0;
+            let variance = rtts
+                .iter()
+                .map(|r| (r.as_secs_f64() * 1000.0 - mean_ms).powi(2))
+                .sum::<f64>()
+                / rtts.len() as f64;
+            (Some(mean), Some(variance.sqrt()))
+        };
+        Self {
+            sequence,
+            rtt,
+            min_rtt: rtts.iter().min().copied(),
+            max_rtt: rtts.iter().max().copied(),
+            jitter_ms,
+            loss_pct,
+            success: !rtts.is_empty(),
+            error: None,
             timestamp: Instant::now(),
         }
     }
 }
 
-/// Generate random payload data.
-fn generate_random_payload() -> Vec<u8> {
+/// Generate `len` bytes of random payload data.
+fn generate_random_payload(len: usize) -> Vec<u8> {
     use rand::Rng;
     let mut rng = rand::thread_rng();
-    (0..TEST_PAYLOAD_SIZE).map(|_| rng.r#gen()).collect()
+    (0..len).map(|_| rng.r#gen()).collect()
 }
 
 /// Get current timestamp in nanoseconds.
@@ -922,6 +1023,15 @@ impl RelayState {
 /// Magic bytes to identify gossip protocol messages.
 pub const GOSSIP_MAGIC: [u8; 4] = *b"GOSP";
 
+/// Decode a [`GossipMessage`] sent as JSON or in the binary wire format.
+fn decode_gossip_message(bytes: &[u8]) -> Result<GossipMessage, serde_json::Error> {
+    match bytes.first() {
+        Some(b'{') | None => serde_json::from_slice(bytes),
+        Some(_) => decode_wire(bytes)
+            .ok_or_else(|| serde::de::Error::custom("Invalid binary gossip message")),
+    }
+}
+
 /// Gossip protocol message types.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GossipMessage {
@@ -982,6 +1092,10 @@ pub struct GossipPeerAnnouncement {
     pub timestamp_ms: u64,
     /// TTL - how many more hops this announcement should propagate (prevents infinite loops).
     pub ttl: u8,
+    /// Discovery topics the announced peer is subscribed to, when it
+    /// announces itself (empty when forwarded or from older nodes).
+    #[serde(default)]
+    pub topics: Vec<String>,
 }
 
 impl PeerListMessage {
@@ -1002,7 +1116,7 @@ impl PeerListMessage {
 
     /// Deserialize from bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
-        let msg: GossipMessage = serde_json::from_slice(bytes)?;
+        let msg = decode_gossip_message(bytes)?;
         match msg {
             GossipMessage::PeerList(list) => Ok(list),
             _ => Err(serde::de::Error::custom("Expected PeerList")),
@@ -1020,9 +1134,22 @@ impl GossipPeerAnnouncement {
             announcer_id,
             timestamp_ms: current_timestamp_ns() / 1_000_000,
             ttl,
+            topics: Vec::new(),
         }
     }
 
+    /// Advertise the announced peer's topic subscriptions.
+    pub fn with_topics(mut self, topics: Vec<String>) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    /// Serialize in `discovery`'s wire format (JSON, or binary with
+    /// `--gossip-binary`), recording the size in its metrics.
+    pub fn to_wire(&self, discovery: &GossipDiscovery) -> Vec<u8> {
+        discovery.encode_announcement(&GossipMessage::PeerAnnouncement(self.clone()))
+    }
+
     /// Serialize to bytes.
     pub fn to_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
         serde_json::to_vec(&GossipMessage::PeerAnnouncement(self.clone()))
@@ -1030,7 +1157,7 @@ impl GossipPeerAnnouncement {
 
     /// Deserialize from bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
-        let msg: GossipMessage = serde_json::from_slice(bytes)?;
+        let msg = decode_gossip_message(bytes)?;
         match msg {
             GossipMessage::PeerAnnouncement(announcement) => Ok(announcement),
             _ => Err(serde::de::Error::custom("Expected PeerAnnouncement")),
@@ -1048,6 +1175,7 @@ impl GossipPeerAnnouncement {
             announcer_id: self.announcer_id.clone(),
             timestamp_ms: self.timestamp_ms,
             ttl: self.ttl - 1,
+            topics: self.topics.clone(),
         })
     }
 }
@@ -1093,7 +1221,7 @@ impl PeerUpsert {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
-        let msg: GossipMessage = serde_json::from_slice(bytes)?;
+        let msg = decode_gossip_message(bytes)?;
         match msg {
             GossipMessage::PeerUpsert(upsert) => Ok(upsert),
             _ => Err(serde::de::Error::custom("Expected PeerUpsert")),
@@ -1155,7 +1283,7 @@ impl PeerTombstone {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
-        let msg: GossipMessage = serde_json::from_slice(bytes)?;
+        let msg = decode_gossip_message(bytes)?;
         match msg {
             GossipMessage::PeerTombstone(tombstone) => Ok(tombstone),
             _ => Err(serde::de::Error::custom("Expected PeerTombstone")),
@@ -1330,7 +1458,7 @@ impl DisconnectAndConnectBack {
 impl GossipMessage {
     /// Deserialize from bytes.
     pub fn from_bytes(data: &[u8]) -> Result<Self, serde_json::Error> {
-        serde_json::from_slice(data)
+        decode_gossip_message(data)
     }
 
     /// Check if bytes look like a gossip message (check magic).
@@ -1372,10 +1500,12 @@ pub mod connectivity_test {
         NatTraversalIpv4,
         /// NAT traversal via hole punching (IPv6)
         NatTraversalIpv6,
-        /// Relayed via MASQUE (IPv4)
+        /// Relayed through a peer (IPv4)
         RelayedIpv4,
-        /// Relayed via MASQUE (IPv6)
+        /// Relayed through a peer (IPv6)
         RelayedIpv6,
+        /// Tunnelled through a MASQUE CONNECT-UDP relay
+        Masque,
     }
 
     impl ConnectivityMethod {
@@ -1414,10 +1544,16 @@ pub mod connectivity_test {
         pub fn is_relayed(&self) -> bool {
             matches!(
                 self,
-                ConnectivityMethod::RelayedIpv4 | ConnectivityMethod::RelayedIpv6
+                ConnectivityMethod::RelayedIpv4
+                    | ConnectivityMethod::RelayedIpv6
+                    | ConnectivityMethod::Masque
             )
         }
 
+        pub fn is_masque(&self) -> bool {
+            matches!(self, ConnectivityMethod::Masque)
+        }
+
         pub fn display_name(&self) -> &'static str {
             match self {
                 ConnectivityMethod::DirectIpv4 => "Direct IPv4",
@@ -1426,6 +1562,7 @@ pub mod connectivity_test {
                 ConnectivityMethod::NatTraversalIpv6 => "NAT IPv6",
                 ConnectivityMethod::RelayedIpv4 => "Relay IPv4",
                 ConnectivityMethod::RelayedIpv6 => "Relay IPv6",
+                ConnectivityMethod::Masque => "MASQUE",
             }
         }
 
@@ -1453,6 +1590,7 @@ pub mod connectivity_test {
                 Self::NatTraversalIpv6 => (crate::registry::ConnectionMethod::HolePunched, true),
                 Self::RelayedIpv4 => (crate::registry::ConnectionMethod::Relayed, false),
                 Self::RelayedIpv6 => (crate::registry::ConnectionMethod::Relayed, true),
+                Self::Masque => (crate::registry::ConnectionMethod::Relayed, false),
             }
         }
     }
@@ -1504,6 +1642,8 @@ pub mod connectivity_test {
         pub inbound_ipv6_direct: Option<bool>,
         pub inbound_nat_traversal: Option<bool>,
         pub inbound_relay: Option<bool>,
+        #[serde(default)]
+        pub inbound_masque: Option<bool>,
         /// All inbound attempts with details
         pub inbound_attempts: Vec<ConnectionAttemptResult>,
 
@@ -1512,6 +1652,8 @@ pub mod connectivity_test {
         pub outbound_ipv6_direct: Option<bool>,
         pub outbound_nat_traversal: Option<bool>,
         pub outbound_relay: Option<bool>,
+        #[serde(default)]
+        pub outbound_masque: Option<bool>,
         /// All outbound attempts with details
         pub outbound_attempts: Vec<ConnectionAttemptResult>,
     }
@@ -1555,6 +1697,11 @@ pub mod connectivity_test {
                         self.inbound_relay = Some(success);
                     }
                 }
+                ConnectivityMethod::Masque => {
+                    if self.inbound_masque.is_none() || success {
+                        self.inbound_masque = Some(success);
+                    }
+                }
             }
         }
 
@@ -1588,6 +1735,11 @@ pub mod connectivity_test {
                         self.outbound_relay = Some(success);
                     }
                 }
+                ConnectivityMethod::Masque => {
+                    if self.outbound_masque.is_none() || success {
+                        self.outbound_masque = Some(success);
+                    }
+                }
             }
         }
 
@@ -1617,12 +1769,16 @@ pub mod connectivity_test {
 
         /// Overall inbound success (any method worked).
         pub fn inbound_success(&self) -> bool {
-            self.inbound_ipv4_success() || self.inbound_ipv6_success()
+            self.inbound_ipv4_success()
+                || self.inbound_ipv6_success()
+                || self.inbound_masque == Some(true)
         }
 
         /// Overall outbound success (any method worked).
         pub fn outbound_success(&self) -> bool {
-            self.outbound_ipv4_success() || self.outbound_ipv6_success()
+            self.outbound_ipv4_success()
+                || self.outbound_ipv6_success()
+                || self.outbound_masque == Some(true)
         }
     }
 
@@ -1690,7 +1846,7 @@ pub mod connectivity_test {
             sender_name: Option<String>,
             method: ConnectivityMethod,
         ) -> Self {
-            let payload = generate_random_payload();
+            let payload = generate_random_payload(TEST_PAYLOAD_SIZE);
             let checksum = {
                 let mut hasher = Sha256::new();
                 hasher.update(&payload);
@@ -1823,6 +1979,67 @@ mod tests {
         assert!(packet.size() < 6000);
     }
 
+    #[test]
+    fn test_burst_result_metrics() {
+        let rtts = [10, 20, 30, 20].map(Duration::from_millis);
+        let result = TestResult::from_burst(7, 5, &rtts);
+        assert!(result.success);
+        assert_eq!(result.rtt, Some(Duration::from_millis(20)));
+        assert_eq!(result.min_rtt, Some(Duration::from_millis(10)));
+        assert_eq!(result.max_rtt, Some(Duration::from_millis(30)));
+        assert!((result.jitter_ms.unwrap() - 50f64.sqrt()).abs() < 1e-9);
+        assert_eq!(result.loss_pct, 20.0);
+
+        let lost = TestResult::from_burst(7, 10, &[]);
+        assert!(!lost.success);
+        assert_eq!(lost.loss_pct, 100.0);
+        assert_eq!(lost.rtt, None);
+        assert_eq!(lost.min_rtt, None);
+        assert_eq!(lost.max_rtt, None);
+        assert_eq!(lost.jitter_ms, None);
+    }
+
+    #[test]
+    fn test_sized_packets_round_trip_and_validate() {
+        let sender_id = [7u8; 32];
+        for size in [TEST_PACKET_HEADER_SIZE, 1200, 9000, MAX_TEST_PACKET_SIZE] {
+            let ping = TestPacket::new_ping_sized(sender_id, 3, size);
+            assert_eq!(ping.size(), size);
+            let restored = TestPacket::from_bytes(&ping.to_bytes().unwrap()).unwrap();
+            assert_eq!(restored.validate(), Ok(()));
+            assert_eq!(ping.create_pong([8u8; 32]).size(), size);
+        }
+        assert_eq!(
+            TestPacket::new_ping_sized(sender_id, 0, 1 << 20).size(),
+            MAX_TEST_PACKET_SIZE
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_truncated_payload() {
+        let mut packet = TestPacket::new_ping_sized([1u8; 32], 0, 9000);
+        packet.payload.truncate(4000);
+        assert_eq!(
+            packet.validate(),
+            Err("truncated packet: 4089 of 9000 bytes".to_string())
+        );
+    }
+
+    #[test]
+    fn test_legacy_packet_without_declared_size_validates() {
+        let packet = TestPacket::new_ping_sized([5u8; 32], 9, 1200);
+        let mut json: serde_json::Value =
+            serde_json::from_slice(&packet.to_bytes().unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("declared_size");
+        let legacy = TestPacket::from_bytes(&serde_json::to_vec(&json).unwrap()).unwrap();
+        assert_eq!(legacy.declared_size, 0);
+        assert_eq!(legacy.validate(), Ok(()));
+
+        let mut corrupted = legacy;
+        corrupted.payload[0] ^= 0xff;
+        assert_eq!(corrupted.validate(), Err("checksum mismatch".to_string()));
+    }
+
     #[test]
     fn test_serialization() {
         let sender_id = [42u8; 32];
@@ -1834,4 +2051,120 @@ mod tests {
         assert_eq!(restored.sender_id, sender_id);
         assert!(restored.verify_checksum());
     }
+
+    #[test]
+    fn test_peer_announcement_topics_survive_forwarding() {
+        let peer = GossipPeerInfo {
+            peer_id: "ab".repeat(32),
+            addresses: vec!["203.0.113.7:9000".parse().unwrap()],
+            is_public: true,
+            is_connected: true,
+            last_seen_ms: 0,
+        };
+        let announcement = GossipPeerAnnouncement::new(peer, "ab".repeat(32), 2)
+            .with_topics(vec!["ant-quic/peers/v1".to_string()]);
+        let restored =
+            GossipPeerAnnouncement::from_bytes(&announcement.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.forward().unwrap().topics, announcement.topics);
+
+        // Announcements from older nodes carry no topics
+        let mut legacy =
+            serde_json::to_value(GossipMessage::PeerAnnouncement(announcement)).unwrap();
+        legacy["PeerAnnouncement"]
+            .as_object_mut()
+            .unwrap()
+            .remove("topics");
+        let legacy = GossipPeerAnnouncement::from_bytes(&serde_json::to_vec(&legacy).unwrap());
+        assert!(legacy.unwrap().topics.is_empty());
+    }
+
+    #[test]
+    fn test_announcements_use_the_configured_wire_format() {
+        use crate::gossip::{GossipConfig, WireFormat};
+        use std::sync::atomic::Ordering;
+
+        let peer = GossipPeerInfo {
+            peer_id: "ab".repeat(32),
+            addresses: vec!["203.0.113.7:9000".parse().unwrap()],
+            is_public: true,
+            is_connected: true,
+            last_seen_ms: 1_700_000_000_000,
+        };
+        let announcement = GossipPeerAnnouncement::new(peer, "cd".repeat(32), 8)
+            .with_topics(vec!["peers".to_string()]);
+
+        let mut sizes = Vec::new();
+        for wire_format in [WireFormat::Json, WireFormat::Binary] {
+            let (tx, _rx) = tokio::sync::mpsc::channel(10);
+            let config = GossipConfig {
+                wire_format,
+                ..GossipConfig::default()
+            };
+            let discovery =
+                GossipDiscovery::new("self".to_string(), vec![], true, true, false, config, tx);
+
+            let payload = announcement.to_wire(&discovery);
+            assert_eq!(
+                discovery
+                    .metrics()
+                    .announcement_bytes_encoded
+                    .load(Ordering::Relaxed),
+                payload.len() as u64
+            );
+
+            // Receivers decode either format without knowing the sender's setting
+            let decoded = GossipPeerAnnouncement::from_bytes(&payload).unwrap();
+            assert_eq!(decoded.peer.peer_id, announcement.peer.peer_id);
+            assert_eq!(decoded.topics, announcement.topics);
+            assert!(matches!(
+                GossipMessage::from_bytes(&payload),
+                Ok(GossipMessage::PeerAnnouncement(_))
+            ));
+            assert!(PeerListMessage::from_bytes(&payload).is_err());
+            sizes.push(payload.len());
+        }
+        assert!(sizes[1] < sizes[0]);
+    }
+
+    #[test]
+    fn test_latency_probe_payload_distinct_from_announcements() {
+        use crate::gossip::{LatencyProbe, deserialize_latency_probe, serialize_latency_probe};
+
+        let probe = LatencyProbe {
+            probe_id: "probe-1".to_string(),
+            origin_id: "proof-test-cli".to_string(),
+            sent_at_ms: 1_700_000_000_000,
+        };
+        let payload = serialize_latency_probe(&probe);
+        assert_eq!(
+            deserialize_latency_probe(&payload).unwrap().probe_id,
+            probe.probe_id
+        );
+        assert!(GossipPeerAnnouncement::from_bytes(&payload).is_err());
+
+        let peer = GossipPeerInfo {
+            peer_id: "ab".repeat(32),
+            addresses: vec![],
+            is_public: true,
+            is_connected: true,
+            last_seen_ms: 0,
+        };
+        let announcement = GossipPeerAnnouncement::new(peer, "ab".repeat(32), 8);
+        assert!(deserialize_latency_probe(&announcement.to_bytes().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_masque_recorded_separately_from_relay() {
+        use connectivity_test::{ConnectivityMethod, PeerConnectivityResult};
+
+        let mut result = PeerConnectivityResult::new("a1b2c3d4".to_string(), "a1".repeat(32));
+        result.record_outbound(ConnectivityMethod::RelayedIpv4, false, None, None);
+        result.record_outbound(ConnectivityMethod::Masque, true, Some(40), None);
+
+        assert_eq!(result.outbound_relay, Some(false));
+        assert_eq!(result.outbound_masque, Some(true));
+        assert!(result.outbound_success());
+        assert!(ConnectivityMethod::Masque.is_relayed());
+        assert_eq!(ConnectivityMethod::Masque.display_name(), "MASQUE");
+    }
 }
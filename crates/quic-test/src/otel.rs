@@ -0,0 +1,114 @@
+//! OpenTelemetry export of the node's tracing spans.
+//!
+//! The node already emits `tracing` spans for registration, discovery, each
+//! dial and each packet exchange, all carrying the remote `peer_id`. With
+//! `--otlp-endpoint` those spans are additionally exported over OTLP/HTTP to
+//! a collector (OpenTelemetry Collector, Jaeger, Tempo, ...), so a fleet of
+//! nodes can be traced end to end. Only built with the `otlp` feature.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Service name reported with every exported span.
+pub const OTLP_SERVICE_NAME: &str = "saorsa-quic-test";
+
+/// Path OTLP/HTTP collectors accept traces on.
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// Flushes and shuts down the exporter when dropped.
+#[derive(Debug)]
+pub struct OtlpGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("WARNING: flushing OTLP spans failed: {}", e);
+        }
+    }
+}
+
+/// Traces URL for a collector given as a base URL or a full traces URL.
+pub fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{}{}", endpoint, OTLP_TRACES_PATH)
+    }
+}
+
+/// Tracer provider batching spans to the OTLP/HTTP collector at `endpoint`.
+pub fn otlp_tracer_provider(endpoint: &str) -> anyhow::Result<SdkTracerProvider> {
+    let url = traces_url(endpoint);
+    // The blocking HTTP client must not be created on an async runtime thread
+    let exporter = std::thread::spawn(move || {
+        SpanExporter::builder()
+            .with_http()
+            .with_endpoint(url)
+            .build()
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("OTLP exporter setup panicked"))??;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(OTLP_SERVICE_NAME)
+                .build(),
+        )
+        .build())
+}
+
+/// `tracing` layer forwarding spans to `provider`.
+pub fn otel_layer<S>(provider: &SdkTracerProvider) -> OpenTelemetryLayer<S, SdkTracer>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(OTLP_SERVICE_NAME))
+}
+
+/// Install the global subscriber exporting spans to `endpoint`.
+///
+/// `log_to_stderr` also installs the usual formatted log output, for modes
+/// that log rather than draw the TUI.
+pub fn init_otlp_tracing(endpoint: &str, log_to_stderr: bool) -> anyhow::Result<OtlpGuard> {
+    let provider = otlp_tracer_provider(endpoint)?;
+    let fmt = log_to_stderr.then(tracing_subscriber::fmt::layer);
+    tracing_subscriber::registry()
+        .with(otel_layer(&provider))
+        .with(fmt)
+        .with(LevelFilter::INFO)
+        .try_init()?;
+    Ok(OtlpGuard { provider })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_url() {
+        assert_eq!(
+            traces_url("http://collector:4318"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://collector:4318/"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://collector:4318/v1/traces"),
+            "http://collector:4318/v1/traces"
+        );
+    }
+}
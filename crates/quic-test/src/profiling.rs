@@ -0,0 +1,106 @@
+//! Sampling CPU profiler for `--profile-flamegraph`.
+//!
+//! Samples every thread's stack for the whole run and writes an SVG
+//! flamegraph when the node exits, to show where CPU goes during heavy
+//! connection churn. Only built with the `flamegraph` feature on Unix.
+
+use anyhow::Context;
+use pprof::ProfilerGuard;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Stack samples taken per second.
+pub const PROFILE_FREQUENCY_HZ: i32 = 99;
+
+/// Libraries whose frames are not sampled; unwinding through them from a
+/// signal handler can deadlock.
+const PROFILE_BLOCKLIST: &[&str] = &["libc", "libgcc", "pthread", "vdso"];
+
+/// A running CPU profile that becomes a flamegraph at `output`.
+///
+/// Call [`finish`](Self::finish) to write it and see errors; dropping the
+/// profiler writes it too and only reports errors on stderr.
+pub struct FlamegraphProfiler {
+    guard: Option<ProfilerGuard<'static>>,
+    output: PathBuf,
+}
+
+impl FlamegraphProfiler {
+    /// Start sampling; the flamegraph will be written to `output`.
+    pub fn start(output: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let guard = pprof::ProfilerGuardBuilder::default()
+            .frequency(PROFILE_FREQUENCY_HZ)
+            .blocklist(PROFILE_BLOCKLIST)
+            .build()
+            .context("could not start the CPU profiler")?;
+        Ok(Self {
+            guard: Some(guard),
+            output: output.into(),
+        })
+    }
+
+    /// Where the flamegraph will be written.
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+
+    /// Stop sampling and write the flamegraph.
+    pub fn finish(mut self) -> anyhow::Result<PathBuf> {
+        self.write()?;
+        Ok(std::mem::take(&mut self.output))
+    }
+
+    fn write(&mut self) -> anyhow::Result<()> {
+        let Some(guard) = self.guard.take() else {
+            return Ok(());
+        };
+        let report = guard
+            .report()
+            .build()
+            .context("could not build the CPU profile")?;
+        drop(guard);
+        let file = File::create(&self.output)
+            .with_context(|| format!("could not create {}", self.output.display()))?;
+        report
+            .flamegraph(file)
+            .with_context(|| format!("could not write {}", self.output.display()))
+    }
+}
+
+impl Drop for FlamegraphProfiler {
+    fn drop(&mut self) {
+        if self.guard.is_none() {
+            return;
+        }
+        match self.write() {
+            Ok(()) => eprintln!("Flamegraph written to {}", self.output.display()),
+            Err(e) => eprintln!("WARNING: flamegraph not written: {:#}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_profiler_writes_flamegraph() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("flamegraph.svg");
+        let profiler = FlamegraphProfiler::start(&output).unwrap();
+        assert_eq!(profiler.output(), output);
+
+        // Burn CPU so there is something to sample
+        let start = Instant::now();
+        let mut acc = 0u64;
+        while start.elapsed() < Duration::from_millis(500) {
+            acc = std::hint::black_box(acc.wrapping_mul(31).wrapping_add(7));
+        }
+
+        let written = profiler.finish().unwrap();
+        assert_eq!(written, output);
+        let svg = std::fs::read_to_string(&output).unwrap();
+        assert!(svg.contains("<svg"), "not an SVG flamegraph");
+    }
+}
@@ -19,13 +19,19 @@
 //! 5. **Freshness**: All proofs have timestamps within acceptable window
 
 use crate::crdt_verification::{CrdtVerifier, CrdtVerifierConfig};
-use crate::debug_automation::{AutomatedDebugger, DebugReport, DebuggerConfig, LogEntry};
+use crate::debug_automation::{
+    AutomatedDebugger, ConnectionOutcome, DebugReport, DebuggerConfig, LogEntry,
+};
 use crate::gossip_verification::{GossipVerifier, GossipVerifierConfig};
+use crate::harness::IpMode;
 use crate::registry::{
-    CrdtConvergenceProof, CrdtType, DataProof, GossipProtocolProof, NetworkConnectivityProof,
-    ProofBasedTestReport, ProofType, SignedAttestation, TestAnomaly,
+    ConnectionFunnel, CrdtConvergenceProof, CrdtType, DataProof, EigenTrustConvergenceProof,
+    GossipProtocolProof, JoinTime, JoinTimeSummary, MatrixCoverage, NatType,
+    NetworkConnectivityProof, ProofBasedTestReport, ProofType, SignedAttestation, TechniqueAttempt,
+    TestAnomaly,
 };
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::time::{Duration, SystemTime};
 
@@ -197,6 +203,10 @@ pub struct ProofOrchestratorConfig {
     pub min_nodes: usize,
     /// Whether to require cross-validation.
     pub require_cross_validation: bool,
+    /// Address families a node's connectivity proof may rely on.
+    pub ip_mode: IpMode,
+    /// Largest EigenTrust score difference between nodes still counted as converged.
+    pub trust_epsilon: f64,
 }
 
 impl Default for ProofOrchestratorConfig {
@@ -210,6 +220,8 @@ impl Default for ProofOrchestratorConfig {
             debug_config: DebuggerConfig::default(),
             min_nodes: 2,
             require_cross_validation: true,
+            ip_mode: IpMode::DualStack,
+            trust_epsilon: 0.05,
         }
     }
 }
@@ -258,6 +270,164 @@ impl StepResult {
     }
 }
 
+/// Version of the [`OrchestratorReportJson`] schema.
+///
+/// Bumped whenever a field is removed, renamed or changes meaning, so CI
+/// parsers can detect breaking changes.
+pub const REPORT_SCHEMA_VERSION: u32 = 2;
+
+/// Machine-readable proof report for CI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrchestratorReportJson {
+    /// Schema version, see [`REPORT_SCHEMA_VERSION`].
+    pub schema_version: u32,
+    /// Session identifier.
+    pub session_id: String,
+    /// Unix timestamp (seconds) the test started.
+    pub started_at: u64,
+    /// Unix timestamp (seconds) the test completed.
+    pub completed_at: u64,
+    /// Overall pass/fail status.
+    pub passed: bool,
+    /// Minimum nodes the test required.
+    pub min_nodes: usize,
+    /// Peer pairs where only one direction connected.
+    #[serde(default)]
+    pub asymmetric_pairs: usize,
+    /// Outcome of each proof, in the order they ran.
+    pub proofs: Vec<ProofOutcomeJson>,
+    /// Sorted IDs of the nodes the run covered, or `None` when unknown
+    /// (no connectivity proof, or a version 1 report).
+    #[serde(default)]
+    pub nodes: Option<Vec<String>>,
+    /// Failure summary (if failed).
+    pub failure_summary: Option<String>,
+}
+
+impl OrchestratorReportJson {
+    /// Compare this report against an earlier `baseline` run.
+    pub fn diff(&self, baseline: &OrchestratorReportJson) -> ReportDiff {
+        let before: HashMap<&str, bool> = baseline
+            .proofs
+            .iter()
+            .map(|p| (p.proof.as_str(), p.passed))
+            .collect();
+        let mut newly_passed = Vec::new();
+        let mut newly_failed = Vec::new();
+        for proof in &self.proofs {
+            match (before.get(proof.proof.as_str()), proof.passed) {
+                (Some(false), true) => newly_passed.push(proof.proof.clone()),
+                (Some(true) | None, false) => newly_failed.push(proof.proof.clone()),
+                _ => {}
+            }
+        }
+
+        let mut diff = ReportDiff {
+            baseline_session: baseline.session_id.clone(),
+            newly_passed,
+            newly_failed,
+            ..Default::default()
+        };
+
+        // An empty list is as good as missing: older reports wrote one
+        // whenever the node set wasn't known
+        fn known(nodes: &Option<Vec<String>>) -> Option<BTreeSet<&String>> {
+            nodes
+                .as_ref()
+                .filter(|nodes| !nodes.is_empty())
+                .map(|nodes| nodes.iter().collect())
+        }
+        match (known(&self.nodes), known(&baseline.nodes)) {
+            (Some(current), Some(previous)) => {
+                diff.nodes_appeared = current
+                    .difference(&previous)
+                    .map(|n| n.to_string())
+                    .collect();
+                diff.nodes_disappeared = previous
+                    .difference(&current)
+                    .map(|n| n.to_string())
+                    .collect();
+                diff.disjoint_nodes = current.is_disjoint(&previous);
+            }
+            _ => diff.nodes_unknown = true,
+        }
+        diff
+    }
+}
+
+/// Differences between a proof run and a baseline run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportDiff {
+    /// Session ID of the baseline run.
+    pub baseline_session: String,
+    /// Proofs that failed in the baseline and pass now.
+    pub newly_passed: Vec<String>,
+    /// Proofs that fail now but passed in (or were absent from) the baseline.
+    pub newly_failed: Vec<String>,
+    /// Nodes present now but not in the baseline, sorted.
+    pub nodes_appeared: Vec<String>,
+    /// Nodes in the baseline that are missing now, sorted.
+    pub nodes_disappeared: Vec<String>,
+    /// Both runs covered nodes but none in common, so the runs are likely
+    /// from different networks and the diff says little.
+    pub disjoint_nodes: bool,
+    /// One of the runs has no node list, so node changes were not compared.
+    pub nodes_unknown: bool,
+}
+
+impl ReportDiff {
+    /// Whether anything changed relative to the baseline.
+    pub fn is_empty(&self) -> bool {
+        self.newly_passed.is_empty()
+            && self.newly_failed.is_empty()
+            && self.nodes_appeared.is_empty()
+            && self.nodes_disappeared.is_empty()
+    }
+}
+
+impl std::fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Diff vs baseline {}", self.baseline_session)?;
+        if self.disjoint_nodes {
+            writeln!(
+                f,
+                "  WARNING: no nodes in common with the baseline; is it from the same network?"
+            )?;
+        }
+        if self.nodes_unknown {
+            writeln!(f, "  Node changes unknown: a run has no node list")?;
+        }
+        if self.is_empty() {
+            return writeln!(f, "  No changes");
+        }
+        let lists = [
+            ("Newly passed", &self.newly_passed),
+            ("Newly failed", &self.newly_failed),
+            ("Nodes appeared", &self.nodes_appeared),
+            ("Nodes disappeared", &self.nodes_disappeared),
+        ];
+        for (label, items) in lists {
+            if !items.is_empty() {
+                writeln!(f, "  {} ({}): {}", label, items.len(), items.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a single proof in an [`OrchestratorReportJson`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofOutcomeJson {
+    /// Proof type (step name, e.g. `connectivity`, `crdt_convergence`).
+    pub proof: String,
+    /// Whether the proof passed.
+    pub passed: bool,
+    /// Human-readable details.
+    pub details: String,
+    /// Sorted IDs of the nodes that failed this proof.
+    pub failed_nodes: Vec<String>,
+}
+
 /// Complete test report from the orchestrator.
 #[derive(Debug, Clone)]
 pub struct OrchestratorReport {
@@ -269,6 +439,16 @@ pub struct OrchestratorReport {
     pub completed_at: SystemTime,
     /// Overall pass/fail status.
     pub passed: bool,
+    /// Minimum nodes the test required.
+    pub min_nodes: usize,
+    /// Peer pairs where only one direction connected.
+    pub asymmetric_pairs: usize,
+    /// How much of the ordered peer-pair matrix was tested.
+    pub matrix_coverage: MatrixCoverage,
+    /// How far connection attempts got before failing.
+    pub connection_funnel: ConnectionFunnel,
+    /// Network-wide time from node start to first verified connection.
+    pub join_times: Option<JoinTimeSummary>,
     /// Results from each step.
     pub step_results: Vec<StepResult>,
     /// Connectivity proof (if generated).
@@ -277,6 +457,8 @@ pub struct OrchestratorReport {
     pub gossip_proof: Option<GossipProtocolProof>,
     /// CRDT proof (if generated).
     pub crdt_proof: Option<CrdtConvergenceProof>,
+    /// EigenTrust proof (if any nodes reported trust vectors).
+    pub eigentrust_proof: Option<EigenTrustConvergenceProof>,
     /// Debug report (if debugging was triggered).
     pub debug_report: Option<DebugReport>,
     /// All anomalies detected across steps.
@@ -295,11 +477,93 @@ impl OrchestratorReport {
             connectivity: self.connectivity_proof.clone(),
             gossip: self.gossip_proof.clone(),
             crdt: self.crdt_proof.clone(),
+            eigentrust: self.eigentrust_proof.clone(),
             anomalies: self.all_anomalies.clone(),
+            matrix_coverage: Some(self.matrix_coverage),
+            join_times: self.join_times,
+            passed: self.passed,
+            failure_summary: self.failure_summary.clone(),
+        }
+    }
+
+    /// Number of nodes covered by the connectivity proof.
+    pub fn node_count(&self) -> usize {
+        self.connectivity_proof
+            .as_ref()
+            .map(|p| p.observed_peers.len())
+            .unwrap_or(0)
+    }
+
+    /// One-line summary: completion time, PASS/FAIL, node count and failed steps.
+    pub fn summary_line(&self) -> String {
+        let mut line = format!(
+            "{} {} nodes={}",
+            humantime_serde::re::humantime::format_rfc3339_seconds(self.completed_at),
+            if self.passed { "PASS" } else { "FAIL" },
+            self.node_count()
+        );
+        let failed: Vec<&str> = self
+            .step_results
+            .iter()
+            .filter(|s| !s.passed)
+            .map(|s| s.name.as_str())
+            .collect();
+        if !failed.is_empty() {
+            line.push_str(&format!(" failed={}", failed.join(",")));
+        }
+        line
+    }
+
+    /// Convert to the versioned JSON structure consumed by CI.
+    pub fn to_json(&self) -> OrchestratorReportJson {
+        let unix_secs = |t: SystemTime| {
+            t.duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        };
+        let proofs = self
+            .step_results
+            .iter()
+            .map(|step| ProofOutcomeJson {
+                proof: step.name.clone(),
+                passed: step.passed,
+                details: step.details.clone(),
+                failed_nodes: step
+                    .anomalies
+                    .iter()
+                    .flat_map(|a| a.nodes_involved.iter().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect(),
+            })
+            .collect();
+        let nodes = self.connectivity_proof.as_ref().map(|p| {
+            p.observed_peers
+                .iter()
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect()
+        });
+
+        OrchestratorReportJson {
+            schema_version: REPORT_SCHEMA_VERSION,
+            session_id: self.session_id.clone(),
+            started_at: unix_secs(self.started_at),
+            completed_at: unix_secs(self.completed_at),
             passed: self.passed,
+            min_nodes: self.min_nodes,
+            asymmetric_pairs: self.asymmetric_pairs,
+            proofs,
+            nodes,
             failure_summary: self.failure_summary.clone(),
         }
     }
+
+    /// Compare this report against an earlier `baseline` run.
+    pub fn diff(&self, baseline: &OrchestratorReport) -> ReportDiff {
+        self.to_json().diff(&baseline.to_json())
+    }
 }
 
 impl std::fmt::Display for OrchestratorReport {
@@ -312,6 +576,16 @@ impl std::fmt::Display for OrchestratorReport {
             "Status: {}",
             if self.passed { "PASSED" } else { "FAILED" }
         )?;
+        if self.asymmetric_pairs > 0 {
+            writeln!(f, "Asymmetric pairs: {}", self.asymmetric_pairs)?;
+        }
+        writeln!(f, "Matrix coverage: {}", self.matrix_coverage.summary())?;
+        if self.connection_funnel.attempted > 0 {
+            writeln!(f, "Connection funnel: {}", self.connection_funnel.summary())?;
+        }
+        if let Some(join_times) = &self.join_times {
+            writeln!(f, "Join time: {}", join_times.summary())?;
+        }
         writeln!(f)?;
 
         writeln!(f, "Step Results:")?;
@@ -343,6 +617,92 @@ impl std::fmt::Display for OrchestratorReport {
     }
 }
 
+/// Reports kept by [`ProofRunHistory`]; older runs only count in the totals.
+pub const MAX_RUN_HISTORY: usize = 100;
+
+/// Pass/fail history of repeated proof runs (watch mode).
+///
+/// The most recent [`MAX_RUN_HISTORY`] reports are kept; totals and failure
+/// reasons cover every run.
+#[derive(Debug, Clone, Default)]
+pub struct ProofRunHistory {
+    /// Most recent reports, oldest first.
+    pub reports: VecDeque<OrchestratorReport>,
+    total_runs: usize,
+    passed_runs: usize,
+    /// Failure reason (failed step name, or `run_error`) -> occurrences.
+    failure_reasons: HashMap<String, usize>,
+}
+
+impl ProofRunHistory {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a completed run.
+    pub fn record(&mut self, report: OrchestratorReport) {
+        self.total_runs += 1;
+        if report.passed {
+            self.passed_runs += 1;
+        }
+        for step in report.step_results.iter().filter(|s| !s.passed) {
+            *self.failure_reasons.entry(step.name.clone()).or_default() += 1;
+        }
+        if self.reports.len() >= MAX_RUN_HISTORY {
+            self.reports.pop_front();
+        }
+        self.reports.push_back(report);
+    }
+
+    /// Count a run that could not produce a report (e.g. registry unreachable).
+    pub fn record_error(&mut self) {
+        self.total_runs += 1;
+        *self
+            .failure_reasons
+            .entry("run_error".to_string())
+            .or_default() += 1;
+    }
+
+    /// Total runs recorded.
+    pub fn total_runs(&self) -> usize {
+        self.total_runs
+    }
+
+    /// Fraction of runs that passed, 0.0 with no runs.
+    pub fn pass_rate(&self) -> f64 {
+        if self.total_runs == 0 {
+            0.0
+        } else {
+            self.passed_runs as f64 / self.total_runs as f64
+        }
+    }
+
+    /// The most frequent failure reason and its count (ties by name).
+    pub fn most_common_failure(&self) -> Option<(&str, usize)> {
+        self.failure_reasons
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(reason, count)| (reason.as_str(), *count))
+    }
+}
+
+impl std::fmt::Display for ProofRunHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} runs, {} passed ({:.1}% pass rate)",
+            self.total_runs,
+            self.passed_runs,
+            self.pass_rate() * 100.0
+        )?;
+        if let Some((reason, count)) = self.most_common_failure() {
+            write!(f, ", most common failure: {} ({}x)", reason, count)?;
+        }
+        Ok(())
+    }
+}
+
 /// Node state for orchestration.
 #[derive(Debug, Clone)]
 pub struct NodeState {
@@ -362,6 +722,20 @@ pub struct NodeState {
     pub last_updated: SystemTime,
     /// Data verification results per peer (keyed by VerificationKey).
     pub data_verifications: HashMap<String, DataVerificationResult>,
+    /// IP versions of successfully established paths (peer_id -> versions).
+    pub path_versions: HashMap<String, HashSet<IpVersion>>,
+    /// Local EigenTrust vector (peer_id -> score), if the node runs the trust layer.
+    pub trust_vector: Option<HashMap<String, f64>>,
+    /// Iterations the node's EigenTrust computation took to converge.
+    pub trust_iterations: Option<u32>,
+    /// Connection technique attempts the node made, in order.
+    pub technique_attempts: Vec<TechniqueAttempt>,
+    /// Reconnects made while holding a resumption token for the peer.
+    pub resumable_reconnects: u64,
+    /// Resumable reconnects that used 0-RTT.
+    pub zero_rtt_reconnects: u64,
+    /// Time from node start to its first verified connection.
+    pub join_time: Option<JoinTime>,
 }
 
 impl Default for NodeState {
@@ -375,6 +749,13 @@ impl Default for NodeState {
             responsive: false,
             last_updated: SystemTime::now(),
             data_verifications: HashMap::new(),
+            path_versions: HashMap::new(),
+            trust_vector: None,
+            trust_iterations: None,
+            technique_attempts: Vec::new(),
+            resumable_reconnects: 0,
+            zero_rtt_reconnects: 0,
+            join_time: None,
         }
     }
 }
@@ -464,6 +845,66 @@ impl ProofOrchestrator {
         }
     }
 
+    /// Create a latency probe expected to reach every registered node.
+    ///
+    /// The caller publishes the probe on the gossip probe topic; receipts come
+    /// back through [`record_probe_receipts`](Self::record_probe_receipts).
+    pub fn start_latency_probe(&mut self) -> crate::gossip::LatencyProbe {
+        let expected_nodes = self.node_states.keys().cloned().collect();
+        self.gossip_verifier
+            .create_latency_probe(&self.config.observer_id, expected_nodes)
+    }
+
+    /// Record the probe receipt times a node reported in its heartbeat.
+    pub fn record_probe_receipts(&mut self, node_id: &str, receipts: &HashMap<String, u64>) {
+        for (probe_id, received_at_ms) in receipts {
+            self.gossip_verifier
+                .record_probe_receipt(probe_id, node_id, *received_at_ms);
+        }
+    }
+
+    /// Record the peers a node's SWIM failure detector has declared dead.
+    pub fn record_swim_dead(&mut self, node_id: &str, dead: Vec<String>) {
+        self.gossip_verifier
+            .record_swim_dead(node_id.to_string(), dead);
+    }
+
+    /// Record the peers in a node's HyParView active view.
+    pub fn record_active_view(&mut self, node_id: &str, peers: Vec<String>) {
+        self.gossip_verifier
+            .record_active_view(node_id.to_string(), peers);
+    }
+
+    /// Record the anti-entropy events a node's gossip layer observed.
+    pub fn record_anti_entropy(
+        &mut self,
+        node_id: &str,
+        events: &[crate::epidemic_gossip::AntiEntropyEvent],
+    ) {
+        self.gossip_verifier.record_anti_entropy(node_id, events);
+    }
+
+    /// Record the nodes the registry has seen heartbeat within its TTL, to
+    /// catch SWIM declaring live nodes dead.
+    pub fn record_registry_alive(&mut self, node_ids: impl IntoIterator<Item = String>) {
+        self.gossip_verifier.record_known_alive(node_ids);
+    }
+
+    /// Record a node's EigenTrust global scores and the iterations its
+    /// trust layer took to converge.
+    pub fn record_trust_vector(
+        &mut self,
+        node_id: &str,
+        scores: HashMap<String, f64>,
+        iterations: u32,
+    ) {
+        if let Some(state) = self.node_states.get_mut(node_id) {
+            state.trust_vector = Some(scores);
+            state.trust_iterations = Some(iterations);
+            state.last_updated = SystemTime::now();
+        }
+    }
+
     /// Record state hash from a node.
     pub fn record_state_hash(&mut self, node_id: &str, hash: [u8; 32]) {
         self.crdt_verifier.update_state(node_id.to_string(), hash);
@@ -481,6 +922,64 @@ impl ProofOrchestrator {
         }
     }
 
+    /// Record a successfully established path to a peer over `ip_version`.
+    pub fn record_established_path(&mut self, node_id: &str, peer_id: &str, ip_version: IpVersion) {
+        if let Some(state) = self.node_states.get_mut(node_id) {
+            state
+                .path_versions
+                .entry(peer_id.to_string())
+                .or_default()
+                .insert(ip_version);
+            state.last_updated = SystemTime::now();
+        }
+    }
+
+    /// Record connection technique attempts a node made.
+    pub fn record_technique_attempts(
+        &mut self,
+        node_id: &str,
+        attempts: impl IntoIterator<Item = TechniqueAttempt>,
+    ) {
+        if let Some(state) = self.node_states.get_mut(node_id) {
+            let start = state.technique_attempts.len();
+            state.technique_attempts.extend(attempts);
+            state.last_updated = SystemTime::now();
+            self.debugger
+                .add_outcomes(state.technique_attempts[start..].iter().map(|attempt| {
+                    ConnectionOutcome {
+                        timestamp: SystemTime::UNIX_EPOCH
+                            + Duration::from_millis(attempt.timestamp_ms),
+                        success: attempt.success,
+                    }
+                }));
+        }
+    }
+
+    /// Record a node's NAT classification for the debug report.
+    pub fn record_nat_type(&mut self, node_id: &str, nat_type: NatType) {
+        if self.node_states.contains_key(node_id) {
+            self.debugger.record_nat_type(node_id, nat_type.to_string());
+        }
+    }
+
+    /// Record a node's reconnect counts: those made while holding a
+    /// resumption token, and how many of them used 0-RTT.
+    pub fn record_reconnects(&mut self, node_id: &str, resumable: u64, zero_rtt: u64) {
+        if let Some(state) = self.node_states.get_mut(node_id) {
+            state.resumable_reconnects = resumable;
+            state.zero_rtt_reconnects = zero_rtt;
+            state.last_updated = SystemTime::now();
+        }
+    }
+
+    /// Record how long a node took to reach its first verified connection.
+    pub fn record_join_time(&mut self, node_id: &str, join_time: JoinTime) {
+        if let Some(state) = self.node_states.get_mut(node_id) {
+            state.join_time = Some(join_time);
+            state.last_updated = SystemTime::now();
+        }
+    }
+
     /// Record data verification result for a peer connection.
     ///
     /// This records the actual bidirectional data transfer verification,
@@ -724,6 +1223,163 @@ impl ProofOrchestrator {
         StepResult::pass("inbound_connectivity", start.elapsed(), details)
     }
 
+    /// IP versions over which a node has at least one successful path.
+    ///
+    /// Both established paths and successful data verifications count.
+    fn successful_ip_versions(state: &NodeState) -> HashSet<IpVersion> {
+        state
+            .path_versions
+            .values()
+            .flatten()
+            .copied()
+            .chain(
+                state
+                    .data_verifications
+                    .values()
+                    .filter(|r| r.is_success())
+                    .map(|r| r.ip_version),
+            )
+            .collect()
+    }
+
+    /// Enforce the configured IP mode on every node's connectivity proof.
+    ///
+    /// Returns `None` in dual-stack mode. In a single-family mode, a node
+    /// whose successful paths are all of the other family (or that has none
+    /// of the required family) fails the step.
+    pub fn verify_ip_mode(&self) -> Option<StepResult> {
+        let start = std::time::Instant::now();
+        let (required, other, anomaly_type) = match self.config.ip_mode {
+            IpMode::DualStack => return None,
+            IpMode::Ipv6Only => (IpVersion::V6, IpVersion::V4, "ipv4_only_paths"),
+            IpMode::Ipv4Only => (IpVersion::V4, IpVersion::V6, "ipv6_only_paths"),
+        };
+
+        let mut node_ids: Vec<&String> = self.node_states.keys().collect();
+        node_ids.sort();
+
+        let mut usable = 0;
+        let mut anomalies = Vec::new();
+        for node_id in &node_ids {
+            let versions = Self::successful_ip_versions(&self.node_states[*node_id]);
+            if versions.contains(&required) {
+                usable += 1;
+                continue;
+            }
+            let reason = if versions.contains(&other) {
+                format!(
+                    "Node {}: all successful paths are {}, rejected in {}-only mode",
+                    node_id, other, required
+                )
+            } else {
+                format!(
+                    "Node {}: no successful {} path in {}-only mode",
+                    node_id, required, required
+                )
+            };
+            anomalies.push(
+                TestAnomaly::new(anomaly_type.to_string(), reason, 4)
+                    .with_nodes(vec![(*node_id).clone()]),
+            );
+        }
+
+        let details = format!(
+            "{}/{} nodes had a usable {} path",
+            usable,
+            node_ids.len(),
+            required
+        );
+        Some(if anomalies.is_empty() {
+            StepResult::pass("ip_mode", start.elapsed(), details)
+        } else {
+            StepResult::fail("ip_mode", start.elapsed(), details, anomalies)
+        })
+    }
+
+    /// Directed pairs `(from, to)` with at least one verification, and
+    /// whether any of them succeeded.
+    ///
+    /// An outbound verification at node A for peer P is evidence about
+    /// A→P; an inbound one is evidence about P→A.
+    fn directed_reachability(&self) -> BTreeMap<(String, String), bool> {
+        let mut pairs: BTreeMap<(String, String), bool> = BTreeMap::new();
+        for (node_id, state) in &self.node_states {
+            for (key, result) in &state.data_verifications {
+                // Keys are "peer:ip_version:direction"
+                let peer_id = key.rsplitn(3, ':').last().unwrap_or(key).to_string();
+                let pair = match result.direction {
+                    ConnectionDirection::Outbound => (node_id.clone(), peer_id),
+                    ConnectionDirection::Inbound => (peer_id, node_id.clone()),
+                };
+                *pairs.entry(pair).or_default() |= result.is_success();
+            }
+        }
+        pairs
+    }
+
+    /// Coverage of the ordered peer-pair matrix across all reporting nodes.
+    pub fn matrix_coverage(&self) -> MatrixCoverage {
+        let pairs = self.directed_reachability();
+        MatrixCoverage::from_results(
+            self.node_states.keys().map(String::as_str),
+            pairs
+                .iter()
+                .map(|((from, to), success)| (from.as_str(), to.as_str(), *success)),
+        )
+    }
+
+    /// Join time percentiles across the nodes that reported one.
+    pub fn join_times(&self) -> Option<JoinTimeSummary> {
+        JoinTimeSummary::from_times(self.node_states.values().filter_map(|s| s.join_time))
+    }
+
+    /// Funnel of every recorded technique attempt across all nodes.
+    pub fn connection_funnel(&self) -> ConnectionFunnel {
+        ConnectionFunnel::from_attempts(
+            self.node_states
+                .values()
+                .flat_map(|state| &state.technique_attempts),
+        )
+    }
+
+    /// Flag peer pairs where only one direction of reachability succeeded.
+    ///
+    /// A pair is asymmetric when A reached B while every verification of
+    /// B→A failed; pairs whose reverse direction was never tested are not
+    /// judged. One-way success usually means a one-sided relay rather than a
+    /// hole punch that opened both NAT mappings.
+    pub fn verify_symmetry(&self) -> StepResult {
+        let start = std::time::Instant::now();
+        let pairs = self.directed_reachability();
+
+        let anomalies: Vec<TestAnomaly> = pairs
+            .iter()
+            .filter(|(_, succeeded)| **succeeded)
+            .filter(|((from, to), _)| pairs.get(&(to.clone(), from.clone())) == Some(&false))
+            .map(|((from, to), _)| TestAnomaly::asymmetric_connectivity(from, to))
+            .collect();
+
+        let tested = pairs
+            .keys()
+            .filter(|(from, to)| from < to && pairs.contains_key(&(to.clone(), from.clone())))
+            .count();
+        let details = format!(
+            "{} asymmetric of {} pairs tested in both directions",
+            anomalies.len(),
+            tested
+        );
+        if anomalies.is_empty() {
+            StepResult::pass("symmetric_connectivity", start.elapsed(), details)
+        } else {
+            StepResult::fail(
+                "symmetric_connectivity",
+                start.elapsed(),
+                details,
+                anomalies,
+            )
+        }
+    }
+
     /// Get aggregated verification summary across all nodes.
     pub fn get_aggregated_verification_summary(&self) -> VerificationSummary {
         let mut aggregate = VerificationSummary::default();
@@ -767,6 +1423,51 @@ impl ProofOrchestrator {
                 4,
             ));
         }
+        if !summary.swim_false_positives.is_empty() {
+            let pairs: Vec<String> = summary
+                .swim_false_positives
+                .iter()
+                .map(|(reporter, victim)| format!("{}->{}", reporter, victim))
+                .collect();
+            let mut nodes: Vec<String> = summary
+                .swim_false_positives
+                .iter()
+                .flat_map(|(reporter, victim)| [reporter.clone(), victim.clone()])
+                .collect();
+            nodes.sort();
+            nodes.dedup();
+            anomalies.push(
+                TestAnomaly::new(
+                    "swim_false_positive".to_string(),
+                    format!(
+                        "SWIM declared {} live node(s) dead: {}",
+                        pairs.len(),
+                        pairs.join(", ")
+                    ),
+                    3,
+                )
+                .with_nodes(nodes),
+            );
+        }
+        if summary.partitions.len() > 1 {
+            let sizes: Vec<String> = summary
+                .partitions
+                .iter()
+                .map(|members| members.len().to_string())
+                .collect();
+            anomalies.push(
+                TestAnomaly::new(
+                    "gossip_partition".to_string(),
+                    format!(
+                        "Gossip overlay split into {} partitions (sizes {})",
+                        summary.partitions.len(),
+                        sizes.join(", ")
+                    ),
+                    5,
+                )
+                .with_nodes(summary.partitions.concat()),
+            );
+        }
         if !summary.plumtree_valid {
             anomalies.push(TestAnomaly::new(
                 "plumtree_failure".to_string(),
@@ -785,6 +1486,13 @@ impl ProofOrchestrator {
             if summary.swim_valid { "OK" } else { "FAIL" },
             if summary.plumtree_valid { "OK" } else { "FAIL" },
         );
+        let details = match summary.broadcast_latency_p95_ms {
+            Some(p95) => format!(
+                "{} broadcast_p95={}ms failures={}",
+                details, p95, summary.broadcast_delivery_failures
+            ),
+            None => details,
+        };
 
         if all_valid {
             StepResult::pass("gossip_protocols", start.elapsed(), details)
@@ -803,11 +1511,14 @@ impl ProofOrchestrator {
         let mut anomalies = Vec::new();
         if !converged {
             for node in &summary.divergent_nodes {
-                anomalies.push(TestAnomaly::new(
-                    "state_divergence".to_string(),
-                    format!("Node {} has divergent state", node),
-                    5,
-                ));
+                anomalies.push(
+                    TestAnomaly::new(
+                        "state_divergence".to_string(),
+                        format!("Node {} has divergent state", node),
+                        5,
+                    )
+                    .with_nodes(vec![node.clone()]),
+                );
             }
         }
 
@@ -825,6 +1536,130 @@ impl ProofOrchestrator {
         }
     }
 
+    /// Generate the EigenTrust convergence proof from reported trust vectors.
+    ///
+    /// Returns `None` when no node reported a trust vector.
+    pub fn generate_eigentrust_proof(&self) -> Option<EigenTrustConvergenceProof> {
+        let mut vectors = HashMap::new();
+        let mut iterations = HashMap::new();
+        for (node_id, state) in &self.node_states {
+            if let Some(vector) = &state.trust_vector {
+                vectors.insert(node_id.clone(), vector.clone());
+            }
+            if let Some(n) = state.trust_iterations {
+                iterations.insert(node_id.clone(), n);
+            }
+        }
+        if vectors.is_empty() {
+            return None;
+        }
+        Some(EigenTrustConvergenceProof::from_trust_vectors(
+            self.session_id.clone(),
+            self.config.trust_epsilon,
+            vectors,
+            &iterations,
+        ))
+    }
+
+    /// Verify that EigenTrust scores agree across nodes.
+    ///
+    /// Skipped (`None`) unless at least two nodes reported trust vectors.
+    pub fn verify_eigentrust(&self) -> Option<StepResult> {
+        let start = std::time::Instant::now();
+        let proof = self.generate_eigentrust_proof()?;
+        if proof.node_count() < 2 {
+            return None;
+        }
+
+        let anomalies: Vec<TestAnomaly> = proof
+            .divergent_nodes
+            .iter()
+            .map(|node| {
+                TestAnomaly::new(
+                    "trust_divergence".to_string(),
+                    format!(
+                        "Node {} trust scores diverge from the network by more than {:.3}",
+                        node,
+                        proof.epsilon / 2.0
+                    ),
+                    4,
+                )
+                .with_nodes(vec![node.clone()])
+            })
+            .collect();
+
+        let details = format!(
+            "convergence:{} nodes:{} max_diff:{:.4} iterations:{}",
+            if proof.is_valid() { "OK" } else { "FAIL" },
+            proof.node_count(),
+            proof.max_pairwise_diff,
+            proof
+                .iterations_to_convergence
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        );
+
+        Some(if proof.is_valid() {
+            StepResult::pass("eigentrust_convergence", start.elapsed(), details)
+        } else {
+            StepResult::fail(
+                "eigentrust_convergence",
+                start.elapsed(),
+                details,
+                anomalies,
+            )
+        })
+    }
+
+    /// Verify that reconnects with a resumption token achieve 0-RTT.
+    ///
+    /// First connections can never be 0-RTT, so only resumable reconnects
+    /// count. Returns `None` when no node has made one yet. The counts are
+    /// inferred from dial timing, so the step does not gate the run.
+    pub fn verify_zero_rtt(&self) -> Option<StepResult> {
+        let start = std::time::Instant::now();
+        let (resumable, zero_rtt) =
+            self.node_states
+                .values()
+                .fold((0, 0), |(resumable, zero_rtt), state| {
+                    (
+                        resumable + state.resumable_reconnects,
+                        zero_rtt + state.zero_rtt_reconnects,
+                    )
+                });
+        if resumable == 0 {
+            return None;
+        }
+
+        let details = format!("{}/{} resumable reconnects used 0-RTT", zero_rtt, resumable);
+        Some(if zero_rtt > 0 {
+            StepResult::pass("zero_rtt_resumption", start.elapsed(), details)
+        } else {
+            let mut node_ids: Vec<String> = self
+                .node_states
+                .iter()
+                .filter(|(_, state)| state.resumable_reconnects > 0)
+                .map(|(node_id, _)| node_id.clone())
+                .collect();
+            node_ids.sort();
+            let anomaly = TestAnomaly::new(
+                "no_zero_rtt".to_string(),
+                format!(
+                    "None of {} reconnects with a resumption token used 0-RTT",
+                    resumable
+                ),
+                3,
+            )
+            .with_nodes(node_ids);
+            StepResult::fail(
+                "zero_rtt_resumption",
+                start.elapsed(),
+                details,
+                vec![anomaly],
+            )
+        })
+    }
+
     /// Generate connectivity proof.
     pub fn generate_connectivity_proof(&self) -> NetworkConnectivityProof {
         let expected: std::collections::HashSet<String> =
@@ -882,6 +1717,27 @@ impl ProofOrchestrator {
         }
         step_results.push(connectivity_result);
 
+        // Reject proofs that rely on an address family the IP mode excludes
+        if let Some(ip_mode_result) = self.verify_ip_mode() {
+            all_anomalies.extend(ip_mode_result.anomalies.clone());
+            if !ip_mode_result.passed {
+                passed = false;
+            }
+            step_results.push(ip_mode_result);
+        }
+
+        // Catch pairs that only connect one way
+        let symmetry_result = self.verify_symmetry();
+        let asymmetric_pairs = symmetry_result.anomalies.len();
+        let matrix_coverage = self.matrix_coverage();
+        let connection_funnel = self.connection_funnel();
+        let join_times = self.join_times();
+        all_anomalies.extend(symmetry_result.anomalies.clone());
+        if !symmetry_result.passed {
+            passed = false;
+        }
+        step_results.push(symmetry_result);
+
         // Generate connectivity proof (regardless of result)
         let connectivity_proof = Some(self.generate_connectivity_proof());
 
@@ -907,6 +1763,23 @@ impl ProofOrchestrator {
         // Generate CRDT proof
         let crdt_proof = Some(self.generate_crdt_proof());
 
+        // EigenTrust agreement, when nodes run the trust layer
+        if let Some(trust_result) = self.verify_eigentrust() {
+            all_anomalies.extend(trust_result.anomalies.clone());
+            if !trust_result.passed {
+                passed = false;
+            }
+            step_results.push(trust_result);
+        }
+        let eigentrust_proof = self.generate_eigentrust_proof();
+
+        // 0-RTT resumption, once some reconnect had a token to use; reported
+        // only, since ant-quic doesn't say whether 0-RTT was accepted
+        if let Some(zero_rtt_result) = self.verify_zero_rtt() {
+            all_anomalies.extend(zero_rtt_result.anomalies.clone());
+            step_results.push(zero_rtt_result);
+        }
+
         // Step 4: If failed and debug enabled, run automated debugging
         let debug_report = if !passed && self.config.debug_on_failure {
             Some(self.debugger.investigate())
@@ -935,10 +1808,16 @@ impl ProofOrchestrator {
             started_at,
             completed_at: SystemTime::now(),
             passed,
+            min_nodes: self.config.min_nodes,
+            asymmetric_pairs,
+            matrix_coverage,
+            connection_funnel,
+            join_times,
             step_results,
             connectivity_proof,
             gossip_proof,
             crdt_proof,
+            eigentrust_proof,
             debug_report,
             all_anomalies,
             failure_summary,
@@ -990,6 +1869,7 @@ mod tests {
                 duplicates: 5,
                 grafts: 2,
                 prunes: 1,
+                ..Default::default()
             },
             ..Default::default()
         }
@@ -1010,6 +1890,54 @@ mod tests {
         assert_eq!(orchestrator.node_states.len(), 2);
     }
 
+    #[test]
+    fn test_swim_dead_reports_checked_against_registry() {
+        let mut orchestrator = ProofOrchestrator::new();
+        orchestrator.record_registry_alive(["node1", "node2"].map(String::from));
+        orchestrator.record_swim_dead("node1", vec!["node2".to_string(), "node9".to_string()]);
+
+        assert_eq!(
+            orchestrator.gossip_verifier.swim_false_positives(),
+            vec![("node1".to_string(), "node2".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_eigentrust_convergence_flags_divergent_node() {
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2", "node3"] {
+            orchestrator.register_node(node.to_string());
+        }
+        assert!(orchestrator.verify_eigentrust().is_none());
+
+        let vector =
+            |a: f64, b: f64| HashMap::from([("peerA".to_string(), a), ("peerB".to_string(), b)]);
+        orchestrator.record_trust_vector("node1", vector(0.60, 0.40), 12);
+        orchestrator.record_trust_vector("node2", vector(0.61, 0.39), 15);
+        let result = orchestrator
+            .verify_eigentrust()
+            .expect("two nodes reported");
+        assert!(result.passed, "{}", result.details);
+        assert!(result.details.contains("iterations:15"));
+
+        orchestrator.record_trust_vector("node3", vector(0.20, 0.80), 40);
+        let result = orchestrator
+            .verify_eigentrust()
+            .expect("three nodes reported");
+        assert!(!result.passed);
+        assert_eq!(result.anomalies.len(), 1);
+        assert_eq!(
+            result.anomalies[0].nodes_involved,
+            vec!["node3".to_string()]
+        );
+
+        let report = orchestrator.run_comprehensive_test();
+        let proof = report.eigentrust_proof.expect("proof generated");
+        assert!(!proof.convergence_achieved);
+        assert!((proof.max_pairwise_diff - 0.41).abs() < 1e-9);
+        assert_eq!(proof.iterations_to_convergence, Some(40));
+    }
+
     #[test]
     fn test_connectivity_insufficient_nodes() {
         let mut orchestrator = ProofOrchestrator::new();
@@ -1092,4 +2020,332 @@ mod tests {
         // Shows 50% mesh for informational purposes
         assert!(result.details.contains("50% mesh"));
     }
+
+    #[test]
+    fn test_v6_only_rejects_ipv4_only_nodes() {
+        let mut orchestrator = ProofOrchestrator::with_config(ProofOrchestratorConfig {
+            ip_mode: IpMode::Ipv6Only,
+            ..Default::default()
+        });
+        orchestrator.register_node("node1".to_string());
+        orchestrator.register_node("node2".to_string());
+        orchestrator.record_established_path("node1", "node2", IpVersion::V6);
+        orchestrator.record_established_path("node1", "node3", IpVersion::V4);
+        orchestrator.record_established_path("node2", "node3", IpVersion::V4);
+
+        let result = orchestrator.verify_ip_mode().expect("v6-only is checked");
+        assert!(!result.passed);
+        assert_eq!(result.details, "1/2 nodes had a usable IPv6 path");
+        assert_eq!(result.anomalies.len(), 1);
+        assert_eq!(result.anomalies[0].anomaly_type, "ipv4_only_paths");
+        assert_eq!(
+            result.anomalies[0].nodes_involved,
+            vec!["node2".to_string()]
+        );
+
+        let report = orchestrator.run_comprehensive_test();
+        assert!(!report.passed);
+        assert!(report.step_results.iter().any(|s| s.name == "ip_mode"));
+
+        // Dual-stack accepts any address family
+        assert!(ProofOrchestrator::new().verify_ip_mode().is_none());
+    }
+
+    #[test]
+    fn test_report_json_lists_failed_nodes() {
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2", "node3"] {
+            orchestrator.register_node(node.to_string());
+            orchestrator.record_gossip_stats(node, make_test_gossip_stats());
+        }
+        orchestrator.record_state_hash("node1", [1u8; 32]);
+        orchestrator.record_state_hash("node2", [1u8; 32]);
+        orchestrator.record_state_hash("node3", [2u8; 32]);
+
+        let json = orchestrator.run_comprehensive_test().to_json();
+        assert_eq!(json.schema_version, REPORT_SCHEMA_VERSION);
+        assert_eq!(json.min_nodes, 2);
+        assert!(!json.passed);
+
+        let crdt = json
+            .proofs
+            .iter()
+            .find(|p| p.proof == "crdt_convergence")
+            .expect("crdt proof in report");
+        assert!(!crdt.passed);
+        assert_eq!(crdt.failed_nodes, vec!["node3".to_string()]);
+
+        let connectivity = &json.proofs[0];
+        assert_eq!(connectivity.proof, "connectivity");
+        assert!(connectivity.passed && connectivity.failed_nodes.is_empty());
+
+        let value = serde_json::to_value(&json).unwrap();
+        assert_eq!(value["schema_version"], 2);
+        let parsed: OrchestratorReportJson = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, json);
+    }
+
+    #[test]
+    fn test_report_diff_against_baseline() {
+        let report =
+            |session: &str, nodes: &[&str], proofs: &[(&str, bool)]| OrchestratorReportJson {
+                schema_version: REPORT_SCHEMA_VERSION,
+                session_id: session.to_string(),
+                started_at: 0,
+                completed_at: 0,
+                passed: proofs.iter().all(|(_, passed)| *passed),
+                min_nodes: 2,
+                asymmetric_pairs: 0,
+                proofs: proofs
+                    .iter()
+                    .map(|(proof, passed)| ProofOutcomeJson {
+                        proof: proof.to_string(),
+                        passed: *passed,
+                        details: String::new(),
+                        failed_nodes: Vec::new(),
+                    })
+                    .collect(),
+                nodes: Some(nodes.iter().map(|n| n.to_string()).collect()),
+                failure_summary: None,
+            };
+
+        let baseline = report(
+            "good",
+            &["node1", "node2", "node3"],
+            &[("connectivity", true), ("crdt_convergence", false)],
+        );
+        let current = report(
+            "now",
+            &["node2", "node3", "node4"],
+            &[("connectivity", false), ("crdt_convergence", true)],
+        );
+        let diff = current.diff(&baseline);
+        assert_eq!(diff.newly_passed, vec!["crdt_convergence".to_string()]);
+        assert_eq!(diff.newly_failed, vec!["connectivity".to_string()]);
+        assert_eq!(diff.nodes_appeared, vec!["node4".to_string()]);
+        assert_eq!(diff.nodes_disappeared, vec!["node1".to_string()]);
+        assert!(!diff.disjoint_nodes);
+        assert!(current.diff(&current).is_empty());
+
+        let elsewhere = report("other", &["nodeA", "nodeB"], &[("connectivity", true)]);
+        let diff = current.diff(&elsewhere);
+        assert!(diff.disjoint_nodes);
+        assert!(diff.to_string().contains("WARNING: no nodes in common"));
+
+        // A baseline without a node list doesn't make every node "appear"
+        let mut legacy =
+            serde_json::to_value(report("old", &[], &[("connectivity", true)])).unwrap();
+        legacy["schema_version"] = 1.into();
+        legacy.as_object_mut().unwrap().remove("nodes");
+        let legacy: OrchestratorReportJson = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.nodes, None);
+        for baseline in [legacy, report("empty", &[], &[("connectivity", true)])] {
+            let diff = current.diff(&baseline);
+            assert!(diff.nodes_unknown);
+            assert!(diff.nodes_appeared.is_empty() && diff.nodes_disappeared.is_empty());
+            assert!(!diff.disjoint_nodes);
+            assert!(diff.to_string().contains("Node changes unknown"));
+        }
+    }
+
+    #[test]
+    fn test_one_way_reachability_flagged_as_asymmetric() {
+        let ok = |direction| {
+            DataVerificationResult::success(
+                DataProof::new(1024, 1024, "a".into(), "b".into(), true),
+                None,
+                IpVersion::V4,
+                direction,
+            )
+        };
+        let failed = |direction| DataVerificationResult::failed(IpVersion::V4, direction);
+
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2", "node3"] {
+            orchestrator.register_node(node.to_string());
+        }
+        // node1 -> node2 works, node2 -> node1 was tried and failed
+        orchestrator.record_data_verification("node1", "node2", ok(ConnectionDirection::Outbound));
+        orchestrator.record_data_verification(
+            "node2",
+            "node1",
+            failed(ConnectionDirection::Outbound),
+        );
+        // node1 <-> node3 works both ways, seen from node3's side
+        orchestrator.record_data_verification("node3", "node1", ok(ConnectionDirection::Outbound));
+        orchestrator.record_data_verification("node3", "node1", ok(ConnectionDirection::Inbound));
+        // node2 -> node3 works; the reverse was never tried, so no verdict
+        orchestrator.record_data_verification("node2", "node3", ok(ConnectionDirection::Outbound));
+
+        let step = orchestrator.verify_symmetry();
+        assert!(!step.passed);
+        assert_eq!(step.anomalies.len(), 1);
+        let anomaly = &step.anomalies[0];
+        assert_eq!(anomaly.anomaly_type, TestAnomaly::ASYMMETRIC_CONNECTIVITY);
+        assert_eq!(
+            anomaly.nodes_involved,
+            vec!["node1".to_string(), "node2".to_string()]
+        );
+
+        let report = orchestrator.run_comprehensive_test();
+        assert_eq!(report.asymmetric_pairs, 1);
+        assert!(!report.passed);
+        assert_eq!(report.to_json().asymmetric_pairs, 1);
+
+        // 3 nodes: 6 ordered pairs, node3 -> node2 never tried
+        let coverage = report.matrix_coverage;
+        assert_eq!(coverage.possible_pairs, 6);
+        assert_eq!(coverage.tested_pairs, 5);
+        assert_eq!(coverage.successful_pairs, 4);
+        assert!(!coverage.is_complete());
+        assert_eq!(report.to_proof_report().matrix_coverage, Some(coverage));
+    }
+
+    #[test]
+    fn test_join_times_in_report() {
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2", "node3"] {
+            orchestrator.register_node(node.to_string());
+        }
+        assert_eq!(orchestrator.run_comprehensive_test().join_times, None);
+
+        // node3 never joined and is left out
+        let join = |discovery_ms, connection_ms| JoinTime {
+            discovery_ms,
+            connection_ms,
+        };
+        orchestrator.record_join_time("node1", join(200, 1800));
+        orchestrator.record_join_time("node2", join(400, 5600));
+        orchestrator.record_join_time("unknown", join(0, 1));
+
+        let report = orchestrator.run_comprehensive_test();
+        let join_times = report.join_times.unwrap();
+        assert_eq!(join_times.nodes, 2);
+        assert_eq!((join_times.p50_ms, join_times.p95_ms), (2000, 6000));
+        assert_eq!(report.to_proof_report().join_times, Some(join_times));
+        assert!(
+            report
+                .to_string()
+                .contains("Join time: p50=2.0s p95=6.0s over 2 nodes")
+        );
+    }
+
+    #[test]
+    fn test_connection_funnel_in_report() {
+        use crate::registry::ConnectionTechnique;
+
+        let attempt = |success, error: Option<&str>| TechniqueAttempt {
+            technique: ConnectionTechnique::DirectIpv4,
+            success,
+            duration_ms: 5,
+            error: error.map(str::to_string),
+            timestamp_ms: 0,
+            data_proof: None,
+            method_proof: None,
+        };
+        let mut orchestrator = ProofOrchestrator::new();
+        orchestrator.register_node("node1".to_string());
+        orchestrator.register_node("node2".to_string());
+        orchestrator.record_technique_attempts(
+            "node1",
+            [
+                attempt(false, Some("connection timed out")),
+                attempt(true, None),
+            ],
+        );
+        orchestrator.record_technique_attempts("node2", [attempt(true, None)]);
+        // Unregistered nodes are ignored
+        orchestrator.record_technique_attempts("node9", [attempt(true, None)]);
+
+        let report = orchestrator.run_comprehensive_test();
+        assert_eq!(report.connection_funnel.attempted, 3);
+        assert_eq!(report.connection_funnel.handshake, 2);
+        assert_eq!(report.connection_funnel.verified, 2);
+        assert!(report.to_string().contains(
+            "Connection funnel: 3 attempted → 2 handshake → 2 coordinated → 2 punched → 2 verified"
+        ));
+    }
+
+    #[test]
+    fn test_zero_rtt_step_needs_resumable_reconnects() {
+        let mut orchestrator = ProofOrchestrator::new();
+        orchestrator.register_node("node1".to_string());
+        orchestrator.register_node("node2".to_string());
+
+        // Only first connections so far: nothing to verify
+        assert!(orchestrator.verify_zero_rtt().is_none());
+
+        orchestrator.record_reconnects("node1", 3, 0);
+        let result = orchestrator.verify_zero_rtt().unwrap();
+        assert!(!result.passed);
+        assert_eq!(
+            result.anomalies[0].nodes_involved,
+            vec!["node1".to_string()]
+        );
+
+        orchestrator.record_reconnects("node2", 2, 1);
+        let result = orchestrator.verify_zero_rtt().unwrap();
+        assert!(result.passed);
+        assert_eq!(result.details, "1/5 resumable reconnects used 0-RTT");
+    }
+
+    #[test]
+    fn test_zero_rtt_step_does_not_gate_the_run() {
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2"] {
+            orchestrator.register_node(node.to_string());
+            orchestrator.record_gossip_stats(node, make_test_gossip_stats());
+            orchestrator.record_state_hash(node, [1u8; 32]);
+        }
+        let baseline = orchestrator.run_comprehensive_test().passed;
+
+        orchestrator.record_reconnects("node1", 3, 0);
+        let report = orchestrator.run_comprehensive_test();
+        let step = report
+            .step_results
+            .iter()
+            .find(|s| s.name == "zero_rtt_resumption")
+            .expect("0-RTT step reported");
+        assert!(!step.passed);
+        assert_eq!(report.passed, baseline);
+    }
+
+    #[test]
+    fn test_run_history_aggregates_runs() {
+        let mut history = ProofRunHistory::new();
+        assert_eq!(history.pass_rate(), 0.0);
+        assert!(history.most_common_failure().is_none());
+
+        let mut orchestrator = ProofOrchestrator::new();
+        for node in ["node1", "node2"] {
+            orchestrator.register_node(node.to_string());
+            orchestrator.record_gossip_stats(node, make_test_gossip_stats());
+        }
+        let passing = orchestrator.run_comprehensive_test();
+        assert!(passing.passed);
+        assert_eq!(passing.node_count(), 2);
+        assert!(passing.summary_line().contains(" PASS nodes=2"));
+
+        orchestrator.record_state_hash("node1", [1u8; 32]);
+        orchestrator.record_state_hash("node2", [2u8; 32]);
+        let failing = orchestrator.run_comprehensive_test();
+        assert!(
+            failing
+                .summary_line()
+                .ends_with("FAIL nodes=2 failed=crdt_convergence")
+        );
+
+        history.record(passing);
+        history.record(failing.clone());
+        history.record(failing);
+        history.record_error();
+        assert_eq!(history.total_runs(), 4);
+        assert_eq!(history.pass_rate(), 0.25);
+        assert_eq!(history.most_common_failure(), Some(("crdt_convergence", 2)));
+        assert_eq!(history.reports.len(), 3);
+        assert_eq!(
+            history.to_string(),
+            "4 runs, 1 passed (25.0% pass rate), most common failure: crdt_convergence (2x)"
+        );
+    }
 }
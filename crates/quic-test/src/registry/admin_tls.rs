@@ -0,0 +1,210 @@
+//! Mutual-TLS listener for the registry admin endpoints.
+//!
+//! When configured, `/api/admin/*` moves off the plain HTTP listener onto a
+//! separate TLS socket that only completes handshakes with clients
+//! presenting a certificate signed by the configured CA. The bearer token
+//! is still checked on top when one is set.
+
+use futures_util::Stream;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+
+/// Handshakes taking longer than this are dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where and how to serve `/api/admin/*` over mutual TLS.
+#[derive(Debug, Clone)]
+pub struct AdminTlsConfig {
+    /// Address of the mTLS listener (must differ from the HTTP bind address)
+    pub bind_addr: SocketAddr,
+    /// PEM server certificate chain
+    pub cert_path: PathBuf,
+    /// PEM server private key
+    pub key_path: PathBuf,
+    /// PEM CA certificate(s) that client certificates must chain to
+    pub client_ca_path: PathBuf,
+}
+
+fn read_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| anyhow::anyhow!("could not read certificates from {:?}: {}", path, e))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates found in {:?}", path);
+    }
+    Ok(certs)
+}
+
+/// Build a server config that requires a client certificate.
+pub(crate) fn server_config(config: &AdminTlsConfig) -> anyhow::Result<rustls::ServerConfig> {
+    let cert_chain = read_certs(&config.cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(&config.key_path).map_err(|e| {
+        anyhow::anyhow!(
+            "could not read private key from {:?}: {}",
+            config.key_path,
+            e
+        )
+    })?;
+
+    let mut roots = rustls::RootCertStore::empty();
+    for ca in read_certs(&config.client_ca_path)? {
+        roots
+            .add(ca)
+            .map_err(|e| anyhow::anyhow!("invalid CA in {:?}: {}", config.client_ca_path, e))?;
+    }
+    let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+        .build()
+        .map_err(|e| anyhow::anyhow!("could not build client verifier: {}", e))?;
+
+    let mut server_config = rustls::ServerConfig::builder()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(cert_chain, key)
+        .map_err(|e| anyhow::anyhow!("invalid server certificate or key: {}", e))?;
+    server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(server_config)
+}
+
+/// Bind the mTLS listener and yield connections whose client certificate
+/// verified, for `warp::serve(..).run_incoming(..)`.
+///
+/// Failed handshakes are logged and dropped without reaching warp.
+pub(crate) async fn bind(
+    config: &AdminTlsConfig,
+) -> anyhow::Result<(
+    SocketAddr,
+    impl Stream<Item = std::io::Result<TlsStream<TcpStream>>> + use<>,
+)> {
+    let acceptor = TlsAcceptor::from(Arc::new(server_config(config)?));
+    let listener = TcpListener::bind(config.bind_addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("could not bind {}: {}", config.bind_addr, e))?;
+    let local_addr = listener.local_addr()?;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(64);
+    tokio::spawn(async move {
+        // Stop accepting once warp drops the stream
+        while !tx.is_closed() {
+            let (tcp, remote) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("Admin TLS accept failed: {}", e);
+                    continue;
+                }
+            };
+            let acceptor = acceptor.clone();
+            let tx = tx.clone();
+            // Handshake off the accept loop so a slow client can't stall it
+            tokio::spawn(async move {
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(tcp)).await {
+                    Ok(Ok(tls)) => {
+                        let _ = tx.send(Ok(tls)).await;
+                    }
+                    Ok(Err(e)) => {
+                        tracing::warn!("Rejected admin TLS connection from {}: {}", remote, e)
+                    }
+                    Err(_) => tracing::debug!("Admin TLS handshake from {} timed out", remote),
+                }
+            });
+        }
+    });
+
+    let incoming = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|conn| (conn, rx))
+    });
+    Ok((local_addr, incoming))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, Issuer, KeyPair};
+    use rustls::pki_types::ServerName;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use warp::Filter;
+
+    #[tokio::test]
+    async fn test_only_clients_with_a_trusted_cert_get_through() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, pem: String| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, pem).unwrap();
+            path
+        };
+
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_key = KeyPair::generate().unwrap();
+        let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+        let ca = Issuer::new(ca_params, ca_key);
+
+        let server_key = KeyPair::generate().unwrap();
+        let server_cert = CertificateParams::new(vec!["localhost".to_string()])
+            .unwrap()
+            .signed_by(&server_key, &ca)
+            .unwrap();
+        let client_key = KeyPair::generate().unwrap();
+        let client_cert = CertificateParams::new(vec!["admin".to_string()])
+            .unwrap()
+            .signed_by(&client_key, &ca)
+            .unwrap();
+
+        let config = AdminTlsConfig {
+            bind_addr: "127.0.0.1:0".parse().unwrap(),
+            cert_path: write("server.pem", server_cert.pem()),
+            key_path: write("server.key", server_key.serialize_pem()),
+            client_ca_path: write("ca.pem", ca_cert.pem()),
+        };
+        let (addr, incoming) = bind(&config).await.unwrap();
+        let route = warp::any().map(|| "ok");
+        tokio::spawn(warp::serve(route).run_incoming(incoming));
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(ca_cert.der().clone()).unwrap();
+        let request = |client_config: rustls::ClientConfig| async move {
+            let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+            let tcp = TcpStream::connect(addr).await?;
+            let name = ServerName::try_from("localhost").unwrap();
+            let mut tls = connector.connect(name, tcp).await?;
+            tls.write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+                .await?;
+            let mut response = String::new();
+            tls.read_to_string(&mut response).await?;
+            Ok::<_, std::io::Error>(response)
+        };
+
+        let trusted = rustls::ClientConfig::builder()
+            .with_root_certificates(roots.clone())
+            .with_client_auth_cert(
+                vec![client_cert.der().clone()],
+                PrivateKeyDer::try_from(client_key.serialize_der()).unwrap(),
+            )
+            .unwrap();
+        let response = request(trusted).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.ends_with("ok"));
+
+        // TLS 1.3 reports the missing client cert after the client's handshake
+        // completes, so the failure surfaces on the request instead
+        let anonymous = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let response = request(anonymous).await;
+        assert!(!response.is_ok_and(|r| r.starts_with("HTTP/1.1 200")));
+
+        // Unreadable material is a startup error, not a panic
+        let missing = AdminTlsConfig {
+            client_ca_path: dir.path().join("missing.pem"),
+            ..config
+        };
+        assert!(server_config(&missing).is_err());
+    }
+}
@@ -13,48 +13,275 @@
 //! - `stats_snapshots.json` - Periodic statistics snapshots
 
 use crate::dashboard::dashboard_routes;
-use crate::registry::persistence::{PersistenceConfig, PersistentStorage};
-use crate::registry::store::PeerStore;
+use crate::registry::admin_tls::{self, AdminTlsConfig};
+use crate::registry::api_error::{ApiError, ApiErrorBody, ApiErrorCode, handle_rejection};
+use crate::registry::blocklist::Blocklist;
+use crate::registry::persistence::{
+    DEFAULT_HISTORY_RETENTION_SECS, PersistenceConfig, PersistentStorage,
+};
+use crate::registry::rate_limit::RateLimiter;
+use crate::registry::store::{DEFAULT_SUCCESS_RATE_FLOOR, PeerStore};
 use crate::registry::types::{
-    ConnectionReport, NetworkEvent, NetworkStats, NodeHeartbeat, NodeRegistration, PeerInfo,
-    RegistrationResponse,
+    ConnectionReport, ExperimentResults, NatType, NetworkEvent, NetworkStats, NodeDeregistration,
+    NodeHeartbeat, NodeRegistration, PeerInfo, PeersPage, RegistrationResponse, TelemetryRecord,
 };
+use rand::Rng;
+use std::collections::{BTreeMap, HashSet};
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use warp::{Filter, Rejection, Reply};
 
-/// Extract the real client IP from proxy headers or remote address.
+/// Extract the client IP a request should be attributed to.
 ///
-/// Priority order:
-/// 1. X-Forwarded-For header (first IP in chain)
-/// 2. X-Real-IP header
-/// 3. Direct remote address
+/// Forwarding headers are only believed when the socket peer is one of
+/// `trusted_proxies`; anyone else could put any address in them. Behind a
+/// trusted proxy the client is the right-most X-Forwarded-For entry that is
+/// not itself a trusted proxy, then X-Real-IP, then the socket address.
 fn extract_client_ip(
     x_forwarded_for: Option<String>,
     x_real_ip: Option<String>,
     remote_addr: Option<SocketAddr>,
+    trusted_proxies: &[IpAddr],
 ) -> Option<IpAddr> {
-    // Try X-Forwarded-For first (nginx adds this)
+    let remote_ip = remote_addr.map(|addr| addr.ip());
+    if !matches!(remote_ip, Some(ip) if trusted_proxies.contains(&ip)) {
+        return remote_ip;
+    }
+
+    // Each proxy appends the address it received the request from
     if let Some(xff) = x_forwarded_for {
-        // X-Forwarded-For can be a comma-separated list; take the first (original client)
-        if let Some(first_ip) = xff.split(',').next() {
-            if let Ok(ip) = first_ip.trim().parse::<IpAddr>() {
-                return Some(ip);
-            }
+        let forwarded = xff
+            .rsplit(',')
+            .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+            .find(|ip| !trusted_proxies.contains(ip));
+        if forwarded.is_some() {
+            return forwarded;
         }
     }
 
-    // Try X-Real-IP
     if let Some(xri) = x_real_ip {
         if let Ok(ip) = xri.trim().parse::<IpAddr>() {
             return Some(ip);
         }
     }
 
-    // Fall back to remote address
-    remote_addr.map(|addr| addr.ip())
+    remote_ip
+}
+
+/// Resolve the client IP of a request (see [`extract_client_ip`]).
+fn client_ip(
+    trusted_proxies: Arc<[IpAddr]>,
+) -> impl Filter<Extract = (Option<IpAddr>,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("X-Forwarded-For")
+        .and(warp::header::optional::<String>("X-Real-IP"))
+        .and(warp::addr::remote())
+        .map(
+            move |xff: Option<String>, real_ip: Option<String>, remote: Option<SocketAddr>| {
+                extract_client_ip(xff, real_ip, remote, &trusted_proxies)
+            },
+        )
+}
+
+/// Reject requests from clients that exhausted `limiter`'s bucket with 429.
+fn rate_limit(
+    limiter: Arc<RateLimiter>,
+    endpoint: &'static str,
+    trusted_proxies: Arc<[IpAddr]>,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    client_ip(trusted_proxies)
+        .and_then(move |ip: Option<IpAddr>| {
+            let limiter = Arc::clone(&limiter);
+            async move {
+                let Some(ip) = ip else {
+                    return Ok(());
+                };
+                limiter.check(ip).map_err(|retry_after| {
+                    tracing::debug!("Rate limited {} on {}", ip, endpoint);
+                    warp::reject::custom(
+                        ApiError::rate_limited(format!("Too many {} requests", endpoint))
+                            .with_retry_after(retry_after),
+                    )
+                })
+            }
+        })
+        .untuple_one()
+}
+
+/// Environment variable the registry binary reads the admin token from
+/// when `--admin-token` is not given.
+pub const ADMIN_TOKEN_ENV_VAR: &str = "SAORSA_ADMIN_TOKEN";
+
+/// Older name of [`ADMIN_TOKEN_ENV_VAR`], still honoured.
+pub const ADMIN_SECRET_ENV_VAR: &str = "SAORSA_ADMIN_SECRET";
+
+/// Legacy header carrying the admin token, accepted alongside
+/// `Authorization: Bearer`.
+const ADMIN_SECRET_HEADER: &str = "X-Admin-Secret";
+
+/// How a listener authenticates `/api/admin/*`.
+#[derive(Debug, Clone)]
+enum AdminAccess {
+    /// No token and no mTLS: every request is refused.
+    Disabled,
+    /// Bearer token required.
+    Token(Arc<str>),
+    /// The listener already verified a client certificate; the token is
+    /// checked on top when configured.
+    ClientCert(Option<Arc<str>>),
+    /// Admin requests are only served on the mTLS listener at this address.
+    TlsOnly(SocketAddr),
+}
+
+/// Token from `Authorization: Bearer <token>` or the legacy header.
+fn presented_token<'a>(authorization: Option<&'a str>, legacy: Option<&'a str>) -> Option<&'a str> {
+    authorization
+        .and_then(|value| value.trim().split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+        .or(legacy)
+}
+
+/// Reject requests to `/api/admin/*` that fail `access` with 401.
+fn admin_auth(access: AdminAccess) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and(warp::header::optional::<String>(ADMIN_SECRET_HEADER))
+        .and_then(move |authorization: Option<String>, legacy: Option<String>| {
+            let access = access.clone();
+            async move {
+                let expected = match access {
+                    AdminAccess::Disabled => {
+                        return Err(warp::reject::custom(ApiError::unauthorized(
+                            "Admin endpoints are disabled (no admin token configured)",
+                        )));
+                    }
+                    AdminAccess::TlsOnly(addr) => {
+                        return Err(warp::reject::custom(ApiError::unauthorized(format!(
+                            "Admin endpoints are only served over mTLS on {}",
+                            addr
+                        ))));
+                    }
+                    AdminAccess::ClientCert(None) => return Ok(()),
+                    AdminAccess::Token(token) | AdminAccess::ClientCert(Some(token)) => token,
+                };
+                match presented_token(authorization.as_deref(), legacy.as_deref()) {
+                    Some(given) if secrets_match(&expected, given) => Ok(()),
+                    _ => Err(warp::reject::custom(ApiError::unauthorized(
+                        "Missing or invalid admin token (expected Authorization: Bearer <token>)",
+                    ))),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Compare secrets without short-circuiting on the first differing byte.
+fn secrets_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Body of `POST /api/admin/block` and `POST /api/admin/unblock`.
+#[derive(Debug, serde::Deserialize)]
+struct AdminPeerRequest {
+    peer_id: String,
+}
+
+/// Page size used when `get_peers()` walks the paginated peer list.
+const PEERS_PAGE_SIZE: usize = 200;
+
+/// Largest page `GET /api/peers` will return.
+const MAX_PEERS_PAGE_LIMIT: usize = 1000;
+
+/// Body of `GET /api/peers` as seen by the client.
+///
+/// Registries from before pagination ignore the query and return the whole
+/// list as a plain array, which is treated as a single last page.
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum PeersResponse {
+    Page(PeersPage),
+    Legacy(Vec<PeerInfo>),
+}
+
+/// Query parameters for `GET /api/peers`.
+///
+/// With neither parameter set the full peer list is returned as a plain
+/// array, as before pagination existed.
+#[derive(Debug, Default, serde::Deserialize)]
+struct PeersQuery {
+    cursor: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Encode the last peer ID of a page as an opaque cursor.
+fn encode_peers_cursor(peer_id: &str) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(peer_id)
+}
+
+/// Decode a cursor produced by [`encode_peers_cursor`].
+fn decode_peers_cursor(cursor: &str) -> Option<String> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Liveness and readiness state behind `/healthz` and `/readyz`.
+#[derive(Debug)]
+struct HealthState {
+    started_at: Instant,
+    /// Set once the QUIC address-discovery endpoint is bound (or at once in
+    /// HTTP-only mode)
+    ready: AtomicBool,
+}
+
+impl HealthState {
+    fn new(ready: bool) -> Arc<Self> {
+        Arc::new(Self {
+            started_at: Instant::now(),
+            ready: AtomicBool::new(ready),
+        })
+    }
+}
+
+/// Load balancer probes: `GET /healthz` (liveness) and `GET /readyz`.
+///
+/// Both are unauthenticated and only read counters, never the peer list, so
+/// they stay cheap under dashboard load.
+fn health_routes(
+    store: Arc<PeerStore>,
+    health: Arc<HealthState>,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let liveness_health = Arc::clone(&health);
+    let healthz = warp::path!("healthz").and(warp::get()).map(move || {
+        warp::reply::json(&serde_json::json!({
+            "status": "ok",
+            "version": env!("CARGO_PKG_VERSION"),
+            "peers": store.len(),
+            "uptime_secs": liveness_health.started_at.elapsed().as_secs(),
+        }))
+    });
+    let readyz = warp::path!("readyz").and(warp::get()).map(move || {
+        let (status, code) = if health.ready.load(Ordering::Acquire) {
+            ("ready", warp::http::StatusCode::OK)
+        } else {
+            ("starting", warp::http::StatusCode::SERVICE_UNAVAILABLE)
+        };
+        warp::reply::with_status(
+            warp::reply::json(&serde_json::json!({ "status": status })),
+            code,
+        )
+    });
+    healthz.or(readyz)
 }
 
 /// Registry API server configuration.
@@ -73,6 +300,25 @@ pub struct RegistryConfig {
     pub data_dir: PathBuf,
     /// Whether to enable persistent storage
     pub persistence_enabled: bool,
+    /// Rolling connection success rate below which dashboards get an alert
+    pub success_rate_floor: f64,
+    /// Serve the web dashboard pages and assets (API routes are always served)
+    pub serve_dashboard: bool,
+    /// Persisted connection records older than this are dropped on startup
+    pub history_retention_secs: u64,
+    /// `POST /api/register` requests allowed per client IP per minute (0 = unlimited)
+    pub register_rate: u32,
+    /// `POST /api/heartbeat` requests allowed per client IP per minute (0 = unlimited)
+    pub heartbeat_rate: u32,
+    /// Peer IDs refused with 403 on register and heartbeat (see [`load_blocklist`](crate::registry::load_blocklist))
+    pub blocklist: HashSet<String>,
+    /// Bearer token required by `/api/admin/*` (None disables them unless `admin_tls` is set)
+    pub admin_token: Option<String>,
+    /// Serve `/api/admin/*` only on a separate listener requiring client certificates
+    pub admin_tls: Option<AdminTlsConfig>,
+    /// Reverse proxies whose X-Forwarded-For / X-Real-IP headers are believed
+    /// (empty = always use the socket address)
+    pub trusted_proxies: Vec<IpAddr>,
 }
 
 impl Default for RegistryConfig {
@@ -85,12 +331,24 @@ impl Default for RegistryConfig {
             cleanup_interval_secs: 30,
             data_dir: PathBuf::from("./data"),
             persistence_enabled: true,
+            success_rate_floor: DEFAULT_SUCCESS_RATE_FLOOR,
+            serve_dashboard: true,
+            history_retention_secs: DEFAULT_HISTORY_RETENTION_SECS,
+            register_rate: 30,
+            // Room for ~50 nodes behind one NAT at the 5s heartbeat interval
+            heartbeat_rate: 600,
+            blocklist: HashSet::new(),
+            admin_token: None,
+            admin_tls: None,
+            trusted_proxies: Vec::new(),
         }
     }
 }
 
 /// Start the registry HTTP server.
 pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()> {
+    let health_state = HealthState::new(config.quic_addr.is_none());
+
     // Start QUIC endpoint for address discovery (if configured)
     // This allows test nodes to connect via QUIC and receive OBSERVED_ADDRESS frames
     // to discover their external IP:port before registering with the HTTP API
@@ -115,6 +373,7 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
             &peer_id[..16.min(peer_id.len())],
             quic_addr
         );
+        health_state.ready.store(true, Ordering::Release);
 
         // Spawn a task to keep the endpoint alive and handle incoming connections
         tokio::spawn(async move {
@@ -144,11 +403,13 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
     }
 
     let store = PeerStore::with_ttl(config.ttl_secs);
+    store.set_success_rate_floor(config.success_rate_floor);
 
     // Initialize persistent storage
     let persistence_config = PersistenceConfig {
         data_dir: config.data_dir.clone(),
         enabled: config.persistence_enabled,
+        history_retention_secs: config.history_retention_secs,
         ..Default::default()
     };
     let persistence = PersistentStorage::new(persistence_config);
@@ -156,6 +417,12 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
         tracing::warn!("Failed to initialize persistence: {}", e);
     } else if persistence.is_enabled() {
         tracing::info!("Persistent storage enabled at {:?}", config.data_dir);
+        // Restore connection history so the matrix survives restarts
+        let history = persistence.connection_history().await;
+        if !history.is_empty() {
+            tracing::info!("Restoring {} connection records", history.len());
+            store.restore_connections(history).await;
+        }
     }
 
     // Clone for cleanup task before moving into filter
@@ -170,29 +437,97 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
         warp::any().map(move || Arc::clone(&p))
     };
 
+    let blocklist = Arc::new(Blocklist::new(&config.blocklist));
+    if blocklist.len() > 0 {
+        tracing::info!("Blocking {} peer IDs", blocklist.len());
+    }
+    let blocklist_filter = warp::any().map(move || Arc::clone(&blocklist));
+
+    let trusted_proxies: Arc<[IpAddr]> = config.trusted_proxies.clone().into();
+    let register_limiter = Arc::new(RateLimiter::new(config.register_rate));
+    let heartbeat_limiter = Arc::new(RateLimiter::new(config.heartbeat_rate));
+    let cleanup_limiters = [
+        Arc::clone(&register_limiter),
+        Arc::clone(&heartbeat_limiter),
+    ];
+
     // POST /api/register - Node registration with client IP extraction
     let register = warp::path!("api" / "register")
         .and(warp::post())
+        .and(rate_limit(
+            register_limiter,
+            "register",
+            Arc::clone(&trusted_proxies),
+        ))
         .and(warp::body::json())
         .and(store_filter.clone())
-        .and(warp::header::optional::<String>("X-Forwarded-For"))
-        .and(warp::header::optional::<String>("X-Real-IP"))
-        .and(warp::addr::remote())
+        .and(blocklist_filter.clone())
+        .and(client_ip(Arc::clone(&trusted_proxies)))
         .and_then(handle_register);
 
+    // DELETE /api/register - Node deregistration on graceful shutdown
+    let deregister = warp::path!("api" / "register")
+        .and(warp::delete())
+        .and(warp::body::json())
+        .and(store_filter.clone())
+        .and_then(handle_deregister);
+
     // POST /api/heartbeat - Node heartbeat
     let heartbeat = warp::path!("api" / "heartbeat")
         .and(warp::post())
+        .and(rate_limit(heartbeat_limiter, "heartbeat", trusted_proxies))
         .and(warp::body::json())
         .and(store_filter.clone())
+        .and(blocklist_filter.clone())
         .and_then(handle_heartbeat);
 
     // GET /api/peers - Get peer list
     let peers = warp::path!("api" / "peers")
         .and(warp::get())
+        .and(warp::query::<PeersQuery>())
         .and(store_filter.clone())
+        .and(blocklist_filter.clone())
         .and_then(handle_get_peers);
 
+    // POST /api/admin/block, /api/admin/unblock - Runtime peer blocklist
+    let admin_token: Option<Arc<str>> = config.admin_token.as_deref().map(Arc::from);
+    let admin_access = match (&config.admin_tls, &admin_token) {
+        (Some(tls), _) => AdminAccess::TlsOnly(tls.bind_addr),
+        (None, Some(token)) => AdminAccess::Token(Arc::clone(token)),
+        (None, None) => AdminAccess::Disabled,
+    };
+    match &admin_access {
+        AdminAccess::Disabled => {
+            tracing::info!("Admin endpoints disabled (no admin token configured)")
+        }
+        AdminAccess::TlsOnly(addr) if admin_token.is_none() => tracing::warn!(
+            "Admin endpoints enabled on {} with client certificates only and NO admin token: \
+             anyone holding a certificate from the client CA can block peers",
+            addr
+        ),
+        _ => {}
+    }
+    let admin_routes = |access: AdminAccess| {
+        let admin_block = warp::path!("api" / "admin" / "block")
+            .and(warp::post())
+            .and(admin_auth(access.clone()))
+            .and(warp::body::json())
+            .and(store_filter.clone())
+            .and(blocklist_filter.clone())
+            .and_then(handle_admin_block);
+        let admin_unblock = warp::path!("api" / "admin" / "unblock")
+            .and(warp::post())
+            .and(admin_auth(access))
+            .and(warp::body::json())
+            .and(blocklist_filter.clone())
+            .and_then(handle_admin_unblock);
+        admin_block.or(admin_unblock)
+    };
+    let admin_tls_routes = admin_routes(AdminAccess::ClientCert(admin_token.clone()))
+        .recover(handle_rejection)
+        .with(warp::log("registry::admin"));
+    let admin = admin_routes(admin_access);
+
     // GET /api/stats - Get network statistics
     let stats = warp::path!("api" / "stats")
         .and(warp::get())
@@ -310,15 +645,22 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
         .and(store_filter.clone())
         .and_then(handle_prometheus_metrics);
 
-    // Dashboard routes (serves Three.js globe UI)
-    let dashboard = dashboard_routes(Arc::clone(&cleanup_store));
+    // GET /healthz, /readyz - Load balancer probes
+    let probes = health_routes(Arc::clone(&cleanup_store), health_state);
+
+    // Dashboard routes (serves Three.js globe UI unless disabled)
+    let dashboard = dashboard_routes(Arc::clone(&cleanup_store), config.serve_dashboard);
 
     // Combine all routes
-    // Note: Dashboard routes are first so "/" serves index.html
+    // Note: Probes are matched before the dashboard's static/WebSocket paths;
+    // dashboard routes come next so "/" serves index.html
     // More specific paths must come before less specific ones
-    let routes = dashboard
+    let routes = probes
+        .or(dashboard)
         .or(register)
+        .or(deregister)
         .or(heartbeat)
+        .or(admin)
         .or(connection)
         .or(all_peers)
         .or(peers)
@@ -337,6 +679,7 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
         .or(websocket)
         .or(health)
         .or(prometheus_metrics)
+        .recover(handle_rejection)
         .with(warp::cors().allow_any_origin())
         .with(warp::log("registry"));
 
@@ -350,6 +693,10 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
             if removed > 0 {
                 tracing::info!("Cleaned up {} expired registrations", removed);
             }
+            let pruned: usize = cleanup_limiters.iter().map(|l| l.prune()).sum();
+            if pruned > 0 {
+                tracing::debug!("Pruned {} idle rate-limit buckets", pruned);
+            }
         }
     });
 
@@ -424,6 +771,13 @@ pub async fn start_registry_server(config: RegistryConfig) -> anyhow::Result<()>
         }
     }
 
+    // The mTLS listener comes up first so bad certificate material stops startup
+    if let Some(tls) = &config.admin_tls {
+        let (addr, incoming) = admin_tls::bind(tls).await?;
+        tracing::info!("Admin endpoints served over mTLS on {}", addr);
+        tokio::spawn(warp::serve(admin_tls_routes).run_incoming(incoming));
+    }
+
     // Now warp can safely bind (port was just released)
     warp::serve(routes).run(config.bind_addr).await;
 
@@ -440,23 +794,30 @@ async fn handle_export_data(persistence: Arc<PersistentStorage>) -> Result<impl
 async fn handle_get_events(persistence: Arc<PersistentStorage>) -> Result<impl Reply, Rejection> {
     match persistence.read_events() {
         Ok(events) => Ok(warp::reply::json(&events)),
-        Err(e) => Ok(warp::reply::json(&serde_json::json!({
-            "error": e,
-            "events": []
-        }))),
+        Err(e) => Err(warp::reject::custom(ApiError::internal(e))),
     }
 }
 
+/// Evict a blocked peer from the store and build its 403 rejection.
+fn reject_blocked(store: &PeerStore, peer_id: &str) -> Rejection {
+    let short = &peer_id[..8.min(peer_id.len())];
+    if store.remove_peer(peer_id) {
+        tracing::info!("Evicted blocked peer {}", short);
+    }
+    tracing::warn!("Refused blocked peer {}", short);
+    warp::reject::custom(ApiError::forbidden(format!("Peer {} is blocked", peer_id)))
+}
+
 /// Handle node registration.
 async fn handle_register(
     registration: NodeRegistration,
     store: Arc<PeerStore>,
-    x_forwarded_for: Option<String>,
-    x_real_ip: Option<String>,
-    remote_addr: Option<SocketAddr>,
+    blocklist: Arc<Blocklist>,
+    client_ip: Option<IpAddr>,
 ) -> Result<impl Reply, Rejection> {
-    // Extract the real client IP from headers or remote address
-    let client_ip = extract_client_ip(x_forwarded_for, x_real_ip, remote_addr);
+    if blocklist.contains(&registration.peer_id) {
+        return Err(reject_blocked(&store, &registration.peer_id));
+    }
 
     tracing::info!(
         "Registration from peer {} ({}) client_ip={:?}",
@@ -471,19 +832,27 @@ async fn handle_register(
                 success: true,
                 error: None,
                 peers,
-                expires_in_secs: 120, // 2 minutes
-            };
-            Ok(warp::reply::json(&response))
-        }
-        Err(e) => {
-            let response = RegistrationResponse {
-                success: false,
-                error: Some(e),
-                peers: vec![],
-                expires_in_secs: 0,
+                expires_in_secs: store.ttl_secs(),
+                ttl_secs: Some(store.ttl_secs()),
             };
             Ok(warp::reply::json(&response))
         }
+        Err(e) => Err(warp::reject::custom(ApiError::bad_request(e))),
+    }
+}
+
+/// Handle node deregistration.
+async fn handle_deregister(
+    deregistration: NodeDeregistration,
+    store: Arc<PeerStore>,
+) -> Result<impl Reply, Rejection> {
+    tracing::info!(
+        "Deregistration from peer {}",
+        &deregistration.peer_id[..8.min(deregistration.peer_id.len())]
+    );
+    match store.deregister(&deregistration.peer_id) {
+        Ok(()) => Ok(warp::reply::json(&serde_json::json!({"success": true}))),
+        Err(e) => Err(warp::reject::custom(ApiError::peer_not_found(e))),
     }
 }
 
@@ -491,7 +860,11 @@ async fn handle_register(
 async fn handle_heartbeat(
     heartbeat: NodeHeartbeat,
     store: Arc<PeerStore>,
+    blocklist: Arc<Blocklist>,
 ) -> Result<impl Reply, Rejection> {
+    if blocklist.contains(&heartbeat.peer_id) {
+        return Err(reject_blocked(&store, &heartbeat.peer_id));
+    }
     // Debug: log received gossip stats
     if let Some(ref gs) = heartbeat.gossip_stats {
         tracing::info!(
@@ -504,24 +877,81 @@ async fn handle_heartbeat(
         );
     }
     match store.heartbeat(heartbeat) {
-        Ok(()) => Ok(warp::reply::with_status(
-            warp::reply::json(&serde_json::json!({"success": true})),
-            warp::http::StatusCode::OK,
-        )),
+        Ok(()) => Ok(warp::reply::json(&serde_json::json!({"success": true}))),
         Err(e) => {
             tracing::warn!("Heartbeat failed: {}", e);
-            Ok(warp::reply::with_status(
-                warp::reply::json(&serde_json::json!({"success": false, "error": e})),
-                warp::http::StatusCode::NOT_FOUND,
-            ))
+            Err(warp::reject::custom(ApiError::peer_not_found(e)))
         }
     }
 }
 
 /// Handle get peers request.
-async fn handle_get_peers(store: Arc<PeerStore>) -> Result<impl Reply, Rejection> {
-    let peers = store.get_all_peers();
-    Ok(warp::reply::json(&peers))
+async fn handle_get_peers(
+    query: PeersQuery,
+    store: Arc<PeerStore>,
+    blocklist: Arc<Blocklist>,
+) -> Result<warp::reply::Json, Rejection> {
+    if query.cursor.is_none() && query.limit.is_none() {
+        let mut peers = store.get_all_peers();
+        peers.retain(|p| !blocklist.contains(&p.peer_id));
+        return Ok(warp::reply::json(&peers));
+    }
+
+    let limit = query.limit.unwrap_or(PEERS_PAGE_SIZE);
+    if limit == 0 || limit > MAX_PEERS_PAGE_LIMIT {
+        return Err(warp::reject::custom(ApiError::bad_request(format!(
+            "limit must be between 1 and {}",
+            MAX_PEERS_PAGE_LIMIT
+        ))));
+    }
+    let after = match query.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(cursor) => Some(decode_peers_cursor(cursor).ok_or_else(|| {
+            warp::reject::custom(ApiError::bad_request("Invalid pagination cursor"))
+        })?),
+        None => None,
+    };
+
+    let (mut peers, last) = store.get_peers_page(after.as_deref(), limit);
+    peers.retain(|p| !blocklist.contains(&p.peer_id));
+    Ok(warp::reply::json(&PeersPage {
+        peers,
+        next_cursor: last.as_deref().map(encode_peers_cursor),
+    }))
+}
+
+/// Handle `POST /api/admin/block`: block a peer and evict it if registered.
+async fn handle_admin_block(
+    request: AdminPeerRequest,
+    store: Arc<PeerStore>,
+    blocklist: Arc<Blocklist>,
+) -> Result<impl Reply, Rejection> {
+    let newly_blocked = blocklist.block(&request.peer_id);
+    let removed = store.remove_peer(&request.peer_id);
+    tracing::warn!(
+        "Admin blocked peer {} (evicted: {})",
+        request.peer_id,
+        removed
+    );
+    Ok(warp::reply::json(&serde_json::json!({
+        "success": true,
+        "peer_id": request.peer_id,
+        "newly_blocked": newly_blocked,
+        "removed": removed,
+    })))
+}
+
+/// Handle `POST /api/admin/unblock`.
+async fn handle_admin_unblock(
+    request: AdminPeerRequest,
+    blocklist: Arc<Blocklist>,
+) -> Result<impl Reply, Rejection> {
+    let was_blocked = blocklist.unblock(&request.peer_id);
+    tracing::warn!("Admin unblocked peer {}", request.peer_id);
+    Ok(warp::reply::json(&serde_json::json!({
+        "success": true,
+        "peer_id": request.peer_id,
+        "was_blocked": was_blocked,
+    })))
 }
 
 /// Handle get stats request.
@@ -625,6 +1055,15 @@ async fn handle_get_orchestrator_status(
 
 /// Prometheus-compatible metrics endpoint.
 async fn handle_prometheus_metrics(store: Arc<PeerStore>) -> Result<impl Reply, Rejection> {
+    Ok(warp::reply::with_header(
+        render_prometheus_metrics(&store),
+        "Content-Type",
+        "text/plain; version=0.0.4",
+    ))
+}
+
+/// Render registry state in Prometheus text exposition format.
+fn render_prometheus_metrics(store: &PeerStore) -> String {
     let stats = store.get_stats();
     let breakdown = &stats.connection_breakdown;
 
@@ -632,7 +1071,7 @@ async fn handle_prometheus_metrics(store: Arc<PeerStore>) -> Result<impl Reply,
     let gossip_stats = store.get_gossip_stats();
 
     // Build Prometheus-format metrics
-    let metrics = format!(
+    let mut metrics = format!(
         r#"# HELP ant_quic_active_nodes Number of currently active nodes
 # TYPE ant_quic_active_nodes gauge
 ant_quic_active_nodes {}
@@ -745,11 +1184,43 @@ ant_quic_connections_by_type{{type="relayed"}} {}
         gossip_stats.total_conn_relayed,
     );
 
-    Ok(warp::reply::with_header(
-        metrics,
-        "Content-Type",
-        "text/plain; version=0.0.4",
-    ))
+    // Network-level gauges, named for alerting across the testnet
+    metrics.push_str(&format!(
+        r#"
+# HELP testnet_registered_peers Number of currently registered peers
+# TYPE testnet_registered_peers gauge
+testnet_registered_peers {}
+
+# HELP testnet_connections_total Connections established by method
+# TYPE testnet_connections_total gauge
+testnet_connections_total{{method="direct"}} {}
+testnet_connections_total{{method="hole_punched"}} {}
+testnet_connections_total{{method="relayed"}} {}
+
+# HELP testnet_nat_type Registered peers by NAT type
+# TYPE testnet_nat_type gauge
+"#,
+        stats.active_nodes, breakdown.direct, breakdown.hole_punched, breakdown.relayed,
+    ));
+    let mut nat_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for peer in store.get_all_peers() {
+        *nat_counts.entry(nat_type_label(peer.nat_type)).or_default() += 1;
+    }
+    for (nat_type, count) in nat_counts {
+        metrics.push_str(&format!(
+            "testnet_nat_type{{type=\"{}\"}} {}\n",
+            nat_type, count
+        ));
+    }
+    metrics
+}
+
+/// Prometheus label value for a NAT type (its snake_case wire name).
+fn nat_type_label(nat_type: NatType) -> String {
+    serde_json::to_value(nat_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// Handle connection report from nodes.
@@ -894,10 +1365,10 @@ async fn handle_get_node_detail(
             };
             Ok(warp::reply::json(&response))
         }
-        None => Ok(warp::reply::json(&serde_json::json!({
-            "error": "Node not found",
-            "peer_id": peer_id
-        }))),
+        None => Err(warp::reject::custom(ApiError::peer_not_found(format!(
+            "Node not found: {}",
+            peer_id
+        )))),
     }
 }
 
@@ -1047,27 +1518,348 @@ async fn handle_websocket(ws: warp::ws::WebSocket, store: Arc<PeerStore>) {
     send_task.abort();
 }
 
+/// Retry settings for [`RegistryClient`].
+///
+/// Failed register, heartbeat and peer-list calls are retried with
+/// exponential backoff: the delay doubles from `base_delay` up to
+/// `max_delay`, and half of each delay is randomised so nodes restarted
+/// together do not hammer the registry in lockstep. Client errors (unknown
+/// peer, bad request) are returned immediately.
+#[derive(Debug, Clone)]
+pub struct RegistryClientConfig {
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Total attempts per call, including the first (at least 1)
+    pub max_attempts: u32,
+}
+
+impl Default for RegistryClientConfig {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(4),
+            max_attempts: 4,
+        }
+    }
+}
+
+impl RegistryClientConfig {
+    /// Delay before retry number `retry` (1-based), with random jitter.
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        self.backoff_delay_with_jitter(retry, rand::thread_rng().gen_range(0.0..=1.0))
+    }
+
+    /// Delay before retry number `retry` for a jitter factor in `[0, 1]`.
+    ///
+    /// The result lies between half and all of the capped exponential delay.
+    fn backoff_delay_with_jitter(&self, retry: u32, jitter: f64) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let capped = exponential.min(self.max_delay);
+        capped / 2 + (capped / 2).mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+/// True if a failed registry call is worth retrying.
+///
+/// Transport failures, timeouts, rate limiting and server errors are;
+/// anything the registry rejected as a client error is not.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    if let Some(api) = err.downcast_ref::<ApiError>() {
+        return matches!(
+            api.code,
+            ApiErrorCode::RateLimited | ApiErrorCode::InternalError
+        );
+    }
+    match err.downcast_ref::<reqwest::Error>() {
+        Some(e) => match e.status() {
+            Some(status) => {
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            None => !e.is_decode() && !e.is_builder(),
+        },
+        None => false,
+    }
+}
+
+/// What a successful [`RegistryClient::check`] learned about the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryCheck {
+    /// Server version from `/healthz`, if the registry reports one.
+    pub version: Option<String>,
+    /// Nodes currently active according to `/api/stats`.
+    pub active_nodes: usize,
+    /// Nodes ever registered according to `/api/stats`.
+    pub total_nodes: usize,
+}
+
+/// Why a registry pre-flight check failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryCheckError {
+    /// The host name could not be resolved.
+    Dns(String),
+    /// The TLS handshake failed (bad certificate, or HTTPS to a plain HTTP port).
+    Tls(String),
+    /// The host answered but nothing is listening on the port.
+    ConnectionRefused(String),
+    /// The registry did not answer in time.
+    Timeout(Duration),
+    /// The registry answered with an error status.
+    Http {
+        /// Endpoint path that failed.
+        endpoint: &'static str,
+        /// HTTP status code returned.
+        status: u16,
+    },
+    /// Anything else, e.g. a malformed URL or an unexpected response body.
+    Other(String),
+}
+
+impl std::fmt::Display for RegistryCheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dns(e) => write!(f, "DNS lookup failed, check the host name: {}", e),
+            Self::Tls(e) => write!(
+                f,
+                "TLS handshake failed, check http:// vs https:// and the certificate: {}",
+                e
+            ),
+            Self::ConnectionRefused(e) => write!(
+                f,
+                "connection refused, check the port and that the registry is running: {}",
+                e
+            ),
+            Self::Timeout(after) => write!(f, "no response within {}s", after.as_secs()),
+            Self::Http { endpoint, status } => {
+                write!(
+                    f,
+                    "{} returned HTTP {}, is this a registry?",
+                    endpoint, status
+                )
+            }
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RegistryCheckError {}
+
+impl RegistryCheckError {
+    /// Classify a failed request by walking its error source chain.
+    fn from_request(endpoint: &'static str, err: &reqwest::Error) -> Self {
+        if let Some(status) = err.status() {
+            return Self::Http {
+                endpoint,
+                status: status.as_u16(),
+            };
+        }
+        let mut chain = Vec::new();
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(err);
+        while let Some(e) = source {
+            if e.downcast_ref::<std::io::Error>()
+                .is_some_and(|io| io.kind() == std::io::ErrorKind::ConnectionRefused)
+            {
+                return Self::ConnectionRefused(e.to_string());
+            }
+            chain.push(e.to_string());
+            source = e.source();
+        }
+        let detail = chain.last().cloned().unwrap_or_default();
+        let text = chain.join(": ").to_lowercase();
+        if text.contains("dns error") || text.contains("failed to lookup address") {
+            Self::Dns(detail)
+        } else if ["tls", "certificate", "handshake", "corrupt message"]
+            .iter()
+            .any(|needle| text.contains(needle))
+        {
+            Self::Tls(detail)
+        } else if text.contains("connection refused") {
+            Self::ConnectionRefused(detail)
+        } else {
+            Self::Other(chain.join(": "))
+        }
+    }
+}
+
+/// How often a client that failed over tries its primary registry again.
+pub const PRIMARY_REPROBE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Split a comma-separated list of registry URLs, dropping empty entries
+/// and trailing slashes.
+pub fn parse_registry_urls(registry_url: &str) -> Vec<String> {
+    registry_url
+        .split(',')
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Client for connecting to the registry from nodes.
+///
+/// Takes one registry URL or a comma-separated list. Calls go to the active
+/// registry, initially the first (primary) one. A transport or server error
+/// fails over to the others in order, and whichever answers becomes active.
+/// While on a fallback, the primary is tried again every
+/// [`PRIMARY_REPROBE_INTERVAL`]. Clones share the active registry.
+#[derive(Clone)]
 pub struct RegistryClient {
-    base_url: String,
+    base_urls: Arc<Vec<String>>,
+    active: Arc<AtomicUsize>,
+    /// When the primary was last tried while on a fallback
+    primary_probed_at: Arc<std::sync::Mutex<Option<Instant>>>,
     client: reqwest::Client,
+    config: RegistryClientConfig,
 }
 
 impl RegistryClient {
-    /// Create a new registry client.
+    /// Create a new registry client with default retry settings.
     pub fn new(registry_url: &str) -> Self {
+        Self::with_config(registry_url, RegistryClientConfig::default())
+    }
+
+    /// Create a new registry client with the given retry settings.
+    pub fn with_config(registry_url: &str, config: RegistryClientConfig) -> Self {
+        let mut base_urls = parse_registry_urls(registry_url);
+        if base_urls.is_empty() {
+            base_urls.push(registry_url.trim().to_string());
+        }
         Self {
-            base_url: registry_url.trim_end_matches('/').to_string(),
+            base_urls: Arc::new(base_urls),
+            active: Arc::new(AtomicUsize::new(0)),
+            primary_probed_at: Arc::new(std::sync::Mutex::new(None)),
             client: reqwest::Client::builder()
                 .timeout(Duration::from_secs(10))
                 .build()
                 .expect("failed to create HTTP client"),
+            config,
+        }
+    }
+
+    /// Registry indices to try, in order: the active one first (or the
+    /// primary, when a re-probe is due), then the rest in list order.
+    fn failover_order(&self, now: Instant) -> Vec<usize> {
+        let active = self.active.load(Ordering::Relaxed);
+        let mut first = active;
+        if active != 0 {
+            let mut probed_at = self
+                .primary_probed_at
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if probed_at.is_none_or(|at| now.duration_since(at) >= PRIMARY_REPROBE_INTERVAL) {
+                *probed_at = Some(now);
+                first = 0;
+            }
         }
+        std::iter::once(first)
+            .chain((0..self.base_urls.len()).filter(|&index| index != first))
+            .collect()
     }
 
-    /// Get the base URL of the registry.
+    /// Make registry `index` the active one.
+    fn set_active(&self, index: usize) {
+        let previous = self.active.swap(index, Ordering::Relaxed);
+        if previous == index {
+            return;
+        }
+        tracing::info!(
+            "Registry failover: now using {} (was {})",
+            self.base_urls[index],
+            self.base_urls[previous]
+        );
+        if index != 0 {
+            *self
+                .primary_probed_at
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        }
+    }
+
+    /// Run `call` with each registry's base URL in [`Self::failover_order`]
+    /// until one answers.
+    ///
+    /// Only transport and server errors move on to the next registry. A
+    /// client error is an answer from a working registry, so that registry
+    /// becomes active and the error is returned.
+    async fn with_failover<T, F, Fut>(&self, operation: &str, mut call: F) -> anyhow::Result<T>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        let mut last_error = None;
+        for index in self.failover_order(Instant::now()) {
+            let base_url = &self.base_urls[index];
+            match call(base_url.clone()).await {
+                Err(e) if is_retryable(&e) => {
+                    if self.base_urls.len() > 1 {
+                        tracing::debug!("Registry {} via {} failed: {}", operation, base_url, e);
+                    }
+                    last_error = Some(e);
+                }
+                result => {
+                    self.set_active(index);
+                    return result;
+                }
+            }
+        }
+        Err(last_error.expect("registry client has at least one URL"))
+    }
+
+    /// Run `call` until it succeeds, fails with a non-retryable error, or
+    /// runs out of attempts.
+    async fn with_retry<T, F, Fut>(&self, operation: &str, mut call: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        let max_attempts = self.config.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            match call().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                    let delay = self.config.backoff_delay(attempt);
+                    tracing::debug!(
+                        "Registry {} attempt {}/{} failed, retrying in {:?}: {}",
+                        operation,
+                        attempt,
+                        max_attempts,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    let message = format!(
+                        "Registry {} failed after {} attempt{}: {}",
+                        operation,
+                        attempt,
+                        if attempt == 1 { "" } else { "s" },
+                        e
+                    );
+                    return Err(e.context(message));
+                }
+            }
+        }
+    }
+
+    /// Base URL of the active registry.
     pub fn base_url(&self) -> &str {
-        &self.base_url
+        &self.base_urls[self.active.load(Ordering::Relaxed)]
+    }
+
+    /// Whether a fallback registry is active instead of the primary.
+    pub fn is_failed_over(&self) -> bool {
+        self.active.load(Ordering::Relaxed) != 0
+    }
+
+    /// Every configured registry URL, primary first.
+    pub fn registry_urls(&self) -> &[String] {
+        &self.base_urls
     }
 
     /// Register this node with the registry.
@@ -1075,52 +1867,217 @@ impl RegistryClient {
         &self,
         registration: &NodeRegistration,
     ) -> anyhow::Result<RegistrationResponse> {
-        let url = format!("{}/api/register", self.base_url);
-        let response = self
-            .client
-            .post(&url)
-            .json(registration)
-            .send()
-            .await?
-            .json()
-            .await?;
-        Ok(response)
+        self.with_retry("register", || {
+            self.with_failover("register", |base_url| async move {
+                let url = format!("{}/api/register", base_url);
+                let response = self.client.post(&url).json(registration).send().await?;
+                if let Err(status_error) = response.error_for_status_ref() {
+                    return match response.json::<ApiErrorBody>().await {
+                        Ok(body) => Err(body.error.into()),
+                        Err(_) => Err(status_error.into()),
+                    };
+                }
+                Ok(response.json().await?)
+            })
+        })
+        .await
+    }
+
+    /// Remove this node from the registry ahead of its TTL.
+    pub async fn deregister(&self, peer_id: &str) -> anyhow::Result<()> {
+        let deregistration = NodeDeregistration {
+            peer_id: peer_id.to_string(),
+        };
+        let deregistration = &deregistration;
+        self.with_failover("deregister", |base_url| async move {
+            self.client
+                .delete(format!("{}/api/register", base_url))
+                .json(deregistration)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+        .await
     }
 
     /// Send heartbeat to registry.
     pub async fn heartbeat(&self, heartbeat: &NodeHeartbeat) -> anyhow::Result<()> {
-        let url = format!("{}/api/heartbeat", self.base_url);
-        let response = self.client.post(&url).json(heartbeat).send().await?;
-        // Check for HTTP error status (including 404 for unknown peer)
-        response.error_for_status()?;
+        self.with_retry("heartbeat", || {
+            self.with_failover("heartbeat", |base_url| async move {
+                let url = format!("{}/api/heartbeat", base_url);
+                let response = self.client.post(&url).json(heartbeat).send().await?;
+                // Check for HTTP error status (including 404 for unknown peer)
+                response.error_for_status()?;
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    /// POST an anonymized telemetry record to `url`.
+    ///
+    /// Sent once without retries; a missed upload is simply skipped.
+    pub async fn upload_telemetry(
+        &self,
+        url: &str,
+        record: &TelemetryRecord,
+    ) -> anyhow::Result<()> {
+        self.client
+            .post(url)
+            .json(record)
+            .send()
+            .await?
+            .error_for_status()?;
         Ok(())
     }
 
     /// Get list of peers from registry.
+    ///
+    /// Walks the paginated peer list so no single response grows with the
+    /// size of the network. Each page is retried on its own, so a transient
+    /// failure does not restart the walk.
     pub async fn get_peers(&self) -> anyhow::Result<Vec<PeerInfo>> {
-        let url = format!("{}/api/peers", self.base_url);
-        let peers = self.client.get(&url).send().await?.json().await?;
-        Ok(peers)
+        let mut peers = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = self
+                .with_retry("peer list", || {
+                    self.get_peers_paginated(cursor.clone(), PEERS_PAGE_SIZE)
+                })
+                .await?;
+            peers.extend(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => return Ok(peers),
+            }
+        }
+    }
+
+    /// Get one page of peers, ordered by peer ID.
+    ///
+    /// Pass `None` for the first page and the returned cursor for the next
+    /// one; the cursor is `None` once the last page has been returned.
+    pub async fn get_peers_paginated(
+        &self,
+        cursor: Option<String>,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<PeerInfo>, Option<String>)> {
+        let mut query = vec![("limit", limit.to_string())];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor));
+        }
+        let response: PeersResponse = self
+            .with_failover("peer list", |base_url| {
+                let query = &query;
+                async move {
+                    let response = self
+                        .client
+                        .get(format!("{}/api/peers", base_url))
+                        .query(query)
+                        .send()
+                        .await?;
+                    if response.status() == reqwest::StatusCode::NOT_FOUND {
+                        tracing::debug!("Registry {} does not serve a peer list", base_url);
+                        return Ok(PeersResponse::Legacy(Vec::new()));
+                    }
+                    Ok(response.error_for_status()?.json().await?)
+                }
+            })
+            .await?;
+        Ok(match response {
+            PeersResponse::Page(page) => (page.peers, page.next_cursor),
+            PeersResponse::Legacy(peers) => (peers, None),
+        })
     }
 
     /// Get network statistics from registry.
     pub async fn get_stats(&self) -> anyhow::Result<NetworkStats> {
-        let url = format!("{}/api/stats", self.base_url);
-        let stats = self.client.get(&url).send().await?.json().await?;
-        Ok(stats)
+        self.with_failover("stats", |base_url| async move {
+            let url = format!("{}/api/stats", base_url);
+            Ok(self.client.get(&url).send().await?.json().await?)
+        })
+        .await
+    }
+
+    /// Pre-flight check: fetch `/healthz` and `/api/stats` within `timeout`.
+    ///
+    /// Unlike the other calls this never retries, so a mistyped URL fails
+    /// fast with a classified error.
+    pub async fn check(&self, timeout: Duration) -> Result<RegistryCheck, RegistryCheckError> {
+        let probe = async {
+            let health: serde_json::Value = self
+                .fetch_for_check("/healthz")
+                .await?
+                .json()
+                .await
+                .map_err(|e| RegistryCheckError::Other(format!("bad /healthz body: {}", e)))?;
+            let stats: NetworkStats = self
+                .fetch_for_check("/api/stats")
+                .await?
+                .json()
+                .await
+                .map_err(|e| RegistryCheckError::Other(format!("bad /api/stats body: {}", e)))?;
+            Ok(RegistryCheck {
+                version: health["version"].as_str().map(str::to_string),
+                active_nodes: stats.active_nodes,
+                total_nodes: stats.total_nodes,
+            })
+        };
+        tokio::time::timeout(timeout, probe)
+            .await
+            .unwrap_or(Err(RegistryCheckError::Timeout(timeout)))
+    }
+
+    async fn fetch_for_check(
+        &self,
+        endpoint: &'static str,
+    ) -> Result<reqwest::Response, RegistryCheckError> {
+        let url = format!("{}{}", self.base_url(), endpoint);
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|e| {
+                if e.is_builder() {
+                    RegistryCheckError::Other(format!("invalid registry URL {:?}: {}", url, e))
+                } else {
+                    RegistryCheckError::from_request(endpoint, &e)
+                }
+            })
+    }
+
+    /// Get experiment results, including every recorded connection.
+    pub async fn get_results(&self) -> anyhow::Result<ExperimentResults> {
+        self.with_failover("results", |base_url| async move {
+            Ok(self
+                .client
+                .get(format!("{}/api/results", base_url))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?)
+        })
+        .await
     }
 
     /// Report a connection to the registry.
     pub async fn report_connection(&self, report: &ConnectionReport) -> anyhow::Result<()> {
-        let url = format!("{}/api/connection", self.base_url);
-        self.client.post(&url).json(report).send().await?;
-        Ok(())
+        self.with_failover("connection report", |base_url| async move {
+            let url = format!("{}/api/connection", base_url);
+            self.client.post(&url).json(report).send().await?;
+            Ok(())
+        })
+        .await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::ApiErrorCode;
 
     #[tokio::test]
     async fn test_registration_response_serialization() {
@@ -1129,6 +2086,7 @@ mod tests {
             error: None,
             peers: vec![],
             expires_in_secs: 120,
+            ttl_secs: Some(120),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -1138,10 +2096,707 @@ mod tests {
     #[test]
     fn test_registry_client_creation() {
         let client = RegistryClient::new("https://saorsa-1.saorsalabs.com");
-        assert_eq!(client.base_url, "https://saorsa-1.saorsalabs.com");
+        assert_eq!(client.base_url(), "https://saorsa-1.saorsalabs.com");
 
         // Test trailing slash handling
         let client2 = RegistryClient::new("https://saorsa-1.saorsalabs.com/");
-        assert_eq!(client2.base_url, "https://saorsa-1.saorsalabs.com");
+        assert_eq!(client2.base_url(), "https://saorsa-1.saorsalabs.com");
+
+        let client3 = RegistryClient::new(" https://a.example/, ,https://b.example ");
+        assert_eq!(
+            client3.registry_urls(),
+            ["https://a.example", "https://b.example"]
+        );
+        assert_eq!(client3.base_url(), "https://a.example");
+    }
+
+    #[tokio::test]
+    async fn test_client_fails_over_between_registries() {
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let down = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+        let (up, hits) = flaky_peers_server(warp::http::StatusCode::OK, 0);
+
+        let client = RegistryClient::with_config(
+            &format!("{},{}", down, up),
+            RegistryClientConfig {
+                max_attempts: 1,
+                ..Default::default()
+            },
+        );
+        client.get_peers().await.unwrap();
+        assert_eq!(client.base_url(), up);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Clones share the active registry, and stay on it until the
+        // primary is due to be probed again
+        let clone = client.clone();
+        assert_eq!(clone.failover_order(Instant::now()), vec![1, 0]);
+        let later = Instant::now() + PRIMARY_REPROBE_INTERVAL;
+        assert_eq!(clone.failover_order(later), vec![0, 1]);
+        assert_eq!(clone.failover_order(later), vec![1, 0]);
+
+        // A client error is an answer: no failover past a working registry
+        let (rejecting, _) = flaky_peers_server(warp::http::StatusCode::BAD_REQUEST, 1);
+        let client = RegistryClient::new(&format!("{},{}", rejecting, up));
+        assert!(client.get_peers().await.is_err());
+        assert_eq!(client.base_url(), rejecting);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_up_to_cap() {
+        let config = RegistryClientConfig {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            max_attempts: 6,
+        };
+        let full: Vec<u128> = (1..=5)
+            .map(|retry| config.backoff_delay_with_jitter(retry, 1.0).as_millis())
+            .collect();
+        assert_eq!(full, vec![100, 200, 400, 800, 1000]);
+        // Jitter only ever shaves off up to half the delay
+        assert_eq!(
+            config.backoff_delay_with_jitter(3, 0.0),
+            Duration::from_millis(200)
+        );
+        for _ in 0..20 {
+            let delay = config.backoff_delay(3);
+            assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+        }
+    }
+
+    /// Serve `/api/peers`, answering with `status` for the first `failures`
+    /// requests and an empty page afterwards. Returns the base URL and hit count.
+    fn flaky_peers_server(
+        status: warp::http::StatusCode,
+        failures: usize,
+    ) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        let route = warp::path!("api" / "peers").map(move || {
+            if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                warp::reply::with_status(warp::reply::json(&serde_json::json!({})), status)
+            } else {
+                let page = PeersPage {
+                    peers: Vec::new(),
+                    next_cursor: None,
+                };
+                warp::reply::with_status(warp::reply::json(&page), warp::http::StatusCode::OK)
+            }
+        });
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        (format!("http://{}", addr), hits)
+    }
+
+    #[tokio::test]
+    async fn test_registry_check_classifies_failures() {
+        let store = PeerStore::new();
+        let stats_store = Arc::clone(&store);
+        let route = health_routes(store, HealthState::new(true)).or(warp::path!("api" / "stats")
+            .and(warp::any().map(move || Arc::clone(&stats_store)))
+            .and_then(handle_get_stats));
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let timeout = Duration::from_secs(5);
+
+        let check = RegistryClient::new(&format!("http://{}", addr))
+            .check(timeout)
+            .await
+            .unwrap();
+        assert_eq!(check.version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+        assert_eq!(check.active_nodes, 0);
+
+        // HTTPS against the plain HTTP listener
+        let err = RegistryClient::new(&format!("https://{}", addr))
+            .check(timeout)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RegistryCheckError::Tls(_)), "{:?}", err);
+
+        // A port nothing listens on
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_addr = closed.local_addr().unwrap();
+        drop(closed);
+        let err = RegistryClient::new(&format!("http://{}", closed_addr))
+            .check(timeout)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, RegistryCheckError::ConnectionRefused(_)),
+            "{:?}",
+            err
+        );
+
+        // Something that is not a registry
+        let (addr, server) =
+            warp::serve(warp::any().map(warp::reply)).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let err = RegistryClient::new(&format!("http://{}", addr))
+            .check(timeout)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RegistryCheckError::Other(_)), "{:?}", err);
+
+        // Healthy but missing the stats endpoint
+        let healthz_only = warp::path!("healthz")
+            .map(|| warp::reply::json(&serde_json::json!({ "status": "ok" })));
+        let (addr, server) = warp::serve(healthz_only).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let err = RegistryClient::new(&format!("http://{}", addr))
+            .check(timeout)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RegistryCheckError::Http {
+                endpoint: "/api/stats",
+                status: 404
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_retries_transient_failures_only() {
+        use std::sync::atomic::Ordering;
+        let config = RegistryClientConfig {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            max_attempts: 3,
+        };
+
+        // Two 503s, then success on the third attempt
+        let (url, hits) = flaky_peers_server(warp::http::StatusCode::SERVICE_UNAVAILABLE, 2);
+        let client = RegistryClient::with_config(&url, config.clone());
+        assert!(client.get_peers().await.unwrap().is_empty());
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // Still failing after the last attempt: the error says how many
+        let (url, hits) = flaky_peers_server(warp::http::StatusCode::SERVICE_UNAVAILABLE, 10);
+        let client = RegistryClient::with_config(&url, config.clone());
+        let err = client.get_peers().await.unwrap_err();
+        assert!(
+            err.to_string().contains("failed after 3 attempts"),
+            "{}",
+            err
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // Client errors are not retried
+        let (url, hits) = flaky_peers_server(warp::http::StatusCode::BAD_REQUEST, 10);
+        let client = RegistryClient::with_config(&url, config);
+        let err = client.get_peers().await.unwrap_err();
+        assert!(
+            err.to_string().contains("failed after 1 attempt:"),
+            "{}",
+            err
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_metrics_expose_testnet_gauges() {
+        let store = PeerStore::new();
+        for (peer_id, nat_type) in [
+            ("aaaa1111", NatType::Symmetric),
+            ("bbbb2222", NatType::Symmetric),
+            ("cccc3333", NatType::FullCone),
+        ] {
+            store
+                .register(NodeRegistration {
+                    peer_id: peer_id.to_string(),
+                    public_key: "test_key".to_string(),
+                    listen_addresses: vec!["127.0.0.1:9000".parse().unwrap()],
+                    external_addresses: vec!["203.0.113.1:9000".parse().unwrap()],
+                    nat_type,
+                    version: "0.14.1".to_string(),
+                    capabilities: Default::default(),
+                    location_label: None,
+                })
+                .unwrap();
+        }
+
+        let route = warp::path!("metrics")
+            .and(warp::get())
+            .and(warp::any().map(move || Arc::clone(&store)))
+            .and_then(handle_prometheus_metrics);
+        let res = warp::test::request().path("/metrics").reply(&route).await;
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers()["content-type"], "text/plain; version=0.0.4");
+
+        let body = String::from_utf8(res.body().to_vec()).unwrap();
+        let lines: Vec<&str> = body.lines().collect();
+        assert!(lines.contains(&"testnet_registered_peers 3"));
+        assert!(lines.contains(&"testnet_connections_total{method=\"direct\"} 0"));
+        assert!(lines.contains(&"testnet_connections_total{method=\"relayed\"} 0"));
+        assert!(lines.contains(&"testnet_nat_type{type=\"symmetric\"} 2"));
+        assert!(lines.contains(&"testnet_nat_type{type=\"full_cone\"} 1"));
+        assert!(lines.contains(&"# TYPE testnet_nat_type gauge"));
+    }
+
+    #[tokio::test]
+    async fn test_get_peers_from_registry_without_pagination() {
+        // A registry from before pagination: the full list as a plain array,
+        // with fields added since then missing
+        let legacy = warp::path!("api" / "peers").map(|| {
+            warp::reply::json(&serde_json::json!([{
+                "peer_id": "aa01",
+                "addresses": ["203.0.113.1:9000"],
+                "nat_type": "full_cone",
+                "country_code": null,
+                "latitude": 0.0,
+                "longitude": 0.0,
+                "last_seen": 0,
+                "connection_success_rate": 1.0,
+                "capabilities": crate::registry::types::NodeCapabilities::default(),
+                "version": "0.10.0",
+                "is_active": true,
+            }]))
+        });
+        let (addr, server) = warp::serve(legacy).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let peers = RegistryClient::new(&format!("http://{}", addr))
+            .get_peers()
+            .await
+            .unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].peer_id, "aa01");
+        assert!(!peers[0].location_estimated);
+
+        // A registry with no peer list at all
+        let missing = warp::path!("api" / "stats").map(warp::reply);
+        let (addr, server) = warp::serve(missing).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+        let peers = RegistryClient::new(&format!("http://{}", addr))
+            .get_peers()
+            .await
+            .unwrap();
+        assert!(peers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_peers_paginates_with_cursor() {
+        let store = PeerStore::new();
+        for id in ["aa01", "aa02", "aa03"] {
+            store
+                .register(NodeRegistration {
+                    peer_id: id.to_string(),
+                    public_key: "test_key".to_string(),
+                    listen_addresses: vec!["127.0.0.1:9000".parse().unwrap()],
+                    external_addresses: vec!["203.0.113.1:9000".parse().unwrap()],
+                    nat_type: NatType::FullCone,
+                    version: "0.14.1".to_string(),
+                    capabilities: Default::default(),
+                    location_label: None,
+                })
+                .unwrap();
+        }
+        let route = warp::path!("api" / "peers")
+            .and(warp::get())
+            .and(warp::query::<PeersQuery>())
+            .and(warp::any().map(move || Arc::clone(&store)))
+            .and(warp::any().map(|| Arc::new(Blocklist::default())))
+            .and_then(handle_get_peers)
+            .recover(handle_rejection);
+
+        // No query: the full list as a plain array
+        let res = warp::test::request().path("/api/peers").reply(&route).await;
+        let all: Vec<PeerInfo> = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let res = warp::test::request()
+            .path("/api/peers?limit=2")
+            .reply(&route)
+            .await;
+        let first: PeersPage = serde_json::from_slice(res.body()).unwrap();
+        let ids: Vec<&str> = first.peers.iter().map(|p| p.peer_id.as_str()).collect();
+        assert_eq!(ids, vec!["aa01", "aa02"]);
+        let cursor = first.next_cursor.expect("more peers remain");
+        assert_eq!(decode_peers_cursor(&cursor).as_deref(), Some("aa02"));
+
+        let res = warp::test::request()
+            .path(&format!("/api/peers?limit=2&cursor={}", cursor))
+            .reply(&route)
+            .await;
+        let second: PeersPage = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(second.peers.len(), 1);
+        assert_eq!(second.peers[0].peer_id, "aa03");
+        assert!(second.next_cursor.is_none());
+
+        let res = warp::test::request()
+            .path("/api/peers?cursor=%%%")
+            .reply(&route)
+            .await;
+        assert_eq!(
+            error_of(res),
+            (400, ApiError::bad_request("Invalid pagination cursor"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_probes() {
+        let store = PeerStore::new();
+        let health = HealthState::new(false);
+        let route = health_routes(store, Arc::clone(&health));
+
+        let res = warp::test::request().path("/healthz").reply(&route).await;
+        assert_eq!(res.status(), 200);
+        let body: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["peers"], 0);
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        assert!(body["uptime_secs"].is_u64());
+
+        let res = warp::test::request().path("/readyz").reply(&route).await;
+        assert_eq!(res.status(), 503);
+        health.ready.store(true, Ordering::Release);
+        let res = warp::test::request().path("/readyz").reply(&route).await;
+        assert_eq!(res.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_rate_limited_per_ip() {
+        let proxy: SocketAddr = "127.0.0.1:40000".parse().unwrap();
+        let limiter = Arc::new(RateLimiter::new(1));
+        let route = warp::path!("api" / "heartbeat")
+            .and(warp::post())
+            .and(rate_limit(limiter, "heartbeat", Arc::from([proxy.ip()])))
+            .map(warp::reply)
+            .recover(handle_rejection);
+        let request = |ip: &str| {
+            warp::test::request()
+                .method("POST")
+                .path("/api/heartbeat")
+                .remote_addr(proxy)
+                .header("X-Real-IP", ip)
+        };
+
+        assert_eq!(request("203.0.113.7").reply(&route).await.status(), 200);
+        let res = request("203.0.113.7").reply(&route).await;
+        assert_eq!(res.headers()["retry-after"], "60");
+        assert_eq!(error_of(res).1.code, ApiErrorCode::RateLimited);
+        assert_eq!(request("203.0.113.8").reply(&route).await.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_ignores_forwarded_headers_from_untrusted_peers() {
+        let limiter = Arc::new(RateLimiter::new(1));
+        let route = warp::path!("api" / "heartbeat")
+            .and(warp::post())
+            .and(rate_limit(limiter, "heartbeat", Arc::from([])))
+            .map(warp::reply)
+            .recover(handle_rejection);
+        let request = |spoofed: &str| {
+            warp::test::request()
+                .method("POST")
+                .path("/api/heartbeat")
+                .remote_addr("198.51.100.9:5000".parse().unwrap())
+                .header("X-Forwarded-For", spoofed)
+                .header("X-Real-IP", spoofed)
+        };
+
+        assert_eq!(request("203.0.113.1").reply(&route).await.status(), 200);
+        let res = request("203.0.113.2").reply(&route).await;
+        assert_eq!(error_of(res).1.code, ApiErrorCode::RateLimited);
+    }
+
+    #[test]
+    fn test_client_ip_skips_trusted_hops_in_forwarded_chain() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let remote = Some(SocketAddr::new(proxy, 443));
+        let chain = Some("1.2.3.4, 203.0.113.5, 10.0.0.1".to_string());
+
+        assert_eq!(
+            extract_client_ip(chain.clone(), None, remote, &[proxy]),
+            Some("203.0.113.5".parse().unwrap())
+        );
+        assert_eq!(extract_client_ip(chain, None, remote, &[]), Some(proxy));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_peers_refused_evicted_and_hidden() {
+        let store = PeerStore::new();
+        let blocklist = Arc::new(Blocklist::new(&HashSet::from(["bad00001".to_string()])));
+        let store_filter = {
+            let store = Arc::clone(&store);
+            warp::any().map(move || Arc::clone(&store))
+        };
+        let blocklist_filter = warp::any().map(move || Arc::clone(&blocklist));
+        let register = warp::path!("api" / "register")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(store_filter.clone())
+            .and(blocklist_filter.clone())
+            .and(client_ip(Arc::from([])))
+            .and_then(handle_register);
+        let heartbeat = warp::path!("api" / "heartbeat")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(store_filter.clone())
+            .and(blocklist_filter.clone())
+            .and_then(handle_heartbeat);
+        let peers = warp::path!("api" / "peers")
+            .and(warp::get())
+            .and(warp::query::<PeersQuery>())
+            .and(store_filter.clone())
+            .and(blocklist_filter.clone())
+            .and_then(handle_get_peers);
+        let admin_block = warp::path!("api" / "admin" / "block")
+            .and(warp::post())
+            .and(admin_auth(AdminAccess::Token(Arc::from("s3cret"))))
+            .and(warp::body::json())
+            .and(store_filter)
+            .and(blocklist_filter.clone())
+            .and_then(handle_admin_block);
+        let admin_unblock = warp::path!("api" / "admin" / "unblock")
+            .and(warp::post())
+            .and(admin_auth(AdminAccess::Token(Arc::from("s3cret"))))
+            .and(warp::body::json())
+            .and(blocklist_filter)
+            .and_then(handle_admin_unblock);
+        let routes = register
+            .or(heartbeat)
+            .or(peers)
+            .or(admin_block)
+            .or(admin_unblock)
+            .recover(handle_rejection);
+
+        let registration = |peer_id: &str| NodeRegistration {
+            peer_id: peer_id.to_string(),
+            public_key: "test_key".to_string(),
+            listen_addresses: vec!["127.0.0.1:9000".parse().unwrap()],
+            external_addresses: vec!["203.0.113.1:9000".parse().unwrap()],
+            nat_type: NatType::FullCone,
+            version: "0.14.1".to_string(),
+            capabilities: Default::default(),
+            location_label: None,
+        };
+        let register = |peer_id: &str| {
+            warp::test::request()
+                .method("POST")
+                .path("/api/register")
+                .json(&registration(peer_id))
+        };
+        let admin = |action: &str, token: &str, peer_id: &str| {
+            warp::test::request()
+                .method("POST")
+                .path(&format!("/api/admin/{}", action))
+                .header("authorization", format!("Bearer {}", token))
+                .json(&serde_json::json!({ "peer_id": peer_id }))
+        };
+        let peer_ids = |res: warp::http::Response<warp::hyper::body::Bytes>| {
+            let peers: Vec<PeerInfo> = serde_json::from_slice(res.body()).unwrap();
+            peers.into_iter().map(|p| p.peer_id).collect::<Vec<_>>()
+        };
+
+        // Blocked from the config file: refused with 403 (case-insensitively)
+        let (status, err) = error_of(register("BAD00001").reply(&routes).await);
+        assert_eq!((status, err.code), (403, ApiErrorCode::Forbidden));
+        assert_eq!(register("900d0001").reply(&routes).await.status(), 200);
+        assert_eq!(register("900d0002").reply(&routes).await.status(), 200);
+
+        // The admin endpoints need the token
+        let (status, _) = error_of(admin("block", "wrong", "900d0002").reply(&routes).await);
+        assert_eq!(status, 401);
+        let res = warp::test::request()
+            .method("POST")
+            .path("/api/admin/block")
+            .json(&serde_json::json!({ "peer_id": "900d0002" }))
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), 401);
+        let res = admin("block", "s3cret", "900d0002").reply(&routes).await;
+        assert_eq!(res.status(), 200);
+        let body: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(body["removed"], true);
+
+        // Evicted: gone from /api/peers and its heartbeats are refused
+        let res = warp::test::request()
+            .path("/api/peers")
+            .reply(&routes)
+            .await;
+        assert_eq!(peer_ids(res), vec!["900d0001"]);
+        assert!(store.first_seen("900d0002").is_none());
+        let res = warp::test::request()
+            .method("POST")
+            .path("/api/heartbeat")
+            .json(&serde_json::json!({
+                "peer_id": "900d0002",
+                "connected_peers": 0,
+                "bytes_sent": 0,
+                "bytes_received": 0
+            }))
+            .reply(&routes)
+            .await;
+        assert_eq!(error_of(res).1.code, ApiErrorCode::Forbidden);
+
+        // Unblocked peers may register again
+        let res = admin("unblock", "s3cret", "900d0002").reply(&routes).await;
+        let body: serde_json::Value = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(body["was_blocked"], true);
+        assert_eq!(register("900d0002").reply(&routes).await.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_admin_access_modes() {
+        let status = |access: AdminAccess, header: Option<(&'static str, &'static str)>| async move {
+            let route = warp::path!("api" / "admin" / "block")
+                .and(admin_auth(access))
+                .map(warp::reply)
+                .recover(handle_rejection);
+            let mut req = warp::test::request()
+                .method("POST")
+                .path("/api/admin/block");
+            if let Some((name, value)) = header {
+                req = req.header(name, value);
+            }
+            let res = req.reply(&route).await;
+            if res.status() == 200 {
+                return (200, String::new());
+            }
+            let (status, err) = error_of(res);
+            assert_eq!(err.code, ApiErrorCode::Unauthorized);
+            (status, err.message)
+        };
+        let token = || AdminAccess::Token(Arc::from("s3cret"));
+
+        let (code, message) = status(AdminAccess::Disabled, Some((ADMIN_SECRET_HEADER, ""))).await;
+        assert_eq!(code, 401);
+        assert!(message.contains("disabled"));
+
+        assert_eq!(
+            status(token(), Some(("authorization", "Bearer s3cret")))
+                .await
+                .0,
+            200
+        );
+        assert_eq!(
+            status(token(), Some(("authorization", "bearer  s3cret")))
+                .await
+                .0,
+            200
+        );
+        assert_eq!(
+            status(token(), Some((ADMIN_SECRET_HEADER, "s3cret")))
+                .await
+                .0,
+            200
+        );
+        assert_eq!(
+            status(token(), Some(("authorization", "Basic s3cret")))
+                .await
+                .0,
+            401
+        );
+        assert_eq!(
+            status(token(), Some(("authorization", "Bearer s3cre")))
+                .await
+                .0,
+            401
+        );
+        assert_eq!(status(token(), None).await.0, 401);
+
+        // Behind verified client certs the token is optional but still enforced if set
+        assert_eq!(status(AdminAccess::ClientCert(None), None).await.0, 200);
+        let cert_and_token = AdminAccess::ClientCert(Some(Arc::from("s3cret")));
+        assert_eq!(status(cert_and_token, None).await.0, 401);
+
+        // The plain listener points callers at the mTLS one
+        let tls_only = AdminAccess::TlsOnly("127.0.0.1:8443".parse().unwrap());
+        let (code, message) = status(tls_only, Some(("authorization", "Bearer s3cret"))).await;
+        assert_eq!(code, 401);
+        assert!(message.contains("127.0.0.1:8443"));
+
+        assert!(secrets_match("abc", "abc"));
+        assert!(!secrets_match("abc", "abd"));
+        assert!(!secrets_match("abc", "abcd"));
+    }
+
+    fn error_of(res: warp::http::Response<warp::hyper::body::Bytes>) -> (u16, ApiError) {
+        let body: ApiErrorBody = serde_json::from_slice(res.body()).expect("uniform error body");
+        (res.status().as_u16(), body.error)
+    }
+
+    #[tokio::test]
+    async fn test_api_errors_use_uniform_body() {
+        let store = PeerStore::new();
+        let store_filter = warp::any().map(move || Arc::clone(&store));
+        let heartbeat = warp::path!("api" / "heartbeat")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(store_filter.clone())
+            .and(warp::any().map(|| Arc::new(Blocklist::default())))
+            .and_then(handle_heartbeat);
+        let node_detail = warp::path!("api" / "node" / String)
+            .and(warp::get())
+            .and(store_filter)
+            .and_then(handle_get_node_detail);
+        let guarded = warp::path!("api" / "guarded" / String).and_then(|why: String| async move {
+            let err = match why.as_str() {
+                "auth" => ApiError::unauthorized("Missing API token"),
+                _ => ApiError::rate_limited("Slow down"),
+            };
+            Err::<String, _>(warp::reject::custom(err))
+        });
+        let routes = heartbeat
+            .or(node_detail)
+            .or(guarded)
+            .recover(handle_rejection);
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/api/heartbeat")
+            .json(&serde_json::json!({
+                "peer_id": "a3b7c9d2e1f00000",
+                "connected_peers": 0,
+                "bytes_sent": 0,
+                "bytes_received": 0
+            }))
+            .reply(&routes)
+            .await;
+        let (status, err) = error_of(res);
+        assert_eq!((status, err.code), (404, ApiErrorCode::PeerNotFound));
+        assert!(err.message.contains("a3b7c9d2e1f00000"));
+
+        let res = warp::test::request()
+            .path("/api/node/deadbeef")
+            .reply(&routes)
+            .await;
+        assert_eq!(error_of(res).1.code, ApiErrorCode::PeerNotFound);
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/api/heartbeat")
+            .header("content-type", "application/json")
+            .body("{not json")
+            .reply(&routes)
+            .await;
+        let (status, err) = error_of(res);
+        assert_eq!((status, err.code), (400, ApiErrorCode::BadRequest));
+
+        let res = warp::test::request()
+            .path("/api/guarded/auth")
+            .reply(&routes)
+            .await;
+        let (status, err) = error_of(res);
+        assert_eq!((status, err.code), (401, ApiErrorCode::Unauthorized));
+
+        let res = warp::test::request()
+            .path("/api/guarded/flood")
+            .reply(&routes)
+            .await;
+        let (status, err) = error_of(res);
+        assert_eq!((status, err.code), (429, ApiErrorCode::RateLimited));
+
+        let raw = serde_json::to_value(ApiErrorBody {
+            error: ApiError::rate_limited("Slow down"),
+        })
+        .unwrap();
+        assert_eq!(
+            raw,
+            serde_json::json!({"error": {"code": "rate_limited", "message": "Slow down"}})
+        );
     }
 }
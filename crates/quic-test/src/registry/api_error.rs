@@ -0,0 +1,163 @@
+//! Uniform error responses for the registry HTTP API.
+//!
+//! Every failed API request answers with the same JSON body:
+//!
+//! ```json
+//! { "error": { "code": "peer_not_found", "message": "Unknown peer: a3b7..." } }
+//! ```
+//!
+//! Handlers reject with an [`ApiError`]; [`handle_rejection`] turns it (and
+//! warp's built-in rejections) into the response, so clients can branch on
+//! `code` instead of parsing messages.
+
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::time::Duration;
+use warp::http::StatusCode;
+use warp::{Rejection, Reply};
+
+/// Machine-readable error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    /// The referenced peer is not registered.
+    PeerNotFound,
+    /// Missing or invalid credentials.
+    Unauthorized,
+    /// The caller is known but not allowed to do this.
+    Forbidden,
+    /// Too many requests from this client.
+    RateLimited,
+    /// Malformed body, query or parameters.
+    BadRequest,
+    /// No route matches the request path.
+    NotFound,
+    /// The route exists but not for this HTTP method.
+    MethodNotAllowed,
+    /// The registry failed to serve an otherwise valid request.
+    InternalError,
+}
+
+impl ApiErrorCode {
+    /// HTTP status sent with this code.
+    pub fn status(self) -> StatusCode {
+        match self {
+            ApiErrorCode::PeerNotFound | ApiErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ApiErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ApiErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ApiErrorCode::BadRequest => StatusCode::BAD_REQUEST,
+            ApiErrorCode::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            ApiErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// An API error: a code plus a human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: ApiErrorCode,
+    pub message: String,
+    /// Sent as the `Retry-After` header (whole seconds), not in the body.
+    #[serde(skip)]
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    pub fn new(code: ApiErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            retry_after: None,
+        }
+    }
+
+    /// Tell the client when to retry (rounded up to whole seconds).
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
+    pub fn peer_not_found(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::PeerNotFound, message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::Unauthorized, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::Forbidden, message)
+    }
+
+    pub fn rate_limited(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::RateLimited, message)
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::BadRequest, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ApiErrorCode::InternalError, message)
+    }
+
+    /// Build the JSON reply with the matching HTTP status.
+    pub fn into_reply(self) -> warp::reply::Response {
+        let status = self.code.status();
+        let retry_after = self.retry_after;
+        let mut response =
+            warp::reply::with_status(warp::reply::json(&ApiErrorBody { error: self }), status)
+                .into_response();
+        if let Some(retry_after) = retry_after {
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(warp::http::header::RETRY_AFTER, secs.max(1).into());
+        }
+        response
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl warp::reject::Reject for ApiError {}
+
+/// Wire format of an error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    pub error: ApiError,
+}
+
+/// Recover handler mapping rejections to the uniform error body.
+pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
+    let error = if let Some(e) = err.find::<ApiError>() {
+        e.clone()
+    } else if err.is_not_found() {
+        ApiError::new(ApiErrorCode::NotFound, "No such endpoint")
+    } else if let Some(e) = err.find::<warp::filters::body::BodyDeserializeError>() {
+        ApiError::bad_request(e.to_string())
+    } else if let Some(e) = err.find::<warp::reject::InvalidQuery>() {
+        ApiError::bad_request(e.to_string())
+    } else if let Some(e) = err.find::<warp::reject::InvalidHeader>() {
+        ApiError::bad_request(e.to_string())
+    } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
+        ApiError::bad_request(e.to_string())
+    } else if let Some(e) = err.find::<warp::reject::UnsupportedMediaType>() {
+        ApiError::bad_request(e.to_string())
+    } else if err.find::<warp::reject::PayloadTooLarge>().is_some() {
+        ApiError::bad_request("Request body too large")
+    } else if err.find::<warp::reject::MethodNotAllowed>().is_some() {
+        ApiError::new(ApiErrorCode::MethodNotAllowed, "Method not allowed")
+    } else {
+        tracing::warn!("Unhandled rejection: {:?}", err);
+        ApiError::internal("Internal server error")
+    };
+    Ok(error.into_reply())
+}
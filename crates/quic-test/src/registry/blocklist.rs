@@ -0,0 +1,89 @@
+//! Peer IDs the registry refuses to serve.
+//!
+//! Blocked peers are answered with 403 on register and heartbeat, evicted
+//! from the peer store and left out of peer lists. The set is seeded from
+//! [`RegistryConfig::blocklist`](super::RegistryConfig::blocklist) and can be
+//! changed at runtime through the admin endpoints.
+
+use super::store::canonical_peer_id;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Runtime set of blocked peer IDs (canonical form).
+#[derive(Debug, Default)]
+pub(crate) struct Blocklist {
+    peers: RwLock<HashSet<String>>,
+}
+
+impl Blocklist {
+    pub(crate) fn new<'a>(peer_ids: impl IntoIterator<Item = &'a String>) -> Self {
+        Self {
+            peers: RwLock::new(peer_ids.into_iter().map(|p| canonical_peer_id(p)).collect()),
+        }
+    }
+
+    pub(crate) fn contains(&self, peer_id: &str) -> bool {
+        self.peers
+            .read()
+            .is_ok_and(|peers| peers.contains(&canonical_peer_id(peer_id)))
+    }
+
+    /// Block `peer_id`; returns false if it already was.
+    pub(crate) fn block(&self, peer_id: &str) -> bool {
+        self.peers
+            .write()
+            .is_ok_and(|mut peers| peers.insert(canonical_peer_id(peer_id)))
+    }
+
+    /// Unblock `peer_id`; returns false if it wasn't blocked.
+    pub(crate) fn unblock(&self, peer_id: &str) -> bool {
+        self.peers
+            .write()
+            .is_ok_and(|mut peers| peers.remove(&canonical_peer_id(peer_id)))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.peers.read().map_or(0, |peers| peers.len())
+    }
+}
+
+/// Load blocked peer IDs from a file: one per line, `#` starts a comment.
+pub fn load_blocklist(path: &Path) -> anyhow::Result<HashSet<String>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(canonical_peer_id)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocklist_is_case_insensitive_and_loads_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocklist.txt");
+        std::fs::write(
+            &path,
+            "# misbehaving peers\nABCDEF01\n\n  1234abcd  # flood\n",
+        )
+        .unwrap();
+
+        let loaded = load_blocklist(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        let blocklist = Blocklist::new(&loaded);
+        assert!(blocklist.contains("abcdef01"));
+        assert!(blocklist.contains(" 1234ABCD"));
+        assert!(!blocklist.contains("ffff0000"));
+
+        assert!(blocklist.block("FFFF0000"));
+        assert!(!blocklist.block("ffff0000"));
+        assert!(blocklist.unblock("abcdef01"));
+        assert!(!blocklist.unblock("abcdef01"));
+        assert_eq!(blocklist.len(), 2);
+    }
+}
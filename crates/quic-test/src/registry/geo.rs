@@ -9,7 +9,17 @@
 //! - IP prefix-to-ASN mappings for major networks
 
 use std::collections::HashMap;
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a successful lookup stays cached
+pub const GEO_CACHE_TTL: Duration = Duration::from_secs(3600);
+/// How long an unresolved address stays cached
+pub const GEO_CACHE_NEGATIVE_TTL: Duration = Duration::from_secs(300);
+/// Expired entries are pruned once the cache grows past this size
+const GEO_CACHE_PRUNE_THRESHOLD: usize = 10_000;
 
 /// Country information with coordinates
 #[derive(Debug, Clone)]
@@ -25,6 +35,17 @@ pub struct CountryInfo {
     pub lon: f64,
 }
 
+/// Resolved location for a single IP address
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoInfo {
+    /// Latitude (with per-IP jitter)
+    pub latitude: f64,
+    /// Longitude (with per-IP jitter)
+    pub longitude: f64,
+    /// ISO 3166-1 alpha-2 country code
+    pub country_code: Option<String>,
+}
+
 /// IPv4 prefix entry for ASN lookup
 #[derive(Debug, Clone)]
 struct Ipv4Prefix {
@@ -116,6 +137,32 @@ impl BgpGeoProvider {
         self.fallback_geo(ipv4)
     }
 
+    /// Resolve an IP address, or `None` if it has no meaningful location
+    ///
+    /// Loopback, unspecified, private and link-local addresses are not
+    /// resolved; `lookup` would otherwise place them with the octet heuristics.
+    pub fn resolve(&self, ip: IpAddr) -> Option<GeoInfo> {
+        if !is_routable(ip) {
+            return None;
+        }
+        let (latitude, longitude, country_code) = self.lookup(ip);
+        Some(GeoInfo {
+            latitude,
+            longitude,
+            country_code,
+        })
+    }
+
+    /// Resolve many addresses at once, e.g. when importing a large peer list
+    ///
+    /// Each distinct address is looked up once; the result is aligned with
+    /// `addrs` (same length and order).
+    pub fn resolve_batch(&self, addrs: &[IpAddr]) -> Vec<Option<GeoInfo>> {
+        let (unique, index) = unique_addrs(addrs);
+        let resolved: Vec<Option<GeoInfo>> = unique.iter().map(|ip| self.resolve(*ip)).collect();
+        index.into_iter().map(|i| resolved[i].clone()).collect()
+    }
+
     /// Lookup ASN for an IPv4 address (lock-free)
     fn lookup_asn(&self, ip: Ipv4Addr) -> Option<u32> {
         let ip_u32 = u32::from(ip);
@@ -392,16 +439,185 @@ impl BgpGeoProvider {
     }
 }
 
+/// Deterministic stand-in coordinates for a peer with no resolved location
+///
+/// The peer ID is hashed (FNV-1a) to a point spread uniformly over the
+/// sphere, so the peer is always drawn and stays put across refreshes.
+pub fn estimated_position(peer_id: &str) -> (f64, f64) {
+    let hash = peer_id.bytes().fold(0xcbf2_9ce4_8422_2325u64, |acc, b| {
+        (acc ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let u = (hash & 0xffff_ffff) as f64 / f64::from(u32::MAX);
+    let v = (hash >> 32) as f64 / f64::from(u32::MAX);
+    let lat = (2.0 * u - 1.0).asin().to_degrees();
+    let lon = v * 360.0 - 180.0;
+    (lat, lon)
+}
+
+/// Whether `ip` can carry a meaningful location
+///
+/// Loopback, unspecified, private and link-local addresses cannot.
+fn is_routable(ip: IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(v4) => {
+            !(v4.is_loopback() || v4.is_unspecified() || v4.is_private() || v4.is_link_local())
+        }
+        IpAddr::V6(v6) => {
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_unique_local()
+                || v6.is_unicast_link_local())
+        }
+    }
+}
+
+/// The address to locate a peer by: its first publicly routable address
+///
+/// IPv4-mapped IPv6 addresses are returned as IPv4. `None` if the peer
+/// advertises no public address.
+pub fn geo_address(addresses: &[SocketAddr]) -> Option<IpAddr> {
+    addresses
+        .iter()
+        .map(|addr| addr.ip().to_canonical())
+        .find(|ip| is_routable(*ip))
+}
+
+/// Deduplicate addresses, returning the distinct addresses (first-seen order)
+/// and, for each input, the index of its entry in that list.
+fn unique_addrs(addrs: &[IpAddr]) -> (Vec<IpAddr>, Vec<usize>) {
+    let mut unique = Vec::new();
+    let mut seen: HashMap<IpAddr, usize> = HashMap::new();
+    let index = addrs
+        .iter()
+        .map(|ip| {
+            *seen.entry(ip.to_canonical()).or_insert_with(|| {
+                unique.push(*ip);
+                unique.len() - 1
+            })
+        })
+        .collect();
+    (unique, index)
+}
+
 impl Default for BgpGeoProvider {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Cached geo entry (positive or negative)
+#[derive(Debug, Clone)]
+struct GeoCacheEntry {
+    geo: Option<GeoInfo>,
+    expires_at: Instant,
+}
+
+/// In-memory TTL cache in front of a `BgpGeoProvider`
+///
+/// Caches unresolved addresses as well as successes, with a shorter TTL for
+/// the former, so repeated lookups for the same IP skip the prefix scan.
+pub struct GeoCache {
+    provider: Arc<BgpGeoProvider>,
+    ttl: Duration,
+    negative_ttl: Duration,
+    entries: Mutex<HashMap<IpAddr, GeoCacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl GeoCache {
+    /// Create a cache with the default TTLs
+    pub fn new(provider: Arc<BgpGeoProvider>) -> Self {
+        Self::with_ttls(provider, GEO_CACHE_TTL, GEO_CACHE_NEGATIVE_TTL)
+    }
+
+    /// Create a cache with explicit positive and negative TTLs
+    pub fn with_ttls(provider: Arc<BgpGeoProvider>, ttl: Duration, negative_ttl: Duration) -> Self {
+        Self {
+            provider,
+            ttl,
+            negative_ttl,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Resolve an address, serving from cache while the entry is fresh
+    pub fn resolve(&self, ip: IpAddr) -> Option<GeoInfo> {
+        self.resolve_at(ip, Instant::now())
+    }
+
+    /// Resolve many addresses; see `BgpGeoProvider::resolve_batch`
+    pub fn resolve_batch(&self, addrs: &[IpAddr]) -> Vec<Option<GeoInfo>> {
+        let now = Instant::now();
+        let (unique, index) = unique_addrs(addrs);
+        let resolved: Vec<Option<GeoInfo>> =
+            unique.iter().map(|ip| self.resolve_at(*ip, now)).collect();
+        index.into_iter().map(|i| resolved[i].clone()).collect()
+    }
+
+    /// Number of lookups served from cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that went to the provider
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn resolve_at(&self, ip: IpAddr, now: Instant) -> Option<GeoInfo> {
+        let key = ip.to_canonical();
+        let Ok(mut entries) = self.entries.lock() else {
+            return self.provider.resolve(ip);
+        };
+        if let Some(entry) = entries.get(&key).filter(|e| e.expires_at > now) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return entry.geo.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let geo = self.provider.resolve(ip);
+        let ttl = if geo.is_some() {
+            self.ttl
+        } else {
+            self.negative_ttl
+        };
+        if entries.len() >= GEO_CACHE_PRUNE_THRESHOLD {
+            entries.retain(|_, e| e.expires_at > now);
+        }
+        entries.insert(
+            key,
+            GeoCacheEntry {
+                geo: geo.clone(),
+                expires_at: now + ttl,
+            },
+        );
+        geo
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_geo_address_skips_non_public() {
+        let addrs: Vec<SocketAddr> = vec![
+            "192.168.1.5:9000".parse().unwrap(),
+            "127.0.0.1:9000".parse().unwrap(),
+            "[::ffff:1.1.1.1]:9000".parse().unwrap(),
+            "8.8.8.8:9000".parse().unwrap(),
+        ];
+        assert_eq!(
+            geo_address(&addrs),
+            Some(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)))
+        );
+        assert_eq!(geo_address(&addrs[..2]), None);
+        assert_eq!(geo_address(&[]), None);
+    }
+
     #[test]
     fn test_cloudflare_lookup() {
         let provider = BgpGeoProvider::new();
@@ -459,4 +675,73 @@ mod tests {
         // Different IPs should have different jitter
         assert!(lat1 != lat2 || lon1 != lon2);
     }
+
+    #[test]
+    fn test_resolve_batch_aligned_and_deduplicated() {
+        let provider = BgpGeoProvider::new();
+        let cloudflare = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
+        let hetzner = IpAddr::V4(Ipv4Addr::new(95, 216, 1, 1));
+        let private = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10));
+        let mapped: IpAddr = "::ffff:1.1.1.1".parse().unwrap();
+        let addrs = [cloudflare, hetzner, cloudflare, private, mapped];
+
+        let (unique, index) = unique_addrs(&addrs);
+        assert_eq!(unique, vec![cloudflare, hetzner, private]);
+        assert_eq!(index, vec![0, 1, 0, 2, 0]);
+
+        let results = provider.resolve_batch(&addrs);
+        assert_eq!(results.len(), addrs.len());
+        for (ip, result) in addrs.iter().zip(&results) {
+            assert_eq!(*result, provider.resolve(*ip));
+        }
+        assert_eq!(
+            results[0].as_ref().and_then(|g| g.country_code.as_deref()),
+            Some("US")
+        );
+        assert_eq!(
+            results[1].as_ref().and_then(|g| g.country_code.as_deref()),
+            Some("DE")
+        );
+        assert!(results[3].is_none());
+        assert_eq!(results[0], results[2]);
+    }
+
+    #[test]
+    fn test_geo_cache_hits_and_negative_expiry() {
+        let cache = GeoCache::with_ttls(
+            Arc::new(BgpGeoProvider::new()),
+            Duration::from_secs(60),
+            Duration::from_secs(10),
+        );
+        let public = IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1));
+        let private = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let start = Instant::now();
+
+        let first = cache.resolve_at(public, start);
+        assert!(first.is_some());
+        assert_eq!(
+            cache.resolve_at(public, start + Duration::from_secs(5)),
+            first
+        );
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        assert!(cache.resolve_at(private, start).is_none());
+        assert!(
+            cache
+                .resolve_at(private, start + Duration::from_secs(5))
+                .is_none()
+        );
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+
+        // Negative entry expires first; the positive one is still fresh
+        let later = start + Duration::from_secs(30);
+        assert!(cache.resolve_at(private, later).is_none());
+        assert_eq!(cache.misses(), 3);
+        assert_eq!(cache.resolve_at(public, later), first);
+        assert_eq!(cache.hits(), 3);
+
+        // Positive entry expires after its own TTL
+        cache.resolve_at(public, start + Duration::from_secs(61));
+        assert_eq!(cache.misses(), 4);
+    }
 }
@@ -53,20 +53,31 @@
 //! client.heartbeat(&heartbeat).await?;
 //! ```
 
+mod admin_tls;
 mod api;
+mod api_error;
+mod blocklist;
 pub mod geo;
 pub mod persistence;
+mod rate_limit;
 mod store;
 mod types;
 
 // Re-export main types
-pub use api::{RegistryClient, RegistryConfig, start_registry_server};
-pub use geo::BgpGeoProvider;
+pub use admin_tls::AdminTlsConfig;
+pub use api::{
+    ADMIN_SECRET_ENV_VAR, ADMIN_TOKEN_ENV_VAR, RegistryCheck, RegistryCheckError, RegistryClient,
+    RegistryClientConfig, RegistryConfig, start_registry_server,
+};
+pub use api_error::{ApiError, ApiErrorBody, ApiErrorCode};
+pub use blocklist::load_blocklist;
+pub use geo::{BgpGeoProvider, GeoCache, GeoInfo, geo_address};
 pub use persistence::{PersistedData, PersistenceConfig, PersistentStorage, StatsSnapshot};
 pub use store::{PeerStore, ProofValidationResult};
 pub use types::{
     ConnectionBreakdown,
     ConnectionDirection,
+    ConnectionFunnel,
     ConnectionMethod,
     ConnectionReport,
     ConnectionTechnique,
@@ -77,14 +88,19 @@ pub use types::{
     CrdtType,
     CrossValidation,
     DataProof,
+    EigenTrustConvergenceProof,
     ExperimentResults,
     FailureReasonCode,
     FilteringBehavior,
     FullMeshProbeResult,
+    FunnelStage,
     GossipProtocolProof,
     HyParViewProof,
     ImpairmentMetrics,
+    JoinTime,
+    JoinTimeSummary,
     MappingBehavior,
+    MatrixCoverage,
     MethodProof,
     MigrationMetrics,
     NatBehavior,
@@ -96,22 +112,27 @@ pub use types::{
     NetworkProfile,
     NetworkStats,
     NodeCapabilities,
+    NodeDeregistration,
     NodeGossipStats,
     NodeHeartbeat,
     NodeRegistration,
     PathTuple,
     PeerInfo,
     PeerStatus,
+    PeersPage,
     PlumtreeProof,
     PortPreservation,
     ProofBasedTestReport,
     ProofType,
+    RTT_BUCKET_BOUNDS_MS,
     RegistrationResponse,
     RelayMetrics,
+    RttHistogram,
     SignedAttestation,
     SuccessLevel,
     SwimProof,
     TechniqueAttempt,
+    TelemetryRecord,
     TemporalMetrics,
     TemporalScenario,
     TestAnomaly,
@@ -119,5 +140,7 @@ pub use types::{
     TestReport,
     TestSuite,
     TestSuiteConfig,
+    classify_connection_error,
+    classify_error_message,
     unix_timestamp_ms,
 };
@@ -44,6 +44,9 @@ const MAX_EVENTS_PER_FILE: usize = 100_000;
 /// Maximum stats snapshots to keep in memory before saving.
 const STATS_BUFFER_THRESHOLD: usize = 100;
 
+/// Default age after which persisted connection records are dropped on load (7 days).
+pub const DEFAULT_HISTORY_RETENTION_SECS: u64 = 7 * 24 * 3600;
+
 /// Persistent data store configuration.
 #[derive(Debug, Clone)]
 pub struct PersistenceConfig {
@@ -57,6 +60,8 @@ pub struct PersistenceConfig {
     pub max_file_size: u64,
     /// Max events per file before rotation (default: 100k).
     pub max_events_per_file: usize,
+    /// Connection records older than this are dropped on load (default: 7 days).
+    pub history_retention_secs: u64,
 }
 
 impl Default for PersistenceConfig {
@@ -67,6 +72,7 @@ impl Default for PersistenceConfig {
             event_buffer_size: EVENT_BUFFER_THRESHOLD,
             max_file_size: MAX_FILE_SIZE_BYTES,
             max_events_per_file: MAX_EVENTS_PER_FILE,
+            history_retention_secs: DEFAULT_HISTORY_RETENTION_SECS,
         }
     }
 }
@@ -212,7 +218,18 @@ impl PersistentStorage {
         if summary_path.exists() {
             match fs::read_to_string(&summary_path) {
                 Ok(content) => match serde_json::from_str::<PersistedData>(&content) {
-                    Ok(loaded) => {
+                    Ok(mut loaded) => {
+                        let dropped = retain_recent_connections(
+                            &mut loaded.connections,
+                            self.config.history_retention_secs,
+                            current_timestamp(),
+                        );
+                        if dropped > 0 {
+                            info!(
+                                "Dropped {} connection records older than {}s",
+                                dropped, self.config.history_retention_secs
+                            );
+                        }
                         *data = loaded;
                         info!(
                             "Loaded experiment data: {} nodes, {} connections, {} snapshots",
@@ -526,6 +543,13 @@ impl PersistentStorage {
         *self.dirty.write().await = true;
     }
 
+    /// Connection records loaded from (or pending save to) disk.
+    ///
+    /// Used on startup to restore the connectivity matrix history.
+    pub async fn connection_history(&self) -> Vec<ConnectionRecord> {
+        self.data.read().await.connections.clone()
+    }
+
     /// Add a statistics snapshot (buffered).
     pub async fn add_stats_snapshot(&self, stats: NetworkStats) {
         if !self.config.enabled {
@@ -678,6 +702,20 @@ impl PersistentStorage {
     }
 }
 
+/// Drop connection records older than `retention_secs` relative to `now`.
+///
+/// Returns the number of records removed.
+fn retain_recent_connections(
+    connections: &mut Vec<ConnectionRecord>,
+    retention_secs: u64,
+    now: u64,
+) -> usize {
+    let cutoff = now.saturating_sub(retention_secs);
+    let before = connections.len();
+    connections.retain(|c| c.timestamp >= cutoff);
+    before - connections.len()
+}
+
 /// Get current Unix timestamp.
 fn current_timestamp() -> u64 {
     SystemTime::now()
@@ -711,6 +749,7 @@ mod tests {
             country_code: Some("US".to_string()),
             latitude: 40.0,
             longitude: -74.0,
+            location_estimated: false,
             last_seen: 12345,
             connection_success_rate: 0.95,
             capabilities: Default::default(),
@@ -722,6 +761,8 @@ mod tests {
             connected_peers: 5,
             gossip_stats: None,
             full_mesh_probes: None,
+            last_error: None,
+            time_to_first_connection: None,
         }];
 
         storage.update_nodes(nodes).await;
@@ -736,6 +777,59 @@ mod tests {
         assert_eq!(data.nodes[0].peer_id, "test123");
     }
 
+    #[tokio::test]
+    async fn test_connection_history_survives_restart() {
+        use crate::registry::types::{ConnectionMethod, ConnectivityMatrix};
+
+        let temp_dir = TempDir::new().unwrap();
+        let config = PersistenceConfig {
+            data_dir: temp_dir.path().to_path_buf(),
+            enabled: true,
+            history_retention_secs: 3600,
+            ..Default::default()
+        };
+        let now = current_timestamp();
+        let record = |id: u64, age_secs: u64| ConnectionRecord {
+            id,
+            from_peer: "aaaa1111".to_string(),
+            to_peer: format!("bbbb{:04}", id),
+            method: ConnectionMethod::HolePunched,
+            is_ipv6: false,
+            rtt_ms: Some(42),
+            timestamp: now - age_secs,
+            from_country: None,
+            to_country: None,
+            is_active: true,
+            connectivity: ConnectivityMatrix {
+                nat_traversal_tested: true,
+                nat_traversal_success: true,
+                ..Default::default()
+            },
+        };
+
+        let storage = PersistentStorage::new(config.clone());
+        storage.initialize().await.unwrap();
+        storage
+            .update_connections(vec![record(1, 7200), record(2, 60), record(3, 10)])
+            .await;
+        storage.save().await.unwrap();
+
+        // Restart: the two-hour-old record is past retention
+        let storage2 = PersistentStorage::new(config);
+        storage2.initialize().await.unwrap();
+        let history = storage2.connection_history().await;
+        assert_eq!(history.iter().map(|c| c.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(history[0].connectivity.nat_traversal_success);
+
+        let store = crate::registry::PeerStore::new();
+        store.restore_connections(history).await;
+        let results = store.get_experiment_results().await;
+        assert_eq!(results.connections.len(), 2);
+        assert!(results.connections.iter().all(|c| !c.is_active));
+        let matrix = store.get_connection_matrix().await;
+        assert_eq!(matrix.matrix.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_event_logging_and_buffering() {
         let temp_dir = TempDir::new().unwrap();
@@ -757,6 +851,7 @@ mod tests {
                     country_code: Some("US".to_string()),
                     latitude: 40.0,
                     longitude: -74.0,
+                    location_estimated: false,
                 })
                 .await;
         }
@@ -772,6 +867,7 @@ mod tests {
                     country_code: Some("US".to_string()),
                     latitude: 40.0,
                     longitude: -74.0,
+                    location_estimated: false,
                 })
                 .await;
         }
@@ -0,0 +1,126 @@
+//! Per-source-IP token-bucket rate limiting for registry write endpoints.
+//!
+//! Each client IP gets a bucket holding up to one minute's worth of tokens,
+//! refilled continuously. A request takes one token; an empty bucket means
+//! the request is refused along with how long until the next token arrives.
+
+use crate::clock::{SharedClock, system_clock};
+use dashmap::DashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token buckets keyed by client IP.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    buckets: DashMap<IpAddr, TokenBucket>,
+    /// Bucket capacity and refill per minute; 0 disables limiting
+    per_minute: u32,
+    clock: SharedClock,
+}
+
+impl RateLimiter {
+    /// Allow `per_minute` requests per IP per minute (bursting up to that many).
+    pub(crate) fn new(per_minute: u32) -> Self {
+        Self::with_clock(per_minute, system_clock())
+    }
+
+    pub(crate) fn with_clock(per_minute: u32, clock: SharedClock) -> Self {
+        Self {
+            buckets: DashMap::new(),
+            per_minute,
+            clock,
+        }
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+
+    /// Take a token for `ip`, or return how long until one is available.
+    pub(crate) fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        if self.per_minute == 0 {
+            return Ok(());
+        }
+        let now = self.clock.now();
+        let capacity = f64::from(self.per_minute);
+        let mut bucket = self.buckets.entry(ip).or_insert(TokenBucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_sec()).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_per_sec(),
+            ))
+        }
+    }
+
+    /// Drop buckets that have refilled completely; they behave exactly like
+    /// a fresh bucket, so one-shot clients don't accumulate.
+    pub(crate) fn prune(&self) -> usize {
+        let now = self.clock.now();
+        let capacity = f64::from(self.per_minute);
+        let refill = self.refill_per_sec();
+        let before = self.buckets.len();
+        self.buckets.retain(|_, bucket| {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            bucket.tokens + elapsed.as_secs_f64() * refill < capacity
+        });
+        before - self.buckets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bucket_limits_refills_and_prunes() {
+        let clock = MockClock::new();
+        let limiter = RateLimiter::with_clock(2, Arc::new(clock.clone()));
+        let a: IpAddr = "203.0.113.1".parse().unwrap();
+        let b: IpAddr = "203.0.113.2".parse().unwrap();
+
+        assert!(limiter.check(a).is_ok());
+        assert!(limiter.check(a).is_ok());
+        let retry = limiter.check(a).expect_err("bucket empty");
+        assert_eq!(retry.as_secs(), 30);
+        // Other clients have their own bucket
+        assert!(limiter.check(b).is_ok());
+
+        clock.advance(Duration::from_secs(30));
+        assert!(limiter.check(a).is_ok());
+        assert!(limiter.check(a).is_err());
+
+        // b has refilled by now; a is still drained
+        assert_eq!(limiter.prune(), 1);
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(limiter.prune(), 1);
+        assert!(limiter.buckets.is_empty());
+    }
+
+    #[test]
+    fn test_zero_rate_disables_limiting() {
+        let limiter = RateLimiter::new(0);
+        let ip: IpAddr = "::1".parse().unwrap();
+        for _ in 0..100 {
+            assert!(limiter.check(ip).is_ok());
+        }
+        assert!(limiter.buckets.is_empty());
+    }
+}
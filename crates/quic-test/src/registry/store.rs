@@ -4,17 +4,20 @@
 //! with automatic expiration of stale entries and persistent
 //! historical tracking for experiment results.
 
-use crate::registry::geo::BgpGeoProvider;
+use crate::clock::{SharedClock, system_clock};
+use crate::registry::geo::{BgpGeoProvider, estimated_position, geo_address};
 use crate::registry::types::{
     ConnectionBreakdown, ConnectionMethod, ConnectionRecord, ConnectivityMatrix, ExperimentResults,
-    FullMeshProbeResult, GossipStats, NatStats, NatType, NetworkEvent, NetworkStats,
-    NodeGossipStats, NodeHeartbeat, NodeRegistration, PeerInfo, PeerStatus,
+    FailureReasonCode, FullMeshProbeResult, GossipStats, MatrixCoverage, NatStats, NatType,
+    NetworkEvent, NetworkStats, NodeGossipStats, NodeHeartbeat, NodeRegistration, PeerInfo,
+    PeerStatus, RttHistogram, TestPattern,
 };
 use dashmap::DashMap;
-use std::collections::HashMap;
+use dashmap::mapref::entry::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::net::{IpAddr, SocketAddr};
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tokio::sync::broadcast;
@@ -22,6 +25,18 @@ use tokio::sync::broadcast;
 /// Default time-to-live for registrations (2 minutes).
 const DEFAULT_TTL_SECS: u64 = 120;
 
+/// Default rolling success rate below which a `SuccessRateAlert` is broadcast.
+pub(crate) const DEFAULT_SUCCESS_RATE_FLOOR: f64 = 0.5;
+
+/// Number of recent heartbeat deltas that make up the rolling success rate.
+const SUCCESS_RATE_WINDOW: usize = 50;
+
+/// Number of recent events kept for WebSocket clients that need to resync.
+pub(crate) const EVENT_LOG_CAPACITY: usize = 1000;
+
+/// Lifecycle changes kept per peer for its timeline.
+const LIFECYCLE_CAPACITY: usize = 64;
+
 /// Heartbeat interval expected from nodes (30 seconds).
 #[allow(dead_code)]
 const HEARTBEAT_INTERVAL_SECS: u64 = 30;
@@ -32,14 +47,39 @@ const ACTIVE_THRESHOLD_SECS: u64 = 60;
 /// Inactive threshold - nodes between active and historical (5 minutes).
 const INACTIVE_THRESHOLD_SECS: u64 = 300;
 
+/// Normalize a peer ID so the same node always maps to the same store key.
+///
+/// Peer IDs are hex strings; surrounding whitespace and letter case are not
+/// significant.
+pub(crate) fn canonical_peer_id(peer_id: &str) -> String {
+    peer_id.trim().to_ascii_lowercase()
+}
+
+/// Coordinates to show a peer at, and whether they are estimated.
+///
+/// Unresolved peers (0, 0) still need a spot on the globe, so they are
+/// placed at a stable position derived from their ID.
+fn display_position(peer_id: &str, latitude: f64, longitude: f64) -> (f64, f64, bool) {
+    if latitude == 0.0 && longitude == 0.0 {
+        let (latitude, longitude) = estimated_position(peer_id);
+        (latitude, longitude, true)
+    } else {
+        (latitude, longitude, false)
+    }
+}
+
+/// Successful outbound connections across all traversal methods.
+fn nat_successes(stats: &NatStats) -> u64 {
+    stats.direct_success + stats.hole_punch_success + stats.relay_success
+}
+
 /// Internal storage entry for a registered node.
 #[derive(Debug, Clone)]
 struct NodeEntry {
     /// Registration data
     registration: NodeRegistration,
-    /// When this entry was created (kept for metadata, expiration uses last_heartbeat)
-    #[allow(dead_code)]
-    registered_at: Instant,
+    /// When this peer was first registered (preserved across re-registrations)
+    first_seen: Instant,
     /// Last heartbeat received
     last_heartbeat: Instant,
     /// Geographic coordinates (resolved from IP)
@@ -59,6 +99,26 @@ struct NodeEntry {
     bytes_received: u64,
     /// Full-mesh connectivity probe results
     full_mesh_probes: Option<HashMap<String, FullMeshProbeResult>>,
+    /// Highest heartbeat sequence number applied since registration
+    last_heartbeat_seq: Option<u64>,
+    /// Registrations and offline transitions, oldest first
+    lifecycle: VecDeque<(Instant, LifecycleEvent)>,
+}
+
+/// A change in whether a peer is registered, for its timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LifecycleEvent {
+    Registered,
+    Offline,
+}
+
+impl NodeEntry {
+    fn record_lifecycle(&mut self, at: Instant, event: LifecycleEvent) {
+        self.lifecycle.push_back((at, event));
+        if self.lifecycle.len() > LIFECYCLE_CAPACITY {
+            self.lifecycle.pop_front();
+        }
+    }
 }
 
 /// Thread-safe peer registry store with historical tracking.
@@ -71,6 +131,10 @@ pub struct PeerStore {
     connections: RwLock<Vec<ConnectionRecord>>,
     /// Event broadcaster for real-time updates
     event_tx: broadcast::Sender<NetworkEvent>,
+    /// Event broadcaster tagging each event with its sequence number
+    sequenced_tx: broadcast::Sender<(u64, NetworkEvent)>,
+    /// Recent events by sequence number, replayed on resync
+    event_log: Mutex<EventLog>,
     /// Store creation time (for uptime calculation)
     created_at: Instant,
     /// Total connections established
@@ -91,6 +155,111 @@ pub struct PeerStore {
     next_connection_id: AtomicU64,
     /// BGP-based geo provider for IP-to-country lookup
     geo_provider: BgpGeoProvider,
+    /// Rolling success rate tracking for dashboard alerts
+    success_rate: Mutex<SuccessRateMonitor>,
+    /// RTT distribution of connection reports, per method
+    rtt_histogram: Mutex<RttHistogram>,
+    /// Time source for TTLs and uptime
+    clock: SharedClock,
+}
+
+/// Bounded history of broadcast events, numbered from 1.
+#[derive(Debug, Default)]
+struct EventLog {
+    /// Sequence number of the most recent event (0 before any event)
+    last_seq: u64,
+    /// Most recent events, oldest first
+    recent: VecDeque<(u64, NetworkEvent)>,
+}
+
+impl EventLog {
+    fn push(&mut self, event: NetworkEvent) -> u64 {
+        self.last_seq += 1;
+        self.recent.push_back((self.last_seq, event));
+        if self.recent.len() > EVENT_LOG_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.last_seq
+    }
+
+    /// Events after `since`, or `None` if some of them were already dropped
+    /// or `since` is from a different (e.g. restarted) registry.
+    fn since(&self, since: u64) -> Option<Vec<(u64, NetworkEvent)>> {
+        if since > self.last_seq {
+            return None;
+        }
+        let oldest = self
+            .recent
+            .front()
+            .map_or(self.last_seq + 1, |(seq, _)| *seq);
+        if since + 1 < oldest {
+            return None;
+        }
+        Some(
+            self.recent
+                .iter()
+                .filter(|(seq, _)| *seq > since)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// Rolling connection success rate with edge-triggered alerting.
+///
+/// Fed with per-heartbeat deltas of the cumulative `NatStats` counters so the
+/// rate reflects recent attempts rather than everything since startup.
+#[derive(Debug)]
+struct SuccessRateMonitor {
+    /// Alert when the rolling rate drops below this value
+    floor: f64,
+    /// Recent (attempts, successes) deltas
+    window: VecDeque<(u64, u64)>,
+    /// Whether an alert is currently outstanding
+    alerting: bool,
+}
+
+impl SuccessRateMonitor {
+    fn new(floor: f64) -> Self {
+        Self {
+            floor,
+            window: VecDeque::with_capacity(SUCCESS_RATE_WINDOW),
+            alerting: false,
+        }
+    }
+
+    /// Record new outcomes; returns an event only when the floor is crossed.
+    fn record(&mut self, attempts: u64, successes: u64) -> Option<NetworkEvent> {
+        if attempts == 0 {
+            return None;
+        }
+        if self.window.len() == SUCCESS_RATE_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back((attempts, successes.min(attempts)));
+
+        let (total_attempts, total_successes) = self
+            .window
+            .iter()
+            .fold((0, 0), |(a, s), (da, ds)| (a + da, s + ds));
+        let rate = total_successes as f64 / total_attempts as f64;
+
+        if !self.alerting && rate < self.floor {
+            self.alerting = true;
+            Some(NetworkEvent::SuccessRateAlert {
+                rate,
+                threshold: self.floor,
+            })
+        } else if self.alerting && rate >= self.floor {
+            self.alerting = false;
+            Some(NetworkEvent::SuccessRateRecovered {
+                rate,
+                threshold: self.floor,
+            })
+        } else {
+            None
+        }
+    }
 }
 
 impl std::fmt::Debug for PeerStore {
@@ -112,13 +281,21 @@ impl PeerStore {
 
     /// Create a new peer store with custom TTL.
     pub fn with_ttl(ttl_secs: u64) -> Arc<Self> {
+        Self::with_clock(ttl_secs, system_clock())
+    }
+
+    /// Create a new peer store with custom TTL and time source.
+    pub fn with_clock(ttl_secs: u64, clock: SharedClock) -> Arc<Self> {
         let (event_tx, _) = broadcast::channel(1000);
+        let (sequenced_tx, _) = broadcast::channel(1000);
         Arc::new(Self {
             peers: DashMap::new(),
             historical_peers: DashMap::new(),
             connections: RwLock::new(Vec::new()),
             event_tx,
-            created_at: Instant::now(),
+            sequenced_tx,
+            event_log: Mutex::new(EventLog::default()),
+            created_at: clock.now(),
             total_connections: AtomicU64::new(0),
             total_bytes: AtomicU64::new(0),
             ipv4_connections: AtomicU64::new(0),
@@ -128,14 +305,59 @@ impl PeerStore {
             ttl_secs,
             next_connection_id: AtomicU64::new(1),
             geo_provider: BgpGeoProvider::new(),
+            success_rate: Mutex::new(SuccessRateMonitor::new(DEFAULT_SUCCESS_RATE_FLOOR)),
+            rtt_histogram: Mutex::new(RttHistogram::default()),
+            clock,
         })
     }
 
+    /// Seconds without a heartbeat before a peer is considered gone.
+    pub fn ttl_secs(&self) -> u64 {
+        self.ttl_secs
+    }
+
+    /// Set the rolling success rate floor used for dashboard alerts.
+    pub fn set_success_rate_floor(&self, floor: f64) {
+        if let Ok(mut monitor) = self.success_rate.lock() {
+            monitor.floor = floor;
+        }
+    }
+
     /// Subscribe to real-time network events.
     pub fn subscribe(&self) -> broadcast::Receiver<NetworkEvent> {
         self.event_tx.subscribe()
     }
 
+    /// Subscribe to real-time network events tagged with sequence numbers.
+    pub fn subscribe_sequenced(&self) -> broadcast::Receiver<(u64, NetworkEvent)> {
+        self.sequenced_tx.subscribe()
+    }
+
+    /// Sequence number of the most recent event (0 before any event).
+    pub fn event_seq(&self) -> u64 {
+        self.event_log.lock().map_or(0, |log| log.last_seq)
+    }
+
+    /// Events with a sequence number above `since`, oldest first.
+    ///
+    /// Returns `None` when the gap can't be filled from the bounded log, in
+    /// which case the caller needs a full state snapshot instead.
+    pub fn events_since(&self, since: u64) -> Option<Vec<(u64, NetworkEvent)>> {
+        self.event_log.lock().ok()?.since(since)
+    }
+
+    /// Number and broadcast an event.
+    fn emit(&self, event: NetworkEvent) {
+        // Hold the log lock while sending so sequence numbers reach
+        // subscribers in order
+        let Ok(mut log) = self.event_log.lock() else {
+            return;
+        };
+        let seq = log.push(event.clone());
+        let _ = self.sequenced_tx.send((seq, event.clone()));
+        let _ = self.event_tx.send(event);
+    }
+
     /// Register a new node or update existing registration.
     pub fn register(&self, registration: NodeRegistration) -> Result<Vec<PeerInfo>, String> {
         self.register_with_client_ip(registration, None)
@@ -150,43 +372,76 @@ impl PeerStore {
         registration: NodeRegistration,
         client_ip: Option<IpAddr>,
     ) -> Result<Vec<PeerInfo>, String> {
-        let peer_id = registration.peer_id.clone();
-        let now = Instant::now();
+        let peer_id = canonical_peer_id(&registration.peer_id);
+        let registration = NodeRegistration {
+            peer_id: peer_id.clone(),
+            ..registration
+        };
+        let now = self.clock.now();
 
         // Check if this node was previously historical (coming back online)
-        let was_historical = self.historical_peers.remove(&peer_id).is_some();
+        let historical = self
+            .historical_peers
+            .remove(&peer_id)
+            .map(|(_, entry)| entry);
+        let was_historical = historical.is_some();
 
         // Resolve geographic coordinates from IP
         // Use client_ip as fallback when external_addresses is empty
         let (latitude, longitude, country_code) =
             self.resolve_geo_with_fallback(&registration.external_addresses, client_ip);
 
-        let entry = NodeEntry {
-            registration: registration.clone(),
-            registered_at: now,
-            last_heartbeat: now,
-            latitude,
-            longitude,
-            country_code: country_code.clone(),
-            nat_stats: NatStats {
-                attempts: 0,
-                direct_success: 0,
-                hole_punch_success: 0,
-                relay_success: 0,
-                failures: 0,
-                inbound_connections: 0,
-                is_behind_nat: false,
-            },
-            gossip_stats: NodeGossipStats::default(),
-            connected_peers: 0,
-            bytes_sent: 0,
-            bytes_received: 0,
-            full_mesh_probes: None,
+        // A restarted peer re-registers with the same ID: update in place so
+        // cumulative stats are not double-counted and first_seen is preserved
+        let is_new = match self.peers.entry(peer_id.clone()) {
+            Entry::Occupied(mut occupied) => {
+                let entry = occupied.get_mut();
+                entry.registration = registration.clone();
+                entry.last_heartbeat = now;
+                entry.latitude = latitude;
+                entry.longitude = longitude;
+                entry.country_code = country_code.clone();
+                // A restarted node starts counting heartbeats from scratch
+                entry.last_heartbeat_seq = None;
+                false
+            }
+            Entry::Vacant(vacant) => {
+                let (first_seen, lifecycle) = historical.map_or((now, VecDeque::new()), |entry| {
+                    (entry.first_seen, entry.lifecycle)
+                });
+                let mut entry = NodeEntry {
+                    registration: registration.clone(),
+                    first_seen,
+                    last_heartbeat: now,
+                    latitude,
+                    longitude,
+                    country_code: country_code.clone(),
+                    nat_stats: NatStats {
+                        attempts: 0,
+                        direct_success: 0,
+                        hole_punch_success: 0,
+                        relay_success: 0,
+                        failures: 0,
+                        inbound_connections: 0,
+                        is_behind_nat: false,
+                        last_failure: None,
+                        failure_reasons: HashMap::new(),
+                        time_to_first_connection: None,
+                    },
+                    gossip_stats: NodeGossipStats::default(),
+                    connected_peers: 0,
+                    bytes_sent: 0,
+                    bytes_received: 0,
+                    full_mesh_probes: None,
+                    last_heartbeat_seq: None,
+                    lifecycle,
+                };
+                entry.record_lifecycle(now, LifecycleEvent::Registered);
+                vacant.insert(entry);
+                true
+            }
         };
 
-        let is_new = !self.peers.contains_key(&peer_id);
-        self.peers.insert(peer_id.clone(), entry);
-
         // Track unique nodes and peak
         if is_new && !was_historical {
             self.total_unique_nodes.fetch_add(1, Ordering::Relaxed);
@@ -208,14 +463,17 @@ impl PeerStore {
         }
 
         if is_new || was_historical {
-            let _ = self.event_tx.send(NetworkEvent::NodeRegistered {
+            let (latitude, longitude, location_estimated) =
+                display_position(&peer_id, latitude, longitude);
+            self.emit(NetworkEvent::NodeRegistered {
                 peer_id: peer_id.clone(),
                 country_code,
                 latitude,
                 longitude,
+                location_estimated,
             });
 
-            let _ = self.event_tx.send(NetworkEvent::ConnectivityTestRequest {
+            self.emit(NetworkEvent::ConnectivityTestRequest {
                 peer_id: peer_id.clone(),
                 addresses: registration.external_addresses.clone(),
                 relay_addr: None,
@@ -232,14 +490,28 @@ impl PeerStore {
 
     /// Process a heartbeat from a node.
     pub fn heartbeat(&self, heartbeat: NodeHeartbeat) -> Result<(), String> {
-        let peer_id = &heartbeat.peer_id;
+        let peer_id = &canonical_peer_id(&heartbeat.peer_id);
 
         let mut entry = self
             .peers
             .get_mut(peer_id)
             .ok_or_else(|| format!("Unknown peer: {}", peer_id))?;
 
-        entry.last_heartbeat = Instant::now();
+        // Ignore duplicated or reordered heartbeats so stats aren't double-counted
+        if let Some(seq) = heartbeat.seq {
+            if entry.last_heartbeat_seq.is_some_and(|last| seq <= last) {
+                tracing::debug!(
+                    "Ignoring stale heartbeat seq {} from {} (last {:?})",
+                    seq,
+                    peer_id,
+                    entry.last_heartbeat_seq
+                );
+                return Ok(());
+            }
+            entry.last_heartbeat_seq = Some(seq);
+        }
+
+        entry.last_heartbeat = self.clock.now();
         entry.connected_peers = heartbeat.connected_peers;
         entry.bytes_sent = heartbeat.bytes_sent;
         entry.bytes_received = heartbeat.bytes_received;
@@ -253,9 +525,15 @@ impl PeerStore {
             entry.registration.nat_type = nat_type;
         }
 
-        // Update NAT stats if provided
+        // Update NAT stats if provided, feeding the rolling success rate
+        let mut rate_event = None;
         if let Some(stats) = heartbeat.nat_stats {
+            let attempts = stats.attempts.saturating_sub(entry.nat_stats.attempts);
+            let successes = nat_successes(&stats).saturating_sub(nat_successes(&entry.nat_stats));
             entry.nat_stats = stats;
+            if let Ok(mut monitor) = self.success_rate.lock() {
+                rate_event = monitor.record(attempts, successes);
+            }
         }
 
         // Update gossip stats if provided
@@ -264,8 +542,13 @@ impl PeerStore {
         }
 
         // Update full-mesh probe results if provided
-        if heartbeat.full_mesh_probes.is_some() {
-            entry.full_mesh_probes = heartbeat.full_mesh_probes;
+        if let Some(probes) = heartbeat.full_mesh_probes {
+            entry.full_mesh_probes = Some(
+                probes
+                    .into_iter()
+                    .map(|(target, result)| (canonical_peer_id(&target), result))
+                    .collect(),
+            );
         }
 
         // Update global counters
@@ -274,17 +557,57 @@ impl PeerStore {
             Ordering::Relaxed,
         );
 
+        drop(entry);
+        if let Some(event) = rate_event {
+            self.emit(event);
+        }
+
         Ok(())
     }
 
+    /// When a currently registered peer was first seen by this registry.
+    pub fn first_seen(&self, peer_id: &str) -> Option<Instant> {
+        self.peers
+            .get(&canonical_peer_id(peer_id))
+            .map(|entry| entry.first_seen)
+    }
+
     /// Get all registered peers.
     pub fn get_all_peers(&self) -> Vec<PeerInfo> {
         self.get_peers_except("")
     }
 
+    /// Get one page of peers ordered by peer ID.
+    ///
+    /// Returns peers whose ID sorts after `after`, at most `limit` of them,
+    /// plus the ID to resume from if more remain. Keying on the last ID
+    /// rather than an offset means peers joining or expiring between
+    /// requests never shift a page: peers present throughout are returned
+    /// exactly once.
+    pub fn get_peers_page(
+        &self,
+        after: Option<&str>,
+        limit: usize,
+    ) -> (Vec<PeerInfo>, Option<String>) {
+        let mut peers: Vec<PeerInfo> = self
+            .get_all_peers()
+            .into_iter()
+            .filter(|p| after.is_none_or(|after| p.peer_id.as_str() > after))
+            .collect();
+        peers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+        let more = peers.len() > limit;
+        peers.truncate(limit);
+        let next = if more {
+            peers.last().map(|p| p.peer_id.clone())
+        } else {
+            None
+        };
+        (peers, next)
+    }
+
     /// Get all peers except the specified one.
     fn get_peers_except(&self, exclude_peer_id: &str) -> Vec<PeerInfo> {
-        let now = Instant::now();
+        let now = self.clock.now();
         let active_threshold = Duration::from_secs(ACTIVE_THRESHOLD_SECS);
 
         self.peers
@@ -358,13 +681,17 @@ impl PeerStore {
         // Get unix timestamp for last_seen
         let last_seen = crate::registry::types::unix_timestamp().saturating_sub(since_heartbeat);
 
+        let (latitude, longitude, location_estimated) =
+            display_position(&entry.registration.peer_id, entry.latitude, entry.longitude);
+
         PeerInfo {
             peer_id: entry.registration.peer_id.clone(),
             addresses,
             nat_type: entry.registration.nat_type,
             country_code: entry.country_code.clone(),
-            latitude: entry.latitude,
-            longitude: entry.longitude,
+            latitude,
+            longitude,
+            location_estimated,
             last_seen,
             connection_success_rate: success_rate,
             capabilities: entry.registration.capabilities.clone(),
@@ -376,12 +703,14 @@ impl PeerStore {
             connected_peers: entry.connected_peers,
             gossip_stats: Some(entry.gossip_stats.clone()),
             full_mesh_probes: entry.full_mesh_probes.clone(),
+            last_error: entry.nat_stats.last_failure,
+            time_to_first_connection: entry.nat_stats.time_to_first_connection,
         }
     }
 
     /// Get network-wide statistics.
     pub fn get_stats(&self) -> NetworkStats {
-        let now = Instant::now();
+        let now = self.clock.now();
         let active_threshold = Duration::from_secs(ACTIVE_THRESHOLD_SECS);
 
         let mut total_nodes = 0;
@@ -440,13 +769,14 @@ impl PeerStore {
             uptime_secs: self.created_at.elapsed().as_secs(),
             ipv4_connections: self.ipv4_connections.load(Ordering::Relaxed),
             ipv6_connections: self.ipv6_connections.load(Ordering::Relaxed),
+            rtt_histogram: self.get_rtt_histogram(),
         }
     }
 
     /// Move expired entries to historical (called periodically).
     /// Returns the number of nodes moved to historical.
     pub fn cleanup_expired(&self) -> usize {
-        let now = Instant::now();
+        let now = self.clock.now();
         let mut moved_to_historical = Vec::new();
 
         for entry in self.peers.iter() {
@@ -457,12 +787,13 @@ impl PeerStore {
         }
 
         let count = moved_to_historical.len();
-        for (peer_id, entry) in moved_to_historical {
+        for (peer_id, mut entry) in moved_to_historical {
             // Move to historical storage instead of deleting
             self.peers.remove(&peer_id);
+            entry.record_lifecycle(now, LifecycleEvent::Offline);
             self.historical_peers.insert(peer_id.clone(), entry);
 
-            let _ = self.event_tx.send(NetworkEvent::NodeOffline {
+            self.emit(NetworkEvent::NodeOffline {
                 peer_id: peer_id.clone(),
             });
         }
@@ -470,6 +801,61 @@ impl PeerStore {
         count
     }
 
+    /// Remove a node that is shutting down, without waiting for its TTL.
+    ///
+    /// The node moves to historical storage, exactly as if it had expired.
+    pub fn deregister(&self, peer_id: &str) -> Result<(), String> {
+        let peer_id = canonical_peer_id(peer_id);
+        let (peer_id, mut entry) = self
+            .peers
+            .remove(&peer_id)
+            .ok_or_else(|| format!("Unknown peer: {}", peer_id))?;
+        entry.record_lifecycle(self.clock.now(), LifecycleEvent::Offline);
+        self.historical_peers.insert(peer_id.clone(), entry);
+
+        self.emit(NetworkEvent::NodeOffline { peer_id });
+        Ok(())
+    }
+
+    /// Forget a peer entirely, active or historical.
+    ///
+    /// Unlike [`deregister`](Self::deregister) nothing is kept; used when a
+    /// peer is blocked. Returns whether the peer was known.
+    pub fn remove_peer(&self, peer_id: &str) -> bool {
+        let peer_id = canonical_peer_id(peer_id);
+        let was_active = self.peers.remove(&peer_id).is_some();
+        let was_historical = self.historical_peers.remove(&peer_id).is_some();
+        if was_active {
+            self.emit(NetworkEvent::NodeOffline { peer_id });
+        }
+        was_active || was_historical
+    }
+
+    /// Restore connection records persisted by a previous registry run.
+    ///
+    /// Restored connections are historical: none of them is considered
+    /// active until the peers report it again. New records continue the
+    /// restored ID sequence.
+    pub async fn restore_connections(&self, records: Vec<ConnectionRecord>) {
+        let mut connections = self.connections.write().await;
+        for mut record in records {
+            record.is_active = false;
+            record.from_peer = canonical_peer_id(&record.from_peer);
+            record.to_peer = canonical_peer_id(&record.to_peer);
+            if record.is_ipv6 {
+                self.ipv6_connections.fetch_add(1, Ordering::Relaxed);
+            } else {
+                self.ipv4_connections.fetch_add(1, Ordering::Relaxed);
+            }
+            self.total_connections.fetch_add(1, Ordering::Relaxed);
+            self.record_rtt(record.method, record.rtt_ms);
+            self.next_connection_id
+                .fetch_max(record.id + 1, Ordering::Relaxed);
+            connections.push(record);
+        }
+        connections.sort_by_key(|c| c.timestamp);
+    }
+
     /// Reset all statistics counters for fresh testing.
     pub async fn reset_stats(&self) {
         // Reset atomic counters
@@ -478,6 +864,9 @@ impl PeerStore {
         self.ipv4_connections.store(0, Ordering::Relaxed);
         self.ipv6_connections.store(0, Ordering::Relaxed);
         self.next_connection_id.store(0, Ordering::Relaxed);
+        if let Ok(mut histogram) = self.rtt_histogram.lock() {
+            *histogram = RttHistogram::default();
+        }
 
         // Clear connection records
         {
@@ -507,11 +896,11 @@ impl PeerStore {
         bytes_sent: u64,
         bytes_received: u64,
     ) {
-        if let Some(mut entry) = self.peers.get_mut(peer_id) {
+        if let Some(mut entry) = self.peers.get_mut(&canonical_peer_id(peer_id)) {
             entry.connected_peers = connected_peers as usize;
             entry.bytes_sent = bytes_sent;
             entry.bytes_received = bytes_received;
-            entry.last_heartbeat = std::time::Instant::now();
+            entry.last_heartbeat = self.clock.now();
         }
     }
 
@@ -526,6 +915,8 @@ impl PeerStore {
         connectivity: ConnectivityMatrix,
     ) {
         let id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        let from_peer = canonical_peer_id(&from_peer);
+        let to_peer = canonical_peer_id(&to_peer);
 
         // Get country codes from peer entries
         let from_country = self
@@ -559,12 +950,13 @@ impl PeerStore {
         }
 
         self.total_connections.fetch_add(1, Ordering::Relaxed);
+        self.record_rtt(method, rtt_ms);
 
         let mut connections = self.connections.write().await;
         connections.push(record);
 
         // Also broadcast the connection event
-        let _ = self.event_tx.send(NetworkEvent::ConnectionEstablished {
+        self.emit(NetworkEvent::ConnectionEstablished {
             from_peer,
             to_peer,
             method,
@@ -572,9 +964,80 @@ impl PeerStore {
         });
     }
 
+    /// Add a reported RTT to the histogram; reports without one are skipped.
+    fn record_rtt(&self, method: ConnectionMethod, rtt_ms: Option<u64>) {
+        if let (Some(rtt), Ok(mut histogram)) = (rtt_ms, self.rtt_histogram.lock()) {
+            histogram.record(method, rtt);
+        }
+    }
+
+    /// RTT distribution of connection reports, bucketed per method.
+    pub fn get_rtt_histogram(&self) -> RttHistogram {
+        self.rtt_histogram
+            .lock()
+            .map(|histogram| histogram.clone())
+            .unwrap_or_default()
+    }
+
+    /// Group registered peers into connected components of the connection graph.
+    ///
+    /// Edges are the recorded connections between registered peers, treated as
+    /// undirected. A healthy network forms a single component; more than one
+    /// means the network is partitioned. Peers with no connections form their
+    /// own single-peer component. Components are sorted largest first and
+    /// peer IDs within a component are sorted.
+    pub async fn connectivity_components(&self) -> Vec<Vec<String>> {
+        let mut adjacency: BTreeMap<String, Vec<String>> = self
+            .peers
+            .iter()
+            .map(|entry| (entry.key().clone(), Vec::new()))
+            .collect();
+        for record in self.connections.read().await.iter() {
+            if record.from_peer == record.to_peer
+                || !adjacency.contains_key(&record.from_peer)
+                || !adjacency.contains_key(&record.to_peer)
+            {
+                continue;
+            }
+            if let Some(edges) = adjacency.get_mut(&record.from_peer) {
+                edges.push(record.to_peer.clone());
+            }
+            if let Some(edges) = adjacency.get_mut(&record.to_peer) {
+                edges.push(record.from_peer.clone());
+            }
+        }
+        connected_components(&adjacency)
+    }
+
+    /// Registered peers with no successful connections.
+    ///
+    /// A peer is isolated when it has no recorded connection to another
+    /// registered peer (a single-peer component) and its heartbeats report no
+    /// NAT traversal successes either. Sorted by peer ID.
+    pub async fn isolated_peers(&self) -> Vec<String> {
+        self.connectivity_components()
+            .await
+            .into_iter()
+            .filter(|component| component.len() == 1)
+            .flatten()
+            .filter(|peer_id| {
+                self.peers
+                    .get(peer_id)
+                    .is_some_and(|entry| nat_successes(&entry.nat_stats) == 0)
+            })
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// True if the connection graph has more than one component.
+    pub async fn is_partitioned(&self) -> bool {
+        self.connectivity_components().await.len() > 1
+    }
+
     /// Get all registered peers including historical.
     pub fn get_all_peers_with_historical(&self) -> Vec<PeerInfo> {
-        let now = Instant::now();
+        let now = self.clock.now();
         let active_threshold = Duration::from_secs(ACTIVE_THRESHOLD_SECS);
 
         let mut peers: Vec<PeerInfo> = self
@@ -596,7 +1059,7 @@ impl PeerStore {
 
     /// Get experiment results summary.
     pub async fn get_experiment_results(&self) -> ExperimentResults {
-        let now = Instant::now();
+        let now = self.clock.now();
         let active_threshold = Duration::from_secs(ACTIVE_THRESHOLD_SECS);
 
         let connections = self.connections.read().await;
@@ -676,7 +1139,7 @@ impl PeerStore {
     /// Get NAT statistics for a specific node.
     pub fn get_node_nat_stats(&self, peer_id: &str) -> NatStats {
         self.peers
-            .get(peer_id)
+            .get(&canonical_peer_id(peer_id))
             .map(|entry| entry.nat_stats.clone())
             .unwrap_or_default()
     }
@@ -685,7 +1148,7 @@ impl PeerStore {
     /// Returns (connected_peers, bytes_sent, bytes_received)
     pub fn get_node_connection_stats(&self, peer_id: &str) -> (usize, u64, u64) {
         self.peers
-            .get(peer_id)
+            .get(&canonical_peer_id(peer_id))
             .map(|entry| {
                 (
                     entry.connected_peers,
@@ -706,37 +1169,21 @@ impl PeerStore {
     /// Resolve geographic coordinates with optional client IP fallback.
     ///
     /// Priority:
-    /// 1. First address in external_addresses list
-    /// 2. Client IP from HTTP request (if external_addresses is empty)
-    /// 3. Default London coordinates
+    /// 1. First public address in external_addresses (see [`geo_address`])
+    /// 2. Client IP from HTTP request (if no external address is public)
+    /// 3. No location (0, 0); served as an estimated position
     fn resolve_geo_with_fallback(
         &self,
         addresses: &[SocketAddr],
         client_ip: Option<IpAddr>,
     ) -> (f64, f64, Option<String>) {
-        // Try to get IP from external addresses first
-        let ip = if let Some(addr) = addresses.first() {
-            match addr.ip() {
-                IpAddr::V4(v4) => Some(IpAddr::V4(v4)),
-                IpAddr::V6(v6) => {
-                    // Handle IPv4-mapped IPv6 addresses
-                    if let Some(v4) = v6.to_ipv4_mapped() {
-                        Some(IpAddr::V4(v4))
-                    } else {
-                        Some(IpAddr::V6(v6))
-                    }
-                }
-            }
-        } else {
-            // No external addresses - use client IP from HTTP request
-            client_ip
-        };
+        let ip = geo_address(addresses).or(client_ip);
 
         match ip {
             Some(ip) => self.geo_provider.lookup(ip),
             None => {
-                // No IP available - return London as default
-                (51.5, -0.1, Some("GB".to_string()))
+                // No IP available - leave unresolved
+                (0.0, 0.0, None)
             }
         }
     }
@@ -930,7 +1377,7 @@ impl PeerStore {
     pub fn get_cache_status(&self) -> crate::registry::types::CacheStatusResponse {
         use crate::registry::types::{CacheStatusResponse, NatType, QualityDistribution};
 
-        let now = Instant::now();
+        let now = self.clock.now();
         let active_threshold = Duration::from_secs(ACTIVE_THRESHOLD_SECS);
 
         let mut active_peers = 0usize;
@@ -1193,7 +1640,8 @@ impl PeerStore {
         };
 
         // Check 1: Verify the observer is a registered peer
-        if !self.peers.contains_key(&proof.observer_id) {
+        let observer_id = canonical_peer_id(&proof.observer_id);
+        if !self.peers.contains_key(&observer_id) {
             result.valid = false;
             result.errors.push(format!(
                 "Observer '{}' is not a registered peer",
@@ -1218,7 +1666,8 @@ impl PeerStore {
             .collect();
 
         for observed in &proof.observed_peers {
-            if !registry_peers.contains(observed) && observed != &proof.observer_id {
+            let canonical = canonical_peer_id(observed);
+            if !registry_peers.contains(&canonical) && canonical != observer_id {
                 result
                     .warnings
                     .push(format!("Observed peer '{}' not in registry", observed));
@@ -1235,7 +1684,7 @@ impl PeerStore {
             }
 
             // Verify the validating peer exists
-            if !registry_peers.contains(&cv.peer_id) {
+            if !registry_peers.contains(&canonical_peer_id(&cv.peer_id)) {
                 result
                     .errors
                     .push(format!("Cross-validator '{}' not in registry", cv.peer_id));
@@ -1394,7 +1843,7 @@ impl PeerStore {
     // ===== Dashboard API Methods =====
 
     /// Get aggregated overview data for the overview page.
-    pub fn get_overview_data(&self) -> crate::dashboard::OverviewResponse {
+    pub async fn get_overview_data(&self) -> crate::dashboard::OverviewResponse {
         use crate::dashboard::{
             ConnectedPeerApi, LocalNodeApi, NetworkStatsApi, OverviewResponse, ProofStatusApi,
         };
@@ -1486,11 +1935,37 @@ impl PeerStore {
             connected_peers,
             local_node,
             uptime_secs,
+            isolated_peers: self.isolated_peers().await,
+            failure_reasons: self.failure_reason_breakdown(),
+        }
+    }
+
+    /// Failed connections across all nodes, grouped by reason.
+    ///
+    /// Sums the per-node failure tallies reported in heartbeats, including
+    /// nodes that have since gone offline. Most frequent reasons come first.
+    pub fn failure_reason_breakdown(&self) -> Vec<crate::dashboard::FailureReasonApi> {
+        let mut totals: HashMap<FailureReasonCode, u64> = HashMap::new();
+        for entry in self.peers.iter().chain(self.historical_peers.iter()) {
+            for (reason, count) in &entry.nat_stats.failure_reasons {
+                *totals.entry(*reason).or_default() += count;
+            }
         }
+        let mut breakdown: Vec<_> = totals
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(reason, count)| crate::dashboard::FailureReasonApi {
+                reason,
+                label: reason.to_string(),
+                count,
+            })
+            .collect();
+        breakdown.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+        breakdown
     }
 
     /// Get connection history for the connectivity matrix.
-    pub fn get_connections_data(&self) -> crate::dashboard::ConnectionsResponse {
+    pub async fn get_connections_data(&self) -> crate::dashboard::ConnectionsResponse {
         use crate::dashboard::{ConnectionEntryApi, ConnectionsResponse, DirectionalStatsApi};
 
         let connections: Vec<ConnectionEntryApi> = self
@@ -1540,14 +2015,184 @@ impl PeerStore {
 
         let total_peers = connections.len();
         let connected_count = connections.len();
+        let edges: Vec<_> = self
+            .connections
+            .read()
+            .await
+            .iter()
+            .flat_map(connection_edges)
+            .collect();
+        // Inbound edges are evidence that the remote peer reached the reporter
+        let coverage = MatrixCoverage::from_results(
+            connections.iter().map(|c| c.full_id.as_str()),
+            edges.iter().map(|edge| match edge.direction.as_str() {
+                "inbound" => (edge.to_peer.as_str(), edge.from_peer.as_str(), edge.success),
+                _ => (edge.from_peer.as_str(), edge.to_peer.as_str(), edge.success),
+            }),
+        );
 
         ConnectionsResponse {
             connections,
             total_peers,
             connected_count,
+            edges,
+            coverage,
         }
     }
 
+    /// Timeline of one peer, active or historical: its registrations,
+    /// every connection path tested to or from it, method changes per
+    /// remote peer, and when it went offline. `None` for unknown peers.
+    pub async fn get_peer_timeline(
+        &self,
+        peer_id: &str,
+    ) -> Option<crate::dashboard::PeerTimelineResponse> {
+        use crate::dashboard::{PeerTimelineEventApi, PeerTimelineKind, PeerTimelineResponse};
+
+        let peer_id = canonical_peer_id(peer_id);
+        let lifecycle = self
+            .peers
+            .get(&peer_id)
+            .map(|entry| entry.lifecycle.clone())
+            .or_else(|| {
+                self.historical_peers
+                    .get(&peer_id)
+                    .map(|entry| entry.lifecycle.clone())
+            })?;
+
+        let now = self.clock.now();
+        let generated_at = crate::registry::types::unix_timestamp();
+        let mut events: Vec<(u64, PeerTimelineKind)> = lifecycle
+            .iter()
+            .map(|(at, event)| {
+                let timestamp = generated_at.saturating_sub(now.duration_since(*at).as_secs());
+                let kind = match event {
+                    LifecycleEvent::Registered => PeerTimelineKind::Registered,
+                    LifecycleEvent::Offline => PeerTimelineKind::Offline,
+                };
+                (timestamp, kind)
+            })
+            .collect();
+
+        let mut last_method: HashMap<String, String> = HashMap::new();
+        for record in self.connections.read().await.iter() {
+            let (remote_peer, direction) = if record.from_peer == peer_id {
+                (&record.to_peer, "outbound")
+            } else if record.to_peer == peer_id {
+                (&record.from_peer, "inbound")
+            } else {
+                continue;
+            };
+
+            let method = format!("{:?}", record.method).to_lowercase();
+            if let Some(previous) = last_method
+                .insert(remote_peer.clone(), method.clone())
+                .filter(|previous| *previous != method)
+            {
+                events.push((
+                    record.timestamp,
+                    PeerTimelineKind::MethodChanged {
+                        remote_peer: remote_peer.clone(),
+                        from: previous,
+                        to: method,
+                    },
+                ));
+            }
+            for edge in connection_edges(record) {
+                events.push((
+                    record.timestamp,
+                    PeerTimelineKind::ConnectionAttempt {
+                        remote_peer: remote_peer.clone(),
+                        method: edge.method,
+                        direction: direction.to_string(),
+                        success: edge.success,
+                        rtt_ms: edge.rtt_ms,
+                    },
+                ));
+            }
+        }
+        // Timestamps are whole seconds: within one second a registration
+        // comes first and going offline last; the sort is stable, so
+        // connections keep their recorded order
+        events.sort_by_key(|(timestamp, kind)| {
+            let rank = match kind {
+                PeerTimelineKind::Registered => 0,
+                PeerTimelineKind::Offline => 2,
+                _ => 1,
+            };
+            (*timestamp, rank)
+        });
+
+        let events = events
+            .into_iter()
+            .map(|(timestamp, kind)| PeerTimelineEventApi {
+                timestamp,
+                utc: chrono::DateTime::from_timestamp(timestamp as i64, 0)
+                    .map(|utc| utc.to_rfc3339())
+                    .unwrap_or_default(),
+                seconds_ago: generated_at.saturating_sub(timestamp),
+                kind,
+            })
+            .collect();
+        Some(PeerTimelineResponse {
+            peer_id,
+            generated_at,
+            events,
+        })
+    }
+
+    /// Get median RTT between each pair of countries from connection reports.
+    ///
+    /// Reports without an RTT or without a country on either end are skipped.
+    pub async fn get_latency_matrix(&self) -> crate::dashboard::LatencyMatrixResponse {
+        use crate::dashboard::{LatencyCellApi, LatencyMatrixResponse, MIN_LATENCY_SAMPLES};
+        use crate::tui::country_flag;
+
+        let mut samples: BTreeMap<(String, String), Vec<u64>> = BTreeMap::new();
+        for record in self.connections.read().await.iter() {
+            if let (Some(rtt), Some(from), Some(to)) =
+                (record.rtt_ms, &record.from_country, &record.to_country)
+            {
+                samples
+                    .entry((from.clone(), to.clone()))
+                    .or_default()
+                    .push(rtt);
+            }
+        }
+
+        let mut countries: Vec<String> = samples
+            .keys()
+            .flat_map(|(from, to)| [from.clone(), to.clone()])
+            .collect();
+        countries.sort();
+        countries.dedup();
+
+        let cells = samples
+            .into_iter()
+            .map(|((source, dest), mut rtts)| {
+                rtts.sort_unstable();
+                let mid = rtts.len() / 2;
+                let median_rtt_ms = (rtts.len() >= MIN_LATENCY_SAMPLES).then(|| {
+                    if rtts.len() % 2 == 0 {
+                        (rtts[mid - 1] + rtts[mid]) / 2
+                    } else {
+                        rtts[mid]
+                    }
+                });
+                LatencyCellApi {
+                    source_flag: country_flag(&source).to_string(),
+                    dest_flag: country_flag(&dest).to_string(),
+                    source_country: source,
+                    dest_country: dest,
+                    median_rtt_ms,
+                    samples: rtts.len(),
+                }
+            })
+            .collect();
+
+        LatencyMatrixResponse { countries, cells }
+    }
+
     /// Get recent protocol frames for the log display.
     pub fn get_frames_data(&self, _limit: usize) -> crate::dashboard::FramesResponse {
         use crate::dashboard::FramesResponse;
@@ -1677,16 +2322,45 @@ pub struct ProofValidationResult {
     pub warnings: Vec<String>,
 }
 
+/// Connected components of an undirected graph given as an adjacency map.
+fn connected_components(adjacency: &BTreeMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    let mut visited: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let mut components = Vec::new();
+    for start in adjacency.keys() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = Vec::new();
+        let mut stack = vec![start.as_str()];
+        while let Some(peer) = stack.pop() {
+            component.push(peer.to_string());
+            for next in adjacency.get(peer).into_iter().flatten() {
+                if visited.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        component.sort();
+        components.push(component);
+    }
+    components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    components
+}
+
 impl Default for PeerStore {
     fn default() -> Self {
         // Note: This creates an Arc-less instance for testing
         let (event_tx, _) = broadcast::channel(1000);
+        let (sequenced_tx, _) = broadcast::channel(1000);
+        let clock = system_clock();
         Self {
             peers: DashMap::new(),
             historical_peers: DashMap::new(),
             connections: RwLock::new(Vec::new()),
             event_tx,
-            created_at: Instant::now(),
+            sequenced_tx,
+            event_log: Mutex::new(EventLog::default()),
+            created_at: clock.now(),
             total_connections: AtomicU64::new(0),
             total_bytes: AtomicU64::new(0),
             ipv4_connections: AtomicU64::new(0),
@@ -1696,10 +2370,82 @@ impl Default for PeerStore {
             ttl_secs: DEFAULT_TTL_SECS,
             next_connection_id: AtomicU64::new(1),
             geo_provider: BgpGeoProvider::new(),
+            success_rate: Mutex::new(SuccessRateMonitor::new(DEFAULT_SUCCESS_RATE_FLOOR)),
+            rtt_histogram: Mutex::new(RttHistogram::default()),
+            clock,
         }
     }
 }
 
+/// Expand a connection record into one edge per tested path.
+///
+/// Reports without per-path results yield a single edge for the reported
+/// method, which only exists because the connection succeeded.
+fn connection_edges(record: &ConnectionRecord) -> Vec<crate::dashboard::ConnectionEdgeApi> {
+    let matrix = &record.connectivity;
+    let direction = match matrix.test_pattern {
+        TestPattern::Outbound => "outbound",
+        TestPattern::Inbound | TestPattern::InboundUnderLoad => "inbound",
+        TestPattern::Simultaneous => "simultaneous",
+    };
+    let edge =
+        |method: &str, rtt_ms: Option<u64>, success: bool| crate::dashboard::ConnectionEdgeApi {
+            from_peer: record.from_peer.clone(),
+            to_peer: record.to_peer.clone(),
+            method: method.to_string(),
+            direction: direction.to_string(),
+            rtt_ms,
+            success,
+        };
+
+    let paths = [
+        (
+            "direct_ipv4",
+            matrix.ipv4_direct_tested,
+            matrix.ipv4_direct_rtt_ms,
+            matrix.ipv4_direct_success,
+        ),
+        (
+            "direct_ipv6",
+            matrix.ipv6_direct_tested,
+            matrix.ipv6_direct_rtt_ms,
+            matrix.ipv6_direct_success,
+        ),
+        (
+            "nat_traversal",
+            matrix.nat_traversal_tested,
+            matrix.nat_traversal_rtt_ms,
+            matrix.nat_traversal_success,
+        ),
+        (
+            "relay",
+            matrix.relay_tested,
+            matrix.relay_rtt_ms,
+            matrix.relay_success,
+        ),
+        (
+            "masque",
+            matrix.masque_tested,
+            matrix.masque_rtt_ms,
+            matrix.masque_success,
+        ),
+    ];
+    let edges: Vec<_> = paths
+        .into_iter()
+        .filter(|(_, tested, _, _)| *tested)
+        .map(|(method, _, rtt_ms, success)| edge(method, rtt_ms, success))
+        .collect();
+    if !edges.is_empty() {
+        return edges;
+    }
+    let method = match record.method {
+        ConnectionMethod::Direct => "direct",
+        ConnectionMethod::HolePunched => "hole_punched",
+        ConnectionMethod::Relayed => "relayed",
+    };
+    vec![edge(method, record.rtt_ms, true)]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1736,6 +2482,393 @@ mod tests {
         assert_eq!(all_peers.len(), 2);
     }
 
+    #[test]
+    fn test_peer_pages_survive_churn() {
+        let store = PeerStore::new();
+        for id in ["aa01", "aa02", "aa03", "aa04", "aa05"] {
+            store.register(make_registration(id)).unwrap();
+        }
+
+        let (page, cursor) = store.get_peers_page(None, 2);
+        let mut seen: Vec<String> = page.into_iter().map(|p| p.peer_id).collect();
+        assert_eq!(seen, vec!["aa01", "aa02"]);
+        assert_eq!(cursor.as_deref(), Some("aa02"));
+
+        // Churn between pages: an already-returned peer leaves, and peers
+        // join on both sides of the cursor
+        store.peers.remove("aa01");
+        store.register(make_registration("aa00")).unwrap();
+        store.register(make_registration("aa06")).unwrap();
+
+        let mut cursor = cursor;
+        while let Some(after) = cursor {
+            let (page, next) = store.get_peers_page(Some(&after), 2);
+            seen.extend(page.into_iter().map(|p| p.peer_id));
+            cursor = next;
+        }
+        assert_eq!(seen, vec!["aa01", "aa02", "aa03", "aa04", "aa05", "aa06"]);
+    }
+
+    #[test]
+    fn test_heartbeat_last_failure_exposed_on_peer() {
+        let store = PeerStore::new();
+        store.register(make_registration("aa01")).unwrap();
+
+        let mut heartbeat = nat_heartbeat("aa01", 3, 1);
+        if let Some(stats) = heartbeat.nat_stats.as_mut() {
+            stats.last_failure = Some(FailureReasonCode::PqcNegotiationFailed);
+        }
+        store.heartbeat(heartbeat).unwrap();
+
+        let peers = store.get_all_peers();
+        assert_eq!(
+            peers[0].last_error,
+            Some(FailureReasonCode::PqcNegotiationFailed)
+        );
+        let json = serde_json::to_value(&peers[0]).unwrap();
+        assert_eq!(json["last_error"], "pqc_negotiation_failed");
+    }
+
+    #[tokio::test]
+    async fn test_latency_matrix_medians_by_country_pair() {
+        let store = PeerStore::new();
+        let record =
+            |id: u64, from: &str, to: Option<&str>, rtt_ms: Option<u64>| ConnectionRecord {
+                id,
+                from_peer: format!("from{}", id),
+                to_peer: format!("to{}", id),
+                method: ConnectionMethod::Direct,
+                is_ipv6: false,
+                rtt_ms,
+                timestamp: id,
+                from_country: Some(from.to_string()),
+                to_country: to.map(str::to_string),
+                is_active: true,
+                connectivity: ConnectivityMatrix::default(),
+            };
+        store
+            .restore_connections(vec![
+                record(1, "DE", Some("US"), Some(120)),
+                record(2, "DE", Some("US"), Some(80)),
+                record(3, "DE", Some("US"), Some(100)),
+                record(4, "DE", Some("US"), Some(400)),
+                record(5, "DE", Some("DE"), Some(10)),
+                record(6, "DE", Some("DE"), Some(12)),
+                record(7, "DE", Some("DE"), Some(14)),
+                record(8, "US", Some("DE"), Some(95)),
+                // Skipped: no RTT, or unknown destination country
+                record(9, "US", Some("DE"), None),
+                record(10, "US", None, Some(50)),
+            ])
+            .await;
+
+        let matrix = store.get_latency_matrix().await;
+        assert_eq!(matrix.countries, vec!["DE".to_string(), "US".to_string()]);
+        let cell = |from: &str, to: &str| {
+            matrix
+                .cells
+                .iter()
+                .find(|c| c.source_country == from && c.dest_country == to)
+                .unwrap()
+        };
+        assert_eq!(cell("DE", "US").median_rtt_ms, Some(110));
+        assert_eq!(cell("DE", "US").samples, 4);
+        assert_eq!(cell("DE", "US").source_flag, "🇩🇪");
+        // Intra-country pairs form the diagonal
+        assert_eq!(cell("DE", "DE").median_rtt_ms, Some(12));
+        // Too few samples for a meaningful median
+        assert_eq!(cell("US", "DE").median_rtt_ms, None);
+        assert_eq!(cell("US", "DE").samples, 1);
+        assert_eq!(matrix.cells.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rtt_histogram_by_method() {
+        let store = PeerStore::new();
+        let report = |method, rtt_ms| {
+            store.record_connection(
+                "a".to_string(),
+                "b".to_string(),
+                method,
+                false,
+                rtt_ms,
+                ConnectivityMatrix::default(),
+            )
+        };
+        report(ConnectionMethod::Direct, Some(8)).await;
+        report(ConnectionMethod::Direct, Some(10)).await;
+        report(ConnectionMethod::Direct, Some(30)).await;
+        report(ConnectionMethod::Relayed, Some(120)).await;
+        report(ConnectionMethod::Relayed, Some(900)).await;
+        // No RTT reported: not counted
+        report(ConnectionMethod::HolePunched, None).await;
+
+        let histogram = store.get_rtt_histogram();
+        assert_eq!(
+            histogram.buckets,
+            [
+                "0-10", "10-25", "25-50", "50-100", "100-250", "250-500", "500+"
+            ]
+        );
+        assert_eq!(histogram.direct, [1, 1, 1, 0, 0, 0, 0]);
+        assert_eq!(histogram.relayed, [0, 0, 0, 0, 1, 0, 1]);
+        assert_eq!(histogram.hole_punched, [0; 7]);
+        assert_eq!(store.get_stats().rtt_histogram, histogram);
+
+        store.reset_stats().await;
+        assert_eq!(store.get_rtt_histogram().samples(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_overview_tallies_failure_reasons() {
+        let store = PeerStore::new();
+        let reports = [
+            (
+                "aa01",
+                vec![
+                    (FailureReasonCode::Timeout, 4),
+                    (FailureReasonCode::ConnectionRefused, 1),
+                ],
+            ),
+            (
+                "aa02",
+                vec![
+                    (FailureReasonCode::Timeout, 2),
+                    (FailureReasonCode::TlsError, 3),
+                ],
+            ),
+        ];
+        for (peer_id, reasons) in reports {
+            store.register(make_registration(peer_id)).unwrap();
+            let mut heartbeat = nat_heartbeat(peer_id, 10, 0);
+            if let Some(stats) = heartbeat.nat_stats.as_mut() {
+                stats.failure_reasons = reasons.into_iter().collect();
+            }
+            store.heartbeat(heartbeat).unwrap();
+        }
+
+        let overview = store.get_overview_data().await;
+        let tally: Vec<(FailureReasonCode, u64)> = overview
+            .failure_reasons
+            .iter()
+            .map(|r| (r.reason, r.count))
+            .collect();
+        assert_eq!(
+            tally,
+            vec![
+                (FailureReasonCode::Timeout, 6),
+                (FailureReasonCode::TlsError, 3),
+                (FailureReasonCode::ConnectionRefused, 1),
+            ]
+        );
+        assert_eq!(overview.failure_reasons[0].label, "Connection timeout");
+    }
+
+    #[test]
+    fn test_node_registered_event_carries_estimated_position() {
+        let store = PeerStore::new();
+        let mut events = store.subscribe();
+        store
+            .register(NodeRegistration {
+                external_addresses: vec![],
+                ..make_registration("abcd1234")
+            })
+            .unwrap();
+
+        match events.try_recv().unwrap() {
+            NetworkEvent::NodeRegistered {
+                peer_id,
+                latitude,
+                longitude,
+                location_estimated,
+                ..
+            } => {
+                assert_eq!(peer_id, "abcd1234");
+                assert!(location_estimated);
+                assert_eq!((latitude, longitude), estimated_position("abcd1234"));
+            }
+            other => panic!("expected NodeRegistered, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unresolved_peer_gets_stable_estimated_position() {
+        let store = PeerStore::new();
+        store
+            .register(NodeRegistration {
+                external_addresses: vec![],
+                ..make_registration("abcd1234")
+            })
+            .unwrap();
+        store.register(make_registration("peer2")).unwrap();
+
+        let peers = store.get_all_peers();
+        let unresolved = peers.iter().find(|p| p.peer_id == "abcd1234").unwrap();
+        assert!(unresolved.location_estimated);
+        assert_eq!(
+            (unresolved.latitude, unresolved.longitude),
+            estimated_position("abcd1234")
+        );
+        assert!((-90.0..=90.0).contains(&unresolved.latitude));
+        assert!((-180.0..=180.0).contains(&unresolved.longitude));
+
+        let again = store.get_all_peers();
+        let again = again.iter().find(|p| p.peer_id == "abcd1234").unwrap();
+        assert_eq!(
+            (again.latitude, again.longitude),
+            (unresolved.latitude, unresolved.longitude)
+        );
+
+        let resolved = peers.iter().find(|p| p.peer_id == "peer2").unwrap();
+        assert!(!resolved.location_estimated);
+        assert_ne!(estimated_position("abcd1234"), estimated_position("peer2"));
+    }
+
+    #[tokio::test]
+    async fn test_connectivity_components_detects_partition() {
+        let store = PeerStore::new();
+        for id in ["aa01", "aa02", "aa03", "bb01", "bb02"] {
+            store.register(make_registration(id)).unwrap();
+        }
+        for (from, to) in [("aa01", "aa02"), ("aa03", "aa02"), ("bb02", "bb01")] {
+            store
+                .record_connection(
+                    from.to_string(),
+                    to.to_string(),
+                    ConnectionMethod::Direct,
+                    false,
+                    Some(20),
+                    ConnectivityMatrix::default(),
+                )
+                .await;
+        }
+
+        let components = store.connectivity_components().await;
+        assert_eq!(
+            components,
+            vec![
+                vec!["aa01".to_string(), "aa02".to_string(), "aa03".to_string()],
+                vec!["bb01".to_string(), "bb02".to_string()],
+            ]
+        );
+        assert!(store.is_partitioned().await);
+
+        // Bridging the clusters merges them
+        store
+            .record_connection(
+                "aa03".to_string(),
+                "bb01".to_string(),
+                ConnectionMethod::HolePunched,
+                false,
+                None,
+                ConnectivityMatrix::default(),
+            )
+            .await;
+        assert_eq!(store.connectivity_components().await.len(), 1);
+        assert!(!store.is_partitioned().await);
+    }
+
+    #[tokio::test]
+    async fn test_overview_lists_isolated_peers() {
+        let store = PeerStore::new();
+        for id in ["aa01", "aa02", "cc01", "dd01"] {
+            store.register(make_registration(id)).unwrap();
+        }
+        store
+            .record_connection(
+                "aa01".to_string(),
+                "aa02".to_string(),
+                ConnectionMethod::Direct,
+                false,
+                Some(20),
+                ConnectivityMatrix::default(),
+            )
+            .await;
+        // dd01 has no recorded connections but reports successes via heartbeat
+        store.heartbeat(nat_heartbeat("dd01", 4, 3)).unwrap();
+
+        let overview = store.get_overview_data().await;
+        assert_eq!(overview.isolated_peers, vec!["cc01".to_string()]);
+    }
+
+    fn nat_heartbeat(peer_id: &str, attempts: u64, successes: u64) -> NodeHeartbeat {
+        NodeHeartbeat {
+            peer_id: peer_id.to_string(),
+            connected_peers: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+            external_addresses: None,
+            nat_type: None,
+            nat_stats: Some(NatStats {
+                attempts,
+                direct_success: successes,
+                failures: attempts - successes,
+                ..Default::default()
+            }),
+            gossip_stats: None,
+            full_mesh_probes: None,
+            seq: None,
+        }
+    }
+
+    #[test]
+    fn test_success_rate_alert_and_recovery() {
+        let store = PeerStore::new();
+        store.set_success_rate_floor(0.5);
+        store.register(make_registration("peer1")).unwrap();
+        let mut rx = store.subscribe();
+
+        // Cumulative counters. Rolling rate: 100%, 50% (at the floor, no
+        // alert), 20% (alert), 17% (still alerting), 55% (recovered)
+        for (attempts, successes) in [(10, 10), (20, 10), (50, 10), (60, 10), (110, 60)] {
+            store
+                .heartbeat(nat_heartbeat("peer1", attempts, successes))
+                .unwrap();
+        }
+
+        let mut alerts = 0;
+        let mut recoveries = 0;
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                NetworkEvent::SuccessRateAlert { rate, threshold } => {
+                    assert!(rate < threshold);
+                    alerts += 1;
+                }
+                NetworkEvent::SuccessRateRecovered { rate, threshold } => {
+                    assert!(rate >= threshold);
+                    recoveries += 1;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(alerts, 1);
+        assert_eq!(recoveries, 1);
+    }
+
+    #[test]
+    fn test_stale_heartbeat_seq_is_ignored() {
+        let store = PeerStore::new();
+        store.register(make_registration("peer1")).unwrap();
+
+        let with_seq = |seq: u64, attempts: u64| NodeHeartbeat {
+            seq: Some(seq),
+            ..nat_heartbeat("peer1", attempts, attempts)
+        };
+
+        store.heartbeat(with_seq(2, 20)).unwrap();
+        // Duplicate and out-of-order heartbeats are accepted but not applied
+        store.heartbeat(with_seq(2, 99)).unwrap();
+        store.heartbeat(with_seq(1, 10)).unwrap();
+        assert_eq!(store.get_node_nat_stats("peer1").attempts, 20);
+
+        store.heartbeat(with_seq(3, 30)).unwrap();
+        assert_eq!(store.get_node_nat_stats("peer1").attempts, 30);
+
+        // Heartbeats without a seq (older clients) are always applied
+        store.heartbeat(nat_heartbeat("peer1", 40, 40)).unwrap();
+        assert_eq!(store.get_node_nat_stats("peer1").attempts, 40);
+    }
+
     #[test]
     fn test_heartbeat() {
         let store = PeerStore::new();
@@ -1756,9 +2889,13 @@ mod tests {
                 failures: 1,
                 inbound_connections: 0,
                 is_behind_nat: false,
+                last_failure: None,
+                failure_reasons: HashMap::new(),
+                time_to_first_connection: None,
             }),
             gossip_stats: None,
             full_mesh_probes: None,
+            seq: None,
         };
 
         assert!(store.heartbeat(heartbeat).is_ok());
@@ -1769,6 +2906,139 @@ mod tests {
         assert_eq!(stats.connection_breakdown.direct, 8);
     }
 
+    #[test]
+    fn test_reregistration_updates_in_place() {
+        let store = PeerStore::new();
+        store.register(make_registration("peer1")).unwrap();
+        let first_seen = store.first_seen("peer1").expect("registered");
+
+        std::thread::sleep(Duration::from_millis(5));
+
+        // Same node restarts with new addresses (and differently-cased ID)
+        let mut again = make_registration("PEER1");
+        again.external_addresses = vec!["198.51.100.7:9100".parse().unwrap()];
+        store.register(again).unwrap();
+
+        let peers = store.get_all_peers();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].peer_id, "peer1");
+        assert_eq!(
+            peers[0].addresses,
+            vec!["198.51.100.7:9100".parse::<SocketAddr>().unwrap()]
+        );
+        assert_eq!(store.first_seen("peer1"), Some(first_seen));
+        assert_eq!(store.get_stats().total_nodes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_peer_ids_canonicalized_at_every_lookup() {
+        let store = PeerStore::new();
+        store.register(make_registration("abcd")).unwrap();
+        store.register(make_registration("ef01")).unwrap();
+
+        store.update_peer_metrics("ABCD", 3, 10, 20);
+        assert_eq!(store.get_node_connection_stats(" Abcd "), (3, 10, 20));
+
+        // A connection reported with differently-cased IDs still joins the graph
+        store
+            .record_connection(
+                "ABCD".to_string(),
+                "EF01 ".to_string(),
+                ConnectionMethod::Direct,
+                false,
+                Some(10),
+                ConnectivityMatrix::default(),
+            )
+            .await;
+        assert!(!store.is_partitioned().await);
+        assert!(store.isolated_peers().await.is_empty());
+
+        let mut proof = crate::registry::types::NetworkConnectivityProof::new("ABCD".to_string());
+        proof.observed_peers.insert("EF01".to_string());
+        let result = store.validate_connectivity_proof(&proof);
+        assert!(result.valid, "{:?}", result.errors);
+        assert!(
+            result
+                .warnings
+                .iter()
+                .all(|w| !w.contains("not in registry"))
+        );
+    }
+
+    #[test]
+    fn test_events_since_replays_bounded_log() {
+        let store = PeerStore::new();
+        store.register(make_registration("peer1")).unwrap();
+        let seq = store.event_seq();
+        assert!(seq > 0);
+
+        store.deregister("peer1").unwrap();
+        let missed = store.events_since(seq).unwrap();
+        assert_eq!(missed.len(), 1);
+        assert!(matches!(
+            &missed[0],
+            (s, NetworkEvent::NodeOffline { peer_id }) if *s == seq + 1 && peer_id == "peer1"
+        ));
+        assert!(store.events_since(seq + 1).unwrap().is_empty());
+        // Ahead of the registry: it must have restarted
+        assert!(store.events_since(seq + 2).is_none());
+
+        let mut log = EventLog::default();
+        for _ in 0..EVENT_LOG_CAPACITY + 5 {
+            log.push(NetworkEvent::NodeOffline {
+                peer_id: "p".to_string(),
+            });
+        }
+        assert_eq!(log.since(5).unwrap().len(), EVENT_LOG_CAPACITY);
+        assert!(log.since(4).is_none());
+    }
+
+    #[test]
+    fn test_deregister_emits_node_offline() {
+        let store = PeerStore::new();
+        store.register(make_registration("peer1")).unwrap();
+        store.register(make_registration("peer2")).unwrap();
+        let mut rx = store.subscribe();
+
+        store.deregister("peer1").unwrap();
+
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(NetworkEvent::NodeOffline { peer_id }) if peer_id == "peer1"
+        ));
+        let active: Vec<String> = store
+            .get_all_peers()
+            .into_iter()
+            .map(|p| p.peer_id)
+            .collect();
+        assert_eq!(active, vec!["peer2".to_string()]);
+        assert_eq!(store.get_all_peers_with_historical().len(), 2);
+        assert!(store.deregister("peer1").is_err());
+    }
+
+    #[test]
+    fn test_heartbeat_defers_reaper_on_mock_clock() {
+        let clock = crate::clock::MockClock::new();
+        let store = PeerStore::with_clock(120, Arc::new(clock.clone()));
+        store.register(make_registration("peer1")).unwrap();
+        let mut rx = store.subscribe();
+
+        clock.advance(Duration::from_secs(119));
+        assert_eq!(store.cleanup_expired(), 0);
+
+        // A heartbeat restarts the TTL
+        store.heartbeat(nat_heartbeat("peer1", 0, 0)).unwrap();
+        clock.advance(Duration::from_secs(119));
+        assert_eq!(store.cleanup_expired(), 0);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(store.cleanup_expired(), 1);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(NetworkEvent::NodeOffline { peer_id }) if peer_id == "peer1"
+        ));
+    }
+
     #[test]
     fn test_unknown_peer_heartbeat() {
         let store = PeerStore::new();
@@ -1783,6 +3053,7 @@ mod tests {
             nat_stats: None,
             gossip_stats: None,
             full_mesh_probes: None,
+            seq: None,
         };
 
         assert!(store.heartbeat(heartbeat).is_err());
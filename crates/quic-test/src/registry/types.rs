@@ -962,6 +962,8 @@ pub enum ProofType {
     GossipProtocol,
     /// CRDT convergence verification
     CrdtConvergence,
+    /// EigenTrust score convergence verification
+    EigenTrustConvergence,
     /// Cross-validation of another node's proof
     CrossValidation,
 }
@@ -972,6 +974,7 @@ impl std::fmt::Display for ProofType {
             Self::Connectivity => write!(f, "Connectivity"),
             Self::GossipProtocol => write!(f, "Gossip Protocol"),
             Self::CrdtConvergence => write!(f, "CRDT Convergence"),
+            Self::EigenTrustConvergence => write!(f, "EigenTrust Convergence"),
             Self::CrossValidation => write!(f, "Cross-Validation"),
         }
     }
@@ -1258,6 +1261,10 @@ pub struct SwimProof {
     pub probes_sent: u64,
     /// Number of probes that received response
     pub probes_received: u64,
+    /// (reporter, victim) pairs where a node still heartbeating to the
+    /// registry was declared dead
+    #[serde(default)]
+    pub false_positive_pairs: Vec<(String, String)>,
 }
 
 impl SwimProof {
@@ -1571,6 +1578,126 @@ impl CrdtConvergenceProof {
     }
 }
 
+/// EigenTrust convergence proof.
+///
+/// Proves that the EigenTrust global scores each node computed agree across the
+/// network: for every peer scored by two or more nodes, the pairwise score
+/// differences stay below `epsilon`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EigenTrustConvergenceProof {
+    /// Unique test identifier
+    pub test_id: String,
+    /// Largest allowed score difference between any two nodes
+    #[serde(default)]
+    pub epsilon: f64,
+    /// Global trust scores reported by each node (node -> peer -> score)
+    #[serde(default)]
+    pub node_trust_vectors: HashMap<String, HashMap<String, f64>>,
+    /// Largest pairwise score difference observed for any peer
+    #[serde(default)]
+    pub max_pairwise_diff: f64,
+    /// Whether every pairwise difference fell below `epsilon`
+    #[serde(default)]
+    pub convergence_achieved: bool,
+    /// Iterations the slowest node needed to converge, if any reported
+    #[serde(default)]
+    pub iterations_to_convergence: Option<u32>,
+    /// Nodes whose scores deviate from the network median by `epsilon / 2` or more
+    #[serde(default)]
+    pub divergent_nodes: Vec<String>,
+    /// Signed attestations from each participating node
+    #[serde(default)]
+    pub attestations: Vec<SignedAttestation>,
+    /// When this proof was generated
+    #[serde(default = "default_system_time")]
+    pub timestamp: SystemTime,
+}
+
+impl Default for EigenTrustConvergenceProof {
+    fn default() -> Self {
+        Self {
+            test_id: String::new(),
+            epsilon: 0.0,
+            node_trust_vectors: HashMap::new(),
+            max_pairwise_diff: 0.0,
+            convergence_achieved: false,
+            iterations_to_convergence: None,
+            divergent_nodes: Vec::new(),
+            attestations: Vec::new(),
+            timestamp: SystemTime::now(),
+        }
+    }
+}
+
+impl EigenTrustConvergenceProof {
+    /// Build a proof from each node's trust vector and iteration count.
+    ///
+    /// A node is divergent when any of its scores is at least `epsilon / 2`
+    /// from the median score for that peer, so the network converges exactly
+    /// when no node is flagged.
+    pub fn from_trust_vectors(
+        test_id: String,
+        epsilon: f64,
+        node_trust_vectors: HashMap<String, HashMap<String, f64>>,
+        iterations: &HashMap<String, u32>,
+    ) -> Self {
+        let mut scores_by_peer: HashMap<&str, Vec<(&str, f64)>> = HashMap::new();
+        for (node, vector) in &node_trust_vectors {
+            for (peer, score) in vector {
+                scores_by_peer
+                    .entry(peer.as_str())
+                    .or_default()
+                    .push((node.as_str(), *score));
+            }
+        }
+
+        let mut max_pairwise_diff: f64 = 0.0;
+        let mut divergent = std::collections::BTreeSet::new();
+        for scores in scores_by_peer.values().filter(|s| s.len() > 1) {
+            let mut sorted: Vec<f64> = scores.iter().map(|(_, s)| *s).collect();
+            sorted.sort_by(f64::total_cmp);
+            let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+            max_pairwise_diff = max_pairwise_diff.max(max - min);
+            let mid = sorted.len() / 2;
+            let median = if sorted.len() % 2 == 0 {
+                (sorted[mid - 1] + sorted[mid]) / 2.0
+            } else {
+                sorted[mid]
+            };
+            for (node, score) in scores {
+                if (score - median).abs() >= epsilon / 2.0 {
+                    divergent.insert(node.to_string());
+                }
+            }
+        }
+
+        Self {
+            test_id,
+            epsilon,
+            max_pairwise_diff,
+            convergence_achieved: max_pairwise_diff < epsilon,
+            iterations_to_convergence: node_trust_vectors
+                .keys()
+                .filter_map(|node| iterations.get(node).copied())
+                .max(),
+            divergent_nodes: divergent.into_iter().collect(),
+            node_trust_vectors,
+            timestamp: SystemTime::now(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the number of participating nodes.
+    pub fn node_count(&self) -> usize {
+        self.node_trust_vectors.len()
+    }
+
+    /// Check if this proof is valid.
+    pub fn is_valid(&self) -> bool {
+        self.convergence_achieved && self.divergent_nodes.is_empty()
+    }
+}
+
 /// Anomaly detected during testing.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TestAnomaly {
@@ -1612,6 +1739,242 @@ impl TestAnomaly {
         self.suggested_location = Some(location);
         self
     }
+
+    /// `from` reached `to`, but every attempt in the reverse direction failed.
+    pub fn asymmetric_connectivity(from: &str, to: &str) -> Self {
+        Self::new(
+            Self::ASYMMETRIC_CONNECTIVITY.to_string(),
+            format!(
+                "{} reached {} but {} could not reach {}",
+                from, to, to, from
+            ),
+            4,
+        )
+        .with_nodes(vec![from.to_string(), to.to_string()])
+    }
+
+    /// Anomaly type of [`TestAnomaly::asymmetric_connectivity`].
+    pub const ASYMMETRIC_CONNECTIVITY: &'static str = "asymmetric_connectivity";
+}
+
+/// How much of the bidirectional connectivity matrix has been tested.
+///
+/// N peers give N×(N-1) ordered pairs. A pair is tested once any result
+/// exists for it and successful if any attempt connected, so a small but
+/// complete proof can be told apart from a large but sparse one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixCoverage {
+    /// Peers in the matrix
+    pub peers: usize,
+    /// Ordered pairs possible between them: N×(N-1)
+    pub possible_pairs: usize,
+    /// Ordered pairs with at least one result
+    pub tested_pairs: usize,
+    /// Tested pairs where some attempt connected
+    pub successful_pairs: usize,
+}
+
+impl MatrixCoverage {
+    /// Tally `(from, to, success)` results over `peers`.
+    ///
+    /// Self-pairs and pairs involving peers outside `peers` are ignored.
+    pub fn from_results<'a>(
+        peers: impl IntoIterator<Item = &'a str>,
+        results: impl IntoIterator<Item = (&'a str, &'a str, bool)>,
+    ) -> Self {
+        let peers: HashSet<&str> = peers.into_iter().collect();
+        let mut pairs: HashMap<(&str, &str), bool> = HashMap::new();
+        for (from, to, success) in results {
+            if from != to && peers.contains(from) && peers.contains(to) {
+                *pairs.entry((from, to)).or_default() |= success;
+            }
+        }
+        let n = peers.len();
+        Self {
+            peers: n,
+            possible_pairs: n * n.saturating_sub(1),
+            tested_pairs: pairs.len(),
+            successful_pairs: pairs.values().filter(|success| **success).count(),
+        }
+    }
+
+    /// Fraction of possible pairs tested (0.0 with fewer than two peers).
+    pub fn coverage(&self) -> f64 {
+        if self.possible_pairs == 0 {
+            0.0
+        } else {
+            self.tested_pairs as f64 / self.possible_pairs as f64
+        }
+    }
+
+    /// Fraction of tested pairs that connected (0.0 when none were tested).
+    pub fn success_rate(&self) -> f64 {
+        if self.tested_pairs == 0 {
+            0.0
+        } else {
+            self.successful_pairs as f64 / self.tested_pairs as f64
+        }
+    }
+
+    /// Every ordered pair has been tested.
+    pub fn is_complete(&self) -> bool {
+        self.possible_pairs > 0 && self.tested_pairs == self.possible_pairs
+    }
+
+    /// e.g. "340/870 pairs tested, 94% success"
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{} pairs tested, {:.0}% success",
+            self.tested_pairs,
+            self.possible_pairs,
+            self.success_rate() * 100.0
+        )
+    }
+}
+
+/// How long a node took from start to its first NAT-verified connection.
+///
+/// Discovery runs from start until the first dialable peer is known;
+/// connection runs from there until a peer is verified in both directions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinTime {
+    /// Start until the first peer was discovered (ms)
+    pub discovery_ms: u64,
+    /// First discovery until the first NAT-verified peer (ms)
+    pub connection_ms: u64,
+}
+
+impl JoinTime {
+    /// Start until the first NAT-verified peer (ms).
+    pub fn total_ms(&self) -> u64 {
+        self.discovery_ms + self.connection_ms
+    }
+}
+
+/// Network-wide join time percentiles across the nodes that have joined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JoinTimeSummary {
+    /// Nodes that reported a join time
+    pub nodes: usize,
+    /// Median total join time (ms)
+    pub p50_ms: u64,
+    /// 95th percentile total join time (ms)
+    pub p95_ms: u64,
+    /// Median discovery time (ms)
+    pub discovery_p50_ms: u64,
+    /// Median connection time (ms)
+    pub connection_p50_ms: u64,
+}
+
+impl JoinTimeSummary {
+    /// Nearest-rank percentiles over `times`; `None` if there are none.
+    pub fn from_times(times: impl IntoIterator<Item = JoinTime>) -> Option<Self> {
+        let times: Vec<JoinTime> = times.into_iter().collect();
+        if times.is_empty() {
+            return None;
+        }
+        let percentile = |f: fn(&JoinTime) -> u64, pct: usize| {
+            let mut values: Vec<u64> = times.iter().map(f).collect();
+            values.sort_unstable();
+            let rank = (pct * values.len()).div_ceil(100).max(1);
+            values[rank - 1]
+        };
+        Some(Self {
+            nodes: times.len(),
+            p50_ms: percentile(JoinTime::total_ms, 50),
+            p95_ms: percentile(JoinTime::total_ms, 95),
+            discovery_p50_ms: percentile(|t| t.discovery_ms, 50),
+            connection_p50_ms: percentile(|t| t.connection_ms, 50),
+        })
+    }
+
+    /// e.g. "p50=4.2s p95=11.0s over 12 nodes (discovery p50 0.3s, connection p50 3.9s)"
+    pub fn summary(&self) -> String {
+        let secs = |ms: u64| ms as f64 / 1000.0;
+        format!(
+            "p50={:.1}s p95={:.1}s over {} nodes (discovery p50 {:.1}s, connection p50 {:.1}s)",
+            secs(self.p50_ms),
+            secs(self.p95_ms),
+            self.nodes,
+            secs(self.discovery_p50_ms),
+            secs(self.connection_p50_ms)
+        )
+    }
+}
+
+/// How far connection attempts got before failing.
+///
+/// Each stage counts the attempts that made it past that stage, so the
+/// largest drop between neighbours localizes the bottleneck. Skipped stages
+/// (a direct connection needs no coordination) count as passed. Failures
+/// that cannot be placed at a stage are kept in `other` and do not count
+/// toward any stage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionFunnel {
+    /// Attempts made
+    pub attempted: u64,
+    /// Attempts that completed the QUIC handshake
+    pub handshake: u64,
+    /// Attempts past NAT traversal coordination
+    pub coordinated: u64,
+    /// Attempts past hole punching
+    pub punched: u64,
+    /// Attempts whose connection carried verified data
+    pub verified: u64,
+    /// Failed attempts with no known stage
+    pub other: u64,
+}
+
+impl ConnectionFunnel {
+    /// Tally technique attempts, classifying each failure by its error text.
+    pub fn from_attempts<'a>(attempts: impl IntoIterator<Item = &'a TechniqueAttempt>) -> Self {
+        let mut funnel = Self::default();
+        for attempt in attempts {
+            let reason = if attempt.success {
+                FailureReasonCode::Success
+            } else {
+                attempt
+                    .error
+                    .as_deref()
+                    .map_or(FailureReasonCode::Unknown, classify_error_message)
+            };
+            funnel.record(reason);
+        }
+        funnel
+    }
+
+    /// Count one attempt that ended with `reason`.
+    pub fn record(&mut self, reason: FailureReasonCode) {
+        self.attempted += 1;
+        // Stages passed before failing
+        let reached = match reason.funnel_stage() {
+            None => 4,
+            Some(FunnelStage::Handshake) => 0,
+            Some(FunnelStage::Coordination) => 1,
+            Some(FunnelStage::HolePunch) => 2,
+            Some(FunnelStage::Verification) => 3,
+            Some(FunnelStage::Other) => {
+                self.other += 1;
+                return;
+            }
+        };
+        self.handshake += u64::from(reached > 0);
+        self.coordinated += u64::from(reached > 1);
+        self.punched += u64::from(reached > 2);
+        self.verified += u64::from(reached > 3);
+    }
+
+    /// e.g. "1000 attempted → 950 handshake → 820 coordinated → 780 punched → 760 verified"
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{} attempted → {} handshake → {} coordinated → {} punched → {} verified",
+            self.attempted, self.handshake, self.coordinated, self.punched, self.verified
+        );
+        if self.other > 0 {
+            line.push_str(&format!(" ({} other failures)", self.other));
+        }
+        line
+    }
 }
 
 /// Comprehensive test report with all proofs.
@@ -1634,9 +1997,18 @@ pub struct ProofBasedTestReport {
     /// CRDT convergence proof
     #[serde(default)]
     pub crdt: Option<CrdtConvergenceProof>,
+    /// EigenTrust convergence proof
+    #[serde(default)]
+    pub eigentrust: Option<EigenTrustConvergenceProof>,
     /// Anomalies detected during testing
     #[serde(default)]
     pub anomalies: Vec<TestAnomaly>,
+    /// How much of the ordered peer-pair matrix was tested
+    #[serde(default)]
+    pub matrix_coverage: Option<MatrixCoverage>,
+    /// Network-wide time from node start to first NAT-verified connection
+    #[serde(default)]
+    pub join_times: Option<JoinTimeSummary>,
     /// Overall test passed
     #[serde(default)]
     pub passed: bool,
@@ -1654,7 +2026,10 @@ impl Default for ProofBasedTestReport {
             connectivity: None,
             gossip: None,
             crdt: None,
+            eigentrust: None,
             anomalies: Vec::new(),
+            matrix_coverage: None,
+            join_times: None,
             passed: false,
             failure_summary: None,
         }
@@ -1690,8 +2065,10 @@ impl ProofBasedTestReport {
             .map(|p| p.all_protocols_valid())
             .unwrap_or(false);
         let crdt_ok = self.crdt.as_ref().map(|p| p.is_valid()).unwrap_or(false);
+        // EigenTrust is optional: only nodes running the trust layer report it
+        let eigentrust_ok = self.eigentrust.as_ref().is_none_or(|p| p.is_valid());
 
-        connectivity_ok && gossip_ok && crdt_ok
+        connectivity_ok && gossip_ok && crdt_ok && eigentrust_ok
     }
 
     /// Add an anomaly to the report.
@@ -2160,6 +2537,165 @@ impl FailureReasonCode {
             Self::AddressUnreachable | Self::NoRouteToHost | Self::PortUnreachable
         )
     }
+
+    /// Funnel stage an attempt failing with this reason did not get past,
+    /// or `None` for [`Self::Success`].
+    ///
+    /// Address and routing failures (DNS, unreachable hosts) never reach the
+    /// QUIC handshake, so they count against it.
+    pub fn funnel_stage(&self) -> Option<FunnelStage> {
+        match self {
+            Self::Success => None,
+            Self::Timeout
+            | Self::ConnectionRefused
+            | Self::HandshakeFailed
+            | Self::CryptoError
+            | Self::TlsError
+            | Self::PqcNegotiationFailed
+            | Self::PmtuBlackhole
+            | Self::AddressUnreachable
+            | Self::NoRouteToHost
+            | Self::PortUnreachable => Some(FunnelStage::Handshake),
+            Self::CoordinatorUnreachable | Self::RelayUnavailable => {
+                Some(FunnelStage::Coordination)
+            }
+            Self::NatBindingExpired | Self::MigrationFailed => Some(FunnelStage::HolePunch),
+            Self::DataVerificationFailed | Self::StreamReset | Self::KeepaliveTimeout => {
+                Some(FunnelStage::Verification)
+            }
+            Self::RateLimited
+            | Self::ResourceExhausted
+            | Self::ProtocolViolation
+            | Self::InternalError
+            | Self::Unknown => Some(FunnelStage::Other),
+        }
+    }
+}
+
+/// Stage of the connection funnel at which an attempt failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunnelStage {
+    /// Resolving, reaching and completing the QUIC handshake with the peer
+    Handshake,
+    /// NAT traversal coordination through a coordinator or relay
+    Coordination,
+    /// Punching through the NAT
+    HolePunch,
+    /// Verifying data flows over the established connection
+    Verification,
+    /// Failures that cannot be placed at a stage
+    Other,
+}
+
+/// Map a transport or QUIC connection error to a [`FailureReasonCode`].
+///
+/// I/O errors anywhere in the source chain are classified by their kind;
+/// otherwise the error messages are matched against known QUIC, TLS and
+/// socket failure texts.
+pub fn classify_connection_error(err: &(dyn std::error::Error + 'static)) -> FailureReasonCode {
+    let mut messages = Vec::new();
+    let mut current = Some(err);
+    while let Some(e) = current {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            let code = classify_io_error_kind(io.kind());
+            if code != FailureReasonCode::Unknown {
+                return code;
+            }
+        }
+        messages.push(e.to_string());
+        current = e.source();
+    }
+    classify_error_message(&messages.join(": "))
+}
+
+fn classify_io_error_kind(kind: std::io::ErrorKind) -> FailureReasonCode {
+    use std::io::ErrorKind;
+    match kind {
+        ErrorKind::TimedOut => FailureReasonCode::Timeout,
+        ErrorKind::ConnectionRefused => FailureReasonCode::ConnectionRefused,
+        ErrorKind::HostUnreachable => FailureReasonCode::NoRouteToHost,
+        ErrorKind::NetworkUnreachable | ErrorKind::AddrNotAvailable => {
+            FailureReasonCode::AddressUnreachable
+        }
+        ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted => FailureReasonCode::StreamReset,
+        ErrorKind::OutOfMemory => FailureReasonCode::ResourceExhausted,
+        _ => FailureReasonCode::Unknown,
+    }
+}
+
+/// Classify an error from its message text alone.
+///
+/// More specific patterns are checked first: a PQC handshake failure is
+/// reported as such even though its message usually also mentions TLS.
+pub fn classify_error_message(message: &str) -> FailureReasonCode {
+    const PATTERNS: &[(&[&str], FailureReasonCode)] = &[
+        (
+            &[
+                "ml-kem",
+                "ml-dsa",
+                "mlkem",
+                "mldsa",
+                "pqc",
+                "post-quantum",
+                "kem",
+            ],
+            FailureReasonCode::PqcNegotiationFailed,
+        ),
+        (
+            &["tls", "certificate", "alert", "no application protocol"],
+            FailureReasonCode::TlsError,
+        ),
+        (
+            &["crypto", "decrypt", "signature", "authentication"],
+            FailureReasonCode::CryptoError,
+        ),
+        (
+            &["keep-alive", "keepalive"],
+            FailureReasonCode::KeepaliveTimeout,
+        ),
+        (
+            &["nat binding", "binding expired"],
+            FailureReasonCode::NatBindingExpired,
+        ),
+        (&["migration"], FailureReasonCode::MigrationFailed),
+        (
+            &["verification failed", "checksum mismatch"],
+            FailureReasonCode::DataVerificationFailed,
+        ),
+        (
+            &["timed out", "timeout", "idle"],
+            FailureReasonCode::Timeout,
+        ),
+        (&["refused"], FailureReasonCode::ConnectionRefused),
+        (&["port unreachable"], FailureReasonCode::PortUnreachable),
+        (
+            &["no route to host", "host unreachable"],
+            FailureReasonCode::NoRouteToHost,
+        ),
+        (
+            &["unreachable", "address not available", "no target address"],
+            FailureReasonCode::AddressUnreachable,
+        ),
+        (&["rate limit", "too many"], FailureReasonCode::RateLimited),
+        (&["relay"], FailureReasonCode::RelayUnavailable),
+        (&["coordinator"], FailureReasonCode::CoordinatorUnreachable),
+        (
+            &["reset", "stopped", "aborted"],
+            FailureReasonCode::StreamReset,
+        ),
+        (
+            &["protocol violation", "protocol error"],
+            FailureReasonCode::ProtocolViolation,
+        ),
+        (&["handshake"], FailureReasonCode::HandshakeFailed),
+    ];
+
+    let message = message.to_ascii_lowercase();
+    PATTERNS
+        .iter()
+        .find(|(needles, _)| needles.iter().any(|n| message.contains(n)))
+        .map_or(FailureReasonCode::Unknown, |(_, code)| *code)
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -2604,6 +3140,59 @@ pub struct NodeHeartbeat {
     /// Maps peer_id -> probe result for all peers this node attempted to probe.
     #[serde(default)]
     pub full_mesh_probes: Option<HashMap<String, FullMeshProbeResult>>,
+    /// Monotonically increasing sequence number (per registration).
+    /// Heartbeats with a seq not greater than the last one seen are ignored,
+    /// so retried or duplicated heartbeats are not applied twice.
+    #[serde(default)]
+    pub seq: Option<u64>,
+}
+
+/// Anonymized record uploaded by nodes run with `--telemetry`.
+///
+/// Carries only what aggregate NAT-type and connectivity statistics need:
+/// never a peer ID or an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryRecord {
+    /// Detected NAT type
+    pub nat_type: NatType,
+    /// Country of the node's external address (ISO 3166-1 alpha-2)
+    pub country_code: Option<String>,
+    /// Client version
+    pub version: String,
+    /// Outbound connection attempts
+    pub attempts: u64,
+    /// Successful direct connections
+    pub direct_success: u64,
+    /// Successful hole-punched connections
+    pub hole_punch_success: u64,
+    /// Successful relayed connections
+    pub relay_success: u64,
+    /// Failed connection attempts
+    pub failures: u64,
+}
+
+impl TelemetryRecord {
+    /// Build a record from the node's NAT type, country and connection stats.
+    pub fn new(nat_type: NatType, country_code: Option<String>, stats: &NatStats) -> Self {
+        Self {
+            nat_type,
+            country_code,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            attempts: stats.attempts,
+            direct_success: stats.direct_success,
+            hole_punch_success: stats.hole_punch_success,
+            relay_success: stats.relay_success,
+            failures: stats.failures,
+        }
+    }
+}
+
+/// Sent by a node that is shutting down, so the registry can drop it
+/// immediately instead of waiting for its TTL to expire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDeregistration {
+    /// Unique peer identifier
+    pub peer_id: String,
 }
 
 /// Result of a full-mesh connectivity probe to a single peer.
@@ -2671,6 +3260,15 @@ pub struct NatStats {
     /// Whether this node is behind NAT (external != local address)
     #[serde(default)]
     pub is_behind_nat: bool,
+    /// Reason for the most recent failed outbound connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<FailureReasonCode>,
+    /// Failed outbound connections by classified reason
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub failure_reasons: HashMap<FailureReasonCode, u64>,
+    /// Time from start to the first NAT-verified peer, once there is one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_to_first_connection: Option<JoinTime>,
 }
 
 /// Information about a registered peer (returned by registry).
@@ -2688,6 +3286,10 @@ pub struct PeerInfo {
     pub latitude: f64,
     /// Geographic longitude (for globe visualization)
     pub longitude: f64,
+    /// True when the coordinates are a placeholder derived from the peer ID
+    /// because the real location could not be resolved
+    #[serde(default)]
+    pub location_estimated: bool,
     /// Unix timestamp of last successful heartbeat
     pub last_seen: u64,
     /// Historical connection success rate (0.0 - 1.0)
@@ -2716,6 +3318,12 @@ pub struct PeerInfo {
     /// Full-mesh connectivity probe results (peer_id -> result)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub full_mesh_probes: Option<HashMap<String, FullMeshProbeResult>>,
+    /// Reason for this node's most recent failed outbound connection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<FailureReasonCode>,
+    /// Time from this node's start to its first NAT-verified peer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_to_first_connection: Option<JoinTime>,
 }
 
 /// Network-wide statistics (returned by /api/stats).
@@ -2743,6 +3351,9 @@ pub struct NetworkStats {
     pub ipv4_connections: u64,
     /// IPv6 connections count
     pub ipv6_connections: u64,
+    /// RTT distribution of reported connections, per method
+    #[serde(default)]
+    pub rtt_histogram: RttHistogram,
 }
 
 /// Breakdown of connections by method.
@@ -2756,6 +3367,74 @@ pub struct ConnectionBreakdown {
     pub relayed: u64,
 }
 
+/// Upper bounds (exclusive, in ms) of the RTT histogram buckets. A final
+/// open-ended bucket counts everything from the last bound up.
+pub const RTT_BUCKET_BOUNDS_MS: [u64; 6] = [10, 25, 50, 100, 250, 500];
+
+/// Reported connection RTTs in logarithmic buckets, split by method
+/// (returned by /api/rtt-histogram).
+///
+/// A mean hides bimodal distributions; the buckets show direct connections
+/// clustering low and relayed ones with a fatter tail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RttHistogram {
+    /// Bucket labels in milliseconds, e.g. "0-10" ... "500+"
+    pub buckets: Vec<String>,
+    /// Direct connection counts per bucket
+    pub direct: Vec<u64>,
+    /// Hole-punched connection counts per bucket
+    pub hole_punched: Vec<u64>,
+    /// Relayed connection counts per bucket
+    pub relayed: Vec<u64>,
+}
+
+impl Default for RttHistogram {
+    fn default() -> Self {
+        let mut buckets = Vec::with_capacity(RTT_BUCKET_BOUNDS_MS.len() + 1);
+        let mut lower = 0;
+        for upper in RTT_BUCKET_BOUNDS_MS {
+            buckets.push(format!("{}-{}", lower, upper));
+            lower = upper;
+        }
+        buckets.push(format!("{}+", lower));
+        let empty = vec![0; buckets.len()];
+        Self {
+            buckets,
+            direct: empty.clone(),
+            hole_punched: empty.clone(),
+            relayed: empty,
+        }
+    }
+}
+
+impl RttHistogram {
+    /// Index of the bucket holding `rtt_ms`.
+    pub fn bucket_index(rtt_ms: u64) -> usize {
+        RTT_BUCKET_BOUNDS_MS
+            .iter()
+            .position(|upper| rtt_ms < *upper)
+            .unwrap_or(RTT_BUCKET_BOUNDS_MS.len())
+    }
+
+    /// Count one connection made via `method` with the given RTT.
+    pub fn record(&mut self, method: ConnectionMethod, rtt_ms: u64) {
+        let counts = match method {
+            ConnectionMethod::Direct => &mut self.direct,
+            ConnectionMethod::HolePunched => &mut self.hole_punched,
+            ConnectionMethod::Relayed => &mut self.relayed,
+        };
+        counts[Self::bucket_index(rtt_ms)] += 1;
+    }
+
+    /// Total connections counted across all methods.
+    pub fn samples(&self) -> u64 {
+        [&self.direct, &self.hole_punched, &self.relayed]
+            .into_iter()
+            .flatten()
+            .sum()
+    }
+}
+
 /// Per-node gossip statistics (reported in heartbeat).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NodeGossipStats {
@@ -2787,6 +3466,9 @@ pub struct NodeGossipStats {
     /// HyParView passive view size (known but not connected)
     #[serde(default)]
     pub hyparview_passive: usize,
+    /// Peer IDs (hex) in the HyParView active view, for partition detection
+    #[serde(default)]
+    pub hyparview_active_peers: Vec<String>,
 
     /// SWIM peers in Alive state
     #[serde(default)]
@@ -2797,6 +3479,9 @@ pub struct NodeGossipStats {
     /// SWIM peers in Dead state
     #[serde(default)]
     pub swim_dead: usize,
+    /// Peer IDs (hex) SWIM has declared dead, checked against the registry
+    #[serde(default)]
+    pub swim_dead_peers: Vec<String>,
 
     /// Plumtree messages sent
     #[serde(default)]
@@ -2864,6 +3549,15 @@ pub struct NodeGossipStats {
     /// PRUNE messages sent (demote eager to lazy)
     #[serde(default)]
     pub plumtree_prunes_sent: u64,
+    /// GRAFTs inferred from delivery order (Plumtree's own are not exposed)
+    #[serde(default)]
+    pub plumtree_grafts_estimated: u64,
+    /// PRUNEs inferred from delivery order (Plumtree's own are not exposed)
+    #[serde(default)]
+    pub plumtree_prunes_estimated: u64,
+    /// Deliveries repeating a recently delivered payload
+    #[serde(default)]
+    pub plumtree_duplicates: u64,
     /// Total messages broadcast
     #[serde(default)]
     pub plumtree_broadcasts: u64,
@@ -2951,6 +3645,24 @@ pub struct NodeGossipStats {
     /// Transport bytes received
     #[serde(default)]
     pub transport_bytes_received: u64,
+
+    /// Subscribers of each discovery topic (topic name -> count)
+    #[serde(default)]
+    pub topic_subscribers: HashMap<String, usize>,
+
+    /// First receipt time of each Plumtree latency probe (probe ID -> unix ms)
+    #[serde(default)]
+    pub probe_receipts: HashMap<String, u64>,
+
+    /// EigenTrust global scores from the node's trust layer (peer ID -> 0.0-1.0).
+    ///
+    /// Empty until the node runs a trust layer; raw local success ratios do
+    /// not belong here since nodes legitimately disagree on them.
+    #[serde(default)]
+    pub trust_vector: HashMap<String, f64>,
+    /// Iterations the trust layer needed to converge
+    #[serde(default)]
+    pub trust_iterations: u32,
 }
 
 /// Gossip network statistics (aggregated from all nodes).
@@ -3045,6 +3757,9 @@ pub enum NetworkEvent {
         latitude: f64,
         /// Geographic longitude
         longitude: f64,
+        /// True when the coordinates are estimated from the peer ID
+        #[serde(default)]
+        location_estimated: bool,
     },
     /// Node went offline (missed heartbeats)
     NodeOffline {
@@ -3078,6 +3793,20 @@ pub enum NetworkEvent {
         /// Request timestamp (unix ms)
         timestamp_ms: u64,
     },
+    /// Rolling connection success rate dropped below the configured floor
+    SuccessRateAlert {
+        /// Current rolling success rate (0.0 - 1.0)
+        rate: f64,
+        /// Configured floor that was crossed
+        threshold: f64,
+    },
+    /// Rolling connection success rate rose back to or above the floor
+    SuccessRateRecovered {
+        /// Current rolling success rate (0.0 - 1.0)
+        rate: f64,
+        /// Configured floor that was crossed
+        threshold: f64,
+    },
 }
 
 /// Response to registration request.
@@ -3091,6 +3820,20 @@ pub struct RegistrationResponse {
     pub peers: Vec<PeerInfo>,
     /// Registration expiry time (heartbeat deadline)
     pub expires_in_secs: u64,
+    /// Registry peer TTL: peers silent this long drop out of the peer list.
+    /// `None` from registries that predate the field.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+/// One page of the registry peer list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeersPage {
+    /// Peers on this page, ordered by peer ID
+    pub peers: Vec<PeerInfo>,
+    /// Opaque cursor for the next page; `None` on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 /// Individual connection record for experiment results.
@@ -3786,6 +4529,161 @@ pub struct QualityDistribution {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_join_time_summary_percentiles() {
+        assert_eq!(JoinTimeSummary::from_times([]), None);
+        let times = (1..=20).map(|i| JoinTime {
+            discovery_ms: 100,
+            connection_ms: i * 1000,
+        });
+        let summary = JoinTimeSummary::from_times(times).unwrap();
+        assert_eq!(summary.nodes, 20);
+        assert_eq!((summary.p50_ms, summary.p95_ms), (10_100, 19_100));
+        assert_eq!(summary.discovery_p50_ms, 100);
+        assert_eq!(summary.connection_p50_ms, 10_000);
+        assert_eq!(
+            summary.summary(),
+            "p50=10.1s p95=19.1s over 20 nodes (discovery p50 0.1s, connection p50 10.0s)"
+        );
+    }
+
+    #[test]
+    fn test_matrix_coverage_counts_ordered_pairs() {
+        let coverage = MatrixCoverage::from_results(
+            ["a", "b", "c"],
+            [
+                ("a", "b", false),
+                ("a", "b", true),
+                ("b", "a", false),
+                ("c", "a", true),
+                // Ignored: self-pair and a peer outside the matrix
+                ("a", "a", true),
+                ("a", "gone", true),
+            ],
+        );
+        assert_eq!(
+            coverage,
+            MatrixCoverage {
+                peers: 3,
+                possible_pairs: 6,
+                tested_pairs: 3,
+                successful_pairs: 2,
+            }
+        );
+        assert_eq!(coverage.coverage(), 0.5);
+        assert_eq!(coverage.summary(), "3/6 pairs tested, 67% success");
+
+        let empty = MatrixCoverage::from_results(["a"], []);
+        assert_eq!(empty.possible_pairs, 0);
+        assert_eq!(empty.coverage(), 0.0);
+        assert_eq!(empty.success_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_connection_funnel_stages() {
+        let attempt = |success, error: Option<&str>| TechniqueAttempt {
+            technique: ConnectionTechnique::HolePunch,
+            success,
+            duration_ms: 10,
+            error: error.map(str::to_string),
+            timestamp_ms: 0,
+            data_proof: None,
+            method_proof: None,
+        };
+        let attempts = [
+            attempt(true, None),
+            attempt(true, None),
+            attempt(false, Some("TLS handshake failed: bad certificate")),
+            attempt(false, Some("coordinator did not respond")),
+            attempt(false, Some("NAT binding expired")),
+            attempt(false, Some("data verification failed")),
+            // No error text and an unrecognized one both land in "other"
+            attempt(false, None),
+            attempt(false, Some("something unexpected")),
+        ];
+        let funnel = ConnectionFunnel::from_attempts(&attempts);
+        assert_eq!(
+            funnel,
+            ConnectionFunnel {
+                attempted: 8,
+                handshake: 5,
+                coordinated: 4,
+                punched: 3,
+                verified: 2,
+                other: 2,
+            }
+        );
+        assert_eq!(
+            funnel.summary(),
+            "8 attempted → 5 handshake → 4 coordinated → 3 punched → 2 verified (2 other failures)"
+        );
+        assert_eq!(FailureReasonCode::Success.funnel_stage(), None);
+        assert_eq!(
+            FailureReasonCode::NoRouteToHost.funnel_stage(),
+            Some(FunnelStage::Handshake)
+        );
+    }
+
+    #[test]
+    fn test_classify_connection_error() {
+        use std::io::{Error, ErrorKind};
+
+        let io = |kind: ErrorKind| classify_connection_error(&Error::new(kind, "socket error"));
+        assert_eq!(io(ErrorKind::TimedOut), FailureReasonCode::Timeout);
+        assert_eq!(
+            io(ErrorKind::ConnectionRefused),
+            FailureReasonCode::ConnectionRefused
+        );
+        assert_eq!(
+            io(ErrorKind::NetworkUnreachable),
+            FailureReasonCode::AddressUnreachable
+        );
+
+        // QUIC/endpoint errors only carry a message
+        let cases = [
+            ("Operation timed out", FailureReasonCode::Timeout),
+            (
+                "Connection error: connection refused (os error 111)",
+                FailureReasonCode::ConnectionRefused,
+            ),
+            (
+                "Connection error: No route to host (os error 113)",
+                FailureReasonCode::NoRouteToHost,
+            ),
+            (
+                "Connection error: Network is unreachable (os error 101)",
+                FailureReasonCode::AddressUnreachable,
+            ),
+            (
+                "Connection error: TLS alert: handshake failure",
+                FailureReasonCode::TlsError,
+            ),
+            (
+                "Connection error: TLS handshake failed: no ML-KEM key share",
+                FailureReasonCode::PqcNegotiationFailed,
+            ),
+            ("connection lost: idle timeout", FailureReasonCode::Timeout),
+            (
+                "NAT binding expired before punch",
+                FailureReasonCode::NatBindingExpired,
+            ),
+            (
+                "echo verification failed: checksum mismatch",
+                FailureReasonCode::DataVerificationFailed,
+            ),
+            ("something unexpected", FailureReasonCode::Unknown),
+        ];
+        for (message, expected) in cases {
+            let err: Box<dyn std::error::Error> = message.into();
+            assert_eq!(
+                classify_connection_error(err.as_ref()),
+                expected,
+                "{}",
+                message
+            );
+        }
+    }
+
     #[test]
     fn test_nat_type_display() {
         assert_eq!(NatType::FullCone.to_string(), "Full Cone");
@@ -5,15 +5,23 @@
 
 use crate::gossip_tests::GossipTestResults;
 use crate::tui::types::{
-    AdaptiveStats, CacheHealth, ConnectedPeer, ConnectionHistoryEntry, ConnectionStatus,
-    ConnectivityTestResults, DhtStats, EigenTrustStats, FrameDirection, GeographicDistribution,
-    HealthStats, LocalNodeInfo, McpState, McpToolCategory, NatTraversalPhase, NatTypeAnalytics,
-    NetworkStatistics, PlacementStats, ProofStatus, ProtocolFrame, TestConnectivityMethod,
-    TrafficType,
+    AdaptiveStats, AddressFamily, AlertSeverity, BootstrapPeerStatus, CacheHealth, ConnectedPeer,
+    ConnectionHistoryEntry, ConnectionStatus, ConnectivityTestResults, CryptoTally, DhtStats,
+    EigenTrustStats, FrameDirection, GeographicDistribution, HealthAlert, HealthStats,
+    LocalNodeInfo, McpState, McpToolCategory, NatTraversalPhase, NatTypeAnalytics,
+    NegotiatedCrypto, NetworkStatistics, PlacementStats, ProofStatus, ProtocolFrame,
+    ProtocolLogFilter, TestConnectivityMethod, TrafficType,
 };
 use ratatui::widgets::TableState;
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// Invocation-history name for node-driven connectivity sweeps.
+pub const CONNECTIVITY_SWEEP_TOOL: &str = "connectivity_sweep";
+
+/// Most health alerts kept; the oldest are dropped first.
+const MAX_HEALTH_ALERTS: usize = 100;
 
 /// Application running state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +44,8 @@ pub enum Tab {
     ProtocolLog,
     /// MCP client for messaging and tool invocation [4]
     Mcp,
+    /// Bootstrap peer reachability [B]
+    Bootstrap,
 }
 
 /// Main TUI application state.
@@ -67,10 +77,18 @@ pub struct App {
     pub error_message: Option<String>,
     /// Info message to display (if any)
     pub info_message: Option<String>,
-    /// Protocol frame log (last 20 frames)
+    /// Protocol frame log (last 200 frames, unfiltered)
     pub protocol_frames: Vec<ProtocolFrame>,
+    /// Frame type or direction filter for the Protocol Log tab
+    pub protocol_log_filter: ProtocolLogFilter,
+    /// Peer ID prefix filter for the Protocol Log tab (empty = none)
+    pub protocol_log_search: String,
+    /// Whether keys are currently typed into the peer ID prefix filter
+    pub protocol_log_searching: bool,
     /// Bootstrap cache health information
     pub cache_health: Option<CacheHealth>,
+    /// Bootstrap peer reachability, sorted by address
+    pub bootstrap_peers: Vec<BootstrapPeerStatus>,
     /// NAT type analytics for connection success rates
     pub nat_analytics: Option<NatTypeAnalytics>,
     /// Geographic distribution of peers for network diversity
@@ -104,6 +122,10 @@ pub struct App {
     pub health_stats: HealthStats,
     /// MCP client state for MCP tab [0]
     pub mcp_state: McpState,
+    /// Configured test packet size in bytes
+    pub test_packet_size: usize,
+    /// History replay control (None when running live)
+    pub replay: Option<crate::tui::ReplayControl>,
 }
 
 impl Default for App {
@@ -133,7 +155,11 @@ impl App {
             error_message: None,
             info_message: None,
             protocol_frames: Vec::new(),
+            protocol_log_filter: ProtocolLogFilter::default(),
+            protocol_log_search: String::new(),
+            protocol_log_searching: false,
             cache_health: None,
+            bootstrap_peers: Vec::new(),
             nat_analytics: None,
             geographic_distribution: None,
             connectivity_test: ConnectivityTestResults::new(),
@@ -151,6 +177,8 @@ impl App {
             placement_stats: PlacementStats::default(),
             health_stats: HealthStats::default(),
             mcp_state: McpState::default(),
+            test_packet_size: crate::node::DEFAULT_TEST_PACKET_SIZE,
+            replay: None,
         }
     }
 
@@ -170,20 +198,40 @@ impl App {
             Tab::Overview => Tab::GossipHealth,
             Tab::GossipHealth => Tab::ProtocolLog,
             Tab::ProtocolLog => Tab::Mcp,
-            Tab::Mcp => Tab::Overview,
+            Tab::Mcp => Tab::Bootstrap,
+            Tab::Bootstrap => Tab::Overview,
         };
     }
 
     /// Cycle to the previous tab.
     pub fn prev_tab(&mut self) {
         self.active_tab = match self.active_tab {
-            Tab::Overview => Tab::Mcp,
+            Tab::Overview => Tab::Bootstrap,
             Tab::GossipHealth => Tab::Overview,
             Tab::ProtocolLog => Tab::GossipHealth,
             Tab::Mcp => Tab::ProtocolLog,
+            Tab::Bootstrap => Tab::Mcp,
         };
     }
 
+    /// Record the result of probing a bootstrap peer.
+    pub fn update_bootstrap_status(
+        &mut self,
+        addr: SocketAddr,
+        reachable: bool,
+        rtt: Option<Duration>,
+    ) {
+        let idx = match self.bootstrap_peers.binary_search_by(|p| p.addr.cmp(&addr)) {
+            Ok(idx) => idx,
+            Err(idx) => {
+                self.bootstrap_peers
+                    .insert(idx, BootstrapPeerStatus::new(addr));
+                idx
+            }
+        };
+        self.bootstrap_peers[idx].record_probe(reachable, rtt);
+    }
+
     /// Update gossip stats from epidemic gossip system.
     pub fn update_gossip_stats(&mut self, stats: crate::registry::NodeGossipStats) {
         self.gossip_stats = Some(stats);
@@ -221,9 +269,13 @@ impl App {
     }
 
     /// Add or update a connected peer.
-    pub fn update_peer(&mut self, peer: ConnectedPeer) {
+    pub fn update_peer(&mut self, mut peer: ConnectedPeer) {
         let peer_id = peer.full_id.clone();
 
+        if let Some(previous) = self.connected_peers.get(&peer_id) {
+            peer.inherit_method_history(previous);
+        }
+
         if let Some(history) = self.connection_history.get_mut(&peer_id) {
             history.update_from_peer(&peer);
         } else {
@@ -249,10 +301,67 @@ impl App {
         self.connected_peers.len()
     }
 
+    /// Record a handshake that offered classical key exchange.
+    pub fn record_classical_fallback(&mut self, peer_id: &str, crypto: &NegotiatedCrypto) {
+        self.stats.classical_fallback += 1;
+        self.push_alert(HealthAlert {
+            severity: AlertSeverity::Warning,
+            message: format!(
+                "Connection to {} may have fallen back to classical crypto (offered {})",
+                &peer_id[..8.min(peer_id.len())],
+                crypto.label()
+            ),
+            component: "crypto".to_string(),
+            timestamp: Instant::now(),
+            timestamp_secs_ago: 0,
+            acknowledged: false,
+        });
+    }
+
+    /// Add a health alert, dropping the oldest beyond [`MAX_HEALTH_ALERTS`].
+    fn push_alert(&mut self, alert: HealthAlert) {
+        let alerts = &mut self.health_stats.alerts;
+        alerts.push(alert);
+        if alerts.len() > MAX_HEALTH_ALERTS {
+            let excess = alerts.len() - MAX_HEALTH_ALERTS;
+            alerts.drain(..excess);
+        }
+    }
+
+    /// Peers we attempted but never connected to, sorted by ID.
+    pub fn isolated_peers(&self) -> Vec<String> {
+        let mut isolated: Vec<String> = self
+            .stats
+            .unique_peers_attempted
+            .difference(&self.stats.unique_peers_connected)
+            .cloned()
+            .collect();
+        isolated.sort();
+        isolated
+    }
+
+    /// Count connected peers by negotiated key exchange (PQC vs. classical).
+    pub fn crypto_tally(&self) -> CryptoTally {
+        CryptoTally::from_peers(self.connected_peers.values())
+    }
+
+    /// Relayed connections per relay peer, busiest relay first.
+    pub fn relay_utilization(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for peer in self.connected_peers.values() {
+            if let Some(ref relay) = peer.relay_peer_id {
+                *counts.entry(relay.clone()).or_default() += 1;
+            }
+        }
+        let mut utilization: Vec<_> = counts.into_iter().collect();
+        utilization.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        utilization
+    }
+
     /// Mark that we sent a packet to a peer.
     pub fn packet_sent(&mut self, peer_id: &str) {
         self.stats.packets_sent += 1;
-        self.stats.bytes_sent += 5120; // 5KB test packet
+        self.stats.bytes_sent += self.test_packet_size as u64;
 
         if let Some(peer) = self.connected_peers.get_mut(peer_id) {
             peer.packets_sent += 1;
@@ -267,7 +376,7 @@ impl App {
     /// Mark that we received a packet from a peer.
     pub fn packet_received(&mut self, peer_id: &str) {
         self.stats.packets_received += 1;
-        self.stats.bytes_received += 5120; // 5KB test packet
+        self.stats.bytes_received += self.test_packet_size as u64;
 
         if let Some(peer) = self.connected_peers.get_mut(peer_id) {
             peer.packets_received += 1;
@@ -346,10 +455,23 @@ impl App {
         self.local_node.last_heartbeat = Some(Instant::now());
     }
 
-    /// Update RTT measurement for a peer.
-    pub fn update_peer_rtt(&mut self, peer_id: &str, rtt: std::time::Duration) {
+    /// Update RTT and jitter measurements for a peer.
+    pub fn update_peer_rtt(
+        &mut self,
+        peer_id: &str,
+        rtt: std::time::Duration,
+        jitter: Option<std::time::Duration>,
+    ) {
+        if let Some(peer) = self.connected_peers.get_mut(peer_id) {
+            peer.update_rtt_with_jitter(rtt, jitter);
+        }
+    }
+
+    /// Record which address family a peer's direct connection uses.
+    pub fn set_peer_address_family(&mut self, peer_id: &str, family: AddressFamily) {
         if let Some(peer) = self.connected_peers.get_mut(peer_id) {
-            peer.update_rtt(rtt);
+            peer.address_family = Some(family);
+            peer.connectivity.active_is_ipv6 = family == AddressFamily::Ipv6;
         }
     }
 
@@ -379,6 +501,12 @@ impl App {
         history
     }
 
+    /// Get the history entry currently selected in the connections table.
+    pub fn selected_history_entry(&self) -> Option<&ConnectionHistoryEntry> {
+        let selected = self.connections_table_state.selected()?;
+        self.history_sorted().get(selected).copied()
+    }
+
     /// Get count of currently connected peers in history.
     pub fn history_connected_count(&self) -> usize {
         self.connection_history
@@ -412,6 +540,52 @@ impl App {
         self.prune_history_if_needed();
     }
 
+    /// Protocol frames passing the Protocol Log filters, newest first.
+    pub fn filtered_protocol_frames(&self) -> impl Iterator<Item = &ProtocolFrame> {
+        self.protocol_frames.iter().rev().filter(|frame| {
+            self.protocol_log_filter.matches(frame)
+                && frame.peer_id.starts_with(&self.protocol_log_search)
+        })
+    }
+
+    /// Move the Protocol Log to the next direction or frame type filter.
+    pub fn cycle_protocol_log_filter(&mut self) {
+        let frame_types: Vec<String> = self
+            .protocol_frames
+            .iter()
+            .map(|frame| frame.frame_type.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        self.protocol_log_filter = self.protocol_log_filter.next(&frame_types);
+    }
+
+    /// Start typing a peer ID prefix filter for the Protocol Log.
+    pub fn protocol_log_start_search(&mut self) {
+        self.protocol_log_searching = true;
+    }
+
+    /// Add a character to the peer ID prefix filter.
+    pub fn protocol_log_search_char(&mut self, c: char) {
+        self.protocol_log_search.push(c);
+    }
+
+    /// Remove the last character of the peer ID prefix filter.
+    pub fn protocol_log_search_backspace(&mut self) {
+        self.protocol_log_search.pop();
+    }
+
+    /// Stop typing, keeping the peer ID prefix filter.
+    pub fn protocol_log_finish_search(&mut self) {
+        self.protocol_log_searching = false;
+    }
+
+    /// Stop typing and drop the peer ID prefix filter.
+    pub fn protocol_log_cancel_search(&mut self) {
+        self.protocol_log_searching = false;
+        self.protocol_log_search.clear();
+    }
+
     /// Update NAT traversal phase for a peer
     pub fn update_nat_phase(
         &mut self,
@@ -520,6 +694,20 @@ impl App {
         );
     }
 
+    /// Record why a connection to a peer failed.
+    pub fn record_connection_failure(
+        &mut self,
+        peer_id: &str,
+        reason: crate::registry::FailureReasonCode,
+        elapsed: Duration,
+    ) {
+        self.connection_history
+            .entry(peer_id.to_string())
+            .or_insert_with(|| ConnectionHistoryEntry::new(peer_id))
+            .record_failure(reason, elapsed);
+        self.prune_history_if_needed();
+    }
+
     fn record_history_attempt(
         &mut self,
         peer_id: &str,
@@ -835,6 +1023,55 @@ impl App {
         }
     }
 
+    /// Whether a connectivity sweep is still waiting for its result.
+    pub fn connectivity_sweep_pending(&self) -> bool {
+        use crate::tui::types::McpInvocationResult;
+
+        self.mcp_state.history.iter().any(|h| {
+            h.tool_name == CONNECTIVITY_SWEEP_TOOL
+                && matches!(h.result, McpInvocationResult::Pending)
+        })
+    }
+
+    /// Record that the node started a connectivity sweep over `peers` peers.
+    pub fn connectivity_sweep_started(&mut self, peers: usize) {
+        use crate::tui::types::{McpInvocation, McpInvocationResult};
+
+        let mut parameters = HashMap::new();
+        parameters.insert("peers".to_string(), peers.to_string());
+        self.mcp_state.history.push(McpInvocation {
+            tool_name: CONNECTIVITY_SWEEP_TOOL.to_string(),
+            parameters,
+            result: McpInvocationResult::Pending,
+            timestamp: Instant::now(),
+            duration: Duration::ZERO,
+        });
+        self.info_message = Some(format!("Connectivity sweep: dialling {} peers...", peers));
+    }
+
+    /// Resolve the pending sweep invocation with its summary.
+    pub fn connectivity_sweep_finished(
+        &mut self,
+        summary: crate::tui::types::ConnectivitySweepSummary,
+    ) {
+        use crate::tui::types::McpInvocationResult;
+
+        let description = summary.describe();
+        if let Some(invocation) = self.mcp_state.history.iter_mut().rev().find(|h| {
+            h.tool_name == CONNECTIVITY_SWEEP_TOOL
+                && matches!(h.result, McpInvocationResult::Pending)
+        }) {
+            invocation.result = McpInvocationResult::Success(description.clone());
+            invocation.duration = summary.duration;
+        }
+        self.info_message = Some(format!("Connectivity sweep: {}", description));
+    }
+
+    /// Show why the node refused to start a connectivity sweep.
+    pub fn connectivity_sweep_rejected(&mut self, reason: &str) {
+        self.error_message = Some(format!("Connectivity sweep: {}", reason));
+    }
+
     // ==========================================================================
     // Contact Management Methods
     // ==========================================================================
@@ -1017,12 +1254,14 @@ pub enum InputEvent {
     PageDown,
     NextTab,
     PrevTab,
-    // Tabs [1-4]
+    // Tabs [1-4], [B]
     TabOverview,
     TabGossipHealth,
     TabProtocolLog,
     TabMcp,
+    TabBootstrap,
     ToggleProofHelp,
+    ToggleReplayPause,
     Unknown,
 }
 
@@ -1042,12 +1281,14 @@ impl InputEvent {
             // Tab navigation
             KeyCode::Tab => Self::NextTab,
             KeyCode::BackTab => Self::PrevTab,
-            // Tabs [1-4]
+            // Tabs [1-4], [B]
             KeyCode::Char('1') => Self::TabOverview,
             KeyCode::Char('2') | KeyCode::Char('g') | KeyCode::Char('G') => Self::TabGossipHealth,
             KeyCode::Char('3') | KeyCode::Char('l') | KeyCode::Char('L') => Self::TabProtocolLog,
             KeyCode::Char('4') | KeyCode::Char('m') | KeyCode::Char('M') => Self::TabMcp,
+            KeyCode::Char('b') | KeyCode::Char('B') => Self::TabBootstrap,
             KeyCode::Char('p') | KeyCode::Char('P') => Self::ToggleProofHelp,
+            KeyCode::Char(' ') => Self::ToggleReplayPause,
             KeyCode::Esc => Self::Quit,
             _ => Self::Unknown,
         }
@@ -1058,7 +1299,7 @@ impl InputEvent {
 mod tests {
     use super::*;
     use crate::registry::ConnectionMethod;
-    use crate::tui::types::HealthStatus;
+    use crate::tui::types::{HealthStatus, NegotiatedCrypto};
 
     #[test]
     fn test_app_creation() {
@@ -1104,6 +1345,92 @@ mod tests {
         assert_eq!(app.connected_count(), 0);
     }
 
+    #[test]
+    fn test_classical_fallback_raises_bounded_warnings() {
+        let mut app = App::new();
+
+        // Peer updates alone never alert; the node reports fallbacks
+        let mut classical = ConnectedPeer::new("classical_peer_02", ConnectionMethod::Direct);
+        classical.crypto = Some(NegotiatedCrypto::new("X25519", "Ed25519"));
+        app.update_peer(classical);
+        assert_eq!(app.stats.classical_fallback, 0);
+        assert!(app.health_stats.alerts.is_empty());
+
+        let crypto = NegotiatedCrypto::new("X25519", "Ed25519");
+        app.record_classical_fallback("classical_peer_02", &crypto);
+        assert_eq!(app.stats.classical_fallback, 1);
+        assert_eq!(app.health_stats.alerts.len(), 1);
+        let alert = &app.health_stats.alerts[0];
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+        assert!(alert.message.contains("X25519"));
+        assert!(alert.message.contains("classica"));
+
+        // A flapping peer keeps counting but cannot grow the alert list
+        for _ in 0..(MAX_HEALTH_ALERTS * 2) {
+            app.record_classical_fallback("flapping_peer_03", &crypto);
+        }
+        assert_eq!(
+            app.stats.classical_fallback,
+            1 + 2 * MAX_HEALTH_ALERTS as u64
+        );
+        assert_eq!(app.health_stats.alerts.len(), MAX_HEALTH_ALERTS);
+        assert!(app.health_stats.alerts[0].message.contains("flapping"));
+    }
+
+    #[test]
+    fn test_isolated_peers_never_connected() {
+        let mut app = App::new();
+        for id in ["peer_b", "peer_a", "peer_c"] {
+            app.stats.unique_peers_attempted.insert(id.to_string());
+        }
+        app.stats
+            .unique_peers_connected
+            .insert("peer_c".to_string());
+        assert_eq!(app.isolated_peers(), vec!["peer_a", "peer_b"]);
+    }
+
+    #[test]
+    fn test_relay_utilization() {
+        let mut app = App::new();
+        for (peer, relay) in [
+            ("p1", Some("r1")),
+            ("p2", Some("r2")),
+            ("p3", Some("r2")),
+            ("p4", None),
+        ] {
+            let mut connected = ConnectedPeer::new(peer, ConnectionMethod::Relayed);
+            connected.relay_peer_id = relay.map(str::to_string);
+            app.connected_peers.insert(peer.to_string(), connected);
+        }
+
+        assert_eq!(
+            app.relay_utilization(),
+            vec![("r2".to_string(), 2), ("r1".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_connection_failure_reason_recorded() {
+        use crate::registry::FailureReasonCode;
+
+        let mut app = App::new();
+        app.record_connection_failure(
+            "a3b7c9d2e1f0",
+            FailureReasonCode::Timeout,
+            Duration::from_secs(30),
+        );
+        app.record_connection_failure(
+            "a3b7c9d2e1f0",
+            FailureReasonCode::ConnectionRefused,
+            Duration::from_millis(40),
+        );
+
+        let entry = &app.connection_history["a3b7c9d2e1f0"];
+        assert_eq!(entry.last_error, Some(FailureReasonCode::ConnectionRefused));
+        assert_eq!(entry.last_error_after, Some(Duration::from_millis(40)));
+        assert_eq!(entry.status, ConnectionStatus::Failed);
+    }
+
     #[test]
     fn test_input_events() {
         use crossterm::event::KeyCode;
@@ -1190,10 +1517,16 @@ mod tests {
         app.next_tab(); // Mcp
         assert_eq!(app.active_tab, Tab::Mcp);
 
+        app.next_tab(); // Bootstrap
+        assert_eq!(app.active_tab, Tab::Bootstrap);
+
         app.next_tab(); // Back to Overview
         assert_eq!(app.active_tab, Tab::Overview);
 
         // Test prev_tab
+        app.prev_tab(); // Bootstrap
+        assert_eq!(app.active_tab, Tab::Bootstrap);
+
         app.prev_tab(); // Mcp
         assert_eq!(app.active_tab, Tab::Mcp);
 
@@ -1201,6 +1534,27 @@ mod tests {
         assert_eq!(app.active_tab, Tab::ProtocolLog);
     }
 
+    #[test]
+    fn test_bootstrap_status_tracks_last_success() {
+        let mut app = App::new();
+        let a: SocketAddr = "10.0.0.2:9000".parse().unwrap();
+        let b: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+
+        app.update_bootstrap_status(a, true, Some(Duration::from_millis(42)));
+        app.update_bootstrap_status(b, false, None);
+        app.update_bootstrap_status(a, false, None);
+
+        assert_eq!(app.bootstrap_peers.len(), 2);
+        assert_eq!(app.bootstrap_peers[0].addr, b);
+        assert_eq!(app.bootstrap_peers[0].last_contact_str(), "never");
+
+        let peer = &app.bootstrap_peers[1];
+        assert!(!peer.reachable);
+        assert_eq!(peer.attempts, 2);
+        assert_eq!(peer.rtt_string(), "42ms");
+        assert!(peer.last_success.is_some());
+    }
+
     #[test]
     fn test_tab_input_events() {
         use crossterm::event::KeyCode;
@@ -1219,5 +1573,100 @@ mod tests {
         );
         assert_eq!(InputEvent::from_key(KeyCode::Char('4')), InputEvent::TabMcp);
         assert_eq!(InputEvent::from_key(KeyCode::Char('m')), InputEvent::TabMcp);
+        assert_eq!(
+            InputEvent::from_key(KeyCode::Char('b')),
+            InputEvent::TabBootstrap
+        );
+    }
+
+    #[test]
+    fn test_update_peer_tracks_method_upgrades() {
+        let mut app = App::new();
+        app.update_peer(ConnectedPeer::new("peer1", ConnectionMethod::Relayed));
+        app.update_peer(ConnectedPeer::new("peer1", ConnectionMethod::Relayed));
+        app.update_peer(ConnectedPeer::new("peer1", ConnectionMethod::HolePunched));
+        app.update_peer(ConnectedPeer::new("peer1", ConnectionMethod::Direct));
+
+        let peer = &app.connected_peers["peer1"];
+        assert_eq!(peer.method_path(), "RELAYED → PUNCHED → DIRECT");
+
+        app.remove_peer("peer1");
+        app.update_peer(ConnectedPeer::new("peer1", ConnectionMethod::Direct));
+        assert_eq!(app.connected_peers["peer1"].method_path(), "DIRECT");
+    }
+
+    #[test]
+    fn test_connectivity_sweep_lifecycle() {
+        use crate::tui::types::{ConnectivitySweepSummary, McpInvocationResult};
+
+        let mut app = App::new();
+        assert!(!app.connectivity_sweep_pending());
+
+        app.connectivity_sweep_started(3);
+        assert!(app.connectivity_sweep_pending());
+
+        let mut summary = ConnectivitySweepSummary::default();
+        summary.record(Some(ConnectionMethod::Direct));
+        summary.record(Some(ConnectionMethod::Relayed));
+        summary.record(None);
+        summary.duration = Duration::from_millis(1500);
+        app.connectivity_sweep_finished(summary);
+
+        assert!(!app.connectivity_sweep_pending());
+        let last = app.mcp_state.history.last().unwrap();
+        assert_eq!(last.tool_name, CONNECTIVITY_SWEEP_TOOL);
+        assert_eq!(last.duration, Duration::from_millis(1500));
+        match &last.result {
+            McpInvocationResult::Success(text) => assert_eq!(
+                text,
+                "2/3 reachable (direct 1, punched 0, relayed 1, failed 1)"
+            ),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_protocol_log_filters() {
+        let mut app = App::new();
+        for (peer_id, frame_type, direction) in [
+            ("aaaa1111", "ADD_ADDRESS", FrameDirection::Sent),
+            ("bbbb2222", "PUNCH_ME_NOW", FrameDirection::Received),
+            ("aaaa3333", "PUNCH_ME_NOW", FrameDirection::Sent),
+        ] {
+            app.add_protocol_frame(ProtocolFrame {
+                peer_id: peer_id.to_string(),
+                frame_type: frame_type.to_string(),
+                direction,
+                timestamp: Instant::now(),
+                context: None,
+            });
+        }
+        let shown = |app: &App| {
+            app.filtered_protocol_frames()
+                .map(|frame| frame.peer_id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shown(&app), ["aaaa3333", "bbbb2222", "aaaa1111"]);
+
+        app.cycle_protocol_log_filter();
+        assert_eq!(shown(&app), ["aaaa3333", "aaaa1111"]);
+        app.cycle_protocol_log_filter();
+        assert_eq!(shown(&app), ["bbbb2222"]);
+        app.cycle_protocol_log_filter();
+        assert_eq!(app.protocol_log_filter.label(), "ADD_ADDRESS");
+        app.cycle_protocol_log_filter();
+        assert_eq!(app.protocol_log_filter.label(), "PUNCH_ME_NOW");
+        assert_eq!(shown(&app), ["aaaa3333", "bbbb2222"]);
+
+        // Peer ID prefix search combines with the frame type filter
+        app.protocol_log_start_search();
+        "aaaa".chars().for_each(|c| app.protocol_log_search_char(c));
+        assert_eq!(shown(&app), ["aaaa3333"]);
+        app.protocol_log_cancel_search();
+        assert!(!app.protocol_log_searching);
+
+        app.cycle_protocol_log_filter();
+        assert_eq!(app.protocol_log_filter, ProtocolLogFilter::All);
+        assert_eq!(app.protocol_frames.len(), 3);
     }
 }
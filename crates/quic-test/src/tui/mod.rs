@@ -38,19 +38,25 @@
 //! ```
 
 mod app;
+mod replay;
 mod screens;
 mod types;
 mod ui;
 
 pub use app::{App, AppState, InputEvent, Tab};
+pub use replay::{
+    RecordedFrame, ReplayControl, frames_to_ndjson, history_to_events, load_history_file,
+    load_recorded_frames, replay_events, replay_frames,
+};
 pub use types::{
-    AlertSeverity, AnomalyEntry, CacheHealth, ComponentHealth, ConnectedPeer, ConnectionQuality,
-    ConnectivityTestResults, ContactDisplay, ContactOnlineStatus, DhtOperationStats, DhtStats,
-    EigenTrustStats, FrameDirection, GeographicDistribution, HealthAlert, HealthStats,
-    HealthStatus, LatencyStats, LocalNodeInfo, McpConnectionStatus, McpState, McpTool,
-    McpToolCategory, MessageDisplay, NatTraversalPhase, NatTypeAnalytics, NetworkStatistics,
-    PlacementStats, ProofStatus, ProtocolFrame, RegionStats, ResourceUsage, TestConnectivityMethod,
-    TrafficDirection, TrafficType, TrustEntry, country_flag,
+    AddressFamily, AlertSeverity, AnomalyEntry, CacheHealth, ComponentHealth, ConnectedPeer,
+    ConnectionQuality, ConnectivitySweepSummary, ConnectivityTestResults, ContactDisplay,
+    ContactOnlineStatus, CryptoTally, DhtOperationStats, DhtStats, EigenTrustStats, FrameDirection,
+    GeographicDistribution, HealthAlert, HealthStats, HealthStatus, LatencyStats, LocalNodeInfo,
+    McpConnectionStatus, McpState, McpTool, McpToolCategory, MessageDisplay, NatTraversalPhase,
+    NatTypeAnalytics, NegotiatedCrypto, NetworkStatistics, PlacementStats, ProofStatus,
+    ProtocolFrame, RegionStats, ResourceUsage, TestConnectivityMethod, TrafficDirection,
+    TrafficType, TrustEntry, country_flag,
 };
 
 use crossterm::{
@@ -98,6 +104,10 @@ fn event_name(event: &TuiEvent) -> &'static str {
         TuiEvent::PacketReceived(_) => "PacketReceived",
         TuiEvent::RegistrationUpdated(_) => "RegistrationUpdated",
         TuiEvent::HeartbeatSent => "HeartbeatSent",
+        TuiEvent::RegistryActive { .. } => "RegistryActive",
+        TuiEvent::DialQueue { .. } => "DialQueue",
+        TuiEvent::JoinTime(_) => "JoinTime",
+        TuiEvent::ClassicalCryptoFallback { .. } => "ClassicalCryptoFallback",
         TuiEvent::Error(_) => "Error",
         TuiEvent::Info(_) => "Info",
         TuiEvent::ClearMessages => "ClearMessages",
@@ -105,12 +115,13 @@ fn event_name(event: &TuiEvent) -> &'static str {
         TuiEvent::RegistrationComplete => "RegistrationComplete",
         TuiEvent::PeerConnected(_) => "PeerConnected",
         TuiEvent::TestPacketResult { .. } => "TestPacketResult",
-        TuiEvent::ConnectionFailed => "ConnectionFailed",
+        TuiEvent::ConnectionFailed { .. } => "ConnectionFailed",
         TuiEvent::ConnectionAttempted => "ConnectionAttempted",
         TuiEvent::InboundConnection => "InboundConnection",
         TuiEvent::OutboundConnection => "OutboundConnection",
         TuiEvent::Ipv4Connection => "Ipv4Connection",
         TuiEvent::Ipv6Connection => "Ipv6Connection",
+        TuiEvent::AddressFamilySelected { .. } => "AddressFamilySelected",
         TuiEvent::GossipPeerDiscovered { .. } => "GossipPeerDiscovered",
         TuiEvent::GossipRelayDiscovered { .. } => "GossipRelayDiscovered",
         TuiEvent::PeerSeen(_) => "PeerSeen",
@@ -119,6 +130,7 @@ fn event_name(event: &TuiEvent) -> &'static str {
         TuiEvent::NatPhaseUpdate { .. } => "NatPhaseUpdate",
         TuiEvent::TrafficTypeUpdate { .. } => "TrafficTypeUpdate",
         TuiEvent::CacheHealthUpdate(_) => "CacheHealthUpdate",
+        TuiEvent::BootstrapStatus { .. } => "BootstrapStatus",
         TuiEvent::NatAnalyticsUpdate(_) => "NatAnalyticsUpdate",
         TuiEvent::GeographicDistributionUpdate(_) => "GeographicDistributionUpdate",
         TuiEvent::ConnectivityTestInbound { .. } => "ConnectivityTestInbound",
@@ -151,6 +163,9 @@ fn event_name(event: &TuiEvent) -> &'static str {
         TuiEvent::MessageSendFailed { .. } => "MessageSendFailed",
         TuiEvent::MessagesLoaded(_) => "MessagesLoaded",
         TuiEvent::MessageReceived(_) => "MessageReceived",
+        TuiEvent::ConnectivitySweepStarted { .. } => "ConnectivitySweepStarted",
+        TuiEvent::ConnectivitySweepComplete(_) => "ConnectivitySweepComplete",
+        TuiEvent::ConnectivitySweepRejected { .. } => "ConnectivitySweepRejected",
     }
 }
 
@@ -173,6 +188,29 @@ pub enum TuiEvent {
     RegistrationUpdated(bool),
     /// Heartbeat sent
     HeartbeatSent,
+    /// The registry the node is currently talking to
+    RegistryActive {
+        /// Base URL of the active registry
+        url: String,
+        /// Whether it is a fallback rather than the primary
+        failover: bool,
+    },
+    /// Outbound dials running and waiting for a slot
+    DialQueue {
+        /// Dials currently in flight
+        in_flight: usize,
+        /// Dials queued behind the concurrency limit
+        queued: usize,
+    },
+    /// Time from start to the first NAT-verified peer
+    JoinTime(crate::registry::JoinTime),
+    /// A completed handshake offered a classical key-exchange group
+    ClassicalCryptoFallback {
+        /// Peer whose handshake offered it
+        peer_id: String,
+        /// Offered key-exchange groups and the peer's signature scheme
+        crypto: NegotiatedCrypto,
+    },
     /// Set error message
     Error(String),
     /// Set info message
@@ -191,11 +229,20 @@ pub enum TuiEvent {
         peer_id: String,
         /// Whether the test succeeded
         success: bool,
-        /// Round-trip time if successful
+        /// Mean round-trip time over the burst, if any packet was answered
         rtt: Option<std::time::Duration>,
+        /// RTT jitter (standard deviation) over the burst
+        jitter: Option<std::time::Duration>,
     },
     /// Connection attempt failed
-    ConnectionFailed,
+    ConnectionFailed {
+        /// Peer we failed to connect to
+        peer_id: String,
+        /// Classified reason for the failure
+        reason: crate::registry::FailureReasonCode,
+        /// How long the attempt ran before giving up
+        elapsed: std::time::Duration,
+    },
     /// Connection attempt started
     ConnectionAttempted,
     /// Inbound connection received (they connected to us - proves NAT traversal works!)
@@ -206,6 +253,13 @@ pub enum TuiEvent {
     Ipv4Connection,
     /// IPv6 connection established
     Ipv6Connection,
+    /// Happy-eyeballs race to a peer was won by `family`
+    AddressFamilySelected {
+        /// Peer the race was for
+        peer_id: String,
+        /// Winning address family
+        family: AddressFamily,
+    },
     /// Gossip: peer discovered via gossip network
     GossipPeerDiscovered {
         /// Peer ID of discovered peer
@@ -261,6 +315,15 @@ pub enum TuiEvent {
     },
     /// Bootstrap cache health updated
     CacheHealthUpdate(CacheHealth),
+    /// Bootstrap peer probed at startup
+    BootstrapStatus {
+        /// Bootstrap peer address
+        addr: std::net::SocketAddr,
+        /// Whether the probe connected
+        reachable: bool,
+        /// Handshake RTT if reachable
+        rtt: Option<std::time::Duration>,
+    },
     /// NAT type analytics updated
     NatAnalyticsUpdate(NatTypeAnalytics),
     /// Geographic distribution updated
@@ -358,6 +421,18 @@ pub enum TuiEvent {
     MessagesLoaded(Vec<types::MessageDisplay>),
     /// Incoming message received
     MessageReceived(types::MessageDisplay),
+    /// Connectivity sweep began dialling every known peer
+    ConnectivitySweepStarted {
+        /// Number of peers being dialled
+        peers: usize,
+    },
+    /// Connectivity sweep finished
+    ConnectivitySweepComplete(types::ConnectivitySweepSummary),
+    /// Connectivity sweep refused (rate limited or already running)
+    ConnectivitySweepRejected {
+        /// Why the sweep did not run
+        reason: String,
+    },
 }
 
 /// MCP request from TUI to McpClient
@@ -413,6 +488,8 @@ pub enum McpRequest {
         /// Public key (hex or base64)
         pubkey: String,
     },
+    /// Dial every currently-known peer and report reachability by method
+    RunConnectivitySweep,
 }
 
 /// Configuration for the TUI.
@@ -492,13 +569,18 @@ pub async fn run_tui(
 
         // Poll for terminal events with timeout
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            // Resize the buffers now so the next draw re-checks the minimum size
+            if let Event::Resize(..) = event {
+                terminal.autoresize()?;
+            }
+            if let Event::Key(key) = event {
                 // Only handle key press events (not release)
                 if key.kind == KeyEventKind::Press {
                     use crossterm::event::KeyCode;
 
                     // MCP tab has special key handling for category/tool navigation and parameter editing
-                    let handled_by_mcp = if app.active_tab == app::Tab::Mcp {
+                    let handled_by_tab = if app.active_tab == app::Tab::Mcp {
                         // Contact add mode has highest priority
                         if app.contact_is_adding() {
                             match key.code {
@@ -677,6 +759,21 @@ pub async fn run_tui(
                                         false // Let 'm' fall through to global handler
                                     }
                                 }
+                                // 's' key to sweep all known peers for reachability
+                                KeyCode::Char('s') => {
+                                    if app.connectivity_sweep_pending() {
+                                        app.info_message =
+                                            Some("Connectivity sweep already running".to_string());
+                                    } else if let Some(ref tx) = mcp_request_tx {
+                                        let _ = tx.try_send(McpRequest::RunConnectivitySweep);
+                                        app.info_message =
+                                            Some("Requesting connectivity sweep...".to_string());
+                                    } else {
+                                        app.error_message =
+                                            Some("MCP client not available".to_string());
+                                    }
+                                    true
+                                }
                                 // Number keys 1-7 for category selection on MCP tab
                                 KeyCode::Char('1') => {
                                     app.mcp_select_category(0);
@@ -709,12 +806,49 @@ pub async fn run_tui(
                                 _ => false, // Let other keys fall through
                             }
                         }
+                    } else if app.active_tab == app::Tab::ProtocolLog {
+                        // Typing a peer ID prefix captures all character input
+                        if app.protocol_log_searching {
+                            match key.code {
+                                KeyCode::Esc => {
+                                    app.protocol_log_cancel_search();
+                                    true
+                                }
+                                KeyCode::Enter => {
+                                    app.protocol_log_finish_search();
+                                    true
+                                }
+                                KeyCode::Backspace => {
+                                    app.protocol_log_search_backspace();
+                                    true
+                                }
+                                KeyCode::Char(c) => {
+                                    app.protocol_log_search_char(c);
+                                    true
+                                }
+                                _ => false,
+                            }
+                        } else {
+                            match key.code {
+                                // 'f' cycles the direction / frame type filter
+                                KeyCode::Char('f') | KeyCode::Char('F') => {
+                                    app.cycle_protocol_log_filter();
+                                    true
+                                }
+                                // '/' starts a peer ID prefix search
+                                KeyCode::Char('/') => {
+                                    app.protocol_log_start_search();
+                                    true
+                                }
+                                _ => false,
+                            }
+                        }
                     } else {
                         false
                     };
 
-                    // Only process global input events if not handled by MCP tab
-                    if !handled_by_mcp {
+                    // Only process global input events if not handled by the active tab
+                    if !handled_by_tab {
                         match InputEvent::from_key(key.code) {
                             InputEvent::Quit => {
                                 // If help overlay is open, close it instead of quitting
@@ -763,9 +897,22 @@ pub async fn run_tui(
                             InputEvent::TabMcp => {
                                 app.active_tab = app::Tab::Mcp;
                             }
+                            InputEvent::TabBootstrap => {
+                                app.active_tab = app::Tab::Bootstrap;
+                            }
                             InputEvent::ToggleProofHelp => {
                                 app.toggle_proof_help();
                             }
+                            InputEvent::ToggleReplayPause => {
+                                if let Some(replay) = &app.replay {
+                                    let paused = replay.toggle_pause();
+                                    app.set_info(if paused {
+                                        "Replay paused"
+                                    } else {
+                                        "Replay resumed"
+                                    });
+                                }
+                            }
                             InputEvent::Unknown => {}
                         }
                     }
@@ -782,6 +929,7 @@ pub async fn run_tui(
         if last_tick.elapsed() >= tick_rate {
             // Clear traffic indicators periodically
             app.clear_traffic_indicators();
+            app.stats.record_bandwidth_sample();
             last_tick = std::time::Instant::now();
         }
 
@@ -811,10 +959,18 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
     match event {
         TuiEvent::UpdateLocalNode(node_info) => {
             let was_registered = app.local_node.registered;
+            let active_registry = app.local_node.active_registry.take();
+            let registry_failover = app.local_node.registry_failover;
+            let join_time = app.local_node.join_time;
             app.local_node = node_info;
+            app.local_node.join_time = app.local_node.join_time.or(join_time);
             if was_registered {
                 app.local_node.registered = true;
             }
+            if app.local_node.active_registry.is_none() {
+                app.local_node.active_registry = active_registry;
+                app.local_node.registry_failover = registry_failover;
+            }
         }
         TuiEvent::UpdatePeer(peer) => {
             app.update_peer(peer);
@@ -837,6 +993,20 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
         TuiEvent::HeartbeatSent => {
             app.heartbeat_sent();
         }
+        TuiEvent::RegistryActive { url, failover } => {
+            app.local_node.active_registry = Some(url);
+            app.local_node.registry_failover = failover;
+        }
+        TuiEvent::DialQueue { in_flight, queued } => {
+            app.stats.dials_in_flight = in_flight;
+            app.stats.dials_queued = queued;
+        }
+        TuiEvent::JoinTime(join_time) => {
+            app.local_node.join_time = Some(join_time);
+        }
+        TuiEvent::ClassicalCryptoFallback { peer_id, crypto } => {
+            app.record_classical_fallback(&peer_id, &crypto);
+        }
         TuiEvent::Error(msg) => {
             app.set_error(&msg);
         }
@@ -893,15 +1063,23 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
                 }
             }
 
+            if peer.zero_rtt {
+                app.stats.zero_rtt_reconnects += 1;
+            }
             app.update_peer(peer);
             app.stats.unique_peers_attempted.insert(peer_id.clone());
             app.stats.unique_peers_connected.insert(peer_id);
             app.stats.connection_successes += 1;
             app.stats.connection_attempts += 1;
         }
-        TuiEvent::ConnectionFailed => {
+        TuiEvent::ConnectionFailed {
+            peer_id,
+            reason,
+            elapsed,
+        } => {
             app.stats.connection_failures += 1;
             app.stats.connection_attempts += 1;
+            app.record_connection_failure(&peer_id, reason, elapsed);
         }
         TuiEvent::ConnectionAttempted => {
             app.stats.connection_attempts += 1;
@@ -910,6 +1088,7 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
             peer_id,
             success,
             rtt,
+            jitter,
         } => {
             if success {
                 // Mark peer as seen (successful communication)
@@ -917,7 +1096,7 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
                 app.packet_sent(&peer_id);
                 app.packet_received(&peer_id);
                 if let Some(rtt) = rtt {
-                    app.update_peer_rtt(&peer_id, rtt);
+                    app.update_peer_rtt(&peer_id, rtt, jitter);
                 }
             }
         }
@@ -933,6 +1112,9 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
         TuiEvent::Ipv6Connection => {
             app.stats.ipv6_connections += 1;
         }
+        TuiEvent::AddressFamilySelected { peer_id, family } => {
+            app.set_peer_address_family(&peer_id, family);
+        }
         TuiEvent::GossipPeerDiscovered {
             peer_id,
             addresses,
@@ -1001,6 +1183,13 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
         TuiEvent::CacheHealthUpdate(health) => {
             app.update_cache_health(health);
         }
+        TuiEvent::BootstrapStatus {
+            addr,
+            reachable,
+            rtt,
+        } => {
+            app.update_bootstrap_status(addr, reachable, rtt);
+        }
         TuiEvent::NatAnalyticsUpdate(analytics) => {
             app.update_nat_analytics(analytics);
         }
@@ -1164,6 +1353,15 @@ fn handle_tui_event(app: &mut App, event: TuiEvent) {
             app.mcp_state.current_messages.push(message);
             app.info_message = Some("New message received".to_string());
         }
+        TuiEvent::ConnectivitySweepStarted { peers } => {
+            app.connectivity_sweep_started(peers);
+        }
+        TuiEvent::ConnectivitySweepComplete(summary) => {
+            app.connectivity_sweep_finished(summary);
+        }
+        TuiEvent::ConnectivitySweepRejected { reason } => {
+            app.connectivity_sweep_rejected(&reason);
+        }
     }
 }
 
@@ -1255,6 +1453,47 @@ mod tests {
         handle_tui_event(&mut app, TuiEvent::RegistrationUpdated(true));
         assert!(app.local_node.registered);
 
+        // Active registry survives a local node refresh
+        handle_tui_event(
+            &mut app,
+            TuiEvent::RegistryActive {
+                url: "https://saorsa-2.saorsalabs.com".to_string(),
+                failover: true,
+            },
+        );
+        handle_tui_event(
+            &mut app,
+            TuiEvent::UpdateLocalNode(LocalNodeInfo::default()),
+        );
+        assert_eq!(
+            app.local_node.active_registry.as_deref(),
+            Some("https://saorsa-2.saorsalabs.com")
+        );
+        assert!(app.local_node.registry_failover);
+
+        handle_tui_event(
+            &mut app,
+            TuiEvent::DialQueue {
+                in_flight: 16,
+                queued: 40,
+            },
+        );
+        assert_eq!(
+            (app.stats.dials_in_flight, app.stats.dials_queued),
+            (16, 40)
+        );
+
+        let join_time = crate::registry::JoinTime {
+            discovery_ms: 300,
+            connection_ms: 3900,
+        };
+        handle_tui_event(&mut app, TuiEvent::JoinTime(join_time));
+        handle_tui_event(
+            &mut app,
+            TuiEvent::UpdateLocalNode(LocalNodeInfo::default()),
+        );
+        assert_eq!(app.local_node.join_time, Some(join_time));
+
         // Test packet events
         let peer = ConnectedPeer::new("test_peer", crate::registry::ConnectionMethod::Direct);
         handle_tui_event(&mut app, TuiEvent::UpdatePeer(peer));
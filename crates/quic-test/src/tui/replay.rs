@@ -0,0 +1,393 @@
+//! Protocol frame recording and replay.
+//!
+//! Frames are stored as NDJSON, one `RecordedFrame` per line, with the time
+//! since the start of the recording instead of an absolute timestamp. Replay
+//! feeds them back into the TUI at the recorded cadence, so the protocol log
+//! can be demoed without a live network.
+//!
+//! Connectivity history files written by `HistoryManager` can be replayed the
+//! same way for post-mortem analysis, with a speed multiplier and pause/resume.
+
+use crate::history::{ConnectivityStatus, HistoryFile};
+use crate::tui::TuiEvent;
+use crate::tui::types::{FrameDirection, ProtocolFrame, TestConnectivityMethod};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+
+/// A protocol frame as stored in a recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Milliseconds since the start of the recording
+    pub offset_ms: u64,
+    /// Peer ID this frame is associated with
+    pub peer_id: String,
+    /// Frame type (ADD_ADDRESS, PUNCH_ME_NOW, etc.)
+    pub frame_type: String,
+    /// Direction: sent or received
+    pub direction: FrameDirection,
+    /// Additional context (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+impl RecordedFrame {
+    /// Record a live frame relative to `start`.
+    pub fn from_frame(frame: &ProtocolFrame, start: Instant) -> Self {
+        Self {
+            offset_ms: frame.timestamp.saturating_duration_since(start).as_millis() as u64,
+            peer_id: frame.peer_id.clone(),
+            frame_type: frame.frame_type.clone(),
+            direction: frame.direction,
+            context: frame.context.clone(),
+        }
+    }
+
+    /// Turn the record back into a frame stamped with the current time.
+    pub fn into_frame(self) -> ProtocolFrame {
+        ProtocolFrame {
+            peer_id: self.peer_id,
+            frame_type: self.frame_type,
+            direction: self.direction,
+            timestamp: Instant::now(),
+            context: self.context,
+        }
+    }
+}
+
+/// Serialize frames as NDJSON with offsets relative to `start`.
+pub fn frames_to_ndjson(frames: &[ProtocolFrame], start: Instant) -> anyhow::Result<String> {
+    let mut out = String::new();
+    for frame in frames {
+        out.push_str(&serde_json::to_string(&RecordedFrame::from_frame(
+            frame, start,
+        ))?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Load a recording, ordered by offset. Blank lines are ignored.
+pub fn load_recorded_frames(path: &Path) -> anyhow::Result<Vec<RecordedFrame>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut frames = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<RecordedFrame>(line)
+                .map_err(|e| anyhow::anyhow!("line {}: {}", i + 1, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    frames.sort_by_key(|f| f.offset_ms);
+    Ok(frames)
+}
+
+/// Send recorded frames to the TUI at their recorded offsets.
+///
+/// Returns the number of frames sent; stops early if the TUI has exited.
+pub async fn replay_frames(frames: Vec<RecordedFrame>, event_tx: mpsc::Sender<TuiEvent>) -> usize {
+    let start = tokio::time::Instant::now();
+    let mut sent = 0;
+    for frame in frames {
+        tokio::time::sleep_until(start + Duration::from_millis(frame.offset_ms)).await;
+        if event_tx
+            .send(TuiEvent::ProtocolFrame(frame.into_frame()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+        sent += 1;
+    }
+    sent
+}
+
+/// Pause/resume handle shared between the TUI and a running history replay.
+#[derive(Debug)]
+pub struct ReplayControl {
+    paused: watch::Sender<bool>,
+    /// Playback speed multiplier (2.0 = twice as fast as recorded)
+    pub speed: f64,
+}
+
+impl ReplayControl {
+    /// Create a control for a replay running at `speed`, plus the receiver the
+    /// replay task watches.
+    pub fn new(speed: f64) -> (Self, watch::Receiver<bool>) {
+        let (paused, rx) = watch::channel(false);
+        (Self { paused, speed }, rx)
+    }
+
+    /// Whether playback is currently paused.
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Toggle pause, returning the new paused state.
+    pub fn toggle_pause(&self) -> bool {
+        self.paused.send_modify(|paused| *paused = !*paused);
+        self.is_paused()
+    }
+}
+
+/// Load a history file written by `HistoryManager`.
+pub fn load_history_file(path: &Path) -> anyhow::Result<HistoryFile> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Convert history entries into TUI events, offset from the first entry.
+///
+/// Entries are ordered by timestamp (stably, so ties keep file order), and
+/// each tested connection method becomes one outbound connectivity result.
+pub fn history_to_events(history: &HistoryFile) -> Vec<(Duration, TuiEvent)> {
+    let mut entries: Vec<_> = history.entries.iter().collect();
+    entries.sort_by_key(|e| e.timestamp);
+    let Some(first) = entries.first().map(|e| e.timestamp) else {
+        return Vec::new();
+    };
+
+    let mut events = Vec::new();
+    for entry in entries {
+        let offset = (entry.timestamp - first).to_std().unwrap_or_default();
+        let c = &entry.connectivity;
+        for (method, status) in [
+            (TestConnectivityMethod::DirectIpv4, &c.ipv4_direct),
+            (TestConnectivityMethod::DirectIpv6, &c.ipv6_direct),
+            (TestConnectivityMethod::NatTraversalIpv4, &c.nat_traversal),
+            (TestConnectivityMethod::RelayedIpv4, &c.relay),
+            (TestConnectivityMethod::Masque, &c.masque),
+        ] {
+            let (success, rtt_ms) = match status {
+                ConnectivityStatus::Success { rtt_ms } => (true, Some(*rtt_ms)),
+                ConnectivityStatus::Failed { .. } => (false, None),
+                ConnectivityStatus::Untested => continue,
+            };
+            events.push((
+                offset,
+                TuiEvent::ConnectivityTestOutbound {
+                    peer_id: entry.peer_id.clone(),
+                    method,
+                    success,
+                    rtt_ms,
+                },
+            ));
+        }
+    }
+    events
+}
+
+/// Send timed events to the TUI at `speed`x their recorded offsets.
+///
+/// Playback position only advances while unpaused, so pausing never skips
+/// events. Returns the number of events sent; stops early if the TUI or the
+/// replay control has gone away.
+pub async fn replay_events(
+    events: Vec<(Duration, TuiEvent)>,
+    speed: f64,
+    mut paused: watch::Receiver<bool>,
+    event_tx: mpsc::Sender<TuiEvent>,
+) -> usize {
+    let mut position = Duration::ZERO;
+    let mut sent = 0;
+    for (offset, event) in events {
+        while position < offset {
+            if *paused.borrow_and_update() {
+                if paused.changed().await.is_err() {
+                    return sent;
+                }
+                continue;
+            }
+            let resumed = tokio::time::Instant::now();
+            tokio::select! {
+                _ = tokio::time::sleep((offset - position).div_f64(speed)) => position = offset,
+                changed = paused.changed() => {
+                    position += resumed.elapsed().mul_f64(speed);
+                    if changed.is_err() {
+                        return sent;
+                    }
+                }
+            }
+        }
+        if event_tx.send(event).await.is_err() {
+            break;
+        }
+        sent += 1;
+    }
+    sent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{HistoryEntry, PeerConnectivity};
+    use crate::tui::App;
+
+    fn frame(frame_type: &str, direction: FrameDirection, at: Instant) -> ProtocolFrame {
+        ProtocolFrame {
+            peer_id: "a3b7c9d2".to_string(),
+            frame_type: frame_type.to_string(),
+            direction,
+            timestamp: at,
+            context: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay_round_trip() {
+        let start = Instant::now();
+        let recorded = vec![
+            frame("ADD_ADDRESS", FrameDirection::Sent, start),
+            frame(
+                "PUNCH_ME_NOW",
+                FrameDirection::Received,
+                start + Duration::from_millis(20),
+            ),
+            frame(
+                "OBSERVED_ADDRESS",
+                FrameDirection::Received,
+                start + Duration::from_millis(40),
+            ),
+        ];
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("frames.ndjson");
+        std::fs::write(&path, frames_to_ndjson(&recorded, start).unwrap()).unwrap();
+
+        let frames = load_recorded_frames(&path).unwrap();
+        assert_eq!(
+            frames.iter().map(|f| f.offset_ms).collect::<Vec<_>>(),
+            vec![0, 20, 40]
+        );
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let replay_started = Instant::now();
+        assert_eq!(replay_frames(frames, tx).await, 3);
+        assert!(replay_started.elapsed() >= Duration::from_millis(40));
+
+        let mut app = App::new();
+        while let Some(TuiEvent::ProtocolFrame(frame)) = rx.recv().await {
+            app.add_protocol_frame(frame);
+        }
+        let types: Vec<&str> = app
+            .protocol_frames
+            .iter()
+            .map(|f| f.frame_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["ADD_ADDRESS", "PUNCH_ME_NOW", "OBSERVED_ADDRESS"]
+        );
+        assert_eq!(app.protocol_frames[1].direction, FrameDirection::Received);
+    }
+
+    fn history_entry(peer_id: &str, at_ms: i64, connectivity: PeerConnectivity) -> HistoryEntry {
+        let mut entry = HistoryEntry::new(peer_id, connectivity);
+        entry.timestamp = chrono::DateTime::from_timestamp_millis(1_700_000_000_000 + at_ms)
+            .expect("valid timestamp");
+        entry
+    }
+
+    #[test]
+    fn test_history_to_events_orders_by_timestamp() {
+        let mut history = HistoryFile::new("node");
+        history.entries = vec![
+            history_entry(
+                "peer-b",
+                500,
+                PeerConnectivity {
+                    relay: ConnectivityStatus::Failed {
+                        reason: "timeout".to_string(),
+                    },
+                    masque: ConnectivityStatus::Success { rtt_ms: 40 },
+                    ..Default::default()
+                },
+            ),
+            history_entry(
+                "peer-a",
+                0,
+                PeerConnectivity {
+                    ipv4_direct: ConnectivityStatus::Success { rtt_ms: 12 },
+                    ipv6_direct: ConnectivityStatus::Failed {
+                        reason: "unreachable".to_string(),
+                    },
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        let events = history_to_events(&history);
+        let summary: Vec<_> = events
+            .iter()
+            .map(|(offset, event)| match event {
+                TuiEvent::ConnectivityTestOutbound {
+                    peer_id,
+                    method,
+                    success,
+                    rtt_ms,
+                } => (
+                    offset.as_millis(),
+                    peer_id.as_str(),
+                    *method,
+                    *success,
+                    *rtt_ms,
+                ),
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    0,
+                    "peer-a",
+                    TestConnectivityMethod::DirectIpv4,
+                    true,
+                    Some(12)
+                ),
+                (0, "peer-a", TestConnectivityMethod::DirectIpv6, false, None),
+                (
+                    500,
+                    "peer-b",
+                    TestConnectivityMethod::RelayedIpv4,
+                    false,
+                    None
+                ),
+                (
+                    500,
+                    "peer-b",
+                    TestConnectivityMethod::Masque,
+                    true,
+                    Some(40)
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_replay_pauses_and_scales() {
+        let events = vec![
+            (Duration::ZERO, TuiEvent::Info("first".to_string())),
+            (
+                Duration::from_millis(400),
+                TuiEvent::Info("second".to_string()),
+            ),
+        ];
+        let (control, paused) = ReplayControl::new(10.0);
+        let (tx, mut rx) = mpsc::channel(16);
+        let replay = tokio::spawn(replay_events(events, control.speed, paused, tx));
+
+        assert!(matches!(rx.recv().await, Some(TuiEvent::Info(m)) if m == "first"));
+        assert!(control.toggle_pause());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(rx.try_recv().is_err(), "paused replay must not advance");
+
+        let resumed = Instant::now();
+        assert!(!control.toggle_pause());
+        assert!(matches!(rx.recv().await, Some(TuiEvent::Info(m)) if m == "second"));
+        assert!(resumed.elapsed() < Duration::from_millis(400));
+        assert_eq!(replay.await.unwrap(), 2);
+    }
+}
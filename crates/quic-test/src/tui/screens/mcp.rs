@@ -88,7 +88,7 @@ fn draw_connection_header(frame: &mut Frame, app: &App, area: Rect) {
         ]
     } else {
         vec![Line::from(Span::styled(
-            "  Not connected - press [C] to connect",
+            "  Not connected - press [C] to connect, [S] to sweep peer reachability",
             Style::default().fg(Color::DarkGray),
         ))]
     };
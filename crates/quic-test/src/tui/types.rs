@@ -3,8 +3,10 @@
 //! This module defines the data structures used by the terminal UI
 //! to display network state and peer connections.
 
-use crate::registry::{ConnectionDirection, ConnectionMethod, ConnectivityMatrix, NatType};
-use std::collections::HashSet;
+use crate::registry::{
+    ConnectionDirection, ConnectionMethod, ConnectivityMatrix, FailureReasonCode, JoinTime, NatType,
+};
+use std::collections::{HashSet, VecDeque};
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
@@ -52,6 +54,24 @@ pub struct ConnectionHistoryEntry {
     pub nat_verified: bool,
     /// Peer's NAT type (for connectivity matrix display)
     pub nat_type: NatType,
+    /// Verified connections where the peer connected to us
+    pub inbound_verified: u32,
+    /// Verified connections where we connected to the peer
+    pub outbound_verified: u32,
+    /// Reason for the most recent failed connection to this peer
+    pub last_error: Option<FailureReasonCode>,
+    /// How long the most recent failed attempt ran before giving up
+    pub last_error_after: Option<Duration>,
+    /// Directions already counted for the current connection, keyed by its
+    /// `connected_at`, so repeated updates aren't counted twice
+    counted: Option<(Instant, VerifiedDirections)>,
+}
+
+/// Which directions of one connection passed NAT verification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct VerifiedDirections {
+    inbound: bool,
+    outbound: bool,
 }
 
 /// Outcome for a connection method attempt.
@@ -270,6 +290,11 @@ impl ConnectionHistoryEntry {
             connection_count: 0,
             nat_verified: false,
             nat_type: NatType::Unknown,
+            inbound_verified: 0,
+            outbound_verified: 0,
+            last_error: None,
+            last_error_after: None,
+            counted: None,
         }
     }
 
@@ -291,8 +316,14 @@ impl ConnectionHistoryEntry {
             connection_count: 1,
             nat_verified: peer.is_nat_verified(),
             nat_type: peer.nat_type,
+            inbound_verified: 0,
+            outbound_verified: 0,
+            last_error: None,
+            last_error_after: None,
+            counted: None,
         };
 
+        entry.record_verified(peer);
         entry.record_attempt(peer.direction, peer.method, true);
         entry.status = ConnectionStatus::Connected;
         entry
@@ -304,6 +335,7 @@ impl ConnectionHistoryEntry {
         self.last_seen = Instant::now();
         self.method = Some(peer.method);
         self.direction = Some(peer.direction);
+        self.record_verified(peer);
         self.record_attempt(peer.direction, peer.method, true);
         self.total_packets += peer.packets_sent + peer.packets_received;
         self.connection_count += 1;
@@ -365,6 +397,65 @@ impl ConnectionHistoryEntry {
         }
     }
 
+    /// Record a failed connection and its reason.
+    pub fn record_failure(&mut self, reason: FailureReasonCode, elapsed: Duration) {
+        self.last_seen = Instant::now();
+        self.last_error = Some(reason);
+        self.last_error_after = Some(elapsed);
+        if self.status == ConnectionStatus::Coordinating {
+            self.status = ConnectionStatus::Failed;
+        }
+    }
+
+    /// Count the directions of `peer`'s connection that passed verification.
+    ///
+    /// Each direction counts once per connection, however often the peer is
+    /// updated; unverified directions don't count at all.
+    pub fn record_verified(&mut self, peer: &super::ConnectedPeer) {
+        let counted = match self.counted {
+            Some((connected_at, counted)) if connected_at == peer.connected_at => counted,
+            _ => VerifiedDirections::default(),
+        };
+        if peer.inbound_verified && !counted.inbound {
+            self.inbound_verified += 1;
+        }
+        if peer.outbound_verified && !counted.outbound {
+            self.outbound_verified += 1;
+        }
+        self.counted = Some((
+            peer.connected_at,
+            VerifiedDirections {
+                inbound: counted.inbound || peer.inbound_verified,
+                outbound: counted.outbound || peer.outbound_verified,
+            },
+        ));
+    }
+
+    /// Ratio of inbound to outbound verified connections.
+    ///
+    /// Returns `None` until at least one outbound connection was verified.
+    /// A ratio near 1.0 means NAT traversal works symmetrically for this peer.
+    pub fn inbound_outbound_ratio(&self) -> Option<f64> {
+        if self.outbound_verified == 0 {
+            None
+        } else {
+            Some(self.inbound_verified as f64 / self.outbound_verified as f64)
+        }
+    }
+
+    /// Get direction counts and ratio as a formatted string (e.g. "3←/2→ 1.50").
+    pub fn direction_ratio_string(&self) -> String {
+        let ratio = match self.inbound_outbound_ratio() {
+            Some(ratio) => format!("{:.2}", ratio),
+            None if self.inbound_verified > 0 => "in-only".to_string(),
+            None => "-".to_string(),
+        };
+        format!(
+            "{}←/{}→ {}",
+            self.inbound_verified, self.outbound_verified, ratio
+        )
+    }
+
     /// Mark as disconnected.
     pub fn mark_disconnected(&mut self) {
         self.status = ConnectionStatus::Disconnected;
@@ -413,6 +504,14 @@ pub enum ConnectionQuality {
 }
 
 impl ConnectionQuality {
+    /// Create quality indicator from RTT and jitter.
+    ///
+    /// Jitter is weighted double, as an unstable link hurts interactive
+    /// traffic more than a steadily slow one.
+    pub fn from_rtt_and_jitter(rtt: Duration, jitter: Option<Duration>) -> Self {
+        Self::from_rtt(rtt + jitter.unwrap_or_default() * 2)
+    }
+
     /// Create quality indicator from RTT measurement.
     pub fn from_rtt(rtt: Duration) -> Self {
         let ms = rtt.as_millis();
@@ -449,6 +548,22 @@ pub enum TrafficDirection {
     Idle,
 }
 
+/// Address family of a direct connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    Ipv4,
+    Ipv6,
+}
+
+impl std::fmt::Display for AddressFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ipv4 => write!(f, "IPv4"),
+            Self::Ipv6 => write!(f, "IPv6"),
+        }
+    }
+}
+
 /// NAT test state for a peer - tracks the connect-back verification flow.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum PeerNatTestState {
@@ -497,6 +612,72 @@ impl PeerNatTestState {
     }
 }
 
+/// Key-exchange and signature algorithms of a connection.
+///
+/// The signature scheme is the one the peer's key uses. ant-quic does not
+/// expose the negotiated key-exchange group, so only the groups offered
+/// for the handshake are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedCrypto {
+    /// Key-exchange groups offered, `|`-joined (e.g. "ML-KEM-768", "X25519")
+    pub offered_key_exchange: String,
+    /// Signature scheme (e.g. "ML-DSA-65", "Ed25519")
+    pub signature: String,
+}
+
+impl NegotiatedCrypto {
+    /// Create from the offered key-exchange groups and the signature scheme.
+    pub fn new(offered_key_exchange: &str, signature: &str) -> Self {
+        Self {
+            offered_key_exchange: offered_key_exchange.to_string(),
+            signature: signature.to_string(),
+        }
+    }
+
+    /// True if the key exchange is post-quantum (pure or hybrid ML-KEM).
+    ///
+    /// PQC only if every offered group is, since any of them may have been
+    /// negotiated.
+    pub fn is_pqc(&self) -> bool {
+        self.offered_key_exchange.split('|').all(|group| {
+            let group = group.trim().to_ascii_uppercase().replace('-', "");
+            group.contains("MLKEM")
+        })
+    }
+
+    /// Compact label for display: offered groups, then the signature
+    /// scheme, e.g. "ML-KEM-768/ML-DSA-65".
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.offered_key_exchange, self.signature)
+    }
+}
+
+/// Network-wide count of PQC vs. classical connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CryptoTally {
+    /// Connections using a post-quantum key exchange
+    pub pqc: usize,
+    /// Connections using a classical key exchange
+    pub classical: usize,
+    /// Connections whose algorithms are not known
+    pub unknown: usize,
+}
+
+impl CryptoTally {
+    /// Tally the negotiated algorithms of the given peers.
+    pub fn from_peers<'a>(peers: impl IntoIterator<Item = &'a ConnectedPeer>) -> Self {
+        let mut tally = Self::default();
+        for peer in peers {
+            match &peer.crypto {
+                Some(crypto) if crypto.is_pqc() => tally.pqc += 1,
+                Some(_) => tally.classical += 1,
+                None => tally.unknown += 1,
+            }
+        }
+        tally
+    }
+}
+
 /// Information about a connected peer for display.
 #[derive(Debug, Clone)]
 pub struct ConnectedPeer {
@@ -514,6 +695,8 @@ pub struct ConnectedPeer {
     pub direction: ConnectionDirection,
     /// Current RTT measurement
     pub rtt: Option<Duration>,
+    /// RTT jitter over the last test burst
+    pub jitter: Option<Duration>,
     /// Connection quality
     pub quality: ConnectionQuality,
     /// TX traffic indicator
@@ -530,6 +713,8 @@ pub struct ConnectedPeer {
     pub addresses: Vec<SocketAddr>,
     /// Connectivity matrix showing all tested paths
     pub connectivity: ConnectivityMatrix,
+    /// Address family that won the IPv4/IPv6 race (None if not direct)
+    pub address_family: Option<AddressFamily>,
     /// Outbound connection verified (we successfully connected to them)
     pub outbound_verified: bool,
     /// Inbound connection verified (they successfully connected to us - proves NAT traversal!)
@@ -554,8 +739,21 @@ pub struct ConnectedPeer {
     pub nat_test_state: PeerNatTestState,
     /// Peer's detected NAT type (from registry)
     pub nat_type: NatType,
+    /// Offered key-exchange groups and the peer's signature scheme
+    pub crypto: Option<NegotiatedCrypto>,
+    /// Relay peer carrying this connection (relayed connections only)
+    pub relay_peer_id: Option<String>,
+    /// Whether this reconnect resumed the session with 0-RTT
+    pub zero_rtt: bool,
+    /// Connection method transitions, oldest first (capped at
+    /// [`METHOD_HISTORY_CAPACITY`])
+    pub method_history: Vec<(Instant, ConnectionMethod)>,
 }
 
+/// Method transitions kept per peer, so a flapping connection can't grow
+/// its history without bound.
+pub const METHOD_HISTORY_CAPACITY: usize = 8;
+
 impl ConnectedPeer {
     /// Create a new connected peer.
     pub fn new(peer_id: &str, method: ConnectionMethod) -> Self {
@@ -588,6 +786,7 @@ impl ConnectedPeer {
             method,
             direction,
             rtt: None,
+            jitter: None,
             quality: ConnectionQuality::Fair,
             tx_active: false,
             rx_active: false,
@@ -596,6 +795,7 @@ impl ConnectedPeer {
             connected_at: now,
             addresses: Vec::new(),
             connectivity: ConnectivityMatrix::default(),
+            address_family: None,
             outbound_verified,
             inbound_verified,
             last_nat_test_time: None,
@@ -608,9 +808,48 @@ impl ConnectedPeer {
             data_rx: false,
             nat_test_state: PeerNatTestState::Pending,
             nat_type: NatType::Unknown,
+            crypto: None,
+            relay_peer_id: None,
+            zero_rtt: false,
+            method_history: vec![(now, method)],
+        }
+    }
+
+    /// Switch to `method`, recording a transition if it differs from the
+    /// last one seen.
+    pub fn record_method(&mut self, method: ConnectionMethod) {
+        self.method = method;
+        if self.method_history.last().map(|(_, m)| *m) == Some(method) {
+            return;
+        }
+        self.method_history.push((Instant::now(), method));
+        if self.method_history.len() > METHOD_HISTORY_CAPACITY {
+            let excess = self.method_history.len() - METHOD_HISTORY_CAPACITY;
+            self.method_history.drain(..excess);
         }
     }
 
+    /// Continue `previous`'s method history, appending our current method
+    /// if the connection changed method since.
+    pub fn inherit_method_history(&mut self, previous: &ConnectedPeer) {
+        let current = self.method;
+        self.method_history = previous.method_history.clone();
+        self.record_method(current);
+    }
+
+    /// Upgrade path through connection methods, e.g. "RELAYED → PUNCHED → DIRECT".
+    pub fn method_path(&self) -> String {
+        self.method_history
+            .iter()
+            .map(|(_, method)| match method {
+                ConnectionMethod::Direct => "DIRECT",
+                ConnectionMethod::HolePunched => "PUNCHED",
+                ConnectionMethod::Relayed => "RELAYED",
+            })
+            .collect::<Vec<_>>()
+            .join(" → ")
+    }
+
     /// Set the gossip peer ID for correlation.
     pub fn set_gossip_peer_id(&mut self, gossip_id: &str) {
         self.gossip_peer_id = Some(gossip_id.to_string());
@@ -641,8 +880,14 @@ impl ConnectedPeer {
 
     /// Update RTT measurement.
     pub fn update_rtt(&mut self, rtt: Duration) {
+        self.update_rtt_with_jitter(rtt, None);
+    }
+
+    /// Update RTT and jitter; quality accounts for both.
+    pub fn update_rtt_with_jitter(&mut self, rtt: Duration, jitter: Option<Duration>) {
         self.rtt = Some(rtt);
-        self.quality = ConnectionQuality::from_rtt(rtt);
+        self.jitter = jitter;
+        self.quality = ConnectionQuality::from_rtt_and_jitter(rtt, jitter);
     }
 
     /// Get formatted RTT string.
@@ -697,6 +942,14 @@ pub struct LocalNodeInfo {
     pub registration_expires_in: Option<Duration>,
     /// Last heartbeat sent
     pub last_heartbeat: Option<Instant>,
+    /// Registry currently answering registrations and heartbeats
+    pub active_registry: Option<String>,
+    /// Whether the active registry is a fallback rather than the primary
+    pub registry_failover: bool,
+    /// Per-attempt outbound connect timeout; slower peers are skipped
+    pub connect_timeout: Option<Duration>,
+    /// Time from start to the first NAT-verified peer, once joined
+    pub join_time: Option<JoinTime>,
 }
 
 impl Default for LocalNodeInfo {
@@ -713,6 +966,10 @@ impl Default for LocalNodeInfo {
             registered: false,
             registration_expires_in: None,
             last_heartbeat: None,
+            active_registry: None,
+            registry_failover: false,
+            connect_timeout: None,
+            join_time: None,
         }
     }
 }
@@ -745,6 +1002,23 @@ impl LocalNodeInfo {
     }
 }
 
+/// Maximum bandwidth samples kept (one per UI tick, ~30 seconds at 250ms).
+pub const MAX_BANDWIDTH_SAMPLES: usize = 120;
+
+/// Samples averaged by [`NetworkStatistics::throughput_bps`] (~2 seconds at 250ms).
+pub const THROUGHPUT_WINDOW_SAMPLES: usize = 8;
+
+/// Cumulative traffic counters captured at a point in time.
+#[derive(Debug, Clone, Copy)]
+pub struct BandwidthSample {
+    /// When the sample was taken
+    pub at: Instant,
+    /// Total bytes sent at this point
+    pub bytes_sent: u64,
+    /// Total bytes received at this point
+    pub bytes_received: u64,
+}
+
 /// Network-wide statistics for display.
 #[derive(Debug, Clone, Default)]
 pub struct NetworkStatistics {
@@ -798,6 +1072,16 @@ pub struct NetworkStatistics {
     pub unique_peers_attempted: HashSet<String>,
     /// Unique peers we successfully connected to
     pub unique_peers_connected: HashSet<String>,
+    /// Bounded series of traffic samples for rate/trend display
+    pub bandwidth_samples: VecDeque<BandwidthSample>,
+    /// Connections whose handshake offered classical (non-PQC) key exchange
+    pub classical_fallback: u64,
+    /// Reconnects that resumed the session with 0-RTT
+    pub zero_rtt_reconnects: u64,
+    /// Outbound dials currently in flight
+    pub dials_in_flight: usize,
+    /// Outbound dials waiting behind the concurrency limit
+    pub dials_queued: usize,
 }
 
 impl NetworkStatistics {
@@ -842,6 +1126,106 @@ impl NetworkStatistics {
     pub fn bytes_received_formatted(&self) -> String {
         format_bytes(self.bytes_received)
     }
+
+    /// Sample the current byte counters into the bandwidth series.
+    pub fn record_bandwidth_sample(&mut self) {
+        self.record_bandwidth_sample_at(Instant::now());
+    }
+
+    /// Sample the current byte counters with an explicit timestamp.
+    pub fn record_bandwidth_sample_at(&mut self, at: Instant) {
+        if self.bandwidth_samples.len() >= MAX_BANDWIDTH_SAMPLES {
+            self.bandwidth_samples.pop_front();
+        }
+        self.bandwidth_samples.push_back(BandwidthSample {
+            at,
+            bytes_sent: self.bytes_sent,
+            bytes_received: self.bytes_received,
+        });
+    }
+
+    /// Current (sent, received) rate in bytes/sec from the two latest samples.
+    pub fn bandwidth_rate(&self) -> (f64, f64) {
+        let mut latest = self.bandwidth_samples.iter().rev();
+        match (latest.next(), latest.next()) {
+            (Some(current), Some(previous)) => sample_rate(previous, current),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Recent (send, receive) throughput in bits/sec.
+    ///
+    /// Averaged over the last [`THROUGHPUT_WINDOW_SAMPLES`] samples so a
+    /// single bursty tick does not dominate the reading.
+    pub fn throughput_bps(&self) -> (f64, f64) {
+        let window = self.bandwidth_samples.len().min(THROUGHPUT_WINDOW_SAMPLES);
+        let skip = self.bandwidth_samples.len() - window;
+        match (
+            self.bandwidth_samples.get(skip),
+            self.bandwidth_samples.back(),
+        ) {
+            (Some(oldest), Some(newest)) => {
+                let (sent, received) = sample_rate(oldest, newest);
+                (sent * 8.0, received * 8.0)
+            }
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Get formatted recent throughput as "↑send ↓receive".
+    pub fn throughput_formatted(&self) -> String {
+        let (sent, received) = self.throughput_bps();
+        format!("↑{} ↓{}", format_bitrate(sent), format_bitrate(received))
+    }
+
+    /// Total (sent + received) rate in bytes/sec for each sample interval, oldest first.
+    pub fn bandwidth_series(&self) -> Vec<u64> {
+        self.bandwidth_samples
+            .iter()
+            .zip(self.bandwidth_samples.iter().skip(1))
+            .map(|(previous, current)| {
+                let (sent, received) = sample_rate(previous, current);
+                (sent + received) as u64
+            })
+            .collect()
+    }
+
+    /// Get formatted total bandwidth rate (e.g. "1.5 KB/s").
+    pub fn bandwidth_rate_formatted(&self) -> String {
+        let (sent, received) = self.bandwidth_rate();
+        format!("{}/s", format_bytes((sent + received) as u64))
+    }
+}
+
+/// Format a bit rate into a human-readable string (e.g. "8.0 kbit/s").
+fn format_bitrate(bps: f64) -> String {
+    if bps >= 1e9 {
+        format!("{:.1} Gbit/s", bps / 1e9)
+    } else if bps >= 1e6 {
+        format!("{:.1} Mbit/s", bps / 1e6)
+    } else if bps >= 1e3 {
+        format!("{:.1} kbit/s", bps / 1e3)
+    } else {
+        format!("{:.0} bit/s", bps)
+    }
+}
+
+/// Byte rates (sent, received) between two samples.
+fn sample_rate(previous: &BandwidthSample, current: &BandwidthSample) -> (f64, f64) {
+    let secs = current
+        .at
+        .saturating_duration_since(previous.at)
+        .as_secs_f64();
+    if secs <= 0.0 {
+        return (0.0, 0.0);
+    }
+    (
+        current.bytes_sent.saturating_sub(previous.bytes_sent) as f64 / secs,
+        current
+            .bytes_received
+            .saturating_sub(previous.bytes_received) as f64
+            / secs,
+    )
 }
 
 /// Format bytes into human-readable string.
@@ -902,6 +1286,51 @@ pub fn country_flag(country_code: &str) -> &'static str {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_method_history_records_transitions_and_caps() {
+        let mut peer = ConnectedPeer::new("aaaa", ConnectionMethod::Relayed);
+        peer.record_method(ConnectionMethod::Relayed);
+        peer.record_method(ConnectionMethod::HolePunched);
+        peer.record_method(ConnectionMethod::Direct);
+        assert_eq!(peer.method, ConnectionMethod::Direct);
+        assert_eq!(peer.method_path(), "RELAYED → PUNCHED → DIRECT");
+
+        for _ in 0..METHOD_HISTORY_CAPACITY {
+            peer.record_method(ConnectionMethod::Relayed);
+            peer.record_method(ConnectionMethod::Direct);
+        }
+        assert_eq!(peer.method_history.len(), METHOD_HISTORY_CAPACITY);
+        assert_eq!(
+            peer.method_history.last().map(|(_, m)| *m),
+            Some(ConnectionMethod::Direct)
+        );
+    }
+
+    #[test]
+    fn test_crypto_tally_counts_pqc_and_classical() {
+        let mut pqc = ConnectedPeer::new("aaaa", ConnectionMethod::Direct);
+        pqc.crypto = Some(NegotiatedCrypto::new("ML-KEM-768", "ML-DSA-65"));
+        let mut hybrid = ConnectedPeer::new("bbbb", ConnectionMethod::Direct);
+        hybrid.crypto = Some(NegotiatedCrypto::new("X25519MLKEM768", "ML-DSA-65"));
+        let mut classical = ConnectedPeer::new("cccc", ConnectionMethod::HolePunched);
+        classical.crypto = Some(NegotiatedCrypto::new("X25519", "Ed25519"));
+        let unknown = ConnectedPeer::new("dddd", ConnectionMethod::Relayed);
+
+        let tally = CryptoTally::from_peers([&pqc, &hybrid, &classical, &unknown]);
+        assert_eq!(
+            tally,
+            CryptoTally {
+                pqc: 2,
+                classical: 1,
+                unknown: 1
+            }
+        );
+        assert_eq!(
+            pqc.crypto.as_ref().map(|c| c.label()).as_deref(),
+            Some("ML-KEM-768/ML-DSA-65")
+        );
+    }
+
     #[test]
     fn test_connection_quality_from_rtt() {
         assert_eq!(
@@ -926,6 +1355,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_quality_penalizes_jitter() {
+        let rtt = Duration::from_millis(40);
+        assert_eq!(
+            ConnectionQuality::from_rtt_and_jitter(rtt, None),
+            ConnectionQuality::Excellent
+        );
+        assert_eq!(
+            ConnectionQuality::from_rtt_and_jitter(rtt, Some(Duration::from_millis(40))),
+            ConnectionQuality::Fair
+        );
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(500), "500 B");
@@ -934,6 +1376,105 @@ mod tests {
         assert_eq!(format_bytes(1_500_000_000), "1.4 GB");
     }
 
+    #[test]
+    fn test_bandwidth_rate_from_consecutive_samples() {
+        let mut stats = NetworkStatistics::default();
+        let start = Instant::now();
+
+        stats.record_bandwidth_sample_at(start);
+        assert_eq!(stats.bandwidth_rate(), (0.0, 0.0));
+
+        stats.bytes_sent = 2000;
+        stats.bytes_received = 1000;
+        stats.record_bandwidth_sample_at(start + Duration::from_secs(2));
+        assert_eq!(stats.bandwidth_rate(), (1000.0, 500.0));
+
+        stats.bytes_sent = 2000;
+        stats.bytes_received = 5000;
+        stats.record_bandwidth_sample_at(start + Duration::from_secs(4));
+        assert_eq!(stats.bandwidth_rate(), (0.0, 2000.0));
+        assert_eq!(stats.bandwidth_series(), vec![1500, 2000]);
+    }
+
+    #[test]
+    fn test_throughput_averages_recent_window() {
+        let mut stats = NetworkStatistics::default();
+        assert_eq!(stats.throughput_bps(), (0.0, 0.0));
+
+        let start = Instant::now();
+        // A burst long before the window, then a steady 1000 B/s upload
+        stats.bytes_received = 1_000_000;
+        stats.record_bandwidth_sample_at(start);
+        for i in 1..=(THROUGHPUT_WINDOW_SAMPLES as u64 + 2) {
+            stats.bytes_sent = i * 250;
+            stats.record_bandwidth_sample_at(start + Duration::from_millis(i * 250));
+        }
+        assert_eq!(stats.throughput_bps(), (8000.0, 0.0));
+        assert_eq!(stats.throughput_formatted(), "↑8.0 kbit/s ↓0 bit/s");
+    }
+
+    #[test]
+    fn test_bandwidth_samples_bounded() {
+        let mut stats = NetworkStatistics::default();
+        let start = Instant::now();
+        for i in 0..(MAX_BANDWIDTH_SAMPLES as u64 + 50) {
+            stats.bytes_sent = i * 100;
+            stats.record_bandwidth_sample_at(start + Duration::from_secs(i));
+        }
+        assert_eq!(stats.bandwidth_samples.len(), MAX_BANDWIDTH_SAMPLES);
+        assert_eq!(stats.bandwidth_series().len(), MAX_BANDWIDTH_SAMPLES - 1);
+        assert_eq!(stats.bandwidth_rate(), (100.0, 0.0));
+    }
+
+    #[test]
+    fn test_inbound_outbound_ratio() {
+        let mut entry = ConnectionHistoryEntry::new("abcdef0123456789");
+        assert_eq!(entry.inbound_outbound_ratio(), None);
+        assert_eq!(entry.direction_ratio_string(), "0←/0→ -");
+
+        // A connection that hasn't passed verification yet doesn't count
+        let mut peer = ConnectedPeer::with_direction(
+            "abcdef0123456789",
+            ConnectionMethod::Direct,
+            ConnectionDirection::Inbound,
+        );
+        peer.inbound_verified = false;
+        entry.record_verified(&peer);
+        assert_eq!(entry.direction_ratio_string(), "0←/0→ -");
+
+        // Repeated updates of the same verified connection count once
+        peer.inbound_verified = true;
+        entry.record_verified(&peer);
+        entry.record_verified(&peer);
+        assert_eq!(entry.inbound_outbound_ratio(), None);
+        assert_eq!(entry.direction_ratio_string(), "1←/0→ in-only");
+
+        // The outbound direction verifying later counts for that direction only
+        peer.outbound_verified = true;
+        entry.record_verified(&peer);
+        assert_eq!(entry.direction_ratio_string(), "1←/1→ 1.00");
+
+        // Each new connection counts again
+        for direction in [
+            ConnectionDirection::Inbound,
+            ConnectionDirection::Inbound,
+            ConnectionDirection::Outbound,
+        ] {
+            std::thread::sleep(Duration::from_millis(1));
+            let reconnect = ConnectedPeer::with_direction(
+                "abcdef0123456789",
+                ConnectionMethod::Direct,
+                direction,
+            );
+            entry.record_verified(&reconnect);
+            entry.record_verified(&reconnect);
+        }
+        assert_eq!(entry.inbound_verified, 3);
+        assert_eq!(entry.outbound_verified, 2);
+        assert_eq!(entry.inbound_outbound_ratio(), Some(1.5));
+        assert_eq!(entry.direction_ratio_string(), "3←/2→ 1.50");
+    }
+
     #[test]
     fn test_country_flag() {
         assert_eq!(country_flag("US"), "🇺🇸");
@@ -1114,7 +1655,8 @@ pub struct ProtocolFrame {
 }
 
 /// Direction of protocol frame
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FrameDirection {
     Sent,
     Received,
@@ -1138,12 +1680,66 @@ impl FrameDirection {
     }
 }
 
+/// Which protocol frames the Protocol Log tab shows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProtocolLogFilter {
+    /// Every frame
+    #[default]
+    All,
+    /// Only frames in one direction
+    Direction(FrameDirection),
+    /// Only frames of one type (ADD_ADDRESS, PUNCH_ME_NOW, etc.)
+    FrameType(String),
+}
+
+impl ProtocolLogFilter {
+    /// Whether `frame` passes this filter.
+    pub fn matches(&self, frame: &ProtocolFrame) -> bool {
+        match self {
+            Self::All => true,
+            Self::Direction(direction) => frame.direction == *direction,
+            Self::FrameType(frame_type) => frame.frame_type == *frame_type,
+        }
+    }
+
+    /// The filter after this one: all, sent, received, then each frame
+    /// type in `frame_types` (sorted) and back to all.
+    pub fn next(&self, frame_types: &[String]) -> Self {
+        let after_received = || {
+            frame_types
+                .first()
+                .map(|frame_type| Self::FrameType(frame_type.clone()))
+                .unwrap_or(Self::All)
+        };
+        match self {
+            Self::All => Self::Direction(FrameDirection::Sent),
+            Self::Direction(FrameDirection::Sent) => Self::Direction(FrameDirection::Received),
+            Self::Direction(FrameDirection::Received) => after_received(),
+            Self::FrameType(current) => frame_types
+                .iter()
+                .find(|frame_type| *frame_type > current)
+                .map(|frame_type| Self::FrameType(frame_type.clone()))
+                .unwrap_or(Self::All),
+        }
+    }
+
+    /// Short label for the tab header.
+    pub fn label(&self) -> String {
+        match self {
+            Self::All => "all".to_string(),
+            Self::Direction(FrameDirection::Sent) => "sent".to_string(),
+            Self::Direction(FrameDirection::Received) => "received".to_string(),
+            Self::FrameType(frame_type) => frame_type.clone(),
+        }
+    }
+}
+
 /// Enhanced traffic type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TrafficType {
     /// Protocol overhead (gossip, heartbeats, NAT frames)
     Protocol,
-    /// Test data packets (5KB test packets)
+    /// Test data packets (5KB by default, see `--packet-size`)
     TestData,
     /// Relay traffic (MASQUE protocol)
     Relay,
@@ -1249,6 +1845,60 @@ impl CacheHealth {
     }
 }
 
+/// Reachability of a bootstrap peer, as probed at startup.
+#[derive(Debug, Clone)]
+pub struct BootstrapPeerStatus {
+    /// Bootstrap peer address
+    pub addr: SocketAddr,
+    /// Whether the most recent probe succeeded
+    pub reachable: bool,
+    /// Handshake RTT of the most recent successful probe
+    pub rtt: Option<Duration>,
+    /// When the peer was last contacted successfully
+    pub last_success: Option<Instant>,
+    /// Number of probes sent to this peer
+    pub attempts: u32,
+}
+
+impl BootstrapPeerStatus {
+    /// Create a status entry for a peer that has not been probed yet.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            reachable: false,
+            rtt: None,
+            last_success: None,
+            attempts: 0,
+        }
+    }
+
+    /// Record the outcome of a probe.
+    pub fn record_probe(&mut self, reachable: bool, rtt: Option<Duration>) {
+        self.attempts += 1;
+        self.reachable = reachable;
+        if reachable {
+            self.rtt = rtt;
+            self.last_success = Some(Instant::now());
+        }
+    }
+
+    /// Time since the last successful contact, or "never".
+    pub fn last_contact_str(&self) -> String {
+        match self.last_success {
+            Some(t) => format!("{} ago", format_elapsed_short(t.elapsed().as_secs())),
+            None => "never".to_string(),
+        }
+    }
+
+    /// Get RTT as a display string.
+    pub fn rtt_string(&self) -> String {
+        match self.rtt {
+            Some(rtt) => format!("{}ms", rtt.as_millis()),
+            None => "-".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct NatTypeAnalytics {
     pub full_cone: NatTypeStats,
@@ -2496,6 +3146,57 @@ pub enum McpInvocationResult {
     Pending,
 }
 
+/// Outcome of an on-demand connectivity sweep across all known peers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectivitySweepSummary {
+    /// Peers we tried to dial
+    pub attempted: usize,
+    /// Peers reached by any method
+    pub succeeded: usize,
+    /// Reached directly
+    pub direct: usize,
+    /// Reached via hole-punching
+    pub hole_punched: usize,
+    /// Reached via a relay
+    pub relayed: usize,
+    /// Wall-clock time the sweep took
+    pub duration: Duration,
+}
+
+impl ConnectivitySweepSummary {
+    /// Record one dial result (`None` = every method failed).
+    pub fn record(&mut self, method: Option<ConnectionMethod>) {
+        self.attempted += 1;
+        let Some(method) = method else {
+            return;
+        };
+        self.succeeded += 1;
+        match method {
+            ConnectionMethod::Direct => self.direct += 1,
+            ConnectionMethod::HolePunched => self.hole_punched += 1,
+            ConnectionMethod::Relayed => self.relayed += 1,
+        }
+    }
+
+    /// Peers that could not be reached.
+    pub fn failed(&self) -> usize {
+        self.attempted - self.succeeded
+    }
+
+    /// One-line summary for the invocation history.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}/{} reachable (direct {}, punched {}, relayed {}, failed {})",
+            self.succeeded,
+            self.attempted,
+            self.direct,
+            self.hole_punched,
+            self.relayed,
+            self.failed()
+        )
+    }
+}
+
 /// Contact information for TUI display.
 #[derive(Debug, Clone)]
 pub struct ContactDisplay {
@@ -19,6 +19,7 @@
 //! - [8] Placement - Geographic diversity, rack/network diversity
 //! - [9] Health - Component status, alerts, anomalies, resources
 //! - [0] MCP - Model Context Protocol client, tool invocation
+//! - [B] Bootstrap - Reachability, last contact and RTT of each bootstrap peer
 
 use crate::registry::{ConnectionMethod, NatType};
 use crate::tui::app::{App, Tab};
@@ -26,10 +27,10 @@ use crate::tui::screens::draw_mcp_tab;
 use crate::tui::types::{ConnectivityTestPhase, country_flag};
 use ratatui::{
     Frame,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Sparkline, Table, Tabs, Wrap},
 };
 
 /// Traffic light colors for connection methods
@@ -37,6 +38,10 @@ const COLOR_DIRECT: Color = Color::Green; // Best: Direct connection
 const COLOR_HOLEPUNCHED: Color = Color::Rgb(255, 165, 0); // Great: NAT traversed (orange)
 const COLOR_RELAYED: Color = Color::Red; // Works: But slower
 
+/// Smallest terminal the full layout is drawn in
+pub const MIN_WIDTH: u16 = 80;
+pub const MIN_HEIGHT: u16 = 24;
+
 /// Get color for connection method (traffic light approach)
 fn method_color(method: &ConnectionMethod) -> Color {
     match method {
@@ -141,6 +146,11 @@ fn format_connectivity_matrix(matrix: &crate::registry::ConnectivityMatrix) -> V
 }
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
+    if frame.area().width < MIN_WIDTH || frame.area().height < MIN_HEIGHT {
+        draw_too_small(frame);
+        return;
+    }
+
     // Main layout: tabs at top, content in middle, footer at bottom
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -162,6 +172,7 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         Tab::GossipHealth => draw_gossip_health_tab(frame, app, main_chunks[1]),
         Tab::ProtocolLog => draw_protocol_log_tab(frame, app, main_chunks[1]),
         Tab::Mcp => draw_mcp_tab(frame, app, main_chunks[1]),
+        Tab::Bootstrap => draw_bootstrap_tab(frame, app, main_chunks[1]),
     }
 
     draw_messages(frame, app, main_chunks[2]);
@@ -173,17 +184,46 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     }
 }
 
+/// Draw a centered notice in place of a layout that does not fit.
+fn draw_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let text = vec![
+        Line::from(Span::styled(
+            format!("Terminal too small (need {}×{})", MIN_WIDTH, MIN_HEIGHT),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("current {}×{}", area.width, area.height),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let height = (text.len() as u16).min(area.height);
+    let centered = Rect::new(
+        area.x,
+        area.y + (area.height - height) / 2,
+        area.width,
+        height,
+    );
+    let notice = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(notice, centered);
+}
+
 /// Draw the tab bar for navigation.
 fn draw_tab_bar(frame: &mut Frame, app: &App, area: Rect) {
     let tab_titles = vec![
         "[1]Ovr", "[2]Gsp", "[3]Mtx", "[4]Log", "[5]DHT", "[6]Trst", "[7]Adp", "[8]Plc", "[9]Hlth",
-        "[0]MCP",
+        "[0]MCP", "[B]Boot",
     ];
     let selected_idx = match app.active_tab {
         Tab::Overview => 0,
         Tab::GossipHealth => 1,
         Tab::ProtocolLog => 2,
         Tab::Mcp => 3,
+        Tab::Bootstrap => 10,
     };
 
     let tabs = Tabs::new(tab_titles)
@@ -192,7 +232,7 @@ fn draw_tab_bar(frame: &mut Frame, app: &App, area: Rect) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan))
                 .title(format!(
-                    " Saorsa TestNet v{} - [P]roof help / Tab/1-9,0 ",
+                    " Saorsa TestNet v{} - [P]roof help / Tab/1-9,0,B ",
                     env!("CARGO_PKG_VERSION")
                 )),
         )
@@ -218,7 +258,7 @@ fn draw_overview_tab(frame: &mut Frame, app: &mut App, area: Rect) {
             Constraint::Length(5), // Proof Status (verification summary)
             Constraint::Length(5), // Network Stats (comprehensive counts)
             Constraint::Length(5), // Your Node (with connection words)
-            Constraint::Min(6),    // Connected Peers
+            Constraint::Min(9),    // Connected Peers + selected peer detail
             Constraint::Length(8), // Activity Log
         ])
         .split(area);
@@ -582,6 +622,7 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let total = app.connected_peers.len();
+    let crypto = app.crypto_tally();
     let history_total = app.connection_history.len();
     let history_disconnected = app.history_disconnected_count();
 
@@ -640,6 +681,41 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
             format!("NAT✓{}", nat_verified),
             Style::default().fg(Color::Green),
         ),
+        Span::raw("  │  "),
+        Span::styled(
+            format!("PQC:{}", crypto.pqc),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("CL:{}", crypto.classical),
+            Style::default().fg(if crypto.classical > 0 {
+                Color::Red
+            } else {
+                Color::DarkGray
+            }),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("0RTT:{}", app.stats.zero_rtt_reconnects),
+            Style::default().fg(if app.stats.zero_rtt_reconnects > 0 {
+                Color::Green
+            } else {
+                Color::DarkGray
+            }),
+        ),
+        Span::raw("  │  "),
+        Span::styled(
+            format!(
+                "Dial:{}+{}q",
+                app.stats.dials_in_flight, app.stats.dials_queued
+            ),
+            Style::default().fg(if app.stats.dials_queued > 0 {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            }),
+        ),
         Span::raw("  "),
         Span::styled(
             traffic,
@@ -663,6 +739,18 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
         Color::Red
     };
 
+    // Relayed connections per relay, so a hotspot relay stands out
+    let relay_utilization = app.relay_utilization();
+    let relay_summary = if relay_utilization.is_empty() {
+        String::new()
+    } else {
+        let per_relay: Vec<String> = relay_utilization
+            .iter()
+            .map(|(relay, count)| format!("{}×{}", &relay[..8.min(relay.len())], count))
+            .collect();
+        format!("  │  RELAYS: {}", per_relay.join(" "))
+    };
+
     let line2 = Line::from(vec![
         Span::raw("  GOSSIP: HyPar "),
         Span::styled(
@@ -705,6 +793,7 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         ),
         connectivity_test_status_span(app),
+        Span::styled(relay_summary, Style::default().fg(COLOR_RELAYED)),
     ]);
 
     let (connected, attempted) = app.stats.unique_peer_counts();
@@ -729,7 +818,9 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(format!("{}", connected), Style::default().fg(Color::Green)),
         Span::raw("/"),
         Span::styled(format!("{}", attempted), Style::default().fg(Color::White)),
-        Span::raw(")  │  PKTS: "),
+        Span::raw(")  │  ISOLATED: "),
+        isolated_span(&app.isolated_peers()),
+        Span::raw("  │  PKTS: "),
         Span::styled(
             format!("{}↑", app.stats.packets_sent),
             Style::default().fg(Color::Cyan),
@@ -739,6 +830,10 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
             format!("{}↓", app.stats.packets_received),
             Style::default().fg(Color::Green),
         ),
+        Span::styled(
+            format!(" @{}", format_bytes_short(app.test_packet_size as u64)),
+            Style::default().fg(Color::DarkGray),
+        ),
         Span::raw("  │  UP: "),
         Span::styled(
             app.stats.uptime(),
@@ -753,8 +848,61 @@ fn draw_network_stats(frame: &mut Frame, app: &App, area: Rect) {
         ),
     ]);
 
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(BANDWIDTH_PANEL_WIDTH),
+        ])
+        .split(area);
+
     let paragraph = Paragraph::new(vec![line1, line2, line3]).block(block);
-    frame.render_widget(paragraph, area);
+    frame.render_widget(paragraph, chunks[0]);
+    draw_bandwidth_sparkline(frame, app, chunks[1]);
+}
+
+/// Isolated peer count with the first few IDs, red when any need attention.
+fn isolated_span(isolated: &[String]) -> Span<'static> {
+    const MAX_SHOWN: usize = 3;
+    if isolated.is_empty() {
+        return Span::styled("0", Style::default().fg(Color::Green));
+    }
+    let ids: Vec<&str> = isolated
+        .iter()
+        .take(MAX_SHOWN)
+        .map(|id| &id[..8.min(id.len())])
+        .collect();
+    let more = if isolated.len() > MAX_SHOWN {
+        ",…"
+    } else {
+        ""
+    };
+    Span::styled(
+        format!("{} [{}{}]", isolated.len(), ids.join(","), more),
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Width of the bandwidth trend panel next to the network stats.
+const BANDWIDTH_PANEL_WIDTH: u16 = 32;
+
+/// Draw the bandwidth-over-time sparkline with the recent throughput in the title.
+fn draw_bandwidth_sparkline(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(format!(" {} ", app.stats.throughput_formatted()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    // Show the most recent samples that fit inside the borders
+    let series = app.stats.bandwidth_series();
+    let visible = usize::from(area.width.saturating_sub(2));
+    let start = series.len().saturating_sub(visible);
+
+    let sparkline = Sparkline::default()
+        .block(block)
+        .data(&series[start..])
+        .style(Style::default().fg(Color::Magenta));
+    frame.render_widget(sparkline, area);
 }
 
 fn connectivity_test_status_span(app: &App) -> Span<'static> {
@@ -870,9 +1018,47 @@ fn draw_node_info(frame: &mut Frame, app: &App, area: Rect) {
             app.local_node.heartbeat_status(),
             Style::default().fg(Color::Green),
         ),
+        Span::raw("    Connect timeout: "),
+        Span::styled(
+            app.local_node
+                .connect_timeout
+                .map(|t| format!("{}s", t.as_secs()))
+                .unwrap_or_else(|| "-".to_string()),
+            Style::default().fg(Color::Cyan),
+        ),
+    ]);
+
+    let registry = match &app.local_node.active_registry {
+        Some(url) if app.local_node.registry_failover => Span::styled(
+            format!("{} (failover)", url),
+            Style::default().fg(Color::Yellow),
+        ),
+        Some(url) => Span::styled(url.clone(), Style::default().fg(Color::Cyan)),
+        None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+    };
+    let secs = |ms: u64| ms as f64 / 1000.0;
+    let join = match app.local_node.join_time {
+        Some(join) => Span::styled(
+            format!(
+                "Joined in {:.1}s (discovery {:.1}s + connect {:.1}s)",
+                secs(join.total_ms()),
+                secs(join.discovery_ms),
+                secs(join.connection_ms)
+            ),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::styled("Joining...", Style::default().fg(Color::Yellow)),
+    };
+    let line3 = Line::from(vec![
+        Span::raw("  Registry: "),
+        registry,
+        Span::raw("    "),
+        join,
     ]);
 
-    let text = vec![line1, line2];
+    let text = vec![line1, line2, line3];
     let paragraph = Paragraph::new(text).block(block);
     frame.render_widget(paragraph, area);
 }
@@ -1023,7 +1209,86 @@ fn draw_peers(frame: &mut Frame, app: &mut App, area: Rect) {
             .fg(Color::Cyan),
     );
 
-    frame.render_stateful_widget(table, area, &mut app.connections_table_state);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(3)])
+        .split(area);
+
+    frame.render_stateful_widget(table, chunks[0], &mut app.connections_table_state);
+    draw_peer_detail(frame, app, chunks[1]);
+}
+
+/// Draw details for the peer selected in the connections table.
+fn draw_peer_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(" PEER DETAIL ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue));
+
+    let line = match app.selected_history_entry() {
+        Some(entry) => Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                entry.full_id.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  │  Conns: "),
+            Span::styled(
+                format!("{}", entry.connection_count),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw("  │  In/Out: "),
+            Span::styled(
+                entry.direction_ratio_string(),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw("  │  NAT: "),
+            Span::styled(
+                format!("{:?}", entry.nat_type),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw("  │  Crypto (offered kx/sig): "),
+            match app
+                .connected_peers
+                .get(&entry.full_id)
+                .and_then(|p| p.crypto.as_ref())
+            {
+                Some(crypto) => Span::styled(
+                    crypto.label(),
+                    Style::default().fg(if crypto.is_pqc() {
+                        Color::Green
+                    } else {
+                        Color::Red
+                    }),
+                ),
+                None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+            },
+            Span::raw("  │  Path: "),
+            match app.connected_peers.get(&entry.full_id) {
+                Some(peer) => Span::styled(peer.method_path(), Style::default().fg(Color::Green)),
+                None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+            },
+            Span::raw("  │  Last error: "),
+            match entry.last_error {
+                Some(reason) => Span::styled(
+                    match entry.last_error_after {
+                        Some(after) => format!("{} after {}s", reason, after.as_secs()),
+                        None => reason.to_string(),
+                    },
+                    Style::default().fg(Color::Red),
+                ),
+                None => Span::styled("-", Style::default().fg(Color::DarkGray)),
+            },
+        ]),
+        None => Line::from(Span::styled(
+            "  Select a peer with ↑/↓ to see details",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+
+    frame.render_widget(Paragraph::new(line).block(block), area);
 }
 
 /// Draw messages panel (errors and info).
@@ -1051,12 +1316,27 @@ fn draw_messages(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Draw footer with controls and info.
-fn draw_footer(frame: &mut Frame, _app: &App, area: Rect) {
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray));
 
-    let line = Line::from(vec![
+    let mut spans = Vec::new();
+    if let Some(replay) = &app.replay {
+        spans.push(Span::styled(
+            format!(
+                "  REPLAY {}x {}",
+                replay.speed,
+                if replay.is_paused() { "⏸" } else { "▶" }
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(" [Space]", Style::default().fg(Color::Cyan)));
+        spans.push(Span::raw(" Pause"));
+    }
+    spans.extend([
         Span::styled("  [Q]", Style::default().fg(Color::Cyan)),
         Span::raw(" Quit  "),
         Span::styled("[T]", Style::default().fg(Color::Cyan)),
@@ -1080,7 +1360,7 @@ fn draw_footer(frame: &mut Frame, _app: &App, area: Rect) {
         ),
     ]);
 
-    let paragraph = Paragraph::new(line).block(block);
+    let paragraph = Paragraph::new(Line::from(spans)).block(block);
     frame.render_widget(paragraph, area);
 }
 
@@ -1535,6 +1815,11 @@ fn draw_pubsub_panel(frame: &mut Frame, app: &App, area: Rect) {
                     format!("{}", stats.plumtree_broadcasts),
                     Style::default().fg(Color::Cyan),
                 ),
+                Span::raw("    Duplicates: "),
+                Span::styled(
+                    format!("{}", stats.plumtree_duplicates),
+                    Style::default().fg(Color::Yellow),
+                ),
             ]),
             Line::from(vec![
                 Span::raw("  IHAVEs Sent: "),
@@ -1549,17 +1834,18 @@ fn draw_pubsub_panel(frame: &mut Frame, app: &App, area: Rect) {
                 ),
             ]),
             Line::from(vec![
-                Span::raw("  GRAFTs Sent: "),
+                Span::raw("  GRAFTs (est.): "),
                 Span::styled(
-                    format!("{}", stats.plumtree_grafts_sent),
+                    format!("{}", stats.plumtree_grafts_estimated),
                     Style::default().fg(Color::Magenta),
                 ),
-                Span::raw("    PRUNEs Sent: "),
+                Span::raw("    PRUNEs (est.): "),
                 Span::styled(
-                    format!("{}", stats.plumtree_prunes_sent),
+                    format!("{}", stats.plumtree_prunes_estimated),
                     Style::default().fg(Color::Red),
                 ),
             ]),
+            topic_subscribers_line(&stats.topic_subscribers),
         ]
     } else {
         vec![Line::from(Span::styled(
@@ -1571,6 +1857,26 @@ fn draw_pubsub_panel(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
+/// Subscriber count per discovery topic, red where nobody is subscribed.
+fn topic_subscribers_line(topics: &std::collections::HashMap<String, usize>) -> Line<'static> {
+    let mut topics: Vec<_> = topics.iter().collect();
+    topics.sort();
+    let mut spans = vec![Span::raw("  Topics:")];
+    if topics.is_empty() {
+        spans.push(Span::styled(" none", Style::default().fg(Color::DarkGray)));
+    }
+    for (topic, count) in topics {
+        let color = if *count == 0 {
+            Color::Red
+        } else {
+            Color::Green
+        };
+        spans.push(Span::raw(format!(" {}=", topic)));
+        spans.push(Span::styled(count.to_string(), Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
+
 /// Draw extended gossip stats (CRDT, Coordinator, Groups, Rendezvous).
 fn draw_extended_gossip_panel(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -2037,11 +2343,82 @@ fn draw_connectivity_matrix_tab(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Draw the Protocol Log tab - real-time message flow visualization.
+/// Draw the Bootstrap tab (reachability of each bootstrap peer).
+fn draw_bootstrap_tab(frame: &mut Frame, app: &App, area: Rect) {
+    let reachable = app.bootstrap_peers.iter().filter(|p| p.reachable).count();
+    let block = Block::default()
+        .title(format!(
+            " BOOTSTRAP PEERS ({}/{} reachable) ",
+            reachable,
+            app.bootstrap_peers.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    if app.bootstrap_peers.is_empty() {
+        let text = Paragraph::new("No bootstrap peers probed yet (local-only mode skips probing)")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        frame.render_widget(text, area);
+        return;
+    }
+
+    let header = Row::new(vec!["", "Address", "Last Contact", "RTT", "Probes"]).style(
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let rows: Vec<Row> = app
+        .bootstrap_peers
+        .iter()
+        .map(|peer| {
+            let (status_color, status_emoji) = if peer.reachable {
+                (Color::Green, "●")
+            } else {
+                (Color::Red, "✗")
+            };
+            Row::new(vec![
+                Cell::from(status_emoji).style(Style::default().fg(status_color)),
+                Cell::from(peer.addr.to_string()).style(Style::default().fg(status_color)),
+                Cell::from(peer.last_contact_str()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(peer.rtt_string()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(peer.attempts.to_string()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),  // Status
+            Constraint::Length(47), // Address (fits a full IPv6 socket address)
+            Constraint::Length(13), // Last contact
+            Constraint::Length(8),  // RTT
+            Constraint::Min(6),     // Probes
+        ],
+    )
+    .header(header)
+    .block(block);
+
+    frame.render_widget(table, area);
+}
+
 fn draw_protocol_log_tab(frame: &mut Frame, app: &App, area: Rect) {
+    let search = if app.protocol_log_searching {
+        format!("peer:{}_", app.protocol_log_search)
+    } else if app.protocol_log_search.is_empty() {
+        "peer:-".to_string()
+    } else {
+        format!("peer:{}", app.protocol_log_search)
+    };
     let block = Block::default()
         .title(format!(
-            " PROTOCOL LOG ({} frames) - Real-time Message Flow ",
-            app.protocol_frames.len()
+            " PROTOCOL LOG ({}/{} frames) - Filter [F]: {}  Search [/]: {} ",
+            app.filtered_protocol_frames().count(),
+            app.protocol_frames.len(),
+            app.protocol_log_filter.label(),
+            search
         ))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
@@ -2090,9 +2467,7 @@ fn draw_protocol_log_tab(frame: &mut Frame, app: &App, area: Rect) {
     )));
 
     for pf in app
-        .protocol_frames
-        .iter()
-        .rev()
+        .filtered_protocol_frames()
         .take(max_lines.saturating_sub(2))
     {
         let age = now.duration_since(pf.timestamp);
@@ -2158,6 +2533,11 @@ fn draw_protocol_log_tab(frame: &mut Frame, app: &App, area: Rect) {
             "  Waiting for protocol frames...",
             Style::default().fg(Color::DarkGray),
         )));
+    } else if app.filtered_protocol_frames().next().is_none() {
+        lines.push(Line::from(Span::styled(
+            "  No frames match the filter",
+            Style::default().fg(Color::DarkGray),
+        )));
     }
 
     frame.render_widget(Paragraph::new(lines).block(block), area);
@@ -2236,4 +2616,30 @@ mod tests {
         let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
         assert_eq!(text, "4✓ 6✗ N✓ R·");
     }
+
+    fn render(width: u16, height: u16) -> String {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let mut app = App::new();
+        terminal.draw(|frame| draw(frame, &mut app)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_small_terminal_shows_notice() {
+        assert!(render(40, 10).contains("Terminal too small (need 80×24)"));
+        assert!(render(120, 20).contains("Terminal too small"));
+        // Degenerate sizes must not panic
+        render(1, 1);
+        render(0, 0);
+
+        assert!(!render(MIN_WIDTH, MIN_HEIGHT).contains("Terminal too small"));
+    }
 }
@@ -7,7 +7,10 @@
 
 class WebSocketManager {
   constructor(options = {}) {
-    this.url = options.url || `ws://${window.location.host}/ws/live`;
+    // delta=true: resync snapshots arrive as changes to the previous one.
+    // compress=deflate: frames arrive as raw DEFLATE, if we can inflate it.
+    const query = WebSocketManager.canInflate() ? 'delta=true&compress=deflate' : 'delta=true';
+    this.url = options.url || `ws://${window.location.host}/ws/live?${query}`;
     this.reconnectInterval = options.reconnectInterval || 3000;
     this.maxReconnectAttempts = options.maxReconnectAttempts || 10;
 
@@ -17,9 +20,17 @@ class WebSocketManager {
     this.connected = false;
     this.initialStateReceived = false;
 
+    // Sequence number of the last event applied (from the server's event log)
+    this.lastSeq = null;
+    this.resyncPending = false;
+
     // State storage
     this.nodes = [];
     this.stats = {};
+    // Last full_state received, which delta snapshots are patched against
+    this.snapshot = null;
+    // Compressed frames are inflated one after another to keep their order
+    this.inflating = Promise.resolve();
 
     // Status callback
     this.onStatusChange = options.onStatusChange || (() => {});
@@ -34,6 +45,7 @@ class WebSocketManager {
     }
 
     this.ws = new WebSocket(this.url);
+    this.ws.binaryType = 'arraybuffer';
 
     this.ws.onopen = () => {
       console.log('WebSocket connected');
@@ -46,6 +58,7 @@ class WebSocketManager {
       console.log('WebSocket disconnected');
       this.connected = false;
       this.initialStateReceived = false;
+      this.resyncPending = false;
       this.onStatusChange('disconnected');
       this.scheduleReconnect();
     };
@@ -56,7 +69,12 @@ class WebSocketManager {
     };
 
     this.ws.onmessage = (event) => {
-      this.handleMessage(event.data);
+      this.inflating = this.inflating
+        .then(() => (typeof event.data === 'string'
+          ? event.data
+          : WebSocketManager.inflate(event.data)))
+        .then(data => this.handleMessage(data))
+        .catch(error => console.error('Error inflating WebSocket message:', error));
     };
   }
 
@@ -86,9 +104,26 @@ class WebSocketManager {
       const message = JSON.parse(data);
       const type = message.type;
 
+      if (typeof message.seq === 'number' && type !== 'full_state' && this.lastSeq !== null) {
+        // Already applied (a replay overlapping live events)
+        if (message.seq <= this.lastSeq) {
+          return;
+        }
+        // Missed some events: ask the server to replay them
+        if (message.seq > this.lastSeq + 1) {
+          this.requestResync();
+          return;
+        }
+      }
+      if (typeof message.seq === 'number') {
+        this.lastSeq = message.seq;
+        this.resyncPending = false;
+      }
+
       // Update internal state for common message types
       switch (type) {
         case 'full_state':
+          this.applySnapshot(message);
           this.nodes = message.nodes || [];
           this.stats = message.stats || {};
           this.initialStateReceived = true;
@@ -124,6 +159,79 @@ class WebSocketManager {
     }
   }
 
+  /**
+   * Keep a full_state as the delta baseline. A delta snapshot is patched
+   * onto the previous one and filled in, so handlers always see full nodes
+   * and stats.
+   */
+  applySnapshot(message) {
+    if (message.delta) {
+      this.snapshot = WebSocketManager.applyMergePatch(this.snapshot, message.patch);
+      message.nodes = Object.values(this.snapshot.nodes || {});
+      message.stats = this.snapshot.stats || {};
+      delete message.patch;
+    } else {
+      const nodes = {};
+      (message.nodes || []).forEach(node => { nodes[node.peer_id] = node; });
+      this.snapshot = { nodes, stats: message.stats || {} };
+    }
+  }
+
+  /**
+   * Whether this browser can inflate raw DEFLATE frames.
+   */
+  static canInflate() {
+    try {
+      new DecompressionStream('deflate-raw');
+      return true;
+    } catch (error) {
+      return false;
+    }
+  }
+
+  /**
+   * Inflate a raw DEFLATE frame into its JSON text.
+   */
+  static inflate(buffer) {
+    const stream = new Blob([buffer]).stream().pipeThrough(new DecompressionStream('deflate-raw'));
+    return new Response(stream).text();
+  }
+
+  /**
+   * Apply a JSON merge patch (RFC 7396): null removes a key, nested objects
+   * are merged, anything else replaces the old value.
+   */
+  static applyMergePatch(target, patch) {
+    if (patch === null || typeof patch !== 'object' || Array.isArray(patch)) {
+      return patch;
+    }
+    const result = (target && typeof target === 'object' && !Array.isArray(target))
+      ? { ...target }
+      : {};
+    for (const [key, value] of Object.entries(patch)) {
+      if (value === null) {
+        delete result[key];
+      } else {
+        result[key] = WebSocketManager.applyMergePatch(result[key], value);
+      }
+    }
+    return result;
+  }
+
+  /**
+   * Ask the server for every event after the last one applied. It replies
+   * with the missed events, or a fresh full_state if they are too old.
+   */
+  requestResync() {
+    if (this.resyncPending) {
+      return;
+    }
+    if (this.ws && this.ws.readyState === WebSocket.OPEN && this.lastSeq !== null) {
+      this.resyncPending = true;
+      this.ws.send(JSON.stringify({ type: 'resync', since: this.lastSeq }));
+    }
+  }
+
   /**
    * Handle node registration event
    */
@@ -134,6 +242,7 @@ class WebSocketManager {
       country_code: message.country_code,
       latitude: message.latitude,
       longitude: message.longitude,
+      location_estimated: message.location_estimated === true,
     };
 
     if (existingIndex >= 0) {
@@ -291,6 +400,13 @@ class DashboardData {
     return this.fetch('/api/peers');
   }
 
+  /**
+   * Fetch one peer's event timeline
+   */
+  async getPeerTimeline(peerId) {
+    return this.fetch(`/api/peer/${encodeURIComponent(peerId)}/timeline`, { fresh: true });
+  }
+
   /**
    * Clear cache
    */
@@ -8,17 +8,72 @@ use std::path::PathBuf;
 use std::time::Duration;
 use tracing::{info, warn};
 
+/// Droplet size used for every testnet node
+const DROPLET_SIZE: &str = "s-2vcpu-4gb";
+
+/// Base image for testnet nodes
+const DROPLET_IMAGE: &str = "ubuntu-22-04-x64";
+
+/// List price of a `DROPLET_SIZE` droplet in USD per month
+const DROPLET_MONTHLY_COST_USD: f64 = 24.0;
+
+/// Lines of cloud-init shown per droplet in a dry run
+const CLOUD_INIT_PREVIEW_LINES: usize = 12;
+
+/// How to authenticate SSH sessions to droplets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SshAuth {
+    /// Private key file on disk
+    KeyFile(PathBuf),
+    /// Identities held by the running ssh-agent (e.g. hardware tokens)
+    Agent,
+}
+
+impl SshAuth {
+    /// Build from CLI options; `--ssh-agent` takes precedence over `--ssh-key`
+    pub fn from_args(ssh_key: PathBuf, use_agent: bool) -> Result<Self> {
+        if use_agent {
+            return Ok(Self::Agent);
+        }
+        Ok(Self::KeyFile(
+            shellexpand::tilde(&ssh_key.to_string_lossy()).parse()?,
+        ))
+    }
+    
+    /// Authenticate `session` as `username`
+    pub fn authenticate(&self, session: &ssh2::Session, username: &str) -> Result<()> {
+        match self {
+            Self::KeyFile(path) => session
+                .userauth_pubkey_file(username, None, path, None)
+                .with_context(|| format!("SSH key authentication failed using {}", path.display())),
+            Self::Agent => {
+                let mut agent = session.agent()?;
+                agent
+                    .connect()
+                    .context("Failed to connect to ssh-agent (is SSH_AUTH_SOCK set?)")?;
+                agent.list_identities()?;
+                if agent.identities()?.is_empty() {
+                    anyhow::bail!("ssh-agent has no identities loaded; add one with `ssh-add`");
+                }
+                session
+                    .userauth_agent(username)
+                    .context("None of the ssh-agent identities were accepted")
+            }
+        }
+    }
+}
+
 /// DigitalOcean deployer
 #[allow(dead_code)]
 pub struct DigitalOceanDeployer {
     api_token: String,
-    ssh_key_path: PathBuf,
+    ssh_auth: SshAuth,
     client: reqwest::Client,
 }
 
 impl DigitalOceanDeployer {
     /// Create new deployer
-    pub fn new(token: Option<String>, ssh_key: PathBuf) -> Result<Self> {
+    pub fn new(token: Option<String>, ssh_auth: SshAuth) -> Result<Self> {
         let api_token = token
             .or_else(|| std::env::var("DO_API_TOKEN").ok())
             .ok_or_else(|| anyhow::anyhow!("DigitalOcean API token not provided"))?;
@@ -29,17 +84,21 @@ impl DigitalOceanDeployer {
         
         Ok(Self {
             api_token,
-            ssh_key_path: shellexpand::tilde(&ssh_key.to_string_lossy()).parse()?,
+            ssh_auth,
             client,
         })
     }
     
     /// Deploy nodes to specified regions
+    ///
+    /// With `dry_run` set, regions and the binary URL are still checked but
+    /// no droplets are created; the planned droplets are printed instead.
     pub async fn deploy(
         &self,
         regions: Vec<String>,
         nodes_per_region: usize,
         github_release: Option<String>,
+        dry_run: bool,
     ) -> Result<()> {
         info!("Deploying {} nodes per region to {:?}", nodes_per_region, regions);
         
@@ -55,6 +114,11 @@ impl DigitalOceanDeployer {
             self.build_and_upload_binary().await?
         };
         
+        if dry_run {
+            self.print_dry_run(&regions, nodes_per_region, &binary_url);
+            return Ok(());
+        }
+        
         // Deploy to each region
         for region in regions {
             info!("Deploying to region: {}", region);
@@ -142,29 +206,81 @@ impl DigitalOceanDeployer {
         Ok(())
     }
     
-    /// Create droplets
-    async fn create_droplets(&self, region: &str, count: usize) -> Result<Vec<Droplet>> {
-        let mut droplets = Vec::new();
-        
-        for i in 0..count {
-            let name = format!("saorsa-testnet-{}-{}", region, i);
-            
-            let _request = CreateDropletRequest {
-                name: name.clone(),
+    /// Build the create requests for a region's droplets
+    fn plan_droplets(&self, region: &str, count: usize) -> Vec<CreateDropletRequest> {
+        (0..count)
+            .map(|i| CreateDropletRequest {
+                name: format!("saorsa-testnet-{}-{}", region, i),
                 region: region.to_string(),
-                size: "s-2vcpu-4gb".to_string(),
-                image: "ubuntu-22-04-x64".to_string(),
+                size: DROPLET_SIZE.to_string(),
+                image: DROPLET_IMAGE.to_string(),
                 ssh_keys: vec![],  // Would add SSH key fingerprint
                 tags: vec!["saorsa-testnet".to_string()],
                 user_data: self.generate_cloud_init(region, i),
-            };
-            
+            })
+            .collect()
+    }
+    
+    /// Log the droplets a deployment would create, then a cost summary
+    fn print_dry_run(&self, regions: &[String], nodes_per_region: usize, binary_url: &str) {
+        info!("Dry run: no droplets will be created");
+        info!("Binary URL: {}", binary_url);
+        
+        for region in regions {
+            for request in self.plan_droplets(region, nodes_per_region) {
+                let preview: Vec<&str> = request
+                    .user_data
+                    .lines()
+                    .take(CLOUD_INIT_PREVIEW_LINES)
+                    .collect();
+                info!(
+                    "Would create droplet: {} (region {}, size {}, image {})\n{}\n...",
+                    request.name,
+                    request.region,
+                    request.size,
+                    request.image,
+                    preview.join("\n")
+                );
+            }
+        }
+        
+        println!("\n=== Planned Deployment (dry run) ===");
+        println!("{:<10} {:>8} {:<14} {:>12}", "Region", "Droplets", "Size", "USD/month");
+        for region in regions {
+            println!(
+                "{:<10} {:>8} {:<14} {:>12.2}",
+                region,
+                nodes_per_region,
+                DROPLET_SIZE,
+                nodes_per_region as f64 * DROPLET_MONTHLY_COST_USD
+            );
+        }
+        let total = regions.len() * nodes_per_region;
+        println!(
+            "{:<10} {:>8} {:<14} {:>12.2}",
+            "Total",
+            total,
+            "",
+            total as f64 * DROPLET_MONTHLY_COST_USD
+        );
+        println!(
+            "Estimated cost: ${:.2}/month (${:.3}/hour) at list price",
+            total as f64 * DROPLET_MONTHLY_COST_USD,
+            total as f64 * DROPLET_MONTHLY_COST_USD / (30.0 * 24.0)
+        );
+    }
+    
+    /// Create droplets
+    async fn create_droplets(&self, region: &str, count: usize) -> Result<Vec<Droplet>> {
+        let mut droplets = Vec::new();
+        
+        for (i, request) in self.plan_droplets(region, count).into_iter().enumerate() {
             // This would use the DigitalOcean API
-            info!("Creating droplet: {}", name);
+            info!("Creating droplet: {}", request.name);
             
             droplets.push(Droplet {
                 id: i as u64,
-                name,
+                name: request.name,
                 ip: format!("10.0.{}.{}", i / 256, i % 256),
                 region: region.to_string(),
             });
@@ -236,13 +352,8 @@ echo "NODE_ID=testnet-{region}-{index}" >> /etc/environment
         session.set_tcp_stream(tcp);
         session.handshake()?;
         
-        // Authenticate with SSH key
-        session.userauth_pubkey_file(
-            "root",
-            None,
-            &self.ssh_key_path,
-            None,
-        )?;
+        // Authenticate with SSH key or agent
+        self.ssh_auth.authenticate(&session, "root")?;
         
         // Run configuration commands
         let commands = vec![
@@ -269,15 +380,15 @@ echo "NODE_ID=testnet-{region}-{index}" >> /etc/environment
 #[allow(dead_code)]
 pub struct ClusterMonitor {
     cluster_name: String,
-    ssh_key_path: PathBuf,
+    ssh_auth: SshAuth,
 }
 
 impl ClusterMonitor {
     /// Create new monitor
-    pub fn new(cluster: String, ssh_key: PathBuf) -> Result<Self> {
+    pub fn new(cluster: String, ssh_auth: SshAuth) -> Result<Self> {
         Ok(Self {
             cluster_name: cluster,
-            ssh_key_path: shellexpand::tilde(&ssh_key.to_string_lossy()).parse()?,
+            ssh_auth,
         })
     }
     
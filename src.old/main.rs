@@ -23,7 +23,7 @@ mod logging;
 
 use node::{BootstrapNode, WorkerNode};
 use tui::Dashboard;
-use deployment::DigitalOceanDeployer;
+use deployment::{DigitalOceanDeployer, SshAuth};
 use testing::TestScenario;
 
 #[derive(Parser)]
@@ -108,6 +108,10 @@ enum Commands {
         #[arg(long, default_value = "~/.ssh/id_ed25519")]
         ssh_key: PathBuf,
 
+        /// Authenticate with ssh-agent identities (takes precedence over --ssh-key)
+        #[arg(long)]
+        ssh_agent: bool,
+
         /// DigitalOcean API token (can also use DO_API_TOKEN env var)
         #[arg(long, env = "DO_API_TOKEN")]
         do_token: Option<String>,
@@ -115,6 +119,10 @@ enum Commands {
         /// Use existing GitHub release
         #[arg(long)]
         github_release: Option<String>,
+
+        /// Show the droplets that would be created without creating them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Run test scenarios
@@ -142,6 +150,10 @@ enum Commands {
         /// Churn rate (nodes per minute)
         #[arg(long, default_value = "2")]
         churn_rate: usize,
+
+        /// RNG seed for reproducible runs (random if omitted)
+        #[arg(long)]
+        seed: Option<u64>,
     },
 
     /// Monitor remote cluster
@@ -158,6 +170,10 @@ enum Commands {
         #[arg(long, default_value = "~/.ssh/id_ed25519")]
         ssh_key: PathBuf,
 
+        /// Authenticate with ssh-agent identities (takes precedence over --ssh-key)
+        #[arg(long)]
+        ssh_agent: bool,
+
         /// Export logs to directory
         #[arg(long)]
         export_logs: Option<PathBuf>,
@@ -172,6 +188,10 @@ enum Commands {
         /// Include detailed metrics
         #[arg(short, long)]
         detailed: bool,
+
+        /// Registry to take connection RTTs from (latency summary and per-method histogram)
+        #[arg(long)]
+        registry: Option<String>,
     },
 }
 
@@ -250,15 +270,18 @@ async fn main() -> Result<()> {
             }
         }
         
-        Commands::Deploy { regions, nodes_per_region, ssh_key, do_token, github_release } => {
+        Commands::Deploy { regions, nodes_per_region, ssh_key, ssh_agent, do_token, github_release, dry_run } => {
             info!("Deploying {} nodes per region to {:?}", nodes_per_region, regions);
-            let deployer = DigitalOceanDeployer::new(do_token, ssh_key)?;
-            deployer.deploy(regions, nodes_per_region, github_release).await?;
+            let ssh_auth = SshAuth::from_args(ssh_key, ssh_agent)?;
+            let deployer = DigitalOceanDeployer::new(do_token, ssh_auth)?;
+            deployer.deploy(regions, nodes_per_region, github_release, dry_run).await?;
         }
         
-        Commands::Test { scenario, duration, export_metrics, nodes, churn, churn_rate } => {
+        Commands::Test { scenario, duration, export_metrics, nodes, churn, churn_rate, seed } => {
             info!("Running {:?} test scenario for {:?}", scenario, duration);
-            let mut test = TestScenario::new(scenario, duration, nodes);
+            let seed = seed.unwrap_or_else(rand::random);
+            println!("Seed: {} (replay with --seed {})", seed, seed);
+            let mut test = TestScenario::new(scenario, duration, nodes, seed);
             
             if churn {
                 test.enable_churn(churn_rate);
@@ -273,14 +296,15 @@ async fn main() -> Result<()> {
             results.print_summary();
         }
         
-        Commands::Monitor { cluster, refresh, ssh_key, export_logs } => {
+        Commands::Monitor { cluster, refresh, ssh_key, ssh_agent, export_logs } => {
             info!("Monitoring cluster: {}", cluster);
-            let monitor = deployment::ClusterMonitor::new(cluster, ssh_key)?;
+            let ssh_auth = SshAuth::from_args(ssh_key, ssh_agent)?;
+            let monitor = deployment::ClusterMonitor::new(cluster, ssh_auth)?;
             monitor.run(refresh, export_logs).await?;
         }
         
-        Commands::Stats { format, detailed } => {
-            let stats = metrics::collect_stats(detailed).await?;
+        Commands::Stats { format, detailed, registry } => {
+            let stats = metrics::collect_stats(detailed, registry.as_deref()).await?;
             
             match format {
                 OutputFormat::Json => {
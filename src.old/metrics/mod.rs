@@ -7,6 +7,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::Write;
+use tracing::warn;
 
 /// Network statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,20 @@ pub struct NetworkStats {
     pub nat: NatMetrics,
     pub adaptive: AdaptiveMetrics,
     pub performance: PerformanceMetrics,
+    /// Connection RTTs per method, from the registry (`--registry`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtt_histogram: Option<RttHistogram>,
+}
+
+/// Connection RTT counts per method, bucketed in milliseconds, as served by
+/// the registry's `/api/rtt-histogram`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RttHistogram {
+    /// Bucket labels, e.g. "0-10" ... "500+"
+    pub buckets: Vec<String>,
+    pub direct: Vec<u64>,
+    pub hole_punched: Vec<u64>,
+    pub relayed: Vec<u64>,
 }
 
 /// Node-level metrics
@@ -38,6 +53,14 @@ pub struct NetworkMetrics {
     pub bandwidth_mbps: f64,
     pub average_latency_ms: f64,
     pub packet_loss_rate: f64,
+    #[serde(default)]
+    pub latency_stddev_ms: f64,
+    #[serde(default)]
+    pub latency_p50_ms: f64,
+    #[serde(default)]
+    pub latency_p95_ms: f64,
+    #[serde(default)]
+    pub latency_p99_ms: f64,
 }
 
 /// NAT traversal metrics
@@ -73,6 +96,53 @@ pub struct PerformanceMetrics {
     pub gossip_propagation_time_ms: f64,
     pub cpu_usage_percent: f64,
     pub memory_usage_mb: f64,
+    #[serde(default)]
+    pub dht_lookup_stddev_ms: f64,
+    #[serde(default)]
+    pub dht_lookup_p50_ms: f64,
+    #[serde(default)]
+    pub dht_lookup_p95_ms: f64,
+    #[serde(default)]
+    pub dht_lookup_p99_ms: f64,
+}
+
+/// Summary of a latency sample set: mean, standard deviation and p50/p95/p99
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatencySummary {
+    pub mean_ms: f64,
+    pub stddev_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+impl LatencySummary {
+    /// Compute the summary from raw samples (nearest-rank percentiles,
+    /// population standard deviation). Returns zeros for an empty set.
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * n).ceil() as usize;
+            sorted[rank.clamp(1, sorted.len()) - 1]
+        };
+
+        Self {
+            mean_ms: mean,
+            stddev_ms: variance.sqrt(),
+            p50_ms: percentile(50.0),
+            p95_ms: percentile(95.0),
+            p99_ms: percentile(99.0),
+        }
+    }
 }
 
 impl NetworkStats {
@@ -100,6 +170,14 @@ impl NetworkStats {
             self.network.messages_per_second,
             self.network.average_latency_ms
         );
+        println!("║   Latency p50/p95/p99: {:<36}║",
+            spread_cell(
+                self.network.latency_p50_ms,
+                self.network.latency_p95_ms,
+                self.network.latency_p99_ms,
+                self.network.latency_stddev_ms,
+            )
+        );
         
         // NAT metrics
         println!("║                                                            ║");
@@ -125,6 +203,14 @@ impl NetworkStats {
             self.performance.dht_lookup_latency_ms,
             self.performance.storage_operations_per_sec
         );
+        println!("║   DHT p50/p95/p99:     {:<36}║",
+            spread_cell(
+                self.performance.dht_lookup_p50_ms,
+                self.performance.dht_lookup_p95_ms,
+                self.performance.dht_lookup_p99_ms,
+                self.performance.dht_lookup_stddev_ms,
+            )
+        );
         
         println!("╚════════════════════════════════════════════════════════════╝\n");
     }
@@ -132,12 +218,12 @@ impl NetworkStats {
     /// Write stats to CSV
     pub fn write_csv<W: Write>(&self, writer: &mut W) -> Result<()> {
         // Write header if needed
-        writeln!(writer, "timestamp,total_nodes,active_nodes,churn_rate,connections,messages_per_sec,latency_ms,nat_success_rate,thompson_success,mab_reward,cache_hit_rate")?;
+        writeln!(writer, "timestamp,total_nodes,active_nodes,churn_rate,connections,messages_per_sec,latency_ms,nat_success_rate,thompson_success,mab_reward,cache_hit_rate,latency_stddev_ms,latency_p50_ms,latency_p95_ms,latency_p99_ms,dht_lookup_ms,dht_lookup_stddev_ms,dht_lookup_p50_ms,dht_lookup_p95_ms,dht_lookup_p99_ms")?;
         
         // Write data row
         writeln!(
             writer,
-            "{},{},{},{:.4},{},{:.2},{:.2},{:.4},{:.4},{:.4},{:.4}",
+            "{},{},{},{:.4},{},{:.2},{:.2},{:.4},{:.4},{:.4},{:.4},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2}",
             self.timestamp.to_rfc3339(),
             self.nodes.total_nodes,
             self.nodes.active_nodes,
@@ -148,15 +234,57 @@ impl NetworkStats {
             self.nat.success_rate,
             self.adaptive.thompson_sampling_success_rate,
             self.adaptive.mab_average_reward,
-            self.adaptive.q_learning_cache_hit_rate
+            self.adaptive.q_learning_cache_hit_rate,
+            self.network.latency_stddev_ms,
+            self.network.latency_p50_ms,
+            self.network.latency_p95_ms,
+            self.network.latency_p99_ms,
+            self.performance.dht_lookup_latency_ms,
+            self.performance.dht_lookup_stddev_ms,
+            self.performance.dht_lookup_p50_ms,
+            self.performance.dht_lookup_p95_ms,
+            self.performance.dht_lookup_p99_ms
         )?;
         
         Ok(())
     }
 }
 
+/// Table cell for a latency spread; all zeros means no samples were taken
+fn spread_cell(p50: f64, p95: f64, p99: f64, stddev: f64) -> String {
+    if [p50, p95, p99, stddev].iter().all(|v| *v == 0.0) {
+        return "unknown".to_string();
+    }
+    format!("{:>6.1}/{:>6.1}/{:>6.1}ms σ {:<6.1}ms", p50, p95, p99, stddev)
+}
+
+/// Fetch the RTT histogram of the connections reported to a registry
+pub async fn fetch_rtt_histogram(registry_url: &str) -> Result<RttHistogram> {
+    let url = format!("{}/api/rtt-histogram", registry_url.trim_end_matches('/'));
+    let body = reqwest::get(&url).await?.error_for_status()?.text().await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// Fetch the RTTs of the connections reported to a registry, in milliseconds
+pub async fn fetch_rtt_samples(registry_url: &str) -> Result<Vec<f64>> {
+    let url = format!("{}/api/connections", registry_url.trim_end_matches('/'));
+    let body = reqwest::get(&url).await?.error_for_status()?.text().await?;
+    let connections: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(connections["edges"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|edge| edge["rtt_ms"].as_f64())
+        .collect())
+}
+
 /// Collect current network statistics
-pub async fn collect_stats(detailed: bool) -> Result<NetworkStats> {
+///
+/// With `registry_url` set, network latency is summarised from the RTTs of
+/// the connections reported to that registry, and its RTT histogram is
+/// included. A registry that cannot serve either is skipped with a warning.
+/// Latency and DHT spreads stay zero (shown as unknown) without samples.
+pub async fn collect_stats(detailed: bool, registry_url: Option<&str>) -> Result<NetworkStats> {
     // This would collect real metrics from running nodes
     // For now, return mock data for testing
     
@@ -167,7 +295,24 @@ pub async fn collect_stats(detailed: bool) -> Result<NetworkStats> {
     nat_types.insert("Symmetric".to_string(), 15);
     nat_types.insert("CGNAT".to_string(), 5);
     
-    let stats = NetworkStats {
+    let (rtt_histogram, latency) = match registry_url {
+        Some(url) => {
+            let histogram = fetch_rtt_histogram(url)
+                .await
+                .map_err(|e| warn!("No RTT histogram from {}: {}", url, e))
+                .ok();
+            let latency = fetch_rtt_samples(url)
+                .await
+                .map_err(|e| warn!("No connection RTTs from {}: {}", url, e))
+                .ok()
+                .filter(|samples| !samples.is_empty())
+                .map(|samples| LatencySummary::from_samples(&samples));
+            (histogram, latency)
+        }
+        None => (None, None),
+    };
+
+    let mut stats = NetworkStats {
         timestamp: Utc::now(),
         nodes: NodeMetrics {
             total_nodes: 100,
@@ -183,6 +328,10 @@ pub async fn collect_stats(detailed: bool) -> Result<NetworkStats> {
             bandwidth_mbps: 125.3,
             average_latency_ms: 45.2,
             packet_loss_rate: 0.001,
+            latency_stddev_ms: 0.0,
+            latency_p50_ms: 0.0,
+            latency_p95_ms: 0.0,
+            latency_p99_ms: 0.0,
         },
         nat: NatMetrics {
             traversal_attempts: 1000,
@@ -209,9 +358,22 @@ pub async fn collect_stats(detailed: bool) -> Result<NetworkStats> {
             gossip_propagation_time_ms: 450.0,
             cpu_usage_percent: if detailed { 35.5 } else { 0.0 },
             memory_usage_mb: if detailed { 512.0 } else { 0.0 },
+            dht_lookup_stddev_ms: 0.0,
+            dht_lookup_p50_ms: 0.0,
+            dht_lookup_p95_ms: 0.0,
+            dht_lookup_p99_ms: 0.0,
         },
+        rtt_histogram,
     };
     
+    if let Some(latency) = latency {
+        stats.network.average_latency_ms = latency.mean_ms;
+        stats.network.latency_stddev_ms = latency.stddev_ms;
+        stats.network.latency_p50_ms = latency.p50_ms;
+        stats.network.latency_p95_ms = latency.p95_ms;
+        stats.network.latency_p99_ms = latency.p99_ms;
+    }
+
     Ok(stats)
 }
 
@@ -240,4 +402,4 @@ pub fn export_metrics(stats: &NetworkStats, path: &std::path::Path) -> Result<()
     }
     
     Ok(())
-}
\ No newline at end of file
+}
@@ -3,6 +3,8 @@
 // Testing scenarios module for Saorsa TestNet
 
 use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use tracing::{info, warn};
@@ -15,11 +17,21 @@ pub struct TestScenario {
     churn_enabled: bool,
     churn_rate: usize,
     start_time: Option<Instant>,
+    seed: u64,
+    rng: StdRng,
 }
 
 impl TestScenario {
     /// Create new test scenario
-    pub fn new(scenario_type: crate::TestScenarioType, duration: Duration, nodes: usize) -> Self {
+    ///
+    /// Every randomized decision draws from an RNG seeded with `seed`, so
+    /// the same seed reproduces a run exactly.
+    pub fn new(
+        scenario_type: crate::TestScenarioType,
+        duration: Duration,
+        nodes: usize,
+        seed: u64,
+    ) -> Self {
         Self {
             scenario_type,
             duration,
@@ -27,9 +39,16 @@ impl TestScenario {
             churn_enabled: false,
             churn_rate: 0,
             start_time: None,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
     
+    /// Seed this scenario's RNG was created with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    
     /// Enable churn simulation
     pub fn enable_churn(&mut self, rate: usize) {
         self.churn_enabled = true;
@@ -38,8 +57,8 @@ impl TestScenario {
     
     /// Run the test scenario
     pub async fn run(&mut self) -> Result<TestResults> {
-        info!("Starting {:?} test scenario with {} nodes for {:?}", 
-            self.scenario_type, self.node_count, self.duration);
+        info!("Starting {:?} test scenario with {} nodes for {:?} (seed {})", 
+            self.scenario_type, self.node_count, self.duration, self.seed);
         
         self.start_time = Some(Instant::now());
         
@@ -66,10 +85,10 @@ impl TestScenario {
     }
     
     /// Run NAT traversal test
-    async fn run_nat_traversal_test(&self) -> Result<TestResults> {
+    async fn run_nat_traversal_test(&mut self) -> Result<TestResults> {
         info!("Running NAT traversal test");
         
-        let mut results = TestResults::new(self.scenario_type.clone());
+        let mut results = TestResults::new(self.scenario_type.clone(), self.seed);
         
         // Test different NAT types
         let nat_types = vec![
@@ -85,15 +104,15 @@ impl TestScenario {
             
             // Simulate NAT traversal attempts
             let attempts = 100;
-            let successes = (attempts as f64 * expected_success * (0.95 + rand::random::<f64>() * 0.1)) as usize;
+            let successes = (attempts as f64 * expected_success * (0.95 + self.rng.gen::<f64>() * 0.1)) as usize;
             
             results.nat_results.insert(
                 nat_type.to_string(),
                 NatTestResult {
                     attempts,
                     successes,
-                    average_punch_time_ms: 200.0 + rand::random::<f64>() * 100.0,
-                    pqc_enabled: rand::random::<bool>(),
+                    average_punch_time_ms: 200.0 + self.rng.gen::<f64>() * 100.0,
+                    pqc_enabled: self.rng.gen::<bool>(),
                 },
             );
         }
@@ -106,18 +125,21 @@ impl TestScenario {
     }
     
     /// Test concurrent NAT traversal
-    async fn test_concurrent_nat_traversal(&self, count: usize) -> Result<f64> {
+    async fn test_concurrent_nat_traversal(&mut self, count: usize) -> Result<f64> {
         info!("Testing {} concurrent NAT traversals", count);
         
         let mut handles = vec![];
         
         for _i in 0..count {
+            // Draw up front so outcomes don't depend on task scheduling
+            let delay = Duration::from_millis(self.rng.gen_range(0..1000));
+            let success = self.rng.gen::<f64>() > 0.1;
             let handle = tokio::spawn(async move {
                 // Simulate connection attempt
-                tokio::time::sleep(Duration::from_millis(rand::random::<u64>() % 1000)).await;
+                tokio::time::sleep(delay).await;
                 
                 // Random success based on realistic rates
-                rand::random::<f64>() > 0.1
+                success
             });
             
             handles.push(handle);
@@ -134,10 +156,10 @@ impl TestScenario {
     }
     
     /// Run churn test
-    async fn run_churn_test(&self) -> Result<TestResults> {
+    async fn run_churn_test(&mut self) -> Result<TestResults> {
         info!("Running churn test with rate: {} nodes/min", self.churn_rate);
         
-        let mut results = TestResults::new(self.scenario_type.clone());
+        let mut results = TestResults::new(self.scenario_type.clone(), self.seed);
         let mut active_nodes = self.node_count;
         
         let test_duration = self.duration;
@@ -149,8 +171,8 @@ impl TestScenario {
             elapsed += Duration::from_secs(60);
             
             // Simulate nodes joining and leaving
-            let nodes_left = rand::random::<usize>() % (self.churn_rate + 1);
-            let nodes_joined = rand::random::<usize>() % (self.churn_rate + 1);
+            let nodes_left = self.rng.gen_range(0..=self.churn_rate);
+            let nodes_joined = self.rng.gen_range(0..=self.churn_rate);
             
             active_nodes = active_nodes.saturating_sub(nodes_left) + nodes_joined;
             
@@ -177,20 +199,20 @@ impl TestScenario {
     }
     
     /// Test network stability
-    async fn test_network_stability(&self, active_nodes: usize) -> Result<f64> {
+    async fn test_network_stability(&mut self, active_nodes: usize) -> Result<f64> {
         // Simulate stability based on node count
         let base_stability = 0.9;
         let node_factor = (active_nodes as f64 / self.node_count as f64).min(1.0);
-        let random_factor = 0.9 + rand::random::<f64>() * 0.1;
+        let random_factor = 0.9 + self.rng.gen::<f64>() * 0.1;
         
         Ok(base_stability * node_factor * random_factor)
     }
     
     /// Run stress test
-    async fn run_stress_test(&self) -> Result<TestResults> {
+    async fn run_stress_test(&mut self) -> Result<TestResults> {
         info!("Running stress test");
         
-        let mut results = TestResults::new(self.scenario_type.clone());
+        let mut results = TestResults::new(self.scenario_type.clone(), self.seed);
         
         // Test increasing load
         let load_levels = vec![100, 500, 1000, 5000, 10000];
@@ -217,7 +239,7 @@ impl TestScenario {
     }
     
     /// Test specific load level
-    async fn test_load_level(&self, messages_per_sec: usize) -> Result<(f64, f64, f64)> {
+    async fn test_load_level(&mut self, messages_per_sec: usize) -> Result<(f64, f64, f64)> {
         let _duration = Duration::from_secs(10);
         let total_messages = messages_per_sec * 10;
         
@@ -229,14 +251,14 @@ impl TestScenario {
             
             // Simulate message processing
             tokio::time::sleep(Duration::from_micros(
-                1000000 / messages_per_sec as u64 + rand::random::<u64>() % 1000
+                1000000 / messages_per_sec as u64 + self.rng.gen_range(0..1000)
             )).await;
             
             let latency = start.elapsed().as_millis() as f64;
             latencies.push(latency);
             
             // Random success based on load
-            if rand::random::<f64>() > (messages_per_sec as f64 / 20000.0) {
+            if self.rng.gen::<f64>() > (messages_per_sec as f64 / 20000.0) {
                 successes += 1;
             }
         }
@@ -252,10 +274,10 @@ impl TestScenario {
     }
     
     /// Run geographic distribution test
-    async fn run_geographic_test(&self) -> Result<TestResults> {
+    async fn run_geographic_test(&mut self) -> Result<TestResults> {
         info!("Running geographic distribution test");
         
-        let mut results = TestResults::new(self.scenario_type.clone());
+        let mut results = TestResults::new(self.scenario_type.clone(), self.seed);
         
         let regions = vec![
             ("North America", vec!["nyc1", "sfo3", "tor1"]),
@@ -285,7 +307,7 @@ impl TestScenario {
     }
     
     /// Test intra-region latency
-    async fn test_intra_region_latency(&self, locations: &[&str]) -> Result<f64> {
+    async fn test_intra_region_latency(&mut self, locations: &[&str]) -> Result<f64> {
         // Simulate realistic intra-region latencies
         let base_latency = match locations.len() {
             1 => 5.0,   // Same datacenter
@@ -293,11 +315,11 @@ impl TestScenario {
             _ => 25.0,  // Larger region
         };
         
-        Ok(base_latency + rand::random::<f64>() * 10.0)
+        Ok(base_latency + self.rng.gen::<f64>() * 10.0)
     }
     
     /// Test inter-region latency
-    async fn test_inter_region_latency(&self, region: &str) -> Result<f64> {
+    async fn test_inter_region_latency(&mut self, region: &str) -> Result<f64> {
         // Simulate realistic inter-region latencies
         let base_latency = match region {
             "North America" => 50.0,
@@ -307,14 +329,14 @@ impl TestScenario {
             _ => 100.0,
         };
         
-        Ok(base_latency + rand::random::<f64>() * 50.0)
+        Ok(base_latency + self.rng.gen::<f64>() * 50.0)
     }
     
     /// Run all tests
-    async fn run_all_tests(&self) -> Result<TestResults> {
+    async fn run_all_tests(&mut self) -> Result<TestResults> {
         info!("Running all test scenarios");
         
-        let mut all_results = TestResults::new(self.scenario_type.clone());
+        let mut all_results = TestResults::new(self.scenario_type.clone(), self.seed);
         
         // Run each test type
         let nat_results = self.run_nat_traversal_test().await?;
@@ -337,6 +359,7 @@ impl TestScenario {
 /// Test results
 pub struct TestResults {
     pub scenario: crate::TestScenarioType,
+    pub seed: u64,
     pub duration: Duration,
     pub nat_results: std::collections::HashMap<String, NatTestResult>,
     pub concurrent_success_rate: f64,
@@ -348,9 +371,10 @@ pub struct TestResults {
 }
 
 impl TestResults {
-    fn new(scenario: crate::TestScenarioType) -> Self {
+    fn new(scenario: crate::TestScenarioType, seed: u64) -> Self {
         Self {
             scenario,
+            seed,
             duration: Duration::default(),
             nat_results: std::collections::HashMap::new(),
             concurrent_success_rate: 0.0,
@@ -379,6 +403,7 @@ impl TestResults {
         println!("║                    TEST RESULTS SUMMARY                    ║");
         println!("╠════════════════════════════════════════════════════════════╣");
         println!("║ Scenario: {:?}", self.scenario);
+        println!("║ Seed: {} (replay with --seed {})", self.seed, self.seed);
         println!("║ Duration: {:?}", self.duration);
         
         if !self.nat_results.is_empty() {
@@ -468,8 +493,9 @@ impl serde::Serialize for TestResults {
     {
         use serde::ser::SerializeStruct;
         
-        let mut state = serializer.serialize_struct("TestResults", 8)?;
+        let mut state = serializer.serialize_struct("TestResults", 9)?;
         state.serialize_field("scenario", &format!("{:?}", self.scenario))?;
+        state.serialize_field("seed", &self.seed)?;
         state.serialize_field("duration_secs", &self.duration.as_secs())?;
         state.serialize_field("nat_results", &self.nat_results)?;
         state.serialize_field("concurrent_success_rate", &self.concurrent_success_rate)?;